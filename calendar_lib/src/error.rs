@@ -0,0 +1,14 @@
+/// Errors that can occur when loading calendar data
+#[derive(Debug, thiserror::Error)]
+pub enum CalendarError {
+    #[error("Failed to read calendar file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid calendar data at line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+}
+
+pub type Result<T> = std::result::Result<T, CalendarError>;
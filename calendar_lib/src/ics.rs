@@ -0,0 +1,474 @@
+//! Minimal iCalendar (RFC 5545) reader and writer covering the VEVENT fields the CLI needs.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use tracing::warn;
+
+use crate::error::{CalendarError, Result};
+use crate::models::Event;
+use crate::recurrence::{Rule, Until};
+use crate::zone::Zone;
+
+/// Days before today for which [`parse_ics_in`] expands recurring events
+const RECURRENCE_PAST_DAYS: i64 = 31;
+
+/// Days after today for which [`parse_ics_in`] expands recurring events
+const RECURRENCE_FUTURE_DAYS: i64 = 366;
+
+/// Parses VEVENT components from iCalendar text with times in the system timezone
+pub fn parse_ics(content: &str) -> Result<Vec<Event>> {
+    parse_ics_in(content, Zone::Local)
+}
+
+/// Parses VEVENT components from iCalendar text with times converted to `zone`
+///
+/// UTC times and times with a known `TZID` are converted; floating times and
+/// all-day dates are kept as written. Recurring events are expanded for a month
+/// back and a year ahead of today, see [`parse_ics_between`].
+pub fn parse_ics_in(content: &str, zone: Zone) -> Result<Vec<Event>> {
+    let today = zone.today();
+    parse_ics_between(
+        content,
+        zone,
+        today - Duration::days(RECURRENCE_PAST_DAYS),
+        today + Duration::days(RECURRENCE_FUTURE_DAYS),
+    )
+}
+
+/// Parses VEVENT components like [`parse_ics_in`], expanding recurring events into
+/// the occurrences that overlap the days from `from` to `to` inclusive
+///
+/// Occurrences follow `RRULE` in the zone of the event start, skip `EXDATE` and
+/// are replaced by events with a matching `RECURRENCE-ID`. Events with an
+/// unsupported rule keep only their first occurrence.
+pub fn parse_ics_between(
+    content: &str,
+    zone: Zone,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Event>> {
+    let mut builders = Vec::new();
+    let mut current: Option<EventBuilder> = None;
+
+    for (index, line) in unfold_lines(content).into_iter().enumerate() {
+        let line_number = index + 1;
+        let Some((head, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut params = head.split(';');
+        let name = params.next().unwrap_or_default().to_ascii_uppercase();
+        let params: Vec<&str> = params.collect();
+        let is_date = params
+            .iter()
+            .any(|param| param.eq_ignore_ascii_case("VALUE=DATE"));
+        let source = params
+            .iter()
+            .find_map(|param| param.strip_prefix("TZID="))
+            .and_then(|tzid| tzid.trim_matches('"').parse::<Tz>().ok())
+            .map(Zone::Named);
+
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => current = Some(EventBuilder::default()),
+            ("END", Some(_)) if value == "VEVENT" => builders.extend(current.take()),
+            ("UID", Some(builder)) => builder.uid = Some(value.to_string()),
+            ("SUMMARY", Some(builder)) => builder.summary = Some(unescape(value)),
+            ("DESCRIPTION", Some(builder)) => builder.description = Some(unescape(value)),
+            ("LOCATION", Some(builder)) => builder.location = Some(unescape(value)),
+            ("URL", Some(builder)) => builder.url = Some(value.to_string()),
+            ("ORGANIZER" | "ATTENDEE", Some(builder)) => {
+                let address = strip_mailto(value);
+                if !address.is_empty() && !builder.attendees.iter().any(|a| a == address) {
+                    builder.attendees.push(address.to_string());
+                }
+            }
+            ("DTSTART", Some(builder)) => {
+                builder.start = Some(parse_date_time(value, source, line_number)?);
+                builder.all_day = is_date || value.len() == 8;
+            }
+            ("DTEND", Some(builder)) => {
+                builder.end = Some(parse_date_time(value, source, line_number)?)
+            }
+            ("RRULE", Some(builder)) => match Rule::parse(value) {
+                Some(rule) => builder.rule = Some((rule, line_number)),
+                None => warn!(
+                    line = line_number,
+                    rule = value,
+                    "Unsupported recurrence rule, only the first occurrence is shown"
+                ),
+            },
+            ("EXDATE", Some(builder)) => {
+                for value in value.split(',') {
+                    let (time, anchor) = parse_date_time(value, source, line_number)?;
+                    builder.exdates.push(in_zone(time, anchor, zone));
+                }
+            }
+            ("RECURRENCE-ID", Some(builder)) => {
+                let (time, anchor) = parse_date_time(value, source, line_number)?;
+                builder.recurrence_id = Some(in_zone(time, anchor, zone));
+            }
+            _ => {}
+        }
+    }
+
+    // Occurrences moved or edited in the calendar app come as separate events
+    let overrides: Vec<(String, NaiveDateTime)> = builders
+        .iter()
+        .filter_map(|builder| Some((builder.uid.clone()?, builder.recurrence_id?)))
+        .collect();
+    let window = (
+        from.and_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+        (to + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time"),
+    );
+
+    let mut events = Vec::new();
+    for mut builder in builders {
+        if builder.rule.is_some() && builder.recurrence_id.is_none() {
+            builder.exdates.extend(
+                overrides
+                    .iter()
+                    .filter(|(uid, _)| builder.uid.as_ref() == Some(uid))
+                    .map(|(_, start)| *start),
+            );
+        }
+        events.extend(builder.build(zone, window)?);
+    }
+
+    Ok(events)
+}
+
+/// Serializes events to an iCalendar document that calendar apps can import
+///
+/// Times are written as floating local time, the same way they are read.
+pub fn to_ics(events: &[Event]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//you//calendar_lib//EN".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        if let Some(uid) = &event.uid {
+            lines.push(format!("UID:{uid}"));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape(location)));
+        }
+        if let Some(url) = &event.url {
+            lines.push(format!("URL:{url}"));
+        }
+        for attendee in &event.attendees {
+            lines.push(format!("ATTENDEE:mailto:{attendee}"));
+        }
+        if event.all_day {
+            lines.push(format!(
+                "DTSTART;VALUE=DATE:{}",
+                event.start.format("%Y%m%d")
+            ));
+            lines.push(format!("DTEND;VALUE=DATE:{}", event.end.format("%Y%m%d")));
+        } else {
+            lines.push(format!("DTSTART:{}", event.start.format("%Y%m%dT%H%M%S")));
+            lines.push(format!("DTEND:{}", event.end.format("%Y%m%dT%H%M%S")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut content = lines.join("\r\n");
+    content.push_str("\r\n");
+    content
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[derive(Default)]
+struct EventBuilder {
+    uid: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    url: Option<String>,
+    attendees: Vec<String>,
+    /// Start as written, with the zone it is written in
+    start: Option<(NaiveDateTime, Option<Zone>)>,
+    end: Option<(NaiveDateTime, Option<Zone>)>,
+    all_day: bool,
+    /// Recurrence rule and the line it was read from
+    rule: Option<(Rule, usize)>,
+    /// Skipped occurrence starts in the target zone
+    exdates: Vec<NaiveDateTime>,
+    /// Start of the occurrence this event replaces, in the target zone
+    recurrence_id: Option<NaiveDateTime>,
+}
+
+impl EventBuilder {
+    /// Builds the event, or its occurrences overlapping `window` if it recurs
+    fn build(self, zone: Zone, window: (NaiveDateTime, NaiveDateTime)) -> Result<Vec<Event>> {
+        let Some((start_time, start_zone)) = self.start else {
+            return Ok(Vec::new());
+        };
+        let start = in_zone(start_time, start_zone, zone);
+        let end = self
+            .end
+            .map_or(start, |(time, anchor)| in_zone(time, anchor, zone));
+        let event = Event {
+            uid: self.uid,
+            summary: self.summary.unwrap_or_default(),
+            description: self.description,
+            location: self.location,
+            url: self.url,
+            attendees: self.attendees,
+            start,
+            end,
+            all_day: self.all_day,
+        };
+        let Some((rule, line)) = self.rule else {
+            return Ok(vec![event]);
+        };
+
+        // Occurrences are generated in the zone of the start, so that meetings
+        // keep their local time across daylight saving changes
+        let rule_zone = start_zone.unwrap_or(zone);
+        let until = match &rule.until {
+            Some(value) if value.len() == 8 => {
+                let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|e| {
+                    CalendarError::Parse {
+                        line,
+                        message: format!("UNTIL={value}: {e}"),
+                    }
+                })?;
+                Some(Until::Date(date))
+            }
+            Some(value) => {
+                let (time, anchor) = parse_date_time(value, start_zone, line)?;
+                Some(Until::Time(in_zone(time, anchor, rule_zone)))
+            }
+            None => None,
+        };
+        let last = in_zone(window.1, Some(zone), rule_zone);
+        let duration = end - start;
+
+        Ok(rule
+            .occurrences(start_time, until, last)
+            .into_iter()
+            .map(|time| in_zone(time, start_zone, zone))
+            .filter(|start| !self.exdates.contains(start))
+            .filter(|start| *start < window.1 && *start + duration > window.0)
+            .map(|start| Event {
+                start,
+                end: start + duration,
+                ..event.clone()
+            })
+            .collect())
+    }
+}
+
+/// Joins continuation lines (starting with a space or tab) to the previous line
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// Address from a `mailto:` calendar user value
+fn strip_mailto(value: &str) -> &str {
+    let value = value.trim();
+    match value.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &value[7..],
+        _ => value,
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
+/// Parses `YYYYMMDD`, `YYYYMMDDTHHMMSS` (floating or in the `source` zone) or
+/// `YYYYMMDDTHHMMSSZ` (UTC) into the time as written and the zone it is written in
+///
+/// Dates and floating times have no zone.
+fn parse_date_time(
+    value: &str,
+    source: Option<Zone>,
+    line: usize,
+) -> Result<(NaiveDateTime, Option<Zone>)> {
+    let invalid = |message: String| CalendarError::Parse { line, message };
+
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S")
+            .map_err(|e| invalid(format!("{value}: {e}")))?;
+        return Ok((naive, Some(Zone::Named(Tz::UTC))));
+    }
+
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map_err(|e| invalid(format!("{value}: {e}")))?;
+        return Ok((
+            date.and_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+            None,
+        ));
+    }
+
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .map_err(|e| invalid(format!("{value}: {e}")))?;
+    Ok((time, source))
+}
+
+/// Wall clock time of `zone` for a time written in `anchor`; times without a
+/// zone are kept as written
+fn in_zone(time: NaiveDateTime, anchor: Option<Zone>, zone: Zone) -> NaiveDateTime {
+    match anchor {
+        Some(anchor) => anchor.convert(time, zone),
+        None => time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:1\r\n\
+SUMMARY:Daily standup\r\n\
+DTSTART;TZID=Europe/Moscow:20240115T100000\r\n\
+DTEND;TZID=Europe/Moscow:20240115T101500\r\n\
+DESCRIPTION:Discuss TREK-1\\nLink: https://meet.example.com/\r\n \
+standup\r\n\
+ORGANIZER;CN=Ann:mailto:ann@example.com\r\n\
+ATTENDEE;CN=Bob;PARTSTAT=ACCEPTED:MAILTO:bob@example.com\r\n\
+ATTENDEE:mailto:ann@example.com\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Holiday\r\n\
+DTSTART;VALUE=DATE:20240116\r\n\
+DTEND;VALUE=DATE:20240117\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_ics_events() {
+        let events = parse_ics_in(SAMPLE, Zone::Named(Tz::Europe__Moscow)).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].summary, "Daily standup");
+        assert_eq!(events[0].start.format("%H:%M").to_string(), "10:00");
+        assert_eq!(
+            events[0].description.as_deref(),
+            Some("Discuss TREK-1\nLink: https://meet.example.com/standup")
+        );
+        assert_eq!(events[0].attendees, ["ann@example.com", "bob@example.com"]);
+        assert!(!events[0].all_day);
+        assert!(events[1].all_day);
+        assert!(events[1].attendees.is_empty());
+    }
+
+    #[test]
+    fn test_parse_ics_converts_to_zone() {
+        let content = "BEGIN:VEVENT\n\
+DTSTART;TZID=Europe/Moscow:20240115T100000\n\
+DTEND:20240115T080000Z\n\
+END:VEVENT\n\
+BEGIN:VEVENT\n\
+DTSTART;VALUE=DATE:20240116\n\
+END:VEVENT\n";
+        let events = parse_ics_in(content, Zone::Named(Tz::Asia__Novosibirsk)).unwrap();
+
+        assert_eq!(events[0].start.format("%H:%M").to_string(), "14:00");
+        assert_eq!(events[0].end.format("%H:%M").to_string(), "15:00");
+        assert_eq!(events[1].start.format("%d %H:%M").to_string(), "16 00:00");
+    }
+
+    #[test]
+    fn test_parse_ics_expands_recurring_events() {
+        let content = "BEGIN:VEVENT\n\
+UID:standup\n\
+SUMMARY:Standup\n\
+DTSTART;TZID=Europe/Berlin:20240322T100000\n\
+DTEND;TZID=Europe/Berlin:20240322T101500\n\
+RRULE:FREQ=WEEKLY;BYDAY=FR;UNTIL=20240412T235959Z\n\
+EXDATE;TZID=Europe/Berlin:20240405T100000\n\
+END:VEVENT\n\
+BEGIN:VEVENT\n\
+UID:standup\n\
+RECURRENCE-ID;TZID=Europe/Berlin:20240329T100000\n\
+SUMMARY:Standup (moved)\n\
+DTSTART;TZID=Europe/Berlin:20240329T120000\n\
+DTEND;TZID=Europe/Berlin:20240329T121500\n\
+END:VEVENT\n\
+BEGIN:VEVENT\n\
+SUMMARY:Board\n\
+DTSTART:20240101T090000\n\
+RRULE:FREQ=MONTHLY;BYMONTHDAY=1\n\
+END:VEVENT\n";
+        let from = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let mut events =
+            parse_ics_between(content, Zone::Named(Tz::Europe__Moscow), from, to).unwrap();
+        events.sort_by_key(|event| event.start);
+
+        // Berlin switches to summer time on March 31, Moscow does not
+        let starts: Vec<String> = events
+            .iter()
+            .map(|event| format!("{} {}", event.summary, event.start.format("%m-%d %H:%M")))
+            .collect();
+        assert_eq!(
+            starts,
+            [
+                "Board 01-01 09:00",
+                "Standup 03-22 12:00",
+                "Standup (moved) 03-29 14:00",
+                "Standup 04-12 11:00"
+            ]
+        );
+        assert_eq!(events[3].end.format("%H:%M").to_string(), "11:15");
+        assert_eq!(events[3].uid.as_deref(), Some("standup"));
+    }
+
+    #[test]
+    fn test_to_ics_round_trip() {
+        let events = parse_ics(SAMPLE).unwrap();
+        let mut event = events[0].clone();
+        event.summary = "Ping QA; TREK-1, TREK-2".to_string();
+
+        let written = to_ics(&[event.clone(), events[1].clone()]);
+        assert!(written.contains("SUMMARY:Ping QA\\; TREK-1\\, TREK-2\r\n"));
+        assert_eq!(parse_ics(&written).unwrap(), vec![event, events[1].clone()]);
+    }
+
+    #[test]
+    fn test_parse_ics_invalid_date() {
+        let content = "BEGIN:VEVENT\nDTSTART:2024-01-15\nEND:VEVENT\n";
+        let error = parse_ics(content).unwrap_err();
+        assert!(matches!(error, CalendarError::Parse { line: 2, .. }));
+    }
+}
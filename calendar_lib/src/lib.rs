@@ -1,18 +1,71 @@
 //! Calendar domain library.
 //!
 //! This crate contains calendar-related domain models and integrations.
+//! Events are read from an iCalendar (`.ics`) export whose path is taken from
+//! the `CALENDAR_ICS` environment variable; [`ics::to_ics`] writes events back
+//! to a file that calendar apps can import.
+//!
+//! Event times are wall clock times of one [`Zone`]: the system timezone by default,
+//! or the user's configured zone via [`load_from_env_in`]. Times with a `TZID` or in
+//! UTC are converted on load, so remote workers see meetings in their local time.
+//! Recurring events (`RRULE`) are expanded into separate occurrences around today.
+//!
+//! ## Example Usage
+//!
+//! ```no_run
+//! use calendar_lib::{events_on, load_from_env};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let events = load_from_env()?;
+//! let today = chrono::Local::now().date_naive();
+//! for event in events_on(&events, today) {
+//!     println!("{} {}", event.start.format("%H:%M"), event.summary);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod error;
+pub mod ics;
+pub mod models;
+mod recurrence;
+pub mod zone;
+
+use std::path::Path;
 
-/// Returns the crate name. Useful for smoke tests while functionality is added.
-pub fn name() -> &'static str {
-    "calendar_lib"
+use tracing::{debug, info, instrument};
+
+pub use error::{CalendarError, Result};
+pub use models::{events_on, Event};
+pub use zone::Zone;
+
+/// Loads all events from an iCalendar file in the system timezone
+pub fn load_events(path: &Path) -> Result<Vec<Event>> {
+    load_events_in(path, Zone::Local)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::name;
+/// Loads all events from an iCalendar file with times in `zone`
+#[instrument(fields(path = %path.display(), %zone))]
+pub fn load_events_in(path: &Path, zone: Zone) -> Result<Vec<Event>> {
+    debug!("Reading calendar file");
+    let content = std::fs::read_to_string(path)?;
+    let events = ics::parse_ics_in(&content, zone)?;
+    info!(events_count = events.len(), "Calendar events loaded");
+    Ok(events)
+}
+
+/// Loads events from the file referenced by `CALENDAR_ICS`
+///
+/// The path is resolved like other credentials: the environment variable first,
+/// then the system keyring.
+pub fn load_from_env() -> Result<Vec<Event>> {
+    load_from_env_in(Zone::Local)
+}
 
-    #[test]
-    fn returns_crate_name() {
-        assert_eq!(name(), "calendar_lib");
-    }
+/// Loads events from the file referenced by `CALENDAR_ICS` with times in `zone`
+pub fn load_from_env_in(zone: Zone) -> Result<Vec<Event>> {
+    let path = core_lib::credentials::resolve("CALENDAR_ICS").ok_or_else(|| {
+        CalendarError::ConfigError("CALENDAR_ICS environment variable not set".to_string())
+    })?;
+    load_events_in(Path::new(&path), zone)
 }
@@ -0,0 +1,155 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+use crate::zone::Zone;
+
+/// A single calendar event with times in the local timezone
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub uid: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    /// Email addresses of the organizer and attendees
+    pub attendees: Vec<String>,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub all_day: bool,
+}
+
+impl Event {
+    /// Returns true if the event overlaps the given day
+    pub fn is_on(&self, date: NaiveDate) -> bool {
+        let day_start = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        let day_end = day_start + Duration::days(1);
+
+        self.start < day_end && (self.end > day_start || self.start >= day_start)
+    }
+
+    /// Returns true if the event is in progress at the given moment
+    pub fn is_active_at(&self, moment: NaiveDateTime) -> bool {
+        self.start <= moment && moment < self.end
+    }
+
+    /// Returns the meeting link: the explicit URL, or the first link found in
+    /// the location or description
+    pub fn meeting_link(&self) -> Option<&str> {
+        self.url.as_deref().or_else(|| {
+            [self.location.as_deref(), self.description.as_deref()]
+                .into_iter()
+                .flatten()
+                .flat_map(str::split_whitespace)
+                .find(|word| word.starts_with("https://") || word.starts_with("http://"))
+        })
+    }
+
+    /// Returns the event with times moved from the `from` zone to the `to` zone
+    ///
+    /// All-day events stay on their dates.
+    pub fn in_zone(&self, from: Zone, to: Zone) -> Event {
+        let mut event = self.clone();
+        if !self.all_day {
+            event.start = from.convert(self.start, to);
+            event.end = from.convert(self.end, to);
+        }
+        event
+    }
+
+    /// Returns tracker issue keys (e.g. `TREK-123`) mentioned in the summary or description
+    pub fn issue_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        let texts = [Some(self.summary.as_str()), self.description.as_deref()];
+
+        for word in texts.into_iter().flatten().flat_map(str::split_whitespace) {
+            let candidate = word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '-');
+            if is_issue_key(candidate) && !keys.iter().any(|key| key == candidate) {
+                keys.push(candidate.to_string());
+            }
+        }
+
+        keys
+    }
+}
+
+fn is_issue_key(candidate: &str) -> bool {
+    let Some((queue, number)) = candidate.split_once('-') else {
+        return false;
+    };
+
+    queue.starts_with(|c: char| c.is_ascii_uppercase())
+        && queue
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Returns events overlapping the given day, ordered by start time
+pub fn events_on(events: &[Event], date: NaiveDate) -> Vec<&Event> {
+    let mut day_events: Vec<&Event> = events.iter().filter(|event| event.is_on(date)).collect();
+    day_events.sort_by_key(|event| event.start);
+    day_events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(summary: &str, start: &str, end: &str) -> Event {
+        Event {
+            uid: None,
+            summary: summary.to_string(),
+            description: None,
+            location: None,
+            url: None,
+            attendees: Vec::new(),
+            start: NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap(),
+            end: NaiveDateTime::parse_from_str(end, "%Y-%m-%d %H:%M").unwrap(),
+            all_day: false,
+        }
+    }
+
+    #[test]
+    fn test_events_on_filters_and_sorts() {
+        let events = vec![
+            event("Late", "2024-01-15 16:00", "2024-01-15 17:00"),
+            event("Tomorrow", "2024-01-16 10:00", "2024-01-16 11:00"),
+            event("Early", "2024-01-15 09:00", "2024-01-15 09:30"),
+        ];
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let summaries: Vec<&str> = events_on(&events, date)
+            .iter()
+            .map(|e| e.summary.as_str())
+            .collect();
+
+        assert_eq!(summaries, vec!["Early", "Late"]);
+    }
+
+    #[test]
+    fn test_issue_keys_and_meeting_link() {
+        let mut event = event(
+            "Sync on TREK-12, TREK-7",
+            "2024-01-15 09:00",
+            "2024-01-15 10:00",
+        );
+        event.description = Some("See TREK-12 and https://meet.example.com/abc".to_string());
+
+        assert_eq!(event.issue_keys(), vec!["TREK-12", "TREK-7"]);
+        assert_eq!(event.meeting_link(), Some("https://meet.example.com/abc"));
+    }
+
+    #[test]
+    fn test_in_zone_keeps_all_day_dates() {
+        let moscow: Zone = "Europe/Moscow".parse().unwrap();
+        let london: Zone = "Europe/London".parse().unwrap();
+        let mut standup = event("Standup", "2024-01-15 10:00", "2024-01-15 10:15");
+
+        let moved = standup.in_zone(moscow, london);
+        assert_eq!(moved.start.format("%H:%M").to_string(), "07:00");
+        assert_eq!(moved.end.format("%H:%M").to_string(), "07:15");
+
+        standup.all_day = true;
+        assert_eq!(standup.in_zone(moscow, london), standup);
+    }
+}
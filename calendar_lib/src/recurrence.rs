@@ -0,0 +1,325 @@
+//! Expansion of iCalendar recurrence rules (`RRULE`) into occurrence start times.
+//!
+//! Supports the rules calendar apps write for ordinary meetings: `FREQ` of
+//! `DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY` with `INTERVAL`, `COUNT`, `UNTIL` and
+//! `BYDAY` (with ordinals such as `1MO` or `-1FR` for monthly rules). Rules with
+//! other `BY*` parts are rejected so that callers can fall back to the first
+//! occurrence instead of showing a wrong schedule.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Weekday};
+
+/// Upper bound on generated periods, so a malformed rule cannot loop for long
+const MAX_PERIODS: u32 = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// `UNTIL` of a rule: a date covers the whole day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Until {
+    Date(NaiveDate),
+    /// Wall clock time in the zone of the event start
+    Time(NaiveDateTime),
+}
+
+/// Weekday of `BYDAY`, optionally with its ordinal within the month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ByDay {
+    ordinal: Option<i32>,
+    weekday: Weekday,
+}
+
+/// Parsed `RRULE` value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    /// Raw `UNTIL` value; its zone is resolved by the caller
+    pub(crate) until: Option<String>,
+    by_day: Vec<ByDay>,
+}
+
+impl Rule {
+    /// Parses an `RRULE` value; None for unsupported or malformed rules
+    pub(crate) fn parse(value: &str) -> Option<Rule> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part.split_once('=')?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = value.parse().ok().filter(|&n| n > 0)?,
+                "COUNT" => count = Some(value.parse().ok()?),
+                "UNTIL" => until = Some(value.to_string()),
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Option<Vec<_>>>()?
+                }
+                // Weeks start on Monday unless the rule says otherwise; other
+                // starts only matter for rare multi-week BYDAY rules
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+
+        let frequency = frequency?;
+        // Ordinals are supported for monthly rules only; yearly rules repeat the
+        // start date
+        let ordinals = by_day.iter().any(|day| day.ordinal.is_some());
+        if ordinals && frequency != Frequency::Monthly
+            || !by_day.is_empty() && frequency == Frequency::Yearly
+        {
+            return None;
+        }
+
+        Some(Rule {
+            frequency,
+            interval,
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    /// Start times of occurrences from `start` up to and including `last`
+    ///
+    /// `start` is always the first occurrence, as RFC 5545 requires. `COUNT` is
+    /// counted from `start`, and `until` stops the series.
+    pub(crate) fn occurrences(
+        &self,
+        start: NaiveDateTime,
+        until: Option<Until>,
+        last: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let until = match until {
+            Some(Until::Date(date)) => {
+                (date + Duration::days(1)).and_time(Default::default()) - Duration::seconds(1)
+            }
+            Some(Until::Time(time)) => time,
+            None => NaiveDateTime::MAX,
+        };
+        let last = last.min(until);
+
+        let mut result = vec![start];
+        let mut emitted = 1;
+        for period in 0..MAX_PERIODS {
+            let Some(candidates) = self.period(start, period) else {
+                break;
+            };
+            if candidates.first().is_some_and(|first| *first > last) {
+                break;
+            }
+            for candidate in candidates {
+                if candidate <= start {
+                    continue;
+                }
+                if candidate > last || self.count.is_some_and(|count| emitted >= count) {
+                    return result;
+                }
+                result.push(candidate);
+                emitted += 1;
+            }
+        }
+        result
+    }
+
+    /// Sorted candidate starts in the `period`-th period of the series
+    ///
+    /// Period 0 is the day, week, month or year of `start` itself, so with `BYDAY`
+    /// it may contain later occurrences of the same week or month. None once dates
+    /// run out.
+    fn period(&self, start: NaiveDateTime, period: u32) -> Option<Vec<NaiveDateTime>> {
+        let step = period.checked_mul(self.interval)?;
+        let time = start.time();
+        let date = start.date();
+        let mut dates = match self.frequency {
+            Frequency::Daily => {
+                let day = date.checked_add_signed(Duration::days(i64::from(step)))?;
+                let matches = self.by_day.is_empty()
+                    || self.by_day.iter().any(|by| by.weekday == day.weekday());
+                if matches {
+                    vec![day]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday().into());
+                let week = monday.checked_add_signed(Duration::weeks(i64::from(step)))?;
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![date.weekday()]
+                } else {
+                    self.by_day.iter().map(|by| by.weekday).collect()
+                };
+                weekdays
+                    .into_iter()
+                    .map(|weekday| week + Duration::days(weekday.num_days_from_monday().into()))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month = date.with_day(1)?.checked_add_months(Months::new(step))?;
+                if self.by_day.is_empty() {
+                    month.with_day(date.day()).into_iter().collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|by| days_in_month(month, *by))
+                        .collect()
+                }
+            }
+            Frequency::Yearly => {
+                let year = date.year().checked_add(i32::try_from(step).ok()?)?;
+                NaiveDate::from_ymd_opt(year, date.month(), date.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+        dates.sort();
+        dates.dedup();
+        Some(dates.into_iter().map(|day| day.and_time(time)).collect())
+    }
+}
+
+/// Parses `MO`, `1MO` or `-1FR`
+fn parse_by_day(value: &str) -> Option<ByDay> {
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, weekday) = value.split_at(split);
+    let weekday = match weekday.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse()
+                .ok()
+                .filter(|&n| n != 0)?,
+        ),
+    };
+    Some(ByDay { ordinal, weekday })
+}
+
+/// Dates of the month starting at `first` that match `by`
+fn days_in_month(first: NaiveDate, by: ByDay) -> Vec<NaiveDate> {
+    let offset =
+        (7 + by.weekday.num_days_from_monday() - first.weekday().num_days_from_monday()) % 7;
+    let days: Vec<NaiveDate> = (0..5)
+        .map(|week| first + Duration::days(i64::from(offset + 7 * week)))
+        .filter(|day| day.month() == first.month())
+        .collect();
+    match by.ordinal {
+        None => days,
+        Some(ordinal) if ordinal > 0 => days
+            .get(ordinal as usize - 1)
+            .copied()
+            .into_iter()
+            .collect(),
+        Some(ordinal) => days
+            .len()
+            .checked_sub(ordinal.unsigned_abs() as usize)
+            .and_then(|index| days.get(index).copied())
+            .into_iter()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn expand(rule: &str, start: &str, last: &str) -> Vec<String> {
+        Rule::parse(rule)
+            .unwrap()
+            .occurrences(time(start), None, time(last))
+            .iter()
+            .map(|occurrence| occurrence.format("%a %m-%d %H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_weekly_by_day_within_window() {
+        assert_eq!(
+            expand(
+                "FREQ=WEEKLY;BYDAY=MO,WE;INTERVAL=2",
+                "2024-01-15 10:00",
+                "2024-02-01 00:00"
+            ),
+            [
+                "Mon 01-15 10:00",
+                "Wed 01-17 10:00",
+                "Mon 01-29 10:00",
+                "Wed 01-31 10:00"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_and_until_stop_series() {
+        assert_eq!(
+            expand("FREQ=DAILY;COUNT=3", "2024-01-15 10:00", "2024-12-31 00:00").len(),
+            3
+        );
+
+        let rule = Rule::parse("FREQ=DAILY;UNTIL=20240117").unwrap();
+        let until = Some(Until::Date(NaiveDate::from_ymd_opt(2024, 1, 17).unwrap()));
+        let occurrences =
+            rule.occurrences(time("2024-01-15 10:00"), until, time("2024-12-31 00:00"));
+        assert_eq!(occurrences.len(), 3);
+    }
+
+    #[test]
+    fn test_monthly_rules() {
+        assert_eq!(
+            expand("FREQ=MONTHLY", "2024-01-31 09:00", "2024-05-01 00:00"),
+            ["Wed 01-31 09:00", "Sun 03-31 09:00"]
+        );
+        assert_eq!(
+            expand(
+                "FREQ=MONTHLY;BYDAY=-1FR",
+                "2024-01-26 17:00",
+                "2024-03-30 00:00"
+            ),
+            ["Fri 01-26 17:00", "Fri 02-23 17:00", "Fri 03-29 17:00"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_rules_are_rejected() {
+        assert!(Rule::parse("FREQ=MONTHLY;BYMONTHDAY=1,15").is_none());
+        assert!(Rule::parse("FREQ=WEEKLY;BYDAY=1MO").is_none());
+        assert!(Rule::parse("FREQ=HOURLY").is_none());
+        assert!(Rule::parse("INTERVAL=2").is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Timezone that event times are shown in
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::error::CalendarError;
+
+/// Timezone of the user: the system one or an IANA zone such as `Asia/Novosibirsk`
+///
+/// Event times are naive local times of the zone the calendar was loaded in, so
+/// comparisons with "now" and "today" must use the same zone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Zone {
+    /// Timezone of the operating system
+    #[default]
+    Local,
+    /// Named IANA timezone
+    Named(Tz),
+}
+
+impl Zone {
+    /// Wall clock time in this zone for a UTC instant
+    pub fn from_utc(&self, utc: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => utc.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => utc.with_timezone(tz).naive_local(),
+        }
+    }
+
+    /// UTC instant for a wall clock time in this zone
+    ///
+    /// Ambiguous times (when clocks go back) resolve to the earlier instant; times
+    /// skipped by a DST jump return None.
+    pub fn to_utc(&self, time: NaiveDateTime) -> Option<DateTime<Utc>> {
+        fn earliest<T: TimeZone>(result: LocalResult<DateTime<T>>) -> Option<DateTime<Utc>> {
+            result.earliest().map(|time| time.with_timezone(&Utc))
+        }
+        match self {
+            Zone::Local => earliest(Local.from_local_datetime(&time)),
+            Zone::Named(tz) => earliest(tz.from_local_datetime(&time)),
+        }
+    }
+
+    /// Wall clock time in `to` for a wall clock time in this zone
+    pub fn convert(&self, time: NaiveDateTime, to: Zone) -> NaiveDateTime {
+        match self.to_utc(time) {
+            Some(utc) => to.from_utc(&utc),
+            None => time,
+        }
+    }
+
+    /// Current wall clock time in this zone
+    pub fn now(&self) -> NaiveDateTime {
+        self.from_utc(&Utc::now())
+    }
+
+    /// Current date in this zone
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+}
+
+impl FromStr for Zone {
+    type Err = CalendarError;
+
+    /// Parses `local` or an IANA timezone name
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("local") {
+            return Ok(Zone::Local);
+        }
+        value
+            .parse::<Tz>()
+            .map(Zone::Named)
+            .map_err(|_| CalendarError::ConfigError(format!("Unknown timezone: {value}")))
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Local => f.write_str("local"),
+            Zone::Named(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse_zone() {
+        assert_eq!("local".parse::<Zone>().unwrap(), Zone::Local);
+        assert_eq!(
+            "Europe/Moscow".parse::<Zone>().unwrap(),
+            Zone::Named(Tz::Europe__Moscow)
+        );
+        assert_eq!(
+            "Asia/Novosibirsk".parse::<Zone>().unwrap().to_string(),
+            "Asia/Novosibirsk"
+        );
+        assert!("Mars/Olympus".parse::<Zone>().is_err());
+    }
+
+    #[test]
+    fn test_convert_between_zones() {
+        let moscow = Zone::Named(Tz::Europe__Moscow);
+        let novosibirsk = Zone::Named(Tz::Asia__Novosibirsk);
+
+        assert_eq!(
+            moscow.convert(time("2026-10-16 10:00"), novosibirsk),
+            time("2026-10-16 14:00")
+        );
+        assert_eq!(
+            novosibirsk.convert(time("2026-10-17 02:00"), moscow),
+            time("2026-10-16 22:00")
+        );
+    }
+
+    #[test]
+    fn test_skipped_time_has_no_utc_instant() {
+        let berlin = Zone::Named(Tz::Europe__Berlin);
+        assert!(berlin.to_utc(time("2026-03-29 02:30")).is_none());
+        assert_eq!(
+            berlin.convert(time("2026-03-29 02:30"), Zone::Named(Tz::UTC)),
+            time("2026-03-29 02:30")
+        );
+    }
+}
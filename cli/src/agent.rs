@@ -0,0 +1,837 @@
+//! Агент: LLM выполняет поручение, вызывая разрешённые функции Трекера и календаря
+//!
+//! Модель получает описание инструментов и в ограниченном числе шагов запрашивает их
+//! вызовы. Чтение выполняется сразу, а изменения (комментарии, смена статуса) — только
+//! после подтверждения пользователя. Комментарий на языке, отличном от языка
+//! организации из конфигурации, переводится до подтверждения. Комментарии во много
+//! задач модель отправляет одним вызовом `add_comments`: он идёт через
+//! `add_comments_bulk` с паузами по лимитам Трекера и сообщает, какие задачи не удалось
+//! прокомментировать.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use clap::Args;
+use llm_lib::{
+    CompletionOptions, FunctionCall, LlmClient, LlmClientTrait, LlmConfig, Message, Tool,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    comments::{AddCommentRequest, BulkComment},
+    ids::IssueKey,
+    models::{Issue, User},
+    search::{SearchParams, SearchRequest},
+    transitions::ExecuteTransitionRequest,
+    TrackerClient,
+};
+
+use crate::config::Config;
+use crate::language::{self, ContentLanguage};
+use crate::llm::DEFAULT_MODEL;
+use crate::redact;
+use crate::settings;
+use crate::timezone;
+
+/// Сколько раз агент может обратиться к LLM, прежде чем сдаться
+pub const DEFAULT_MAX_STEPS: usize = 10;
+
+/// Сколько задач возвращает поиск за один вызов
+const MAX_SEARCH_RESULTS: u32 = 20;
+
+const SYSTEM_PROMPT: &str = "Ты помощник, который выполняет поручения пользователя в \
+Яндекс Трекере и его календаре. Пользуйся только предложенными инструментами, сначала \
+находи нужные задачи и проверяй их, затем вноси изменения. Язык запросов Трекера: \
+например, \"Assignee: me() Resolution: empty()\". Пользователь может отклонить \
+изменение — тогда не повторяй его. Закончив, кратко перечисли, что сделано.";
+
+#[derive(Args)]
+pub struct AgentArgs {
+    /// Поручение на естественном языке
+    task: String,
+
+    #[arg(short, long)]
+    model: Option<String>,
+
+    /// Выполнять изменения без подтверждения
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Максимум обращений к LLM
+    #[arg(long, default_value_t = DEFAULT_MAX_STEPS)]
+    max_steps: usize,
+}
+
+impl AgentArgs {
+    pub async fn execute(self) -> Result<()> {
+        let model = self.model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        let llm = LlmClient::new(LlmConfig::new(model)?)?;
+        let tracker = settings::tracker_client()?;
+        let language = Config::load()?.language.enforce;
+        let yes = self.yes;
+
+        let outcome = run(
+            &llm,
+            &tracker,
+            &self.task,
+            self.max_steps,
+            language,
+            |action| yes || ask_confirmation(action),
+        )
+        .await?;
+
+        for entry in &outcome.log {
+            println!("{entry}");
+        }
+        println!("\n{}\n", outcome.answer);
+
+        let usage = llm.key_usage();
+        if usage.len() > 1 {
+            println!("Ключи OpenRouter:");
+            for key in usage {
+                let exhausted = if key.exhausted {
+                    ", средства кончились"
+                } else {
+                    ""
+                };
+                println!(
+                    "  {}: запросов {}, токенов {}{exhausted}",
+                    key.label, key.requests, key.total_tokens
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Итог работы агента: ответ модели и журнал вызванных инструментов
+#[derive(Debug)]
+pub struct AgentOutcome {
+    pub answer: String,
+    pub log: Vec<String>,
+}
+
+/// Разрешённые агенту действия
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentAction {
+    SearchIssues {
+        query: String,
+    },
+    GetIssue {
+        key: IssueKey,
+    },
+    GetComments {
+        key: IssueKey,
+    },
+    GetTransitions {
+        key: IssueKey,
+    },
+    CalendarEvents {
+        date: Option<NaiveDate>,
+    },
+    AddComment {
+        key: IssueKey,
+        text: String,
+    },
+    AddComments {
+        comments: Vec<(IssueKey, String)>,
+    },
+    Transition {
+        key: IssueKey,
+        transition_id: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct KeyArgs {
+    key: IssueKey,
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct CalendarArgs {
+    /// Дата в формате YYYY-MM-DD
+    date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CommentArgs {
+    key: IssueKey,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct BulkCommentArgs {
+    comments: Vec<CommentArgs>,
+}
+
+#[derive(Deserialize)]
+struct TransitionArgs {
+    key: IssueKey,
+    transition_id: String,
+}
+
+impl AgentAction {
+    /// Разбирает вызов инструмента; функции вне списка отклоняются
+    pub fn parse(call: &FunctionCall) -> Result<Self> {
+        let arguments = if call.arguments.trim().is_empty() {
+            "{}"
+        } else {
+            call.arguments.as_str()
+        };
+        let action = match call.name.as_str() {
+            "search_issues" => {
+                let args: SearchArgs = serde_json::from_str(arguments)?;
+                AgentAction::SearchIssues { query: args.query }
+            }
+            "get_issue" => AgentAction::GetIssue {
+                key: serde_json::from_str::<KeyArgs>(arguments)?.key,
+            },
+            "get_comments" => AgentAction::GetComments {
+                key: serde_json::from_str::<KeyArgs>(arguments)?.key,
+            },
+            "get_transitions" => AgentAction::GetTransitions {
+                key: serde_json::from_str::<KeyArgs>(arguments)?.key,
+            },
+            "calendar_events" => {
+                let args: CalendarArgs = serde_json::from_str(arguments)?;
+                let date = args
+                    .date
+                    .map(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d"))
+                    .transpose()
+                    .context("Дата ожидается в формате YYYY-MM-DD")?;
+                AgentAction::CalendarEvents { date }
+            }
+            "add_comment" => {
+                let args: CommentArgs = serde_json::from_str(arguments)?;
+                AgentAction::AddComment {
+                    key: args.key,
+                    text: args.text,
+                }
+            }
+            "add_comments" => {
+                let args: BulkCommentArgs = serde_json::from_str(arguments)?;
+                if args.comments.is_empty() {
+                    bail!("Не передано ни одного комментария");
+                }
+                AgentAction::AddComments {
+                    comments: args
+                        .comments
+                        .into_iter()
+                        .map(|comment| (comment.key, comment.text))
+                        .collect(),
+                }
+            }
+            "execute_transition" => {
+                let args: TransitionArgs = serde_json::from_str(arguments)?;
+                AgentAction::Transition {
+                    key: args.key,
+                    transition_id: args.transition_id,
+                }
+            }
+            other => bail!("Инструмент {other} агенту недоступен"),
+        };
+        Ok(action)
+    }
+
+    /// Изменяет ли действие данные и требует ли подтверждения
+    pub fn is_mutation(&self) -> bool {
+        matches!(
+            self,
+            AgentAction::AddComment { .. }
+                | AgentAction::AddComments { .. }
+                | AgentAction::Transition { .. }
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            AgentAction::SearchIssues { query } => format!("поиск задач: {query}"),
+            AgentAction::GetIssue { key } => format!("задача {key}"),
+            AgentAction::GetComments { key } => format!("комментарии {key}"),
+            AgentAction::GetTransitions { key } => format!("переходы {key}"),
+            AgentAction::CalendarEvents { date } => match date {
+                Some(date) => format!("встречи {date}"),
+                None => "встречи сегодня".to_string(),
+            },
+            AgentAction::AddComment { key, text } => format!("комментарий в {key}:\n{text}"),
+            AgentAction::AddComments { comments } => {
+                let mut description = format!("комментарии в {} задач:", comments.len());
+                for (key, text) in comments {
+                    description.push_str(&format!("\n{key}: {text}"));
+                }
+                description
+            }
+            AgentAction::Transition { key, transition_id } => {
+                format!("переход {key} → {transition_id}")
+            }
+        }
+    }
+
+    async fn execute(&self, tracker: &TrackerClient) -> Result<Value> {
+        let result = match self {
+            AgentAction::SearchIssues { query } => {
+                let request = SearchRequest {
+                    query: Some(query.clone()),
+                    ..Default::default()
+                };
+                let params = SearchParams {
+                    per_page: Some(MAX_SEARCH_RESULTS),
+                    ..Default::default()
+                };
+                let issues = tracker.search_issues(&request, Some(params)).await?;
+                Value::Array(
+                    issues
+                        .iter()
+                        .map(|issue| issue_brief(&redact::issue(issue)))
+                        .collect(),
+                )
+            }
+            AgentAction::GetIssue { key } => {
+                let issue = redact::issue(&tracker.get_issue(key, None).await?);
+                let mut brief = issue_brief(&issue);
+                brief["description"] = json!(issue.description);
+                brief
+            }
+            AgentAction::GetComments { key } => {
+                let comments = tracker.get_comments(key).await?;
+                Value::Array(
+                    comments
+                        .iter()
+                        .map(|comment| {
+                            json!({
+                                "author": user_name(comment.created_by.as_ref()),
+                                "createdAt": comment.created_at,
+                                "text": redact::text(key, &comment.text),
+                            })
+                        })
+                        .collect(),
+                )
+            }
+            AgentAction::GetTransitions { key } => {
+                let transitions = tracker.get_transitions(key).await?;
+                Value::Array(
+                    transitions
+                        .iter()
+                        .map(|transition| {
+                            json!({
+                                "id": transition.id,
+                                "display": transition.display,
+                                "to": transition.to.as_ref().and_then(|status| status.display.clone()),
+                            })
+                        })
+                        .collect(),
+                )
+            }
+            AgentAction::CalendarEvents { date } => {
+                let zone = timezone::current();
+                let date = date.unwrap_or_else(|| zone.today());
+                let events = calendar_lib::load_from_env_in(zone)?;
+                Value::Array(
+                    calendar_lib::events_on(&events, date)
+                        .into_iter()
+                        .map(|event| {
+                            json!({
+                                "summary": event.summary,
+                                "start": event.start.to_string(),
+                                "end": event.end.to_string(),
+                                "allDay": event.all_day,
+                                "issues": event.issue_keys(),
+                            })
+                        })
+                        .collect(),
+                )
+            }
+            AgentAction::AddComment { key, text } => {
+                tracker
+                    .add_comment(key, &AddCommentRequest::new(text.clone()))
+                    .await?;
+                json!({"ok": true})
+            }
+            AgentAction::AddComments { comments } => {
+                let items: Vec<BulkComment> = comments
+                    .iter()
+                    .map(|(key, text)| BulkComment::new(key.clone(), text))
+                    .collect();
+                let report = tracker.add_comments_bulk(&items).await;
+                json!({
+                    "posted": report.posted.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+                    "failed": report
+                        .failed
+                        .iter()
+                        .map(|(key, err)| json!({"key": key, "error": err.to_string()}))
+                        .collect::<Vec<_>>(),
+                })
+            }
+            AgentAction::Transition { key, transition_id } => {
+                tracker
+                    .execute_transition(
+                        key,
+                        transition_id,
+                        &ExecuteTransitionRequest::default(),
+                        None,
+                    )
+                    .await?;
+                json!({"ok": true})
+            }
+        };
+        Ok(result)
+    }
+}
+
+fn issue_brief(issue: &Issue) -> Value {
+    json!({
+        "key": issue.key,
+        "summary": issue.summary,
+        "status": issue.status.as_ref().and_then(|status| status.display.clone()),
+        "assignee": issue.assignee.as_ref().map(|user| user_name(Some(user))),
+        "updatedAt": issue.updated_at,
+    })
+}
+
+fn user_name(user: Option<&User>) -> String {
+    user.and_then(|user| user.display.clone().or_else(|| user.login.clone()))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+/// Инструменты, доступные модели
+pub fn tools() -> Vec<Tool> {
+    let key = json!({
+        "type": "object",
+        "properties": {"key": {"type": "string", "description": "Ключ задачи, например TREK-1"}},
+        "required": ["key"]
+    });
+    vec![
+        Tool::function(
+            "search_issues",
+            "Найти задачи запросом на языке запросов Трекера",
+            json!({
+                "type": "object",
+                "properties": {"query": {"type": "string"}},
+                "required": ["query"]
+            }),
+        ),
+        Tool::function("get_issue", "Получить задачу с описанием", key.clone()),
+        Tool::function("get_comments", "Получить комментарии задачи", key.clone()),
+        Tool::function(
+            "get_transitions",
+            "Получить доступные переходы по статусам",
+            key,
+        ),
+        Tool::function(
+            "calendar_events",
+            "Встречи из календаря пользователя за день",
+            json!({
+                "type": "object",
+                "properties": {"date": {"type": "string", "description": "Дата YYYY-MM-DD, по умолчанию сегодня"}}
+            }),
+        ),
+        Tool::function(
+            "add_comment",
+            "Добавить комментарий в задачу (требует подтверждения пользователя)",
+            json!({
+                "type": "object",
+                "properties": {"key": {"type": "string"}, "text": {"type": "string"}},
+                "required": ["key", "text"]
+            }),
+        ),
+        Tool::function(
+            "add_comments",
+            "Добавить комментарии во много задач одним вызовом (требует подтверждения пользователя)",
+            json!({
+                "type": "object",
+                "properties": {"comments": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"key": {"type": "string"}, "text": {"type": "string"}},
+                        "required": ["key", "text"]
+                    }
+                }},
+                "required": ["comments"]
+            }),
+        ),
+        Tool::function(
+            "execute_transition",
+            "Перевести задачу по переходу из get_transitions (требует подтверждения пользователя)",
+            json!({
+                "type": "object",
+                "properties": {"key": {"type": "string"}, "transition_id": {"type": "string"}},
+                "required": ["key", "transition_id"]
+            }),
+        ),
+    ]
+}
+
+/// Выполняет поручение; `confirm` решает, можно ли выполнить изменение
+///
+/// Комментарии переводятся на `language`, если он задан и модель написала их на другом.
+#[instrument(skip(llm, tracker, confirm))]
+pub async fn run<T: LlmClientTrait>(
+    llm: &T,
+    tracker: &TrackerClient,
+    task: &str,
+    max_steps: usize,
+    language: Option<ContentLanguage>,
+    mut confirm: impl FnMut(&str) -> bool,
+) -> Result<AgentOutcome> {
+    let mut messages = vec![Message::system(SYSTEM_PROMPT), Message::user(task)];
+    let mut log = Vec::new();
+
+    for step in 1..=max_steps {
+        let options = CompletionOptions::new().tools(tools());
+        let response = llm.chat_completion(messages.clone(), Some(options)).await?;
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .context("LLM вернула пустой ответ")?;
+
+        if message.tool_calls.is_empty() {
+            info!(step, "Агент завершил работу");
+            return Ok(AgentOutcome {
+                answer: message.content,
+                log,
+            });
+        }
+
+        let calls = message.tool_calls.clone();
+        messages.push(message);
+        for call in calls {
+            let (entry, result) =
+                call_tool(llm, tracker, &call.function, language, &mut confirm).await;
+            debug!(tool = %call.function.name, result = %result, "Результат инструмента");
+            log.push(entry);
+            messages.push(Message::tool(call.id, result));
+        }
+    }
+
+    bail!("Агент не уложился в {max_steps} шагов")
+}
+
+/// Выполняет один вызов; ошибки возвращаются модели текстом, чтобы она могла их учесть
+async fn call_tool<T: LlmClientTrait>(
+    llm: &T,
+    tracker: &TrackerClient,
+    call: &FunctionCall,
+    language: Option<ContentLanguage>,
+    confirm: &mut impl FnMut(&str) -> bool,
+) -> (String, String) {
+    let mut action = match AgentAction::parse(call) {
+        Ok(action) => action,
+        Err(err) => {
+            warn!(tool = %call.name, error = %err, "Некорректный вызов инструмента");
+            return (
+                format!("✗ {}: {err}", call.name),
+                json!({"error": err.to_string()}).to_string(),
+            );
+        }
+    };
+
+    let texts: Vec<&mut String> = match &mut action {
+        AgentAction::AddComment { text, .. } => vec![text],
+        AgentAction::AddComments { comments } => {
+            comments.iter_mut().map(|(_, text)| text).collect()
+        }
+        _ => Vec::new(),
+    };
+    for text in texts {
+        match language::enforce(llm, language, text).await {
+            Ok(translated) => *text = translated,
+            Err(err) => {
+                return (
+                    format!("✗ перевод комментария: {err}"),
+                    json!({"error": err.to_string()}).to_string(),
+                )
+            }
+        }
+    }
+
+    let description = action.describe();
+    if action.is_mutation() && !confirm(&description) {
+        return (
+            format!("⊘ отклонено: {description}"),
+            json!({"error": "Пользователь отклонил это действие"}).to_string(),
+        );
+    }
+
+    match action.execute(tracker).await {
+        Ok(result) => (format!("✓ {description}"), result.to_string()),
+        Err(err) => (
+            format!("✗ {description}: {err}"),
+            json!({"error": err.to_string()}).to_string(),
+        ),
+    }
+}
+
+fn ask_confirmation(action: &str) -> bool {
+    print!("\nАгент хочет выполнить: {action}\nВыполнить? [y/N] ");
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "да" | "д")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_lib::{ChatCompletionResponse, MockLlmClientTrait, Role};
+    use tracker_lib::TrackerConfig;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn response(message: Value) -> ChatCompletionResponse {
+        serde_json::from_value(json!({
+            "id": "test-id",
+            "model": "test-model",
+            "created": 1234567890_u64,
+            "choices": [{"index": 0, "message": message, "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+        }))
+        .unwrap()
+    }
+
+    fn tool_call(name: &str, arguments: Value) -> ChatCompletionResponse {
+        response(json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call-1",
+                "type": "function",
+                "function": {"name": name, "arguments": arguments.to_string()}
+            }]
+        }))
+    }
+
+    /// LLM, которая сначала вызывает инструмент, а затем отвечает текстом
+    fn llm_calling(name: &'static str, arguments: Value) -> MockLlmClientTrait {
+        let mut llm = MockLlmClientTrait::new();
+        let mut step = 0;
+        llm.expect_chat_completion()
+            .times(2)
+            .returning(move |messages, _| {
+                step += 1;
+                let response = if step == 1 {
+                    tool_call(name, arguments.clone())
+                } else {
+                    let result = messages.last().unwrap();
+                    assert_eq!(result.role, Role::Tool);
+                    response(json!({"role": "assistant", "content": format!("Итог: {}", result.content)}))
+                };
+                Box::pin(async move { Ok(response) })
+            });
+        llm
+    }
+
+    fn tracker(uri: String) -> TrackerClient {
+        TrackerClient::new(TrackerConfig::new("test-oauth-token").with_base_url(uri)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_tools() {
+        let call = FunctionCall {
+            name: "delete_issue".to_string(),
+            arguments: "{\"key\":\"TREK-1\"}".to_string(),
+        };
+        assert!(AgentAction::parse(&call).is_err());
+
+        let call = FunctionCall {
+            name: "add_comment".to_string(),
+            arguments: "{\"key\":\"TREK-1\",\"text\":\"Спасибо\"}".to_string(),
+        };
+        let action = AgentAction::parse(&call).unwrap();
+        assert!(action.is_mutation());
+        assert!(!AgentAction::GetIssue {
+            key: "TREK-1".parse().unwrap()
+        }
+        .is_mutation());
+    }
+
+    #[tokio::test]
+    async fn test_reads_run_without_confirmation() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "key": "TREK-1",
+                "summary": "Ревью фичи"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let llm = llm_calling("get_issue", json!({"key": "TREK-1"}));
+
+        let outcome = run(
+            &llm,
+            &tracker(mock_server.uri()),
+            "что с TREK-1?",
+            5,
+            None,
+            |_| panic!("чтение не требует подтверждения"),
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.answer.contains("Ревью фичи"));
+        assert_eq!(outcome.log, vec!["✓ задача TREK-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_declined_mutation_is_not_executed() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/TREK-1/comments"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let llm = llm_calling("add_comment", json!({"key": "TREK-1", "text": "Закрываю"}));
+        let mut asked = Vec::new();
+
+        let outcome = run(
+            &llm,
+            &tracker(mock_server.uri()),
+            "закрой ревью",
+            5,
+            None,
+            |action| {
+                asked.push(action.to_string());
+                false
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(asked, vec!["комментарий в TREK-1:\nЗакрываю"]);
+        assert!(outcome.answer.contains("отклонил"));
+        assert!(outcome.log[0].starts_with("⊘ отклонено"));
+    }
+
+    #[tokio::test]
+    async fn test_comment_is_translated_before_confirmation() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/TREK-1/comments"))
+            .and(body_json(
+                json!({"text": "Закрываю: исправлено в релизе 1.4"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut llm = llm_calling(
+            "add_comment",
+            json!({"key": "TREK-1", "text": "Closing: fixed in release 1.4"}),
+        );
+        llm.expect_complete_with_system()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async {
+                    Ok("Закрываю: исправлено в релизе 1.4".to_string())
+                })
+            });
+        let mut asked = Vec::new();
+
+        run(
+            &llm,
+            &tracker(mock_server.uri()),
+            "закрой ревью",
+            5,
+            Some(ContentLanguage::Ru),
+            |action| {
+                asked.push(action.to_string());
+                true
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            asked,
+            vec!["комментарий в TREK-1:\nЗакрываю: исправлено в релизе 1.4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_comments_report_failures() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/TREK-1/comments"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/TREK-2/comments"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let llm = llm_calling(
+            "add_comments",
+            json!({"comments": [
+                {"key": "TREK-1", "text": "Исправлено в 2.4"},
+                {"key": "TREK-2", "text": "Исправлено в 2.4"}
+            ]}),
+        );
+        let mut asked = Vec::new();
+
+        let outcome = run(
+            &llm,
+            &tracker(mock_server.uri()),
+            "отпишись в задачах релиза",
+            5,
+            None,
+            |action| {
+                asked.push(action.to_string());
+                true
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            asked,
+            vec!["комментарии в 2 задач:\nTREK-1: Исправлено в 2.4\nTREK-2: Исправлено в 2.4"]
+        );
+        assert!(
+            outcome.answer.contains("\"posted\":[\"TREK-1\"]"),
+            "{}",
+            outcome.answer
+        );
+        assert!(outcome.answer.contains("TREK-2"));
+    }
+
+    #[tokio::test]
+    async fn test_stops_after_step_limit() {
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_chat_completion().times(2).returning(|_, _| {
+            Box::pin(async { Ok(tool_call("calendar_events", json!({"date": "bad"}))) })
+        });
+
+        let result = run(
+            &llm,
+            &tracker("http://127.0.0.1:9".to_string()),
+            "...",
+            2,
+            None,
+            |_| true,
+        )
+        .await;
+
+        assert!(result.unwrap_err().to_string().contains("2 шагов"));
+    }
+}
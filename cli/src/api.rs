@@ -0,0 +1,336 @@
+//! Локальный REST API для виджетов
+//!
+//! `you api` поднимает на локальном адресе упрощённый API поверх локального индекса,
+//! календаря и клиента Трекера, чтобы виджетам (Raycast, Übersicht, Stream Deck)
+//! не нужно было самим хранить токены:
+//!
+//! - `GET /issues?q=...&limit=20` — мои задачи из индекса, недавние или найденные по тексту;
+//! - `GET /agenda?date=YYYY-MM-DD` — события календаря на день (по умолчанию сегодня);
+//! - `POST /issues/{key}/comments` с телом `{"text": "..."}` — быстрый комментарий.
+//!
+//! Если задан `YOU_API_TOKEN`, запросы должны передавать его в `Authorization: Bearer`.
+//! Без токена API запускается только на loopback.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{bail, Context, Result};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::NaiveDate;
+use clap::Args;
+use core_lib::credentials;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+use tracker_lib::{
+    comments::AddCommentRequest, ids::IssueKey, webhook, TrackerClient, TrackerError,
+};
+
+use crate::index::{self, IssueIndex};
+use crate::{settings, snooze::Snoozes, timezone};
+
+/// Переменная окружения (или запись keyring) с токеном доступа к API
+pub const TOKEN_VAR: &str = "YOU_API_TOKEN";
+
+/// Сколько задач отдаёт `/issues` без параметра `limit`
+const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Args)]
+pub struct ApiArgs {
+    /// Порт локального API
+    #[arg(short, long, default_value_t = 7777)]
+    port: u16,
+
+    /// Адрес локального API
+    #[arg(long, default_value = "127.0.0.1")]
+    bind: IpAddr,
+}
+
+struct ApiState {
+    token: Option<String>,
+    client: TrackerClient,
+    /// Соединение SQLite не потокобезопасно, поэтому запросы к индексу идут по очереди
+    index: Mutex<IssueIndex>,
+}
+
+impl ApiArgs {
+    pub async fn execute(&self) -> Result<()> {
+        let token = credentials::resolve(TOKEN_VAR);
+        check_exposure(self.bind, token.as_deref())?;
+
+        // Виджеты опрашивают одни и те же задачи, поэтому ответы проверяются по ETag
+        let client = settings::tracker_client_builder()?
+            .response_cache(true)
+            .build()?;
+        let index = IssueIndex::open_default()?;
+        match index::sync(&index, &client, false).await {
+            Ok(synced) => info!(synced, "Индекс обновлён перед запуском API"),
+            Err(err) => {
+                warn!(error = %err, "Не удалось обновить индекс, отдаются сохранённые задачи")
+            }
+        }
+
+        let state = Arc::new(ApiState {
+            token,
+            client,
+            index: Mutex::new(index),
+        });
+
+        let addr = SocketAddr::new(self.bind, self.port);
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Не удалось занять адрес {addr}"))?;
+        println!("Локальный API на http://{addr}");
+        axum::serve(listener, router(state)).await?;
+        Ok(())
+    }
+}
+
+/// Без токена API доступен только с этой машины: он читает задачи и пишет
+/// комментарии от имени владельца токена Трекера
+fn check_exposure(bind: IpAddr, token: Option<&str>) -> Result<()> {
+    if token.is_some() {
+        return Ok(());
+    }
+    if !bind.is_loopback() {
+        bail!(
+            "{TOKEN_VAR} не задан: без токена API можно открыть только на loopback, а не на {bind}"
+        );
+    }
+    warn!("{TOKEN_VAR} не задан, API доступен без токена");
+    Ok(())
+}
+
+fn router(state: Arc<ApiState>) -> Router {
+    Router::new()
+        .route("/issues", get(issues))
+        .route("/issues/{key}/comments", post(comment))
+        .route("/agenda", get(agenda))
+        .with_state(state)
+}
+
+/// Ошибка запроса: код ответа и текст в поле `error`
+#[derive(Debug)]
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}"))
+    }
+}
+
+impl From<TrackerError> for ApiError {
+    fn from(err: TrackerError) -> Self {
+        let status = match &err {
+            TrackerError::NotFound { .. } => StatusCode::NOT_FOUND,
+            TrackerError::Forbidden => StatusCode::FORBIDDEN,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        Self(status, err.to_string())
+    }
+}
+
+fn authorize(state: &ApiState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let Some(token) = &state.token else {
+        return Ok(());
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if webhook::verify_secret(token, provided) {
+        Ok(())
+    } else {
+        Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            "неверный токен".to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IssuesQuery {
+    q: Option<String>,
+    limit: Option<usize>,
+}
+
+async fn issues(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Query(query): Query<IssuesQuery>,
+) -> Result<Json<Value>, ApiError> {
+    authorize(&state, &headers)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let index = state.index.lock().unwrap_or_else(|err| err.into_inner());
+    let today = timezone::current().today();
+    let snoozes = Snoozes::load();
+    // Отложенные задачи отбрасываются до лимита, иначе они съедают места в выдаче
+    let fetch_limit = limit + snoozes.hidden_count(today);
+    let issues: Vec<Value> = match query.q.as_deref().filter(|q| !q.trim().is_empty()) {
+        Some(text) => index
+            .search_text(text, fetch_limit)?
+            .into_iter()
+            .filter(|hit| !snoozes.is_snoozed(&hit.key, today))
+            .take(limit)
+            .map(|hit| json!({"key": hit.key, "summary": hit.summary, "status": hit.status}))
+            .collect(),
+        None => index
+            .recent(fetch_limit)?
+            .into_iter()
+            .filter(|issue| !snoozes.is_snoozed(&issue.key, today))
+            .take(limit)
+            .map(|issue| {
+                json!({
+                    "key": issue.key,
+                    "summary": issue.summary,
+                    "status": issue.status,
+                    "updatedAt": issue.updated_at,
+                })
+            })
+            .collect(),
+    };
+    Ok(Json(Value::Array(issues)))
+}
+
+#[derive(Debug, Deserialize)]
+struct AgendaQuery {
+    /// Дата в формате `YYYY-MM-DD`
+    date: Option<String>,
+}
+
+async fn agenda(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Query(query): Query<AgendaQuery>,
+) -> Result<Json<Value>, ApiError> {
+    authorize(&state, &headers)?;
+    let zone = timezone::current();
+    let date = match query.date.as_deref() {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+            ApiError(
+                StatusCode::BAD_REQUEST,
+                format!("некорректная дата {date}, ожидается YYYY-MM-DD"),
+            )
+        })?,
+        None => zone.today(),
+    };
+    let events = calendar_lib::load_from_env_in(zone).context("Не удалось загрузить календарь")?;
+    Ok(Json(agenda_json(&events, date)))
+}
+
+fn agenda_json(events: &[calendar_lib::Event], date: NaiveDate) -> Value {
+    Value::Array(
+        calendar_lib::events_on(events, date)
+            .into_iter()
+            .map(|event| {
+                json!({
+                    "summary": event.summary,
+                    "start": event.start.to_string(),
+                    "end": event.end.to_string(),
+                    "allDay": event.all_day,
+                    "location": event.location,
+                    "issues": event.issue_keys(),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentBody {
+    text: String,
+}
+
+async fn comment(
+    State(state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Path(key): Path<IssueKey>,
+    Json(body): Json<CommentBody>,
+) -> Result<(StatusCode, Json<Value>), ApiError> {
+    authorize(&state, &headers)?;
+    if body.text.trim().is_empty() {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            "пустой комментарий".to_string(),
+        ));
+    }
+    let comment = state
+        .client
+        .add_comment(&key, &AddCommentRequest::new(body.text))
+        .await?;
+    info!(issue_key = %key, "Комментарий добавлен через локальный API");
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({"issue": key, "id": comment.id})),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(token: Option<&str>) -> ApiState {
+        ApiState {
+            token: token.map(str::to_string),
+            client: TrackerClient::with_token("test-token").unwrap(),
+            index: Mutex::new(IssueIndex::open_in_memory().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_authorize_requires_bearer_token_when_configured() {
+        let mut headers = HeaderMap::new();
+        assert!(authorize(&state(None), &headers).is_ok());
+
+        let protected = state(Some("s3cret"));
+        assert!(authorize(&protected, &headers).is_err());
+        headers.insert(header::AUTHORIZATION, "Bearer s3cret".parse().unwrap());
+        assert!(authorize(&protected, &headers).is_ok());
+        headers.insert(header::AUTHORIZATION, "s3cret".parse().unwrap());
+        assert!(authorize(&protected, &headers).is_err());
+    }
+
+    #[test]
+    fn test_external_bind_requires_token() {
+        let external: IpAddr = "0.0.0.0".parse().unwrap();
+        assert!(check_exposure(external, None).is_err());
+        assert!(check_exposure(external, Some("secret")).is_ok());
+        assert!(check_exposure("::1".parse().unwrap(), None).is_ok());
+    }
+
+    #[test]
+    fn test_agenda_json_lists_events_of_the_day() {
+        let events = calendar_lib::ics::parse_ics(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VEVENT\r\n\
+             SUMMARY:Разбор TREK-7\r\n\
+             DTSTART:20240503T100000\r\n\
+             DTEND:20240503T110000\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        )
+        .unwrap();
+
+        let agenda = agenda_json(&events, NaiveDate::from_ymd_opt(2024, 5, 3).unwrap());
+        assert_eq!(agenda[0]["summary"], "Разбор TREK-7");
+        assert_eq!(agenda[0]["issues"], json!(["TREK-7"]));
+        assert_eq!(
+            agenda_json(&events, NaiveDate::from_ymd_opt(2024, 5, 4).unwrap()),
+            json!([])
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Команды календаря: `you calendar to-issue`
+//!
+//! Встреча из календаря (`CALENDAR_ICS`) превращается в задачу Трекера: название
+//! события становится названием задачи, повестка и заметки — описанием, конец
+//! встречи — сроком. В описание попадают время, ссылка на встречу и UID события,
+//! а поле `unique` не даёт создать вторую задачу по той же встрече.
+
+use anyhow::{bail, Context, Result};
+use calendar_lib::Event;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use clap::Subcommand;
+use tracing::{debug, info, instrument};
+use tracker_lib::{ids::QueueKey, task::CreateIssueRequest, TrackerError};
+
+use crate::config::Config;
+use crate::settings;
+use crate::timezone;
+use crate::tracker::export_created;
+
+/// Префикс поля `unique` задач, созданных по встречам
+const UNIQUE_PREFIX: &str = "calendar:";
+
+/// Команды календаря
+#[derive(Subcommand)]
+pub enum CalendarCommands {
+    /// Создать задачу по встрече: название, повестка и срок — из события
+    ToIssue {
+        /// UID события или его начало, если оно однозначно
+        event_id: String,
+
+        /// Ключ очереди для новой задачи
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// Не применять пресет очереди из конфигурации
+        #[arg(long)]
+        no_preset: bool,
+    },
+}
+
+impl CalendarCommands {
+    /// Выполняет команду календаря
+    pub async fn execute(&self) -> Result<()> {
+        match self {
+            CalendarCommands::ToIssue {
+                event_id,
+                queue,
+                no_preset,
+            } => execute_to_issue(event_id, queue, *no_preset).await,
+        }
+    }
+}
+
+/// Находит событие по UID: сначала точное совпадение, затем единственное по началу
+///
+/// У повторяющейся встречи один UID на все повторы; выбирается ближайший
+/// незавершённый к моменту `now`, а если все прошли — последний.
+pub fn find_event<'a>(
+    events: &'a [Event],
+    event_id: &str,
+    now: NaiveDateTime,
+) -> Result<&'a Event> {
+    let exact: Vec<&Event> = events
+        .iter()
+        .filter(|event| event.uid.as_deref() == Some(event_id))
+        .collect();
+    let matches = if exact.is_empty() {
+        events
+            .iter()
+            .filter(|event| {
+                event
+                    .uid
+                    .as_deref()
+                    .is_some_and(|uid| uid.starts_with(event_id))
+            })
+            .collect()
+    } else {
+        exact
+    };
+    let mut series: Vec<&Event> = Vec::new();
+    for event in &matches {
+        if !series.iter().any(|known| known.uid == event.uid) {
+            series.push(event);
+        }
+    }
+    match series.as_slice() {
+        [_] => Ok(matches
+            .iter()
+            .filter(|event| event.end > now)
+            .min_by_key(|event| event.start)
+            .or_else(|| matches.iter().max_by_key(|event| event.start))
+            .expect("совпадения не пусты")),
+        [] => bail!("Событие {event_id} не найдено в календаре"),
+        _ => bail!(
+            "UID {event_id} подходит к нескольким событиям: {}",
+            series
+                .iter()
+                .map(|event| event.summary.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Последний день встречи: для событий на весь день конец в календаре не включается
+fn last_day(event: &Event) -> NaiveDate {
+    if event.all_day && event.end > event.start {
+        (event.end - Duration::days(1)).date()
+    } else {
+        event.end.date()
+    }
+}
+
+/// Запрос на создание задачи по встрече
+pub fn to_request(event: &Event, queue: &QueueKey) -> CreateIssueRequest {
+    let summary = match event.summary.trim() {
+        "" => "Встреча без названия",
+        summary => summary,
+    };
+    let when = if event.all_day {
+        let last = last_day(event);
+        if last == event.start.date() {
+            format!("{}, весь день", event.start.format("%Y-%m-%d"))
+        } else {
+            format!(
+                "{} – {}",
+                event.start.format("%Y-%m-%d"),
+                last.format("%Y-%m-%d")
+            )
+        }
+    } else {
+        format!(
+            "{}–{}",
+            event.start.format("%Y-%m-%d %H:%M"),
+            event.end.format("%H:%M")
+        )
+    };
+
+    let mut header = vec![format!("Встреча: {when}")];
+    if let Some(link) = event.meeting_link() {
+        header.push(format!("Ссылка: {link}"));
+    }
+    if let Some(uid) = &event.uid {
+        header.push(format!("Событие календаря: {uid}"));
+    }
+    let mut description = header.join("\n");
+    if let Some(notes) = event.description.as_deref().map(str::trim) {
+        if !notes.is_empty() {
+            description.push_str("\n\n");
+            description.push_str(notes);
+        }
+    }
+
+    let mut request = CreateIssueRequest::new(queue.clone(), summary);
+    request.description = Some(description);
+    request.deadline = Some(last_day(event));
+    request.unique = event
+        .uid
+        .as_ref()
+        .map(|uid| format!("{UNIQUE_PREFIX}{uid}"));
+    request
+}
+
+/// Создаёт задачу по встрече из календаря
+#[instrument]
+async fn execute_to_issue(event_id: &str, queue: &QueueKey, no_preset: bool) -> Result<()> {
+    let zone = timezone::current();
+    let events = calendar_lib::load_from_env_in(zone).context("Не удалось загрузить календарь")?;
+    let event = find_event(&events, event_id, zone.now())?;
+    debug!(summary = %event.summary, "Событие найдено");
+
+    let mut request = to_request(event, queue);
+    if !no_preset {
+        if let Some(preset) = Config::load()?.presets.get(queue) {
+            info!(%queue, "Применён пресет очереди");
+            preset.apply(&mut request);
+        }
+    }
+
+    let client = settings::tracker_client()?;
+    match client.create_issue(&request).await {
+        Ok(issue) => {
+            println!("Создана задача {}: {}", issue.key, issue.summary);
+            info!(issue_key = %issue.key, "Задача по встрече создана");
+            export_created(&issue.key, &issue.summary);
+            Ok(())
+        }
+        Err(TrackerError::ApiError { status, .. }) if status.as_u16() == 409 => {
+            bail!("Задача по встрече «{}» уже создана", event.summary)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(uid: &str, summary: &str, start: &str, end: &str) -> Event {
+        Event {
+            uid: Some(uid.to_string()),
+            summary: summary.to_string(),
+            description: None,
+            location: None,
+            url: None,
+            attendees: Vec::new(),
+            start: NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap(),
+            end: NaiveDateTime::parse_from_str(end, "%Y-%m-%d %H:%M").unwrap(),
+            all_day: false,
+        }
+    }
+
+    #[test]
+    fn test_meeting_becomes_issue_request() {
+        let mut retro = event(
+            "4f1c-retro@calendar",
+            "Ретро релиза",
+            "2026-10-20 15:00",
+            "2026-10-20 16:00",
+        );
+        retro.description =
+            Some("Повестка:\n- что сломалось\n- https://telemost.yandex.ru/j/42\n".to_string());
+        let events = vec![
+            retro.clone(),
+            event(
+                "4f2a-standup@calendar",
+                "Стендап",
+                "2026-10-20 10:00",
+                "2026-10-20 10:15",
+            ),
+            event(
+                "4f2a-standup@calendar",
+                "Стендап",
+                "2026-10-21 10:00",
+                "2026-10-21 10:15",
+            ),
+        ];
+
+        let now = NaiveDateTime::parse_from_str("2026-10-20 12:00", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(find_event(&events, "4f1c", now).unwrap(), &retro);
+        assert!(find_event(&events, "4f", now).is_err());
+        assert!(find_event(&events, "missing", now).is_err());
+        // Из повторов встречи берётся ближайший незавершённый
+        assert_eq!(find_event(&events, "4f2a", now).unwrap(), &events[2]);
+
+        let request = to_request(&retro, &"TREK".parse().unwrap());
+        assert_eq!(request.summary, "Ретро релиза");
+        assert_eq!(
+            request.description.as_deref(),
+            Some(
+                "Встреча: 2026-10-20 15:00–16:00\nСсылка: https://telemost.yandex.ru/j/42\n\
+Событие календаря: 4f1c-retro@calendar\n\nПовестка:\n- что сломалось\n- https://telemost.yandex.ru/j/42"
+            )
+        );
+        assert_eq!(
+            request.unique.as_deref(),
+            Some("calendar:4f1c-retro@calendar")
+        );
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["deadline"],
+            "2026-10-20"
+        );
+
+        let mut offsite = event(
+            "offsite",
+            "Выездная",
+            "2026-10-21 00:00",
+            "2026-10-23 00:00",
+        );
+        offsite.all_day = true;
+        let request = to_request(&offsite, &"TREK".parse().unwrap());
+        assert_eq!(
+            request.deadline,
+            Some(NaiveDate::from_ymd_opt(2026, 10, 22).unwrap())
+        );
+        assert!(request
+            .description
+            .unwrap()
+            .starts_with("Встреча: 2026-10-21 – 2026-10-22"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Конфигурация CLI из YAML файла
+//!
+//! Файл ищется по пути из переменной окружения `YOU_CONFIG`, иначе
+//! используется `~/.config/you/config.yaml`. Отсутствующий файл не является ошибкой.
+//! Команды, которые сами меняют конфигурацию (например, `you search save`), переписывают
+//! в файле только свою секцию.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_norway::{Mapping, Value};
+use tracing::{debug, info, instrument};
+use tracker_lib::ids::QueueKey;
+
+use crate::hooks::CommandHooks;
+use crate::language::LanguageConfig;
+use crate::notify::NotifyConfig;
+use crate::personas::Persona;
+use crate::prep::PrepConfig;
+use crate::presets::QueuePreset;
+use crate::prompts::PromptPreset;
+use crate::queues::QueueConfig;
+use crate::redact::RedactConfig;
+use crate::sandbox::SandboxConfig;
+use crate::schedule::RecurringTask;
+use crate::search::SavedSearch;
+use crate::serve::WebhookRule;
+use crate::sprint::SprintConfig;
+use crate::today::TodayConfig;
+use crate::tui::{layout::LayoutMode, theme::ThemeConfig};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Режим только для чтения для всех команд, как `--read-only`
+    pub read_only: bool,
+    /// Пресеты полей новых задач по очередям
+    pub presets: HashMap<QueueKey, QueuePreset>,
+    /// Настройки очередей: какие уведомления приходят по их задачам
+    pub queue: HashMap<QueueKey, QueueConfig>,
+    /// Именованные промпты для `you llm run` и экрана LLM
+    pub prompts: HashMap<String, PromptPreset>,
+    /// Персоны для `--persona` в командах `you llm` и экрана LLM
+    pub personas: HashMap<String, Persona>,
+    /// Именованные поиски для `you search run` и выбора в TUI
+    pub searches: HashMap<String, SavedSearch>,
+    /// Язык организации для текстов, которые LLM пишет в Трекер
+    pub language: LanguageConfig,
+    /// Скрытие личных данных и секретов в тексте задач перед отправкой в LLM
+    pub redact: RedactConfig,
+    /// Команда и её ёмкость для `you llm plan-sprint`
+    pub sprint: SprintConfig,
+    /// Часовой пояс пользователя (имя IANA или local), как `--tz`
+    pub timezone: Option<String>,
+    /// Уведомления на рабочем столе от `you serve` и TUI
+    pub notify: NotifyConfig,
+    /// Личная задача для `you today sync`
+    pub today: TodayConfig,
+    /// Скрипты до и после команд по имени команды (`tracker create`)
+    pub hooks: HashMap<String, CommandHooks>,
+    /// Тестовая организация и очередь для `--sandbox`
+    pub sandbox: SandboxConfig,
+    pub tui: TuiConfig,
+    pub report: ReportConfig,
+    pub serve: ServeConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// Имя активной темы: dark, light или одна из `themes`
+    pub theme: Option<String>,
+
+    /// Пользовательские темы
+    pub themes: HashMap<String, ThemeConfig>,
+
+    /// Раскладка по умолчанию: split (список и детали) или single
+    pub layout: LayoutMode,
+
+    /// Ширина левой панели в процентах
+    pub list_width: Option<u16>,
+
+    /// Период фонового обновления открытых задач в секундах; 0 отключает обновление
+    pub refresh_interval_secs: Option<u64>,
+
+    /// Длительность фокус-таймера в минутах
+    pub focus_minutes: Option<u64>,
+
+    /// Сколько записей вывода хранит каждый экран
+    pub scrollback: Option<usize>,
+
+    /// Файл, в который дописываются команды и вывод всех экранов; без него журнал не пишется
+    pub session_log: Option<PathBuf>,
+
+    /// Глубина предпросмотра ссылок на задачи в карточке (по умолчанию 1, 0 — выключить)
+    pub link_preview_depth: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ReportConfig {
+    /// Шаблон запроса к LLM для `report release --polish`: `{version}`, `{queue}`, `{notes}`
+    pub release_template: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ServeConfig {
+    /// Действия `you serve` по вебхукам Трекера
+    pub actions: Vec<WebhookRule>,
+
+    /// Повторяющиеся задачи, которые `you serve` создаёт по расписанию
+    pub schedule: Vec<RecurringTask>,
+
+    /// Справки к встречам, которые `you serve` готовит перед их началом
+    pub prep: PrepConfig,
+}
+
+impl Config {
+    /// Загружает конфигурацию; при отсутствии файла возвращает значения по умолчанию
+    #[instrument]
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_path() else {
+            debug!("Путь к конфигурации не определён, используются значения по умолчанию");
+            return Ok(Self::default());
+        };
+
+        if !path.exists() {
+            debug!(path = %path.display(), "Файл конфигурации не найден");
+            return Ok(Self::default());
+        }
+
+        Self::load_from(&path)
+    }
+
+    /// Загружает конфигурацию из конкретного файла
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Не удалось прочитать конфигурацию {}", path.display()))?;
+        let config: Config = serde_norway::from_str(&content)
+            .with_context(|| format!("Некорректная конфигурация {}", path.display()))?;
+
+        info!(path = %path.display(), "Конфигурация загружена");
+        Ok(config)
+    }
+
+    /// Записывает секцию верхнего уровня в файл конфигурации и возвращает путь к нему
+    ///
+    /// Остальные секции сохраняются как есть, но комментарии в файле теряются.
+    #[instrument(skip(value))]
+    pub fn save_section<T: Serialize>(key: &str, value: &T) -> Result<PathBuf> {
+        let path = config_path().context("Не удалось определить путь к конфигурации")?;
+        let content =
+            if path.exists() {
+                Some(std::fs::read_to_string(&path).with_context(|| {
+                    format!("Не удалось прочитать конфигурацию {}", path.display())
+                })?)
+            } else {
+                None
+            };
+        let content = with_section(content.as_deref(), key, value)
+            .with_context(|| format!("Некорректная конфигурация {}", path.display()))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        info!(path = %path.display(), section = key, "Конфигурация сохранена");
+        Ok(path)
+    }
+
+    /// Путь к файлу конфигурации, даже если файла ещё нет
+    pub fn path() -> Option<PathBuf> {
+        config_path()
+    }
+
+    /// Каталог файла конфигурации: там же лежат пользовательские шаблоны вывода
+    pub fn dir() -> Option<PathBuf> {
+        config_path()?.parent().map(Path::to_path_buf)
+    }
+}
+
+/// YAML конфигурации с заменённой секцией `key`
+fn with_section<T: Serialize>(content: Option<&str>, key: &str, value: &T) -> Result<String> {
+    let mut root: Value = match content {
+        Some(content) => serde_norway::from_str(content)?,
+        None => Value::Null,
+    };
+    if root.is_null() {
+        root = Value::Mapping(Mapping::new());
+    }
+    let Value::Mapping(sections) = &mut root else {
+        bail!("конфигурация должна быть словарём");
+    };
+    sections.insert(
+        Value::String(key.to_string()),
+        serde_norway::to_value(value)?,
+    );
+    Ok(serde_norway::to_string(&root)?)
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("YOU_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/you/config.yaml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::ContentLanguage;
+
+    #[test]
+    fn test_parse_config_with_custom_theme() {
+        let yaml = r##"
+tui:
+  theme: solarized
+  themes:
+    solarized:
+      base: dark
+      border: "#586e75"
+      status_done: green
+  layout: single
+  list_width: 40
+  refresh_interval_secs: 0
+  focus_minutes: 50
+report:
+  release_template: "Кратко перескажи {version}:\n{notes}"
+language:
+  enforce: ru
+"##;
+
+        let config: Config = serde_norway::from_str(yaml).unwrap();
+        assert_eq!(config.tui.theme.as_deref(), Some("solarized"));
+        let theme = &config.tui.themes["solarized"];
+        assert_eq!(theme.base.as_deref(), Some("dark"));
+        assert_eq!(theme.border.as_deref(), Some("#586e75"));
+        assert_eq!(config.tui.layout, LayoutMode::Single);
+        assert_eq!(config.tui.list_width, Some(40));
+        assert_eq!(config.tui.refresh_interval_secs, Some(0));
+        assert_eq!(config.tui.focus_minutes, Some(50));
+        assert_eq!(config.language.enforce, Some(ContentLanguage::Ru));
+        assert_eq!(
+            config.report.release_template.as_deref(),
+            Some("Кратко перескажи {version}:\n{notes}")
+        );
+    }
+
+    #[test]
+    fn test_with_section_keeps_other_sections() {
+        let searches = HashMap::from([(
+            "mybugs".to_string(),
+            SavedSearch {
+                query: "Type: Bug Assignee: me()".to_string(),
+                filter_id: None,
+            },
+        )]);
+        let yaml = with_section(
+            Some("read_only: true\nsearches: {}\n"),
+            "searches",
+            &searches,
+        )
+        .unwrap();
+
+        let config: Config = serde_norway::from_str(&yaml).unwrap();
+        assert!(config.read_only);
+        assert_eq!(config.searches["mybugs"].query, "Type: Bug Assignee: me()");
+
+        let fresh = with_section(None, "searches", &searches).unwrap();
+        assert!(fresh.starts_with("searches:"));
+        assert!(with_section(Some("- list"), "searches", &searches).is_err());
+    }
+
+    #[test]
+    fn test_parse_empty_config() {
+        let config: Config = serde_norway::from_str("{}").unwrap();
+        assert!(config.tui.theme.is_none());
+        assert!(config.tui.themes.is_empty());
+        assert_eq!(config.tui.layout, LayoutMode::Split);
+        assert!(config.report.release_template.is_none());
+        assert!(config.language.enforce.is_none());
+        assert!(config.serve.actions.is_empty());
+        assert!(config.serve.schedule.is_empty());
+    }
+}
@@ -0,0 +1,370 @@
+//! Диагностика окружения: `you doctor`
+//!
+//! Проверяет прокси из `WORK_PROXY`, доступ к Трекеру и OpenRouter запросами «кто я»
+//! и источник календаря `CALENDAR_ICS`. Для каждой проверки выводится время ответа,
+//! а для каждой проблемы — что сделать, чтобы её исправить. Если хоть одна проверка
+//! не прошла, команда завершается с ошибкой, поэтому её удобно запускать в скриптах.
+
+use std::{
+    future::Future,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+use clap::Args;
+use core_lib::proxy::{self, WORK_PROXY};
+use llm_lib::{LlmClient, LlmConfig, LlmError};
+use tracing::instrument;
+use tracker_lib::{TrackerClient, TrackerError, ORG_ID_VAR, ORG_KIND_VAR};
+
+use crate::llm::DEFAULT_MODEL;
+use crate::settings;
+use crate::timezone;
+
+/// Сколько ждать ответа одной проверки
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Аргументы команды `you doctor`
+#[derive(Args)]
+pub struct DoctorArgs {}
+
+impl DoctorArgs {
+    pub async fn execute(self) -> Result<()> {
+        let (proxy, tracker, openrouter) =
+            tokio::join!(check_proxy(), tracker_check(), openrouter_check());
+        let checks = [proxy, tracker, openrouter, check_calendar()];
+        for check in &checks {
+            println!("{}", check.render());
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|check| check.status == Status::Fail)
+            .count();
+        if failed > 0 {
+            bail!("Проверок с ошибками: {failed}");
+        }
+        println!("\nВсё в порядке");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    /// Необязательная настройка отсутствует или требует внимания
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    latency: Option<Duration>,
+    /// Что сделать, чтобы исправить проблему
+    remedy: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            latency: None,
+            remedy: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, remedy: impl Into<String>) -> Self {
+        Self {
+            status: Status::Warn,
+            remedy: Some(remedy.into()),
+            ..Self::ok(name, detail)
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, remedy: impl Into<String>) -> Self {
+        Self {
+            status: Status::Fail,
+            remedy: Some(remedy.into()),
+            ..Self::ok(name, detail)
+        }
+    }
+
+    fn latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    fn render(&self) -> String {
+        let mark = match self.status {
+            Status::Ok => "✓",
+            Status::Warn => "!",
+            Status::Fail => "✗",
+        };
+        let mut line = format!("{mark} {:<11} {}", self.name, self.detail);
+        if let Some(latency) = self.latency {
+            line.push_str(&format!(" · {} мс", latency.as_millis()));
+        }
+        if let Some(remedy) = &self.remedy {
+            line.push_str(&format!("\n    → {remedy}"));
+        }
+        line
+    }
+}
+
+/// Выполнить запрос проверки с таймаутом и замером времени
+async fn timed<T>(request: impl Future<Output = T>) -> Option<(T, Duration)> {
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, request).await.ok()?;
+    Some((result, started.elapsed()))
+}
+
+fn timeout_check(name: &'static str, service: &str) -> Check {
+    Check::fail(
+        name,
+        format!("нет ответа за {} с", CHECK_TIMEOUT.as_secs()),
+        format!("Проверьте сеть и {WORK_PROXY}: {service} не отвечает"),
+    )
+}
+
+/// Прокси: формат значения и доступность адреса
+#[instrument]
+async fn check_proxy() -> Check {
+    const NAME: &str = "Прокси";
+    let Ok(value) = std::env::var(WORK_PROXY) else {
+        return Check::ok(NAME, "не задан, запросы идут напрямую");
+    };
+    if let Err(err) = proxy::parse_work_proxy_url(&value) {
+        return Check::fail(
+            NAME,
+            err.to_string(),
+            format!("Укажите {WORK_PROXY} в формате host:port, например proxy.example.com:1080"),
+        );
+    }
+
+    let address = value.trim();
+    match timed(tokio::net::TcpStream::connect(address)).await {
+        Some((Ok(_), latency)) => Check::ok(NAME, format!("{address} доступен")).latency(latency),
+        Some((Err(err), _)) => Check::fail(
+            NAME,
+            format!("{address} недоступен: {err}"),
+            "Проверьте адрес и порт прокси и подключение к рабочей сети (VPN)",
+        ),
+        None => timeout_check(NAME, "прокси"),
+    }
+}
+
+async fn tracker_check() -> Check {
+    match settings::tracker_client() {
+        Ok(client) => check_tracker(&client).await,
+        Err(err) => Check::fail(
+            "Трекер",
+            err.to_string(),
+            format!(
+                "Задайте TRACKER_TOKEN и {ORG_ID_VAR} (тип организации — {ORG_KIND_VAR}: \
+                 360 или cloud) или сохраните токен на экране настройки TUI"
+            ),
+        ),
+    }
+}
+
+/// Трекер: токен и организация проверяются запросом текущего пользователя
+#[instrument(skip_all)]
+async fn check_tracker(client: &TrackerClient) -> Check {
+    const NAME: &str = "Трекер";
+    let Some((result, latency)) = timed(client.get_myself()).await else {
+        return timeout_check(NAME, "API Трекера");
+    };
+    match result {
+        Ok(user) => {
+            let login = user.login.as_deref().unwrap_or("?");
+            let detail = match user.display.as_deref() {
+                Some(display) => format!("вход как {login} ({display})"),
+                None => format!("вход как {login}"),
+            };
+            Check::ok(NAME, detail).latency(latency)
+        }
+        Err(err) => Check::fail(NAME, err.to_string(), tracker_remedy(&err)).latency(latency),
+    }
+}
+
+fn tracker_remedy(err: &TrackerError) -> String {
+    match err {
+        TrackerError::Unauthorized | TrackerError::AuthError(_) => {
+            "Токен TRACKER_TOKEN недействителен или истёк: получите новый OAuth-токен \
+             и обновите переменную или keyring"
+                .to_string()
+        }
+        TrackerError::Forbidden => format!(
+            "Нет доступа к организации: проверьте {ORG_ID_VAR} и {ORG_KIND_VAR} \
+             (360 или cloud)"
+        ),
+        TrackerError::RequestFailed(_) => {
+            format!("Нет связи с API Трекера: проверьте сеть, {WORK_PROXY} и TRACKER_API_URL")
+        }
+        _ => "Проверьте TRACKER_API_URL и статус сервиса Трекера".to_string(),
+    }
+}
+
+async fn openrouter_check() -> Check {
+    let client = LlmConfig::new(DEFAULT_MODEL).and_then(LlmClient::new);
+    match client {
+        Ok(client) => check_openrouter(&client).await,
+        Err(err) => Check::fail(
+            "OpenRouter",
+            err.to_string(),
+            "Задайте OPEN_ROUTER_TOKEN (ключ на https://openrouter.ai/keys) \
+             или сохраните его на экране настройки TUI",
+        ),
+    }
+}
+
+/// OpenRouter: ключ проверяется запросом сведений о нём, без обращения к модели
+#[instrument(skip_all)]
+async fn check_openrouter(client: &LlmClient) -> Check {
+    const NAME: &str = "OpenRouter";
+    let Some((result, latency)) = timed(client.key_info()).await else {
+        return timeout_check(NAME, "OpenRouter");
+    };
+    let check = match result {
+        Ok(key) => {
+            let label = key.label.as_deref().unwrap_or("без названия");
+            match key.limit {
+                Some(limit) if key.usage >= limit => Check::warn(
+                    NAME,
+                    format!("ключ {label}: лимит ${limit:.2} исчерпан"),
+                    "Поднимите лимит ключа или пополните баланс на https://openrouter.ai",
+                ),
+                Some(limit) => Check::ok(
+                    NAME,
+                    format!("ключ {label}: потрачено ${:.2} из ${limit:.2}", key.usage),
+                ),
+                None => Check::ok(NAME, format!("ключ {label}: потрачено ${:.2}", key.usage)),
+            }
+        }
+        Err(err) => {
+            let remedy = match &err {
+                LlmError::AuthError => {
+                    "Ключ OPEN_ROUTER_TOKEN отклонён: создайте новый на https://openrouter.ai/keys"
+                        .to_string()
+                }
+                LlmError::RateLimitExceeded { .. } => {
+                    "Слишком много запросов: повторите проверку через минуту".to_string()
+                }
+                LlmError::RequestFailed(_) => {
+                    format!("Нет связи с openrouter.ai: проверьте сеть и {WORK_PROXY}")
+                }
+                _ => "Проверьте статус OpenRouter на https://status.openrouter.ai".to_string(),
+            };
+            Check::fail(NAME, err.to_string(), remedy)
+        }
+    };
+    check.latency(latency)
+}
+
+/// Календарь: файл `CALENDAR_ICS` читается и разбирается
+#[instrument]
+fn check_calendar() -> Check {
+    const NAME: &str = "Календарь";
+    let Some(path) = core_lib::credentials::resolve("CALENDAR_ICS") else {
+        return Check::warn(
+            NAME,
+            "не настроен",
+            "Чтобы видеть встречи в TUI и `llm plan-day`, укажите CALENDAR_ICS=/path/to/calendar.ics",
+        );
+    };
+
+    let started = Instant::now();
+    let zone = timezone::current();
+    match calendar_lib::load_events_in(Path::new(&path), zone) {
+        Ok(events) => {
+            let today = calendar_lib::events_on(&events, zone.today()).len();
+            Check::ok(
+                NAME,
+                format!(
+                    "{path}: событий {}, сегодня {today} (пояс {zone})",
+                    events.len()
+                ),
+            )
+            .latency(started.elapsed())
+        }
+        Err(err) => Check::fail(
+            NAME,
+            format!("{path}: {err}"),
+            "Проверьте путь в CALENDAR_ICS и что файл — экспорт календаря в формате iCalendar",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracker_lib::TrackerConfig;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[test]
+    fn test_render_shows_latency_and_remedy() {
+        let ok = Check::ok("Трекер", "вход как jdoe").latency(Duration::from_millis(120));
+        assert_eq!(ok.render(), "✓ Трекер      вход как jdoe · 120 мс");
+
+        let fail = Check::fail("OpenRouter", "ключ отклонён", "Создайте новый ключ");
+        assert_eq!(
+            fail.render(),
+            "✗ OpenRouter  ключ отклонён\n    → Создайте новый ключ"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_tracker_explains_rejected_token() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/myself"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+        let client = TrackerClient::new(
+            TrackerConfig::new("expired-token").with_base_url(mock_server.uri()),
+        )
+        .unwrap();
+
+        let check = check_tracker(&client).await;
+        assert_eq!(check.status, Status::Fail);
+        assert!(check.latency.is_some());
+        assert!(check.remedy.unwrap().contains("TRACKER_TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_check_openrouter_reports_key_usage() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"label": "work", "usage": 2.5, "limit": 10.0}
+            })))
+            .mount(&mock_server)
+            .await;
+        let client = LlmClient::new(LlmConfig {
+            api_key: "test-api-key".to_string(),
+            base_url: mock_server.uri(),
+            model: DEFAULT_MODEL.to_string(),
+            timeout_secs: 30,
+            site_url: None,
+            app_name: None,
+            user_agent: "cli-tests".to_string(),
+            fallback_keys: Vec::new(),
+        })
+        .unwrap();
+
+        let check = check_openrouter(&client).await;
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(check.detail, "ключ work: потрачено $2.50 из $10.00");
+    }
+}
@@ -0,0 +1,258 @@
+//! Черновики комментариев и запросов к LLM
+//!
+//! TUI сохраняет текст редактора комментария и строку ввода экрана LLM после каждого
+//! изменения, чтобы он пережил падение или случайный выход. Черновик лежит в
+//! `$XDG_STATE_HOME/you/drafts/<имя>.md` и удаляется после отправки; при следующем
+//! открытии редактора текст восстанавливается. `you drafts` показывает и удаляет
+//! оставшиеся черновики.
+
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use clap::Subcommand;
+use tracing::{debug, instrument};
+
+use crate::paths;
+
+/// Черновик запроса на экране LLM
+pub const LLM_CHAT: &str = "llm-chat";
+
+const EXTENSION: &str = "md";
+
+/// Сколько символов черновика показывать в списке
+const PREVIEW_CHARS: usize = 60;
+
+/// Имя черновика комментария к задаче
+pub fn comment(issue_key: &str) -> String {
+    format!("comment-{issue_key}")
+}
+
+/// Сохранённый черновик
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draft {
+    pub name: String,
+    pub text: String,
+    pub modified: SystemTime,
+}
+
+impl Draft {
+    /// Строка для списка: имя, время изменения и начало текста
+    pub fn line(&self) -> String {
+        let modified: DateTime<Local> = self.modified.into();
+        let preview: String = self
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+        format!(
+            "{:<20} {}  {preview}",
+            self.name,
+            modified.format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+/// Каталог черновиков
+#[derive(Debug, Clone)]
+pub struct Drafts {
+    dir: PathBuf,
+}
+
+impl Drafts {
+    /// Черновики в каталоге состояния
+    pub fn open_default() -> Result<Self> {
+        let dir = paths::state_dir().context("Не удалось определить каталог для черновиков")?;
+        Ok(Self::new(dir.join("drafts")))
+    }
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            bail!("Некорректное имя черновика «{name}»");
+        }
+        Ok(self.dir.join(format!("{name}.{EXTENSION}")))
+    }
+
+    /// Сохраняет черновик; пустой текст удаляет его
+    ///
+    /// Файл сначала пишется рядом и затем переименовывается, чтобы падение посреди
+    /// записи не испортило прошлую версию.
+    pub fn save(&self, name: &str, text: &str) -> Result<()> {
+        if text.trim().is_empty() {
+            self.remove(name)?;
+            return Ok(());
+        }
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Не удалось создать каталог {}", self.dir.display()))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, text)
+            .with_context(|| format!("Не удалось записать {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn load(&self, name: &str) -> Option<String> {
+        let text = std::fs::read_to_string(self.path(name).ok()?).ok()?;
+        (!text.trim().is_empty()).then_some(text)
+    }
+
+    /// Удаляет черновик; false — его не было
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        let path = self.path(name)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                debug!(name, "Черновик удалён");
+                Ok(true)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err).with_context(|| format!("Не удалось удалить {}", path.display())),
+        }
+    }
+
+    /// Все черновики, свежие сверху
+    pub fn list(&self) -> Result<Vec<Draft>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Не удалось прочитать {}", self.dir.display()))
+            }
+        };
+
+        let mut drafts = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            drafts.push(read_draft(name, &path)?);
+        }
+        drafts.sort_by_key(|draft| std::cmp::Reverse(draft.modified));
+        Ok(drafts)
+    }
+}
+
+fn read_draft(name: &str, path: &Path) -> Result<Draft> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+    let modified = std::fs::metadata(path)?.modified()?;
+    Ok(Draft {
+        name: name.to_string(),
+        text,
+        modified,
+    })
+}
+
+/// Команды черновиков
+#[derive(Subcommand)]
+pub enum DraftCommands {
+    /// Показать сохранённые черновики
+    List,
+    /// Вывести текст черновика
+    Show {
+        /// Имя черновика (например, comment-TREK-123)
+        name: String,
+    },
+    /// Удалить черновик
+    Delete {
+        /// Имя черновика
+        name: String,
+    },
+    /// Удалить все черновики
+    Clear,
+}
+
+impl DraftCommands {
+    /// Выполняет команду черновиков
+    #[instrument(skip(self))]
+    pub fn execute(&self) -> Result<()> {
+        let drafts = Drafts::open_default()?;
+        match self {
+            DraftCommands::List => {
+                let list = drafts.list()?;
+                if list.is_empty() {
+                    println!("Черновиков нет");
+                }
+                for draft in list {
+                    println!("{}", draft.line());
+                }
+            }
+            DraftCommands::Show { name } => match drafts.load(name) {
+                Some(text) => println!("{text}"),
+                None => bail!("Черновик «{name}» не найден"),
+            },
+            DraftCommands::Delete { name } => {
+                if !drafts.remove(name)? {
+                    bail!("Черновик «{name}» не найден");
+                }
+                println!("Черновик {name} удалён");
+            }
+            DraftCommands::Clear => {
+                let list = drafts.list()?;
+                for draft in &list {
+                    drafts.remove(&draft.name)?;
+                }
+                println!("Удалено черновиков: {}", list.len());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_load_and_remove_draft() {
+        let dir = std::env::temp_dir().join(format!("you-drafts-{}", std::process::id()));
+        let drafts = Drafts::new(dir.clone());
+        let name = comment("TREK-1");
+
+        drafts
+            .save(&name, "Проверил на стенде,\nвсё работает")
+            .unwrap();
+        drafts.save(LLM_CHAT, "summarize this").unwrap();
+        assert_eq!(
+            drafts.load(&name).as_deref(),
+            Some("Проверил на стенде,\nвсё работает")
+        );
+        let names: Vec<String> = drafts
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|draft| draft.name)
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"comment-TREK-1".to_string()));
+
+        // Пустой текст — то же, что удаление
+        drafts.save(&name, "  ").unwrap();
+        assert!(drafts.load(&name).is_none());
+        assert!(drafts.remove(LLM_CHAT).unwrap());
+        assert!(!drafts.remove(LLM_CHAT).unwrap());
+        assert!(drafts.save("../escape", "text").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+//! Задача из письма: `you tracker from-email --file message.eml`
+//!
+//! Тема письма становится названием задачи, текст — описанием (с отправителем и датой),
+//! вложения загружаются в созданную задачу. Message-ID письма передаётся в поле `unique`,
+//! поэтому повторный импорт того же письма не создаст вторую задачу. С `--llm` модель
+//! предлагает приоритет и исполнителя; исполнитель ищется в справочнике пользователей.
+
+use anyhow::{Context, Result};
+use llm_lib::LlmClientTrait;
+use mail_parser::{MessageParser, MimeHeaders};
+use serde::Deserialize;
+use tracing::{debug, instrument};
+
+use crate::llm::extract_json_object;
+use crate::redact;
+use crate::triage::PRIORITIES;
+
+/// Префиксы ответов и пересылок, которые убираются из темы
+const SUBJECT_PREFIXES: &[&str] = &["re:", "fw:", "fwd:", "ответ:", "пересылка:"];
+
+/// Сколько символов письма передавать LLM
+const LLM_BODY_LIMIT: usize = 4000;
+
+const SYSTEM_PROMPT: &str = "Ты разбираешь письмо, из которого создаётся задача в трекере. \
+Ответь только JSON объектом вида {\"priority\": \"...\", \"assignee\": \"...\", \"reason\": \"...\"} \
+без пояснений. assignee — логин или имя человека, которого письмо просит выполнить работу, \
+или null, если такого нет.";
+
+/// Вложение письма
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAttachment {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Разобранное письмо
+#[derive(Debug, Clone, PartialEq)]
+pub struct Email {
+    pub subject: String,
+    /// Отправитель в виде `Имя <адрес>`
+    pub from: Option<String>,
+    /// Дата отправки в RFC 3339
+    pub date: Option<String>,
+    pub message_id: Option<String>,
+    pub body: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+impl Email {
+    /// Разбирает письмо в формате RFC 5322 (файл .eml)
+    pub fn parse(raw: &[u8]) -> Result<Self> {
+        let message = MessageParser::default()
+            .parse(raw)
+            .context("Не удалось разобрать письмо")?;
+        let from = message.from().and_then(|from| from.first()).map(|addr| {
+            match (addr.name(), addr.address()) {
+                (Some(name), Some(address)) => format!("{name} <{address}>"),
+                (name, address) => name.or(address).unwrap_or_default().to_string(),
+            }
+        });
+        let attachments = message
+            .attachments()
+            .enumerate()
+            .map(|(index, part)| EmailAttachment {
+                name: part
+                    .attachment_name()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("attachment-{}", index + 1)),
+                data: part.contents().to_vec(),
+            })
+            .collect();
+        Ok(Self {
+            subject: message.subject().unwrap_or_default().to_string(),
+            from,
+            date: message.date().map(|date| date.to_rfc3339()),
+            message_id: message.message_id().map(str::to_string),
+            body: message
+                .body_text(0)
+                .map(|body| body.trim().to_string())
+                .unwrap_or_default(),
+            attachments,
+        })
+    }
+
+    /// Название задачи: тема без `Re:` и `Fwd:`
+    pub fn summary(&self) -> String {
+        let mut subject = self.subject.trim();
+        while let Some(prefix) = SUBJECT_PREFIXES
+            .iter()
+            .find(|prefix| starts_with_ignore_case(subject, prefix))
+        {
+            subject = subject[prefix.len()..].trim_start();
+        }
+        if subject.is_empty() {
+            "Письмо без темы".to_string()
+        } else {
+            subject.to_string()
+        }
+    }
+
+    /// Описание задачи: отправитель, дата и текст письма
+    pub fn description(&self) -> String {
+        let mut header = Vec::new();
+        if let Some(from) = &self.from {
+            header.push(format!("От: {from}"));
+        }
+        if let Some(date) = &self.date {
+            header.push(format!("Дата: {date}"));
+        }
+        if header.is_empty() {
+            return self.body.clone();
+        }
+        format!("{}\n\n{}", header.join("\n"), self.body)
+    }
+
+    /// Значение поля `unique` задачи: повторное создание из того же письма отклонит Трекер
+    pub fn unique(&self) -> Option<String> {
+        self.message_id.as_ref().map(|id| format!("email:{id}"))
+    }
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|start| start.to_lowercase() == *prefix)
+}
+
+/// Приоритет и исполнитель, предложенные LLM
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EmailSuggestion {
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Просит LLM предложить приоритет и исполнителя по тексту письма
+#[instrument(skip_all)]
+pub async fn suggest<T: LlmClientTrait>(llm: &T, email: &Email) -> Result<EmailSuggestion> {
+    let body: String = email.body.chars().take(LLM_BODY_LIMIT).collect();
+    let prompt = format!(
+        "Тема: {}\nОт: {}\n\n{}\n\nДопустимые приоритеты: {}",
+        email.summary(),
+        email.from.as_deref().unwrap_or("неизвестно"),
+        body,
+        PRIORITIES.join(", "),
+    );
+    let prompt = redact::text("email", &prompt);
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(response = %response, "Ответ LLM получен");
+    parse_suggestion(&response)
+}
+
+/// Извлекает JSON объект из ответа LLM; неизвестный приоритет и пустой исполнитель отбрасываются
+pub fn parse_suggestion(response: &str) -> Result<EmailSuggestion> {
+    let mut suggestion: EmailSuggestion = serde_json::from_str(extract_json_object(response)?)
+        .context("Не удалось разобрать предложение из ответа LLM")?;
+    suggestion.priority = suggestion
+        .priority
+        .filter(|priority| PRIORITIES.contains(&priority.as_str()));
+    suggestion.assignee = suggestion
+        .assignee
+        .map(|assignee| assignee.trim().to_string())
+        .filter(|assignee| !assignee.is_empty());
+    Ok(suggestion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "From: Анна Иванова <anna@example.com>\r\n\
+Subject: =?UTF-8?B?UmU6IEZ3ZDog0J7RiNC40LHQutCwINCyINC+0YLRh9GR0YLQtQ==?=\r\n\
+Date: Fri, 16 Oct 2026 09:30:00 +0300\r\n\
+Message-ID: <42@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+Отчёт падает при выгрузке.\r\n\
+--b1\r\n\
+Content-Type: text/plain; name=\"log.txt\"\r\n\
+Content-Disposition: attachment; filename=\"log.txt\"\r\n\
+\r\n\
+panic at line 42\r\n\
+--b1--\r\n";
+
+    #[test]
+    fn test_parse_email_fields_and_attachments() {
+        let email = Email::parse(MESSAGE.as_bytes()).unwrap();
+
+        assert_eq!(email.summary(), "Ошибка в отчёте");
+        assert_eq!(email.unique().as_deref(), Some("email:42@example.com"));
+        assert_eq!(
+            email.description(),
+            "От: Анна Иванова <anna@example.com>\nДата: 2026-10-16T09:30:00+03:00\n\n\
+             Отчёт падает при выгрузке."
+        );
+        assert_eq!(email.attachments.len(), 1);
+        assert_eq!(email.attachments[0].name, "log.txt");
+        assert_eq!(email.attachments[0].data, b"panic at line 42");
+    }
+
+    #[test]
+    fn test_parse_suggestion_drops_unknown_values() {
+        let suggestion = parse_suggestion(
+            "```json\n{\"priority\": \"urgent\", \"assignee\": \" \", \"reason\": \"клиент\"}\n```",
+        )
+        .unwrap();
+        assert_eq!(suggestion.priority, None);
+        assert_eq!(suggestion.assignee, None);
+
+        let suggestion =
+            parse_suggestion("{\"priority\": \"critical\", \"assignee\": \"Иван Петров\"}")
+                .unwrap();
+        assert_eq!(suggestion.priority.as_deref(), Some("critical"));
+        assert_eq!(suggestion.assignee.as_deref(), Some("Иван Петров"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Оценка трудозатрат задачи с помощью LLM
+//!
+//! Для задачи ищутся похожие закрытые задачи в локальном индексе (по эмбеддингам),
+//! к ним подтягиваются фактическое время до закрытия и списанное время, и LLM
+//! предлагает оценку с обоснованием. Оценка записывается в поле «Оценка» задачи.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use llm_lib::{LlmClient, LlmClientTrait};
+use serde::Deserialize;
+use tracing::{debug, info, instrument};
+use tracker_lib::{
+    ids::IssueKey,
+    models::{Issue, TRACKER_DATE_FORMAT},
+    task::UpdateIssueRequest,
+    worklog::{format_duration, parse_duration},
+    TrackerClient,
+};
+
+use crate::index::{self, IssueIndex};
+use crate::llm::extract_json_object;
+use crate::redact;
+
+/// Сколько задач индекса просматривать в поисках закрытых похожих
+const CANDIDATE_FACTOR: usize = 4;
+
+const SYSTEM_PROMPT: &str = "Ты помогаешь оценивать трудозатраты задач по истории похожих. \
+Ответь только JSON объектом вида {\"hours\": 6, \"rationale\": \"...\"} без пояснений.";
+
+/// Похожая закрытая задача с фактическими затратами
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarIssue {
+    pub key: String,
+    pub summary: String,
+    /// Близость к оцениваемой задаче, от 0 до 1
+    pub similarity: f64,
+    /// Дней от создания до резолюции
+    pub days_to_close: Option<f64>,
+    /// Сумма списанного времени
+    pub spent: Duration,
+}
+
+impl SimilarIssue {
+    fn line(&self) -> String {
+        let days = self
+            .days_to_close
+            .map(|days| format!("{days:.1} дн."))
+            .unwrap_or_else(|| "неизвестно".to_string());
+        let spent = if self.spent.is_zero() {
+            "не списано".to_string()
+        } else {
+            format!("{:.1} ч", self.spent.as_secs_f64() / 3600.0)
+        };
+        format!(
+            "{} {} — закрыта за {days}, списано {spent}",
+            self.key, self.summary
+        )
+    }
+}
+
+/// Оценка, предложенная LLM
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Estimate {
+    pub hours: f64,
+    #[serde(default)]
+    pub rationale: String,
+}
+
+impl Estimate {
+    /// Оценка в формате поля `estimation` Трекера
+    pub fn iso_duration(&self) -> String {
+        format_duration(Duration::from_secs_f64(self.hours.max(0.0) * 3600.0))
+    }
+}
+
+/// Дней от создания задачи до резолюции
+pub fn days_to_close(issue: &Issue) -> Option<f64> {
+    let created =
+        DateTime::parse_from_str(issue.created_at.as_deref()?, TRACKER_DATE_FORMAT).ok()?;
+    let resolved =
+        DateTime::parse_from_str(issue.resolved_at.as_deref()?, TRACKER_DATE_FORMAT).ok()?;
+    Some((resolved - created).num_minutes() as f64 / (24.0 * 60.0))
+}
+
+/// Похожие закрытые задачи из индекса с их фактическими затратами
+#[instrument(skip(client, index, llm, issue), fields(issue_key = %issue.key))]
+pub async fn find_similar_closed(
+    client: &TrackerClient,
+    index: &IssueIndex,
+    llm: &LlmClient,
+    issue: &Issue,
+    limit: usize,
+) -> Result<Vec<SimilarIssue>> {
+    index::embed_missing(index, llm).await?;
+    let query = llm
+        .embeddings(vec![index::embedding_text(
+            &issue.summary,
+            issue.description.as_deref(),
+        )])
+        .await?
+        .into_iter()
+        .next()
+        .context("Сервис эмбеддингов вернул пустой ответ")?;
+
+    let mut similar = Vec::new();
+    for hit in index.search_semantic(&query, limit * CANDIDATE_FACTOR)? {
+        if similar.len() == limit {
+            break;
+        }
+        if hit.key == issue.key {
+            continue;
+        }
+        let candidate = client.get_issue(&hit.key.parse()?, None).await?;
+        if candidate.resolved_at.is_none() {
+            continue;
+        }
+        let spent = client
+            .get_worklogs(&hit.key.parse()?)
+            .await?
+            .iter()
+            .filter_map(|worklog| worklog.duration.as_deref().and_then(parse_duration))
+            .sum();
+        similar.push(SimilarIssue {
+            key: candidate.key.clone(),
+            summary: candidate.summary.clone(),
+            similarity: hit.score,
+            days_to_close: days_to_close(&candidate),
+            spent,
+        });
+    }
+    info!(count = similar.len(), "Похожие закрытые задачи найдены");
+    Ok(similar)
+}
+
+/// Запрашивает у LLM оценку задачи по истории похожих
+#[instrument(skip(llm, issue, similar), fields(issue_key = %issue.key))]
+pub async fn estimate<T: LlmClientTrait>(
+    llm: &T,
+    issue: &Issue,
+    similar: &[SimilarIssue],
+) -> Result<Estimate> {
+    let issue = redact::issue(issue);
+    let history = if similar.is_empty() {
+        "(похожих закрытых задач не найдено)".to_string()
+    } else {
+        similar
+            .iter()
+            .map(|similar| format!("- {}", redact::text(&similar.key, &similar.line())))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let prompt = format!(
+        "Задача {}: {}\n\n{}\n\nПохожие закрытые задачи:\n{history}\n\nОцени трудозатраты в часах.",
+        issue.key,
+        issue.summary,
+        issue.description.as_deref().unwrap_or("(без описания)"),
+    );
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(response = %response, "Ответ LLM получен");
+    parse_estimate(&response)
+}
+
+/// Извлекает JSON объект из ответа LLM, даже если он обёрнут в текст или блок кода
+pub fn parse_estimate(response: &str) -> Result<Estimate> {
+    serde_json::from_str(extract_json_object(response)?)
+        .context("Не удалось разобрать оценку из ответа LLM")
+}
+
+/// Записывает оценку в задачу с учётом известной версии
+#[instrument(skip(client, issue, estimate), fields(issue_key = %issue.key))]
+pub async fn apply(client: &TrackerClient, issue: &Issue, estimate: &Estimate) -> Result<Issue> {
+    let request = UpdateIssueRequest::new().set("estimation", estimate.iso_duration());
+    let updated = client
+        .update_issue(&issue.issue_key()?, &request, issue.version)
+        .await?;
+    info!(estimation = %estimate.iso_duration(), "Оценка записана в задачу");
+    Ok(updated)
+}
+
+/// Полный сценарий `you llm estimate`: поиск истории, оценка и запись после подтверждения
+pub async fn run(
+    client: &TrackerClient,
+    llm: &LlmClient,
+    embeddings: &LlmClient,
+    issue_key: &IssueKey,
+    limit: usize,
+    yes: bool,
+) -> Result<()> {
+    let issue = client.get_issue(issue_key, None).await?;
+    let index = IssueIndex::open_default()?;
+    index::sync(&index, client, false).await?;
+
+    let similar = find_similar_closed(client, &index, embeddings, &issue, limit).await?;
+    if similar.is_empty() {
+        println!("Похожих закрытых задач в индексе не найдено, оценка только по описанию");
+    } else {
+        println!("Похожие закрытые задачи:");
+        for item in &similar {
+            println!("  {} ({:.0}%)", item.line(), item.similarity * 100.0);
+        }
+    }
+
+    let estimate = estimate(llm, &issue, &similar).await?;
+    println!(
+        "\nОценка {}: {} ({:.1} ч)\n{}",
+        issue.key,
+        estimate.iso_duration(),
+        estimate.hours,
+        estimate.rationale
+    );
+
+    if yes || crate::tracker::confirm("\nЗаписать оценку в задачу?") {
+        apply(client, &issue, &estimate).await?;
+        println!("Оценка записана в {}", issue.key);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use llm_lib::MockLlmClientTrait;
+
+    fn issue() -> Issue {
+        testing::issue("TREK-1")
+            .summary("Экспорт в CSV")
+            .field("createdAt", "2024-05-01T10:00:00.000+0000")
+            .field("resolvedAt", "2024-05-03T22:00:00.000+0000")
+            .build()
+    }
+
+    #[test]
+    fn test_days_to_close() {
+        assert_eq!(days_to_close(&issue()), Some(2.5));
+
+        let open = testing::issue("TREK-2").summary("Открытая").build();
+        assert_eq!(days_to_close(&open), None);
+    }
+
+    #[test]
+    fn test_parse_estimate_and_iso_duration() {
+        let estimate =
+            parse_estimate("```json\n{\"hours\": 6.5, \"rationale\": \"как TREK-7\"}\n```")
+                .unwrap();
+        assert_eq!(estimate.rationale, "как TREK-7");
+        assert_eq!(estimate.iso_duration(), "PT6H30M");
+        assert!(parse_estimate("не знаю").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_sends_history_to_llm() {
+        let similar = vec![SimilarIssue {
+            key: "TREK-7".to_string(),
+            summary: "Экспорт в XLSX".to_string(),
+            similarity: 0.9,
+            days_to_close: Some(2.0),
+            spent: Duration::from_secs(5 * 3600),
+        }];
+
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_complete_with_system()
+            .times(1)
+            .returning(|_, prompt| {
+                assert!(
+                    prompt.contains("TREK-7 Экспорт в XLSX — закрыта за 2.0 дн., списано 5.0 ч")
+                );
+                Box::pin(async { Ok("{\"hours\": 4, \"rationale\": \"проще\"}".to_string()) })
+            });
+
+        let estimate = estimate(&mock_client, &issue(), &similar).await.unwrap();
+        assert_eq!(estimate.hours, 4.0);
+        assert_eq!(estimate.iso_duration(), "PT4H");
+    }
+}
@@ -0,0 +1,410 @@
+//! Проверка пресетов промптов на наборе примеров
+//!
+//! `you llm eval --suite eval.yaml` подставляет входные данные каждого примера в пресет
+//! из библиотеки (или в промпт `plan-day` / `summarize-search`, как их отправляют сами
+//! команды), получает ответ модели и оценивает его проверками: регулярным выражением,
+//! разбором JSON или вердиктом модели-судьи. Итог сравнивается с эталоном рядом с
+//! набором (`eval.baseline.json`): пример, который проходил в эталоне, а теперь нет, —
+//! регрессия, и команда завершается ошибкой. `--update-baseline` сохраняет текущий итог
+//! как новый эталон.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use llm_lib::{LlmClient, LlmClientTrait, LlmConfig};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::{
+    config::Config,
+    llm::DEFAULT_MODEL,
+    personas, plan,
+    prompts::{self, PromptPreset},
+    summarize,
+};
+
+/// Системный промпт модели-судьи
+const JUDGE_PROMPT: &str = "Ты строго проверяешь ответы языковой модели. Тебе дают критерий \
+и ответ. Первой строкой напиши PASS, если ответ полностью соответствует критерию, иначе FAIL; \
+второй строкой — одно предложение о причине.";
+
+/// Набор примеров из YAML файла
+#[derive(Debug, Deserialize)]
+pub struct Suite {
+    /// Модель по умолчанию для примеров
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Модель для проверок `judge`
+    #[serde(default)]
+    pub judge_model: Option<String>,
+
+    pub cases: Vec<Case>,
+}
+
+/// Пример: пресет, входные данные и проверки ответа
+#[derive(Debug, Deserialize)]
+pub struct Case {
+    pub name: String,
+
+    /// Пресет из библиотеки, `plan-day` или `summarize-search`
+    pub preset: String,
+
+    /// Переменные пресета; `@путь` читается из файла относительно набора
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+
+    /// Персона; у `plan-day` по умолчанию `project-manager`
+    #[serde(default)]
+    pub persona: Option<String>,
+
+    #[serde(default)]
+    pub model: Option<String>,
+
+    pub checks: Vec<Check>,
+}
+
+/// Проверка ответа
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Check {
+    /// Ответ содержит совпадение с регулярным выражением
+    Regex { pattern: String },
+    /// Ответ (можно в блоке ```json) — корректный JSON
+    Json,
+    /// Модель-судья считает, что ответ соответствует критерию
+    Judge { criterion: String },
+}
+
+/// Итог примера
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseResult {
+    pub name: String,
+    /// Непройденные проверки; пусто — пример прошёл
+    pub failures: Vec<String>,
+}
+
+impl CaseResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Эталон: прошёл ли пример в прошлый раз
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Baseline {
+    pub cases: BTreeMap<String, bool>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Не удалось разобрать эталон {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Не удалось записать {}", path.display()))
+    }
+
+    pub fn from_results(results: &[CaseResult]) -> Self {
+        Self {
+            cases: results
+                .iter()
+                .map(|result| (result.name.clone(), result.passed()))
+                .collect(),
+        }
+    }
+
+    /// Примеры, которые проходили в эталоне и не проходят сейчас
+    pub fn regressions<'a>(&self, results: &'a [CaseResult]) -> Vec<&'a str> {
+        results
+            .iter()
+            .filter(|result| !result.passed() && self.cases.get(&result.name) == Some(&true))
+            .map(|result| result.name.as_str())
+            .collect()
+    }
+}
+
+/// Эталон по умолчанию: `eval.yaml` → `eval.baseline.json`
+pub fn baseline_path(suite: &Path) -> PathBuf {
+    suite.with_extension("baseline.json")
+}
+
+/// Пресет для примера и персона по умолчанию
+///
+/// `plan-day` и `summarize-search` собираются из тех же промптов, что отправляют команды.
+fn target(
+    name: &str,
+    library: &BTreeMap<String, PromptPreset>,
+) -> Result<(PromptPreset, Option<&'static str>)> {
+    match name {
+        "plan-day" => Ok((
+            PromptPreset {
+                prompt: format!("{}\n\n{{context}}", plan::DAY_PROMPT),
+                ..PromptPreset::default()
+            },
+            Some(personas::PROJECT_MANAGER),
+        )),
+        "summarize-search" => Ok((
+            PromptPreset {
+                system: Some(summarize::CHUNK_PROMPT.to_string()),
+                prompt: "{issues}".to_string(),
+                ..PromptPreset::default()
+            },
+            None,
+        )),
+        _ => match library.get(name) {
+            Some(preset) => Ok((preset.clone(), None)),
+            None => bail!("Пресет «{name}» не найден; список — `you llm prompts`"),
+        },
+    }
+}
+
+/// Переменные примера; `@путь` отсчитывается от каталога набора
+fn resolve_vars(vars: &HashMap<String, String>, dir: &Path) -> Result<HashMap<String, String>> {
+    vars.iter()
+        .map(|(name, value)| {
+            let value = match value.strip_prefix('@') {
+                Some(path) => {
+                    let path = dir.join(path);
+                    std::fs::read_to_string(&path).with_context(|| {
+                        format!("Не удалось прочитать {} для «{name}»", path.display())
+                    })?
+                }
+                None => value.clone(),
+            };
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Проверяет ответ; возвращает описания непройденных проверок
+#[instrument(skip_all, fields(checks = checks.len()))]
+pub async fn score<T: LlmClientTrait>(checks: &[Check], answer: &str, judge: &T) -> Vec<String> {
+    let mut failures = Vec::new();
+    for check in checks {
+        match check {
+            Check::Regex { pattern } => match Regex::new(pattern) {
+                Ok(regex) if regex.is_match(answer) => {}
+                Ok(_) => failures.push(format!("regex «{pattern}»: нет совпадения")),
+                Err(err) => failures.push(format!("regex «{pattern}»: {err}")),
+            },
+            Check::Json => {
+                if let Err(err) = serde_json::from_str::<serde_json::Value>(strip_fence(answer)) {
+                    failures.push(format!("json: {err}"));
+                }
+            }
+            Check::Judge { criterion } => {
+                let prompt = format!("Критерий: {criterion}\n\nОтвет:\n{answer}");
+                match judge
+                    .complete_with_system(JUDGE_PROMPT.to_string(), prompt)
+                    .await
+                {
+                    Ok(verdict) if verdict.trim_start().starts_with("PASS") => {}
+                    Ok(verdict) => failures.push(format!(
+                        "judge «{criterion}»: {}",
+                        verdict
+                            .trim()
+                            .trim_start_matches("FAIL")
+                            .trim()
+                            .replace('\n', " ")
+                    )),
+                    Err(err) => failures.push(format!("judge «{criterion}»: {err}")),
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// Содержимое блока кода, если ответ целиком в нём
+fn strip_fence(answer: &str) -> &str {
+    let trimmed = answer.trim();
+    trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(|body| body.split_once('\n').map_or(body, |(_, code)| code))
+        .unwrap_or(trimmed)
+}
+
+/// Выполняет набор и печатает итог по каждому примеру
+#[instrument]
+pub async fn run(suite_path: &Path, model: Option<&str>, update_baseline: bool) -> Result<()> {
+    let content = std::fs::read_to_string(suite_path)
+        .with_context(|| format!("Не удалось прочитать {}", suite_path.display()))?;
+    let suite: Suite = serde_norway::from_str(&content)
+        .with_context(|| format!("Не удалось разобрать {}", suite_path.display()))?;
+    let dir = suite_path.parent().unwrap_or(Path::new("."));
+    let library = prompts::library(&Config::load()?.prompts);
+    let judge = LlmClient::new(LlmConfig::new(
+        suite.judge_model.as_deref().unwrap_or(DEFAULT_MODEL),
+    )?)?;
+
+    let mut results = Vec::with_capacity(suite.cases.len());
+    for case in &suite.cases {
+        let failures = match answer(case, &suite, model, &library, dir).await {
+            Ok(answer) => score(&case.checks, &answer, &judge).await,
+            Err(err) => vec![format!("запрос: {err:#}")],
+        };
+        let result = CaseResult {
+            name: case.name.clone(),
+            failures,
+        };
+        if result.passed() {
+            println!("✓ {}", result.name);
+        } else {
+            println!("✗ {}", result.name);
+            for failure in &result.failures {
+                println!("    {failure}");
+            }
+        }
+        results.push(result);
+    }
+
+    let baseline_path = baseline_path(suite_path);
+    let baseline = Baseline::load(&baseline_path)?;
+    let regressions = baseline.regressions(&results);
+    let passed = results.iter().filter(|result| result.passed()).count();
+    println!("Прошло примеров: {passed} из {}", results.len());
+    info!(
+        passed,
+        total = results.len(),
+        regressions = regressions.len(),
+        "Набор выполнен"
+    );
+
+    if update_baseline {
+        Baseline::from_results(&results).save(&baseline_path)?;
+        println!("Эталон обновлён: {}", baseline_path.display());
+    }
+    if !regressions.is_empty() {
+        bail!("Регрессии относительно эталона: {}", regressions.join(", "));
+    }
+    Ok(())
+}
+
+/// Ответ модели на пример
+async fn answer(
+    case: &Case,
+    suite: &Suite,
+    model: Option<&str>,
+    library: &BTreeMap<String, PromptPreset>,
+    dir: &Path,
+) -> Result<String> {
+    let (preset, default_persona) = target(&case.preset, library)?;
+    let persona = case
+        .persona
+        .as_deref()
+        .or(default_persona)
+        .map(personas::load)
+        .transpose()?;
+    let mut rendered = preset.render(&resolve_vars(&case.vars, dir)?)?;
+    if let Some(persona) = &persona {
+        persona.apply(&mut rendered);
+    }
+    let model = model
+        .or(case.model.as_deref())
+        .or(suite.model.as_deref())
+        .or(preset.model.as_deref())
+        .or(persona
+            .as_ref()
+            .and_then(|persona| persona.model.as_deref()))
+        .unwrap_or(DEFAULT_MODEL);
+    let llm = LlmClient::new(LlmConfig::new(model)?)?;
+    rendered.send(&llm).await.inspect_err(|err| {
+        warn!(case = %case.name, error = %format!("{err:#}"), "Пример не выполнен");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use llm_lib::MockLlmClientTrait;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_score_runs_regex_json_and_judge_checks() {
+        let checks: Vec<Check> = serde_norway::from_str(
+            r#"
+- check: regex
+  pattern: '"priority":\s*"critical"'
+- check: json
+- check: judge
+  criterion: Тип задачи — bug
+"#,
+        )
+        .unwrap();
+        let mut judge = MockLlmClientTrait::new();
+        judge
+            .expect_complete_with_system()
+            .withf(|_, prompt| prompt.starts_with("Критерий: Тип задачи — bug"))
+            .times(2)
+            .returning(|_, prompt| {
+                let verdict = if prompt.contains("\"bug\"") {
+                    "PASS\nТип указан"
+                } else {
+                    "FAIL\nТип не bug"
+                };
+                Box::pin(async move { Ok(verdict.to_string()) })
+            });
+
+        let answer = "```json\n{\"type\": \"bug\", \"priority\": \"critical\"}\n```";
+        assert!(score(&checks, answer, &judge).await.is_empty());
+
+        let failures = score(&checks, "{\"type\": \"task\"", &judge).await;
+        assert_eq!(failures.len(), 3);
+        assert!(failures[0].starts_with("regex"));
+        assert!(failures[1].starts_with("json: "));
+        assert_eq!(failures[2], "judge «Тип задачи — bug»: Тип не bug");
+    }
+
+    #[test]
+    fn test_baseline_reports_only_new_failures() {
+        let result = |name: &str, passed: bool| CaseResult {
+            name: name.to_string(),
+            failures: if passed {
+                Vec::new()
+            } else {
+                vec!["json: EOF".to_string()]
+            },
+        };
+        let baseline = Baseline::from_results(&[
+            result("plan-day", true),
+            result("triage", false),
+            result("review", true),
+        ]);
+        let now = [
+            result("plan-day", false),
+            result("triage", false),
+            result("review", true),
+            result("new-case", false),
+        ];
+        assert_eq!(baseline.regressions(&now), ["plan-day"]);
+        assert_eq!(
+            baseline_path(Path::new("evals/eval.yaml")),
+            Path::new("evals/eval.baseline.json")
+        );
+    }
+
+    #[test]
+    fn test_targets_use_command_prompts() {
+        let library = prompts::library(&HashMap::new());
+        let (plan_day, persona) = target("plan-day", &library).unwrap();
+        assert!(plan_day.prompt.starts_with(plan::DAY_PROMPT));
+        assert_eq!(plan_day.variables(), ["context"]);
+        assert_eq!(persona, Some(personas::PROJECT_MANAGER));
+        assert!(target("review", &library).is_ok());
+        assert!(target("missing", &library).is_err());
+    }
+}
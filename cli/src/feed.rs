@@ -0,0 +1,149 @@
+//! Лента активности очереди для `you tracker feed`
+//!
+//! Каждая задача из [`TrackerClient::get_queue_activity`] выводится одной строкой с
+//! последним изменением; строки идут по времени, самые свежие — внизу.
+//!
+//! [`TrackerClient::get_queue_activity`]: tracker_lib::TrackerClient::get_queue_activity
+
+use chrono::{DateTime, FixedOffset, Local};
+use serde_json::Value;
+use tracker_lib::activity::QueueActivity;
+use tracker_lib::models::{ChangelogEntry, TRACKER_DATE_FORMAT};
+
+/// Строки ленты для изменений не раньше `since`, по возрастанию времени
+pub fn render(activity: &[QueueActivity], since: DateTime<FixedOffset>) -> Vec<String> {
+    let mut items: Vec<(DateTime<FixedOffset>, &QueueActivity)> = activity
+        .iter()
+        .filter_map(|item| {
+            let at = DateTime::parse_from_str(item.updated_at()?, TRACKER_DATE_FORMAT).ok()?;
+            (at >= since).then_some((at, item))
+        })
+        .collect();
+    items.sort_by_key(|(at, _)| *at);
+
+    items
+        .into_iter()
+        .map(|(at, item)| {
+            let change = item.latest_change.as_ref();
+            let author = change
+                .and_then(|change| change.updated_by.as_ref())
+                .and_then(|user| user.display.as_deref().or(user.login.as_deref()))
+                .unwrap_or("—");
+            format!(
+                "{}  {:<10} {author}: {} · {}",
+                at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                item.issue.key,
+                change.map_or_else(|| "обновлена".to_string(), describe),
+                item.issue.summary
+            )
+        })
+        .collect()
+}
+
+/// Что изменилось: поля со старым и новым значением или вид события
+fn describe(change: &ChangelogEntry) -> String {
+    if !change.fields.is_empty() {
+        return change
+            .fields
+            .iter()
+            .map(|field| {
+                let name = field
+                    .field
+                    .display
+                    .as_deref()
+                    .or(field.field.id.as_deref())
+                    .unwrap_or("поле");
+                format!(
+                    "{name}: {} → {}",
+                    display(field.from.as_ref()),
+                    display(field.to.as_ref())
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+    match change.change_type.as_deref() {
+        Some("IssueCreated") => "создал задачу".to_string(),
+        Some("IssueCommentAdded") => "оставил комментарий".to_string(),
+        Some("IssueAttachmentAdded") => "добавил вложение".to_string(),
+        Some("IssueLinked") => "связал задачу".to_string(),
+        Some(other) => other.to_string(),
+        None => "обновлена".to_string(),
+    }
+}
+
+/// Значение поля из истории: отображаемое имя объекта, строка или список
+fn display(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "—".to_string(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| display(Some(item)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(Value::Object(object)) => ["display", "key", "id"]
+            .iter()
+            .find_map(|name| object.get(*name).and_then(Value::as_str))
+            .unwrap_or("…")
+            .to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+    use serde_json::json;
+    use tracker_lib::models::Issue;
+
+    fn activity(issue: Issue, change: Option<Value>) -> QueueActivity {
+        QueueActivity {
+            issue,
+            latest_change: change.map(|change| serde_json::from_value(change).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_render_orders_by_time_and_skips_old() {
+        let since =
+            DateTime::parse_from_str("2026-10-15T12:00:00.000+0000", TRACKER_DATE_FORMAT).unwrap();
+        let items = [
+            activity(
+                issue("TREK-2").summary("Падает экспорт").build(),
+                Some(json!({
+                    "updatedAt": "2026-10-16T09:30:00.000+0000",
+                    "updatedBy": {"display": "Иван Петров"},
+                    "fields": [
+                        {
+                            "field": {"id": "status", "display": "Статус"},
+                            "from": {"key": "open", "display": "Открыт"},
+                            "to": {"key": "inProgress", "display": "В работе"}
+                        },
+                        {"field": {"id": "tags", "display": "Теги"}, "from": null, "to": ["ui", "p1"]}
+                    ]
+                })),
+            ),
+            activity(
+                issue("TREK-1")
+                    .summary("Новая задача")
+                    .field("updatedAt", "2026-10-15T18:00:00.000+0000")
+                    .build(),
+                None,
+            ),
+            activity(
+                issue("TREK-3").summary("Старая задача").build(),
+                Some(json!({"updatedAt": "2026-10-14T10:00:00.000+0000", "type": "IssueCreated"})),
+            ),
+        ];
+
+        let lines = render(&items, since);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("TREK-1"));
+        assert!(lines[0].ends_with("—: обновлена · Новая задача"));
+        assert!(lines[1].ends_with(
+            "Иван Петров: Статус: Открыт → В работе, Теги: — → ui, p1 · Падает экспорт"
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+//! Хуки вокруг команд
+//!
+//! Секция `hooks` конфигурации назначает команде (`tracker create`, `llm ask`, `sync` —
+//! подкоманды через пробел) скрипты оболочки: `pre` выполняются до команды, и ненулевой
+//! код возврата отменяет её; `post` — после успешной команды, и их ошибки только
+//! выводятся. Скрипт получает имя команды в `YOU_COMMAND`, а `post` — ещё и переменные,
+//! которые выставила команда, например `YOU_ISSUE_KEY` после `tracker create`.
+
+use std::{process::Command, sync::Mutex};
+
+use anyhow::{bail, Context, Result};
+use clap::ArgMatches;
+use serde::Deserialize;
+use tracing::{info, instrument, warn};
+
+use crate::settings;
+
+/// Переменные для `post`-хуков, выставленные выполняемой командой
+static EXPORTS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Скрипты для одной команды
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CommandHooks {
+    /// Выполняются до команды; ошибка отменяет команду
+    pub pre: Vec<String>,
+    /// Выполняются после успешной команды
+    pub post: Vec<String>,
+}
+
+/// Имя команды из разобранных аргументов: подкоманды через пробел
+pub fn command_name(matches: &ArgMatches) -> String {
+    let mut parts = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        parts.push(name);
+        current = sub;
+    }
+    parts.join(" ")
+}
+
+/// Передаёт переменную в `post`-хуки текущей команды
+pub fn export(name: &str, value: impl Into<String>) {
+    EXPORTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push((name.to_string(), value.into()));
+}
+
+impl CommandHooks {
+    /// Выполняет `pre`-хуки по порядку; первая ошибка отменяет команду
+    #[instrument(skip(self))]
+    pub fn run_pre(&self, command: &str) -> Result<()> {
+        for script in &self.pre {
+            run(script, command, &[])
+                .with_context(|| format!("Хук pre отменил команду «{command}»"))?;
+        }
+        Ok(())
+    }
+
+    /// Выполняет все `post`-хуки с переменными команды; ошибки только выводятся
+    #[instrument(skip(self))]
+    pub fn run_post(&self, command: &str) {
+        if self.post.is_empty() {
+            return;
+        }
+        let exports = std::mem::take(
+            &mut *EXPORTS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+        for script in &self.post {
+            if let Err(err) = run(script, command, &exports) {
+                warn!(script, error = %format!("{err:#}"), "Хук post завершился с ошибкой");
+                eprintln!("Хук post: {err:#}");
+            }
+        }
+    }
+}
+
+fn run(script: &str, command: &str, exports: &[(String, String)]) -> Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .envs(settings::child_env())
+        .env("YOU_COMMAND", command)
+        .envs(exports.iter().map(|(name, value)| (name, value)))
+        .status()
+        .with_context(|| format!("Не удалось запустить {script}"))?;
+    if !status.success() {
+        bail!("{script} завершился с ошибкой: {status}");
+    }
+    info!(script, "Хук выполнен");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_name_joins_subcommands() {
+        let cli = clap::Command::new("you")
+            .arg(
+                clap::Arg::new("verbose")
+                    .short('v')
+                    .action(clap::ArgAction::Count),
+            )
+            .subcommand(
+                clap::Command::new("tracker")
+                    .subcommand(clap::Command::new("create").arg(clap::Arg::new("summary"))),
+            );
+        let matches = cli.get_matches_from(["you", "-v", "tracker", "create", "Релиз"]);
+        assert_eq!(command_name(&matches), "tracker create");
+    }
+
+    #[test]
+    fn test_pre_failure_cancels_and_post_gets_exports() {
+        let dir = std::env::temp_dir().join(format!("you-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("post.txt");
+
+        let hooks = CommandHooks {
+            pre: vec!["true".to_string(), "exit 3".to_string()],
+            post: vec![format!(
+                "echo \"$YOU_COMMAND $YOU_ISSUE_KEY\" > {}",
+                out.display()
+            )],
+        };
+        let err = hooks.run_pre("tracker create").unwrap_err();
+        assert!(format!("{err:#}").contains("exit 3 завершился с ошибкой"));
+
+        export("YOU_ISSUE_KEY", "TREK-7");
+        hooks.run_post("tracker create");
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "tracker create TREK-7\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,515 @@
+//! Входящие уведомления: упоминания, назначения и смены статусов
+//!
+//! Трекер опрашивается по задачам, где я исполнитель, автор или наблюдатель
+//! (тот же запрос, что и у локального индекса). Уведомления и отметки о
+//! прочтении хранятся в `$XDG_STATE_HOME/you/inbox.json`.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    models::{ChangelogEntry, Comment, Issue, User, TRACKER_DATE_FORMAT},
+    search::{SearchParams, SearchRequest},
+    TrackerClient,
+};
+
+use crate::{config::Config, index, paths, queues::NotifyRules, reminders, settings};
+
+/// Сколько уведомлений хранить; старые прочитанные удаляются первыми
+const MAX_NOTIFICATIONS: usize = 500;
+/// Глубина первого опроса, когда прошлого ещё не было
+const FIRST_POLL_DAYS: i64 = 1;
+const POLL_PAGE_SIZE: u32 = 100;
+
+/// Тип уведомления; в конфигурации (`notify.sinks`, `queue.<очередь>.notify`) пишется в snake_case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationKind {
+    #[serde(alias = "mention")]
+    Mention,
+    #[serde(alias = "assigned")]
+    Assigned,
+    #[serde(alias = "status_changed")]
+    StatusChanged,
+    /// Событие из вебхука `you serve`
+    #[serde(alias = "webhook")]
+    Webhook,
+    /// Наступил срок напоминания `you tracker remind`
+    #[serde(alias = "reminder")]
+    Reminder,
+    /// Истёк таймер SLA (вебхук `slaBreached`)
+    #[serde(alias = "sla_breach")]
+    SlaBreach,
+}
+
+impl NotificationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationKind::Mention => "упоминание",
+            NotificationKind::Assigned => "назначение",
+            NotificationKind::StatusChanged => "статус",
+            NotificationKind::Webhook => "вебхук",
+            NotificationKind::Reminder => "напоминание",
+            NotificationKind::SlaBreach => "SLA",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Идентификатор записи истории или комментария, чтобы не дублировать уведомления
+    pub id: String,
+    pub kind: NotificationKind,
+    pub issue_key: String,
+    pub issue_summary: String,
+    pub text: String,
+    pub author: Option<String>,
+    /// Время события в формате API Трекера
+    pub at: String,
+    pub read: bool,
+}
+
+impl Notification {
+    /// Заголовок уведомления: ключ и название задачи
+    pub fn title(&self) -> String {
+        if self.issue_summary.is_empty() {
+            self.issue_key.clone()
+        } else {
+            format!("{}: {}", self.issue_key, self.issue_summary)
+        }
+    }
+
+    /// Строка для списка: отметка непрочитанного, задача, тип и текст
+    pub fn line(&self) -> String {
+        let marker = if self.read { " " } else { "●" };
+        let author = self
+            .author
+            .as_deref()
+            .map(|author| format!(" ({author})"))
+            .unwrap_or_default();
+        format!(
+            "{marker} {} [{}] {}{author}",
+            self.issue_key,
+            self.kind.label(),
+            self.text
+        )
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Inbox {
+    /// Время прошлого опроса в формате API Трекера
+    pub last_poll: Option<String>,
+    /// Уведомления, новые в начале
+    pub notifications: Vec<Notification>,
+}
+
+impl Inbox {
+    /// Загружает входящие; повреждённый или отсутствующий файл даёт пустой список
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = inbox_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Входящие не найдены");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = %err, "Не удалось разобрать входящие");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = inbox_path().context("Не удалось определить путь для входящих")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Время прошлого опроса; None, если опроса ещё не было
+    pub fn last_poll_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.last_poll.as_deref().and_then(parse_time)
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    pub fn mark_read(&mut self, index: usize) {
+        if let Some(notification) = self.notifications.get_mut(index) {
+            notification.read = true;
+        }
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for notification in &mut self.notifications {
+            notification.read = true;
+        }
+    }
+
+    /// Добавляет новые уведомления в начало, пропуская уже известные; возвращает число добавленных
+    pub fn merge(&mut self, mut fresh: Vec<Notification>) -> usize {
+        let known: HashSet<&str> = self.notifications.iter().map(|n| n.id.as_str()).collect();
+        fresh.retain(|n| !known.contains(n.id.as_str()));
+        fresh.sort_by(|a, b| b.at.cmp(&a.at));
+        let added = fresh.len();
+
+        fresh.append(&mut self.notifications);
+        self.notifications = fresh;
+        while self.notifications.len() > MAX_NOTIFICATIONS {
+            match self.notifications.iter().rposition(|n| n.read) {
+                Some(oldest_read) => self.notifications.remove(oldest_read),
+                None => self.notifications.remove(self.notifications.len() - 1),
+            };
+        }
+        added
+    }
+}
+
+fn inbox_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("inbox.json"))
+}
+
+/// Опрашивает Трекер и добавляет во входящие события после прошлого опроса
+///
+/// Уведомления, которые отсекают правила очередей, пропускаются. Возвращает количество
+/// новых уведомлений.
+#[instrument(skip_all)]
+pub async fn poll(client: &TrackerClient, inbox: &mut Inbox, rules: &NotifyRules) -> Result<usize> {
+    let now = Utc::now().fixed_offset();
+    let since = inbox
+        .last_poll
+        .as_deref()
+        .and_then(parse_time)
+        .unwrap_or_else(|| now - Duration::days(FIRST_POLL_DAYS));
+    let me = client.get_myself().await?;
+
+    let request = SearchRequest {
+        query: Some(index::sync_query(Some(
+            &since.format("%Y-%m-%d").to_string(),
+        ))),
+        ..Default::default()
+    };
+    let params = SearchParams {
+        per_page: Some(POLL_PAGE_SIZE),
+        ..Default::default()
+    };
+    let issues = client.search_issues(&request, Some(params)).await?;
+    debug!(count = issues.len(), "Задачи с изменениями");
+
+    let mut fresh = Vec::new();
+    for issue in &issues {
+        let changelog = client.get_changelog(&issue.issue_key()?, None).await?;
+        fresh.extend(changelog_notifications(issue, &changelog, &me, since));
+        let comments = client.get_comments(&issue.issue_key()?).await?;
+        fresh.extend(mention_notifications(issue, &comments, &me, since));
+    }
+
+    fresh.retain(|notification| rules.allows(notification));
+    let added = inbox.merge(fresh);
+    inbox.last_poll = Some(now.format(TRACKER_DATE_FORMAT).to_string());
+    info!(added, unread = inbox.unread_count(), "Входящие обновлены");
+    Ok(added)
+}
+
+/// Назначения на меня и смены статуса, сделанные другими после `since`
+fn changelog_notifications(
+    issue: &Issue,
+    entries: &[ChangelogEntry],
+    me: &User,
+    since: DateTime<FixedOffset>,
+) -> Vec<Notification> {
+    let mut notifications = Vec::new();
+    for entry in entries {
+        let Some(at) = entry.updated_at.as_deref() else {
+            continue;
+        };
+        if !is_after(at, since) || entry.updated_by.as_ref().is_some_and(|u| is_me(u, me)) {
+            continue;
+        }
+        let author = entry.updated_by.as_ref().and_then(|u| u.display.clone());
+
+        for change in &entry.fields {
+            let (kind, text) = match change.field.id.as_deref() {
+                Some("assignee") if change.to.as_ref().is_some_and(|to| value_is_me(to, me)) => {
+                    (NotificationKind::Assigned, "назначена на вас".to_string())
+                }
+                Some("status") => (
+                    NotificationKind::StatusChanged,
+                    format!(
+                        "{} → {}",
+                        display(change.from.as_ref()),
+                        display(change.to.as_ref())
+                    ),
+                ),
+                _ => continue,
+            };
+            notifications.push(Notification {
+                id: format!(
+                    "{}:{}:{}",
+                    issue.key,
+                    entry.id.as_deref().unwrap_or(at),
+                    change.field.id.as_deref().unwrap_or_default()
+                ),
+                kind,
+                issue_key: issue.key.clone(),
+                issue_summary: issue.summary.clone(),
+                text,
+                author: author.clone(),
+                at: at.to_string(),
+                read: false,
+            });
+        }
+    }
+    notifications
+}
+
+/// Комментарии других с `@логином` после `since`
+fn mention_notifications(
+    issue: &Issue,
+    comments: &[Comment],
+    me: &User,
+    since: DateTime<FixedOffset>,
+) -> Vec<Notification> {
+    let Some(login) = me.login.as_deref() else {
+        return Vec::new();
+    };
+    let mention = format!("@{login}");
+
+    comments
+        .iter()
+        .filter(|comment| {
+            comment
+                .created_at
+                .as_deref()
+                .is_some_and(|at| is_after(at, since))
+                && !comment.created_by.as_ref().is_some_and(|u| is_me(u, me))
+                && comment.text.contains(&mention)
+        })
+        .map(|comment| Notification {
+            id: format!(
+                "{}:comment:{}",
+                issue.key,
+                comment.id.map(|id| id.to_string()).unwrap_or_default()
+            ),
+            kind: NotificationKind::Mention,
+            issue_key: issue.key.clone(),
+            issue_summary: issue.summary.clone(),
+            text: first_line(&comment.text),
+            author: comment.created_by.as_ref().and_then(|u| u.display.clone()),
+            at: comment.created_at.clone().unwrap_or_default(),
+            read: false,
+        })
+        .collect()
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, TRACKER_DATE_FORMAT).ok()
+}
+
+fn is_after(value: &str, since: DateTime<FixedOffset>) -> bool {
+    parse_time(value).is_some_and(|at| at > since)
+}
+
+fn is_me(user: &User, me: &User) -> bool {
+    (user.id.is_some() && user.id == me.id) || (user.login.is_some() && user.login == me.login)
+}
+
+/// Пользователь в значении поля истории: сравнивается по id и логину
+fn value_is_me(value: &serde_json::Value, me: &User) -> bool {
+    serde_json::from_value::<User>(value.clone()).is_ok_and(|user| is_me(&user, me))
+}
+
+fn display(value: Option<&serde_json::Value>) -> String {
+    value
+        .and_then(|value| value.get("display"))
+        .and_then(|display| display.as_str())
+        .unwrap_or("—")
+        .to_string()
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Аргументы команды `you inbox`
+#[derive(Args)]
+pub struct InboxArgs {
+    /// Показать и прочитанные уведомления
+    #[arg(short, long)]
+    all: bool,
+
+    /// Отметить все уведомления прочитанными после вывода
+    #[arg(short, long)]
+    mark_read: bool,
+
+    /// Не опрашивать Трекер, показать сохранённое
+    #[arg(long)]
+    no_poll: bool,
+}
+
+impl InboxArgs {
+    pub async fn execute(self) -> Result<()> {
+        let mut inbox = Inbox::load();
+        reminders::deliver_due(&mut inbox)?;
+        if !self.no_poll {
+            let rules = NotifyRules::new(&Config::load()?.queue);
+            poll(&settings::tracker_client()?, &mut inbox, &rules).await?;
+        }
+
+        let shown: Vec<&Notification> = inbox
+            .notifications
+            .iter()
+            .filter(|n| self.all || !n.read)
+            .collect();
+        if shown.is_empty() {
+            println!("Новых уведомлений нет");
+        }
+        for notification in shown {
+            println!("{}", notification.line());
+        }
+
+        if self.mark_read {
+            inbox.mark_all_read();
+        }
+        inbox.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing;
+
+    fn me() -> User {
+        serde_json::from_value(json!({"id": "42", "login": "me", "display": "Я"})).unwrap()
+    }
+
+    fn issue() -> Issue {
+        testing::issue("TREK-1").summary("Падает экспорт").build()
+    }
+
+    fn since() -> DateTime<FixedOffset> {
+        parse_time("2024-05-01T10:00:00.000+0000").unwrap()
+    }
+
+    fn notification(id: &str, at: &str, read: bool) -> Notification {
+        Notification {
+            id: id.to_string(),
+            kind: NotificationKind::Mention,
+            issue_key: "TREK-1".to_string(),
+            issue_summary: String::new(),
+            text: String::new(),
+            author: None,
+            at: at.to_string(),
+            read,
+        }
+    }
+
+    #[test]
+    fn test_changelog_yields_assignments_and_status_changes_by_others() {
+        let entries: Vec<ChangelogEntry> = serde_json::from_value(json!([
+            {
+                "id": "c1",
+                "updatedAt": "2024-05-01T11:00:00.000+0000",
+                "updatedBy": {"id": "7", "display": "Коллега"},
+                "fields": [
+                    {"field": {"id": "assignee"}, "from": null, "to": {"id": "42", "display": "Я"}},
+                    {"field": {"id": "status"}, "from": {"display": "Открыт"}, "to": {"display": "В работе"}}
+                ]
+            },
+            {
+                "id": "c2",
+                "updatedAt": "2024-05-01T12:00:00.000+0000",
+                "updatedBy": {"id": "42", "display": "Я"},
+                "fields": [{"field": {"id": "status"}, "to": {"display": "Закрыт"}}]
+            },
+            {
+                "id": "c0",
+                "updatedAt": "2024-05-01T09:00:00.000+0000",
+                "updatedBy": {"id": "7"},
+                "fields": [{"field": {"id": "status"}, "to": {"display": "Открыт"}}]
+            }
+        ]))
+        .unwrap();
+
+        let notifications = changelog_notifications(&issue(), &entries, &me(), since());
+
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].kind, NotificationKind::Assigned);
+        assert_eq!(notifications[0].author.as_deref(), Some("Коллега"));
+        assert_eq!(notifications[1].kind, NotificationKind::StatusChanged);
+        assert_eq!(notifications[1].text, "Открыт → В работе");
+    }
+
+    #[test]
+    fn test_mentions_require_login_in_text_from_someone_else() {
+        let comments: Vec<Comment> = serde_json::from_value(json!([
+            {"id": 1, "text": "@me глянь, пожалуйста\nлоги ниже", "createdAt": "2024-05-01T11:00:00.000+0000", "createdBy": {"login": "colleague"}},
+            {"id": 2, "text": "Без упоминания", "createdAt": "2024-05-01T11:00:00.000+0000", "createdBy": {"login": "colleague"}},
+            {"id": 3, "text": "@me напоминаю себе", "createdAt": "2024-05-01T11:00:00.000+0000", "createdBy": {"login": "me"}}
+        ]))
+        .unwrap();
+
+        let notifications = mention_notifications(&issue(), &comments, &me(), since());
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].id, "TREK-1:comment:1");
+        assert_eq!(notifications[0].text, "@me глянь, пожалуйста");
+    }
+
+    #[test]
+    fn test_merge_skips_known_and_keeps_newest_first() {
+        let mut inbox = Inbox::default();
+        inbox.merge(vec![notification(
+            "a",
+            "2024-05-01T10:00:00.000+0000",
+            true,
+        )]);
+
+        let added = inbox.merge(vec![
+            notification("a", "2024-05-01T10:00:00.000+0000", false),
+            notification("b", "2024-05-02T10:00:00.000+0000", false),
+        ]);
+
+        assert_eq!(added, 1);
+        let ids: Vec<&str> = inbox.notifications.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["b", "a"]);
+        assert_eq!(inbox.unread_count(), 1);
+    }
+
+    #[test]
+    fn test_merge_drops_oldest_read_when_full() {
+        let mut inbox = Inbox::default();
+        let mut old: Vec<Notification> = (0..MAX_NOTIFICATIONS)
+            .map(|i| notification(&format!("old-{i}"), "2024-05-01T10:00:00.000+0000", false))
+            .collect();
+        old[0].read = true;
+        inbox.merge(old);
+
+        inbox.merge(vec![notification(
+            "new",
+            "2024-05-02T10:00:00.000+0000",
+            false,
+        )]);
+
+        assert_eq!(inbox.notifications.len(), MAX_NOTIFICATIONS);
+        assert_eq!(inbox.notifications[0].id, "new");
+        assert_eq!(inbox.unread_count(), MAX_NOTIFICATIONS);
+    }
+}
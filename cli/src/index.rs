@@ -0,0 +1,625 @@
+//! Локальный поисковый индекс моих задач
+//!
+//! Задачи, где я исполнитель, автор или наблюдатель, складываются в SQLite
+//! (`$XDG_STATE_HOME/you/index.sqlite3`) с полнотекстовым индексом FTS5.
+//! Синхронизация инкрементальная: запрашиваются только задачи, обновлённые
+//! с даты прошлой синхронизации. По желанию к задачам добавляются векторы
+//! эмбеддингов, и выдача FTS объединяется с семантической.
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use llm_lib::LlmClient;
+use rusqlite::{params, Connection, OptionalExtension};
+use tracing::{debug, info, instrument};
+use tracker_lib::{
+    models::Issue,
+    search::{SearchParams, SearchRequest},
+    TrackerClient,
+};
+
+use crate::paths;
+
+/// Модель эмбеддингов по умолчанию
+pub const DEFAULT_EMBEDDING_MODEL: &str = "openai/text-embedding-3-small";
+
+const INDEX_FILE: &str = "index.sqlite3";
+const SYNC_PAGE_SIZE: u32 = 100;
+const EMBEDDING_BATCH: usize = 64;
+const LAST_SYNC: &str = "last_sync";
+/// Сглаживающая константа reciprocal rank fusion
+const RRF_K: f64 = 60.0;
+/// Косинусная близость, начиная с которой задача считается возможным дублем
+const DUPLICATE_SIMILARITY: f64 = 0.8;
+const DUPLICATE_CANDIDATES: usize = 3;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS issues (
+    key TEXT PRIMARY KEY,
+    summary TEXT NOT NULL,
+    description TEXT,
+    status TEXT,
+    updated_at TEXT,
+    embedding BLOB
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS issues_fts USING fts5(
+    key UNINDEXED,
+    summary,
+    description,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+CREATE TABLE IF NOT EXISTS sync_state (
+    name TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+/// Найденная в индексе задача
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    /// Чем больше, тем выше задача в выдаче
+    pub score: f64,
+}
+
+/// Задача из индекса в порядке обновления
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedIssue {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    pub updated_at: Option<String>,
+    pub description: Option<String>,
+}
+
+pub struct IssueIndex {
+    conn: Connection,
+}
+
+impl IssueIndex {
+    /// Открывает индекс в каталоге состояния, создавая его при необходимости
+    pub fn open_default() -> Result<Self> {
+        let dir = paths::state_dir().context("Не удалось определить каталог для индекса")?;
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Не удалось создать каталог {}", dir.display()))?;
+        Self::open(&dir.join(INDEX_FILE))
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Не удалось открыть индекс {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("Не удалось создать схему индекса")?;
+        Ok(Self { conn })
+    }
+
+    /// Добавляет или обновляет задачу; вектор сбрасывается, если поменялся текст
+    pub fn upsert(&self, issue: &Issue) -> Result<()> {
+        let status = issue.status.as_ref().and_then(|s| s.display.as_deref());
+        self.conn.execute(
+            "INSERT INTO issues (key, summary, description, status, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(key) DO UPDATE SET
+                 embedding = CASE
+                     WHEN summary = excluded.summary AND description IS excluded.description
+                     THEN embedding
+                 END,
+                 summary = excluded.summary,
+                 description = excluded.description,
+                 status = excluded.status,
+                 updated_at = excluded.updated_at",
+            params![
+                issue.key,
+                issue.summary,
+                issue.description,
+                status,
+                issue.updated_at
+            ],
+        )?;
+        self.conn
+            .execute("DELETE FROM issues_fts WHERE key = ?1", params![issue.key])?;
+        self.conn.execute(
+            "INSERT INTO issues_fts (key, summary, description) VALUES (?1, ?2, ?3)",
+            params![issue.key, issue.summary, issue.description],
+        )?;
+        Ok(())
+    }
+
+    pub fn count(&self) -> Result<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM issues", [], |row| row.get(0))?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+
+    /// Дата прошлой синхронизации (`YYYY-MM-DD`)
+    pub fn last_sync(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT value FROM sync_state WHERE name = ?1",
+                params![LAST_SYNC],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_last_sync(&self, date: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_state (name, value) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET value = excluded.value",
+            params![LAST_SYNC, date],
+        )?;
+        Ok(())
+    }
+
+    /// Задачи без вектора: ключ и текст для эмбеддинга
+    pub fn missing_embeddings(&self) -> Result<Vec<(String, String)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT key, summary, description FROM issues WHERE embedding IS NULL ORDER BY key",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let summary: String = row.get(1)?;
+            let description: Option<String> = row.get(2)?;
+            Ok((key, embedding_text(&summary, description.as_deref())))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn set_embedding(&self, key: &str, vector: &[f32]) -> Result<()> {
+        self.conn.execute(
+            "UPDATE issues SET embedding = ?2 WHERE key = ?1",
+            params![key, encode_vector(vector)],
+        )?;
+        Ok(())
+    }
+
+    /// Полнотекстовый поиск; совпадение в названии весит больше, чем в описании
+    pub fn search_text(&self, text: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let Some(query) = match_query(text) else {
+            return Ok(Vec::new());
+        };
+        let mut statement = self.conn.prepare(
+            "SELECT issues.key, issues.summary, issues.status, bm25(issues_fts, 0.0, 10.0, 1.0)
+             FROM issues_fts JOIN issues ON issues.key = issues_fts.key
+             WHERE issues_fts MATCH ?1
+             ORDER BY bm25(issues_fts, 0.0, 10.0, 1.0)
+             LIMIT ?2",
+        )?;
+        let rows = statement.query_map(params![query, limit as i64], |row| {
+            let rank: f64 = row.get(3)?;
+            Ok(SearchHit {
+                key: row.get(0)?,
+                summary: row.get(1)?,
+                status: row.get(2)?,
+                score: -rank,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Задача по ключу, если она есть в индексе
+    pub fn get(&self, key: &str) -> Result<Option<IndexedIssue>> {
+        let issue = self
+            .conn
+            .query_row(
+                "SELECT key, summary, status, updated_at, description FROM issues WHERE key = ?1",
+                params![key],
+                |row| {
+                    Ok(IndexedIssue {
+                        key: row.get(0)?,
+                        summary: row.get(1)?,
+                        status: row.get(2)?,
+                        updated_at: row.get(3)?,
+                        description: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(issue)
+    }
+
+    /// Недавно обновлённые задачи, новые в начале
+    pub fn recent(&self, limit: usize) -> Result<Vec<IndexedIssue>> {
+        let mut statement = self.conn.prepare(
+            "SELECT key, summary, status, updated_at, description FROM issues
+             ORDER BY updated_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = statement.query_map(params![limit as i64], |row| {
+            Ok(IndexedIssue {
+                key: row.get(0)?,
+                summary: row.get(1)?,
+                status: row.get(2)?,
+                updated_at: row.get(3)?,
+                description: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Ближайшие к запросу задачи по косинусной близости векторов
+    pub fn search_semantic(&self, query: &[f32], limit: usize) -> Result<Vec<SearchHit>> {
+        let mut statement = self.conn.prepare(
+            "SELECT key, summary, status, embedding FROM issues WHERE embedding IS NOT NULL",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let embedding: Vec<u8> = row.get(3)?;
+            Ok(SearchHit {
+                key: row.get(0)?,
+                summary: row.get(1)?,
+                status: row.get(2)?,
+                score: f64::from(cosine(query, &decode_vector(&embedding))),
+            })
+        })?;
+        let mut hits = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// До трёх самых близких к вектору задач с близостью не ниже порога дубля
+    pub fn likely_duplicates(&self, query: &[f32]) -> Result<Vec<SearchHit>> {
+        let mut hits = self.search_semantic(query, DUPLICATE_CANDIDATES)?;
+        hits.retain(|hit| hit.score >= DUPLICATE_SIMILARITY);
+        Ok(hits)
+    }
+}
+
+/// Запрос на языке Трекера: мои задачи, обновлённые начиная с `since`
+pub fn sync_query(since: Option<&str>) -> String {
+    let mut query = "(Assignee: me() OR Author: me() OR Followers: me())".to_string();
+    if let Some(since) = since {
+        query.push_str(&format!(" Updated: >= \"{since}\""));
+    }
+    query.push_str(" \"Sort by\": Updated ASC");
+    query
+}
+
+/// Выражение FTS5: каждое слово запроса ищется по префиксу, совпадения объединяются через OR
+pub fn match_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word.to_lowercase()))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+/// Подтягивает в индекс мои задачи, изменённые после прошлой синхронизации
+///
+/// `full` игнорирует дату прошлой синхронизации и перечитывает все задачи.
+/// Возвращает количество обновлённых задач.
+#[instrument(skip(index, client))]
+pub async fn sync(index: &IssueIndex, client: &TrackerClient, full: bool) -> Result<usize> {
+    let since = if full { None } else { index.last_sync()? };
+    // Дата фиксируется до запроса, чтобы не пропустить задачи, изменённые во время синхронизации
+    let started = Local::now().date_naive().format("%Y-%m-%d").to_string();
+    let request = SearchRequest {
+        query: Some(sync_query(since.as_deref())),
+        ..Default::default()
+    };
+    debug!(since = ?since, "Синхронизация индекса");
+
+    let mut synced = 0;
+    for page in 1.. {
+        let params = SearchParams {
+            per_page: Some(SYNC_PAGE_SIZE),
+            page: Some(page),
+            ..Default::default()
+        };
+        let issues = client.search_issues(&request, Some(params)).await?;
+        for issue in &issues {
+            index.upsert(issue)?;
+        }
+        synced += issues.len();
+        if issues.len() < SYNC_PAGE_SIZE as usize {
+            break;
+        }
+    }
+
+    index.set_last_sync(&started)?;
+    info!(synced, "Индекс синхронизирован");
+    Ok(synced)
+}
+
+/// Считает векторы для задач, у которых их ещё нет
+#[instrument(skip(index, llm))]
+pub async fn embed_missing(index: &IssueIndex, llm: &LlmClient) -> Result<usize> {
+    let missing = index.missing_embeddings()?;
+    for batch in missing.chunks(EMBEDDING_BATCH) {
+        let texts = batch.iter().map(|(_, text)| text.clone()).collect();
+        let vectors = llm.embeddings(texts).await?;
+        for ((key, _), vector) in batch.iter().zip(&vectors) {
+            index.set_embedding(key, vector)?;
+        }
+    }
+    info!(count = missing.len(), "Векторы задач посчитаны");
+    Ok(missing.len())
+}
+
+/// Ищет задачи в индексе; с `llm` выдача FTS объединяется с семантической
+#[instrument(skip(index, llm))]
+pub async fn find(
+    index: &IssueIndex,
+    text: &str,
+    limit: usize,
+    llm: Option<&LlmClient>,
+) -> Result<Vec<SearchHit>> {
+    let text_hits = index.search_text(text, limit)?;
+    let Some(llm) = llm else {
+        return Ok(text_hits);
+    };
+
+    embed_missing(index, llm).await?;
+    let query = llm
+        .embeddings(vec![text.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .context("Сервис эмбеддингов вернул пустой ответ")?;
+    let semantic_hits = index.search_semantic(&query, limit)?;
+    Ok(fuse(&[text_hits, semantic_hits], limit))
+}
+
+/// Ищет в индексе возможные дубли новой задачи по близости эмбеддингов
+#[instrument(skip(index, llm, description))]
+pub async fn find_duplicates(
+    index: &IssueIndex,
+    llm: &LlmClient,
+    summary: &str,
+    description: Option<&str>,
+) -> Result<Vec<SearchHit>> {
+    embed_missing(index, llm).await?;
+    let query = llm
+        .embeddings(vec![embedding_text(summary, description)])
+        .await?
+        .into_iter()
+        .next()
+        .context("Сервис эмбеддингов вернул пустой ответ")?;
+    let hits = index.likely_duplicates(&query)?;
+    debug!(count = hits.len(), "Возможные дубли найдены");
+    Ok(hits)
+}
+
+/// Reciprocal rank fusion: место задачи в каждой выдаче даёт `1 / (K + место)`
+fn fuse(rankings: &[Vec<SearchHit>], limit: usize) -> Vec<SearchHit> {
+    let mut fused: HashMap<&str, SearchHit> = HashMap::new();
+    for ranking in rankings {
+        for (position, hit) in ranking.iter().enumerate() {
+            let score = 1.0 / (RRF_K + position as f64 + 1.0);
+            fused
+                .entry(hit.key.as_str())
+                .and_modify(|existing| existing.score += score)
+                .or_insert_with(|| SearchHit {
+                    score,
+                    ..hit.clone()
+                });
+        }
+    }
+    let mut hits: Vec<SearchHit> = fused.into_values().collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.key.cmp(&b.key)));
+    hits.truncate(limit);
+    hits
+}
+
+/// Текст задачи для эмбеддинга: название и описание
+pub fn embedding_text(summary: &str, description: Option<&str>) -> String {
+    match description {
+        Some(description) => format!("{summary}\n\n{description}"),
+        None => summary.to_string(),
+    }
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    /// Открытая задача с названием и описанием
+    fn open_issue(key: &str, summary: &str, description: &str) -> Issue {
+        testing::issue(key)
+            .summary(summary)
+            .description(description)
+            .status("open", "Открыт")
+            .build()
+    }
+
+    fn hit(key: &str) -> SearchHit {
+        SearchHit {
+            key: key.to_string(),
+            summary: String::new(),
+            status: None,
+            score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_match_query_quotes_words_as_prefixes() {
+        assert_eq!(
+            match_query("that bug about \"socks\"-proxy").as_deref(),
+            Some("\"that\"* OR \"bug\"* OR \"about\"* OR \"socks\"* OR \"proxy\"*")
+        );
+        assert_eq!(match_query(" -- "), None);
+    }
+
+    #[test]
+    fn test_sync_query_is_incremental() {
+        assert_eq!(
+            sync_query(Some("2024-05-01")),
+            "(Assignee: me() OR Author: me() OR Followers: me()) Updated: >= \"2024-05-01\" \"Sort by\": Updated ASC"
+        );
+        assert!(!sync_query(None).contains("Updated: >="));
+    }
+
+    #[test]
+    fn test_search_ranks_summary_matches_first() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        index
+            .upsert(&open_issue(
+                "TREK-1",
+                "Обновить зависимости",
+                "Заодно проверить socks proxy",
+            ))
+            .unwrap();
+        index
+            .upsert(&open_issue(
+                "TREK-2",
+                "Не работает SOCKS прокси",
+                "Запросы уходят мимо",
+            ))
+            .unwrap();
+        index
+            .upsert(&open_issue("TREK-3", "Поправить вёрстку", "Кнопка съехала"))
+            .unwrap();
+
+        let hits = index.search_text("socks", 10).unwrap();
+
+        let keys: Vec<&str> = hits.iter().map(|hit| hit.key.as_str()).collect();
+        assert_eq!(keys, ["TREK-2", "TREK-1"]);
+        assert_eq!(hits[0].status.as_deref(), Some("Открыт"));
+    }
+
+    #[test]
+    fn test_recent_orders_by_update_time() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        for (key, updated_at) in [
+            ("TREK-1", "2024-05-01T10:00:00.000+0000"),
+            ("TREK-2", "2024-05-03T10:00:00.000+0000"),
+            ("TREK-3", "2024-05-02T10:00:00.000+0000"),
+        ] {
+            let mut issue = open_issue(key, "Задача", "");
+            issue.updated_at = Some(updated_at.to_string());
+            index.upsert(&issue).unwrap();
+        }
+
+        let recent = index.recent(2).unwrap();
+
+        let keys: Vec<&str> = recent.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(keys, ["TREK-2", "TREK-3"]);
+        assert_eq!(recent[0].status.as_deref(), Some("Открыт"));
+    }
+
+    #[test]
+    fn test_upsert_replaces_text_and_resets_embedding() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        index
+            .upsert(&open_issue("TREK-1", "Старое название", ""))
+            .unwrap();
+        index.set_embedding("TREK-1", &[1.0, 0.0]).unwrap();
+        assert!(index.missing_embeddings().unwrap().is_empty());
+
+        index
+            .upsert(&open_issue("TREK-1", "Новое название", ""))
+            .unwrap();
+
+        assert_eq!(index.count().unwrap(), 1);
+        assert!(index.search_text("старое", 10).unwrap().is_empty());
+        assert_eq!(index.search_text("новое", 10).unwrap().len(), 1);
+        assert_eq!(index.missing_embeddings().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_semantic_search_orders_by_cosine() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        index.upsert(&open_issue("TREK-1", "Первая", "")).unwrap();
+        index.upsert(&open_issue("TREK-2", "Вторая", "")).unwrap();
+        index.set_embedding("TREK-1", &[1.0, 0.0]).unwrap();
+        index.set_embedding("TREK-2", &[0.6, 0.8]).unwrap();
+
+        let hits = index.search_semantic(&[0.0, 1.0], 1).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "TREK-2");
+        assert!((hits[0].score - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_likely_duplicates_skip_distant_issues() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        for (key, vector) in [
+            ("TREK-1", [1.0, 0.0]),
+            ("TREK-2", [0.6, 0.8]),
+            ("TREK-3", [0.9, 0.1]),
+            ("TREK-4", [0.0, 1.0]),
+        ] {
+            index.upsert(&open_issue(key, "Задача", "")).unwrap();
+            index.set_embedding(key, &vector).unwrap();
+        }
+
+        let keys: Vec<String> = index
+            .likely_duplicates(&[1.0, 0.0])
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.key)
+            .collect();
+
+        assert_eq!(keys, ["TREK-1", "TREK-3"]);
+    }
+
+    #[test]
+    fn test_last_sync_roundtrip() {
+        let index = IssueIndex::open_in_memory().unwrap();
+        assert_eq!(index.last_sync().unwrap(), None);
+
+        index.set_last_sync("2024-05-01").unwrap();
+        index.set_last_sync("2024-05-02").unwrap();
+
+        assert_eq!(index.last_sync().unwrap().as_deref(), Some("2024-05-02"));
+    }
+
+    #[test]
+    fn test_fuse_prefers_issues_found_by_both_rankings() {
+        let text = vec![hit("TREK-1"), hit("TREK-2")];
+        let semantic = vec![hit("TREK-3"), hit("TREK-2")];
+
+        let fused = fuse(&[text, semantic], 2);
+
+        let keys: Vec<&str> = fused.iter().map(|hit| hit.key.as_str()).collect();
+        assert_eq!(keys, ["TREK-2", "TREK-1"]);
+    }
+
+    #[test]
+    fn test_vector_encoding_roundtrip() {
+        let vector = [0.25, -1.5, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+}
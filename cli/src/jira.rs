@@ -0,0 +1,495 @@
+//! Совместимость с экспортом Jira для переезда команд
+//!
+//! Импорт читает JSON (ответ REST API поиска, массив задач или одну задачу) и CSV
+//! экспорт Jira и превращает задачи в запросы создания задач Трекера. Экспорт
+//! сохраняет задачи Трекера в JSON того же вида, что отдаёт REST API Jira.
+//! Соответствие типов, приоритетов и пользователей задаётся YAML файлом, поверх
+//! встроенного соответствия стандартных типов и приоритетов.
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::{info, instrument, warn};
+use tracker_lib::{
+    ids::{QueueKey, UserLogin},
+    models::Issue,
+    task::CreateIssueRequest,
+    TrackerClient, TrackerError,
+};
+
+/// Префикс поля `unique`: повторный импорт той же задачи Jira отклоняется Трекером
+const UNIQUE_PREFIX: &str = "jira-import:";
+
+const DEFAULT_TYPES: &[(&str, &str)] = &[
+    ("Bug", "bug"),
+    ("Task", "task"),
+    ("Sub-task", "task"),
+    ("Story", "newFeature"),
+    ("New Feature", "newFeature"),
+    ("Improvement", "improvement"),
+    ("Epic", "epic"),
+];
+
+const DEFAULT_PRIORITIES: &[(&str, &str)] = &[
+    ("Highest", "blocker"),
+    ("High", "critical"),
+    ("Medium", "normal"),
+    ("Low", "minor"),
+    ("Lowest", "trivial"),
+];
+
+/// Соответствие значений полей Jira и Трекера
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FieldMapping {
+    /// Название типа Jira → ключ типа Трекера
+    pub types: HashMap<String, String>,
+
+    /// Название приоритета Jira → ключ приоритета Трекера
+    pub priorities: HashMap<String, String>,
+
+    /// Пользователь Jira (имя, email или accountId) → логин в Трекере
+    pub users: HashMap<String, UserLogin>,
+}
+
+impl FieldMapping {
+    /// Встроенное соответствие, дополненное и переопределённое файлом `path`
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut mapping = Self {
+            types: to_map(DEFAULT_TYPES),
+            priorities: to_map(DEFAULT_PRIORITIES),
+            users: HashMap::new(),
+        };
+        if let Some(path) = path {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+            let custom: FieldMapping = serde_norway::from_str(&content)
+                .with_context(|| format!("Некорректное соответствие полей {}", path.display()))?;
+            mapping.types.extend(custom.types);
+            mapping.priorities.extend(custom.priorities);
+            mapping.users.extend(custom.users);
+        }
+        Ok(mapping)
+    }
+}
+
+fn to_map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(jira, tracker)| (jira.to_string(), tracker.to_string()))
+        .collect()
+}
+
+/// Значение по имени без учёта регистра
+fn lookup<V: Clone>(table: &HashMap<String, V>, name: &str) -> Option<V> {
+    table.get(name).cloned().or_else(|| {
+        table
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+    })
+}
+
+/// Обратный поиск: имя в Jira по значению Трекера
+fn reverse_lookup<V: AsRef<str>>(table: &HashMap<String, V>, value: &str) -> Option<String> {
+    let mut names: Vec<&String> = table
+        .iter()
+        .filter(|(_, tracker)| tracker.as_ref() == value)
+        .map(|(jira, _)| jira)
+        .collect();
+    // Порядок HashMap случаен, а выбрать нужно всегда одно и то же имя
+    names.sort();
+    names.first().map(|name| name.to_string())
+}
+
+/// Задача Jira, приведённая к общему виду из JSON или CSV
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JiraIssue {
+    pub key: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub issue_type: Option<String>,
+    pub priority: Option<String>,
+    pub labels: Vec<String>,
+    /// Имена пользователя-исполнителя в порядке предпочтения: имя, email, accountId
+    pub assignee: Vec<String>,
+}
+
+impl JiraIssue {
+    /// Запрос создания задачи Трекера
+    pub fn to_create_request(
+        &self,
+        queue: &QueueKey,
+        mapping: &FieldMapping,
+    ) -> CreateIssueRequest {
+        let issue_type = self.issue_type.as_deref().and_then(|name| {
+            let mapped = lookup(&mapping.types, name);
+            if mapped.is_none() {
+                warn!(issue_key = %self.key, issue_type = %name, "Тип Jira не сопоставлен");
+            }
+            mapped
+        });
+        let priority = self
+            .priority
+            .as_deref()
+            .and_then(|name| lookup(&mapping.priorities, name));
+        let assignee = self
+            .assignee
+            .iter()
+            .find_map(|name| lookup(&mapping.users, name));
+
+        CreateIssueRequest {
+            description: self.description.clone(),
+            issue_type,
+            priority,
+            assignee,
+            tags: self.labels.clone(),
+            unique: Some(format!("{UNIQUE_PREFIX}{}", self.key)),
+            ..CreateIssueRequest::new(queue.clone(), &self.summary)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RestIssue {
+    key: String,
+    fields: RestFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestFields {
+    summary: String,
+    /// Строка в API v2 или документ Atlassian Document Format в API v3
+    #[serde(default)]
+    description: Option<Value>,
+    #[serde(default)]
+    issuetype: Option<Named>,
+    #[serde(default)]
+    priority: Option<Named>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignee: Option<RestUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestUser {
+    name: Option<String>,
+    #[serde(rename = "emailAddress")]
+    email_address: Option<String>,
+    #[serde(rename = "accountId")]
+    account_id: Option<String>,
+}
+
+impl From<RestIssue> for JiraIssue {
+    fn from(issue: RestIssue) -> Self {
+        let fields = issue.fields;
+        let assignee = fields
+            .assignee
+            .map(|user| {
+                [user.name, user.email_address, user.account_id]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            })
+            .unwrap_or_default();
+        JiraIssue {
+            key: issue.key,
+            summary: fields.summary,
+            description: fields.description.as_ref().and_then(description_text),
+            issue_type: fields.issuetype.map(|named| named.name),
+            priority: fields.priority.map(|named| named.name),
+            labels: fields.labels,
+            assignee,
+        }
+    }
+}
+
+/// Текст описания: строка как есть, из документа ADF — текстовые узлы по абзацам
+fn description_text(value: &Value) -> Option<String> {
+    fn collect(node: &Value, out: &mut String) {
+        if let Some(text) = node.get("text").and_then(Value::as_str) {
+            out.push_str(text);
+        }
+        if let Some(children) = node.get("content").and_then(Value::as_array) {
+            for child in children {
+                collect(child, out);
+            }
+            let block = matches!(
+                node.get("type").and_then(Value::as_str),
+                Some("paragraph" | "heading" | "listItem" | "codeBlock")
+            );
+            if block && !out.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+    }
+
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Object(_) => {
+            let mut out = String::new();
+            collect(value, &mut out);
+            out
+        }
+        _ => return None,
+    };
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Разбирает JSON экспорт: `{"issues": [...]}`, массив задач или одну задачу
+pub fn parse_json(content: &str) -> Result<Vec<JiraIssue>> {
+    let value: Value = serde_json::from_str(content).context("Файл не является JSON")?;
+    let issues = match value {
+        Value::Object(mut object) if object.contains_key("issues") => {
+            object.remove("issues").unwrap_or_default()
+        }
+        Value::Object(_) => Value::Array(vec![value]),
+        array => array,
+    };
+    let issues: Vec<RestIssue> =
+        serde_json::from_value(issues).context("Не удалось разобрать задачи Jira")?;
+    Ok(issues.into_iter().map(JiraIssue::from).collect())
+}
+
+/// Разбирает CSV экспорт Jira; повторяющиеся колонки `Labels` собираются в теги
+pub fn parse_csv(content: &str) -> Result<Vec<JiraIssue>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let mut issues = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let mut issue = JiraIssue::default();
+        for (header, value) in headers.iter().zip(record.iter()) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match header.trim() {
+                "Issue key" => issue.key = value.to_string(),
+                "Summary" => issue.summary = value.to_string(),
+                "Description" => issue.description = Some(value.to_string()),
+                "Issue Type" => issue.issue_type = Some(value.to_string()),
+                "Priority" => issue.priority = Some(value.to_string()),
+                "Labels" => issue.labels.push(value.to_string()),
+                "Assignee" => issue.assignee.push(value.to_string()),
+                _ => {}
+            }
+        }
+        if issue.key.is_empty() || issue.summary.is_empty() {
+            bail!(
+                "В строке {} CSV нет колонок «Issue key» или «Summary»",
+                issues.len() + 2
+            );
+        }
+        issues.push(issue);
+    }
+    Ok(issues)
+}
+
+/// Читает экспорт Jira; формат определяется по расширению `.csv`, иначе JSON
+pub fn read_export(path: &Path) -> Result<Vec<JiraIssue>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+    let is_csv = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    if is_csv {
+        parse_csv(&content)
+    } else {
+        parse_json(&content)
+    }
+}
+
+/// Задача Трекера в формате REST API Jira
+pub fn to_jira(issue: &Issue, mapping: &FieldMapping) -> Value {
+    let issue_type = issue.issue_type.as_ref().and_then(|issue_type| {
+        issue_type
+            .key
+            .as_deref()
+            .and_then(|key| reverse_lookup(&mapping.types, key))
+            .or_else(|| issue_type.display.clone())
+    });
+    let priority = issue.priority.as_ref().and_then(|priority| {
+        priority
+            .key
+            .as_deref()
+            .and_then(|key| reverse_lookup(&mapping.priorities, key))
+            .or_else(|| priority.display.clone())
+    });
+    let assignee = issue
+        .assignee
+        .as_ref()
+        .and_then(|user| user.login.as_deref())
+        .map(|login| reverse_lookup(&mapping.users, login).unwrap_or_else(|| login.to_string()));
+
+    json!({
+        "key": issue.key,
+        "fields": {
+            "summary": issue.summary,
+            "description": issue.description,
+            "issuetype": issue_type.map(|name| json!({ "name": name })),
+            "priority": priority.map(|name| json!({ "name": name })),
+            "labels": issue.tags,
+            "assignee": assignee.map(|name| json!({ "name": name })),
+            "status": issue
+                .status
+                .as_ref()
+                .and_then(|status| status.display.as_ref())
+                .map(|name| json!({ "name": name })),
+        }
+    })
+}
+
+/// Создаёт задачи в очереди; возвращает строки отчёта
+#[instrument(skip(client, issues, mapping))]
+pub async fn import(
+    client: &TrackerClient,
+    issues: &[JiraIssue],
+    queue: &QueueKey,
+    mapping: &FieldMapping,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    let mut report = Vec::new();
+    for jira in issues {
+        let request = jira.to_create_request(queue, mapping);
+        if dry_run {
+            report.push(format!(
+                "[dry-run] {} → {queue}: {} (тип {}, приоритет {})",
+                jira.key,
+                request.summary,
+                request.issue_type.as_deref().unwrap_or("—"),
+                request.priority.as_deref().unwrap_or("—"),
+            ));
+            continue;
+        }
+        let line = match client.create_issue(&request).await {
+            Ok(issue) => format!("{} → {}", jira.key, issue.key),
+            Err(TrackerError::ApiError { status, .. }) if status.as_u16() == 409 => {
+                format!("{} уже импортирована", jira.key)
+            }
+            Err(err) => {
+                warn!(jira_key = %jira.key, error = %err, "Не удалось импортировать задачу");
+                format!("{}: ошибка: {err}", jira.key)
+            }
+        };
+        report.push(line);
+    }
+    info!(count = report.len(), "Импорт из Jira завершён");
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_parse_rest_json_with_adf_description() {
+        let content = json!({
+            "issues": [{
+                "key": "PROJ-1",
+                "fields": {
+                    "summary": "Login fails",
+                    "description": {
+                        "type": "doc",
+                        "content": [
+                            {"type": "paragraph", "content": [{"type": "text", "text": "Steps:"}]},
+                            {"type": "paragraph", "content": [{"type": "text", "text": "open page"}]}
+                        ]
+                    },
+                    "issuetype": {"name": "Bug"},
+                    "priority": {"name": "High"},
+                    "labels": ["auth"],
+                    "assignee": {"emailAddress": "ann@example.com", "accountId": "5b10"}
+                }
+            }]
+        })
+        .to_string();
+
+        let issues = parse_json(&content).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].description.as_deref(), Some("Steps:\nopen page"));
+        assert_eq!(issues[0].assignee, ["ann@example.com", "5b10"]);
+    }
+
+    #[test]
+    fn test_parse_csv_collects_repeated_labels() {
+        let content = "Summary,Issue key,Issue Type,Priority,Labels,Labels,Description\n\
+                       \"Crash, on save\",PROJ-2,Story,Lowest,ui,,\"multi\nline\"\n";
+
+        let issues = parse_csv(content).unwrap();
+        assert_eq!(
+            issues,
+            [JiraIssue {
+                key: "PROJ-2".to_string(),
+                summary: "Crash, on save".to_string(),
+                description: Some("multi\nline".to_string()),
+                issue_type: Some("Story".to_string()),
+                priority: Some("Lowest".to_string()),
+                labels: vec!["ui".to_string()],
+                ..Default::default()
+            }]
+        );
+        assert!(parse_csv("Summary\nБез ключа\n").is_err());
+    }
+
+    #[test]
+    fn test_create_request_uses_mapping() {
+        let mut mapping = FieldMapping::load(None).unwrap();
+        mapping
+            .users
+            .insert("ann@example.com".to_string(), "ann".parse().unwrap());
+        let jira = JiraIssue {
+            key: "PROJ-1".to_string(),
+            summary: "Login fails".to_string(),
+            issue_type: Some("bug".to_string()),
+            priority: Some("Unknown".to_string()),
+            assignee: vec!["ann@example.com".to_string()],
+            labels: vec!["auth".to_string()],
+            ..Default::default()
+        };
+
+        let request = jira.to_create_request(&"TREK".parse().unwrap(), &mapping);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "queue": "TREK",
+                "summary": "Login fails",
+                "type": "bug",
+                "assignee": "ann",
+                "tags": ["auth"],
+                "unique": "jira-import:PROJ-1"
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_jira_reverse_maps_fields() {
+        let mapping = FieldMapping::load(None).unwrap();
+        let issue = testing::issue("TREK-5")
+            .summary("Экспорт")
+            .issue_type("newFeature", "Новая возможность")
+            .field("priority", json!({"key": "custom", "display": "Особый"}))
+            .assignee("ann", "Анна")
+            .tags(&["ui"])
+            .build();
+
+        let jira = to_jira(&issue, &mapping);
+        assert_eq!(jira["fields"]["issuetype"]["name"], "New Feature");
+        assert_eq!(jira["fields"]["priority"]["name"], "Особый");
+        assert_eq!(jira["fields"]["assignee"]["name"], "ann");
+        assert_eq!(jira["fields"]["labels"], json!(["ui"]));
+    }
+}
@@ -0,0 +1,155 @@
+//! Язык текстов, которые LLM пишет в Трекер
+//!
+//! Модель иногда отвечает по-английски даже на русские задачи. Если в секции
+//! `language` конфигурации задан язык организации, комментарии агента, подсказки
+//! разбора из `you serve` и сводки `you llm summarize-search` проверяются перед
+//! отправкой: текст на другом языке переводится вторым запросом к той же модели.
+
+use anyhow::Result;
+use llm_lib::LlmClientTrait;
+use serde::Deserialize;
+use tracing::{info, instrument};
+
+/// Меньше букв — слишком мало, чтобы судить о языке
+const MIN_LETTERS: usize = 12;
+
+/// Доля кириллицы среди букв, начиная с которой текст считается русским
+const CYRILLIC_SHARE: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentLanguage {
+    Ru,
+    En,
+}
+
+impl ContentLanguage {
+    /// Название языка для промпта перевода
+    fn name(self) -> &'static str {
+        match self {
+            ContentLanguage::Ru => "русский",
+            ContentLanguage::En => "английский",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LanguageConfig {
+    /// Язык организации; None — тексты LLM отправляются как есть
+    pub enforce: Option<ContentLanguage>,
+}
+
+/// Язык текста по доле кириллицы; блоки кода не учитываются
+///
+/// Возвращает `None`, если букв слишком мало, чтобы судить.
+pub fn detect(text: &str) -> Option<ContentLanguage> {
+    let mut in_code = false;
+    let (mut cyrillic, mut letters) = (0usize, 0usize);
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        for c in line.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            if matches!(c, '\u{0400}'..='\u{04FF}') {
+                cyrillic += 1;
+            }
+        }
+    }
+    if letters < MIN_LETTERS {
+        return None;
+    }
+    if cyrillic as f32 / letters as f32 >= CYRILLIC_SHARE {
+        Some(ContentLanguage::Ru)
+    } else {
+        Some(ContentLanguage::En)
+    }
+}
+
+/// Текст на языке `target`: перевод вторым запросом к LLM, если язык другой
+///
+/// Без `target` и для текстов, язык которых не определить, текст возвращается как есть.
+#[instrument(skip(llm, text))]
+pub async fn enforce<C: LlmClientTrait>(
+    llm: &C,
+    target: Option<ContentLanguage>,
+    text: &str,
+) -> Result<String> {
+    let Some(target) = target else {
+        return Ok(text.to_string());
+    };
+    let Some(detected) = detect(text) else {
+        return Ok(text.to_string());
+    };
+    if detected == target {
+        return Ok(text.to_string());
+    }
+
+    let system = format!(
+        "Ты переводчик рабочих текстов для трекера задач. Переведи текст на {} язык. \
+         Сохрани разметку Markdown, ключи задач (например, TREK-123), код, ссылки, \
+         логины и имена как есть. Ответь только переводом, без пояснений.",
+        target.name()
+    );
+    let translated = llm.complete_with_system(system, text.to_string()).await?;
+    info!(from = ?detected, to = ?target, "Текст LLM переведён на язык организации");
+    Ok(translated.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_lib::MockLlmClientTrait;
+
+    #[test]
+    fn test_detect_ignores_code_and_short_texts() {
+        assert_eq!(
+            detect("Проверил на стенде TREK-12, exporter работает"),
+            Some(ContentLanguage::Ru)
+        );
+        assert_eq!(
+            detect("Closing as fixed in TREK-12, verified on staging"),
+            Some(ContentLanguage::En)
+        );
+        assert_eq!(
+            detect("Исправлено, проверьте экспорт:\n```rust\nfn export_to_csv(issues: &[Issue]) -> Result<String>\n```"),
+            Some(ContentLanguage::Ru)
+        );
+        assert_eq!(detect("LGTM 👍"), None);
+    }
+
+    #[tokio::test]
+    async fn test_enforce_translates_only_other_language() {
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .withf(|system, prompt| {
+                system.contains("на русский язык") && prompt.starts_with("Fixed")
+            })
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async {
+                    Ok("Исправлено в TREK-12, проверено на стенде\n".to_string())
+                })
+            });
+
+        let ru = Some(ContentLanguage::Ru);
+        let translated = enforce(&llm, ru, "Fixed in TREK-12, verified on staging")
+            .await
+            .unwrap();
+        assert_eq!(translated, "Исправлено в TREK-12, проверено на стенде");
+
+        let russian = "Исправлено в TREK-12, проверено на стенде";
+        assert_eq!(enforce(&llm, ru, russian).await.unwrap(), russian);
+        assert_eq!(
+            enforce(&llm, None, "Fixed in TREK-12, verified on staging")
+                .await
+                .unwrap(),
+            "Fixed in TREK-12, verified on staging"
+        );
+    }
+}
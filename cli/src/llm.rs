@@ -1,7 +1,52 @@
-use anyhow::{Context, Result};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
 use clap::Subcommand;
-use llm_lib::{CompletionOptions, LlmClient, LlmClientTrait, LlmConfig, Message};
-use tracing::{info, instrument};
+use llm_lib::{
+    Branches, CompletionOptions, Conversation, FileInput, LlmClient, LlmClientTrait, LlmConfig,
+    Message, Role,
+};
+use tracing::{info, instrument, warn};
+use tracker_lib::ids::IssueKey;
+
+use crate::{
+    calendar,
+    config::Config,
+    estimate, eval, index, language,
+    output::{self, OutputFormat},
+    personas, plan, prep, prompts, settings,
+    snooze::Snoozes,
+    sprint, summarize, timezone,
+    today::{self, DayPlan},
+};
+
+/// Модель по умолчанию для запросов к LLM
+pub const DEFAULT_MODEL: &str = "anthropic/claude-3.5-sonnet";
+
+/// Вложения больше этого размера модели не принимают
+const MAX_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Типы вложений, которые понимают модели с поддержкой изображений
+const ATTACHMENT_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("webp", "image/webp"),
+    ("gif", "image/gif"),
+    ("pdf", "application/pdf"),
+];
+
+const DESCRIBE_PROMPT: &str = "Опиши вложение к задаче в трекере: что на нём изображено, \
+     какие видны ошибки, значения и подписи. Пиши кратко и по делу, в Markdown, \
+     чтобы описание можно было вставить в задачу.";
+
+const EXTRACT_PROMPT: &str = "Извлеки весь текст из вложения как есть, сохраняя порядок; \
+     списки и таблицы оформи в Markdown. Ответь только текстом вложения, без пояснений.";
 
 #[derive(Subcommand)]
 pub enum LlmCommands {
@@ -16,6 +61,187 @@ pub enum LlmCommands {
 
         #[arg(long)]
         max_tokens: Option<u32>,
+
+        /// Персона: системный промпт, модель и температура по умолчанию
+        #[arg(long)]
+        persona: Option<String>,
+
+        /// Записать ответ в файл вместо вывода в терминал
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Получать ответ по частям: с --out части сразу дописываются в файл, а в терминале
+        /// видна скорость генерации
+        #[arg(long)]
+        stream: bool,
+    },
+    /// План работы на сегодня по открытым задачам и встречам из календаря
+    PlanDay {
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Персона, которая составляет план
+        #[arg(long, default_value = personas::PROJECT_MANAGER)]
+        persona: String,
+
+        /// Сколько открытых задач учитывать
+        #[arg(short, long, default_value_t = plan::DEFAULT_LIMIT)]
+        limit: usize,
+    },
+    /// Оценить трудозатраты задачи по похожим закрытым задачам и записать оценку
+    Estimate {
+        /// Ключ задачи (например, TREK-123)
+        issue_key: IssueKey,
+
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Модель эмбеддингов для поиска похожих задач
+        #[arg(long)]
+        embedding_model: Option<String>,
+
+        /// Сколько похожих закрытых задач передать LLM
+        #[arg(short, long, default_value_t = 5)]
+        limit: usize,
+
+        /// Записать оценку без подтверждения
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Предложить состав спринта по бэклогу доски, оценкам и ёмкости команды
+    PlanSprint {
+        /// Идентификатор доски
+        #[arg(long)]
+        board: u64,
+
+        /// Спринт; по умолчанию ближайший запланированный спринт доски
+        #[arg(long)]
+        sprint: Option<u64>,
+
+        /// Запрос бэклога; по умолчанию нерешённые задачи доски вне спринтов
+        #[arg(short, long)]
+        query: Option<String>,
+
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Сколько задач бэклога учитывать
+        #[arg(short, long, default_value_t = sprint::DEFAULT_LIMIT)]
+        limit: usize,
+
+        /// Добавить задачи в спринт без подтверждения
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Формат вывода; с json задачи добавляются в спринт только с --yes
+        /// (схема — `you schema llm-plan-sprint`)
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+    /// Тематическая сводка по задачам из поиска: темы, риски и ответственные
+    SummarizeSearch {
+        /// Запрос на языке запросов Трекера (например, "Queue: TREK Status: open")
+        #[arg(short, long)]
+        query: String,
+
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Сколько найденных задач учитывать
+        #[arg(short, long, default_value_t = 200)]
+        limit: usize,
+
+        /// Размер одной части выборки в токенах контекста модели
+        #[arg(long, default_value_t = summarize::DEFAULT_CHUNK_TOKENS)]
+        chunk_tokens: usize,
+    },
+    /// Справка к встрече: общие с участниками задачи и их недавняя активность
+    Prep {
+        /// UID события из CALENDAR_ICS или его начало, если оно однозначно
+        event_id: String,
+
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Сколько общих задач учитывать
+        #[arg(short, long, default_value_t = prep::DEFAULT_LIMIT)]
+        limit: usize,
+    },
+    /// Выполнить именованный промпт из библиотеки (например, review или translate-ru)
+    Run {
+        /// Имя пресета (список — `you llm prompts`)
+        preset: String,
+
+        /// Переменная шаблона: имя=значение, значение @path читается из файла
+        #[arg(long = "var", value_parser = prompts::parse_var)]
+        vars: Vec<(String, String)>,
+
+        /// Текст для переменной {input}
+        input: Option<String>,
+
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Персона: её системный промпт идёт перед системным промптом пресета
+        #[arg(long)]
+        persona: Option<String>,
+    },
+    /// Показать пресеты промптов и их переменные
+    Prompts,
+    /// Прогнать пресеты на наборе примеров и сравнить итог с эталоном
+    Eval {
+        /// YAML файл с примерами и проверками
+        #[arg(long)]
+        suite: PathBuf,
+
+        /// Модель для всех примеров вместо указанных в наборе
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Сохранить итог как новый эталон
+        #[arg(long)]
+        update_baseline: bool,
+    },
+    /// Описать изображение или PDF из вложений задачи либо извлечь из него текст
+    DescribeAttachment {
+        /// Ключ задачи (например, TREK-123)
+        issue_key: String,
+
+        /// Идентификатор вложения
+        attachment_id: String,
+
+        /// Модель с поддержкой изображений
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Извлечь текст вместо описания
+        #[arg(long)]
+        extract_text: bool,
+    },
+    /// Диалог с LLM в терминале; пустая строка или Ctrl+D завершает его
+    ///
+    /// `/fork N [текст]` продолжает диалог в новой ветке с N-го запроса (по умолчанию
+    /// отправляет его ещё раз), `/branches` показывает ветки, `/branch N` переключает ветку,
+    /// `/new` начинает диалог заново.
+    Chat {
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Системный промпт нового диалога
+        #[arg(long)]
+        system: Option<String>,
+
+        /// Сохранять стенограмму в Markdown после каждого ответа
+        #[arg(long)]
+        save: Option<PathBuf>,
+
+        /// Продолжить диалог из сохранённой стенограммы (и дописывать в неё без --save)
+        #[arg(long, conflicts_with = "system")]
+        resume: Option<PathBuf>,
+
+        /// Персона: системный промпт нового диалога, модель и температура
+        #[arg(long)]
+        persona: Option<String>,
     },
 }
 
@@ -28,42 +254,669 @@ impl LlmCommands {
                 model,
                 temperature,
                 max_tokens,
+                persona,
+                out,
+                stream,
             } => {
-                let model = model.unwrap_or_else(|| "anthropic/claude-3.5-sonnet".to_string());
+                let persona = persona.as_deref().map(personas::load).transpose()?;
+                let persona = persona.unwrap_or_default();
+                let model = model
+                    .or(persona.model)
+                    .unwrap_or_else(|| DEFAULT_MODEL.to_string());
                 let config = LlmConfig::new(model)?;
                 let client = LlmClient::new(config)?;
-                let response = ask(&client, &prompt, temperature, max_tokens).await?;
-                println!("\n{}\n", response);
+                let system = Some(persona.system).filter(|system| !system.trim().is_empty());
+                let temperature = temperature.or(persona.temperature);
+                if stream {
+                    let (messages, options) = ask_request(&prompt, system, temperature, max_tokens);
+                    return ask_stream(&client, messages, options, out.as_deref()).await;
+                }
+                let response = ask(&client, &prompt, system, temperature, max_tokens).await?;
+                match out {
+                    Some(path) => {
+                        std::fs::write(&path, &response)
+                            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+                        println!("Ответ записан в {}", path.display());
+                    }
+                    None => println!("\n{}\n", response),
+                }
+                Ok(())
+            }
+            LlmCommands::PlanDay {
+                model,
+                persona,
+                limit,
+            } => {
+                let persona = personas::load(&persona)?;
+                let tracker = settings::tracker_client()?;
+                let zone = timezone::current();
+                let snoozes = Snoozes::load();
+                let fetch_limit = limit + snoozes.hidden_count(zone.today());
+                let mut issues =
+                    summarize::fetch_issues(&tracker, plan::PLAN_QUERY, fetch_limit).await?;
+                issues.retain(|issue| !snoozes.is_snoozed(&issue.key, zone.today()));
+                issues.truncate(limit);
+                let events = calendar_lib::load_from_env_in(zone).unwrap_or_else(|err| {
+                    warn!(error = %err, "Календарь недоступен, план строится только по задачам");
+                    Vec::new()
+                });
+                let context = plan::day_context(
+                    &issues,
+                    &calendar_lib::events_on(&events, zone.today()),
+                    zone,
+                    plan::CONTEXT_TOKENS,
+                );
+
+                let model = model
+                    .or_else(|| persona.model.clone())
+                    .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                let plan = plan::plan_day(&llm, &persona, &context).await?;
+                today::remember(&DayPlan::from_text(zone.today(), &plan, &issues));
+                println!("{plan}");
+                Ok(())
+            }
+            LlmCommands::Estimate {
+                issue_key,
+                model,
+                embedding_model,
+                limit,
+                yes,
+            } => {
+                let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let embedding_model =
+                    embedding_model.unwrap_or_else(|| index::DEFAULT_EMBEDDING_MODEL.to_string());
+                let tracker = settings::tracker_client()?;
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                let embeddings = LlmClient::new(LlmConfig::new(embedding_model)?)?;
+                estimate::run(&tracker, &llm, &embeddings, &issue_key, limit, yes).await
+            }
+            LlmCommands::PlanSprint {
+                board,
+                sprint,
+                query,
+                model,
+                limit,
+                yes,
+                output,
+            } => plan_sprint(board, sprint, query, model, limit, yes, output).await,
+            LlmCommands::SummarizeSearch {
+                query,
+                model,
+                limit,
+                chunk_tokens,
+            } => {
+                let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let tracker = settings::tracker_client()?;
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                let issues = summarize::fetch_issues(&tracker, &query, limit).await?;
+                let summary = summarize::summarize(&llm, &query, &issues, chunk_tokens).await?;
+                let language = Config::load()?.language.enforce;
+                let summary = language::enforce(&llm, language, &summary).await?;
+                println!("{summary}");
+                Ok(())
+            }
+            LlmCommands::Prep {
+                event_id,
+                model,
+                limit,
+            } => {
+                let zone = timezone::current();
+                let events = calendar_lib::load_from_env_in(zone)
+                    .context("Не удалось загрузить календарь")?;
+                let event = calendar::find_event(&events, &event_id, zone.now())?;
+                let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let tracker = settings::tracker_client()?;
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                let brief = prep::prepare(&tracker, &llm, event, limit).await?;
+                println!("{brief}");
+                Ok(())
+            }
+            LlmCommands::Run {
+                preset,
+                vars,
+                input,
+                model,
+                persona,
+            } => {
+                let persona = persona.as_deref().map(personas::load).transpose()?;
+                let library = prompts::library(&Config::load()?.prompts);
+                let Some(preset) = library.get(&preset) else {
+                    anyhow::bail!("Пресет «{preset}» не найден; список — `you llm prompts`");
+                };
+                let mut vars: HashMap<String, String> = vars.into_iter().collect();
+                if let Some(input) = input {
+                    vars.insert(prompts::INPUT_VAR.to_string(), input);
+                }
+                let mut rendered = preset.render(&vars)?;
+                if let Some(persona) = &persona {
+                    persona.apply(&mut rendered);
+                }
+
+                let model = model
+                    .or_else(|| preset.model.clone())
+                    .or_else(|| persona.and_then(|persona| persona.model))
+                    .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                println!("{}", rendered.send(&llm).await?);
+                Ok(())
+            }
+            LlmCommands::Chat {
+                model,
+                system,
+                save,
+                resume,
+                persona,
+            } => {
+                let persona = persona.as_deref().map(personas::load).transpose()?;
+                let persona = persona.unwrap_or_default();
+                let mut branches = match &resume {
+                    Some(path) => load_transcript(path)?,
+                    None => {
+                        // Системный промпт персоны идёт первым, --system — после него
+                        let system = [Some(persona.system.clone()), system]
+                            .into_iter()
+                            .flatten()
+                            .filter(|system| !system.trim().is_empty())
+                            .collect::<Vec<_>>()
+                            .join("\n\n");
+                        Branches::new(
+                            Some(system)
+                                .filter(|system| !system.is_empty())
+                                .map(Conversation::with_system)
+                                .unwrap_or_default(),
+                        )
+                    }
+                };
+                if resume.is_some() {
+                    println!(
+                        "Продолжаем диалог: {} сообщений, ветка {} из {}",
+                        branches.current().messages().len(),
+                        branches.current_index() + 1,
+                        branches.branches().len()
+                    );
+                }
+                let save = save.or(resume);
+
+                let model = model
+                    .or(persona.model)
+                    .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                let options = persona
+                    .temperature
+                    .map(|temperature| CompletionOptions::new().temperature(temperature));
+                let mut lines = std::io::stdin().lock().lines();
+                loop {
+                    print!("> ");
+                    std::io::stdout().flush()?;
+                    let Some(line) = lines.next().transpose()? else {
+                        break;
+                    };
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    let prompt = match ChatCommand::parse(&line) {
+                        None => line,
+                        Some(Err(err)) => {
+                            println!("{err}\n{CHAT_COMMANDS_HELP}");
+                            continue;
+                        }
+                        Some(Ok(command)) => match command.apply(&mut branches) {
+                            Ok((message, Some(prompt))) => {
+                                println!("{message}");
+                                prompt
+                            }
+                            Ok((message, None)) => {
+                                println!("{message}\n");
+                                continue;
+                            }
+                            Err(err) => {
+                                println!("{err}");
+                                continue;
+                            }
+                        },
+                    };
+                    let answer =
+                        chat_turn(&llm, branches.current_mut(), prompt, options.clone()).await?;
+                    println!("\n{answer}\n");
+                    if let Some(path) = &save {
+                        std::fs::write(path, branches.to_markdown())
+                            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+                    }
+                }
+                Ok(())
+            }
+            LlmCommands::DescribeAttachment {
+                issue_key,
+                attachment_id,
+                model,
+                extract_text,
+            } => {
+                let tracker = settings::tracker_client()?;
+                let (attachment, data) = tracker
+                    .download_attachment(&issue_key.parse()?, &attachment_id)
+                    .await?;
+                let file = attachment_file(&attachment.name, attachment.mimetype.as_deref(), data)?;
+                let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+                let llm = LlmClient::new(LlmConfig::new(model)?)?;
+                println!("{}", describe_attachment(&llm, file, extract_text).await?);
+                Ok(())
+            }
+            LlmCommands::Eval {
+                suite,
+                model,
+                update_baseline,
+            } => eval::run(&suite, model.as_deref(), update_baseline).await,
+            LlmCommands::Prompts => {
+                for (name, preset) in prompts::library(&Config::load()?.prompts) {
+                    let variables = preset
+                        .variables()
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    println!(
+                        "{name:<14} {:<45} {variables}",
+                        preset.description.as_deref().unwrap_or("")
+                    );
+                }
                 Ok(())
             }
         }
     }
 }
 
-#[instrument(skip(client))]
+/// Сценарий `you llm plan-sprint`: бэклог, ёмкость, предложение LLM и добавление в спринт
+async fn plan_sprint(
+    board: u64,
+    sprint_id: Option<u64>,
+    query: Option<String>,
+    model: Option<String>,
+    limit: usize,
+    yes: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let tracker = settings::tracker_client()?;
+    let target = sprint::target_sprint(&tracker, board, sprint_id).await?;
+    let (start, end) = sprint::sprint_dates(&target)?;
+    let name = target
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("{start} — {end}"));
+
+    let query =
+        query.unwrap_or_else(|| format!("Boards: {board} Sprint: empty() Resolution: empty()"));
+    let backlog = summarize::fetch_issues(&tracker, &query, limit).await?;
+    if backlog.is_empty() {
+        bail!("В бэклоге доски {board} нет задач");
+    }
+    let events = calendar_lib::load_from_env_in(timezone::current()).unwrap_or_else(|err| {
+        warn!(error = %err, "Календарь недоступен, ёмкость считается без встреч");
+        Vec::new()
+    });
+    let capacity = sprint::capacity(&Config::load()?.sprint, &events, start, end);
+
+    let model = model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let llm = LlmClient::new(LlmConfig::new(model)?)?;
+    let plan = sprint::propose(&llm, &name, &backlog, &capacity).await?;
+
+    let assign = match output {
+        OutputFormat::Json => {
+            output::print_json(&plan)?;
+            yes
+        }
+        OutputFormat::Text => {
+            println!("{}", sprint::render(&plan));
+            !plan.issues.is_empty()
+                && (yes || crate::tracker::confirm("\nДобавить задачи в спринт?"))
+        }
+    };
+    if assign {
+        let sprint_id = target.id.context("У спринта нет идентификатора")?;
+        sprint::assign(&tracker, sprint_id, &plan).await?;
+        if output == OutputFormat::Text {
+            println!("Задач добавлено в спринт: {}", plan.issues.len());
+        }
+    }
+    Ok(())
+}
+
+fn load_transcript(path: &Path) -> Result<Branches> {
+    let markdown = std::fs::read_to_string(path)
+        .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+    let branches = Branches::from_markdown(&markdown);
+    if branches
+        .branches()
+        .iter()
+        .all(|branch| branch.conversation.is_empty())
+    {
+        bail!("В {} нет сообщений диалога", path.display());
+    }
+    Ok(branches)
+}
+
+pub const CHAT_COMMANDS_HELP: &str = "Команды: /fork N [текст] — ветка с N-го запроса, \
+/branches — ветки, /branch N — переключить, /new — новый диалог";
+
+/// Команда диалога, начинающаяся с `/`: в `you llm chat` и на экране LLM в TUI
+#[derive(Debug, PartialEq)]
+pub enum ChatCommand {
+    /// Новая ветка перед запросом `number`; без `prompt` запрос отправляется ещё раз
+    Fork {
+        number: usize,
+        prompt: Option<String>,
+    },
+    Branches,
+    Switch(usize),
+    /// Новый диалог с тем же системным промптом
+    New,
+}
+
+impl ChatCommand {
+    /// None — строка не команда, а обычная реплика
+    pub fn parse(line: &str) -> Option<Result<Self>> {
+        let line = line.trim().strip_prefix('/')?;
+        let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+        let number = |value: &str| -> Result<usize> {
+            value
+                .parse()
+                .ok()
+                .filter(|number| *number > 0)
+                .with_context(|| format!("Ожидается номер, а не «{value}»"))
+        };
+        Some(match name {
+            "fork" => {
+                let (value, prompt) = rest.split_once(' ').unwrap_or((rest, ""));
+                number(value).map(|number| ChatCommand::Fork {
+                    number,
+                    prompt: Some(prompt.trim().to_string()).filter(|prompt| !prompt.is_empty()),
+                })
+            }
+            "branches" => Ok(ChatCommand::Branches),
+            "new" => Ok(ChatCommand::New),
+            "branch" => number(rest).map(ChatCommand::Switch),
+            _ => Err(anyhow::anyhow!("Неизвестная команда /{name}")),
+        })
+    }
+
+    /// Выполняет команду; возвращает сообщение для пользователя и запрос, который
+    /// нужно отправить в текущую ветку
+    pub fn apply(self, branches: &mut Branches) -> Result<(String, Option<String>)> {
+        match self {
+            ChatCommand::Fork { number, prompt } => {
+                let parent = branches.current_index();
+                let original = branches.fork_at_prompt(number)?;
+                let message = format!(
+                    "Ветка {} от ветки {} перед запросом {number}",
+                    branches.current_index() + 1,
+                    parent + 1
+                );
+                Ok((message, Some(prompt.unwrap_or(original))))
+            }
+            ChatCommand::Branches => {
+                let lines: Vec<String> = branches
+                    .branches()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, branch)| {
+                        let marker = if index == branches.current_index() {
+                            "*"
+                        } else {
+                            " "
+                        };
+                        let origin = match branch.parent {
+                            Some(parent) => format!(
+                                "от ветки {} после сообщения {}",
+                                parent + 1,
+                                branch.forked_at
+                            ),
+                            None => "основная".to_string(),
+                        };
+                        format!(
+                            "{marker} {}: {origin}, сообщений {}",
+                            index + 1,
+                            branch.conversation.messages().len()
+                        )
+                    })
+                    .collect();
+                Ok((lines.join("\n"), None))
+            }
+            ChatCommand::Switch(number) => {
+                branches.switch(number - 1)?;
+                let last = branches
+                    .current()
+                    .messages()
+                    .last()
+                    .map(|message| message.content.as_str())
+                    .unwrap_or("");
+                Ok((format!("Ветка {number}\n\n{last}"), None))
+            }
+            ChatCommand::New => {
+                let mut conversation = Conversation::new();
+                for message in branches.current().messages() {
+                    if message.role == Role::System {
+                        conversation.push(message.clone());
+                    }
+                }
+                *branches = Branches::new(conversation);
+                Ok(("Новый диалог".to_string(), None))
+            }
+        }
+    }
+}
+
+/// Отправить реплику с историей диалога; при ошибке история не меняется
+#[instrument(skip_all)]
+async fn chat_turn<T: LlmClientTrait>(
+    client: &T,
+    conversation: &mut Conversation,
+    input: String,
+    options: Option<CompletionOptions>,
+) -> Result<String> {
+    let user = Message::user(input);
+    let mut messages = conversation.messages().to_vec();
+    messages.push(user.clone());
+
+    let completion = client.chat_completion(messages, options).await?;
+    let answer = completion
+        .content()
+        .context("No content in response")?
+        .to_string();
+
+    conversation.push(user);
+    conversation.push(Message::assistant(answer.clone()));
+    Ok(answer)
+}
+
+/// Файл вложения для модели; тип берётся из Трекера, а без него — по расширению
+fn attachment_file(name: &str, mimetype: Option<&str>, data: Vec<u8>) -> Result<FileInput> {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let mime_type = mimetype
+        .filter(|mime| ATTACHMENT_TYPES.iter().any(|(_, known)| known == mime))
+        .or_else(|| {
+            ATTACHMENT_TYPES
+                .iter()
+                .find(|(known, _)| Some(*known) == extension.as_deref())
+                .map(|(_, mime)| *mime)
+        });
+    let Some(mime_type) = mime_type else {
+        bail!(
+            "Вложение «{name}» ({}) не поддерживается: нужно изображение PNG, JPEG, WebP, GIF или PDF",
+            mimetype.unwrap_or("тип неизвестен")
+        );
+    };
+    if data.len() > MAX_ATTACHMENT_BYTES {
+        bail!(
+            "Вложение «{name}» слишком большое: {} МБ, допустимо до {} МБ",
+            data.len() / (1024 * 1024),
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        );
+    }
+    Ok(FileInput::new(name, mime_type, data))
+}
+
+/// Описание вложения или его текст от модели с поддержкой изображений
+#[instrument(skip(client, file), fields(name = %file.filename, mime = %file.mime_type))]
+async fn describe_attachment<T: LlmClientTrait>(
+    client: &T,
+    file: FileInput,
+    extract_text: bool,
+) -> Result<String> {
+    let prompt = if extract_text {
+        EXTRACT_PROMPT
+    } else {
+        DESCRIBE_PROMPT
+    };
+    let messages = vec![Message::user_with_files(prompt, vec![file])];
+    let completion = client.chat_completion(messages, None).await?;
+    Ok(completion
+        .content()
+        .context("No content in response")?
+        .trim()
+        .to_string())
+}
+
+#[instrument(skip(client, system))]
 async fn ask<T: LlmClientTrait>(
     client: &T,
     prompt: &str,
+    system: Option<String>,
     temperature: Option<f32>,
     max_tokens: Option<u32>,
 ) -> Result<String> {
     info!("Sending request to LLM");
 
-    let response = if let (Some(temp), Some(tokens)) = (temperature, max_tokens) {
-        let options = CompletionOptions::new()
-            .temperature(temp)
-            .max_tokens(tokens);
-        let messages = vec![Message::user(prompt.to_string())];
-        let completion = client.chat_completion(messages, Some(options)).await?;
-        completion
-            .content()
-            .context("No content in response")?
-            .to_string()
+    if system.is_none() && temperature.is_none() && max_tokens.is_none() {
+        return Ok(client.complete(prompt.to_string()).await?);
+    }
+
+    let (messages, options) = ask_request(prompt, system, temperature, max_tokens);
+    let completion = client.chat_completion(messages, Some(options)).await?;
+    Ok(completion
+        .content()
+        .context("No content in response")?
+        .to_string())
+}
+
+fn ask_request(
+    prompt: &str,
+    system: Option<String>,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+) -> (Vec<Message>, CompletionOptions) {
+    let mut options = CompletionOptions::new();
+    if let Some(temperature) = temperature {
+        options = options.temperature(temperature);
+    }
+    if let Some(tokens) = max_tokens {
+        options = options.max_tokens(tokens);
+    }
+    let messages = system
+        .map(Message::system)
+        .into_iter()
+        .chain([Message::user(prompt.to_string())])
+        .collect();
+    (messages, options)
+}
+
+/// Как часто обновлять строку со скоростью генерации
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Потоковый ответ: части пишутся в `out` по мере получения (или в терминал без `out`),
+/// поэтому обрыв соединения не теряет уже сгенерированный текст
+#[instrument(skip_all)]
+async fn ask_stream(
+    client: &LlmClient,
+    messages: Vec<Message>,
+    options: CompletionOptions,
+    out: Option<&Path>,
+) -> Result<()> {
+    let mut file = out
+        .map(|path| {
+            std::fs::File::create(path)
+                .with_context(|| format!("Не удалось создать {}", path.display()))
+        })
+        .transpose()?;
+    let started = Instant::now();
+    let mut printed = started;
+    let mut tokens = 0;
+    let mut write_error = None;
+    let completion = client
+        .chat_completion_stream(messages, Some(options), |delta| {
+            tokens += llm_lib::context::estimate_tokens(delta);
+            let Some(file) = &mut file else {
+                print!("{delta}");
+                let _ = std::io::stdout().flush();
+                return;
+            };
+            if write_error.is_none() {
+                // Сбрасываем каждую часть, чтобы файл был полным на момент обрыва
+                write_error = file
+                    .write_all(delta.as_bytes())
+                    .and_then(|()| file.flush())
+                    .err();
+            }
+            if printed.elapsed() >= PROGRESS_INTERVAL {
+                printed = Instant::now();
+                eprint!("\r{}", stream_progress(tokens, started.elapsed()));
+            }
+        })
+        .await;
+    if let (Some(err), Some(path)) = (write_error, out) {
+        return Err(err).with_context(|| format!("Не удалось записать {}", path.display()));
+    }
+    let completion = completion?;
+    let tokens = completion
+        .usage
+        .map(|usage| usage.completion_tokens as usize)
+        .unwrap_or(tokens);
+    match out {
+        Some(path) => eprintln!(
+            "\r{} → {}",
+            stream_progress(tokens, started.elapsed()),
+            path.display()
+        ),
+        None => println!("\n"),
+    }
+    if completion.finish_reason.as_deref() == Some("length") {
+        warn!("Ответ обрезан по лимиту токенов");
+        eprintln!("Ответ обрезан по лимиту токенов (--max-tokens)");
+    }
+    Ok(())
+}
+
+/// Строка прогресса: сколько токенов получено и с какой скоростью
+fn stream_progress(tokens: usize, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64();
+    let rate = if seconds > 0.0 {
+        tokens as f64 / seconds
     } else {
-        client.complete(prompt.to_string()).await?
+        0.0
     };
+    format!("{tokens} токенов за {seconds:.1} с, {rate:.1} ток/с")
+}
 
-    Ok(response)
+/// JSON объект из ответа LLM, даже если он обёрнут в текст или блок кода
+///
+/// Закрывающая скобка ищется только после открывающей, поэтому `}` в пояснении
+/// перед объектом не ломает разбор.
+pub fn extract_json_object(response: &str) -> Result<&str> {
+    extract_json(response, '{', '}').context("В ответе LLM нет JSON объекта")
+}
+
+/// JSON массив из ответа LLM; как [`extract_json_object`], но для `[...]`
+pub fn extract_json_array(response: &str) -> Result<&str> {
+    extract_json(response, '[', ']').context("В ответе LLM нет JSON массива")
+}
+
+fn extract_json(response: &str, open: char, close: char) -> Option<&str> {
+    let start = response.find(open)?;
+    let end = start + response[start..].rfind(close)?;
+    Some(&response[start..=end])
 }
 
 #[cfg(test)]
@@ -71,6 +924,17 @@ mod tests {
     use super::*;
     use llm_lib::MockLlmClientTrait;
 
+    #[test]
+    fn test_extract_json_ignores_closing_brace_before_object() {
+        assert_eq!(
+            extract_json_object("Смайлик :} и ответ: {\"a\": 1} готово").unwrap(),
+            "{\"a\": 1}"
+        );
+        assert_eq!(extract_json_array("] [\"x\"]").unwrap(), "[\"x\"]");
+        assert!(extract_json_object("} без объекта {").is_err());
+        assert!(extract_json_object("без скобок").is_err());
+    }
+
     #[tokio::test]
     async fn test_ask_with_minimal_arguments() {
         let mut mock_client = MockLlmClientTrait::new();
@@ -79,7 +943,7 @@ mod tests {
             .times(1)
             .returning(|_| Box::pin(async { Ok("Hello, world!".to_string()) }));
 
-        let result = ask(&mock_client, "test prompt", None, None).await;
+        let result = ask(&mock_client, "test prompt", None, None, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "Hello, world!");
@@ -111,9 +975,171 @@ mod tests {
                 })
             });
 
-        let result = ask(&mock_client, "test prompt", Some(0.7), Some(150)).await;
+        let result = ask(&mock_client, "test prompt", None, Some(0.7), Some(150)).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "Response with options");
     }
+
+    #[tokio::test]
+    async fn test_ask_with_persona_system_prompt() {
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_chat_completion()
+            .withf(|messages, options| {
+                messages.len() == 2
+                    && messages[0].content == "Ты руководитель проектов"
+                    && messages[1].content == "test prompt"
+                    && options.as_ref().and_then(|o| o.temperature) == Some(0.3)
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(completion("План")) }));
+
+        let result = ask(
+            &mock_client,
+            "test prompt",
+            Some("Ты руководитель проектов".to_string()),
+            Some(0.3),
+            None,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "План");
+    }
+
+    fn completion(content: &str) -> llm_lib::ChatCompletionResponse {
+        llm_lib::ChatCompletionResponse {
+            id: "test-id".to_string(),
+            model: "test-model".to_string(),
+            choices: vec![llm_lib::Choice {
+                index: 0,
+                message: Message::assistant(content),
+                finish_reason: Some("stop".to_string()),
+            }],
+            usage: llm_lib::Usage {
+                prompt_tokens: 10,
+                completion_tokens: 20,
+                total_tokens: 30,
+            },
+            created: 1234567890,
+        }
+    }
+
+    #[test]
+    fn test_stream_progress() {
+        assert_eq!(
+            stream_progress(150, Duration::from_millis(2500)),
+            "150 токенов за 2.5 с, 60.0 ток/с"
+        );
+        assert_eq!(
+            stream_progress(0, Duration::ZERO),
+            "0 токенов за 0.0 с, 0.0 ток/с"
+        );
+    }
+
+    #[test]
+    fn test_chat_commands() {
+        assert!(ChatCommand::parse("обычный вопрос").is_none());
+        assert_eq!(
+            ChatCommand::parse("/fork 3").unwrap().unwrap(),
+            ChatCommand::Fork {
+                number: 3,
+                prompt: None
+            }
+        );
+        assert_eq!(
+            ChatCommand::parse("/fork 2 а если короче?")
+                .unwrap()
+                .unwrap(),
+            ChatCommand::Fork {
+                number: 2,
+                prompt: Some("а если короче?".to_string())
+            }
+        );
+        assert_eq!(
+            ChatCommand::parse("/branch 1").unwrap().unwrap(),
+            ChatCommand::Switch(1)
+        );
+        assert!(ChatCommand::parse("/branch 0").unwrap().is_err());
+        assert!(ChatCommand::parse("/undo").unwrap().is_err());
+
+        let mut branches = Branches::new(Conversation::from_markdown(
+            "## User\n\nКак в Rust?\n\n## Assistant\n\nЧерез async\n",
+        ));
+        let (message, prompt) = ChatCommand::Fork {
+            number: 1,
+            prompt: None,
+        }
+        .apply(&mut branches)
+        .unwrap();
+        assert_eq!(message, "Ветка 2 от ветки 1 перед запросом 1");
+        assert_eq!(prompt.as_deref(), Some("Как в Rust?"));
+        assert_eq!(branches.current_index(), 1);
+        assert!(branches.current().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_chat_turn_sends_history_and_keeps_it_on_error() {
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_chat_completion()
+            .withf(|messages, _| messages.len() == 3 && messages[2].content == "А в Go?")
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(completion("Через горутины")) }));
+        mock_client
+            .expect_chat_completion()
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async { Err(llm_lib::LlmError::InvalidRequest("сбой".to_string())) })
+            });
+
+        let mut conversation = Conversation::from_markdown(
+            "# Chat transcript\n\n## User\n\nКак в Rust?\n\n## Assistant\n\nЧерез async\n",
+        );
+        let answer = chat_turn(&mock_client, &mut conversation, "А в Go?".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(answer, "Через горутины");
+        assert_eq!(conversation.messages().len(), 4);
+
+        assert!(
+            chat_turn(&mock_client, &mut conversation, "Ещё".to_string(), None)
+                .await
+                .is_err()
+        );
+        assert_eq!(conversation.messages().len(), 4);
+    }
+
+    #[test]
+    fn test_attachment_file_checks_type_and_size() {
+        let file = attachment_file("screen.PNG", None, vec![1, 2, 3]).unwrap();
+        assert_eq!(file.mime_type, "image/png");
+        let file = attachment_file("scan", Some("application/pdf"), vec![1]).unwrap();
+        assert_eq!(file.mime_type, "application/pdf");
+
+        let err = attachment_file("logs.zip", Some("application/zip"), vec![1])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("logs.zip") && err.contains("application/zip"));
+        assert!(attachment_file("big.png", None, vec![0; MAX_ATTACHMENT_BYTES + 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_describe_attachment_sends_file() {
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_chat_completion()
+            .withf(|messages, _| {
+                messages.len() == 1
+                    && messages[0].content == EXTRACT_PROMPT
+                    && messages[0].files.len() == 1
+                    && messages[0].files[0].filename == "scan.pdf"
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(completion("Акт приёмки\n")) }));
+
+        let file = FileInput::new("scan.pdf", "application/pdf", b"%PDF-".to_vec());
+        let text = describe_attachment(&mock_client, file, true).await.unwrap();
+        assert_eq!(text, "Акт приёмки");
+    }
 }
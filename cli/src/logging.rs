@@ -0,0 +1,96 @@
+//! Журналирование: JSON-трейсы в файл с ротацией и консоль по флагу `-v`
+//!
+//! Файлы пишутся в `$XDG_STATE_HOME/you/logs` (по умолчанию `~/.local/state/you/logs`)
+//! и ротируются раз в сутки, поэтому даже сессии TUI, где консоль занята
+//! интерфейсом, оставляют журнал для разбора проблем.
+
+use anyhow::{Context, Result};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::paths;
+
+const LOG_DIR: &str = "logs";
+const LOG_PREFIX: &str = "you";
+/// Сколько суточных файлов хранить
+const MAX_LOG_FILES: usize = 7;
+/// Уровни для файла: подробно для своих крейтов, без шума HTTP-стека
+const FILE_DIRECTIVES: &str = "info,cli=debug,core_lib=debug,tracker_lib=debug,llm_lib=debug,\
+                               calendar_lib=debug,workflows_lib=debug";
+
+/// Уровень консольного журнала по количеству флагов `-v`
+fn console_directive(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Настраивает журналирование на время работы процесса
+///
+/// `console` выключают для TUI, который сам занимает терминал. `RUST_LOG`, если задан,
+/// переопределяет уровень консоли. Возвращённый guard нужно держать до выхода,
+/// иначе хвост журнала не успеет записаться в файл.
+pub fn init(verbose: u8, console: bool) -> Option<WorkerGuard> {
+    let (file_layer, guard) = match file_writer() {
+        Ok((writer, guard)) => {
+            let layer = fmt::layer()
+                .json()
+                .with_writer(writer)
+                .with_filter(EnvFilter::new(FILE_DIRECTIVES));
+            (Some(layer), Some(guard))
+        }
+        Err(err) => {
+            eprintln!("Журнал в файл отключён: {err:#}");
+            (None, None)
+        }
+    };
+
+    let console_layer = console.then(|| {
+        let filter = EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(console_directive(verbose)));
+        fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter)
+    });
+
+    tracing_subscriber::registry()
+        .with(file_layer)
+        .with(console_layer)
+        .init();
+    guard
+}
+
+fn file_writer() -> Result<(tracing_appender::non_blocking::NonBlocking, WorkerGuard)> {
+    let dir = paths::state_dir()
+        .context("Не удалось определить каталог для журналов")?
+        .join(LOG_DIR);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Не удалось создать каталог {}", dir.display()))?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .with_context(|| format!("Не удалось открыть журнал в {}", dir.display()))?;
+    Ok(tracing_appender::non_blocking(appender))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_verbosity_levels() {
+        assert_eq!(console_directive(0), "warn");
+        assert_eq!(console_directive(1), "info");
+        assert_eq!(console_directive(2), "debug");
+        assert_eq!(console_directive(5), "trace");
+    }
+}
@@ -1,9 +1,64 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use tracing::info;
 
+mod agent;
+mod api;
+mod calendar;
+mod config;
+mod doctor;
+mod drafts;
+mod email;
+mod estimate;
+mod eval;
+mod feed;
+mod hooks;
+mod inbox;
+mod index;
+mod jira;
+mod language;
+mod logging;
+mod metrics;
+mod notify;
+mod org;
+mod output;
+mod paths;
+mod people;
+mod personas;
+mod plan;
+mod prep;
+mod presets;
+mod progress;
+mod prompts;
+mod prs;
+mod queues;
+mod redact;
+mod reminders;
+mod render;
+mod report;
+mod sandbox;
+mod schedule;
+mod search;
+mod secrets;
+mod serve;
+mod settings;
+mod sinks;
+mod snooze;
+mod sprint;
+mod stale;
+mod summarize;
+mod tags;
+mod templates;
+#[cfg(test)]
+mod testing;
+mod timezone;
+mod titles;
+mod today;
+
 mod tracker;
 use tracker::TrackerCommands;
 
+mod triage;
+
 mod llm;
 use llm::LlmCommands;
 
@@ -13,6 +68,26 @@ mod tui;
 #[command(name = "you")]
 #[command(about = "CLI утилита для управления рабочими задачами", long_about = None)]
 struct Cli {
+    /// Подробнее выводить журнал в консоль (-v — info, -vv — debug, -vvv — trace)
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Только чтение: запросы, изменяющие данные в Трекере, отклоняются
+    #[arg(long, global = true)]
+    read_only: bool,
+
+    /// Песочница: тестовая организация и очередь из секции sandbox конфигурации
+    #[arg(long, global = true)]
+    sandbox: bool,
+
+    /// Не скрывать почту, телефоны, хосты и секреты в тексте задач, отправляемом в LLM
+    #[arg(long, global = true)]
+    no_redact: bool,
+
+    /// Часовой пояс для календаря, плана дня и сроков задач: имя IANA (например, Asia/Novosibirsk) или local
+    #[arg(long, global = true, value_name = "ZONE")]
+    tz: Option<calendar_lib::Zone>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -29,28 +104,120 @@ enum Commands {
         #[command(subcommand)]
         command: LlmCommands,
     },
+    /// Отчёты: заметки о выпуске
+    Report {
+        #[command(subcommand)]
+        command: report::ReportCommands,
+    },
+    /// Сохранённые поиски задач
+    Search {
+        #[command(subcommand)]
+        command: search::SearchCommands,
+    },
+    /// Чеклист на сегодня по плану дня
+    Today {
+        #[command(subcommand)]
+        command: today::TodayCommands,
+    },
+    /// Календарь: задачи по встречам
+    Calendar {
+        #[command(subcommand)]
+        command: calendar::CalendarCommands,
+    },
+    /// Черновики комментариев и запросов из TUI
+    Drafts {
+        #[command(subcommand)]
+        command: drafts::DraftCommands,
+    },
+    /// Поручение агенту: LLM сама вызывает функции Трекера и календаря
+    Agent(agent::AgentArgs),
+    /// Входящие: упоминания, назначения и смены статусов по моим задачам
+    Inbox(inbox::InboxArgs),
+    /// Сервер для вебхуков Трекера: уведомления, разбор LLM и скрипты по событиям
+    Serve(serve::ServeArgs),
+    /// Загрузить пользователей, очереди и статусы Трекера в локальный справочник
+    Sync(org::SyncArgs),
+    /// Локальный REST API для виджетов: мои задачи, повестка дня, быстрый комментарий
+    Api(api::ApiArgs),
+    /// Проверка окружения: доступ к Трекеру, OpenRouter, прокси и календарю
+    Doctor(doctor::DoctorArgs),
+    /// JSON Schema вывода команды с `--output json`
+    Schema(output::SchemaArgs),
     /// Интерактивный TUI режим
     Tui,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Инициализация трейсинга
-    use tracing_subscriber::{fmt, EnvFilter};
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let command_name = hooks::command_name(&matches);
 
-    fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+    // TUI занимает терминал, поэтому в консоль журнал пишут только обычные команды
+    let console_logs = !matches!(cli.command, Commands::Tui);
+    let _log_guard = logging::init(cli.verbose, console_logs);
 
     info!("Запуск CLI");
 
-    let cli = Cli::parse();
+    // Ошибка в конфигурации не должна молча выключать режим только для чтения
+    let read_only = cli.read_only || config::Config::load()?.read_only;
+    if read_only {
+        info!("Включён режим только для чтения");
+    }
+    // Токен и организация песочницы подставляются при создании каждого клиента Трекера
+    let sandbox = if cli.sandbox {
+        let sandbox = config::Config::load()?
+            .sandbox
+            .activate(core_lib::credentials::resolve)?;
+        if console_logs {
+            eprintln!("{}", sandbox::banner(&sandbox));
+        }
+        Some(sandbox)
+    } else {
+        None
+    };
+    if cli.no_redact {
+        info!("Скрытие данных перед отправкой в LLM отключено");
+    }
+    if let Some(zone) = cli.tz {
+        info!(%zone, "Задан часовой пояс");
+    }
+    settings::init(settings::Settings {
+        read_only,
+        sandbox,
+        no_redact: cli.no_redact,
+        timezone: cli.tz,
+    });
+
+    // Хуки из неразборчивой конфигурации не пропускаются молча: команда не запускается
+    let hooks = config::Config::load()?
+        .hooks
+        .remove(&command_name)
+        .unwrap_or_default();
+    hooks.run_pre(&command_name)?;
+    run(cli.command).await?;
+    hooks.run_post(&command_name);
+
+    Ok(())
+}
 
-    match cli.command {
+/// Выполняет выбранную команду; хуки вокруг неё запускает `main`
+async fn run(command: Commands) -> anyhow::Result<()> {
+    match command {
         Commands::Tracker { command } => command.execute().await?,
         Commands::Llm { command } => command.execute().await?,
+        Commands::Report { command } => command.execute().await?,
+        Commands::Search { command } => command.execute().await?,
+        Commands::Today { command } => command.execute().await?,
+        Commands::Calendar { command } => command.execute().await?,
+        Commands::Drafts { command } => command.execute()?,
+        Commands::Agent(args) => args.execute().await?,
+        Commands::Inbox(args) => args.execute().await?,
+        Commands::Serve(args) => args.execute().await?,
+        Commands::Sync(args) => args.execute().await?,
+        Commands::Api(args) => args.execute().await?,
+        Commands::Doctor(args) => args.execute().await?,
+        Commands::Schema(args) => args.execute()?,
         Commands::Tui => tui::run_tui().await?,
     }
 
@@ -0,0 +1,275 @@
+//! Метрики Prometheus для режима сервера
+//!
+//! Счётчики пополняются из событий клиентов Трекера и LLM и из обработчика
+//! вебхуков; `/metrics` отдаёт их в текстовом формате вместе с давностью
+//! последнего опроса входящих и синхронизации индекса.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use llm_lib::events::LlmEvent;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tracker_lib::events::TrackerEvent;
+
+use crate::{inbox::Inbox, index::IssueIndex};
+
+#[derive(Debug, Default)]
+struct LlmStats {
+    requests: u64,
+    errors: u64,
+    tokens: u64,
+}
+
+/// Метрика LLM: имя, описание и значение из статистики модели
+type LlmMetric = (&'static str, &'static str, fn(&LlmStats) -> u64);
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    tracker_requests: AtomicU64,
+    tracker_errors: AtomicU64,
+    llm: Mutex<BTreeMap<String, LlmStats>>,
+    /// Принятые вебхуки по коду ответа
+    webhooks: Mutex<BTreeMap<u16, u64>>,
+}
+
+/// Давность синхронизаций в секундах; None, если синхронизации не было
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SyncLag {
+    pub inbox_poll: Option<f64>,
+    pub index_sync: Option<f64>,
+}
+
+impl SyncLag {
+    /// Давность по сохранённым входящим и локальному индексу
+    pub fn current() -> Self {
+        let now = Utc::now();
+        let inbox_poll = Inbox::load()
+            .last_poll_time()
+            .map(|at| seconds_between(at.with_timezone(&Utc), now));
+        // Индекс хранит только дату, поэтому отсчёт идёт от её начала
+        let index_sync = IssueIndex::open_default()
+            .ok()
+            .and_then(|index| index.last_sync().ok().flatten())
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .and_then(|start| start.and_local_timezone(Local).earliest())
+            .map(|at| seconds_between(at.with_timezone(&Utc), now));
+        Self {
+            inbox_poll,
+            index_sync,
+        }
+    }
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds().max(0) as f64 / 1000.0
+}
+
+impl Metrics {
+    pub fn record_tracker(&self, event: &TrackerEvent) {
+        if let TrackerEvent::RequestFinished { success, .. } = event {
+            self.tracker_requests.fetch_add(1, Ordering::Relaxed);
+            if !success {
+                self.tracker_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn record_llm(&self, event: &LlmEvent) {
+        if let LlmEvent::RequestFinished {
+            model,
+            success,
+            total_tokens,
+        } = event
+        {
+            let mut llm = self.llm.lock().unwrap_or_else(|err| err.into_inner());
+            let stats = llm.entry(model.clone()).or_default();
+            stats.requests += 1;
+            if !success {
+                stats.errors += 1;
+            }
+            stats.tokens += u64::from(total_tokens.unwrap_or(0));
+        }
+    }
+
+    pub fn record_webhook(&self, status: u16) {
+        let mut webhooks = self.webhooks.lock().unwrap_or_else(|err| err.into_inner());
+        *webhooks.entry(status).or_default() += 1;
+    }
+
+    /// Отправитель событий Трекера, который пополняет счётчики
+    pub fn tracker_sender(self: &Arc<Self>) -> UnboundedSender<TrackerEvent> {
+        let (sender, mut receiver) = unbounded_channel();
+        let metrics = Arc::clone(self);
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                metrics.record_tracker(&event);
+            }
+        });
+        sender
+    }
+
+    /// Отправитель событий LLM, который пополняет счётчики
+    pub fn llm_sender(self: &Arc<Self>) -> UnboundedSender<LlmEvent> {
+        let (sender, mut receiver) = unbounded_channel();
+        let metrics = Arc::clone(self);
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                metrics.record_llm(&event);
+            }
+        });
+        sender
+    }
+
+    /// Метрики в текстовом формате Prometheus
+    pub fn render(&self, lag: SyncLag) -> String {
+        let mut out = String::new();
+
+        metric_header(
+            &mut out,
+            "you_webhooks_received_total",
+            "counter",
+            "Принятые вебхуки по коду ответа",
+        );
+        let webhooks = self.webhooks.lock().unwrap_or_else(|err| err.into_inner());
+        for (status, count) in webhooks.iter() {
+            let _ = writeln!(
+                out,
+                "you_webhooks_received_total{{status=\"{status}\"}} {count}"
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "you_tracker_requests_total",
+            "counter",
+            "Запросы к API Трекера",
+        );
+        let _ = writeln!(
+            out,
+            "you_tracker_requests_total {}",
+            self.tracker_requests.load(Ordering::Relaxed)
+        );
+        metric_header(
+            &mut out,
+            "you_tracker_request_errors_total",
+            "counter",
+            "Запросы к API Трекера, завершившиеся ошибкой",
+        );
+        let _ = writeln!(
+            out,
+            "you_tracker_request_errors_total {}",
+            self.tracker_errors.load(Ordering::Relaxed)
+        );
+
+        let llm = self.llm.lock().unwrap_or_else(|err| err.into_inner());
+        let llm_metrics: [LlmMetric; 3] = [
+            ("you_llm_requests_total", "Запросы к LLM", |s| {
+                s.requests
+            }),
+            (
+                "you_llm_request_errors_total",
+                "Запросы к LLM, завершившиеся ошибкой",
+                |s| s.errors,
+            ),
+            (
+                "you_llm_tokens_total",
+                "Потраченные токены LLM",
+                |s| s.tokens,
+            ),
+        ];
+        for (name, help, value) in llm_metrics {
+            metric_header(&mut out, name, "counter", help);
+            for (model, stats) in llm.iter() {
+                let _ = writeln!(
+                    out,
+                    "{name}{{model=\"{}\"}} {}",
+                    escape_label(model),
+                    value(stats)
+                );
+            }
+        }
+
+        let lags = [
+            (
+                "you_inbox_poll_age_seconds",
+                "Секунд с последнего опроса входящих",
+                lag.inbox_poll,
+            ),
+            (
+                "you_index_sync_age_seconds",
+                "Секунд с последней синхронизации индекса",
+                lag.index_sync,
+            ),
+        ];
+        for (name, help, value) in lags {
+            if let Some(value) = value {
+                metric_header(&mut out, name, "gauge", help);
+                let _ = writeln!(out, "{name} {value}");
+            }
+        }
+
+        out
+    }
+}
+
+fn metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_events() {
+        let metrics = Metrics::default();
+        metrics.record_tracker(&TrackerEvent::RequestStarted {
+            path: "issues/TREK-1".to_string(),
+        });
+        for success in [true, false] {
+            metrics.record_tracker(&TrackerEvent::RequestFinished {
+                path: "issues/TREK-1".to_string(),
+                success,
+            });
+        }
+        metrics.record_llm(&LlmEvent::RequestFinished {
+            model: "openai/gpt-4o".to_string(),
+            success: true,
+            total_tokens: Some(120),
+        });
+        metrics.record_webhook(202);
+        metrics.record_webhook(202);
+        metrics.record_webhook(401);
+
+        let text = metrics.render(SyncLag {
+            inbox_poll: Some(30.0),
+            index_sync: None,
+        });
+
+        assert!(text.contains("you_tracker_requests_total 2\n"));
+        assert!(text.contains("you_tracker_request_errors_total 1\n"));
+        assert!(text.contains("you_llm_tokens_total{model=\"openai/gpt-4o\"} 120\n"));
+        assert!(text.contains("you_llm_request_errors_total{model=\"openai/gpt-4o\"} 0\n"));
+        assert!(text.contains("you_webhooks_received_total{status=\"202\"} 2\n"));
+        assert!(text.contains("you_webhooks_received_total{status=\"401\"} 1\n"));
+        assert!(text
+            .contains("# TYPE you_inbox_poll_age_seconds gauge\nyou_inbox_poll_age_seconds 30\n"));
+        assert!(!text.contains("you_index_sync_age_seconds"));
+    }
+}
@@ -0,0 +1,267 @@
+//! Уведомления на рабочем столе
+//!
+//! [`Notifier`] скрывает различия систем: в Linux уведомление показывает `notify-send`,
+//! в macOS — `osascript`, а в WSL — `powershell.exe`, потому что D-Bus внутри WSL не
+//! доходит до Windows. Без графического окружения (например, по SSH), при ошибке запуска
+//! или с `notify.desktop: false` вместо уведомления звенит терминальный колокольчик;
+//! TUI вдобавок показывает всплывающее сообщение в строке состояния.
+//!
+//! Уведомления отправляют `you serve` (правила `notify` и наступившие напоминания) и TUI
+//! (напоминания).
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::{inbox::Notification, sinks::SinkRule};
+
+/// Идентификатор приложения PowerShell: без зарегистрированного AppID Windows молча
+/// отбрасывает уведомление
+const POWERSHELL_APP_ID: &str =
+    "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+
+/// Показ уведомления через WinRT; текст приходит в переменных окружения, чтобы не
+/// экранировать его для PowerShell
+const POWERSHELL_SCRIPT: &str = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null
+$Manager = [Windows.UI.Notifications.ToastNotificationManager]
+$Template = $Manager::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+$Text = $Template.GetElementsByTagName('text')
+$Text.Item(0).AppendChild($Template.CreateTextNode($env:YOU_NOTIFY_TITLE)) > $null
+$Text.Item(1).AppendChild($Template.CreateTextNode($env:YOU_NOTIFY_BODY)) > $null
+$Manager::CreateToastNotifier($env:YOU_NOTIFY_APP).Show([Windows.UI.Notifications.ToastNotification]::new($Template))";
+
+/// Переменные с текстом уведомления, которые WSL передаёт в PowerShell
+const WSLENV_VARS: &str = "YOU_NOTIFY_TITLE:YOU_NOTIFY_BODY:YOU_NOTIFY_APP";
+
+/// Секция `notify` конфигурации
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Показывать уведомления на рабочем столе; `false` — только колокольчик
+    pub desktop: bool,
+
+    /// Звенеть колокольчиком, если уведомление на рабочем столе не показано
+    pub bell: bool,
+
+    /// Куда `you serve` дополнительно пересылает уведомления: Slack и Telegram
+    pub sinks: Vec<SinkRule>,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            desktop: true,
+            bell: true,
+            sinks: Vec::new(),
+        }
+    }
+}
+
+/// Способ показа уведомления
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    NotifySend,
+    AppleScript,
+    Wsl,
+    /// Рабочего стола нет или уведомления выключены
+    Terminal,
+}
+
+/// Как было доставлено уведомление
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Desktop,
+    Bell,
+    /// Уведомления на рабочем столе нет, колокольчик выключен
+    Silent,
+}
+
+/// Отправитель уведомлений
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    backend: Backend,
+    bell: bool,
+}
+
+impl Notifier {
+    pub fn new(config: &NotifyConfig) -> Self {
+        let backend = if config.desktop {
+            detect(cfg!(target_os = "macos"), |name| {
+                std::env::var_os(name).is_some()
+            })
+        } else {
+            Backend::Terminal
+        };
+        debug!(?backend, "Способ показа уведомлений");
+        Self {
+            backend,
+            bell: config.bell,
+        }
+    }
+
+    /// Показать уведомление; при неудаче — колокольчик
+    pub fn send(&self, title: &str, body: &str) -> Delivery {
+        if let Some(mut command) = command(self.backend, title, body) {
+            let spawned = command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match spawned {
+                Ok(mut child) => {
+                    // Процесс показа завершается сам; ожидание в отдельном потоке
+                    // забирает его код выхода, чтобы не копились зомби
+                    std::thread::spawn(move || {
+                        if let Err(err) = child.wait() {
+                            debug!(error = %err, "Процесс уведомления не дождались");
+                        }
+                    });
+                    return Delivery::Desktop;
+                }
+                Err(err) => warn!(
+                    backend = ?self.backend,
+                    error = %err,
+                    "Не удалось показать уведомление на рабочем столе"
+                ),
+            }
+        }
+        if !self.bell {
+            return Delivery::Silent;
+        }
+        // Колокольчик не должен мешать работе: ошибка записи в терминал не важна
+        let _ = ring_bell();
+        Delivery::Bell
+    }
+
+    /// Уведомление о записи входящих: задача в заголовке, событие в тексте
+    pub fn inbox(&self, notification: &Notification) -> Delivery {
+        let body = format!("{}: {}", notification.kind.label(), notification.text);
+        self.send(&notification.title(), &body)
+    }
+}
+
+/// Способ показа для текущей системы; `has_var` проверяет переменную окружения
+fn detect(macos: bool, has_var: impl Fn(&str) -> bool) -> Backend {
+    if macos {
+        Backend::AppleScript
+    } else if has_var("WSL_DISTRO_NAME") || has_var("WSL_INTEROP") {
+        Backend::Wsl
+    } else if has_var("WAYLAND_DISPLAY") || has_var("DISPLAY") {
+        Backend::NotifySend
+    } else {
+        Backend::Terminal
+    }
+}
+
+/// Команда показа уведомления; None, если рабочего стола нет
+fn command(backend: Backend, title: &str, body: &str) -> Option<Command> {
+    let command = match backend {
+        Backend::NotifySend => {
+            let mut command = Command::new("notify-send");
+            command.args(["--app-name=you", "--", title, body]);
+            command
+        }
+        Backend::AppleScript => {
+            // Текст передаётся аргументами скрипта, а не подставляется в его код
+            let mut command = Command::new("osascript");
+            command.args([
+                "-e",
+                "on run argv",
+                "-e",
+                "display notification (item 2 of argv) with title (item 1 of argv)",
+                "-e",
+                "end run",
+                title,
+                body,
+            ]);
+            command
+        }
+        Backend::Wsl => {
+            let mut command = Command::new("powershell.exe");
+            // Переменные попадают в процесс Windows, только если перечислены в WSLENV
+            let wslenv = match std::env::var("WSLENV") {
+                Ok(value) if !value.is_empty() => format!("{value}:{WSLENV_VARS}"),
+                _ => WSLENV_VARS.to_string(),
+            };
+            command
+                .args([
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-Command",
+                    POWERSHELL_SCRIPT,
+                ])
+                .env("YOU_NOTIFY_TITLE", title)
+                .env("YOU_NOTIFY_BODY", body)
+                .env("YOU_NOTIFY_APP", POWERSHELL_APP_ID)
+                .env("WSLENV", wslenv);
+            command
+        }
+        Backend::Terminal => return None,
+    };
+    Some(command)
+}
+
+fn ring_bell() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use super::*;
+
+    #[test]
+    fn test_detect_backend() {
+        let env = |vars: &'static [&'static str]| move |name: &str| vars.contains(&name);
+
+        assert_eq!(detect(true, env(&[])), Backend::AppleScript);
+        assert_eq!(
+            detect(false, env(&["WSL_DISTRO_NAME", "DISPLAY"])),
+            Backend::Wsl
+        );
+        assert_eq!(
+            detect(false, env(&["WAYLAND_DISPLAY"])),
+            Backend::NotifySend
+        );
+        assert_eq!(detect(false, env(&["SSH_TTY"])), Backend::Terminal);
+    }
+
+    #[test]
+    fn test_command_passes_text_as_arguments() {
+        let title = "TREK-1: \"Релиз\"";
+        let apple = command(Backend::AppleScript, title, "ping QA").unwrap();
+        let args: Vec<&OsStr> = apple.get_args().collect();
+        assert_eq!(apple.get_program(), "osascript");
+        assert_eq!(
+            args[args.len() - 2..],
+            [OsStr::new(title), OsStr::new("ping QA")]
+        );
+
+        let wsl = command(Backend::Wsl, title, "ping QA").unwrap();
+        assert!(
+            wsl.get_envs()
+                .any(|(name, value)| name == "YOU_NOTIFY_BODY"
+                    && value == Some(OsStr::new("ping QA")))
+        );
+
+        assert!(command(Backend::Terminal, title, "ping QA").is_none());
+    }
+
+    #[test]
+    fn test_disabled_desktop_falls_back_to_bell() {
+        let notifier = Notifier::new(&NotifyConfig {
+            desktop: false,
+            bell: false,
+            sinks: Vec::new(),
+        });
+        assert_eq!(notifier.send("TREK-1", "ping QA"), Delivery::Silent);
+    }
+}
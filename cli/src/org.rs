@@ -0,0 +1,248 @@
+//! Справочник организации: пользователи, очереди и статусы
+//!
+//! `you sync` загружает пользователей (`users.json`), очереди и статусы (`org.json`)
+//! в `$XDG_STATE_HOME/you/`, поэтому мастер создания задач в TUI, проверка `--status`
+//! и названия статусов работают сразу и без сети. `--every 6h` повторяет синхронизацию,
+//! пока команда запущена. Без неё справочник загружается при первом обращении и
+//! обновляется раз в сутки; если Трекер недоступен, используется сохранённый.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    models::{QueueDetails, Status},
+    TrackerClient,
+};
+
+use crate::{paths, people, reminders, settings};
+
+/// Через сколько справочник считается устаревшим
+const MAX_AGE_HOURS: i64 = 24;
+
+/// Сохранённые очереди и статусы организации
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrgDirectory {
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub queues: Vec<QueueDetails>,
+    pub statuses: Vec<Status>,
+}
+
+impl OrgDirectory {
+    /// Загружает справочник; повреждённый или отсутствующий файл даёт пустой справочник
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = directory_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Справочник организации не найден");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = %err, "Не удалось разобрать справочник организации");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = directory_path().context("Не удалось определить путь для справочника")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.fetched_at
+            .is_some_and(|fetched_at| now - fetched_at < Duration::hours(MAX_AGE_HOURS))
+    }
+
+    /// Название статуса по ключу; None, если статус не известен
+    pub fn status_display(&self, key: &str) -> Option<&str> {
+        self.statuses
+            .iter()
+            .find(|status| status.key.as_deref() == Some(key))
+            .and_then(|status| status.display.as_deref())
+    }
+
+    /// Проверяет ключи статусов; пустой справочник пропускает любые
+    pub fn check_statuses(&self, keys: &[String]) -> Result<()> {
+        if self.statuses.is_empty() {
+            return Ok(());
+        }
+        let unknown: Vec<&str> = keys
+            .iter()
+            .map(String::as_str)
+            .filter(|key| self.statuses.iter().all(|s| s.key.as_deref() != Some(*key)))
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        let known: Vec<String> = self
+            .statuses
+            .iter()
+            .filter_map(|status| {
+                let key = status.key.as_deref()?;
+                Some(match status.display.as_deref() {
+                    Some(display) => format!("{key} ({display})"),
+                    None => key.to_string(),
+                })
+            })
+            .collect();
+        bail!(
+            "Неизвестный статус: {}. Статусы организации: {}",
+            unknown.join(", "),
+            known.join(", ")
+        );
+    }
+}
+
+fn directory_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("org.json"))
+}
+
+/// Справочник из файла, а если он устарел или `refresh` — заново из Трекера
+///
+/// Если Трекер недоступен, возвращается сохранённый справочник, даже устаревший.
+#[instrument(skip(client))]
+pub async fn cached(client: &TrackerClient, refresh: bool) -> Result<OrgDirectory> {
+    let saved = OrgDirectory::load();
+    if !refresh && saved.is_fresh(Utc::now()) {
+        debug!(
+            queues = saved.queues.len(),
+            statuses = saved.statuses.len(),
+            "Справочник организации из файла"
+        );
+        return Ok(saved);
+    }
+
+    match fetch(client).await {
+        Ok(directory) => Ok(directory),
+        Err(err) if saved.fetched_at.is_some() => {
+            warn!(error = %format!("{err:#}"), "Не удалось обновить справочник, используется сохранённый");
+            Ok(saved)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Загружает очереди и статусы из Трекера и сохраняет их
+async fn fetch(client: &TrackerClient) -> Result<OrgDirectory> {
+    let (mut queues, statuses) = tokio::try_join!(client.get_queues(), client.get_statuses())
+        .context("Не удалось загрузить очереди и статусы организации")?;
+    queues.sort_by(|a, b| a.key.cmp(&b.key));
+    let directory = OrgDirectory {
+        fetched_at: Some(Utc::now()),
+        queues,
+        statuses,
+    };
+    if let Err(err) = directory.save() {
+        warn!(error = %err, "Не удалось сохранить справочник организации");
+    }
+    info!(
+        queues = directory.queues.len(),
+        statuses = directory.statuses.len(),
+        "Справочник организации обновлён"
+    );
+    Ok(directory)
+}
+
+/// Аргументы команды `you sync`
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Повторять синхронизацию с этим интервалом (30m, 6h, 1d), пока команда запущена
+    #[arg(long, value_parser = reminders::parse_delay)]
+    every: Option<Duration>,
+}
+
+impl SyncArgs {
+    pub async fn execute(self) -> Result<()> {
+        let client = settings::tracker_client()?;
+        let Some(every) = self.every else {
+            return sync(&client).await;
+        };
+        let period = every
+            .to_std()
+            .ok()
+            .filter(|period| !period.is_zero())
+            .context("Интервал синхронизации должен быть больше нуля")?;
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            // Ошибка одной синхронизации не останавливает следующие
+            if let Err(err) = sync(&client).await {
+                warn!(error = %format!("{err:#}"), "Синхронизация справочника не удалась");
+                eprintln!("Ошибка синхронизации: {err:#}");
+            }
+        }
+    }
+}
+
+/// Обновляет пользователей, очереди и статусы
+#[instrument(skip(client))]
+async fn sync(client: &TrackerClient) -> Result<()> {
+    let (users, org) = tokio::try_join!(people::fetch(client), fetch(client))?;
+    println!(
+        "Справочник обновлён: пользователей {}, очередей {}, статусов {}",
+        users.people.len(),
+        org.queues.len(),
+        org.statuses.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(key: &str, display: &str) -> Status {
+        serde_json::from_value(serde_json::json!({"key": key, "display": display})).unwrap()
+    }
+
+    #[test]
+    fn test_check_statuses_against_directory() {
+        let directory = OrgDirectory {
+            fetched_at: Some(Utc::now()),
+            queues: Vec::new(),
+            statuses: vec![status("open", "Открыт"), status("inReview", "Ревью")],
+        };
+        assert_eq!(directory.status_display("inReview"), Some("Ревью"));
+        assert_eq!(directory.status_display("closed"), None);
+        assert!(directory.check_statuses(&["open".to_string()]).is_ok());
+
+        let err = directory
+            .check_statuses(&["open".to_string(), "inreview".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Неизвестный статус: inreview."));
+        assert!(err.contains("inReview (Ревью)"));
+
+        // Без синхронизации проверка не мешает работать
+        assert!(OrgDirectory::default()
+            .check_statuses(&["anything".to_string()])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_is_fresh_expires_after_a_day() {
+        let now = Utc::now();
+        let directory = OrgDirectory {
+            fetched_at: Some(now - Duration::hours(2)),
+            ..OrgDirectory::default()
+        };
+        assert!(directory.is_fresh(now));
+        assert!(!directory.is_fresh(now + Duration::hours(MAX_AGE_HOURS)));
+        assert!(!OrgDirectory::default().is_fresh(now));
+    }
+}
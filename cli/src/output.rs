@@ -0,0 +1,390 @@
+//! Машиночитаемый вывод команд: `--output json` и `you schema`
+//!
+//! В JSON команды выводят не ответы API как есть, а собственные структуры с
+//! постоянным набором полей, чтобы скрипты не ломались от изменений Трекера. Схема
+//! каждого такого вывода печатается командой `you schema <команда>`; тест модуля
+//! сверяет схемы со структурами, поэтому поле нельзя добавить, не описав его.
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracker_lib::{models::Issue, template::issue_url};
+
+use crate::index::SearchHit;
+
+/// Версия JSON Schema, по которой описаны выводы
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Формат вывода команды
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Текст для человека (с шаблонами и цветами)
+    #[default]
+    Text,
+    /// JSON по схеме из `you schema`
+    Json,
+}
+
+/// Задача в JSON выводе
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueOutput {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub issue_type: Option<String>,
+    pub priority: Option<String>,
+    pub queue: Option<String>,
+    /// Логин исполнителя
+    pub assignee: Option<String>,
+    /// Логин автора
+    pub author: Option<String>,
+    pub parent: Option<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub resolved_at: Option<String>,
+    pub link: String,
+}
+
+impl From<&Issue> for IssueOutput {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            key: issue.key.clone(),
+            summary: issue.summary.clone(),
+            status: issue.status.as_ref().and_then(|s| s.display.clone()),
+            issue_type: issue.issue_type.as_ref().and_then(|t| t.key.clone()),
+            priority: issue.priority.as_ref().and_then(|p| p.key.clone()),
+            queue: issue.queue.as_ref().and_then(|q| q.key.clone()),
+            assignee: issue.assignee.as_ref().and_then(|u| u.login.clone()),
+            author: issue.created_by.as_ref().and_then(|u| u.login.clone()),
+            parent: issue.parent.as_ref().and_then(|p| p.key.clone()),
+            tags: issue.tags.clone(),
+            description: issue.description.clone(),
+            created_at: issue.created_at.clone(),
+            updated_at: issue.updated_at.clone(),
+            resolved_at: issue.resolved_at.clone(),
+            link: issue_url(&issue.key),
+        }
+    }
+}
+
+/// Результат поиска по локальному индексу в JSON выводе
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HitOutput {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    pub score: f64,
+}
+
+impl From<&SearchHit> for HitOutput {
+    fn from(hit: &SearchHit) -> Self {
+        Self {
+            key: hit.key.clone(),
+            summary: hit.summary.clone(),
+            status: hit.status.clone(),
+            score: hit.score,
+        }
+    }
+}
+
+/// Напечатать значение как JSON
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Команды с JSON выводом
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaCommand {
+    /// `you tracker issue --output json`
+    TrackerIssue,
+    /// `you tracker find --output json`
+    TrackerFind,
+    /// `you search run --output json`
+    SearchRun,
+    /// `you llm plan-sprint --output json`
+    LlmPlanSprint,
+    /// `you report velocity --output json`
+    ReportVelocity,
+}
+
+/// Аргументы команды `you schema`
+#[derive(Args)]
+pub struct SchemaArgs {
+    /// Команда, схему вывода которой напечатать
+    command: SchemaCommand,
+}
+
+impl SchemaArgs {
+    pub fn execute(self) -> Result<()> {
+        print_json(&schema(self.command))
+    }
+}
+
+/// JSON Schema вывода команды с `--output json`
+pub fn schema(command: SchemaCommand) -> Value {
+    let (title, body) = match command {
+        SchemaCommand::TrackerIssue => ("you tracker issue", issue_schema()),
+        SchemaCommand::TrackerFind => (
+            "you tracker find",
+            json!({"type": "array", "items": hit_schema()}),
+        ),
+        SchemaCommand::SearchRun => (
+            "you search run",
+            json!({"type": "array", "items": issue_schema()}),
+        ),
+        SchemaCommand::LlmPlanSprint => ("you llm plan-sprint", sprint_plan_schema()),
+        SchemaCommand::ReportVelocity => ("you report velocity", velocity_schema()),
+    };
+    let mut schema = json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": title,
+    });
+    if let (Value::Object(schema), Value::Object(body)) = (&mut schema, body) {
+        schema.extend(body);
+    }
+    schema
+}
+
+fn string() -> Value {
+    json!({"type": "string"})
+}
+
+fn nullable_string() -> Value {
+    json!({"type": ["string", "null"]})
+}
+
+fn object(properties: Value) -> Value {
+    let required: Vec<&String> = properties
+        .as_object()
+        .map(|properties| properties.keys().collect())
+        .unwrap_or_default();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn issue_schema() -> Value {
+    object(json!({
+        "key": string(),
+        "summary": string(),
+        "status": nullable_string(),
+        "type": nullable_string(),
+        "priority": nullable_string(),
+        "queue": nullable_string(),
+        "assignee": nullable_string(),
+        "author": nullable_string(),
+        "parent": nullable_string(),
+        "tags": {"type": "array", "items": string()},
+        "description": nullable_string(),
+        "created_at": nullable_string(),
+        "updated_at": nullable_string(),
+        "resolved_at": nullable_string(),
+        "link": string(),
+    }))
+}
+
+fn hit_schema() -> Value {
+    object(json!({
+        "key": string(),
+        "summary": string(),
+        "status": nullable_string(),
+        "score": {"type": "number"},
+    }))
+}
+
+fn sprint_plan_schema() -> Value {
+    object(json!({
+        "sprint": string(),
+        "capacity_hours": {"type": "number"},
+        "planned_hours": {"type": "number"},
+        "issues": {"type": "array", "items": object(json!({
+            "key": string(),
+            "summary": string(),
+            "hours": {"type": ["number", "null"]},
+            "reason": string(),
+        }))},
+        "rationale": string(),
+    }))
+}
+
+fn velocity_schema() -> Value {
+    let number = || json!({"type": "number"});
+    let percentiles = || {
+        let mut schema = object(json!({
+            "count": {"type": "integer"},
+            "p50": number(),
+            "p85": number(),
+            "p95": number(),
+        }));
+        schema["type"] = json!(["object", "null"]);
+        schema
+    };
+    object(json!({
+        "sprints": {"type": "array", "items": object(json!({
+            "sprint_id": {"type": ["integer", "null"]},
+            "name": string(),
+            "start": string(),
+            "end": string(),
+            "committed": {"type": "integer"},
+            "completed": {"type": "integer"},
+            "committed_points": number(),
+            "completed_points": number(),
+            "cycle_time": percentiles(),
+            "lead_time": percentiles(),
+        }))},
+        "average_completed": number(),
+        "average_points": number(),
+        "cycle_time": percentiles(),
+        "lead_time": percentiles(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sprint::{PlannedIssue, SprintPlan};
+    use crate::testing::issue;
+    use chrono::DateTime;
+    use tracker_lib::metrics::{IssueTimeline, VelocityReport};
+    use tracker_lib::models::SprintDetails;
+
+    /// Соответствие значения схеме в объёме, который используют схемы модуля
+    fn check(value: &Value, schema: &Value, path: &str) {
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => panic!("{path}: в схеме нет type"),
+        };
+        let kind = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(number) if number.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        // Целое число подходит и под схему number
+        let matches = types.contains(&kind) || (kind == "integer" && types.contains(&"number"));
+        assert!(matches, "{path}: {kind} вместо {types:?}");
+
+        match value {
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    check(item, &schema["items"], &format!("{path}[{i}]"));
+                }
+            }
+            Value::Object(fields) => {
+                let properties = schema["properties"].as_object().unwrap();
+                for name in schema["required"].as_array().unwrap() {
+                    let name = name.as_str().unwrap();
+                    assert!(fields.contains_key(name), "{path}: нет поля {name}");
+                }
+                for (name, field) in fields {
+                    let property = properties
+                        .get(name)
+                        .unwrap_or_else(|| panic!("{path}: поле {name} не описано в схеме"));
+                    check(field, property, &format!("{path}.{name}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_outputs_match_schemas() {
+        let full = issue("TREK-2")
+            .summary("Миграция")
+            .status("open", "Открыт")
+            .issue_type("task", "Задача")
+            .priority("normal")
+            .field("queue", json!({"key": "TREK"}))
+            .assignee("ivanov", "Иванов")
+            .field("createdBy", json!({"login": "petrov"}))
+            .field("parent", json!({"key": "TREK-1"}))
+            .tags(&["db"])
+            .description("Перенести таблицы")
+            .field("createdAt", "2026-10-16T09:30:00.000+0000")
+            .field("updatedAt", "2026-10-16T10:00:00.000+0000")
+            .build();
+        let minimal = issue("TREK-3").summary("Схема").build();
+
+        let issue_output = serde_json::to_value(IssueOutput::from(&full)).unwrap();
+        check(&issue_output, &schema(SchemaCommand::TrackerIssue), "$");
+        assert_eq!(issue_output["link"], "https://st.yandex-team.ru/TREK-2");
+
+        let search_output =
+            serde_json::to_value([IssueOutput::from(&full), IssueOutput::from(&minimal)]).unwrap();
+        check(&search_output, &schema(SchemaCommand::SearchRun), "$");
+
+        let hit = SearchHit {
+            key: "TREK-2".to_string(),
+            summary: "Миграция".to_string(),
+            status: None,
+            score: 0.5,
+        };
+        let find_output = serde_json::to_value([HitOutput::from(&hit)]).unwrap();
+        check(&find_output, &schema(SchemaCommand::TrackerFind), "$");
+
+        let plan = SprintPlan {
+            sprint: "Спринт 42".to_string(),
+            capacity_hours: 21.0,
+            planned_hours: 6.0,
+            issues: vec![
+                PlannedIssue {
+                    key: "TREK-1".to_string(),
+                    summary: "Экспорт".to_string(),
+                    hours: Some(6.0),
+                    reason: "блокирует релиз".to_string(),
+                },
+                PlannedIssue {
+                    key: "TREK-2".to_string(),
+                    summary: "Опечатка".to_string(),
+                    hours: None,
+                    reason: "мелкая".to_string(),
+                },
+            ],
+            rationale: "Запас на ревью".to_string(),
+        };
+        let plan_output = serde_json::to_value(plan).unwrap();
+        check(&plan_output, &schema(SchemaCommand::LlmPlanSprint), "$");
+
+        let sprint: SprintDetails = serde_json::from_value(json!({
+            "id": 42,
+            "name": "Спринт 42",
+            "startDate": "2026-10-01",
+            "endDate": "2026-10-14"
+        }))
+        .unwrap();
+        let timeline = IssueTimeline {
+            key: "TREK-1".to_string(),
+            story_points: Some(3.0),
+            created: DateTime::parse_from_rfc3339("2026-10-01T09:00:00+00:00").ok(),
+            started: None,
+            resolved: DateTime::parse_from_rfc3339("2026-10-03T09:00:00+00:00").ok(),
+        };
+        let report = VelocityReport::new(&[(sprint, vec![timeline])]);
+        let velocity_output = serde_json::to_value(report).unwrap();
+        check(
+            &velocity_output,
+            &schema(SchemaCommand::ReportVelocity),
+            "$",
+        );
+    }
+
+    #[test]
+    fn test_schema_has_dialect_and_title() {
+        let schema = schema(SchemaCommand::SearchRun);
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        assert_eq!(schema["title"], "you search run");
+        assert_eq!(schema["items"]["additionalProperties"], false);
+    }
+}
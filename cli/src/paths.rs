@@ -0,0 +1,13 @@
+//! Каталоги, в которых CLI хранит своё состояние
+
+use std::path::PathBuf;
+
+/// Каталог состояния: `$XDG_STATE_HOME/you` или `~/.local/state/you`
+pub fn state_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+        })?;
+    Some(base.join("you"))
+}
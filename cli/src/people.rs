@@ -0,0 +1,373 @@
+//! Справочник пользователей организации для выбора исполнителя и наблюдателей
+//!
+//! Список пользователей сохраняется в `$XDG_STATE_HOME/you/users.json` и обновляется
+//! раз в сутки или по `--refresh`, поэтому подсказки в TUI и разбор `--assignee`
+//! не ходят в сеть на каждое нажатие. Поиск нечёткий: буквы запроса должны идти в
+//! логине или имени по порядку, но не обязательно подряд (`ivpt` найдёт `ivan.petrov`).
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{ids::UserLogin, models::User, TrackerClient};
+
+use crate::paths;
+
+/// Через сколько справочник считается устаревшим
+const MAX_AGE_HOURS: i64 = 24;
+
+/// Сколько вариантов показывать, когда имя подходит нескольким пользователям
+const MAX_CANDIDATES: usize = 10;
+
+/// Бонус за совпадение в начале слова и за букву сразу после предыдущей совпавшей
+const WORD_START_BONUS: i64 = 8;
+const CONSECUTIVE_BONUS: i64 = 5;
+
+/// Пользователь справочника
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Person {
+    pub login: String,
+    pub display: Option<String>,
+}
+
+impl Person {
+    /// Пользователь без логина (например, робот) в справочник не попадает
+    pub fn from_user(user: User) -> Option<Self> {
+        Some(Self {
+            login: user.login?,
+            display: user.display,
+        })
+    }
+
+    /// Имя с логином для списков выбора
+    pub fn label(&self) -> String {
+        match &self.display {
+            Some(display) => format!("{display} ({})", self.login),
+            None => self.login.clone(),
+        }
+    }
+
+    /// Лучшая оценка запроса по логину и имени; None, если не подходит ни то, ни другое
+    pub fn score(&self, query: &str) -> Option<i64> {
+        let by_display = self
+            .display
+            .as_deref()
+            .and_then(|display| fuzzy_score(query, display));
+        fuzzy_score(query, &self.login).max(by_display)
+    }
+}
+
+/// Сохранённый список пользователей организации
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Directory {
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub people: Vec<Person>,
+}
+
+impl Directory {
+    /// Загружает справочник; повреждённый или отсутствующий файл даёт пустой справочник
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = directory_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Справочник пользователей не найден");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = %err, "Не удалось разобрать справочник пользователей");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = directory_path().context("Не удалось определить путь для справочника")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn from_users(users: Vec<User>, fetched_at: DateTime<Utc>) -> Self {
+        let mut people: Vec<Person> = users.into_iter().filter_map(Person::from_user).collect();
+        people.sort_by(|a, b| a.login.cmp(&b.login));
+        Self {
+            fetched_at: Some(fetched_at),
+            people,
+        }
+    }
+
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.fetched_at
+            .is_some_and(|fetched_at| now - fetched_at < Duration::hours(MAX_AGE_HOURS))
+    }
+
+    pub fn get(&self, login: &str) -> Option<&Person> {
+        self.people.iter().find(|person| person.login == login)
+    }
+
+    /// Пользователи, подходящие под запрос, от лучшего совпадения; пустой запрос — все
+    pub fn search(&self, query: &str) -> Vec<&Person> {
+        let query = query.trim();
+        if query.is_empty() {
+            return self.people.iter().collect();
+        }
+        let mut scored: Vec<(i64, &Person)> = self
+            .people
+            .iter()
+            .filter_map(|person| Some((person.score(query)?, person)))
+            .collect();
+        // sort_by_key стабилен: при равной оценке остаётся порядок по логину
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, person)| person).collect()
+    }
+}
+
+fn directory_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("users.json"))
+}
+
+/// Справочник из файла, а если он устарел или `refresh` — заново из Трекера
+///
+/// Если Трекер недоступен, возвращается сохранённый справочник, даже устаревший.
+#[instrument(skip(client))]
+pub async fn cached(client: &TrackerClient, refresh: bool) -> Result<Directory> {
+    let saved = Directory::load();
+    if !refresh && saved.is_fresh(Utc::now()) {
+        debug!(
+            people = saved.people.len(),
+            "Справочник пользователей из файла"
+        );
+        return Ok(saved);
+    }
+
+    match fetch(client).await {
+        Ok(directory) => Ok(directory),
+        Err(err) if saved.fetched_at.is_some() => {
+            warn!(error = %format!("{err:#}"), "Не удалось обновить справочник, используется сохранённый");
+            Ok(saved)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Загружает справочник из Трекера и сохраняет его
+pub async fn fetch(client: &TrackerClient) -> Result<Directory> {
+    let users = client
+        .get_users()
+        .await
+        .context("Не удалось загрузить пользователей организации")?;
+    let directory = Directory::from_users(users, Utc::now());
+    if let Err(err) = directory.save() {
+        warn!(error = %err, "Не удалось сохранить справочник пользователей");
+    }
+    info!(
+        people = directory.people.len(),
+        "Справочник пользователей обновлён"
+    );
+    Ok(directory)
+}
+
+/// Нечёткая оценка совпадения `query` с `text`; None, если буквы запроса не найдены по порядку
+///
+/// Выше оцениваются совпадения в начале слов и подряд идущие буквы, ниже — разрывы.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+    for (index, c) in text.chars().flat_map(char::to_lowercase).enumerate() {
+        if matched < query.len() && c == query[matched] {
+            let word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+            if word_start {
+                score += WORD_START_BONUS;
+            }
+            match last_match {
+                Some(last) if last + 1 == index => score += CONSECUTIVE_BONUS,
+                Some(last) => score -= (index - last - 1) as i64,
+                None => score -= index as i64,
+            }
+            last_match = Some(index);
+            matched += 1;
+        }
+        previous = Some(c);
+    }
+    (matched == query.len()).then_some(score)
+}
+
+/// Разбирает логины из аргументов, допуская части имени и логина
+///
+/// Точный логин из справочника или логин, которого нет в справочнике, берутся как есть.
+/// Запрос, подходящий одному пользователю, заменяется его логином; если подходят
+/// несколько, в терминале предлагается выбрать, иначе — ошибка со списком вариантов.
+#[instrument(skip(client))]
+pub async fn resolve_logins(client: &TrackerClient, queries: &[String]) -> Result<Vec<UserLogin>> {
+    if queries.is_empty() {
+        return Ok(Vec::new());
+    }
+    let directory = match cached(client, false).await {
+        Ok(directory) => directory,
+        Err(err) => {
+            warn!(error = %err, "Справочник недоступен, логины берутся как есть");
+            Directory::default()
+        }
+    };
+    queries
+        .iter()
+        .map(|query| resolve_login(&directory, query))
+        .collect()
+}
+
+fn resolve_login(directory: &Directory, query: &str) -> Result<UserLogin> {
+    let query = query.trim();
+    if directory.get(query).is_some() {
+        return query.parse().map_err(Into::into);
+    }
+
+    let candidates = directory.search(query);
+    let person = match candidates.as_slice() {
+        [] => {
+            return query
+                .parse()
+                .with_context(|| format!("Пользователь «{query}» не найден"))
+        }
+        [person] => *person,
+        _ if io::stdin().is_terminal() => {
+            let labels: Vec<String> = candidates
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|person| person.label())
+                .collect();
+            let index = choose(&format!("Кого вы имели в виду под «{query}»?"), &labels)
+                .with_context(|| format!("Пользователь для «{query}» не выбран"))?;
+            candidates[index]
+        }
+        _ => {
+            let labels: Vec<String> = candidates
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|person| person.label())
+                .collect();
+            bail!(
+                "«{query}» подходит нескольким пользователям: {}. Укажите логин",
+                labels.join(", ")
+            );
+        }
+    };
+    println!("«{query}» — {}", person.label());
+    person.login.parse().map_err(Into::into)
+}
+
+/// Предлагает выбрать вариант по номеру в терминале; None — пустой или неверный ответ
+pub(crate) fn choose(question: &str, options: &[String]) -> Option<usize> {
+    println!("{question}");
+    for (index, option) in options.iter().enumerate() {
+        println!("  {}. {option}", index + 1);
+    }
+    print!("Номер: ");
+    io::stdout().flush().ok()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).ok()?;
+    let number: usize = answer.trim().parse().ok()?;
+    (1..=options.len()).contains(&number).then(|| number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(login: &str, display: &str) -> Person {
+        Person {
+            login: login.to_string(),
+            display: Some(display.to_string()),
+        }
+    }
+
+    fn directory() -> Directory {
+        Directory {
+            fetched_at: None,
+            people: vec![
+                person("anna.ivanova", "Анна Иванова"),
+                person("ivan.petrov", "Иван Петров"),
+                person("ivanov", "Сергей Иванов"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score_requires_letters_in_order() {
+        assert!(fuzzy_score("ivpt", "ivan.petrov").is_some());
+        assert!(fuzzy_score("tpvi", "ivan.petrov").is_none());
+        assert_eq!(fuzzy_score("", "ivan"), Some(0));
+        // Начало слова и буквы подряд ценнее разбросанных
+        assert!(fuzzy_score("pet", "ivan.petrov") > fuzzy_score("pet", "pxexxt"));
+    }
+
+    #[test]
+    fn test_search_ranks_best_match_first() {
+        let directory = directory();
+
+        let logins: Vec<&str> = directory
+            .search("иван")
+            .iter()
+            .map(|person| person.login.as_str())
+            .collect();
+        assert_eq!(logins[0], "ivan.petrov");
+        assert_eq!(logins.len(), 3);
+
+        let found = directory.search("ivpt");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].login, "ivan.petrov");
+
+        assert_eq!(directory.search(" ").len(), 3);
+    }
+
+    #[test]
+    fn test_resolve_login_prefers_exact_and_unique_matches() {
+        let directory = directory();
+
+        assert_eq!(
+            resolve_login(&directory, "ivanov").unwrap().as_ref(),
+            "ivanov"
+        );
+        assert_eq!(
+            resolve_login(&directory, "Петров").unwrap().as_ref(),
+            "ivan.petrov"
+        );
+        // Пользователя нет в справочнике: логин берётся как есть
+        assert_eq!(
+            resolve_login(&directory, "newbie").unwrap().as_ref(),
+            "newbie"
+        );
+        assert!(resolve_login(&directory, "Кто угодно").is_err());
+    }
+
+    #[test]
+    fn test_is_fresh_expires_after_a_day() {
+        let now = Utc::now();
+        let directory = Directory::from_users(Vec::new(), now - Duration::hours(2));
+        assert!(directory.is_fresh(now));
+        assert!(!directory.is_fresh(now + Duration::hours(MAX_AGE_HOURS)));
+        assert!(!Directory::default().is_fresh(now));
+    }
+}
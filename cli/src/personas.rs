@@ -0,0 +1,136 @@
+//! Персоны ассистента для LLM
+//!
+//! Персона — системный промпт с моделью и температурой по умолчанию. Встроенная
+//! персона `project-manager` составляет план в `you llm plan-day`; свои персоны
+//! задаются и переопределяются в секции `personas` конфигурации. Команды `you llm`
+//! выбирают персону флагом `--persona`, экран LLM в TUI — клавишей Ctrl+P.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::prompts::RenderedPrompt;
+
+/// Персона, которая по умолчанию составляет план дня
+pub const PROJECT_MANAGER: &str = "project-manager";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Persona {
+    /// Системный промпт; идёт перед системным промптом пресета
+    pub system: String,
+
+    /// Модель, если персоне нужна не модель по умолчанию
+    pub model: Option<String>,
+
+    /// Температура, если она не задана в запросе
+    pub temperature: Option<f32>,
+}
+
+impl Persona {
+    /// Дополнить промпт системным промптом и температурой персоны
+    pub fn apply(&self, prompt: &mut RenderedPrompt) {
+        if !self.system.trim().is_empty() {
+            prompt.system = Some(match prompt.system.take() {
+                Some(system) => format!("{}\n\n{system}", self.system),
+                None => self.system.clone(),
+            });
+        }
+        prompt.temperature = prompt.temperature.or(self.temperature);
+    }
+}
+
+/// Встроенные персоны, дополненные и переопределённые персонами из конфигурации
+pub fn library(configured: &HashMap<String, Persona>) -> BTreeMap<String, Persona> {
+    let mut library = BTreeMap::from([(
+        PROJECT_MANAGER.to_string(),
+        Persona {
+            system: "Ты опытный руководитель проектов. Помогаешь расставить приоритеты: \
+                     сначала блокеры и задачи с близким сроком, затем то, что ждут другие. \
+                     Учитываешь встречи и оставляешь время на сосредоточенную работу. \
+                     Отвечаешь по делу, в Markdown, со ссылками на ключи задач."
+                .to_string(),
+            model: None,
+            temperature: Some(0.3),
+        },
+    )]);
+    library.extend(
+        configured
+            .iter()
+            .map(|(name, persona)| (name.clone(), persona.clone())),
+    );
+    library
+}
+
+/// Персона по имени из библиотеки с учётом конфигурации
+pub fn load(name: &str) -> Result<Persona> {
+    let library = library(&Config::load()?.personas);
+    match library.get(name) {
+        Some(persona) => Ok(persona.clone()),
+        None => bail!(
+            "Персона «{name}» не найдена; доступны: {}",
+            library.keys().cloned().collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_config_overrides_builtins() {
+        let configured: HashMap<String, Persona> = serde_norway::from_str(
+            r#"
+project-manager:
+  system: Ты тимлид маленькой команды
+reviewer:
+  system: Ты придирчивый ревьюер
+  model: openai/gpt-4o
+  temperature: 0.1
+"#,
+        )
+        .unwrap();
+
+        let library = library(&configured);
+        assert_eq!(
+            library[PROJECT_MANAGER].system,
+            "Ты тимлид маленькой команды"
+        );
+        assert!(library[PROJECT_MANAGER].temperature.is_none());
+        assert_eq!(library["reviewer"].model.as_deref(), Some("openai/gpt-4o"));
+        assert_eq!(library["reviewer"].temperature, Some(0.1));
+    }
+
+    #[test]
+    fn test_apply_prepends_system_and_keeps_explicit_temperature() {
+        let persona = Persona {
+            system: "Ты руководитель проектов".to_string(),
+            temperature: Some(0.3),
+            ..Default::default()
+        };
+
+        let mut plain = RenderedPrompt {
+            system: None,
+            prompt: "План?".to_string(),
+            temperature: None,
+        };
+        persona.apply(&mut plain);
+        assert_eq!(plain.system.as_deref(), Some("Ты руководитель проектов"));
+        assert_eq!(plain.temperature, Some(0.3));
+
+        let mut preset = RenderedPrompt {
+            system: Some("Отвечай списком".to_string()),
+            prompt: "План?".to_string(),
+            temperature: Some(0.9),
+        };
+        persona.apply(&mut preset);
+        assert_eq!(
+            preset.system.as_deref(),
+            Some("Ты руководитель проектов\n\nОтвечай списком")
+        );
+        assert_eq!(preset.temperature, Some(0.9));
+    }
+}
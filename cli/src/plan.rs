@@ -0,0 +1,210 @@
+//! План дня по открытым задачам и встречам
+//!
+//! `you llm plan-day` собирает задачи, назначенные на пользователя, и сегодняшние
+//! встречи из календаря, укладывает их в бюджет контекста и просит персону
+//! (по умолчанию `project-manager`) составить план.
+
+use anyhow::Result;
+use calendar_lib::{Event, Zone};
+use chrono::Utc;
+use llm_lib::{
+    context::{self, ContextItem},
+    LlmClientTrait,
+};
+use tracing::instrument;
+use tracker_lib::models::Issue;
+
+use crate::personas::Persona;
+use crate::prompts::RenderedPrompt;
+use crate::redact;
+use crate::timezone;
+
+/// Открытые задачи пользователя, недавно обновлённые первыми
+pub const PLAN_QUERY: &str = "Assignee: me() Resolution: empty() \"Sort by\": Updated DESC";
+
+/// Сколько задач по умолчанию учитывать в плане
+pub const DEFAULT_LIMIT: usize = 50;
+
+/// Бюджет контекста в токенах для задач и встреч
+pub const CONTEXT_TOKENS: usize = 6000;
+
+/// Запрос плана дня; после него идёт собранный контекст
+pub const DAY_PROMPT: &str = "Составь план работы на сегодня. Учти встречи, выдели 3–5 главных \
+задач, предложи порядок и время на каждую и отметь, что можно отложить.";
+
+/// Вес приоритета Трекера: при нехватке бюджета важные задачи остаются в контексте
+fn priority_weight(issue: &Issue) -> u32 {
+    let key = issue
+        .priority
+        .as_ref()
+        .and_then(|priority| priority.key.as_deref());
+    match key {
+        Some("blocker") => 4,
+        Some("critical") => 3,
+        Some("minor") => 1,
+        Some("trivial") => 0,
+        _ => 2,
+    }
+}
+
+fn describe_event(event: &Event) -> String {
+    let time = if event.all_day {
+        "весь день".to_string()
+    } else {
+        format!(
+            "{}–{}",
+            event.start.format("%H:%M"),
+            event.end.format("%H:%M")
+        )
+    };
+    match &event.location {
+        Some(location) => format!("- {time} {} ({location})", event.summary),
+        None => format!("- {time} {}", event.summary),
+    }
+}
+
+/// Контекст для плана: встречи целиком, затем задачи по приоритету и свежести
+///
+/// Время встреч и сроки задач даны в поясе `zone`; явный пояс указывается в заголовке.
+pub fn day_context(
+    issues: &[Issue],
+    events: &[&Event],
+    zone: Zone,
+    budget_tokens: usize,
+) -> String {
+    let now = Utc::now();
+    let mut items = Vec::new();
+    if !events.is_empty() {
+        let agenda: Vec<String> = events.iter().map(|event| describe_event(event)).collect();
+        let title = match zone {
+            Zone::Local => "Встречи сегодня".to_string(),
+            Zone::Named(_) => format!("Встречи сегодня ({zone})"),
+        };
+        items.push(ContextItem::new(title, agenda.join("\n")).priority(u32::MAX));
+    }
+    items.extend(issues.iter().enumerate().map(|(index, issue)| {
+        let issue = &redact::issue(issue);
+        let mut details = Vec::new();
+        if let Some(status) = issue.status.as_ref().and_then(|s| s.display.as_deref()) {
+            details.push(format!("Статус: {status}"));
+        }
+        if let Some(priority) = issue.priority.as_ref().and_then(|p| p.display.as_deref()) {
+            details.push(format!("Приоритет: {priority}"));
+        }
+        if let Some(deadline) = timezone::deadline_note(issue, zone, now) {
+            details.push(format!("Дедлайн: {deadline}"));
+        }
+        if let Some(description) = issue
+            .description
+            .as_deref()
+            .filter(|text| !text.trim().is_empty())
+        {
+            details.push(description.to_string());
+        }
+        // Среди задач одного приоритета выше те, что обновлялись недавно
+        let freshness = issues.len().saturating_sub(index) as u32;
+        ContextItem::new(
+            format!("{}: {}", issue.key, issue.summary),
+            details.join("\n"),
+        )
+        .priority(priority_weight(issue) * 1_000 + freshness)
+    }));
+    context::pack(items, budget_tokens)
+}
+
+/// Попросить персону составить план дня по собранному контексту
+#[instrument(skip_all)]
+pub async fn plan_day<T: LlmClientTrait>(
+    llm: &T,
+    persona: &Persona,
+    context: &str,
+) -> Result<String> {
+    let mut prompt = RenderedPrompt {
+        system: None,
+        prompt: format!("{DAY_PROMPT}\n\n{context}"),
+        temperature: None,
+    };
+    persona.apply(&mut prompt);
+    prompt.send(llm).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_day_context_lists_meetings_and_issues() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let standup = Event {
+            uid: None,
+            summary: "Стендап".to_string(),
+            description: None,
+            location: Some("Переговорная 3".to_string()),
+            url: None,
+            attendees: Vec::new(),
+            start: date.and_hms_opt(10, 0, 0).unwrap(),
+            end: date.and_hms_opt(10, 15, 0).unwrap(),
+            all_day: false,
+        };
+        let issues = [
+            issue("TREK-1").priority("normal").build(),
+            issue("TREK-2").priority("blocker").build(),
+        ];
+
+        let context = day_context(&issues, &[&standup], Zone::Local, CONTEXT_TOKENS);
+        assert!(context.starts_with(
+            "## Встречи сегодня\n\n- 10:00–10:15 Стендап (Переговорная 3)\n\n## TREK-1"
+        ));
+        assert!(context.contains("## TREK-2: Задача TREK-2\n\nПриоритет: blocker"));
+
+        // В маленький бюджет попадает блокер, а не более свежая обычная задача
+        let tight = day_context(&issues, &[], Zone::Local, 14);
+        assert!(tight.contains("TREK-2"));
+        assert!(!tight.contains("TREK-1"));
+    }
+
+    #[test]
+    fn test_day_context_shows_zone_and_deadline() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let review = Event {
+            uid: None,
+            summary: "Ревью".to_string(),
+            description: None,
+            location: None,
+            url: None,
+            attendees: Vec::new(),
+            start: date.and_hms_opt(14, 0, 0).unwrap(),
+            end: date.and_hms_opt(15, 0, 0).unwrap(),
+            all_day: false,
+        };
+        let release = issue("TREK-3")
+            .priority("critical")
+            .field("deadline", "2026-10-20")
+            .build();
+
+        let zone = "Asia/Novosibirsk".parse().unwrap();
+        let context = day_context(&[release], &[&review], zone, CONTEXT_TOKENS);
+        assert!(context.starts_with("## Встречи сегодня (Asia/Novosibirsk)\n\n- 14:00–15:00 Ревью"));
+        assert!(context.contains("Дедлайн: 20.10.2026 ("));
+    }
+
+    #[tokio::test]
+    async fn test_plan_day_uses_persona() {
+        let mut llm = llm_lib::MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .withf(|system, prompt| {
+                system == "Ты руководитель проектов" && prompt.ends_with("## TREK-1: Релиз")
+            })
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok("1. TREK-1".to_string()) }));
+
+        let persona = Persona {
+            system: "Ты руководитель проектов".to_string(),
+            ..Default::default()
+        };
+        let plan = plan_day(&llm, &persona, "## TREK-1: Релиз").await.unwrap();
+        assert_eq!(plan, "1. TREK-1");
+    }
+}
@@ -0,0 +1,439 @@
+//! Справка к встрече: `you llm prep`
+//!
+//! По участникам встречи из календаря находятся задачи Трекера, общие с пользователем
+//! (исполнитель, автор или наблюдатель с обеих сторон), и задачи, упомянутые в самом
+//! событии. Последние комментарии к ним и повестка встречи укладываются в бюджет
+//! контекста, а LLM составляет справку на одну страницу. Логин участника — часть адреса
+//! до `@`, как в организациях Яндекс 360.
+//!
+//! `you serve` с секцией `serve.prep` готовит справки сам: за `minutes_before` минут
+//! до встречи справка сохраняется в `$XDG_STATE_HOME/you/prep/` и показывается
+//! уведомлением.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use calendar_lib::{Event, Zone};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use llm_lib::{
+    context::{self, ContextItem},
+    LlmClient, LlmClientTrait, LlmConfig,
+};
+use serde::Deserialize;
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    models::{Comment, Issue, TRACKER_DATE_FORMAT},
+    TrackerClient,
+};
+
+use crate::llm::DEFAULT_MODEL;
+use crate::notify::Notifier;
+use crate::{paths, redact, summarize, timezone};
+
+/// Сколько общих задач учитывать по умолчанию
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// За сколько минут до встречи `you serve` готовит справку по умолчанию
+pub const DEFAULT_MINUTES_BEFORE: i64 = 15;
+
+/// Бюджет контекста в токенах для встречи и задач
+const CONTEXT_TOKENS: usize = 6000;
+
+/// Комментарии скольких задач запрашивать для недавней активности
+const ACTIVITY_ISSUES: usize = 10;
+
+/// Комментарии старше этого в справку не попадают
+const ACTIVITY_DAYS: i64 = 14;
+
+/// Сколько последних комментариев задачи показывать
+const COMMENTS_PER_ISSUE: usize = 3;
+
+/// Как часто `you serve` проверяет, не пора ли готовить справку
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+const SYSTEM_PROMPT: &str = "Ты готовишь пользователя к рабочей встрече. Пиши кратко, \
+по делу, на языке повестки.";
+
+/// Запрос справки; после него идёт собранный контекст
+pub const PREP_PROMPT: &str = "Составь справку к встрече на одну страницу в Markdown: \
+цель встречи по повестке, состояние общих с участниками задач, что изменилось за последние \
+дни, открытые вопросы и что подготовить заранее. Ссылайся на задачи по ключам и не \
+придумывай задач, которых нет в контексте.";
+
+/// Секция `serve.prep` конфигурации
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PrepConfig {
+    /// Готовить справки к встречам с участниками
+    pub enabled: bool,
+    /// За сколько минут до начала встречи; по умолчанию 15
+    pub minutes_before: Option<i64>,
+    /// Модель LLM
+    pub model: Option<String>,
+}
+
+/// Задача, общая с участниками, и её свежие комментарии
+#[derive(Debug, Clone)]
+pub struct SharedIssue {
+    pub issue: Issue,
+    pub comments: Vec<Comment>,
+}
+
+/// Логины участников встречи без самого пользователя
+pub fn attendee_logins(event: &Event, me: Option<&str>) -> Vec<String> {
+    let mut logins: Vec<String> = Vec::new();
+    for address in &event.attendees {
+        let login = address.split('@').next().unwrap_or_default().trim();
+        let is_me = me.is_some_and(|me| me.eq_ignore_ascii_case(login));
+        if !login.is_empty() && !is_me && !logins.iter().any(|known| known == login) {
+            logins.push(login.to_string());
+        }
+    }
+    logins
+}
+
+/// Запрос задач, где участвуют и пользователь, и кто-то из `logins`
+pub fn shared_query(logins: &[String]) -> Option<String> {
+    if logins.is_empty() {
+        return None;
+    }
+    let people = logins.join(", ");
+    Some(format!(
+        "(Assignee: {people} OR Author: {people} OR Followers: {people}) \
+AND (Assignee: me() OR Author: me() OR Followers: me()) \
+AND (Resolution: empty() OR Updated: >= now() - {ACTIVITY_DAYS}d) \"Sort by\": Updated DESC"
+    ))
+}
+
+/// Общие задачи участников и задачи из описания встречи с недавними комментариями
+#[instrument(skip(client, event), fields(summary = %event.summary))]
+pub async fn gather(
+    client: &TrackerClient,
+    event: &Event,
+    limit: usize,
+) -> Result<Vec<SharedIssue>> {
+    let me = match client.get_myself().await {
+        Ok(user) => user.login,
+        Err(err) => {
+            warn!(error = %err, "Не удалось узнать свой логин, он не исключён из участников");
+            None
+        }
+    };
+    let logins = attendee_logins(event, me.as_deref());
+    debug!(?logins, "Участники встречи");
+
+    let mut issues = summarize::fetch_keys(client, event.issue_keys()).await?;
+    if let Some(query) = shared_query(&logins) {
+        let shared = summarize::fetch_issues(client, &query, limit).await?;
+        for issue in shared {
+            if !issues.iter().any(|known| known.key == issue.key) {
+                issues.push(issue);
+            }
+        }
+    }
+    issues.truncate(limit);
+
+    let since = Utc::now() - Duration::days(ACTIVITY_DAYS);
+    let mut shared = Vec::with_capacity(issues.len());
+    for (index, issue) in issues.into_iter().enumerate() {
+        let comments = if index < ACTIVITY_ISSUES {
+            match client.get_comments(&issue.issue_key()?).await {
+                Ok(comments) => recent_comments(comments, since),
+                Err(err) => {
+                    debug!(issue_key = %issue.key, error = %err, "Комментарии недоступны");
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        shared.push(SharedIssue { issue, comments });
+    }
+    info!(count = shared.len(), "Задачи для справки собраны");
+    Ok(shared)
+}
+
+/// Последние комментарии не старше `since`, по порядку написания
+fn recent_comments(comments: Vec<Comment>, since: DateTime<Utc>) -> Vec<Comment> {
+    let mut recent: Vec<Comment> = comments
+        .into_iter()
+        .filter(|comment| {
+            comment
+                .created_at
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_str(at, TRACKER_DATE_FORMAT).ok())
+                .is_some_and(|at| at >= since)
+        })
+        .collect();
+    let skip = recent.len().saturating_sub(COMMENTS_PER_ISSUE);
+    recent.drain(..skip);
+    recent
+}
+
+/// Контекст справки: встреча целиком, затем задачи в порядке поиска
+pub fn prep_context(event: &Event, issues: &[SharedIssue], budget_tokens: usize) -> String {
+    let mut meeting = vec![format!(
+        "Время: {}–{}",
+        event.start.format("%Y-%m-%d %H:%M"),
+        event.end.format("%H:%M")
+    )];
+    // Логины вместо адресов: адреса всё равно скрылись бы перед отправкой
+    let logins = attendee_logins(event, None);
+    if !logins.is_empty() {
+        meeting.push(format!("Участники: {}", logins.join(", ")));
+    }
+    if let Some(agenda) = event
+        .description
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        meeting.push(format!("Повестка:\n{}", agenda.trim()));
+    }
+    let mut items = vec![ContextItem::new(
+        format!("Встреча: {}", event.summary),
+        redact::text("prep", &meeting.join("\n")),
+    )
+    .priority(u32::MAX)];
+
+    items.extend(issues.iter().enumerate().map(|(index, shared)| {
+        let issue = &redact::issue(&shared.issue);
+        let mut details = Vec::new();
+        if let Some(status) = issue.status.as_ref().and_then(|s| s.display.as_deref()) {
+            details.push(format!("Статус: {status}"));
+        }
+        if let Some(assignee) = issue.assignee.as_ref().and_then(|a| a.display.as_deref()) {
+            details.push(format!("Исполнитель: {assignee}"));
+        }
+        if let Some(updated) = issue.updated_at.as_deref() {
+            details.push(format!(
+                "Обновлена: {}",
+                updated.get(..10).unwrap_or(updated)
+            ));
+        }
+        for comment in &shared.comments {
+            let author = comment
+                .created_by
+                .as_ref()
+                .and_then(|user| user.display.as_deref())
+                .unwrap_or("кто-то");
+            let text = redact::text("prep", comment.text.trim());
+            details.push(format!("Комментарий ({author}): {text}"));
+        }
+        ContextItem::new(
+            format!("{}: {}", issue.key, issue.summary),
+            details.join("\n"),
+        )
+        .priority(issues.len().saturating_sub(index) as u32)
+    }));
+    context::pack(items, budget_tokens)
+}
+
+/// Попросить LLM составить справку по собранному контексту
+#[instrument(skip_all)]
+pub async fn brief<T: LlmClientTrait>(llm: &T, context: &str) -> Result<String> {
+    let response = llm
+        .complete_with_system(
+            SYSTEM_PROMPT.to_string(),
+            format!("{PREP_PROMPT}\n\n{context}"),
+        )
+        .await?;
+    Ok(response.trim().to_string())
+}
+
+/// Собирает задачи и составляет справку к встрече
+pub async fn prepare<T: LlmClientTrait>(
+    client: &TrackerClient,
+    llm: &T,
+    event: &Event,
+    limit: usize,
+) -> Result<String> {
+    let issues = gather(client, event, limit).await?;
+    let context = prep_context(event, &issues, CONTEXT_TOKENS);
+    brief(llm, &context).await
+}
+
+/// Встречи с участниками, которые начинаются в ближайшие `minutes_before` минут
+pub fn upcoming(events: &[Event], now: NaiveDateTime, minutes_before: i64) -> Vec<&Event> {
+    let horizon = now + Duration::minutes(minutes_before);
+    events
+        .iter()
+        .filter(|event| !event.all_day && !event.attendees.is_empty())
+        .filter(|event| event.start > now && event.start <= horizon)
+        .collect()
+}
+
+/// Файл справки: дата и время встречи и UID или название
+fn brief_path(event: &Event) -> Option<PathBuf> {
+    let name: String = event
+        .uid
+        .as_deref()
+        .unwrap_or(&event.summary)
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(60)
+        .collect();
+    let file = format!("{}-{name}.md", event.start.format("%Y%m%d-%H%M"));
+    Some(paths::state_dir()?.join("prep").join(file))
+}
+
+/// Готовит справки к ближайшим встречам, пока работает `you serve`
+pub async fn run(client: TrackerClient, config: PrepConfig, notifier: Notifier) {
+    let minutes_before = config.minutes_before.unwrap_or(DEFAULT_MINUTES_BEFORE);
+    let model = config.model.unwrap_or_else(|| DEFAULT_MODEL.to_string());
+    let llm = match LlmConfig::new(model).and_then(LlmClient::new) {
+        Ok(llm) => llm,
+        Err(err) => {
+            warn!(error = %err, "Справки к встречам не готовятся: LLM недоступна");
+            return;
+        }
+    };
+    let mut prepared: HashSet<(Option<String>, NaiveDateTime)> = HashSet::new();
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        let zone = timezone::current();
+        let events = match calendar_lib::load_from_env_in(zone) {
+            Ok(events) => events,
+            Err(err) => {
+                debug!(error = %err, "Календарь недоступен");
+                continue;
+            }
+        };
+        for event in upcoming(&events, zone_now(zone), minutes_before) {
+            if !prepared.insert((event.uid.clone(), event.start)) {
+                continue;
+            }
+            if let Err(err) = deliver(&client, &llm, event, &notifier).await {
+                warn!(summary = %event.summary, error = %err, "Справка к встрече не готова");
+            }
+        }
+    }
+}
+
+fn zone_now(zone: Zone) -> NaiveDateTime {
+    zone.from_utc(&Utc::now())
+}
+
+/// Составляет справку, сохраняет её и показывает уведомление
+#[instrument(skip_all, fields(summary = %event.summary))]
+async fn deliver(
+    client: &TrackerClient,
+    llm: &LlmClient,
+    event: &Event,
+    notifier: &Notifier,
+) -> Result<()> {
+    let brief = prepare(client, llm, event, DEFAULT_LIMIT).await?;
+    let path = brief_path(event).context("Не определён каталог состояния для справок")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Не удалось создать {}", dir.display()))?;
+    }
+    std::fs::write(&path, format!("# {}\n\n{brief}\n", event.summary))
+        .with_context(|| format!("Не удалось записать {}", path.display()))?;
+    info!(path = %path.display(), "Справка к встрече сохранена");
+    notifier.send(
+        &format!(
+            "Встреча в {}: {}",
+            event.start.format("%H:%M"),
+            event.summary
+        ),
+        &format!("Справка: {}", path.display()),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn meeting(start: &str, attendees: &[&str]) -> Event {
+        let start = NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap();
+        Event {
+            uid: Some(format!("sync-{}", start.format("%H%M"))),
+            summary: "Синк по релизу".to_string(),
+            description: Some("Обсудить TREK-7 и сроки".to_string()),
+            location: None,
+            url: None,
+            attendees: attendees.iter().map(|a| a.to_string()).collect(),
+            start,
+            end: start + Duration::minutes(30),
+            all_day: false,
+        }
+    }
+
+    #[test]
+    fn test_attendees_become_shared_query() {
+        let event = meeting(
+            "2026-10-16 12:00",
+            &["me@corp.ru", "ann@corp.ru", "bob@corp.ru", "ann@corp.ru"],
+        );
+        let logins = attendee_logins(&event, Some("ME"));
+        assert_eq!(logins, ["ann", "bob"]);
+
+        let query = shared_query(&logins).unwrap();
+        assert!(
+            query.starts_with("(Assignee: ann, bob OR Author: ann, bob OR Followers: ann, bob)")
+        );
+        assert!(query.contains("(Assignee: me() OR Author: me() OR Followers: me())"));
+        assert!(shared_query(&[]).is_none());
+
+        let now = NaiveDateTime::parse_from_str("2026-10-16 11:50", "%Y-%m-%d %H:%M").unwrap();
+        let events = vec![
+            event.clone(),
+            meeting("2026-10-16 12:30", &["ann@corp.ru"]),
+            meeting("2026-10-16 12:05", &[]),
+        ];
+        let soon: Vec<_> = upcoming(&events, now, DEFAULT_MINUTES_BEFORE)
+            .into_iter()
+            .map(|event| event.uid.clone().unwrap())
+            .collect();
+        assert_eq!(soon, ["sync-1200"]);
+    }
+
+    #[tokio::test]
+    async fn test_brief_gets_meeting_and_shared_issues() {
+        let event = meeting("2026-10-16 12:00", &["ann@corp.ru"]);
+        let issue = testing::issue("TREK-7")
+            .summary("Релиз 2.0")
+            .status("inProgress", "В работе")
+            .build();
+        let comment: Comment = serde_json::from_value(serde_json::json!({
+            "text": "Ждём согласования от безопасности",
+            "createdBy": {"display": "Анна"}
+        }))
+        .unwrap();
+        let context = prep_context(
+            &event,
+            &[SharedIssue {
+                issue,
+                comments: vec![comment],
+            }],
+            CONTEXT_TOKENS,
+        );
+
+        let mut llm = llm_lib::MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .withf(|_, prompt| {
+                prompt.starts_with(PREP_PROMPT)
+                    && prompt.contains("Встреча: Синк по релизу")
+                    && prompt.contains("Участники: ann")
+                    && prompt.contains("Повестка:\nОбсудить TREK-7 и сроки")
+                    && prompt.contains("TREK-7: Релиз 2.0")
+                    && prompt.contains("Комментарий (Анна): Ждём согласования от безопасности")
+            })
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async { Ok("## Синк по релизу\n".to_string()) })
+            });
+
+        assert_eq!(brief(&llm, &context).await.unwrap(), "## Синк по релизу");
+    }
+}
@@ -0,0 +1,84 @@
+//! Пресеты полей задачи для очередей
+//!
+//! В секции `presets` конфигурации для очереди задаются тип, приоритет, компоненты,
+//! теги и ревьюеры по умолчанию. `you tracker create` применяет пресет очереди к
+//! новой задаче: явно переданные значения важнее, списки дополняются. `--no-preset`
+//! отключает пресет.
+
+use serde::Deserialize;
+use tracker_lib::{ids::UserLogin, task::CreateIssueRequest};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct QueuePreset {
+    /// Ключ типа задачи (например, bug)
+    #[serde(rename = "type")]
+    pub issue_type: Option<String>,
+
+    /// Ключ приоритета (например, normal)
+    pub priority: Option<String>,
+
+    /// Компоненты задачи
+    pub components: Vec<String>,
+
+    /// Теги задачи
+    pub tags: Vec<String>,
+
+    /// Ревьюеры: добавляются в наблюдатели задачи
+    pub reviewers: Vec<UserLogin>,
+}
+
+impl QueuePreset {
+    /// Дополнить запрос значениями пресета, не перезаписывая заданные явно
+    pub fn apply(&self, request: &mut CreateIssueRequest) {
+        if request.issue_type.is_none() {
+            request.issue_type = self.issue_type.clone();
+        }
+        if request.priority.is_none() {
+            request.priority = self.priority.clone();
+        }
+        extend_unique(&mut request.components, &self.components);
+        extend_unique(&mut request.tags, &self.tags);
+        extend_unique(&mut request.followers, &self.reviewers);
+    }
+}
+
+fn extend_unique<T: Clone + PartialEq>(target: &mut Vec<T>, values: &[T]) {
+    for value in values {
+        if !target.contains(value) {
+            target.push(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_keeps_explicit_values_and_merges_lists() {
+        let preset: QueuePreset = serde_norway::from_str(
+            r#"
+type: bug
+priority: normal
+components: [backend]
+tags: [cli, triage]
+reviewers: [ann, bob]
+"#,
+        )
+        .unwrap();
+
+        let mut request = CreateIssueRequest {
+            priority: Some("critical".to_string()),
+            tags: vec!["cli".to_string()],
+            ..CreateIssueRequest::new("TREK".parse().unwrap(), "Падает экспорт")
+        };
+        preset.apply(&mut request);
+
+        assert_eq!(request.issue_type.as_deref(), Some("bug"));
+        assert_eq!(request.priority.as_deref(), Some("critical"));
+        assert_eq!(request.components, ["backend"]);
+        assert_eq!(request.tags, ["cli", "triage"]);
+        assert_eq!(request.followers, ["ann", "bob"]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Полоса прогресса загрузки вложений в терминале
+//!
+//! Полоса перерисовывается в stderr, только если он терминал: при выводе в файл или
+//! конвейер прогресс не засоряет журнал. Повторная попытка после временной ошибки
+//! начинает полосу с нуля и отмечается номером попытки.
+
+use std::io::{self, IsTerminal, Write};
+
+use tracker_lib::attachments::UploadProgress;
+
+/// Ширина полосы в символах
+const BAR_WIDTH: usize = 24;
+
+const MEGABYTE: f64 = 1024.0 * 1024.0;
+
+/// Обработчик прогресса для `upload_attachment_with_progress`
+pub fn upload_bar(name: &str) -> impl Fn(UploadProgress) + Send + Sync + 'static {
+    let name = name.to_string();
+    let enabled = io::stderr().is_terminal();
+    move |progress| {
+        if enabled {
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r{}", render(&name, progress));
+            let _ = stderr.flush();
+        }
+    }
+}
+
+/// Стирает полосу, чтобы следующая строка вывода начиналась с начала
+pub fn finish_bar() {
+    if io::stderr().is_terminal() {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+fn render(name: &str, progress: UploadProgress) -> String {
+    let ratio = if progress.total == 0 {
+        1.0
+    } else {
+        progress.sent as f64 / progress.total as f64
+    };
+    let filled = ((ratio * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let mut line = format!(
+        "{name} [{}{}] {:>3}% {:.1}/{:.1} МБ",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        (ratio * 100.0).round() as u64,
+        progress.sent as f64 / MEGABYTE,
+        progress.total as f64 / MEGABYTE,
+    );
+    if progress.attempt > 1 {
+        line.push_str(&format!(", попытка {}", progress.attempt));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_bar_with_attempt() {
+        let progress = UploadProgress {
+            sent: 3 * 1024 * 1024,
+            total: 12 * 1024 * 1024,
+            attempt: 1,
+        };
+        assert_eq!(
+            render("dump.bin", progress),
+            "dump.bin [######------------------]  25% 3.0/12.0 МБ"
+        );
+
+        let retry = UploadProgress {
+            sent: 12 * 1024 * 1024,
+            attempt: 2,
+            ..progress
+        };
+        assert!(render("dump.bin", retry).ends_with("100% 12.0/12.0 МБ, попытка 2"));
+    }
+}
@@ -0,0 +1,359 @@
+//! Библиотека именованных промптов для LLM
+//!
+//! Пресет — шаблон запроса с переменными `{name}` и, при необходимости, системный
+//! промпт и модель. Встроенные пресеты `review`, `explain` и `translate-ru` можно
+//! переопределить и дополнить в секции `prompts` конфигурации. `you llm run review
+//! --var file=@src/main.rs` подставляет переменные и отправляет запрос; значение с `@`
+//! читается из файла. В TUI пресет выбирается клавишей Tab на экране LLM.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{bail, Context, Result};
+use llm_lib::{CompletionOptions, LlmClientTrait, Message};
+use serde::Deserialize;
+use tracing::instrument;
+
+/// Переменная, в которую TUI подставляет введённый текст
+pub const INPUT_VAR: &str = "input";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PromptPreset {
+    /// Краткое описание для списка пресетов
+    pub description: Option<String>,
+
+    /// Системный промпт; может содержать переменные
+    pub system: Option<String>,
+
+    /// Шаблон запроса с переменными `{name}`
+    pub prompt: String,
+
+    /// Модель, если пресету нужна не модель по умолчанию
+    pub model: Option<String>,
+}
+
+/// Промпт с подставленными переменными
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedPrompt {
+    pub system: Option<String>,
+    pub prompt: String,
+    /// Температура; без неё запрос уходит с настройками модели по умолчанию
+    pub temperature: Option<f32>,
+}
+
+impl PromptPreset {
+    fn builtin(description: &str, system: &str, prompt: &str) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            system: Some(system.to_string()),
+            prompt: prompt.to_string(),
+            model: None,
+        }
+    }
+
+    /// Имена переменных в порядке первого упоминания: сначала системный промпт, затем запрос
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for template in self.system.iter().chain([&self.prompt]) {
+            for name in placeholders(template) {
+                if !names.iter().any(|known| known == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names
+    }
+
+    /// Подставить переменные; незаданная переменная — ошибка
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<RenderedPrompt> {
+        let missing: Vec<String> = self
+            .variables()
+            .into_iter()
+            .filter(|name| !vars.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            bail!("Не заданы переменные пресета: {}", missing.join(", "));
+        }
+        Ok(RenderedPrompt {
+            system: self
+                .system
+                .as_deref()
+                .map(|system| substitute(system, vars)),
+            prompt: substitute(&self.prompt, vars),
+            temperature: None,
+        })
+    }
+}
+
+impl RenderedPrompt {
+    /// Отправить промпт в LLM
+    #[instrument(skip_all)]
+    pub async fn send<T: LlmClientTrait>(self, llm: &T) -> Result<String> {
+        if let Some(temperature) = self.temperature {
+            let messages = self
+                .system
+                .map(Message::system)
+                .into_iter()
+                .chain([Message::user(self.prompt)])
+                .collect();
+            let options = CompletionOptions::new().temperature(temperature);
+            let completion = llm.chat_completion(messages, Some(options)).await?;
+            return Ok(completion
+                .content()
+                .context("No content in response")?
+                .to_string());
+        }
+        let response = match self.system {
+            Some(system) => llm.complete_with_system(system, self.prompt).await?,
+            None => llm.complete(self.prompt).await?,
+        };
+        Ok(response)
+    }
+
+    /// Отправить промпт после предыдущих реплик диалога
+    #[instrument(skip_all, fields(history = history.len()))]
+    pub async fn send_with_history<T: LlmClientTrait>(
+        self,
+        llm: &T,
+        history: &[Message],
+    ) -> Result<String> {
+        let messages = self
+            .system
+            .map(Message::system)
+            .into_iter()
+            .chain(history.iter().cloned())
+            .chain([Message::user(self.prompt)])
+            .collect();
+        let options = self
+            .temperature
+            .map(|temperature| CompletionOptions::new().temperature(temperature));
+        let completion = llm.chat_completion(messages, options).await?;
+        Ok(completion
+            .content()
+            .context("No content in response")?
+            .to_string())
+    }
+}
+
+/// Встроенные пресеты, дополненные и переопределённые пресетами из конфигурации
+pub fn library(configured: &HashMap<String, PromptPreset>) -> BTreeMap<String, PromptPreset> {
+    let mut library = BTreeMap::from([
+        (
+            "review".to_string(),
+            PromptPreset::builtin(
+                "Ревью кода: ошибки, риски и что упростить",
+                "Ты опытный ревьюер кода. Отвечай по делу и в Markdown.",
+                "Сделай ревью кода. Найди ошибки, риски и места, которые стоит упростить; \
+                 для каждого замечания предложи правку.\n\n```\n{file}\n```",
+            ),
+        ),
+        (
+            "explain".to_string(),
+            PromptPreset::builtin(
+                "Объяснить код или текст простыми словами",
+                "Ты терпеливый наставник. Объясняй простыми словами и по шагам.",
+                "Объясни, что здесь происходит и зачем:\n\n{input}",
+            ),
+        ),
+        (
+            "translate-ru".to_string(),
+            PromptPreset::builtin(
+                "Перевести на русский",
+                "Ты технический переводчик. Отвечай только переводом.",
+                "Переведи на русский язык. Сохрани форматирование, код и термины без \
+                 устоявшегося перевода:\n\n{input}",
+            ),
+        ),
+    ]);
+    library.extend(
+        configured
+            .iter()
+            .map(|(name, preset)| (name.clone(), preset.clone())),
+    );
+    library
+}
+
+/// Разбирает `--var name=value`; значение `@path` читается из файла
+pub fn parse_var(value: &str) -> Result<(String, String), String> {
+    let (name, value) = value
+        .split_once('=')
+        .ok_or_else(|| format!("«{value}»: ожидается имя=значение"))?;
+    let name = name.trim();
+    if !is_variable_name(name) {
+        return Err(format!("«{name}» не подходит для имени переменной"));
+    }
+    let value = resolve_value(value).map_err(|err| format!("{err:#}"))?;
+    Ok((name.to_string(), value))
+}
+
+/// Значение переменной: `@path` заменяется содержимым файла
+pub fn resolve_value(value: &str) -> Result<String> {
+    match value.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Не удалось прочитать {path} для переменной")),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Переменные из строки TUI: `имя=значение` в начале, остаток — в единственную
+/// оставшуюся переменную пресета (или в `{input}`)
+pub fn vars_from_line(preset: &PromptPreset, line: &str) -> Result<HashMap<String, String>> {
+    let mut vars = HashMap::new();
+    let mut rest = line.trim();
+    while let Some(token) = rest.split_whitespace().next() {
+        let Ok((name, value)) = parse_var(token) else {
+            break;
+        };
+        vars.insert(name, value);
+        rest = rest[token.len()..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        let unfilled: Vec<String> = preset
+            .variables()
+            .into_iter()
+            .filter(|name| !vars.contains_key(name))
+            .collect();
+        let target = match unfilled.as_slice() {
+            [single] => single.clone(),
+            _ => INPUT_VAR.to_string(),
+        };
+        vars.insert(target, resolve_value(rest)?);
+    }
+    Ok(vars)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Имена в фигурных скобках; скобки с другим содержимым (код, JSON) не считаются переменными
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|part| {
+        let (name, _) = part.split_once('}')?;
+        is_variable_name(name).then_some(name)
+    })
+}
+
+/// Подстановка за один проход, чтобы `{name}` внутри значений не раскрывался повторно
+fn substitute(template: &str, vars: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .split_once('}')
+            .filter(|(name, _)| is_variable_name(name))
+            .and_then(|(name, tail)| Some((vars.get(name)?, tail)));
+        match value {
+            Some((value, tail)) => {
+                result.push_str(value);
+                rest = tail;
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_substitutes_variables_and_keeps_code_braces() {
+        let preset = PromptPreset {
+            system: Some("Ты эксперт по {lang}".to_string()),
+            prompt: "Объясни {lang}: fn main() { {input} }".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(preset.variables(), ["lang", "input"]);
+        let rendered = preset
+            .render(&vars(&[("lang", "Rust"), ("input", "loop {lang}")]))
+            .unwrap();
+        assert_eq!(rendered.system.as_deref(), Some("Ты эксперт по Rust"));
+        assert_eq!(rendered.prompt, "Объясни Rust: fn main() { loop {lang} }");
+
+        let err = preset.render(&vars(&[("lang", "Rust")])).unwrap_err();
+        assert!(err.to_string().contains("input"));
+    }
+
+    #[test]
+    fn test_library_config_overrides_builtins() {
+        let configured: HashMap<String, PromptPreset> = serde_norway::from_str(
+            r#"
+explain:
+  prompt: "Коротко: {input}"
+standup:
+  description: Текст для стендапа
+  model: openai/gpt-4o-mini
+  prompt: "Сделай стендап из заметок: {notes}"
+"#,
+        )
+        .unwrap();
+
+        let library = library(&configured);
+        assert_eq!(library["explain"].prompt, "Коротко: {input}");
+        assert_eq!(library["review"].variables(), ["file"]);
+        assert_eq!(
+            library["standup"].model.as_deref(),
+            Some("openai/gpt-4o-mini")
+        );
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse_var("lang=Rust = 2021"),
+            Ok(("lang".to_string(), "Rust = 2021".to_string()))
+        );
+        assert!(parse_var("lang").is_err());
+        assert!(parse_var("два слова=x").is_err());
+        assert!(parse_var("file=@/nonexistent/prompt.txt").is_err());
+    }
+
+    #[test]
+    fn test_vars_from_line_fills_single_variable() {
+        let library = library(&HashMap::new());
+
+        let review = vars_from_line(&library["review"], "fn main() {}").unwrap();
+        assert_eq!(review, vars(&[("file", "fn main() {}")]));
+
+        let preset = PromptPreset {
+            prompt: "{lang}: {input}".to_string(),
+            ..Default::default()
+        };
+        let line = vars_from_line(&preset, "lang=Go что такое defer?").unwrap();
+        assert_eq!(line, vars(&[("lang", "Go"), ("input", "что такое defer?")]));
+    }
+
+    #[tokio::test]
+    async fn test_send_uses_system_prompt() {
+        let mut llm = llm_lib::MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .withf(|system, prompt| system.contains("переводчик") && prompt.ends_with("Hello"))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok("Привет".to_string()) }));
+
+        let rendered = library(&HashMap::new())["translate-ru"]
+            .render(&vars(&[("input", "Hello")]))
+            .unwrap();
+        assert_eq!(rendered.send(&llm).await.unwrap(), "Привет");
+    }
+}
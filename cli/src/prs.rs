@@ -0,0 +1,311 @@
+//! Связь pull/merge request'ов с задачами Трекера
+//!
+//! Открытые PR берутся через `gh` (GitHub) или `glab` (GitLab), ключи задач ищутся
+//! в названии, описании, ветке и сообщениях коммитов. В каждую найденную задачу
+//! добавляется комментарий со ссылкой на PR, и задача переводится в статус ревью.
+//! Повторный запуск ничего не дублирует: задачи, где ссылка уже есть, пропускаются.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use tokio::process::Command;
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    comments::AddCommentRequest,
+    ids::IssueKey,
+    models::{Issue, Transition},
+    task::find_issue_keys,
+    transitions::ExecuteTransitionRequest,
+    TrackerClient, TrackerError,
+};
+
+/// Статус, в который переводятся задачи с открытым PR
+pub const DEFAULT_REVIEW_STATUS: &str = "inReview";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Forge {
+    Github,
+    Gitlab,
+}
+
+impl Forge {
+    /// Определяет хостинг по адресу `origin` текущего репозитория
+    pub async fn detect() -> Result<Self> {
+        let url = run("git", &["remote", "get-url", "origin"]).await?;
+        Ok(if url.contains("gitlab") {
+            Forge::Gitlab
+        } else {
+            Forge::Github
+        })
+    }
+
+    async fn list_open(self, limit: usize) -> Result<Vec<PullRequest>> {
+        let limit = limit.to_string();
+        let output = match self {
+            Forge::Github => {
+                run(
+                    "gh",
+                    &[
+                        "pr",
+                        "list",
+                        "--state",
+                        "open",
+                        "--limit",
+                        &limit,
+                        "--json",
+                        "number,title,url,body,headRefName,commits",
+                    ],
+                )
+                .await?
+            }
+            Forge::Gitlab => {
+                run(
+                    "glab",
+                    &["mr", "list", "--per-page", &limit, "--output", "json"],
+                )
+                .await?
+            }
+        };
+        serde_json::from_str(&output).context("Не удалось разобрать список PR")
+    }
+}
+
+/// Pull request GitHub или merge request GitLab
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    #[serde(alias = "iid")]
+    pub number: u64,
+    pub title: String,
+    #[serde(alias = "web_url")]
+    pub url: String,
+    #[serde(default, alias = "description")]
+    pub body: Option<String>,
+    #[serde(default, alias = "headRefName", alias = "source_branch")]
+    pub branch: Option<String>,
+    /// Коммиты отдаёт только `gh`
+    #[serde(default)]
+    pub commits: Vec<PullRequestCommit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestCommit {
+    #[serde(default, rename = "messageHeadline")]
+    pub headline: String,
+    #[serde(default, rename = "messageBody")]
+    pub body: String,
+}
+
+impl PullRequest {
+    /// Ключи задач из названия, ветки, описания и коммитов
+    pub fn issue_keys(&self) -> Vec<IssueKey> {
+        let mut texts = vec![self.title.as_str()];
+        texts.extend(self.branch.as_deref());
+        texts.extend(self.body.as_deref());
+        for commit in &self.commits {
+            texts.push(&commit.headline);
+            texts.push(&commit.body);
+        }
+        find_issue_keys(&texts.join("\n"))
+    }
+
+    fn comment(&self) -> String {
+        format!(
+            "Pull request #{}: {}\n{}",
+            self.number, self.title, self.url
+        )
+    }
+}
+
+/// Переход в статус: по ключу статуса или его названию
+pub(crate) fn transition_to<'a>(
+    transitions: &'a [Transition],
+    status: &str,
+) -> Option<&'a Transition> {
+    transitions.iter().find(|transition| {
+        transition.to.as_ref().is_some_and(|to| {
+            to.key
+                .as_deref()
+                .is_some_and(|key| key.eq_ignore_ascii_case(status))
+                || to
+                    .display
+                    .as_deref()
+                    .is_some_and(|display| display.eq_ignore_ascii_case(status))
+        })
+    })
+}
+
+fn in_status(issue: &Issue, status: &str) -> bool {
+    issue.status.as_ref().is_some_and(|current| {
+        current
+            .key
+            .as_deref()
+            .is_some_and(|key| key.eq_ignore_ascii_case(status))
+            || current
+                .display
+                .as_deref()
+                .is_some_and(|display| display.eq_ignore_ascii_case(status))
+    })
+}
+
+/// Связывает открытые PR с задачами; возвращает строки отчёта
+#[instrument(skip(client))]
+pub async fn sync(
+    client: &TrackerClient,
+    forge: Forge,
+    limit: usize,
+    review_status: &str,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    let prs = forge.list_open(limit).await?;
+    debug!(count = prs.len(), "Открытые PR получены");
+
+    let mut report = Vec::new();
+    for pr in &prs {
+        for key in pr.issue_keys() {
+            let line = match link(client, pr, &key, review_status, dry_run).await {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!(issue_key = %key, error = %err, "Не удалось связать PR с задачей");
+                    format!("{key} ← #{}: ошибка: {err}", pr.number)
+                }
+            };
+            report.push(line);
+        }
+    }
+    info!(links = report.len(), "PR сопоставлены с задачами");
+    Ok(report)
+}
+
+async fn link(
+    client: &TrackerClient,
+    pr: &PullRequest,
+    key: &IssueKey,
+    review_status: &str,
+    dry_run: bool,
+) -> Result<String> {
+    let issue = match client.get_issue(key, None).await {
+        Ok(issue) => issue,
+        // Похожие на ключ строки (например, UTF-8) не обязаны быть задачами
+        Err(TrackerError::NotFound { .. }) => {
+            return Ok(format!("{key} ← #{}: задача не найдена", pr.number))
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut actions = Vec::new();
+    let comments = client.get_comments(key).await?;
+    if !comments
+        .iter()
+        .any(|comment| comment.text.contains(&pr.url))
+    {
+        if !dry_run {
+            client
+                .add_comment(key, &AddCommentRequest::new(pr.comment()))
+                .await?;
+        }
+        actions.push("комментарий со ссылкой".to_string());
+    }
+
+    if !in_status(&issue, review_status) {
+        let transitions = client.get_transitions(key).await?;
+        match transition_to(&transitions, review_status) {
+            Some(transition) => {
+                if !dry_run {
+                    client
+                        .execute_transition(
+                            key,
+                            &transition.id,
+                            &ExecuteTransitionRequest::default(),
+                            issue.version,
+                        )
+                        .await?;
+                }
+                actions.push(format!(
+                    "переход «{}»",
+                    transition.display.as_deref().unwrap_or(&transition.id)
+                ));
+            }
+            None => actions.push(format!("нет перехода в {review_status}")),
+        }
+    }
+
+    let prefix = if dry_run { "[dry-run] " } else { "" };
+    let actions = if actions.is_empty() {
+        "уже связано".to_string()
+    } else {
+        actions.join(", ")
+    };
+    Ok(format!("{prefix}{key} ← #{}: {actions}", pr.number))
+}
+
+async fn run(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .with_context(|| format!("Не удалось запустить {program}; установлен ли он?"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} завершился с ошибкой: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_github_pr_keys_include_branch_and_commits() {
+        let prs: Vec<PullRequest> = serde_json::from_value(json!([{
+            "number": 42,
+            "title": "Fix socks proxy",
+            "url": "https://github.com/org/repo/pull/42",
+            "body": "Closes TREK-2",
+            "headRefName": "TREK-1-socks",
+            "commits": [{"messageHeadline": "OPS-7: bump reqwest", "messageBody": ""}]
+        }]))
+        .unwrap();
+
+        assert_eq!(prs[0].issue_keys(), ["TREK-1", "TREK-2", "OPS-7"]);
+    }
+
+    #[test]
+    fn test_gitlab_mr_fields_are_mapped() {
+        let prs: Vec<PullRequest> = serde_json::from_value(json!([{
+            "iid": 5,
+            "title": "TREK-3 retry on 429",
+            "web_url": "https://gitlab.com/org/repo/-/merge_requests/5",
+            "description": null,
+            "source_branch": "retry"
+        }]))
+        .unwrap();
+
+        assert_eq!(prs[0].number, 5);
+        assert_eq!(prs[0].url, "https://gitlab.com/org/repo/-/merge_requests/5");
+        assert_eq!(prs[0].issue_keys(), ["TREK-3"]);
+    }
+
+    #[test]
+    fn test_transition_to_matches_key_or_display() {
+        let transitions: Vec<Transition> = serde_json::from_value(json!([
+            {"id": "close", "to": {"key": "closed", "display": "Закрыт"}},
+            {"id": "review", "to": {"key": "inReview", "display": "In Review"}}
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            transition_to(&transitions, "inreview").map(|t| t.id.as_str()),
+            Some("review")
+        );
+        assert_eq!(
+            transition_to(&transitions, "In Review").map(|t| t.id.as_str()),
+            Some("review")
+        );
+        assert!(transition_to(&transitions, "testing").is_none());
+    }
+}
@@ -0,0 +1,263 @@
+//! Правила уведомлений по очередям
+//!
+//! `queue.TREK.notify: [assigned, mention]` оставляет для задач очереди TREK только
+//! перечисленные типы уведомлений: остальные не попадают во входящие `you serve` и
+//! `you inbox` и не пересылаются в чаты. Очереди без правила получают все уведомления,
+//! а напоминания приходят всегда. `you serve` и TUI следят за файлом конфигурации
+//! через уведомления файловой системы (`notify`) и применяют изменённые правила без
+//! перезапуска.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::ids::{IssueKey, QueueKey};
+
+use crate::{
+    config::Config,
+    inbox::{Notification, NotificationKind},
+};
+
+/// Сколько ждать после изменения файла: редакторы сохраняют его несколькими событиями
+const RELOAD_DELAY: Duration = Duration::from_millis(200);
+
+/// Настройки очереди в секции `queue`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QueueConfig {
+    /// Типы уведомлений по задачам очереди; без ключа — все
+    pub notify: Option<Vec<NotificationKind>>,
+}
+
+/// Какие типы уведомлений пропускать по каждой очереди
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NotifyRules {
+    queues: HashMap<QueueKey, Vec<NotificationKind>>,
+}
+
+impl NotifyRules {
+    pub fn new(queues: &HashMap<QueueKey, QueueConfig>) -> Self {
+        Self {
+            queues: queues
+                .iter()
+                .filter_map(|(queue, config)| Some((queue.clone(), config.notify.clone()?)))
+                .collect(),
+        }
+    }
+
+    /// Пропускать ли уведомление; напоминания и задачи вне правил проходят всегда
+    pub fn allows(&self, notification: &Notification) -> bool {
+        if notification.kind == NotificationKind::Reminder {
+            return true;
+        }
+        let Ok(issue_key) = notification.issue_key.parse::<IssueKey>() else {
+            return true;
+        };
+        match self.queues.get(&issue_key.queue()) {
+            Some(kinds) => kinds.contains(&notification.kind),
+            None => true,
+        }
+    }
+}
+
+/// Правила, которые перечитываются при изменении файла конфигурации
+#[derive(Debug, Clone, Default)]
+pub struct LiveRules {
+    rules: Arc<RwLock<NotifyRules>>,
+}
+
+impl LiveRules {
+    pub fn new(rules: NotifyRules) -> Self {
+        Self {
+            rules: Arc::new(RwLock::new(rules)),
+        }
+    }
+
+    pub fn allows(&self, notification: &Notification) -> bool {
+        self.current().allows(notification)
+    }
+
+    pub fn current(&self) -> NotifyRules {
+        self.rules
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Следит за файлом конфигурации в фоне; без пути к конфигурации правила не меняются
+    ///
+    /// Наблюдается каталог файла, а не сам файл: редакторы часто сохраняют его
+    /// через переименование временного файла.
+    pub fn watch(&self) {
+        if let Some(path) = Config::path() {
+            self.watch_path(path);
+        }
+    }
+
+    fn watch_path(&self, path: PathBuf) {
+        let Some(dir) = path
+            .parent()
+            .filter(|dir| dir.is_dir())
+            .map(Path::to_path_buf)
+        else {
+            debug!("Каталога конфигурации нет, правила уведомлений не перечитываются");
+            return;
+        };
+        let (sender, mut events) = mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })
+        .and_then(|mut watcher| {
+            watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        let watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!(error = %err, "Не удалось следить за конфигурацией, правила уведомлений не перечитываются");
+                return;
+            }
+        };
+
+        let rules = self.clone();
+        tokio::spawn(async move {
+            // Наблюдатель живёт, пока живёт задача
+            let _watcher = watcher;
+            while let Some(event) = events.recv().await {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(err) => {
+                        warn!(error = %err, "Ошибка слежения за конфигурацией");
+                        continue;
+                    }
+                };
+                let changed = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) && event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == path.file_name());
+                if !changed {
+                    continue;
+                }
+                tokio::time::sleep(RELOAD_DELAY).await;
+                while events.try_recv().is_ok() {}
+                rules.reload_from(&path);
+            }
+        });
+    }
+
+    /// Перечитывает правила; при ошибке в файле остаются прежние
+    #[instrument(skip(self))]
+    fn reload_from(&self, path: &Path) {
+        let config = if path.exists() {
+            Config::load_from(path)
+        } else {
+            Ok(Config::default())
+        };
+        let fresh = match config {
+            Ok(config) => NotifyRules::new(&config.queue),
+            Err(err) => {
+                warn!(error = %format!("{err:#}"), "Конфигурация не перечитана, правила уведомлений прежние");
+                return;
+            }
+        };
+        let mut rules = self
+            .rules
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *rules == fresh {
+            debug!("Правила уведомлений не изменились");
+            return;
+        }
+        info!(queues = fresh.queues.len(), "Правила уведомлений обновлены");
+        *rules = fresh;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(kind: NotificationKind, issue_key: &str) -> Notification {
+        Notification {
+            id: "1".to_string(),
+            kind,
+            issue_key: issue_key.to_string(),
+            issue_summary: String::new(),
+            text: String::new(),
+            author: None,
+            at: "2026-10-16T09:00:00.000+0000".to_string(),
+            read: false,
+        }
+    }
+
+    #[test]
+    fn test_rules_filter_kinds_per_queue() {
+        let queues: HashMap<QueueKey, QueueConfig> = serde_norway::from_str(
+            "\
+TREK:
+  notify: [assigned, mention]
+OPS: {}
+",
+        )
+        .unwrap();
+        let rules = NotifyRules::new(&queues);
+
+        assert!(rules.allows(&notification(NotificationKind::Assigned, "TREK-1")));
+        assert!(!rules.allows(&notification(NotificationKind::StatusChanged, "TREK-1")));
+        assert!(rules.allows(&notification(NotificationKind::Reminder, "TREK-1")));
+        assert!(rules.allows(&notification(NotificationKind::StatusChanged, "OPS-1")));
+        assert!(rules.allows(&notification(NotificationKind::StatusChanged, "WEB-1")));
+    }
+
+    #[test]
+    fn test_live_rules_reload_and_keep_old_rules_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let status = notification(NotificationKind::StatusChanged, "TREK-1");
+
+        let rules = LiveRules::default();
+        std::fs::write(&path, "queue:\n  TREK:\n    notify: [mention]\n").unwrap();
+        rules.reload_from(&path);
+        assert!(!rules.allows(&status));
+
+        std::fs::write(&path, "queue: [не словарь").unwrap();
+        rules.reload_from(&path);
+        assert!(!rules.allows(&status));
+
+        std::fs::write(&path, "queue:\n  TREK:\n    notify: [status_changed]\n").unwrap();
+        rules.reload_from(&path);
+        assert!(rules.allows(&status));
+    }
+
+    #[tokio::test]
+    async fn test_watch_applies_saved_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(&path, "queue:\n  TREK:\n    notify: [mention]\n").unwrap();
+        let status = notification(NotificationKind::StatusChanged, "TREK-1");
+
+        let rules = LiveRules::default();
+        rules.reload_from(&path);
+        rules.watch_path(path.clone());
+        assert!(!rules.allows(&status));
+
+        std::fs::write(&path, "queue:\n  TREK:\n    notify: [status_changed]\n").unwrap();
+        for _ in 0..50 {
+            if rules.allows(&status) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(rules.allows(&status));
+    }
+}
@@ -0,0 +1,332 @@
+//! Скрытие личных данных и секретов перед отправкой текста задач в LLM
+//!
+//! Заголовки, описания и комментарии задач попадают в промпты сводок, разбора,
+//! оценок, плана дня и агента. Перед этим из них убираются адреса почты, телефоны,
+//! внутренние хосты и секреты (токены, пароли, приватные ключи), а также слова из
+//! словаря и выражения из секции `redact` конфигурации:
+//!
+//! ```yaml
+//! redact:
+//!   hosts: [corp.example.com]
+//!   words: [Феникс]
+//!   patterns:
+//!     passport: '\d{4} \d{6}'
+//! ```
+//!
+//! Найденное заменяется меткой правила, например `[email]`. Сколько замен сделало
+//! каждое правило, записывается в журнал `$XDG_STATE_HOME/you/redaction.log` — без
+//! самих значений. `--no-redact` отключает скрытие для одной команды.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use tracker_lib::models::Issue;
+
+use crate::config::Config;
+use crate::paths;
+use crate::settings;
+
+/// Переменная окружения, отключающая скрытие; через неё `--no-redact` передаётся хукам и скриптам
+pub const NO_REDACT_VAR: &str = "YOU_NO_REDACT";
+
+/// Журнал замен в каталоге состояния
+const AUDIT_LOG: &str = "redaction.log";
+
+/// Суффиксы внутренних хостов, которые скрываются всегда
+const DEFAULT_HOSTS: &[&str] = &["yandex.net", "internal", "local"];
+
+const EMAIL: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b";
+
+/// Номера с кодом страны или российские с восьмёркой: +7 (912) 345-67-89, 8 912 345 67 89
+const PHONE: &str = r"(?:\+\d{1,3}|\b8)[\s-]?\(?\d{3}\)?[\s-]?\d{3}[\s-]?\d{2}[\s-]?\d{2}\b";
+
+/// OAuth- и IAM-токены Яндекса, ключи OpenRouter, GitHub и AWS, пары `password: ...`
+/// и приватные ключи PEM
+const SECRET: &str = concat!(
+    r"(?s)-----BEGIN [A-Z ]*PRIVATE KEY-----.*?-----END [A-Z ]*PRIVATE KEY-----",
+    r"|\by0_[A-Za-z0-9_-]{20,}",
+    r"|\bt1\.[A-Za-z0-9_.-]{20,}",
+    r"|\bsk-[A-Za-z0-9_-]{20,}",
+    r"|\bgh[pousr]_[A-Za-z0-9]{20,}",
+    r"|\bAKIA[0-9A-Z]{16}\b",
+    r"|(?i)\b(?:password|passwd|pwd|пароль|token|токен|secret|api[_-]?key)\b\s*[:=]\s*\S+",
+);
+
+/// Секция `redact` конфигурации
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    /// Скрывать ли данные; `false` — как `--no-redact` для всех команд
+    pub enabled: bool,
+
+    /// Дополнительные суффиксы внутренних хостов (например, corp.example.com)
+    pub hosts: Vec<String>,
+
+    /// Слова и фразы, которые скрываются без учёта регистра (кодовые имена, клиенты)
+    pub words: Vec<String>,
+
+    /// Свои регулярные выражения: имя правила — выражение
+    pub patterns: HashMap<String, String>,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hosts: Vec::new(),
+            words: Vec::new(),
+            patterns: HashMap::new(),
+        }
+    }
+}
+
+/// Правило: имя (оно же метка замены) и выражение
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    regex: Regex,
+}
+
+/// Текст после скрытия и число замен по правилам
+#[derive(Debug, Default, PartialEq)]
+pub struct Redaction {
+    pub text: String,
+    pub counts: Vec<(String, usize)>,
+}
+
+/// Набор правил скрытия
+#[derive(Debug)]
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+impl Redactor {
+    /// Встроенные правила и правила из конфигурации
+    ///
+    /// Секреты идут первыми, чтобы токен с `@` не превратился в адрес почты, а почта —
+    /// раньше хостов, чтобы домен адреса не скрывался отдельно.
+    pub fn new(config: &RedactConfig) -> Result<Self> {
+        let mut hosts: Vec<String> = DEFAULT_HOSTS
+            .iter()
+            .map(|host| host.to_string())
+            .chain(
+                config
+                    .hosts
+                    .iter()
+                    .map(|host| host.trim_matches('.').to_string()),
+            )
+            .filter(|host| !host.is_empty())
+            .collect();
+        // Длинные суффиксы раньше: иначе от db1.corp.example.com останется хвост
+        hosts.sort_by_key(|host| std::cmp::Reverse(host.len()));
+        let hosts: Vec<String> = hosts.iter().map(|host| regex::escape(host)).collect();
+        let mut rules = vec![
+            rule("secret", SECRET)?,
+            rule("email", EMAIL)?,
+            rule(
+                "host",
+                &format!(r"(?i)\b(?:[a-z0-9-]+\.)+(?:{})\b", hosts.join("|")),
+            )?,
+            rule("phone", PHONE)?,
+        ];
+
+        let mut patterns: Vec<(&String, &String)> = config.patterns.iter().collect();
+        patterns.sort();
+        for (name, pattern) in patterns {
+            rules.push(
+                rule(name, pattern)
+                    .with_context(|| format!("Неверное выражение redact.patterns.{name}"))?,
+            );
+        }
+
+        let words: Vec<String> = config
+            .words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(regex::escape)
+            .collect();
+        if !words.is_empty() {
+            rules.push(rule("word", &format!(r"(?i)\b(?:{})\b", words.join("|")))?);
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Скрыть данные в тексте
+    pub fn redact(&self, text: &str) -> Redaction {
+        let mut redaction = Redaction {
+            text: text.to_string(),
+            counts: Vec::new(),
+        };
+        for rule in &self.rules {
+            let count = rule.regex.find_iter(&redaction.text).count();
+            if count == 0 {
+                continue;
+            }
+            let label = format!("[{}]", rule.name);
+            redaction.text = rule
+                .regex
+                .replace_all(&redaction.text, label.as_str())
+                .into_owned();
+            redaction.counts.push((rule.name.clone(), count));
+        }
+        redaction
+    }
+}
+
+fn rule(name: &str, pattern: &str) -> Result<Rule> {
+    let regex = Regex::new(pattern)
+        .with_context(|| format!("Не удалось собрать правило скрытия {name}"))?;
+    Ok(Rule {
+        name: name.to_string(),
+        regex,
+    })
+}
+
+/// Правила для текущей команды; None — скрытие отключено
+///
+/// Загружаются один раз. Если своё выражение из конфигурации не собирается, остаются
+/// встроенные правила: отправить данные как есть хуже, чем скрыть лишнее.
+fn active() -> Option<&'static Redactor> {
+    static REDACTOR: OnceLock<Option<Redactor>> = OnceLock::new();
+    REDACTOR
+        .get_or_init(|| {
+            if settings::get().no_redact || std::env::var_os(NO_REDACT_VAR).is_some() {
+                debug!("Скрытие данных отключено флагом --no-redact");
+                return None;
+            }
+            let config = Config::load().map(|config| config.redact).unwrap_or_default();
+            if !config.enabled {
+                debug!("Скрытие данных отключено в конфигурации");
+                return None;
+            }
+            Redactor::new(&config)
+                .or_else(|err| {
+                    warn!(error = %format!("{err:#}"), "Используются только встроенные правила скрытия");
+                    Redactor::new(&RedactConfig::default())
+                })
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Текст для LLM со скрытыми данными; `context` — откуда текст (ключ задачи) для журнала
+pub fn text(context: &str, text: &str) -> String {
+    let Some(redactor) = active() else {
+        return text.to_string();
+    };
+    let redaction = redactor.redact(text);
+    if !redaction.counts.is_empty() {
+        if let Err(err) = audit(context, &redaction.counts) {
+            warn!(error = %format!("{err:#}"), "Не удалось записать журнал скрытия");
+        }
+    }
+    redaction.text
+}
+
+/// Копия задачи для LLM: заголовок, описание и раскрытые комментарии без скрытых данных
+pub fn issue(issue: &Issue) -> Issue {
+    let mut issue = issue.clone();
+    issue.summary = text(&issue.key, &issue.summary);
+    issue.description = issue
+        .description
+        .as_deref()
+        .map(|description| text(&issue.key, description));
+    if let Some(comments) = &mut issue.comments {
+        for comment in comments {
+            comment.text = text(&issue.key, &comment.text);
+        }
+    }
+    issue
+}
+
+/// Дописать в журнал, сколько замен сделало каждое правило
+fn audit(context: &str, counts: &[(String, usize)]) -> Result<()> {
+    let dir = paths::state_dir().context("Не удалось определить каталог состояния")?;
+    std::fs::create_dir_all(&dir)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(AUDIT_LOG))?;
+    let at = chrono::Local::now().to_rfc3339();
+    for (rule, count) in counts {
+        let entry = serde_json::json!({
+            "at": at,
+            "context": context,
+            "rule": rule,
+            "count": count,
+        });
+        writeln!(file, "{entry}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(config: &RedactConfig, text: &str) -> Redaction {
+        Redactor::new(config).unwrap().redact(text)
+    }
+
+    #[test]
+    fn test_builtin_rules() {
+        let text = "Пишите ivan.petrov@example.com или звоните +7 (912) 345-67-89.\n\
+                    Логи на build01.search.yandex.net, токен: y0_AgAAAAAbCdEfGhIjKlMnOpQrStUv";
+        let redaction = redact(&RedactConfig::default(), text);
+
+        assert_eq!(
+            redaction.text,
+            "Пишите [email] или звоните [phone].\nЛоги на [host], [secret]"
+        );
+        assert_eq!(
+            redaction.counts,
+            [
+                ("secret".to_string(), 1),
+                ("email".to_string(), 1),
+                ("host".to_string(), 1),
+                ("phone".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_untouched() {
+        let text =
+            "Экспорт в CSV падает на 1234567890 строках, см. TREK-12 и https://st.yandex-team.ru";
+        let redaction = redact(&RedactConfig::default(), text);
+        assert_eq!(redaction.text, text);
+        assert!(redaction.counts.is_empty());
+    }
+
+    #[test]
+    fn test_config_hosts_words_and_patterns() {
+        let config = RedactConfig {
+            hosts: vec!["corp".to_string(), ".corp.example.com".to_string()],
+            words: vec!["Феникс".to_string()],
+            patterns: HashMap::from([("passport".to_string(), r"\d{4} \d{6}".to_string())]),
+            ..Default::default()
+        };
+        let redaction = redact(
+            &config,
+            "Проект феникс: db1.corp.example.com, паспорт 4510 123456",
+        );
+        assert_eq!(redaction.text, "Проект [word]: [host], паспорт [passport]");
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_error() {
+        let config = RedactConfig {
+            patterns: HashMap::from([("broken".to_string(), "(".to_string())]),
+            ..Default::default()
+        };
+        let err = Redactor::new(&config).unwrap_err();
+        assert!(format!("{err:#}").contains("redact.patterns.broken"));
+    }
+}
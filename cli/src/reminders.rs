@@ -0,0 +1,298 @@
+//! Напоминания по задачам
+//!
+//! `you tracker remind TREK-123 --in 3d "ping QA"` сохраняет напоминание в
+//! `$XDG_STATE_HOME/you/reminders.json`. Когда срок наступает, напоминание попадает во
+//! входящие: `you serve` проверяет сроки раз в минуту, `you inbox` и TUI — при запуске
+//! и обновлении. Сработавшее напоминание можно отложить или отметить выполненным.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{ids::IssueKey, models::TRACKER_DATE_FORMAT};
+
+use crate::{
+    inbox::{Inbox, Notification, NotificationKind},
+    paths,
+    snooze::Snoozes,
+    timezone,
+};
+
+/// Префикс идентификатора уведомления, созданного напоминанием
+const NOTIFICATION_PREFIX: &str = "reminder:";
+
+/// Длительность события календаря для напоминания
+const CALENDAR_EVENT_MINUTES: i64 = 15;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: u64,
+    pub issue_key: IssueKey,
+    pub text: String,
+    /// Срок в формате API Трекера
+    pub due: String,
+    /// Уведомление о наступившем сроке уже во входящих
+    #[serde(default)]
+    pub notified: bool,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl Reminder {
+    pub fn due_time(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_str(&self.due, TRACKER_DATE_FORMAT).ok()
+    }
+
+    /// Строка для списка: номер, срок, задача и текст
+    pub fn line(&self) -> String {
+        let due = self
+            .due_time()
+            .map(|due| {
+                due.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| self.due.clone());
+        let marker = if self.done { "✓" } else { " " };
+        format!(
+            "{marker} #{} {due} {} {}",
+            self.id, self.issue_key, self.text
+        )
+    }
+
+    fn notification(&self) -> Notification {
+        Notification {
+            // Срок входит в идентификатор, чтобы отложенное напоминание сработало снова
+            id: format!("{NOTIFICATION_PREFIX}{}:{}", self.id, self.due),
+            kind: NotificationKind::Reminder,
+            issue_key: self.issue_key.to_string(),
+            issue_summary: String::new(),
+            text: self.text.clone(),
+            author: None,
+            at: self.due.clone(),
+            read: false,
+        }
+    }
+
+    /// Событие для календаря на время срока
+    pub fn calendar_event(&self) -> Option<calendar_lib::Event> {
+        let start = self.due_time()?.with_timezone(&Local).naive_local();
+        Some(calendar_lib::Event {
+            uid: Some(format!("you-reminder-{}-{}", self.id, self.issue_key)),
+            summary: format!("{}: {}", self.issue_key, self.text),
+            description: None,
+            location: None,
+            url: None,
+            attendees: Vec::new(),
+            start,
+            end: start + Duration::minutes(CALENDAR_EVENT_MINUTES),
+            all_day: false,
+        })
+    }
+}
+
+/// Номер напоминания, из которого создано уведомление
+pub fn reminder_id(notification: &Notification) -> Option<u64> {
+    let rest = notification.id.strip_prefix(NOTIFICATION_PREFIX)?;
+    rest.split(':').next()?.parse().ok()
+}
+
+/// Разбирает задержку вида `30m`, `2h`, `3d` или `1w`
+pub fn parse_delay(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("«{value}»: ожидается число и единица, например 3d"))?;
+    match unit {
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        _ => Err(format!("«{value}»: единица должна быть m, h, d или w")),
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reminders {
+    next_id: u64,
+    pub reminders: Vec<Reminder>,
+}
+
+impl Reminders {
+    /// Загружает напоминания; повреждённый или отсутствующий файл даёт пустой список
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = reminders_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Напоминания не найдены");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = %err, "Не удалось разобрать напоминания");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = reminders_path().context("Не удалось определить путь для напоминаний")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn add(
+        &mut self,
+        issue_key: IssueKey,
+        text: String,
+        due: DateTime<FixedOffset>,
+    ) -> &Reminder {
+        self.next_id += 1;
+        self.reminders.push(Reminder {
+            id: self.next_id,
+            issue_key,
+            text,
+            due: due.format(TRACKER_DATE_FORMAT).to_string(),
+            notified: false,
+            done: false,
+        });
+        &self.reminders[self.reminders.len() - 1]
+    }
+
+    /// Переносит срок напоминания; оно сработает снова
+    pub fn snooze(&mut self, id: u64, due: DateTime<FixedOffset>) -> Result<&Reminder> {
+        let reminder = self.find_mut(id)?;
+        reminder.due = due.format(TRACKER_DATE_FORMAT).to_string();
+        reminder.notified = false;
+        reminder.done = false;
+        Ok(reminder)
+    }
+
+    pub fn complete(&mut self, id: u64) -> Result<&Reminder> {
+        let reminder = self.find_mut(id)?;
+        reminder.done = true;
+        Ok(reminder)
+    }
+
+    /// Невыполненные напоминания по сроку
+    pub fn active(&self) -> Vec<&Reminder> {
+        let mut active: Vec<&Reminder> = self.reminders.iter().filter(|r| !r.done).collect();
+        active.sort_by_key(|r| r.due_time());
+        active
+    }
+
+    /// Уведомления о напоминаниях, срок которых наступил к `now`; они отмечаются отправленными
+    pub fn take_due(&mut self, now: DateTime<FixedOffset>) -> Vec<Notification> {
+        self.reminders
+            .iter_mut()
+            .filter(|r| !r.done && !r.notified && r.due_time().is_some_and(|due| due <= now))
+            .map(|r| {
+                r.notified = true;
+                r.notification()
+            })
+            .collect()
+    }
+
+    fn find_mut(&mut self, id: u64) -> Result<&mut Reminder> {
+        match self.reminders.iter_mut().find(|r| r.id == id) {
+            Some(reminder) => Ok(reminder),
+            None => bail!("Напоминание #{id} не найдено"),
+        }
+    }
+}
+
+fn reminders_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("reminders.json"))
+}
+
+/// Переносит наступившие напоминания и вернувшиеся отложенные задачи во входящие;
+/// возвращает уведомления о них
+///
+/// Входящие сохраняет вызывающий код.
+pub fn deliver_due(inbox: &mut Inbox) -> Result<Vec<Notification>> {
+    let mut reminders = Reminders::load();
+    let mut due = reminders.take_due(Utc::now().fixed_offset());
+    if !due.is_empty() {
+        reminders.save()?;
+    }
+    let mut snoozes = Snoozes::load();
+    let returned = snoozes.take_expired(timezone::current().today());
+    if !returned.is_empty() {
+        snoozes.save()?;
+        due.extend(returned);
+    }
+    if due.is_empty() {
+        return Ok(due);
+    }
+    inbox.merge(due.clone());
+    info!(count = due.len(), "Напоминания перенесены во входящие");
+    Ok(due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_str(value, TRACKER_DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_delay() {
+        assert_eq!(parse_delay("3d"), Ok(Duration::days(3)));
+        assert_eq!(parse_delay("90m"), Ok(Duration::minutes(90)));
+        assert_eq!(parse_delay(" 1w "), Ok(Duration::weeks(1)));
+        assert!(parse_delay("3").is_err());
+        assert!(parse_delay("d").is_err());
+        assert!(parse_delay("2y").is_err());
+    }
+
+    #[test]
+    fn test_take_due_notifies_once_until_snoozed() {
+        let mut reminders = Reminders::default();
+        let key: IssueKey = "TREK-1".parse().unwrap();
+        reminders.add(
+            key.clone(),
+            "ping QA".to_string(),
+            at("2024-05-01T10:00:00.000+0000"),
+        );
+        reminders.add(key, "позже".to_string(), at("2024-05-03T10:00:00.000+0000"));
+
+        let now = at("2024-05-02T10:00:00.000+0000");
+        let due = reminders.take_due(now);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].kind, NotificationKind::Reminder);
+        assert_eq!(due[0].text, "ping QA");
+        assert_eq!(reminder_id(&due[0]), Some(1));
+        assert!(reminders.take_due(now).is_empty());
+
+        reminders
+            .snooze(1, at("2024-05-02T09:00:00.000+0000"))
+            .unwrap();
+        let again = reminders.take_due(now);
+        assert_eq!(again.len(), 1);
+        assert_ne!(again[0].id, due[0].id);
+
+        reminders.complete(2).unwrap();
+        assert!(reminders
+            .take_due(at("2024-05-04T10:00:00.000+0000"))
+            .is_empty());
+        assert_eq!(reminders.active().len(), 1);
+        assert!(reminders.complete(7).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Цветной вывод задач в терминал
+//!
+//! Статус выводится цветной плашкой, служебные поля (автор, даты, очередь) — приглушённо,
+//! ключ задачи — ссылкой OSC 8 на веб-интерфейс. Цвета включаются, только если вывод
+//! идёт в терминал: при перенаправлении в файл или в другую команду текст остаётся
+//! простым. `NO_COLOR` отключает цвета всегда, `CLICOLOR_FORCE` включает их и без терминала.
+
+use std::io::IsTerminal;
+
+use tracker_lib::{
+    models::Issue,
+    template::{issue_url, IssueTemplate},
+};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+
+/// Группа статуса для выбора цвета
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    Done,
+    InProgress,
+    Open,
+    Other,
+}
+
+impl StatusKind {
+    /// Группа по названию статуса на русском или английском
+    pub fn of(status: &str) -> Self {
+        let status = status.to_lowercase();
+        let matches_any = |words: &[&str]| words.iter().any(|word| status.contains(word));
+
+        if matches_any(&["закрыт", "решен", "готово", "closed", "resolved", "done"])
+        {
+            StatusKind::Done
+        } else if matches_any(&["работе", "ревью", "progress", "review", "testing"]) {
+            StatusKind::InProgress
+        } else if matches_any(&["открыт", "нужно", "open", "backlog", "todo"]) {
+            StatusKind::Open
+        } else {
+            StatusKind::Other
+        }
+    }
+
+    /// Цвет плашки: чёрный текст на фоне группы
+    fn badge(self) -> &'static str {
+        match self {
+            StatusKind::Done => "\x1b[30;42m",
+            StatusKind::InProgress => "\x1b[30;43m",
+            StatusKind::Open => "\x1b[30;44m",
+            StatusKind::Other => "\x1b[30;47m",
+        }
+    }
+}
+
+/// Вывод задач с цветами или без них
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    /// Цвета для стандартного вывода: только в терминале и с учётом `NO_COLOR`
+    pub fn stdout() -> Self {
+        let color = color_enabled(std::io::stdout().is_terminal(), |name| {
+            std::env::var(name).ok()
+        });
+        Self { color }
+    }
+
+    #[cfg(test)]
+    fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Задача по шаблону с раскрашенными значениями
+    pub fn issue(&self, template: &IssueTemplate, issue: &Issue) -> String {
+        if !self.color {
+            return template.render(issue);
+        }
+        template.render_with(issue, |name, value| match name {
+            "key" => self.key(&value),
+            "status" => self.status(&value),
+            "link" => hyperlink(&value, &value),
+            "type" | "priority" | "queue" | "author" | "created" | "updated" | "tags" => {
+                format!("{DIM}{value}{RESET}")
+            }
+            _ => value,
+        })
+    }
+
+    /// Ключ задачи: жирный и со ссылкой на задачу
+    pub fn key(&self, key: &str) -> String {
+        if !self.color {
+            return key.to_string();
+        }
+        hyperlink(&issue_url(key), &format!("{BOLD}{key}{RESET}"))
+    }
+
+    /// Статус цветной плашкой
+    pub fn status(&self, status: &str) -> String {
+        if !self.color {
+            return status.to_string();
+        }
+        format!("{} {status} {RESET}", StatusKind::of(status).badge())
+    }
+}
+
+/// Включены ли цвета: `NO_COLOR` важнее `CLICOLOR_FORCE`, без них решает терминал
+fn color_enabled(is_terminal: bool, env: impl Fn(&str) -> Option<String>) -> bool {
+    let set = |name: &str| env(name).is_some_and(|value| !value.is_empty() && value != "0");
+    if set("NO_COLOR") {
+        return false;
+    }
+    if set("CLICOLOR_FORCE") {
+        return true;
+    }
+    is_terminal && env("TERM").as_deref() != Some("dumb")
+}
+
+/// Ссылка OSC 8: терминалы без поддержки показывают только текст
+fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn env(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_color_enabled_follows_terminal_and_env() {
+        assert!(color_enabled(true, env(&[])));
+        assert!(!color_enabled(false, env(&[])));
+        assert!(!color_enabled(true, env(&[("NO_COLOR", "1")])));
+        assert!(!color_enabled(true, env(&[("TERM", "dumb")])));
+        assert!(color_enabled(false, env(&[("CLICOLOR_FORCE", "1")])));
+        assert!(!color_enabled(
+            false,
+            env(&[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")])
+        ));
+    }
+
+    #[test]
+    fn test_issue_colors_only_when_enabled() {
+        let issue = testing::issue("TREK-1")
+            .summary("Экспорт")
+            .status("inProgress", "В работе")
+            .build();
+        let template = IssueTemplate::builtin("short").unwrap();
+
+        assert_eq!(
+            Renderer::new(false).issue(&template, &issue),
+            "TREK-1  [В работе]  Экспорт"
+        );
+        let colored = Renderer::new(true).issue(&template, &issue);
+        assert!(colored.contains("\x1b]8;;https://st.yandex-team.ru/TREK-1\x1b\\"));
+        assert!(colored.contains("\x1b[30;43m В работе \x1b[0m"));
+        assert!(colored.ends_with("]  Экспорт"));
+    }
+}
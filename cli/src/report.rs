@@ -0,0 +1,485 @@
+//! Отчёты по задачам трекера
+//!
+//! `you report release` собирает задачи версии (поле «Исправить в версии» или тег),
+//! группирует их по типу и выводит заметки о выпуске в Markdown. С `--polish` черновик
+//! переписывает LLM по шаблону из `--template`, конфигурации или встроенному.
+//!
+//! `you report velocity` считает по последним завершённым спринтам доски скорость
+//! команды и перцентили времени цикла и выполнения; CSV и JSON удобно забирать в таблицы.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Subcommand, ValueEnum};
+use llm_lib::{LlmClient, LlmClientTrait, LlmConfig};
+use tracing::{debug, info, instrument};
+use tracker_lib::{
+    ids::QueueKey,
+    metrics::{Percentiles, VelocityReport},
+    models::Issue,
+    search::{SearchParams, SearchRequest},
+    TrackerClient,
+};
+
+use crate::config::Config;
+use crate::llm::DEFAULT_MODEL;
+use crate::output;
+use crate::redact;
+use crate::settings;
+
+const RELEASE_PAGE_SIZE: u32 = 100;
+
+/// Сколько последних спринтов учитывать в отчёте о скорости
+pub const DEFAULT_SPRINTS: usize = 6;
+
+/// Формат отчёта о скорости
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum VelocityFormat {
+    /// Таблица для человека
+    #[default]
+    Text,
+    /// JSON по схеме `you schema report-velocity`
+    Json,
+    /// CSV: строка на спринт
+    Csv,
+}
+
+/// Разделы заметок: ключ типа задачи и заголовок, в порядке вывода
+const SECTIONS: &[(&str, &str)] = &[
+    ("newFeature", "Новые возможности"),
+    ("improvement", "Улучшения"),
+    ("bug", "Исправления"),
+    ("task", "Задачи"),
+];
+
+const OTHER_SECTION: &str = "Прочее";
+
+const SYSTEM_PROMPT: &str = "Ты технический писатель и готовишь заметки о выпуске. \
+Отвечай только Markdown без пояснений.";
+
+/// Шаблон запроса к LLM; `{version}`, `{queue}` и `{notes}` заменяются значениями
+pub const DEFAULT_RELEASE_TEMPLATE: &str = "Перепиши черновик заметок о выпуске {version} \
+очереди {queue} для пользователей: сохрани разделы и ключи задач, сформулируй пункты \
+понятным языком и добавь в начало короткое описание главного в выпуске.\n\n{notes}";
+
+/// Команды отчётов
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Заметки о выпуске по задачам версии
+    Release {
+        /// Версия: значение поля «Исправить в версии» или тег (например, v1.4)
+        #[arg(long)]
+        version: String,
+
+        /// Ключ очереди (например, TREK)
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// Переписать черновик с помощью LLM
+        #[arg(long)]
+        polish: bool,
+
+        /// Модель LLM для --polish
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Файл с шаблоном запроса к LLM для --polish
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+    /// Скорость команды, время цикла и время выполнения по спринтам доски
+    Velocity {
+        /// Идентификатор доски
+        #[arg(long)]
+        board: u64,
+
+        /// Сколько последних завершённых спринтов учитывать
+        #[arg(short, long, default_value_t = DEFAULT_SPRINTS)]
+        sprints: usize,
+
+        /// Формат вывода
+        #[arg(short, long, value_enum, default_value_t)]
+        output: VelocityFormat,
+    },
+}
+
+impl ReportCommands {
+    /// Выполняет команду отчёта
+    pub async fn execute(&self) -> Result<()> {
+        match self {
+            ReportCommands::Release {
+                version,
+                queue,
+                polish,
+                model,
+                template,
+            } => {
+                let model = model.as_deref().unwrap_or(DEFAULT_MODEL);
+                let polish = polish.then_some((model, template.as_ref()));
+                execute_release(version, queue, polish).await
+            }
+            ReportCommands::Velocity {
+                board,
+                sprints,
+                output,
+            } => execute_velocity(*board, *sprints, *output).await,
+        }
+    }
+}
+
+#[instrument]
+async fn execute_release(
+    version: &str,
+    queue: &QueueKey,
+    polish: Option<(&str, Option<&PathBuf>)>,
+) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let issues = fetch_release_issues(&client, queue, version).await?;
+    if issues.is_empty() {
+        println!("В очереди {queue} нет задач версии {version}");
+        return Ok(());
+    }
+
+    let mut notes = release_notes(version, &issues);
+    if let Some((model, template_path)) = polish {
+        let template = match template_path {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Не удалось прочитать шаблон {}", path.display()))?,
+            None => Config::load()?
+                .report
+                .release_template
+                .unwrap_or_else(|| DEFAULT_RELEASE_TEMPLATE.to_string()),
+        };
+        let llm = LlmClient::new(LlmConfig::new(model)?)?;
+        let draft = redact::text(&format!("release {version}"), &notes);
+        notes = polish_notes(&llm, &template, version, queue, &draft).await?;
+    }
+    println!("{notes}");
+    Ok(())
+}
+
+/// Задачи очереди с версией в поле «Исправить в версии» или в тегах
+pub fn release_query(queue: &QueueKey, version: &str) -> String {
+    format!(
+        "Queue: {queue} (\"Fix Version\": \"{version}\" OR Tags: \"{version}\") \"Sort by\": Key ASC"
+    )
+}
+
+#[instrument(skip(client))]
+pub async fn fetch_release_issues(
+    client: &TrackerClient,
+    queue: &QueueKey,
+    version: &str,
+) -> Result<Vec<Issue>> {
+    let request = SearchRequest {
+        query: Some(release_query(queue, version)),
+        ..Default::default()
+    };
+
+    let mut issues = Vec::new();
+    for page in 1.. {
+        let params = SearchParams {
+            per_page: Some(RELEASE_PAGE_SIZE),
+            page: Some(page),
+            ..Default::default()
+        };
+        let batch = client.search_issues(&request, Some(params)).await?;
+        let last = batch.len() < RELEASE_PAGE_SIZE as usize;
+        issues.extend(batch);
+        if last {
+            break;
+        }
+    }
+    info!(count = issues.len(), "Задачи версии получены");
+    Ok(issues)
+}
+
+/// Черновик заметок о выпуске: разделы по типам задач в порядке `SECTIONS`
+pub fn release_notes(version: &str, issues: &[Issue]) -> String {
+    let mut sections: Vec<(String, Vec<&Issue>)> = SECTIONS
+        .iter()
+        .map(|(_, title)| (title.to_string(), Vec::new()))
+        .collect();
+
+    for issue in issues {
+        let issue_type = issue.issue_type.as_ref();
+        let known = issue_type
+            .and_then(|t| t.key.as_deref())
+            .and_then(|key| SECTIONS.iter().position(|(section, _)| *section == key));
+        let index = match known {
+            Some(index) => index,
+            None => {
+                let title = issue_type
+                    .and_then(|t| t.display.clone())
+                    .unwrap_or_else(|| OTHER_SECTION.to_string());
+                match sections.iter().position(|(existing, _)| *existing == title) {
+                    Some(index) => index,
+                    None => {
+                        sections.push((title, Vec::new()));
+                        sections.len() - 1
+                    }
+                }
+            }
+        };
+        sections[index].1.push(issue);
+    }
+
+    let mut lines = vec![format!("# Выпуск {version}")];
+    for (title, issues) in sections.iter().filter(|(_, issues)| !issues.is_empty()) {
+        lines.push(String::new());
+        lines.push(format!("## {title}"));
+        lines.push(String::new());
+        lines.extend(
+            issues
+                .iter()
+                .map(|issue| format!("- {} {}", issue.key, issue.summary)),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Подставляет значения в шаблон запроса
+pub fn render_template(template: &str, version: &str, queue: &str, notes: &str) -> String {
+    template
+        .replace("{version}", version)
+        .replace("{queue}", queue)
+        .replace("{notes}", notes)
+}
+
+#[instrument(skip(llm, template, notes))]
+pub async fn polish_notes<T: LlmClientTrait>(
+    llm: &T,
+    template: &str,
+    version: &str,
+    queue: &str,
+    notes: &str,
+) -> Result<String> {
+    let prompt = render_template(template, version, queue, notes);
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(length = response.len(), "Заметки переписаны LLM");
+    Ok(response.trim().to_string())
+}
+
+#[instrument]
+async fn execute_velocity(board: u64, sprints: usize, format: VelocityFormat) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let report = client.velocity_report(board, sprints).await?;
+    match format {
+        VelocityFormat::Json => output::print_json(&report),
+        VelocityFormat::Csv => {
+            print!("{}", velocity_csv(&report)?);
+            Ok(())
+        }
+        VelocityFormat::Text if report.sprints.is_empty() => {
+            println!("На доске {board} нет завершённых спринтов с датами");
+            Ok(())
+        }
+        VelocityFormat::Text => {
+            println!("{}", velocity_text(&report));
+            Ok(())
+        }
+    }
+}
+
+fn format_percentiles(stats: Option<&Percentiles>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "p50 {:.1} д, p85 {:.1} д, p95 {:.1} д (задач: {})",
+            stats.p50, stats.p85, stats.p95, stats.count
+        ),
+        None => "нет данных".to_string(),
+    }
+}
+
+/// Таблица спринтов и итоги отчёта о скорости
+fn velocity_text(report: &VelocityReport) -> String {
+    let mut lines = Vec::new();
+    for sprint in &report.sprints {
+        lines.push(format!(
+            "{} ({} — {}): решено {} из {}, story points {} из {}",
+            sprint.name,
+            sprint.start.format("%d.%m"),
+            sprint.end.format("%d.%m"),
+            sprint.completed,
+            sprint.committed,
+            sprint.completed_points,
+            sprint.committed_points
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Скорость: {:.1} задач и {:.1} story points за спринт",
+        report.average_completed, report.average_points
+    ));
+    lines.push(format!(
+        "Время цикла: {}",
+        format_percentiles(report.cycle_time.as_ref())
+    ));
+    lines.push(format!(
+        "Время выполнения: {}",
+        format_percentiles(report.lead_time.as_ref())
+    ));
+    lines.join("\n")
+}
+
+/// Отчёт о скорости в CSV: строка на спринт, длительности в днях
+fn velocity_csv(report: &VelocityReport) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "sprint_id",
+        "name",
+        "start",
+        "end",
+        "committed",
+        "completed",
+        "committed_points",
+        "completed_points",
+        "cycle_p50",
+        "cycle_p85",
+        "cycle_p95",
+        "lead_p50",
+        "lead_p85",
+        "lead_p95",
+    ])?;
+    let days = |stats: Option<&Percentiles>| -> [String; 3] {
+        match stats {
+            Some(stats) => [stats.p50, stats.p85, stats.p95].map(|value| format!("{value:.2}")),
+            None => Default::default(),
+        }
+    };
+    for sprint in &report.sprints {
+        let mut record = vec![
+            sprint
+                .sprint_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            sprint.name.clone(),
+            sprint.start.to_string(),
+            sprint.end.to_string(),
+            sprint.committed.to_string(),
+            sprint.completed.to_string(),
+            sprint.committed_points.to_string(),
+            sprint.completed_points.to_string(),
+        ];
+        record.extend(days(sprint.cycle_time.as_ref()));
+        record.extend(days(sprint.lead_time.as_ref()));
+        writer.write_record(&record)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .context("Не удалось записать отчёт в CSV")?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use llm_lib::MockLlmClientTrait;
+    use serde_json::json;
+    use tracker_lib::metrics::IssueTimeline;
+
+    use super::*;
+    use crate::testing::issue;
+
+    #[test]
+    fn test_release_query_matches_fix_version_or_tag() {
+        assert_eq!(
+            release_query(&"TREK".parse().unwrap(), "v1.4"),
+            "Queue: TREK (\"Fix Version\": \"v1.4\" OR Tags: \"v1.4\") \"Sort by\": Key ASC"
+        );
+    }
+
+    #[test]
+    fn test_release_notes_group_by_type() {
+        let issues = [
+            issue("TREK-1").issue_type("bug", "Ошибка").build(),
+            issue("TREK-2").issue_type("epic", "Эпик").build(),
+            issue("TREK-3")
+                .issue_type("newFeature", "Новая возможность")
+                .build(),
+            issue("TREK-4").issue_type("bug", "Ошибка").build(),
+        ];
+
+        assert_eq!(
+            release_notes("v1.4", &issues),
+            "# Выпуск v1.4\n\n\
+             ## Новые возможности\n\n- TREK-3 Задача TREK-3\n\n\
+             ## Исправления\n\n- TREK-1 Задача TREK-1\n- TREK-4 Задача TREK-4\n\n\
+             ## Эпик\n\n- TREK-2 Задача TREK-2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_polish_notes_renders_template() {
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_complete_with_system()
+            .times(1)
+            .returning(|_, prompt| {
+                assert_eq!(prompt, "TREK v1.4:\n# Выпуск v1.4");
+                Box::pin(async { Ok("  # v1.4 — быстрее экспорт\n".to_string()) })
+            });
+
+        let notes = polish_notes(
+            &mock_client,
+            "{queue} {version}:\n{notes}",
+            "v1.4",
+            "TREK",
+            "# Выпуск v1.4",
+        )
+        .await
+        .unwrap();
+        assert_eq!(notes, "# v1.4 — быстрее экспорт");
+    }
+
+    fn velocity_report() -> VelocityReport {
+        let sprint = serde_json::from_value(json!({
+            "id": 42,
+            "name": "Спринт 42",
+            "startDate": "2026-10-01",
+            "endDate": "2026-10-14"
+        }))
+        .unwrap();
+        let at = |day: u32| {
+            chrono::DateTime::parse_from_rfc3339(&format!("2026-10-{day:02}T09:00:00+00:00")).ok()
+        };
+        let issues = vec![
+            IssueTimeline {
+                key: "TREK-1".to_string(),
+                story_points: Some(3.0),
+                created: at(1),
+                started: at(2),
+                resolved: at(4),
+            },
+            IssueTimeline {
+                key: "TREK-2".to_string(),
+                story_points: Some(5.0),
+                created: at(1),
+                started: None,
+                resolved: None,
+            },
+        ];
+        VelocityReport::new(&[(sprint, issues)])
+    }
+
+    #[test]
+    fn test_velocity_text_and_csv() {
+        let report = velocity_report();
+
+        assert_eq!(
+            velocity_text(&report),
+            "Спринт 42 (01.10 — 14.10): решено 1 из 2, story points 3 из 8\n\n\
+             Скорость: 1.0 задач и 3.0 story points за спринт\n\
+             Время цикла: p50 2.0 д, p85 2.0 д, p95 2.0 д (задач: 1)\n\
+             Время выполнения: p50 3.0 д, p85 3.0 д, p95 3.0 д (задач: 1)"
+        );
+        let csv = velocity_csv(&report).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert!(rows[0].starts_with("sprint_id,name,start,end,committed"));
+        assert_eq!(
+            rows[1],
+            "42,Спринт 42,2026-10-01,2026-10-14,2,1,8,3,2.00,2.00,2.00,3.00,3.00,3.00"
+        );
+    }
+}
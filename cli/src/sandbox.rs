@@ -0,0 +1,169 @@
+//! Песочница Трекера для проверки рискованной автоматизации
+//!
+//! `--sandbox` переключает все команды на тестовую организацию и очередь из секции
+//! `sandbox` конфигурации: агент, массовые изменения и `you serve` можно сначала
+//! прогнать на тестовой очереди. Задачи других очередей клиент Трекера не создаёт и не
+//! изменяет, а каждая команда и строка состояния TUI предупреждают о песочнице.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::info;
+use tracker_lib::{
+    ids::QueueKey, OrgKind, TrackerConfig, ORG_ID_VAR, ORG_KIND_VAR, SANDBOX_QUEUE_VAR,
+};
+
+use crate::settings;
+
+/// Переменная окружения с токеном Трекера, которую подменяет песочница
+const TOKEN_VAR: &str = "TRACKER_TOKEN";
+
+/// Секция `sandbox` конфигурации
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Очередь, в которой разрешены изменения задач
+    pub queue: Option<QueueKey>,
+    /// Идентификатор тестовой организации; без него остаётся основная
+    pub org_id: Option<String>,
+    /// Тип тестовой организации: `360` или `cloud`
+    pub org_kind: Option<String>,
+    /// Имя переменной или записи хранилища ключей с токеном тестовой организации
+    pub token: Option<String>,
+}
+
+impl SandboxConfig {
+    /// Включает песочницу: проверяет настройки и находит токен тестовой организации
+    ///
+    /// `resolve` ищет токен по имени, как `core_lib::credentials::resolve`.
+    pub fn activate(&self, resolve: impl Fn(&str) -> Option<String>) -> Result<Sandbox> {
+        let queue = self
+            .queue
+            .clone()
+            .context("Песочница не настроена: укажите sandbox.queue в конфигурации")?;
+        let token = match &self.token {
+            Some(name) => Some(resolve(name).with_context(|| {
+                format!("Токен песочницы {name} не найден ни в окружении, ни в хранилище ключей")
+            })?),
+            None => None,
+        };
+        let org_kind = self.org_kind.as_deref().map(str::parse).transpose()?;
+        info!(queue = %queue, org_id = ?self.org_id, "Включена песочница Трекера");
+        Ok(Sandbox {
+            queue,
+            token,
+            org_id: self.org_id.clone(),
+            org_kind,
+        })
+    }
+}
+
+/// Включённая песочница: куда переключаются клиенты Трекера этого процесса
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+    /// Очередь, в которой разрешены изменения задач
+    pub queue: QueueKey,
+    /// Токен тестовой организации; без него остаётся основной
+    pub token: Option<String>,
+    /// Тестовая организация; без неё остаётся основная
+    pub org_id: Option<String>,
+    /// Тип тестовой организации; без него определяется по виду `org_id`
+    pub org_kind: Option<OrgKind>,
+}
+
+impl Sandbox {
+    /// Переключает конфигурацию клиента Трекера на песочницу; токен подставляет
+    /// создание клиента
+    pub fn apply(&self, config: TrackerConfig) -> TrackerConfig {
+        let mut config = config.with_sandbox_queue(self.queue.clone());
+        if let Some(org_id) = &self.org_id {
+            config.org_id = Some(org_id.clone());
+            // Тип основной организации к тестовой не относится
+            config.org_kind = self.org_kind;
+        }
+        config
+    }
+
+    /// Переменные окружения, с которыми дочерний `you` тоже работает в песочнице
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![(SANDBOX_QUEUE_VAR, self.queue.to_string())];
+        if let Some(token) = &self.token {
+            vars.push((TOKEN_VAR, token.clone()));
+        }
+        if let Some(org_id) = &self.org_id {
+            vars.push((ORG_ID_VAR, org_id.clone()));
+            let kind = match self.org_kind {
+                Some(OrgKind::Yandex360) => "360",
+                Some(OrgKind::Cloud) => "cloud",
+                None => "",
+            };
+            vars.push((ORG_KIND_VAR, kind.to_string()));
+        }
+        vars
+    }
+}
+
+/// Очередь-песочница, если песочница включена флагом или переменной окружения
+pub fn active_queue() -> Option<QueueKey> {
+    match &settings::get().sandbox {
+        Some(sandbox) => Some(sandbox.queue.clone()),
+        None => tracker_lib::sandbox_queue_from_env().ok().flatten(),
+    }
+}
+
+/// Предупреждение, которое команды выводят перед работой в песочнице
+pub fn banner(sandbox: &Sandbox) -> String {
+    let org = sandbox
+        .org_id
+        .clone()
+        .or_else(|| std::env::var(ORG_ID_VAR).ok())
+        .unwrap_or_else(|| "основная".to_string());
+    format!(
+        "⚠ ПЕСОЧНИЦА: организация {org}, изменения только в очереди {}",
+        sandbox.queue
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_switches_org_token_and_queue() {
+        let config = SandboxConfig {
+            queue: Some("SANDBOX".parse().unwrap()),
+            org_id: Some("7654321".to_string()),
+            org_kind: None,
+            token: Some("TRACKER_SANDBOX_TOKEN".to_string()),
+        };
+        let sandbox = config
+            .activate(|name| (name == "TRACKER_SANDBOX_TOKEN").then(|| "sandbox-token".to_string()))
+            .unwrap();
+        assert_eq!(sandbox.token.as_deref(), Some("sandbox-token"));
+        assert_eq!(
+            sandbox.env(),
+            vec![
+                (SANDBOX_QUEUE_VAR, "SANDBOX".to_string()),
+                (TOKEN_VAR, "sandbox-token".to_string()),
+                (ORG_ID_VAR, "7654321".to_string()),
+                (ORG_KIND_VAR, String::new()),
+            ]
+        );
+
+        let main_org = TrackerConfig::new("main-token")
+            .with_org_id("bpf3crucp1v2pgmu4vc2")
+            .with_org_kind(OrgKind::Cloud);
+        let switched = sandbox.apply(main_org);
+        assert_eq!(switched.org_id.as_deref(), Some("7654321"));
+        assert_eq!(switched.org_kind, None);
+        assert_eq!(
+            switched.sandbox_queue.map(|queue| queue.to_string()),
+            Some("SANDBOX".to_string())
+        );
+
+        let err = config.activate(|_| None).unwrap_err().to_string();
+        assert!(err.contains("TRACKER_SANDBOX_TOKEN"));
+
+        let err = SandboxConfig::default().activate(|_| None).unwrap_err();
+        assert!(err.to_string().contains("sandbox.queue"));
+    }
+}
@@ -0,0 +1,408 @@
+//! Повторяющиеся задачи по расписанию
+//!
+//! `you serve` раз в минуту проверяет правила из `serve.schedule` конфигурации
+//! и создаёт задачи, время которых наступило. Расписание задаётся в формате cron
+//! (минута, час, день месяца, месяц, день недели) в местном времени.
+//!
+//! Повторы исключаются полем `unique`: для каждого срабатывания правила оно своё,
+//! и Трекер отклоняет повторное создание кодом 409. Время последней проверки
+//! сохраняется, поэтому после перезапуска пропущенное срабатывание (не старше
+//! недели) создаётся один раз.
+
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    ids::{QueueKey, UserLogin},
+    models::Issue,
+    task::CreateIssueRequest,
+    TrackerClient, TrackerError,
+};
+
+use crate::paths;
+
+/// Префикс поля `unique` задач, созданных по расписанию
+const UNIQUE_PREFIX: &str = "you-schedule:";
+
+/// Формат времени срабатывания в `unique` и в файле состояния
+const OCCURRENCE_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Насколько далеко в прошлое искать пропущенные срабатывания после перезапуска
+const MAX_CATCH_UP_DAYS: i64 = 7;
+
+/// Насколько далеко вперёд искать ближайшее срабатывание
+const LOOKAHEAD_DAYS: i64 = 366;
+
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Расписание в формате cron: `*`, списки, диапазоны, шаги и имена дней и месяцев,
+/// например `0 10 * * fri` — каждую пятницу в 10:00
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// День месяца и день недели, если оба ограничены, объединяются через «или», как в cron
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            bail!("Расписание «{value}» должно состоять из пяти полей: минута час день месяц день_недели");
+        };
+        let mut weekdays_mask = parse_field(weekdays, 0, 7, &WEEKDAYS, 0)?;
+        // 7 — тоже воскресенье
+        if weekdays_mask & (1 << 7) != 0 {
+            weekdays_mask = (weekdays_mask | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minutes, 0, 59, &[], 0)?,
+            hours: parse_field(hours, 0, 23, &[], 0)?,
+            days: parse_field(days, 1, 31, &[], 1)?,
+            months: parse_field(months, 1, 12, &MONTHS, 1)?,
+            weekdays: weekdays_mask,
+            days_restricted: days != "*",
+            weekdays_restricted: weekdays != "*",
+        })
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+/// Разбирает поле cron в битовую маску допустимых значений
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], first_name: u32) -> Result<u64> {
+    let value = |text: &str| -> Result<u32> {
+        let lower = text.to_lowercase();
+        let number = match names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + first_name,
+            None => text
+                .parse()
+                .with_context(|| format!("Некорректное значение «{text}» в расписании"))?,
+        };
+        if !(min..=max).contains(&number) {
+            bail!("Значение {number} в расписании вне диапазона {min}–{max}");
+        }
+        Ok(number)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .with_context(|| format!("Некорректный шаг «{step}» в расписании"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // «5/15» означает «с 5 до конца с шагом 15»
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                }
+            },
+        };
+        if start > end {
+            bail!("Пустой диапазон «{range}» в расписании");
+        }
+        for number in (start..=end).step_by(step as usize) {
+            mask |= 1 << number;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronSchedule {
+    /// Совпадает ли минута с расписанием (секунды не учитываются)
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        let bit = |mask: u64, value: u32| mask & (1 << value) != 0;
+        let day = bit(self.days, at.day());
+        let weekday = bit(self.weekdays, at.weekday().num_days_from_sunday());
+        let day_matches = match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        };
+        bit(self.minutes, at.minute())
+            && bit(self.hours, at.hour())
+            && bit(self.months, at.month())
+            && day_matches
+    }
+
+    /// Ближайшее срабатывание строго после `after`
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = truncate_to_minute(after) + Duration::minutes(1);
+        let end = start + Duration::days(LOOKAHEAD_DAYS);
+        let mut at = start;
+        while at < end {
+            if self.matches(at) {
+                return Some(at);
+            }
+            at += Duration::minutes(1);
+        }
+        None
+    }
+
+    /// Последнее срабатывание в промежутке (`after`, `until`]
+    pub fn latest_between(
+        &self,
+        after: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        let after = truncate_to_minute(after);
+        let mut at = truncate_to_minute(until);
+        while at > after {
+            if self.matches(at) {
+                return Some(at);
+            }
+            at -= Duration::minutes(1);
+        }
+        None
+    }
+}
+
+fn truncate_to_minute(at: NaiveDateTime) -> NaiveDateTime {
+    at.with_second(0)
+        .and_then(|at| at.with_nanosecond(0))
+        .unwrap_or(at)
+}
+
+/// Правило повторяющейся задачи из конфигурации
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecurringTask {
+    /// Имя правила; входит в `unique`, поэтому его не стоит менять
+    pub name: String,
+
+    /// Расписание в формате cron, например `0 10 * * fri`
+    pub schedule: CronSchedule,
+
+    /// Ключ очереди
+    pub queue: QueueKey,
+
+    /// Название задачи; `{date}` заменяется датой срабатывания
+    pub summary: String,
+
+    /// Описание задачи; `{date}` заменяется датой срабатывания
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Ключ типа задачи
+    #[serde(default, rename = "type")]
+    pub issue_type: Option<String>,
+
+    /// Ключ приоритета
+    #[serde(default)]
+    pub priority: Option<String>,
+
+    /// Логин исполнителя
+    #[serde(default)]
+    pub assignee: Option<UserLogin>,
+
+    /// Теги задачи
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl RecurringTask {
+    /// Запрос на создание задачи для срабатывания в момент `at`
+    pub fn request(&self, at: NaiveDateTime) -> CreateIssueRequest {
+        let date = at.format("%Y-%m-%d").to_string();
+        let expand = |text: &str| text.replace("{date}", &date);
+        let mut request = CreateIssueRequest::new(self.queue.clone(), expand(&self.summary));
+        request.description = self.description.as_deref().map(expand);
+        request.issue_type = self.issue_type.clone();
+        request.priority = self.priority.clone();
+        request.assignee = self.assignee.clone();
+        request.tags = self.tags.clone();
+        request.unique = Some(format!(
+            "{UNIQUE_PREFIX}{}:{}",
+            self.name,
+            at.format(OCCURRENCE_FORMAT)
+        ));
+        request
+    }
+}
+
+/// Создаёт задачу срабатывания; None, если она уже была создана раньше
+#[instrument(skip(client, task), fields(task = %task.name))]
+pub async fn create(
+    client: &TrackerClient,
+    task: &RecurringTask,
+    at: NaiveDateTime,
+) -> Result<Option<Issue>> {
+    match client.create_issue(&task.request(at)).await {
+        Ok(issue) => {
+            info!(issue_key = %issue.key, "Задача по расписанию создана");
+            Ok(Some(issue))
+        }
+        Err(TrackerError::ApiError { status, .. }) if status.as_u16() == 409 => {
+            debug!("Задача этого срабатывания уже создана");
+            Ok(None)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Время последней проверки расписания
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ScheduleState {
+    last_check: Option<String>,
+}
+
+impl ScheduleState {
+    fn load() -> Self {
+        state_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = state_path().context("Не удалось определить путь для состояния расписания")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    fn last_check(&self) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(self.last_check.as_deref()?, OCCURRENCE_FORMAT).ok()
+    }
+}
+
+fn state_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("schedule.json"))
+}
+
+/// Фоновый цикл: раз в минуту создаёт задачи, время которых наступило
+pub async fn run(client: TrackerClient, tasks: Vec<RecurringTask>) {
+    let mut state = ScheduleState::load();
+    let now = truncate_to_minute(Local::now().naive_local());
+    let oldest = now - Duration::days(MAX_CATCH_UP_DAYS);
+    let mut last_check = state.last_check().unwrap_or(now).max(oldest);
+
+    loop {
+        let now = truncate_to_minute(Local::now().naive_local());
+        for task in &tasks {
+            let Some(at) = task.schedule.latest_between(last_check, now) else {
+                continue;
+            };
+            if let Err(err) = create(&client, task, at).await {
+                warn!(task = %task.name, error = %err, "Не удалось создать задачу по расписанию");
+            }
+        }
+
+        last_check = now;
+        state.last_check = Some(now.format(OCCURRENCE_FORMAT).to_string());
+        if let Err(err) = state.save() {
+            warn!(error = %err, "Не удалось сохранить состояние расписания");
+        }
+
+        let elapsed = u64::from(Local::now().second());
+        tokio::time::sleep(std::time::Duration::from_secs(60 - elapsed.min(59))).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-05-03 — пятница
+        NaiveDate::from_ymd_opt(2024, 5, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_match_schedule() {
+        let friday: CronSchedule = "0 10 * * fri".parse().unwrap();
+        assert!(friday.matches(at(3, 10, 0)));
+        assert!(!friday.matches(at(3, 10, 1)));
+        assert!(!friday.matches(at(2, 10, 0)));
+        assert_eq!(friday.next_after(at(3, 10, 0)), Some(at(10, 10, 0)));
+
+        let steps: CronSchedule = "*/15 9-18 * * 1-5".parse().unwrap();
+        assert!(steps.matches(at(3, 9, 45)));
+        assert!(!steps.matches(at(4, 9, 45)));
+
+        // День месяца и день недели объединяются через «или»
+        let either: CronSchedule = "0 9 1 * sun,7".parse().unwrap();
+        assert!(either.matches(at(1, 9, 0)));
+        assert!(either.matches(at(5, 9, 0)));
+        assert!(!either.matches(at(6, 9, 0)));
+
+        assert!("0 10 * *".parse::<CronSchedule>().is_err());
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+    }
+
+    #[test]
+    fn test_latest_between_returns_only_last_missed_occurrence() {
+        let hourly: CronSchedule = "30 * * * *".parse().unwrap();
+        assert_eq!(
+            hourly.latest_between(at(3, 8, 0), at(3, 11, 10)),
+            Some(at(3, 10, 30))
+        );
+        assert_eq!(hourly.latest_between(at(3, 10, 30), at(3, 11, 10)), None);
+    }
+
+    #[test]
+    fn test_recurring_task_request_is_unique_per_occurrence() {
+        let task: RecurringTask = serde_norway::from_str(
+            r#"
+name: weekly-demo
+schedule: "0 10 * * fri"
+queue: TREK
+summary: "Подготовить демо {date}"
+type: task
+tags: [demo]
+"#,
+        )
+        .unwrap();
+
+        let request = task.request(at(3, 10, 0));
+        assert_eq!(request.summary, "Подготовить демо 2024-05-03");
+        assert_eq!(request.issue_type.as_deref(), Some("task"));
+        assert_eq!(
+            request.unique.as_deref(),
+            Some("you-schedule:weekly-demo:2024-05-03T10:00")
+        );
+        assert_ne!(task.request(at(10, 10, 0)).unique, request.unique);
+    }
+}
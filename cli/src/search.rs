@@ -0,0 +1,352 @@
+//! Сохранённые поиски: `you search`
+//!
+//! Поиск — именованный запрос на языке запросов Трекера из секции `searches`
+//! конфигурации. `you search save` добавляет или меняет поиск, `you search run`
+//! выполняет его; в TUI поиски выбираются клавишей F10 на экране Tracker. С `--sync`
+//! поиск сохраняется и как фильтр Трекера, чтобы он был виден в веб-интерфейсе.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+use tracker_lib::{
+    filters::FilterRequest,
+    ids::QueueKey,
+    models::Issue,
+    search::SearchRequest,
+    template::{self, IssueTemplate},
+    TrackerClient,
+};
+
+use crate::config::Config;
+use crate::output::{self, IssueOutput, OutputFormat};
+use crate::render::Renderer;
+use crate::settings;
+use crate::summarize;
+use crate::templates;
+
+/// Секция конфигурации с поисками
+const SECTION: &str = "searches";
+
+/// Сколько задач по умолчанию выводит `you search run`
+pub const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    /// Запрос на языке запросов Трекера
+    pub query: String,
+
+    /// Идентификатор фильтра Трекера, если поиск синхронизирован
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_id: Option<u64>,
+}
+
+/// Команды сохранённых поисков
+#[derive(Subcommand)]
+pub enum SearchCommands {
+    /// Сохранить поиск под именем или изменить его запрос
+    Save {
+        /// Имя поиска (например, mybugs)
+        name: String,
+
+        /// Запрос на языке запросов (например, "Type: Bug Assignee: me() Resolution: empty()")
+        #[arg(short, long)]
+        query: String,
+
+        /// Сохранить поиск и как фильтр Трекера
+        #[arg(long)]
+        sync: bool,
+    },
+    /// Выполнить сохранённый поиск
+    Run {
+        /// Имя поиска
+        name: String,
+
+        /// Сколько задач показать
+        #[arg(short, long, default_value_t = DEFAULT_LIMIT)]
+        limit: usize,
+
+        /// Шаблон строки задачи: short, full, имя своего шаблона или путь к файлу .tmpl
+        #[arg(short, long, default_value = templates::SHORT)]
+        template: String,
+
+        /// Формат вывода; для json шаблон не используется (схема — `you schema search-run`)
+        #[arg(short, long, value_enum, default_value_t)]
+        output: OutputFormat,
+
+        /// Искать параллельно в каждой из очередей (через запятую: TREK,OPS)
+        #[arg(long, value_delimiter = ',')]
+        queues: Vec<QueueKey>,
+    },
+    /// Показать сохранённые поиски
+    List,
+    /// Удалить сохранённый поиск; фильтр в Трекере остаётся
+    Delete {
+        /// Имя поиска
+        name: String,
+    },
+    /// Сохранить все поиски как фильтры Трекера
+    Sync,
+}
+
+impl SearchCommands {
+    /// Выполняет команду сохранённых поисков
+    pub async fn execute(&self) -> Result<()> {
+        match self {
+            SearchCommands::Save { name, query, sync } => execute_save(name, query, *sync).await,
+            SearchCommands::Run {
+                name,
+                limit,
+                template,
+                output,
+                queues,
+            } => execute_run(name, *limit, template, *output, queues).await,
+            SearchCommands::List => execute_list(),
+            SearchCommands::Delete { name } => execute_delete(name),
+            SearchCommands::Sync => execute_sync().await,
+        }
+    }
+}
+
+#[instrument]
+async fn execute_save(name: &str, query: &str, sync: bool) -> Result<()> {
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        bail!("Имя поиска не должно быть пустым или содержать пробелы: «{name}»");
+    }
+    let query = query.trim();
+    if query.is_empty() {
+        bail!("Запрос поиска «{name}» пуст");
+    }
+
+    let mut searches = Config::load()?.searches;
+    let search = searches.entry(name.to_string()).or_default();
+    search.query = query.to_string();
+    if sync {
+        let client = settings::tracker_client()?;
+        sync_search(&client, name, search).await?;
+    }
+    let path = Config::save_section(SECTION, &searches)?;
+    println!("Поиск {name} сохранён в {}", path.display());
+    Ok(())
+}
+
+#[instrument]
+async fn execute_run(
+    name: &str,
+    limit: usize,
+    template: &str,
+    output: OutputFormat,
+    queues: &[QueueKey],
+) -> Result<()> {
+    let template = templates::load(template)?;
+    let config = Config::load()?;
+    let search = find(&config.searches, name)?;
+    let client = settings::tracker_client()?;
+    let issues = if queues.is_empty() {
+        summarize::fetch_issues(&client, &search.query, limit).await?
+    } else {
+        fetch_across_queues(&client, queues, &search.query, limit).await?
+    };
+    if output == OutputFormat::Json {
+        let issues: Vec<IssueOutput> = issues.iter().map(IssueOutput::from).collect();
+        return output::print_json(&issues);
+    }
+    if issues.is_empty() {
+        println!("По поиску {name} ничего не найдено");
+        return Ok(());
+    }
+    let renderer = Renderer::stdout();
+    for issue in &issues {
+        println!("{}", renderer.issue(&template, issue));
+    }
+    Ok(())
+}
+
+/// Задачи поиска из нескольких очередей; недоступные и обрезанные по `limit` очереди
+/// выводятся в stderr
+async fn fetch_across_queues(
+    client: &TrackerClient,
+    queues: &[QueueKey],
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Issue>> {
+    let request = SearchRequest {
+        query: Some(query.to_string()),
+        ..Default::default()
+    };
+    let mut found = client.search_across_queues(queues, &request, limit).await;
+    if found.failures.len() == queues.len() {
+        if let Some((queue, error)) = found.failures.pop() {
+            bail!("Поиск не удался ни в одной очереди, последняя ошибка ({queue}): {error}");
+        }
+    }
+    for (queue, error) in &found.failures {
+        eprintln!("Очередь {queue} пропущена: {error}");
+    }
+    for queue in &found.truncated {
+        eprintln!("Очередь {queue}: загружены первые {limit} задач");
+    }
+    found.issues.truncate(limit);
+    Ok(found.issues)
+}
+
+fn execute_list() -> Result<()> {
+    let searches = sorted(&Config::load()?.searches);
+    if searches.is_empty() {
+        println!("Сохранённых поисков нет: добавьте поиск командой `you search save`");
+        return Ok(());
+    }
+    for (name, search) in searches {
+        let filter = search
+            .filter_id
+            .map(|id| format!("  (фильтр {id})"))
+            .unwrap_or_default();
+        println!("{name:<16} {}{filter}", search.query);
+    }
+    Ok(())
+}
+
+#[instrument]
+fn execute_delete(name: &str) -> Result<()> {
+    let mut searches = Config::load()?.searches;
+    find(&searches, name)?;
+    searches.remove(name);
+    Config::save_section(SECTION, &searches)?;
+    println!("Поиск {name} удалён");
+    Ok(())
+}
+
+#[instrument]
+async fn execute_sync() -> Result<()> {
+    let mut searches = Config::load()?.searches;
+    if searches.is_empty() {
+        println!("Сохранённых поисков нет");
+        return Ok(());
+    }
+    let client = settings::tracker_client()?;
+    for (name, search) in searches.iter_mut() {
+        sync_search(&client, name, search).await?;
+    }
+    Config::save_section(SECTION, &searches)?;
+    println!("Синхронизировано поисков: {}", searches.len());
+    Ok(())
+}
+
+/// Создать фильтр Трекера для поиска или обновить уже созданный
+#[instrument(skip(client, search))]
+async fn sync_search(client: &TrackerClient, name: &str, search: &mut SavedSearch) -> Result<()> {
+    let request = FilterRequest::new(name, &search.query);
+    match search.filter_id {
+        Some(id) => {
+            client.update_filter(id, &request).await?;
+        }
+        None => {
+            let filter = client.create_filter(&request).await?;
+            search.filter_id = Some(filter.id);
+        }
+    }
+    info!(filter_id = ?search.filter_id, "Поиск синхронизирован с фильтром Трекера");
+    Ok(())
+}
+
+/// Поиск по имени; если его нет, ошибка перечисляет доступные
+pub fn find<'a>(searches: &'a HashMap<String, SavedSearch>, name: &str) -> Result<&'a SavedSearch> {
+    match searches.get(name) {
+        Some(search) => Ok(search),
+        None if searches.is_empty() => bail!("Поиск «{name}» не найден: сохранённых поисков нет"),
+        None => bail!(
+            "Поиск «{name}» не найден; доступны: {}",
+            sorted(searches)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Поиски в порядке имён
+pub fn sorted(searches: &HashMap<String, SavedSearch>) -> Vec<(String, SavedSearch)> {
+    let mut searches: Vec<(String, SavedSearch)> = searches
+        .iter()
+        .map(|(name, search)| (name.clone(), search.clone()))
+        .collect();
+    searches.sort_by(|(a, _), (b, _)| a.cmp(b));
+    searches
+}
+
+/// Строка результата поиска: ключ, статус и название
+pub fn issue_row(issue: &Issue) -> String {
+    IssueTemplate::new(template::SHORT).render(issue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracker_lib::TrackerConfig;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn searches() -> HashMap<String, SavedSearch> {
+        HashMap::from([
+            (
+                "review".to_string(),
+                SavedSearch {
+                    query: "Status: review".to_string(),
+                    filter_id: None,
+                },
+            ),
+            (
+                "mybugs".to_string(),
+                SavedSearch {
+                    query: "Type: Bug Assignee: me()".to_string(),
+                    filter_id: Some(42),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_find_lists_available_names() {
+        let searches = searches();
+        assert_eq!(find(&searches, "mybugs").unwrap().filter_id, Some(42));
+
+        let err = find(&searches, "typo").unwrap_err().to_string();
+        assert_eq!(err, "Поиск «typo» не найден; доступны: mybugs, review");
+    }
+
+    #[tokio::test]
+    async fn test_sync_search_creates_then_updates_filter() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/filters/"))
+            .and(body_json(serde_json::json!({
+                "name": "review",
+                "query": "Status: review"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"id": 7})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/v3/filters/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": 7})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client =
+            TrackerClient::new(TrackerConfig::new("test-token").with_base_url(mock_server.uri()))
+                .unwrap();
+
+        let mut search = searches().remove("review").unwrap();
+        sync_search(&client, "review", &mut search).await.unwrap();
+        assert_eq!(search.filter_id, Some(7));
+
+        sync_search(&client, "review", &mut search).await.unwrap();
+    }
+}
@@ -0,0 +1,34 @@
+//! Хранение токенов в системном хранилище ключей
+//!
+//! Клиенты библиотек сами находят токены через `core_lib::credentials`: сначала в
+//! переменной окружения, затем в keyring. Поэтому сохранённый токен подхватывает
+//! следующий созданный клиент, а окружение процесса не меняется.
+
+use anyhow::{Context, Result};
+use core_lib::credentials;
+use tracing::{info, instrument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Secret {
+    TrackerToken,
+    OpenRouterToken,
+}
+
+impl Secret {
+    /// Переменная окружения, из которой токен читают клиенты
+    pub fn env_var(self) -> &'static str {
+        match self {
+            Secret::TrackerToken => "TRACKER_TOKEN",
+            Secret::OpenRouterToken => "OPEN_ROUTER_TOKEN",
+        }
+    }
+}
+
+/// Сохраняет токен в keyring; новые клиенты найдут его сами
+#[instrument(skip(value))]
+pub fn store(secret: Secret, value: &str) -> Result<()> {
+    credentials::store(secret.env_var(), value)
+        .with_context(|| format!("Не удалось сохранить {} в keyring", secret.env_var()))?;
+    info!(env_var = secret.env_var(), "Токен сохранён в keyring");
+    Ok(())
+}
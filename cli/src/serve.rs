@@ -0,0 +1,528 @@
+//! Режим сервера: приём вебхуков Трекера
+//!
+//! `you serve` слушает `POST /webhook`, проверяет секрет из `TRACKER_WEBHOOK_SECRET`
+//! и тело запроса через `tracker_lib::webhook`, сразу отвечает 202 и в фоне выполняет
+//! действия из `serve.actions` конфигурации, подходящие по событию и очереди. Без
+//! секрета сервер запускается только на loopback и без правил, меняющих задачи или
+//! запускающих команды.
+//! Заодно по расписанию из `serve.schedule` создаются повторяющиеся задачи,
+//! а наступившие напоминания `you tracker remind` раз в минуту переносятся во входящие.
+//! С `serve.prep` перед встречами из календаря готовятся справки `you llm prep`.
+//! `GET /metrics` отдаёт метрики Prometheus: вебхуки, запросы к Трекеру и LLM,
+//! потраченные токены и давность синхронизаций.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    process::Stdio,
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Result};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use chrono::{Local, Utc};
+use clap::Args;
+use core_lib::credentials;
+use llm_lib::{events::LlmEvent, LlmClient, LlmConfig};
+use serde::Deserialize;
+use tokio::{
+    io::AsyncWriteExt,
+    process::Command,
+    sync::{mpsc::UnboundedSender, Mutex},
+};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    comments::AddCommentRequest,
+    events::TrackerEvent,
+    ids::QueueKey,
+    models::TRACKER_DATE_FORMAT,
+    webhook::{self, WebhookEvent, WebhookPayload},
+};
+
+use crate::{
+    config::Config,
+    inbox::{Inbox, Notification, NotificationKind},
+    language::{self, ContentLanguage},
+    llm::DEFAULT_MODEL,
+    metrics::{Metrics, SyncLag},
+    notify::Notifier,
+    prep,
+    queues::{LiveRules, NotifyRules},
+    reminders, schedule, settings,
+    sinks::Sinks,
+    triage,
+};
+
+/// Как часто проверять сроки напоминаний
+const REMINDER_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Переменная окружения (или запись keyring) с общим секретом триггеров
+pub const SECRET_VAR: &str = "TRACKER_WEBHOOK_SECRET";
+
+/// Правило: какое действие выполнить для каких вебхуков
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookRule {
+    /// События; пустой список — любые
+    #[serde(default)]
+    pub on: Vec<WebhookEvent>,
+
+    /// Ключ очереди; без него — любая очередь
+    #[serde(default)]
+    pub queue: Option<QueueKey>,
+
+    #[serde(flatten)]
+    pub action: WebhookAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WebhookAction {
+    /// Добавить уведомление во входящие
+    Notify,
+    /// Классифицировать задачу LLM: подсказка комментарием или сразу применить
+    Triage {
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default)]
+        apply: bool,
+    },
+    /// Запустить команду оболочки; тело вебхука передаётся в stdin,
+    /// ключ задачи и событие — в `ISSUE_KEY` и `WEBHOOK_EVENT`
+    Script { command: String },
+}
+
+impl WebhookRule {
+    pub fn matches(&self, payload: &WebhookPayload) -> bool {
+        let event_matches = self.on.is_empty() || self.on.contains(&payload.event);
+        let queue_matches = match &self.queue {
+            Some(queue) => payload.queue() == *queue,
+            None => true,
+        };
+        event_matches && queue_matches
+    }
+}
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Порт для приёма вебхуков
+    #[arg(short, long, default_value_t = 8080)]
+    port: u16,
+
+    /// Адрес для приёма вебхуков
+    #[arg(long, default_value = "127.0.0.1")]
+    bind: IpAddr,
+}
+
+struct ServerState {
+    secret: Option<String>,
+    rules: Vec<WebhookRule>,
+    /// Вебхуки обрабатываются параллельно, а входящие хранятся в одном файле
+    inbox: Mutex<()>,
+    /// Язык организации для подсказок разбора в комментариях
+    language: Option<ContentLanguage>,
+    metrics: Arc<Metrics>,
+    notifier: Notifier,
+    /// Пересылка уведомлений в Slack и Telegram
+    sinks: Sinks,
+    /// Какие уведомления пропускать по очередям; перечитываются при изменении конфигурации
+    notify_rules: LiveRules,
+    tracker_events: UnboundedSender<TrackerEvent>,
+    llm_events: UnboundedSender<LlmEvent>,
+}
+
+impl ServeArgs {
+    pub async fn execute(&self) -> Result<()> {
+        let config = Config::load()?;
+        let language = config.language.enforce;
+        let notifier = Notifier::new(&config.notify);
+        let sinks = Sinks::new(config.notify.sinks);
+        if !sinks.is_empty() {
+            println!("Пересылка уведомлений в чаты, правил: {}", sinks.len());
+        }
+        let notify_rules = LiveRules::new(NotifyRules::new(&config.queue));
+        notify_rules.watch();
+        let config = config.serve;
+        let rules = config.actions;
+        let secret = credentials::resolve(SECRET_VAR);
+        if secret.is_none() {
+            if let Some(reason) = secret_required(self.bind, &rules) {
+                bail!(
+                    "{SECRET_VAR} не задан, а {reason}: без секрета вебхук может прислать \
+                     кто угодно. Задайте секрет в переменной или keyring"
+                );
+            }
+            warn!("{SECRET_VAR} не задан, вебхуки принимаются без проверки секрета");
+        }
+
+        let metrics = Arc::new(Metrics::default());
+        let state = Arc::new(ServerState {
+            secret,
+            rules,
+            inbox: Mutex::new(()),
+            language,
+            notifier,
+            sinks,
+            notify_rules,
+            tracker_events: metrics.tracker_sender(),
+            llm_events: metrics.llm_sender(),
+            metrics,
+        });
+        if !config.schedule.is_empty() {
+            let client = settings::tracker_client_builder()
+                .and_then(|builder| builder.response_cache(true).build())
+                .context("Для задач по расписанию нужен доступ к Трекеру")?
+                .with_event_sender(state.tracker_events.clone());
+            let now = Local::now().naive_local();
+            for task in &config.schedule {
+                match task.schedule.next_after(now) {
+                    Some(next) => println!("Расписание {}: следующая задача {next}", task.name),
+                    None => println!("Расписание {}: срабатываний в ближайший год нет", task.name),
+                }
+            }
+            tokio::spawn(schedule::run(client, config.schedule));
+        }
+        tokio::spawn(deliver_reminders(Arc::clone(&state)));
+        if config.prep.enabled {
+            let client = settings::tracker_client_builder()
+                .and_then(|builder| builder.response_cache(true).build())
+                .context("Для справок к встречам нужен доступ к Трекеру")?
+                .with_event_sender(state.tracker_events.clone());
+            println!(
+                "Справки к встречам за {} мин до начала",
+                config
+                    .prep
+                    .minutes_before
+                    .unwrap_or(prep::DEFAULT_MINUTES_BEFORE)
+            );
+            tokio::spawn(prep::run(client, config.prep, state.notifier.clone()));
+        }
+
+        let rules_count = state.rules.len();
+        let addr = SocketAddr::new(self.bind, self.port);
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Не удалось занять адрес {addr}"))?;
+
+        println!("Приём вебхуков на http://{addr}/webhook, правил: {rules_count}");
+        axum::serve(listener, router(state)).await?;
+        Ok(())
+    }
+}
+
+fn router(state: Arc<ServerState>) -> Router {
+    Router::new()
+        .route("/webhook", post(receive))
+        .route("/health", get(|| async { "ok" }))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+/// Переносит наступившие напоминания во входящие, пока работает сервер
+async fn deliver_reminders(state: Arc<ServerState>) {
+    let mut interval = tokio::time::interval(REMINDER_CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        let _guard = state.inbox.lock().await;
+        let mut inbox = Inbox::load();
+        let result = reminders::deliver_due(&mut inbox).and_then(|due| {
+            if !due.is_empty() {
+                inbox.save()?;
+            }
+            Ok(due)
+        });
+        match result {
+            Ok(due) => {
+                for notification in &due {
+                    state.notifier.inbox(notification);
+                    state.sinks.send(notification).await;
+                }
+            }
+            Err(err) => warn!(error = %err, "Не удалось перенести напоминания во входящие"),
+        }
+    }
+}
+
+async fn metrics(State(state): State<Arc<ServerState>>) -> String {
+    state.metrics.render(SyncLag::current())
+}
+
+async fn receive(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let status = accept(&state, &headers, body);
+    state.metrics.record_webhook(status.as_u16());
+    status
+}
+
+fn accept(state: &Arc<ServerState>, headers: &HeaderMap, body: Bytes) -> StatusCode {
+    if let Some(secret) = &state.secret {
+        let provided = headers
+            .get(webhook::SECRET_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if !webhook::verify_secret(secret, provided) {
+            warn!("Вебхук отклонён: неверный секрет");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let payload = match WebhookPayload::parse(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!(error = %err, "Вебхук отклонён: некорректное тело");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+    info!(event = ?payload.event, issue_key = %payload.issue.key, "Вебхук получен");
+
+    tokio::spawn(dispatch(Arc::clone(state), payload, body));
+    StatusCode::ACCEPTED
+}
+
+async fn dispatch(state: Arc<ServerState>, payload: WebhookPayload, body: Bytes) {
+    for rule in state.rules.iter().filter(|rule| rule.matches(&payload)) {
+        if let Err(err) = run_action(&state, &rule.action, &payload, &body).await {
+            warn!(
+                action = ?rule.action,
+                issue_key = %payload.issue.key,
+                error = %err,
+                "Действие по вебхуку не выполнено"
+            );
+        }
+    }
+}
+
+#[instrument(skip(state, payload, body), fields(issue_key = %payload.issue.key))]
+async fn run_action(
+    state: &ServerState,
+    action: &WebhookAction,
+    payload: &WebhookPayload,
+    body: &[u8],
+) -> Result<()> {
+    match action {
+        WebhookAction::Notify => {
+            let notification = notification(payload);
+            if !state.notify_rules.allows(&notification) {
+                debug!(kind = ?notification.kind, "Уведомление отключено правилами очереди");
+                return Ok(());
+            }
+            let _guard = state.inbox.lock().await;
+            let mut inbox = Inbox::load();
+            let fresh = inbox.merge(vec![notification.clone()]) > 0;
+            inbox.save()?;
+            if fresh {
+                state.notifier.inbox(&notification);
+                state.sinks.send(&notification).await;
+            }
+        }
+        WebhookAction::Triage { model, apply } => {
+            let client =
+                settings::tracker_client()?.with_event_sender(state.tracker_events.clone());
+            let llm = LlmClient::new(LlmConfig::new(model.as_deref().unwrap_or(DEFAULT_MODEL))?)?
+                .with_event_sender(state.llm_events.clone());
+            let issue = client.get_issue(&payload.issue.key, None).await?;
+            let mut suggestion = triage::suggest(&llm, &issue).await?;
+            if *apply {
+                triage::apply(&client, &issue.issue_key()?, &suggestion).await?;
+            } else {
+                suggestion.reason =
+                    language::enforce(&llm, state.language, &suggestion.reason).await?;
+                let text = format!("Подсказка разбора:\n{}", suggestion.describe());
+                client
+                    .add_comment(&payload.issue.key, &AddCommentRequest::new(text))
+                    .await?;
+            }
+        }
+        WebhookAction::Script { command } => {
+            let mut child = Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .envs(settings::child_env())
+                .env("ISSUE_KEY", payload.issue.key.as_str())
+                .env("WEBHOOK_EVENT", event_name(payload.event))
+                .stdin(Stdio::piped())
+                .spawn()
+                .with_context(|| format!("Не удалось запустить {command}"))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(body).await?;
+            }
+            let status = child.wait().await?;
+            if !status.success() {
+                bail!("{command} завершился с ошибкой: {status}");
+            }
+        }
+    }
+    info!(action = ?action, "Действие по вебхуку выполнено");
+    Ok(())
+}
+
+/// Имя события в том виде, в каком оно приходит в теле вебхука
+fn event_name(event: WebhookEvent) -> String {
+    serde_json::to_value(event)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Уведомление во входящие по вебхуку
+fn notification(payload: &WebhookPayload) -> Notification {
+    let at = Utc::now().format(TRACKER_DATE_FORMAT).to_string();
+    let (kind, text) = match payload.event {
+        WebhookEvent::StatusChanged => (
+            NotificationKind::StatusChanged,
+            format!(
+                "статус: {}",
+                payload.issue.status.as_deref().unwrap_or("изменён")
+            ),
+        ),
+        WebhookEvent::IssueCreated => (NotificationKind::Webhook, "задача создана".to_string()),
+        WebhookEvent::IssueUpdated => (NotificationKind::Webhook, "задача изменена".to_string()),
+        WebhookEvent::CommentAdded => (
+            NotificationKind::Webhook,
+            payload
+                .comment
+                .clone()
+                .unwrap_or_else(|| "новый комментарий".to_string()),
+        ),
+        WebhookEvent::IssueAssigned => (NotificationKind::Assigned, "назначена".to_string()),
+        WebhookEvent::SlaBreached => (NotificationKind::SlaBreach, "нарушен SLA".to_string()),
+        WebhookEvent::Other => (NotificationKind::Webhook, "событие триггера".to_string()),
+    };
+    Notification {
+        id: format!(
+            "webhook:{}:{}:{at}",
+            event_name(payload.event),
+            payload.issue.key
+        ),
+        kind,
+        issue_key: payload.issue.key.to_string(),
+        issue_summary: payload.issue.summary.clone().unwrap_or_default(),
+        text,
+        author: payload.author.clone(),
+        at,
+        read: false,
+    }
+}
+
+/// Почему без секрета сервер запускать нельзя; None — можно
+///
+/// Без секрета вебхук подделает любой, кто достучится до адреса, поэтому это
+/// допустимо только на loopback и только с правилами, которые не меняют задачи, не
+/// пишут в них комментарии и не запускают команды.
+fn secret_required(bind: IpAddr, rules: &[WebhookRule]) -> Option<String> {
+    if !bind.is_loopback() {
+        return Some(format!("сервер слушает внешний адрес {bind}"));
+    }
+    rules.iter().find_map(|rule| match &rule.action {
+        WebhookAction::Script { command } => Some(format!("правило запускает команду «{command}»")),
+        WebhookAction::Triage { apply: true, .. } => {
+            Some("правило сразу применяет разбор LLM к задачам".to_string())
+        }
+        WebhookAction::Triage { apply: false, .. } => {
+            Some("правило пишет подсказку разбора LLM комментарием в задачу".to_string())
+        }
+        WebhookAction::Notify => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(event: &str, key: &str) -> WebhookPayload {
+        WebhookPayload::parse(
+            format!(r#"{{"event": "{event}", "issue": {{"key": "{key}"}}, "comment": "Готово"}}"#)
+                .as_bytes(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_match_event_and_queue() {
+        let rules: Vec<WebhookRule> = serde_norway::from_str(
+            r#"
+- on: [issueCreated]
+  queue: trek
+  action: triage
+  apply: true
+- action: script
+  command: ./notify.sh
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules[0].action,
+            WebhookAction::Triage {
+                model: None,
+                apply: true
+            }
+        );
+        assert!(rules[0].matches(&payload("issueCreated", "TREK-1")));
+        assert!(!rules[0].matches(&payload("issueUpdated", "TREK-1")));
+        assert!(!rules[0].matches(&payload("issueCreated", "OPS-1")));
+        assert!(rules[1].matches(&payload("issueMoved", "OPS-1")));
+    }
+
+    #[test]
+    fn test_secret_required_outside_loopback_and_for_writing_rules() {
+        let loopback: IpAddr = "127.0.0.1".parse().unwrap();
+        let notify = WebhookRule {
+            on: Vec::new(),
+            queue: None,
+            action: WebhookAction::Notify,
+        };
+        let suggest = WebhookRule {
+            action: WebhookAction::Triage {
+                model: None,
+                apply: false,
+            },
+            ..notify.clone()
+        };
+        assert_eq!(
+            secret_required(loopback, std::slice::from_ref(&notify)),
+            None
+        );
+        assert!(secret_required(loopback, &[notify.clone(), suggest])
+            .unwrap()
+            .contains("комментарием"));
+
+        let external = secret_required("0.0.0.0".parse().unwrap(), std::slice::from_ref(&notify));
+        assert!(external.unwrap().contains("0.0.0.0"));
+
+        let script = WebhookRule {
+            action: WebhookAction::Script {
+                command: "./deploy.sh".to_string(),
+            },
+            ..notify.clone()
+        };
+        assert!(secret_required(loopback, &[notify.clone(), script])
+            .unwrap()
+            .contains("./deploy.sh"));
+
+        let apply = WebhookRule {
+            action: WebhookAction::Triage {
+                model: None,
+                apply: true,
+            },
+            ..notify
+        };
+        assert!(secret_required(loopback, &[apply]).is_some());
+    }
+
+    #[test]
+    fn test_notification_from_comment_webhook() {
+        let notification = notification(&payload("commentAdded", "TREK-1"));
+
+        assert_eq!(notification.kind, NotificationKind::Webhook);
+        assert_eq!(notification.text, "Готово");
+        assert!(notification.id.starts_with("webhook:commentAdded:TREK-1:"));
+        assert!(!notification.read);
+    }
+}
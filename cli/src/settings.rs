@@ -0,0 +1,96 @@
+//! Параметры запуска, общие для всех команд
+//!
+//! Глобальные флаги (`--read-only`, `--sandbox`, `--no-redact`, `--tz`) разбираются один
+//! раз в `main` и дальше читаются отсюда: переменные окружения процесса во время работы
+//! не меняются. Дочерним процессам — хукам и скриптам `you serve` — те же режимы
+//! передаются переменными окружения (см. [`child_env`]).
+
+use std::sync::OnceLock;
+
+use calendar_lib::Zone;
+use tracing::warn;
+use tracker_lib::{TrackerClient, TrackerClientBuilder, READ_ONLY_VAR};
+
+use crate::{redact, sandbox::Sandbox, timezone};
+
+/// Режимы, заданные при запуске
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// Только чтение: из `--read-only` или ключа `read_only` конфигурации
+    pub read_only: bool,
+    /// Песочница из `--sandbox`
+    pub sandbox: Option<Sandbox>,
+    /// Не скрывать данные перед отправкой в LLM (`--no-redact`)
+    pub no_redact: bool,
+    /// Часовой пояс из `--tz`
+    pub timezone: Option<Zone>,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Задаёт режимы процесса; `main` вызывает её до запуска команды
+pub fn init(settings: Settings) {
+    if SETTINGS.set(settings).is_err() {
+        warn!("Параметры запуска уже заданы, повторная установка пропущена");
+    }
+}
+
+/// Режимы текущего процесса; без [`init`] (например, в тестах) — значения по умолчанию
+pub fn get() -> &'static Settings {
+    SETTINGS.get_or_init(Settings::default)
+}
+
+/// Клиент Трекера из окружения с учётом режима только для чтения и песочницы
+pub fn tracker_client() -> tracker_lib::Result<TrackerClient> {
+    tracker_client_builder()?.build()
+}
+
+/// Построитель клиента Трекера с теми же режимами, что у [`tracker_client`]
+pub fn tracker_client_builder() -> tracker_lib::Result<TrackerClientBuilder> {
+    let settings = get();
+    let token = settings
+        .sandbox
+        .as_ref()
+        .and_then(|sandbox| sandbox.token.clone());
+    Ok(TrackerClient::builder_from_env(token)?.configure(|config| {
+        let read_only = config.read_only || settings.read_only;
+        let config = config.with_read_only(read_only);
+        match &settings.sandbox {
+            Some(sandbox) => sandbox.apply(config),
+            None => config,
+        }
+    }))
+}
+
+/// Включён ли режим только для чтения флагом, конфигурацией или `TRACKER_READ_ONLY`
+pub fn read_only() -> bool {
+    get().read_only || tracker_lib::read_only_from_env()
+}
+
+/// Организация Трекера, с которой работают клиенты: тестовая в песочнице
+pub fn org_id() -> Option<String> {
+    get()
+        .sandbox
+        .as_ref()
+        .and_then(|sandbox| sandbox.org_id.clone())
+        .or_else(|| std::env::var(tracker_lib::ORG_ID_VAR).ok())
+}
+
+/// Переменные окружения, с которыми дочерний `you` работает в тех же режимах
+pub fn child_env() -> Vec<(&'static str, String)> {
+    let settings = get();
+    let mut vars = Vec::new();
+    if settings.read_only {
+        vars.push((READ_ONLY_VAR, "1".to_string()));
+    }
+    if let Some(sandbox) = &settings.sandbox {
+        vars.extend(sandbox.env());
+    }
+    if settings.no_redact {
+        vars.push((redact::NO_REDACT_VAR, "1".to_string()));
+    }
+    if let Some(zone) = settings.timezone {
+        vars.push((timezone::TZ_VAR, zone.to_string()));
+    }
+    vars
+}
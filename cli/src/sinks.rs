@@ -0,0 +1,293 @@
+//! Пересылка уведомлений `you serve` в Slack и Telegram
+//!
+//! Правила `notify.sinks` выбирают уведомления по типу (`assigned`, `mention`,
+//! `sla_breach` и т.д.) и очереди задачи. Адрес входящего вебхука Slack и токен бота
+//! Telegram не хранятся в конфигурации: правило называет переменную окружения или
+//! запись keyring, из которой они читаются при запуске сервера. Ошибка отправки
+//! только записывается в журнал и не мешает остальным получателям.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::ids::{IssueKey, QueueKey};
+
+use crate::inbox::{Notification, NotificationKind};
+
+/// Адрес Bot API Telegram
+const TELEGRAM_API: &str = "https://api.telegram.org";
+
+const SLACK_SECRET_VAR: &str = "SLACK_WEBHOOK_URL";
+const TELEGRAM_SECRET_VAR: &str = "TELEGRAM_BOT_TOKEN";
+
+/// Правило: какие уведомления куда пересылать
+#[derive(Debug, Clone, Deserialize)]
+pub struct SinkRule {
+    /// Типы уведомлений; пустой список — любые
+    #[serde(default)]
+    pub on: Vec<NotificationKind>,
+
+    /// Ключ очереди; без него — любая очередь
+    #[serde(default)]
+    pub queue: Option<QueueKey>,
+
+    #[serde(flatten)]
+    pub target: SinkTarget,
+}
+
+/// Получатель уведомлений
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkTarget {
+    /// Входящий вебхук Slack; `secret` — переменная с адресом вебхука
+    Slack {
+        #[serde(default = "slack_secret")]
+        secret: String,
+    },
+    /// Чат Telegram; `secret` — переменная с токеном бота
+    Telegram {
+        #[serde(default = "telegram_secret")]
+        secret: String,
+        chat_id: String,
+    },
+}
+
+fn slack_secret() -> String {
+    SLACK_SECRET_VAR.to_string()
+}
+
+fn telegram_secret() -> String {
+    TELEGRAM_SECRET_VAR.to_string()
+}
+
+impl SinkTarget {
+    fn secret(&self) -> &str {
+        match self {
+            SinkTarget::Slack { secret } | SinkTarget::Telegram { secret, .. } => secret,
+        }
+    }
+}
+
+impl SinkRule {
+    pub fn matches(&self, notification: &Notification) -> bool {
+        let kind_matches = self.on.is_empty() || self.on.contains(&notification.kind);
+        let queue_matches = match &self.queue {
+            Some(queue) => notification
+                .issue_key
+                .parse::<IssueKey>()
+                .is_ok_and(|key| key.queue() == *queue),
+            None => true,
+        };
+        kind_matches && queue_matches
+    }
+}
+
+/// Правило с прочитанным секретом
+#[derive(Debug, Clone)]
+struct Sink {
+    rule: SinkRule,
+    secret: String,
+}
+
+/// Отправитель уведомлений во внешние чаты
+#[derive(Debug, Clone)]
+pub struct Sinks {
+    http: reqwest::Client,
+    sinks: Vec<Sink>,
+    telegram_api: String,
+}
+
+impl Sinks {
+    /// Читает секреты правил из окружения и keyring; правила без секрета пропускаются
+    pub fn new(rules: Vec<SinkRule>) -> Self {
+        Self::with_resolver(rules, core_lib::credentials::resolve)
+    }
+
+    fn with_resolver(rules: Vec<SinkRule>, resolve: impl Fn(&str) -> Option<String>) -> Self {
+        let sinks = rules
+            .into_iter()
+            .filter_map(|rule| match resolve(rule.target.secret()) {
+                Some(secret) => Some(Sink { rule, secret }),
+                None => {
+                    warn!(
+                        secret = rule.target.secret(),
+                        "Секрет получателя уведомлений не задан, правило пропущено"
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self {
+            http: reqwest::Client::new(),
+            sinks,
+            telegram_api: TELEGRAM_API.to_string(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Пересылает уведомление всем подходящим получателям
+    #[instrument(skip_all, fields(issue_key = %notification.issue_key))]
+    pub async fn send(&self, notification: &Notification) {
+        let text = message(notification);
+        for sink in self
+            .sinks
+            .iter()
+            .filter(|sink| sink.rule.matches(notification))
+        {
+            match self.post(sink, &text).await {
+                Ok(()) => info!(sink = ?sink.rule.target, "Уведомление переслано"),
+                Err(err) => warn!(
+                    sink = ?sink.rule.target,
+                    error = %err,
+                    "Не удалось переслать уведомление"
+                ),
+            }
+        }
+    }
+
+    async fn post(&self, sink: &Sink, text: &str) -> Result<()> {
+        let request = match &sink.rule.target {
+            SinkTarget::Slack { .. } => self.http.post(&sink.secret).json(&json!({"text": text})),
+            SinkTarget::Telegram { chat_id, .. } => self
+                .http
+                .post(format!(
+                    "{}/bot{}/sendMessage",
+                    self.telegram_api, sink.secret
+                ))
+                .json(&json!({"chat_id": chat_id, "text": text})),
+        };
+        let response = request.send().await.context("Получатель недоступен")?;
+        let status = response.status();
+        debug!(status = %status, "Ответ получателя уведомлений");
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Получатель ответил {status}: {body}");
+        }
+        Ok(())
+    }
+}
+
+/// Текст сообщения: задача в первой строке, событие во второй
+fn message(notification: &Notification) -> String {
+    format!(
+        "{}\n{}: {}",
+        notification.title(),
+        notification.kind.label(),
+        notification.text
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn notification(kind: NotificationKind, issue_key: &str) -> Notification {
+        Notification {
+            id: "1".to_string(),
+            kind,
+            issue_key: issue_key.to_string(),
+            issue_summary: "Релиз".to_string(),
+            text: "назначена на вас".to_string(),
+            author: None,
+            at: "2026-10-16T09:00:00.000+0000".to_string(),
+            read: false,
+        }
+    }
+
+    #[test]
+    fn test_rules_match_kind_and_queue() {
+        let rules: Vec<SinkRule> = serde_norway::from_str(
+            "\
+- type: slack
+  on: [assigned, sla_breach]
+  queue: TREK
+- type: telegram
+  chat_id: \"-100500\"
+",
+        )
+        .unwrap();
+        assert_eq!(
+            rules[0].target,
+            SinkTarget::Slack {
+                secret: SLACK_SECRET_VAR.to_string()
+            }
+        );
+
+        assert!(rules[0].matches(&notification(NotificationKind::Assigned, "TREK-1")));
+        assert!(rules[0].matches(&notification(NotificationKind::SlaBreach, "TREK-2")));
+        assert!(!rules[0].matches(&notification(NotificationKind::Assigned, "OPS-1")));
+        assert!(!rules[0].matches(&notification(NotificationKind::Mention, "TREK-1")));
+        assert!(rules[1].matches(&notification(NotificationKind::Mention, "OPS-1")));
+    }
+
+    #[tokio::test]
+    async fn test_send_routes_to_matching_sinks() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/slack"))
+            .and(body_json(
+                json!({"text": "TREK-1: Релиз\nназначение: назначена на вас"}),
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/botsecret-token/sendMessage"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let rules = vec![
+            SinkRule {
+                on: vec![NotificationKind::Assigned],
+                queue: None,
+                target: SinkTarget::Slack {
+                    secret: "SLACK".to_string(),
+                },
+            },
+            SinkRule {
+                on: vec![NotificationKind::SlaBreach],
+                queue: None,
+                target: SinkTarget::Telegram {
+                    secret: "TELEGRAM".to_string(),
+                    chat_id: "42".to_string(),
+                },
+            },
+        ];
+        let uri = server.uri();
+        let mut sinks = Sinks::with_resolver(rules, |name| match name {
+            "SLACK" => Some(format!("{uri}/slack")),
+            "TELEGRAM" => Some("secret-token".to_string()),
+            _ => None,
+        });
+        sinks.telegram_api = server.uri();
+
+        sinks
+            .send(&notification(NotificationKind::Assigned, "TREK-1"))
+            .await;
+    }
+
+    #[test]
+    fn test_rules_without_secret_are_skipped() {
+        let rules = vec![SinkRule {
+            on: Vec::new(),
+            queue: None,
+            target: SinkTarget::Slack {
+                secret: "MISSING".to_string(),
+            },
+        }];
+        assert_eq!(Sinks::with_resolver(rules, |_| None).len(), 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Отложенные задачи
+//!
+//! `you tracker snooze TREK-5 --until monday` скрывает задачу до указанного дня из
+//! `you tracker find`, `GET /issues` локального API, сводки TUI и плана дня. Сроки хранятся
+//! в `$XDG_STATE_HOME/you/snoozed.json`. Когда день наступает, задача возвращается, а во
+//! входящие попадает уведомление — вместе с наступившими напоминаниями.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
+use tracker_lib::ids::IssueKey;
+
+use crate::{
+    inbox::{Notification, NotificationKind},
+    paths, reminders, timezone,
+};
+
+/// Префикс идентификатора уведомления о вернувшейся задаче
+const NOTIFICATION_PREFIX: &str = "snooze:";
+
+/// Названия дней недели для `--until`
+const WEEKDAYS: &[(&str, Weekday)] = &[
+    ("monday", Weekday::Mon),
+    ("mon", Weekday::Mon),
+    ("понедельник", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("tue", Weekday::Tue),
+    ("вторник", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("wed", Weekday::Wed),
+    ("среда", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("thu", Weekday::Thu),
+    ("четверг", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("fri", Weekday::Fri),
+    ("пятница", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sat", Weekday::Sat),
+    ("суббота", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+    ("sun", Weekday::Sun),
+    ("воскресенье", Weekday::Sun),
+];
+
+/// Разбирает день для `--until` относительно сегодняшнего дня по часовому поясу пользователя
+pub fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    parse_until_from(value, timezone::current().today())
+}
+
+/// Разбирает `YYYY-MM-DD`, `tomorrow`/`завтра`, день недели (ближайший после `today`)
+/// или задержку в днях и неделях (`3d`, `1w`)
+pub fn parse_until_from(value: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let value = value.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    if value == "tomorrow" || value == "завтра" {
+        return Ok(today + Duration::days(1));
+    }
+    if let Some((_, weekday)) = WEEKDAYS.iter().find(|(name, _)| *name == value) {
+        let ahead =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        let ahead = if ahead == 0 { 7 } else { ahead };
+        return Ok(today + Duration::days(i64::from(ahead)));
+    }
+    let delay = reminders::parse_delay(&value).map_err(|_| {
+        format!("«{value}»: ожидается дата YYYY-MM-DD, tomorrow, день недели или 3d")
+    })?;
+    if delay < Duration::days(1) {
+        return Err(format!("«{value}»: задачу можно отложить минимум на день"));
+    }
+    Ok(today + Duration::days(delay.num_days()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snoozes {
+    /// День, в который задача вернётся, по ключу задачи
+    pub issues: BTreeMap<String, NaiveDate>,
+}
+
+impl Snoozes {
+    /// Загружает отложенные задачи; повреждённый или отсутствующий файл даёт пустой список
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = snoozes_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Отложенные задачи не найдены");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(path = %path.display(), error = %err, "Не удалось разобрать отложенные задачи");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = snoozes_path().context("Не удалось определить путь для отложенных задач")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn snooze(&mut self, issue_key: &IssueKey, until: NaiveDate) {
+        self.issues.insert(issue_key.to_string(), until);
+    }
+
+    /// Возвращает задачу раньше срока; false, если она не была отложена
+    pub fn wake(&mut self, issue_key: &IssueKey) -> bool {
+        self.issues.remove(issue_key.as_str()).is_some()
+    }
+
+    /// Скрыта ли задача в день `today`
+    pub fn is_snoozed(&self, issue_key: &str, today: NaiveDate) -> bool {
+        self.issues
+            .get(issue_key)
+            .is_some_and(|until| *until > today)
+    }
+
+    /// Сколько задач скрыто в день `today`: на столько больше берут списки с лимитом,
+    /// чтобы после фильтра осталось `limit` задач
+    pub fn hidden_count(&self, today: NaiveDate) -> usize {
+        self.issues.values().filter(|until| **until > today).count()
+    }
+
+    /// Уведомления о задачах, вернувшихся к `today`; они удаляются из списка
+    pub fn take_expired(&mut self, today: NaiveDate) -> Vec<Notification> {
+        let expired: Vec<(String, NaiveDate)> = self
+            .issues
+            .iter()
+            .filter(|(_, until)| **until <= today)
+            .map(|(key, until)| (key.clone(), *until))
+            .collect();
+        expired
+            .into_iter()
+            .map(|(key, until)| {
+                self.issues.remove(&key);
+                notification(key, until)
+            })
+            .collect()
+    }
+}
+
+/// Фильтр для списков задач: отложенные на сегодня задачи не показываются
+pub fn visible(today: NaiveDate) -> impl Fn(&str) -> bool {
+    let snoozes = Snoozes::load();
+    move |issue_key| !snoozes.is_snoozed(issue_key, today)
+}
+
+fn notification(issue_key: String, until: NaiveDate) -> Notification {
+    Notification {
+        id: format!("{NOTIFICATION_PREFIX}{issue_key}:{until}"),
+        kind: NotificationKind::Reminder,
+        issue_key,
+        issue_summary: String::new(),
+        text: "отложенная задача вернулась".to_string(),
+        author: None,
+        at: format!("{until}T00:00:00.000+0000"),
+        read: false,
+    }
+}
+
+fn snoozes_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("snoozed.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_until() {
+        // 16.10.2026 — пятница
+        let today = date("2026-10-16");
+        assert_eq!(parse_until_from("monday", today), Ok(date("2026-10-19")));
+        assert_eq!(parse_until_from("Пятница", today), Ok(date("2026-10-23")));
+        assert_eq!(parse_until_from("tomorrow", today), Ok(date("2026-10-17")));
+        assert_eq!(parse_until_from("1w", today), Ok(date("2026-10-23")));
+        assert_eq!(
+            parse_until_from("2026-11-01", today),
+            Ok(date("2026-11-01"))
+        );
+        assert!(parse_until_from("2h", today).is_err());
+        assert!(parse_until_from("someday", today).is_err());
+    }
+
+    #[test]
+    fn test_snoozed_issue_returns_with_notification() {
+        let mut snoozes = Snoozes::default();
+        let key: IssueKey = "TREK-5".parse().unwrap();
+        snoozes.snooze(&key, date("2026-10-19"));
+
+        assert!(snoozes.is_snoozed("TREK-5", date("2026-10-18")));
+        assert!(!snoozes.is_snoozed("TREK-6", date("2026-10-18")));
+        assert_eq!(snoozes.hidden_count(date("2026-10-18")), 1);
+        assert_eq!(snoozes.hidden_count(date("2026-10-19")), 0);
+        assert!(snoozes.take_expired(date("2026-10-18")).is_empty());
+
+        let returned = snoozes.take_expired(date("2026-10-19"));
+        assert_eq!(returned.len(), 1);
+        assert_eq!(returned[0].issue_key, "TREK-5");
+        assert_eq!(returned[0].id, "snooze:TREK-5:2026-10-19");
+        assert!(!snoozes.is_snoozed("TREK-5", date("2026-10-19")));
+        assert!(!snoozes.wake(&key));
+    }
+}
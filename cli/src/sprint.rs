@@ -0,0 +1,467 @@
+//! Планирование спринта с помощью LLM
+//!
+//! `you llm plan-sprint --board 12` берёт задачи бэклога доски с оценками, считает
+//! ёмкость команды на даты спринта и просит LLM предложить состав спринта. Ёмкость
+//! задаётся секцией `sprint` конфигурации:
+//!
+//! ```yaml
+//! sprint:
+//!   focus_factor: 0.7
+//!   team:
+//!     - login: ivanov
+//!       hours_per_day: 6
+//!       days_off: [2026-10-20]
+//!       calendar: true
+//!     - login: petrov
+//! ```
+//!
+//! Для участника с `calendar: true` учитывается календарь из `CALENDAR_ICS`: дни с
+//! событиями на весь день (отпуск, отгул) не считаются рабочими, а встречи вычитаются
+//! из рабочих часов. Без секции команда — один человек с календарём. Предложение
+//! проверяется: задачи не из бэклога отбрасываются, часы пересчитываются по оценкам
+//! Трекера. После подтверждения задачи добавляются в спринт.
+
+use anyhow::{bail, Context, Result};
+use calendar_lib::Event;
+use chrono::{Datelike, NaiveDate, Weekday};
+use llm_lib::LlmClientTrait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    models::{Issue, SprintDetails},
+    task::UpdateIssueRequest,
+    worklog::parse_duration,
+    TrackerClient,
+};
+
+use crate::llm::extract_json_object;
+use crate::redact;
+
+/// Сколько задач бэклога по умолчанию показывать LLM
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Рабочих часов в день у участника, если они не указаны
+const DEFAULT_HOURS_PER_DAY: f64 = 6.0;
+
+/// Доля рабочего времени на задачи спринта, если она не указана
+const DEFAULT_FOCUS_FACTOR: f64 = 0.7;
+
+const SYSTEM_PROMPT: &str = "Ты помогаешь команде спланировать спринт. Выбери из бэклога \
+задачи, которые команда успеет сделать за спринт: сумма оценок не должна превышать ёмкость, \
+важные и блокирующие задачи — раньше, задачи без оценки бери только с объяснением. Ответь \
+только JSON объектом вида {\"issues\": [{\"key\": \"TREK-1\", \"reason\": \"...\"}], \
+\"rationale\": \"...\"} без пояснений.";
+
+/// Секция `sprint` конфигурации
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SprintConfig {
+    /// Участники команды; пусто — один пользователь с календарём
+    pub team: Vec<TeamMember>,
+
+    /// Доля рабочего времени на задачи спринта, от 0 до 1 (по умолчанию 0.7)
+    pub focus_factor: Option<f64>,
+}
+
+/// Участник команды
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamMember {
+    pub login: String,
+
+    /// Рабочих часов в день (по умолчанию 6)
+    pub hours_per_day: Option<f64>,
+
+    /// Нерабочие дни: отпуск, отгулы, праздники
+    #[serde(default)]
+    pub days_off: Vec<NaiveDate>,
+
+    /// Учитывать встречи и события на весь день из своего календаря
+    #[serde(default)]
+    pub calendar: bool,
+}
+
+/// Ёмкость участника на спринт
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberCapacity {
+    pub login: String,
+    /// Рабочих дней в спринте
+    pub days: usize,
+    /// Часов на задачи с учётом встреч и фокус-фактора
+    pub hours: f64,
+}
+
+/// Ёмкость участников на рабочие дни с `start` по `end` включительно
+pub fn capacity(
+    config: &SprintConfig,
+    events: &[Event],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<MemberCapacity> {
+    let focus = config
+        .focus_factor
+        .unwrap_or(DEFAULT_FOCUS_FACTOR)
+        .clamp(0.0, 1.0);
+    let me = [TeamMember {
+        login: "me".to_string(),
+        hours_per_day: None,
+        days_off: Vec::new(),
+        calendar: true,
+    }];
+    let team = if config.team.is_empty() {
+        &me[..]
+    } else {
+        &config.team[..]
+    };
+
+    team.iter()
+        .map(|member| {
+            let per_day = member.hours_per_day.unwrap_or(DEFAULT_HOURS_PER_DAY);
+            let mut days = 0;
+            let mut hours = 0.0;
+            for date in start.iter_days().take_while(|date| *date <= end) {
+                if matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+                    || member.days_off.contains(&date)
+                {
+                    continue;
+                }
+                let mut available = per_day;
+                if member.calendar {
+                    let day_events = calendar_lib::events_on(events, date);
+                    if day_events.iter().any(|event| event.all_day) {
+                        continue;
+                    }
+                    let meetings: f64 = day_events
+                        .iter()
+                        .map(|event| (event.end - event.start).num_minutes() as f64 / 60.0)
+                        .sum();
+                    available = (available - meetings).max(0.0);
+                }
+                days += 1;
+                hours += available;
+            }
+            MemberCapacity {
+                login: member.login.clone(),
+                days,
+                hours: hours * focus,
+            }
+        })
+        .collect()
+}
+
+/// Задача, предложенная в спринт
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedIssue {
+    pub key: String,
+    pub summary: String,
+    /// Оценка из Трекера в часах; None — задача без оценки
+    pub hours: Option<f64>,
+    /// Почему задача взята в спринт
+    pub reason: String,
+}
+
+/// Предложенный состав спринта (`--output json`, схема — `you schema llm-plan-sprint`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SprintPlan {
+    pub sprint: String,
+    pub capacity_hours: f64,
+    /// Сумма оценок предложенных задач
+    pub planned_hours: f64,
+    pub issues: Vec<PlannedIssue>,
+    pub rationale: String,
+}
+
+/// Ответ LLM
+#[derive(Debug, Deserialize)]
+struct Proposal {
+    issues: Vec<ProposedIssue>,
+    #[serde(default)]
+    rationale: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProposedIssue {
+    key: String,
+    #[serde(default)]
+    reason: String,
+}
+
+/// Оценка задачи в часах
+fn estimate_hours(issue: &Issue) -> Option<f64> {
+    let duration = parse_duration(issue.estimation.as_deref()?)?;
+    Some(duration.as_secs_f64() / 3600.0)
+}
+
+/// Строка задачи бэклога для промпта
+fn backlog_line(issue: &Issue) -> String {
+    let issue = redact::issue(issue);
+    let mut details = Vec::new();
+    if let Some(priority) = issue.priority.as_ref().and_then(|p| p.display.as_deref()) {
+        details.push(format!("приоритет {priority}"));
+    }
+    match estimate_hours(&issue) {
+        Some(hours) => details.push(format!("оценка {hours:.1} ч")),
+        None => details.push("без оценки".to_string()),
+    }
+    if let Some(points) = issue.story_points {
+        details.push(format!("{points} SP"));
+    }
+    format!("- {} {} ({})", issue.key, issue.summary, details.join(", "))
+}
+
+/// Просит LLM предложить состав спринта из задач бэклога
+#[instrument(skip(llm, sprint, backlog, capacity), fields(backlog = backlog.len()))]
+pub async fn propose<T: LlmClientTrait>(
+    llm: &T,
+    sprint: &str,
+    backlog: &[Issue],
+    capacity: &[MemberCapacity],
+) -> Result<SprintPlan> {
+    let capacity_hours: f64 = capacity.iter().map(|member| member.hours).sum();
+    let team = capacity
+        .iter()
+        .map(|member| {
+            format!(
+                "- {}: {} раб. дн., {:.1} ч",
+                member.login, member.days, member.hours
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let backlog_lines = backlog
+        .iter()
+        .map(backlog_line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = format!(
+        "Спринт: {sprint}\nЁмкость команды на задачи: {capacity_hours:.1} ч\n{team}\n\n\
+         Бэклог по порядку доски:\n{backlog_lines}\n\nПредложи состав спринта."
+    );
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(response = %response, "Ответ LLM получен");
+
+    let proposal = parse_proposal(&response)?;
+    Ok(plan(sprint, capacity_hours, backlog, proposal))
+}
+
+/// Извлекает JSON объект из ответа LLM, даже если он обёрнут в текст или блок кода
+fn parse_proposal(response: &str) -> Result<Proposal> {
+    serde_json::from_str(extract_json_object(response)?)
+        .context("Не удалось разобрать состав спринта из ответа LLM")
+}
+
+/// Сверяет предложение с бэклогом: чужие и повторные ключи отбрасываются
+fn plan(sprint: &str, capacity_hours: f64, backlog: &[Issue], proposal: Proposal) -> SprintPlan {
+    let mut issues: Vec<PlannedIssue> = Vec::new();
+    for proposed in proposal.issues {
+        if issues.iter().any(|planned| planned.key == proposed.key) {
+            continue;
+        }
+        let Some(issue) = backlog.iter().find(|issue| issue.key == proposed.key) else {
+            warn!(key = %proposed.key, "LLM предложила задачу не из бэклога");
+            continue;
+        };
+        issues.push(PlannedIssue {
+            key: issue.key.clone(),
+            summary: issue.summary.clone(),
+            hours: estimate_hours(issue),
+            reason: proposed.reason,
+        });
+    }
+    SprintPlan {
+        sprint: sprint.to_string(),
+        capacity_hours,
+        planned_hours: issues.iter().filter_map(|issue| issue.hours).sum(),
+        issues,
+        rationale: proposal.rationale,
+    }
+}
+
+/// Спринт для планирования: указанный или ближайший черновик доски
+pub async fn target_sprint(
+    client: &TrackerClient,
+    board: u64,
+    sprint_id: Option<u64>,
+) -> Result<SprintDetails> {
+    if let Some(sprint_id) = sprint_id {
+        return Ok(client.get_sprint(sprint_id).await?);
+    }
+    client
+        .get_board_sprints(board)
+        .await?
+        .into_iter()
+        .filter(|sprint| sprint.status.as_deref() == Some("draft"))
+        .min_by(|a, b| a.start_date.cmp(&b.start_date))
+        .with_context(|| format!("На доске {board} нет запланированного спринта; укажите --sprint"))
+}
+
+/// Даты начала и окончания спринта
+pub fn sprint_dates(sprint: &SprintDetails) -> Result<(NaiveDate, NaiveDate)> {
+    let date = |value: Option<&str>| {
+        value.and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+    };
+    match (
+        date(sprint.start_date.as_deref()),
+        date(sprint.end_date.as_deref()),
+    ) {
+        (Some(start), Some(end)) if start <= end => Ok((start, end)),
+        _ => bail!("У спринта не заданы даты начала и окончания"),
+    }
+}
+
+/// Добавляет задачи плана в спринт
+#[instrument(skip(client, plan), fields(count = plan.issues.len()))]
+pub async fn assign(client: &TrackerClient, sprint_id: u64, plan: &SprintPlan) -> Result<()> {
+    let request = UpdateIssueRequest::new().set("sprint", json!([{ "id": sprint_id.to_string() }]));
+    for planned in &plan.issues {
+        client
+            .update_issue(&planned.key.parse()?, &request, None)
+            .await?;
+        info!(key = %planned.key, "Задача добавлена в спринт");
+    }
+    Ok(())
+}
+
+/// Текстовый вывод плана
+pub fn render(plan: &SprintPlan) -> String {
+    let mut lines = vec![format!(
+        "Спринт {}: {:.1} ч из {:.1} ч ёмкости",
+        plan.sprint, plan.planned_hours, plan.capacity_hours
+    )];
+    for issue in &plan.issues {
+        let hours = issue
+            .hours
+            .map(|hours| format!("{hours:.1} ч"))
+            .unwrap_or_else(|| "без оценки".to_string());
+        lines.push(format!(
+            "  {} {} ({hours}) — {}",
+            issue.key, issue.summary, issue.reason
+        ));
+    }
+    if plan.planned_hours > plan.capacity_hours {
+        lines.push("Внимание: сумма оценок больше ёмкости команды".to_string());
+    }
+    if !plan.rationale.is_empty() {
+        lines.push(String::new());
+        lines.push(plan.rationale.clone());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+    use llm_lib::MockLlmClientTrait;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn event(start: &str, end: &str, all_day: bool) -> Event {
+        Event {
+            uid: None,
+            summary: "Встреча".to_string(),
+            description: None,
+            location: None,
+            url: None,
+            attendees: Vec::new(),
+            start: chrono::NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap(),
+            end: chrono::NaiveDateTime::parse_from_str(end, "%Y-%m-%d %H:%M").unwrap(),
+            all_day,
+        }
+    }
+
+    #[test]
+    fn test_capacity_skips_weekends_days_off_and_meetings() {
+        let config = SprintConfig {
+            team: vec![
+                TeamMember {
+                    login: "ivanov".to_string(),
+                    hours_per_day: Some(8.0),
+                    days_off: Vec::new(),
+                    calendar: true,
+                },
+                TeamMember {
+                    login: "petrov".to_string(),
+                    hours_per_day: None,
+                    days_off: vec![date("2026-10-20")],
+                    calendar: false,
+                },
+            ],
+            focus_factor: Some(0.5),
+        };
+        let events = [
+            event("2026-10-19 10:00", "2026-10-19 12:00", false),
+            event("2026-10-21 00:00", "2026-10-22 00:00", true),
+        ];
+
+        // Пн 19 — Вс 25 октября: пять рабочих дней
+        let capacity = capacity(&config, &events, date("2026-10-19"), date("2026-10-25"));
+
+        assert_eq!(capacity[0].login, "ivanov");
+        assert_eq!(capacity[0].days, 4);
+        assert_eq!(capacity[0].hours, (8.0 * 4.0 - 2.0) * 0.5);
+        assert_eq!(capacity[1].days, 4);
+        assert_eq!(capacity[1].hours, 6.0 * 4.0 * 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_propose_keeps_only_backlog_issues() {
+        let backlog = [
+            issue("TREK-1").field("estimation", "PT6H").build(),
+            issue("TREK-2").build(),
+        ];
+        let capacity = [MemberCapacity {
+            login: "me".to_string(),
+            days: 5,
+            hours: 21.0,
+        }];
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .times(1)
+            .returning(|_, prompt| {
+                assert!(prompt.contains("TREK-1 Задача TREK-1 (оценка 6.0 ч)"));
+                assert!(prompt.contains("TREK-2 Задача TREK-2 (без оценки)"));
+                Box::pin(async {
+                    Ok("```json\n{\"issues\": [\
+                        {\"key\": \"TREK-1\", \"reason\": \"блокирует релиз\"},\
+                        {\"key\": \"TREK-9\", \"reason\": \"выдумана\"},\
+                        {\"key\": \"TREK-1\", \"reason\": \"повтор\"},\
+                        {\"key\": \"TREK-2\", \"reason\": \"мелкая\"}],\
+                        \"rationale\": \"Запас на ревью\"}\n```"
+                        .to_string())
+                })
+            });
+
+        let plan = propose(&llm, "Спринт 42", &backlog, &capacity)
+            .await
+            .unwrap();
+
+        let keys: Vec<&str> = plan.issues.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(keys, ["TREK-1", "TREK-2"]);
+        assert_eq!(plan.issues[0].reason, "блокирует релиз");
+        assert_eq!(plan.planned_hours, 6.0);
+        assert_eq!(plan.capacity_hours, 21.0);
+        assert_eq!(plan.rationale, "Запас на ревью");
+    }
+
+    #[test]
+    fn test_sprint_dates_require_both_dates() {
+        let sprint: SprintDetails = serde_json::from_value(json!({
+            "id": 7,
+            "startDate": "2026-10-19",
+            "endDate": "2026-10-30"
+        }))
+        .unwrap();
+        assert_eq!(
+            sprint_dates(&sprint).unwrap(),
+            (date("2026-10-19"), date("2026-10-30"))
+        );
+
+        let draft: SprintDetails = serde_json::from_value(json!({"id": 8})).unwrap();
+        assert!(sprint_dates(&draft).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+//! Поиск зависших задач и напоминания по ним
+//!
+//! `you tracker stale --queue TREK --days 14` находит нерешённые задачи в активных
+//! статусах, по которым N дней не было работы. Дата обновления задачи сдвигается и от
+//! служебных изменений (наблюдатели, роботы), поэтому для недавно обновлённых задач
+//! смотрятся история и комментарии: учитываются только изменения людей по
+//! содержательным полям. По найденным задачам можно призвать исполнителя
+//! комментарием-напоминанием, которое пишет LLM, или вернуть задачу в бэклог.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use llm_lib::LlmClientTrait;
+use tracing::{debug, info, instrument};
+use tracker_lib::{
+    models::{ChangelogEntry, Comment, Issue, User, TRACKER_DATE_FORMAT},
+    TrackerClient,
+};
+
+use crate::redact;
+use crate::summarize;
+
+/// Статусы, в которых задача должна двигаться
+pub const DEFAULT_ACTIVE_STATUSES: &[&str] = &["inProgress", "needInfo", "inReview", "testing"];
+
+/// Сколько задач очереди проверять по умолчанию
+pub const DEFAULT_LIMIT: usize = 200;
+
+/// Поля, изменения которых не означают работу над задачей
+const NOISE_FIELDS: &[&str] = &[
+    "followers",
+    "favorite",
+    "votes",
+    "access",
+    "pendingReplyFrom",
+];
+
+/// Префикс логинов роботов
+const ROBOT_PREFIX: &str = "robot-";
+
+/// Сколько символов последнего комментария показывать LLM
+const LAST_COMMENT_CHARS: usize = 500;
+
+const NUDGE_PROMPT: &str = "Напиши короткое вежливое напоминание исполнителю задачи, по \
+которой давно нет движения: спроси о текущем состоянии, блокерах и сроках. Одно–три \
+предложения без приветствия и подписи, ответь только текстом комментария.";
+
+/// Задача без работы дольше порога
+#[derive(Debug, Clone)]
+pub struct StaleIssue {
+    pub issue: Issue,
+    /// Последнее содержательное изменение или комментарий
+    pub last_activity: DateTime<FixedOffset>,
+}
+
+impl StaleIssue {
+    /// Сколько полных дней задача без работы
+    pub fn idle_days(&self, now: DateTime<FixedOffset>) -> i64 {
+        (now - self.last_activity).num_days()
+    }
+
+    /// Кого призвать в напоминание: исполнителя, а без него автора
+    pub fn summonee(&self) -> Option<&str> {
+        self.issue
+            .assignee
+            .as_ref()
+            .or(self.issue.created_by.as_ref())
+            .and_then(|user| user.login.as_deref())
+    }
+
+    pub fn line(&self, now: DateTime<FixedOffset>) -> String {
+        let status = self
+            .issue
+            .status
+            .as_ref()
+            .and_then(|status| status.display.as_deref())
+            .unwrap_or("—");
+        let assignee = self
+            .issue
+            .assignee
+            .as_ref()
+            .and_then(|user| user.login.as_deref())
+            .unwrap_or("без исполнителя");
+        format!(
+            "{} [{status}] {assignee}, {} дн. без работы — {}",
+            self.issue.key,
+            self.idle_days(now),
+            self.issue.summary
+        )
+    }
+}
+
+/// Запрос нерешённых задач очереди в активных статусах, давно обновлённые первыми
+pub fn stale_query(queue: &str, statuses: &[String]) -> String {
+    format!(
+        "Queue: {queue} Resolution: empty() Status: {} \"Sort by\": Updated ASC",
+        statuses.join(", ")
+    )
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, TRACKER_DATE_FORMAT).ok()
+}
+
+fn is_robot(user: Option<&User>) -> bool {
+    user.and_then(|user| user.login.as_deref())
+        .is_some_and(|login| login.starts_with(ROBOT_PREFIX))
+}
+
+/// Последняя работа над задачей: изменения людей по содержательным полям и их комментарии
+///
+/// Если таких событий нет, работой считается создание задачи.
+pub fn last_activity(
+    issue: &Issue,
+    changelog: &[ChangelogEntry],
+    comments: &[Comment],
+) -> Option<DateTime<FixedOffset>> {
+    let changes = changelog
+        .iter()
+        .filter(|entry| !is_robot(entry.updated_by.as_ref()))
+        .filter(|entry| {
+            entry.fields.iter().any(|change| {
+                change
+                    .field
+                    .id
+                    .as_deref()
+                    .is_some_and(|field| !NOISE_FIELDS.contains(&field))
+            })
+        })
+        .filter_map(|entry| entry.updated_at.as_deref());
+    let comments = comments
+        .iter()
+        .filter(|comment| !is_robot(comment.created_by.as_ref()))
+        .filter_map(|comment| comment.created_at.as_deref());
+    changes
+        .chain(comments)
+        .chain(issue.created_at.as_deref())
+        .filter_map(parse_time)
+        .max()
+}
+
+/// Находит зависшие задачи очереди
+///
+/// Задачи, обновлённые раньше порога, зависли без проверки истории; у остальных
+/// последняя работа определяется по истории и комментариям.
+#[instrument(skip(client, statuses))]
+pub async fn find(
+    client: &TrackerClient,
+    queue: &str,
+    statuses: &[String],
+    days: i64,
+    limit: usize,
+) -> Result<Vec<StaleIssue>> {
+    let cutoff = Utc::now().fixed_offset() - Duration::days(days);
+    let issues = summarize::fetch_issues(client, &stale_query(queue, statuses), limit).await?;
+
+    let mut stale = Vec::new();
+    for issue in issues {
+        let updated = issue.updated_at.as_deref().and_then(parse_time);
+        let last = match updated {
+            Some(updated) if updated < cutoff => Some(updated),
+            _ => {
+                let changelog = client.get_changelog(&issue.issue_key()?, None).await?;
+                let comments = client.get_comments(&issue.issue_key()?).await?;
+                last_activity(&issue, &changelog, &comments)
+            }
+        };
+        let Some(last_activity) = last.filter(|last| *last < cutoff) else {
+            continue;
+        };
+        debug!(issue_key = %issue.key, %last_activity, "Задача зависла");
+        stale.push(StaleIssue {
+            issue,
+            last_activity,
+        });
+    }
+    stale.sort_by_key(|stale| stale.last_activity);
+    info!(count = stale.len(), "Зависшие задачи найдены");
+    Ok(stale)
+}
+
+/// Текст напоминания по задаче от LLM с учётом последнего комментария
+#[instrument(skip_all, fields(issue_key = %stale.issue.key))]
+pub async fn draft_nudge<T: LlmClientTrait>(
+    llm: &T,
+    stale: &StaleIssue,
+    last_comment: Option<&Comment>,
+) -> Result<String> {
+    let issue = redact::issue(&stale.issue);
+    let status = issue
+        .status
+        .as_ref()
+        .and_then(|status| status.display.as_deref())
+        .unwrap_or("—");
+    let mut prompt = format!(
+        "Задача {}: {}\nСтатус: {status}\nБез работы: {} дн.",
+        issue.key,
+        issue.summary,
+        stale.idle_days(Utc::now().fixed_offset()),
+    );
+    if let Some(comment) = last_comment {
+        let text: String = redact::text(&issue.key, &comment.text)
+            .chars()
+            .take(LAST_COMMENT_CHARS)
+            .collect();
+        prompt.push_str(&format!("\nПоследний комментарий:\n{text}"));
+    }
+    let nudge = llm
+        .complete_with_system(NUDGE_PROMPT.to_string(), prompt)
+        .await?;
+    Ok(nudge.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+    use llm_lib::MockLlmClientTrait;
+    use serde_json::json;
+    use tracker_lib::TrackerConfig;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn ago(days: i64) -> String {
+        (Utc::now() - Duration::days(days))
+            .format(TRACKER_DATE_FORMAT)
+            .to_string()
+    }
+
+    #[test]
+    fn test_last_activity_ignores_robots_and_noise() {
+        let issue = issue("TREK-1")
+            .summary("Миграция")
+            .field("createdAt", "2026-09-01T10:00:00.000+0000")
+            .build();
+        let changelog: Vec<ChangelogEntry> = serde_json::from_value(json!([
+            {
+                "updatedAt": "2026-09-10T10:00:00.000+0000",
+                "updatedBy": {"login": "ivanov"},
+                "fields": [{"field": {"id": "status"}}]
+            },
+            {
+                "updatedAt": "2026-10-10T10:00:00.000+0000",
+                "updatedBy": {"login": "petrov"},
+                "fields": [{"field": {"id": "followers"}}]
+            },
+            {
+                "updatedAt": "2026-10-12T10:00:00.000+0000",
+                "updatedBy": {"login": "robot-sync"},
+                "fields": [{"field": {"id": "tags"}}]
+            }
+        ]))
+        .unwrap();
+        let comments: Vec<Comment> = serde_json::from_value(json!([
+            {
+                "text": "Посмотрю",
+                "createdBy": {"login": "ivanov"},
+                "createdAt": "2026-09-15T10:00:00.000+0000"
+            },
+            {
+                "text": "Напоминание",
+                "createdBy": {"login": "robot-stale"},
+                "createdAt": "2026-10-14T10:00:00.000+0000"
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            last_activity(&issue, &changelog, &comments),
+            parse_time("2026-09-15T10:00:00.000+0000")
+        );
+        assert_eq!(
+            last_activity(&issue, &[], &[]),
+            parse_time("2026-09-01T10:00:00.000+0000")
+        );
+    }
+
+    #[test]
+    fn test_stale_query() {
+        let statuses = vec!["inProgress".to_string(), "inReview".to_string()];
+        assert_eq!(
+            stale_query("TREK", &statuses),
+            "Queue: TREK Resolution: empty() Status: inProgress, inReview \"Sort by\": Updated ASC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_checks_history_of_recent_updates() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"key": "TREK-1", "summary": "Старая", "updatedAt": ago(30)},
+                {"key": "TREK-2", "summary": "Тронута роботом", "updatedAt": ago(1), "createdAt": ago(40)},
+                {"key": "TREK-3", "summary": "В работе", "updatedAt": ago(1), "createdAt": ago(40)}
+            ])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-1/changelog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-2/changelog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "updatedAt": ago(1),
+                "updatedBy": {"login": "robot-sync"},
+                "fields": [{"field": {"id": "tags"}}]
+            }])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-3/changelog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "updatedAt": ago(1),
+                "updatedBy": {"login": "ivanov"},
+                "fields": [{"field": {"id": "description"}}]
+            }])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-2/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-3/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&mock_server)
+            .await;
+        let client = TrackerClient::new(
+            TrackerConfig::new("test-oauth-token").with_base_url(mock_server.uri()),
+        )
+        .unwrap();
+
+        let stale = find(&client, "TREK", &["inProgress".to_string()], 14, 10)
+            .await
+            .unwrap();
+
+        let keys: Vec<&str> = stale.iter().map(|stale| stale.issue.key.as_str()).collect();
+        assert_eq!(keys, ["TREK-2", "TREK-1"]);
+        assert_eq!(stale[0].idle_days(Utc::now().fixed_offset()), 40);
+    }
+
+    #[tokio::test]
+    async fn test_draft_nudge_uses_last_comment() {
+        let stale = StaleIssue {
+            issue: issue("TREK-1").summary("Миграция").build(),
+            last_activity: Utc::now().fixed_offset() - Duration::days(20),
+        };
+        let comment: Comment =
+            serde_json::from_value(json!({"text": "Жду доступы от админов"})).unwrap();
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .times(1)
+            .returning(|_, prompt| {
+                assert!(prompt.contains("Без работы: 20 дн."));
+                assert!(prompt.contains("Жду доступы от админов"));
+                Box::pin(async { Ok("  Доступы уже выдали?  ".to_string()) })
+            });
+
+        let nudge = draft_nudge(&llm, &stale, Some(&comment)).await.unwrap();
+
+        assert_eq!(nudge, "Доступы уже выдали?");
+    }
+}
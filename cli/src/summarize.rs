@@ -0,0 +1,252 @@
+//! Сводка по результатам поиска задач с помощью LLM
+//!
+//! `you llm summarize-search --query "..."` забирает найденные задачи и раскладывает их
+//! на части, помещающиеся в контекст модели. Части пересказываются одним пакетным
+//! запросом, а если частей несколько, частичные сводки объединяются ещё одним запросом
+//! в общую: темы, риски и ответственные по всей выборке.
+
+use anyhow::{bail, Result};
+use llm_lib::{context::CHARS_PER_TOKEN, LlmClientTrait};
+use tracing::{info, instrument};
+use tracker_lib::{
+    models::Issue,
+    search::{SearchParams, SearchRequest},
+    TrackerClient,
+};
+
+use crate::redact;
+
+/// Размер части по умолчанию, в токенах
+pub const DEFAULT_CHUNK_TOKENS: usize = 12_000;
+
+/// Сколько символов описания задачи попадает в промпт
+const DESCRIPTION_LIMIT: usize = 400;
+
+const SEARCH_PAGE_SIZE: u32 = 50;
+
+/// Системный промпт пересказа одной части выборки
+pub const CHUNK_PROMPT: &str =
+    "Ты анализируешь выборку задач из трекера. Сгруппируй задачи по темам \
+и ответь в Markdown тремя разделами: «Темы» (тема, ключи задач и одно предложение о сути), \
+«Риски» (блокеры, критичные и зависшие задачи) и «Ответственные» (кто над какими темами работает). \
+Не придумывай задач, которых нет в списке.";
+
+const MERGE_PROMPT: &str = "Ниже частичные сводки по одной выборке задач из трекера. Объедини их \
+в одну сводку в Markdown с разделами «Темы», «Риски» и «Ответственные»: склей одинаковые темы, \
+сохрани ключи задач и не добавляй ничего от себя.";
+
+/// Найти задачи по запросу, не больше `limit`
+#[instrument(skip(client))]
+pub async fn fetch_issues(client: &TrackerClient, query: &str, limit: usize) -> Result<Vec<Issue>> {
+    let request = SearchRequest {
+        query: Some(query.to_string()),
+        ..Default::default()
+    };
+    let issues = search_pages(client, &request, limit).await?;
+    info!(count = issues.len(), "Задачи для сводки получены");
+    Ok(issues)
+}
+
+/// Задачи с указанными ключами; удалённые и недоступные задачи просто не попадают в ответ
+#[instrument(skip(client), fields(count = keys.len()))]
+pub async fn fetch_keys(client: &TrackerClient, keys: Vec<String>) -> Result<Vec<Issue>> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = keys.len();
+    let request = SearchRequest {
+        keys: Some(keys),
+        ..Default::default()
+    };
+    search_pages(client, &request, limit).await
+}
+
+async fn search_pages(
+    client: &TrackerClient,
+    request: &SearchRequest,
+    limit: usize,
+) -> Result<Vec<Issue>> {
+    let mut issues = Vec::new();
+    for page in 1.. {
+        let params = SearchParams {
+            per_page: Some(SEARCH_PAGE_SIZE),
+            page: Some(page),
+            ..Default::default()
+        };
+        let batch = client.search_issues(request, Some(params)).await?;
+        let last = batch.len() < SEARCH_PAGE_SIZE as usize;
+        issues.extend(batch);
+        if last || issues.len() >= limit {
+            break;
+        }
+    }
+    issues.truncate(limit);
+    Ok(issues)
+}
+
+/// Строка задачи для промпта: ключ, статус, приоритет, исполнитель и начало описания
+pub fn issue_line(issue: &Issue) -> String {
+    let status = issue
+        .status
+        .as_ref()
+        .and_then(|s| s.display.as_deref())
+        .unwrap_or("без статуса");
+    let priority = issue
+        .priority
+        .as_ref()
+        .and_then(|p| p.display.as_deref())
+        .unwrap_or("без приоритета");
+    let assignee = issue
+        .assignee
+        .as_ref()
+        .and_then(|u| u.display.as_deref().or(u.login.as_deref()))
+        .unwrap_or("не назначена");
+
+    let mut line = format!(
+        "{} [{status}, {priority}, {assignee}] {}",
+        issue.key, issue.summary
+    );
+    if let Some(description) = issue.description.as_deref().map(str::trim) {
+        if !description.is_empty() {
+            let short: String = description.chars().take(DESCRIPTION_LIMIT).collect();
+            line.push_str(" — ");
+            line.push_str(&short.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+    line
+}
+
+/// Разложить строки на части не длиннее `max_chars` символов
+///
+/// Строка длиннее лимита попадает в отдельную часть целиком.
+pub fn chunk_lines(lines: &[String], max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        let len = current.chars().count();
+        if !current.is_empty() && len + 1 + line.chars().count() > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Тематическая сводка по задачам: пакетный пересказ частей и их объединение
+#[instrument(skip(llm, issues), fields(count = issues.len()))]
+pub async fn summarize<T: LlmClientTrait>(
+    llm: &T,
+    query: &str,
+    issues: &[Issue],
+    chunk_tokens: usize,
+) -> Result<String> {
+    if issues.is_empty() {
+        bail!("По запросу «{query}» задачи не найдены");
+    }
+
+    let lines: Vec<String> = issues
+        .iter()
+        .map(|issue| issue_line(&redact::issue(issue)))
+        .collect();
+    let prompts: Vec<String> = chunk_lines(&lines, chunk_tokens * CHARS_PER_TOKEN)
+        .into_iter()
+        .map(|chunk| format!("Запрос: {query}\n\nЗадачи:\n{chunk}"))
+        .collect();
+    info!(chunks = prompts.len(), "Задачи разложены на части");
+
+    let mut summaries = llm
+        .complete_batch(CHUNK_PROMPT.to_string(), prompts)
+        .await?;
+    if summaries.len() == 1 {
+        return Ok(summaries.remove(0));
+    }
+
+    let merged = summaries
+        .iter()
+        .enumerate()
+        .map(|(i, summary)| format!("## Часть {}\n\n{summary}", i + 1))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(llm
+        .complete_with_system(MERGE_PROMPT.to_string(), merged)
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use llm_lib::MockLlmClientTrait;
+
+    fn issue(key: &str, summary: &str) -> Issue {
+        testing::issue(key)
+            .summary(summary)
+            .status("open", "Открыта")
+            .assignee("ann", "Анна")
+            .description("Падает   при\nзагрузке")
+            .build()
+    }
+
+    #[test]
+    fn test_issue_line() {
+        assert_eq!(
+            issue_line(&issue("TREK-1", "Экспорт")),
+            "TREK-1 [Открыта, без приоритета, Анна] Экспорт — Падает при загрузке"
+        );
+    }
+
+    #[test]
+    fn test_chunk_lines_respects_limit() {
+        let lines = vec![
+            "aaaa".to_string(),
+            "bbbb".to_string(),
+            "cccccccccc".to_string(),
+        ];
+
+        assert_eq!(chunk_lines(&lines, 9), ["aaaa\nbbbb", "cccccccccc"]);
+        assert_eq!(chunk_lines(&lines, 100), ["aaaa\nbbbb\ncccccccccc"]);
+        assert!(chunk_lines(&[], 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_summarize_merges_chunk_summaries() {
+        let issues = vec![issue("TREK-1", "Экспорт"), issue("TREK-2", "Импорт")];
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_complete_batch()
+            .withf(|_, prompts| prompts.len() == 2 && prompts[1].contains("TREK-2"))
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async { Ok(vec!["тема A".into(), "тема B".into()]) })
+            });
+        llm.expect_complete_with_system()
+            .withf(|_, merged| merged.contains("## Часть 2\n\nтема B"))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok("итог".to_string()) }));
+
+        // Лимит в 10 токенов вмещает только одну задачу в часть
+        let summary = summarize(&llm, "Queue: TREK", &issues, 10).await.unwrap();
+        assert_eq!(summary, "итог");
+    }
+
+    #[tokio::test]
+    async fn test_summarize_single_chunk_skips_merge() {
+        let mut llm = MockLlmClientTrait::new();
+        llm.expect_complete_batch()
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(vec!["сводка".into()]) }));
+        llm.expect_complete_with_system().never();
+
+        let issues = [issue("TREK-1", "Экспорт")];
+        let summary = summarize(&llm, "Queue: TREK", &issues, DEFAULT_CHUNK_TOKENS)
+            .await
+            .unwrap();
+        assert_eq!(summary, "сводка");
+        assert!(summarize(&llm, "Queue: TREK", &[], 10).await.is_err());
+    }
+}
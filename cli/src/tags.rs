@@ -0,0 +1,251 @@
+//! Массовое изменение тегов задач
+//!
+//! `you tracker tags --query "..." --add needs-review --remove triage` находит задачи,
+//! показывает, как изменятся их теги, и отправляет одно массовое изменение Трекера
+//! только для задач, теги которых действительно поменяются. После завершения операции
+//! задачи перечитываются, и для каждой сообщается, применено ли изменение.
+
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use tracing::{info, instrument};
+use tracker_lib::{bulkchange::BulkUpdateRequest, models::Issue, TrackerClient};
+
+use crate::summarize;
+
+/// Сколько задач запроса изменять по умолчанию
+pub const DEFAULT_LIMIT: usize = 500;
+
+/// Сколько ждать завершения массового изменения
+const BULK_CHANGE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Добавляемые и удаляемые теги
+#[derive(Debug, Clone, Default)]
+pub struct TagChange {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagChange {
+    /// Теги задачи после изменения; порядок существующих тегов сохраняется
+    pub fn apply(&self, tags: &[String]) -> Vec<String> {
+        let mut result: Vec<String> = tags
+            .iter()
+            .filter(|tag| !self.remove.contains(tag))
+            .cloned()
+            .collect();
+        for tag in &self.add {
+            if !result.contains(tag) {
+                result.push(tag.clone());
+            }
+        }
+        result
+    }
+
+    /// Значения полей для массового изменения
+    pub fn values(&self) -> serde_json::Value {
+        let mut tags = serde_json::Map::new();
+        if !self.add.is_empty() {
+            tags.insert("add".to_string(), self.add.clone().into());
+        }
+        if !self.remove.is_empty() {
+            tags.insert("remove".to_string(), self.remove.clone().into());
+        }
+        serde_json::json!({ "tags": tags })
+    }
+}
+
+/// Теги одной задачи до и после изменения
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagPlan {
+    pub key: String,
+    pub summary: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+impl TagPlan {
+    pub fn changes(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+impl fmt::Display for TagPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tags = |tags: &[String]| {
+            if tags.is_empty() {
+                "—".to_string()
+            } else {
+                tags.join(", ")
+            }
+        };
+        write!(f, "{}  {}: {}", self.key, self.summary, tags(&self.before))?;
+        if self.changes() {
+            write!(f, " → {}", tags(&self.after))?;
+        } else {
+            write!(f, " (без изменений)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Как изменятся теги каждой задачи
+pub fn plan(issues: &[Issue], change: &TagChange) -> Vec<TagPlan> {
+    issues
+        .iter()
+        .map(|issue| TagPlan {
+            key: issue.key.clone(),
+            summary: issue.summary.clone(),
+            before: issue.tags.clone(),
+            after: change.apply(&issue.tags),
+        })
+        .collect()
+}
+
+/// Итог изменения одной задачи
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagOutcome {
+    Applied,
+    /// Теги задачи после операции не совпали с ожидаемыми
+    NotApplied {
+        actual: Vec<String>,
+    },
+    /// Задача не найдена при повторном чтении
+    Missing,
+}
+
+/// Сверяет теги перечитанных задач с планом
+pub fn verify<'a>(plans: &'a [TagPlan], issues: &[Issue]) -> Vec<(&'a TagPlan, TagOutcome)> {
+    plans
+        .iter()
+        .map(|plan| {
+            let outcome = match issues.iter().find(|issue| issue.key == plan.key) {
+                None => TagOutcome::Missing,
+                Some(issue) => {
+                    // Теги, которые за это время поменяли другие, не считаются ошибкой
+                    let mut added = plan.after.iter().filter(|tag| !plan.before.contains(tag));
+                    let mut removed = plan.before.iter().filter(|tag| !plan.after.contains(tag));
+                    let applied = added.all(|tag| issue.tags.contains(tag))
+                        && !removed.any(|tag| issue.tags.contains(tag));
+                    if applied {
+                        TagOutcome::Applied
+                    } else {
+                        TagOutcome::NotApplied {
+                            actual: issue.tags.clone(),
+                        }
+                    }
+                }
+            };
+            (plan, outcome)
+        })
+        .collect()
+}
+
+/// Запускает массовое изменение тегов и сверяет результат по каждой задаче
+#[instrument(skip(client, plans), fields(count = plans.len()))]
+pub async fn apply<'a>(
+    client: &TrackerClient,
+    plans: &'a [TagPlan],
+    change: &TagChange,
+) -> Result<Vec<(&'a TagPlan, TagOutcome)>> {
+    let keys: Vec<String> = plans.iter().map(|plan| plan.key.clone()).collect();
+    let started = client
+        .bulk_update(&BulkUpdateRequest::new(keys.clone(), change.values()))
+        .await?;
+    let finished = client
+        .wait_bulk_change(&started.id, BULK_CHANGE_TIMEOUT)
+        .await?;
+    if !finished.is_finished() {
+        bail!(
+            "Массовое изменение {} не завершилось за {} с; проверьте его позже в Трекере",
+            finished.id,
+            BULK_CHANGE_TIMEOUT.as_secs()
+        );
+    }
+    info!(status = ?finished.status, "Массовое изменение тегов завершено");
+
+    let issues = summarize::fetch_keys(client, keys).await?;
+    Ok(verify(plans, &issues))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+
+    fn change() -> TagChange {
+        TagChange {
+            add: vec!["needs-review".to_string()],
+            remove: vec!["triage".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_plan_keeps_order_and_skips_unchanged() {
+        let issues = [
+            issue("TREK-1").tags(&["triage", "backend"]).build(),
+            issue("TREK-2").tags(&["needs-review"]).build(),
+        ];
+        let plans = plan(&issues, &change());
+
+        assert_eq!(plans[0].after, ["backend", "needs-review"]);
+        assert!(plans[0].changes());
+        assert!(!plans[1].changes());
+        assert_eq!(
+            plans[0].to_string(),
+            "TREK-1  Задача TREK-1: triage, backend → backend, needs-review"
+        );
+        assert_eq!(
+            plans[1].to_string(),
+            "TREK-2  Задача TREK-2: needs-review (без изменений)"
+        );
+    }
+
+    #[test]
+    fn test_values_include_only_requested_operations() {
+        let only_add = TagChange {
+            add: vec!["a".to_string()],
+            remove: Vec::new(),
+        };
+        assert_eq!(
+            only_add.values(),
+            serde_json::json!({"tags": {"add": ["a"]}})
+        );
+        assert_eq!(
+            change().values(),
+            serde_json::json!({"tags": {"add": ["needs-review"], "remove": ["triage"]}})
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_each_issue() {
+        let plans = plan(
+            &[
+                issue("TREK-1").tags(&["triage"]).build(),
+                issue("TREK-2").tags(&[]).build(),
+                issue("TREK-3").tags(&[]).build(),
+            ],
+            &change(),
+        );
+        let after = [
+            issue("TREK-1").tags(&["needs-review"]).build(),
+            issue("TREK-2").tags(&["triage"]).build(),
+        ];
+
+        let outcomes: Vec<TagOutcome> = verify(&plans, &after)
+            .into_iter()
+            .map(|(_, outcome)| outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                TagOutcome::Applied,
+                TagOutcome::NotApplied {
+                    actual: vec!["triage".to_string()]
+                },
+                TagOutcome::Missing
+            ]
+        );
+    }
+}
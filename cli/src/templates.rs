@@ -0,0 +1,108 @@
+//! Шаблоны вывода задач: `--template short|full|файл.tmpl`
+//!
+//! Встроенные шаблоны `full` и `short` переопределяются файлами `full.tmpl` и
+//! `short.tmpl` в каталоге `templates` рядом с конфигурацией; там же можно держать свои
+//! шаблоны и выбирать их по имени. Переменные шаблона перечислены в
+//! [`tracker_lib::template::VARIABLES`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use tracing::debug;
+use tracker_lib::template::{IssueTemplate, VARIABLES};
+
+use crate::config::Config;
+
+/// Шаблон `you tracker issue` по умолчанию
+pub const FULL: &str = "full";
+
+/// Шаблон списков задач по умолчанию
+pub const SHORT: &str = "short";
+
+const EXTENSION: &str = "tmpl";
+
+/// Каталог пользовательских шаблонов
+fn templates_dir() -> Option<PathBuf> {
+    Config::dir().map(|dir| dir.join("templates"))
+}
+
+/// Шаблон по имени или пути к файлу
+pub fn load(spec: &str) -> Result<IssueTemplate> {
+    resolve(spec, templates_dir().as_deref())
+}
+
+/// Путь к файлу (`*.tmpl` или с `/`) читается как есть, относительный ищется и в `dir`;
+/// имя — сначала `dir/<имя>.tmpl`, затем встроенный шаблон
+fn resolve(spec: &str, dir: Option<&Path>) -> Result<IssueTemplate> {
+    let is_path = spec.ends_with(&format!(".{EXTENSION}")) || spec.contains('/');
+    let template = if is_path {
+        let path = PathBuf::from(spec);
+        let path = match dir {
+            Some(dir) if path.is_relative() && !path.exists() => dir.join(path),
+            _ => path,
+        };
+        read(&path)?
+    } else {
+        let custom = dir
+            .map(|dir| dir.join(format!("{spec}.{EXTENSION}")))
+            .filter(|path| path.exists());
+        match (custom, IssueTemplate::builtin(spec)) {
+            (Some(path), _) => read(&path)?,
+            (None, Some(builtin)) => builtin,
+            (None, None) => bail!(
+                "Шаблон «{spec}» не найден: встроенные — {FULL} и {SHORT}, свои шаблоны \
+                 кладите в {}",
+                dir.map(|dir| format!("{}/<имя>.{EXTENSION}", dir.display()))
+                    .unwrap_or_else(|| "каталог templates рядом с конфигурацией".to_string())
+            ),
+        }
+    };
+
+    let unknown = template.unknown_variables();
+    if !unknown.is_empty() {
+        bail!(
+            "В шаблоне «{spec}» неизвестные переменные: {}; доступны: {}",
+            unknown.join(", "),
+            VARIABLES.join(", ")
+        );
+    }
+    Ok(template)
+}
+
+fn read(path: &Path) -> Result<IssueTemplate> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Не удалось прочитать шаблон {}", path.display()))?;
+    debug!(path = %path.display(), "Шаблон вывода загружен");
+    Ok(IssueTemplate::new(source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_user_templates() {
+        let dir = std::env::temp_dir().join(format!("you-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("short.tmpl"), "{key}: {summary}").unwrap();
+        std::fs::write(dir.join("mine.tmpl"), "{key} {assignee}").unwrap();
+        std::fs::write(dir.join("typo.tmpl"), "{key} {asignee}").unwrap();
+
+        let short = resolve(SHORT, Some(&dir)).unwrap();
+        assert_eq!(short, IssueTemplate::new("{key}: {summary}"));
+        assert_eq!(
+            resolve(FULL, Some(&dir)).unwrap(),
+            IssueTemplate::builtin(FULL).unwrap()
+        );
+        assert_eq!(
+            resolve("mine.tmpl", Some(&dir)).unwrap(),
+            IssueTemplate::new("{key} {assignee}")
+        );
+
+        let err = resolve("typo", Some(&dir)).unwrap_err().to_string();
+        assert!(err.contains("неизвестные переменные: asignee"));
+        assert!(resolve("missing", Some(&dir)).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
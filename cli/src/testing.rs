@@ -0,0 +1,59 @@
+//! Заготовки для модульных тестов
+//!
+//! Задачи Трекера в тестах собираются через [`issue`], а не копией JSON в каждом модуле.
+
+use serde_json::{json, Map, Value};
+use tracker_lib::models::Issue;
+
+/// Задача с ключом `key` и названием «Задача {key}»
+pub fn issue(key: &str) -> IssueBuilder {
+    let mut fields = Map::new();
+    fields.insert("key".to_string(), json!(key));
+    fields.insert("summary".to_string(), json!(format!("Задача {key}")));
+    IssueBuilder { fields }
+}
+
+/// Построитель задачи: поля задаются в формате API Трекера
+pub struct IssueBuilder {
+    fields: Map<String, Value>,
+}
+
+impl IssueBuilder {
+    pub fn summary(self, summary: &str) -> Self {
+        self.field("summary", summary)
+    }
+
+    pub fn description(self, description: &str) -> Self {
+        self.field("description", description)
+    }
+
+    pub fn status(self, key: &str, display: &str) -> Self {
+        self.field("status", json!({"key": key, "display": display}))
+    }
+
+    pub fn priority(self, key: &str) -> Self {
+        self.field("priority", json!({"key": key, "display": key}))
+    }
+
+    pub fn issue_type(self, key: &str, display: &str) -> Self {
+        self.field("type", json!({"key": key, "display": display}))
+    }
+
+    pub fn assignee(self, login: &str, display: &str) -> Self {
+        self.field("assignee", json!({"login": login, "display": display}))
+    }
+
+    pub fn tags(self, tags: &[&str]) -> Self {
+        self.field("tags", tags)
+    }
+
+    /// Любое другое поле: `deadline`, `estimation`, `createdAt`…
+    pub fn field(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.fields.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Issue {
+        serde_json::from_value(Value::Object(self.fields)).unwrap()
+    }
+}
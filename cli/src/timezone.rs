@@ -0,0 +1,118 @@
+//! Часовой пояс пользователя для календаря, плана дня и дедлайнов
+//!
+//! Пояс берётся из `--tz`, затем из ключа `timezone` конфигурации (имя IANA, например
+//! `Asia/Novosibirsk`, или `local`), иначе используется системный. В этом поясе
+//! загружается календарь, считается «сегодня» и показываются сроки задач, поэтому
+//! удалённый сотрудник видит расписание в своём времени, даже если ноутбук или сервер
+//! настроен на другой пояс.
+
+use calendar_lib::Zone;
+use chrono::{DateTime, Local, Utc};
+use tracing::warn;
+use tracker_lib::models::Issue;
+
+use crate::{config::Config, settings};
+
+/// Переменная окружения с поясом; через неё `--tz` передаётся хукам и скриптам
+pub const TZ_VAR: &str = "YOU_TZ";
+
+/// Часовой пояс текущей команды
+///
+/// Неизвестное имя в конфигурации не мешает работе: используется системный пояс.
+pub fn current() -> Zone {
+    if let Some(zone) = settings::get().timezone {
+        return zone;
+    }
+    let configured = std::env::var(TZ_VAR)
+        .ok()
+        .or_else(|| Config::load().ok().and_then(|config| config.timezone));
+    let Some(name) = configured else {
+        return Zone::Local;
+    };
+    name.parse().unwrap_or_else(|err| {
+        warn!(error = %err, "Используется системный часовой пояс");
+        Zone::Local
+    })
+}
+
+/// Момент дедлайна задачи: конец дня дедлайна в поясе `zone`
+pub fn deadline(issue: &Issue, zone: Zone) -> Option<DateTime<Utc>> {
+    let deadline = match zone {
+        Zone::Local => issue.deadline_at(&Local)?.with_timezone(&Utc),
+        Zone::Named(tz) => issue.deadline_at(&tz)?.with_timezone(&Utc),
+    };
+    Some(deadline)
+}
+
+/// Срок задачи для человека и LLM: дата и сколько осталось на момент `now`
+pub fn deadline_note(issue: &Issue, zone: Zone, now: DateTime<Utc>) -> Option<String> {
+    let date = issue.deadline_date()?;
+    let left = deadline(issue, zone)? - now;
+    let note = if left.num_seconds() < 0 {
+        format!("просрочен на {} дн.", (-left).num_days() + 1)
+    } else if left.num_hours() < 24 {
+        format!("осталось {} ч", left.num_hours())
+    } else {
+        format!("осталось {} дн.", left.num_days())
+    };
+    Some(format!("{} ({note})", date.format("%d.%m.%Y")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn issue(deadline: &str) -> Issue {
+        testing::issue("TREK-1")
+            .summary("Релиз")
+            .field("deadline", deadline)
+            .build()
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_deadline_ends_with_day_in_zone() {
+        let novosibirsk: Zone = "Asia/Novosibirsk".parse().unwrap();
+        let moscow: Zone = "Europe/Moscow".parse().unwrap();
+
+        assert_eq!(
+            deadline(&issue("2026-10-20"), novosibirsk),
+            Some(utc("2026-10-20T16:59:59Z"))
+        );
+        assert_eq!(
+            deadline(&issue("2026-10-20"), moscow),
+            Some(utc("2026-10-20T20:59:59Z"))
+        );
+    }
+
+    #[test]
+    fn test_deadline_note_depends_on_zone() {
+        let now = utc("2026-10-20T18:00:00Z");
+        let issue = issue("2026-10-20");
+
+        // В Новосибирске 21 октября уже наступило, в Москве ещё 21:00 20-го
+        assert_eq!(
+            deadline_note(&issue, "Asia/Novosibirsk".parse().unwrap(), now).as_deref(),
+            Some("20.10.2026 (просрочен на 1 дн.)")
+        );
+        assert_eq!(
+            deadline_note(&issue, "Europe/Moscow".parse().unwrap(), now).as_deref(),
+            Some("20.10.2026 (осталось 2 ч)")
+        );
+        assert_eq!(
+            deadline_note(
+                &issue,
+                "Europe/Moscow".parse().unwrap(),
+                utc("2026-10-16T09:00:00Z")
+            )
+            .as_deref(),
+            Some("20.10.2026 (осталось 4 дн.)")
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Варианты названия задачи от LLM
+//!
+//! Если описание новой задачи длинное, `tracker create` и мастер создания в TUI
+//! предлагают три коротких названия по описанию. Выбранный вариант заменяет
+//! введённое название; можно оставить своё. Описание перед отправкой проходит
+//! через скрытие личных данных, как и остальные тексты задач.
+
+use anyhow::{Context, Result};
+use llm_lib::LlmClientTrait;
+use tracing::{debug, instrument};
+
+use crate::llm::extract_json_array;
+use crate::redact;
+
+/// С какой длины описания (в символах) предлагаются варианты названия
+pub const LONG_DESCRIPTION: usize = 400;
+
+/// Сколько вариантов просить у LLM
+pub const SUGGESTIONS: usize = 3;
+
+/// Сколько символов описания передавать LLM
+const LLM_DESCRIPTION_LIMIT: usize = 4000;
+
+/// Длиннее этого вариант не считается коротким названием
+const MAX_TITLE_CHARS: usize = 100;
+
+const SYSTEM_PROMPT: &str = "Ты предлагаешь названия задач в трекере. По описанию задачи \
+и черновику названия предложи три разных коротких названия: до 80 символов, без точки в \
+конце, на языке описания; суть проблемы или работы, а не пересказ. Ответь только JSON \
+массивом из трёх строк без пояснений.";
+
+/// Достаточно ли длинное описание, чтобы предлагать варианты названия
+pub fn is_long(description: &str) -> bool {
+    description.trim().chars().count() >= LONG_DESCRIPTION
+}
+
+/// Просит LLM предложить варианты названия по черновику и описанию
+#[instrument(skip_all)]
+pub async fn suggest<T: LlmClientTrait>(
+    llm: &T,
+    summary: &str,
+    description: &str,
+) -> Result<Vec<String>> {
+    let description: String = description.chars().take(LLM_DESCRIPTION_LIMIT).collect();
+    let prompt = format!("Черновик названия: {summary}\n\nОписание:\n{description}");
+    let prompt = redact::text("create", &prompt);
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(response = %response, "Ответ LLM получен");
+    parse_titles(&response, summary)
+}
+
+/// Извлекает варианты из JSON массива в ответе LLM
+///
+/// Пустые, слишком длинные и повторяющиеся варианты, а также совпадающие с черновиком,
+/// отбрасываются.
+pub fn parse_titles(response: &str, summary: &str) -> Result<Vec<String>> {
+    let candidates: Vec<String> = serde_json::from_str(extract_json_array(response)?)
+        .context("Не удалось разобрать варианты названия из ответа LLM")?;
+
+    let mut titles: Vec<String> = Vec::new();
+    for candidate in candidates {
+        let title = candidate.trim().trim_end_matches('.').trim().to_string();
+        let usable = !title.is_empty()
+            && title.chars().count() <= MAX_TITLE_CHARS
+            && title.to_lowercase() != summary.trim().to_lowercase()
+            && !titles.contains(&title);
+        if usable {
+            titles.push(title);
+        }
+    }
+    titles.truncate(SUGGESTIONS);
+    if titles.is_empty() {
+        anyhow::bail!("LLM не предложила ни одного подходящего названия");
+    }
+    Ok(titles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_titles_filters_and_limits() {
+        let response = "Вот варианты:\n```json\n[\"Падает экспорт CSV при пустых датах.\", \
+\"Падает экспорт\", \"\", \"Экспорт CSV: ошибка на пустой дате\", \
+\"Экспорт CSV: ошибка на пустой дате\", \"Проверять даты перед выгрузкой\", \"Лишний\"]\n```";
+        assert_eq!(
+            parse_titles(response, "Падает экспорт").unwrap(),
+            [
+                "Падает экспорт CSV при пустых датах",
+                "Экспорт CSV: ошибка на пустой дате",
+                "Проверять даты перед выгрузкой"
+            ]
+        );
+        assert!(parse_titles("[\"Падает экспорт\"]", "Падает экспорт").is_err());
+        assert!(parse_titles("Не могу помочь", "Падает экспорт").is_err());
+
+        assert!(!is_long("Короткое описание"));
+        assert!(is_long(&"а".repeat(LONG_DESCRIPTION)));
+    }
+
+    #[tokio::test]
+    async fn test_suggest_sends_draft_and_description() {
+        let mut llm = llm_lib::MockLlmClientTrait::new();
+        llm.expect_complete_with_system()
+            .withf(|system, prompt| {
+                system.contains("JSON массивом")
+                    && prompt.starts_with("Черновик названия: Ошибка")
+                    && prompt.ends_with("Не приходит письмо о сбросе пароля")
+            })
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async {
+                    Ok("[\"Нет письма о сбросе пароля\"]".to_string())
+                })
+            });
+
+        let titles = suggest(&llm, "Ошибка", "Не приходит письмо о сбросе пароля")
+            .await
+            .unwrap();
+        assert_eq!(titles, ["Нет письма о сбросе пароля"]);
+    }
+}
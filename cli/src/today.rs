@@ -0,0 +1,423 @@
+//! Чеклист на сегодня: `you today sync`
+//!
+//! `you llm plan-day` запоминает задачи из составленного плана в
+//! `$XDG_STATE_HOME/you/today.json`. `you today sync` переносит их в чеклист личной
+//! задачи «Сегодня» (`--issue` или `today.issue` в конфигурации), а без неё — в
+//! локальный файл `today.md`, и отмечает пункты задач, которые уже закрыты. Пункты,
+//! добавленные вручную, и отметки, поставленные руками, синхронизация не трогает.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use tracker_lib::{
+    checklists::ChecklistItemRequest, ids::IssueKey, models::Issue, task::find_issue_keys,
+    TrackerClient,
+};
+
+use crate::config::Config;
+use crate::paths;
+use crate::settings;
+use crate::summarize;
+use crate::timezone;
+
+/// Секция `today` конфигурации
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TodayConfig {
+    /// Личная задача, в чеклист которой переносится план
+    pub issue: Option<IssueKey>,
+}
+
+/// Команды чеклиста на сегодня
+#[derive(Subcommand)]
+pub enum TodayCommands {
+    /// Перенести задачи плана дня в чеклист и отметить закрытые
+    Sync {
+        /// Личная задача «Сегодня»; по умолчанию `today.issue` из конфигурации
+        #[arg(long, conflicts_with = "file")]
+        issue: Option<IssueKey>,
+
+        /// Локальный файл чеклиста вместо задачи; по умолчанию `today.md` в каталоге состояния
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+impl TodayCommands {
+    /// Выполняет команду чеклиста на сегодня
+    pub async fn execute(&self) -> Result<()> {
+        match self {
+            TodayCommands::Sync { issue, file } => {
+                execute_sync(issue.as_ref(), file.as_deref()).await
+            }
+        }
+    }
+}
+
+/// Задача из плана дня
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedIssue {
+    pub key: IssueKey,
+    pub summary: String,
+}
+
+impl PlannedIssue {
+    /// Текст пункта чеклиста
+    pub fn text(&self) -> String {
+        format!("{}: {}", self.key, self.summary)
+    }
+}
+
+/// Задачи, которые попали в план дня
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayPlan {
+    pub date: NaiveDate,
+    pub issues: Vec<PlannedIssue>,
+}
+
+impl DayPlan {
+    /// Задачи, упомянутые в тексте плана, в порядке упоминания
+    ///
+    /// Учитываются только задачи из контекста плана: ключ, который LLM выдумала,
+    /// в чеклист не попадёт.
+    pub fn from_text(date: NaiveDate, plan: &str, issues: &[Issue]) -> Self {
+        let issues = find_issue_keys(plan)
+            .into_iter()
+            .filter_map(|key| {
+                let issue = issues.iter().find(|issue| issue.key == key.as_ref())?;
+                Some(PlannedIssue {
+                    key,
+                    summary: issue.summary.clone(),
+                })
+            })
+            .collect();
+        Self { date, issues }
+    }
+
+    /// Загружает сохранённый план; повреждённый или отсутствующий файл даёт None
+    #[instrument]
+    pub fn load() -> Option<Self> {
+        let path = plan_path()?;
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "План дня не найден");
+                return None;
+            }
+        };
+        serde_json::from_str(&content)
+            .map_err(|err| {
+                warn!(path = %path.display(), error = %err, "Не удалось разобрать план дня");
+            })
+            .ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = plan_path().context("Не удалось определить путь для плана дня")?;
+        write_file(&path, &serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn plan_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("today.json"))
+}
+
+fn default_file() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("today.md"))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+    }
+    std::fs::write(path, content).with_context(|| format!("Не удалось записать {}", path.display()))
+}
+
+/// Запоминает задачи плана дня для `you today sync`
+pub fn remember(plan: &DayPlan) {
+    match plan.save() {
+        Ok(()) => info!(issues = plan.issues.len(), "План дня сохранён"),
+        Err(err) => warn!(error = %err, "Не удалось сохранить план дня"),
+    }
+}
+
+/// Пункт чеклиста: текст и отметка
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChecklistLine {
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Изменение чеклиста
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// Добавить пункт задачи из плана
+    Add { text: String, checked: bool },
+    /// Отметить существующий пункт с индексом `index`: задача закрыта
+    Check { index: usize },
+}
+
+/// Что поменять в чеклисте, чтобы он отражал план и закрытые задачи
+///
+/// Пункт задачи находится по ключу в начале текста. Отметки только ставятся:
+/// пункт, отмеченный руками, синхронизация не снимет.
+pub fn sync_actions(
+    plan: &DayPlan,
+    lines: &[ChecklistLine],
+    closed: &HashSet<String>,
+) -> Vec<SyncAction> {
+    let mut actions = Vec::new();
+    for planned in &plan.issues {
+        let is_closed = closed.contains(planned.key.as_ref());
+        let existing = lines
+            .iter()
+            .position(|line| find_issue_keys(&line.text).first() == Some(&planned.key));
+        match existing {
+            Some(index) if is_closed && !lines[index].checked => {
+                actions.push(SyncAction::Check { index })
+            }
+            Some(_) => {}
+            None => actions.push(SyncAction::Add {
+                text: planned.text(),
+                checked: is_closed,
+            }),
+        }
+    }
+    actions
+}
+
+/// Заголовок файла чеклиста на дату
+fn file_title(date: NaiveDate) -> String {
+    format!("# Сегодня, {}", date.format("%d.%m.%Y"))
+}
+
+/// Разбирает строку Markdown вида `- [ ] текст` или `- [x] текст`
+fn parse_line(line: &str) -> Option<ChecklistLine> {
+    let rest = line.trim_start().strip_prefix("- [")?;
+    let (mark, text) = rest.split_once("] ")?;
+    let checked = match mark {
+        " " => false,
+        "x" | "X" => true,
+        _ => return None,
+    };
+    Some(ChecklistLine {
+        text: text.trim().to_string(),
+        checked,
+    })
+}
+
+fn format_line(line: &ChecklistLine) -> String {
+    let mark = if line.checked { "x" } else { " " };
+    format!("- [{mark}] {}", line.text)
+}
+
+/// Применяет план к содержимому файла чеклиста и возвращает новое содержимое
+///
+/// Файл за другой день начинается заново; остальные строки файла сохраняются.
+pub fn sync_file(content: &str, plan: &DayPlan, closed: &HashSet<String>) -> (String, usize) {
+    let title = file_title(plan.date);
+    let mut lines: Vec<String> = if content.lines().next() == Some(title.as_str()) {
+        content.lines().map(str::to_string).collect()
+    } else {
+        vec![title, String::new()]
+    };
+
+    let (positions, items): (Vec<usize>, Vec<ChecklistLine>) = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(position, line)| Some((position, parse_line(line)?)))
+        .unzip();
+    let actions = sync_actions(plan, &items, closed);
+    for action in &actions {
+        match action {
+            SyncAction::Add { text, checked } => lines.push(format_line(&ChecklistLine {
+                text: text.clone(),
+                checked: *checked,
+            })),
+            SyncAction::Check { index } => {
+                lines[positions[*index]] = format_line(&ChecklistLine {
+                    text: items[*index].text.clone(),
+                    checked: true,
+                })
+            }
+        }
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    (content, actions.len())
+}
+
+#[instrument]
+async fn execute_sync(issue: Option<&IssueKey>, file: Option<&Path>) -> Result<()> {
+    let today = timezone::current().today();
+    let plan = match DayPlan::load() {
+        Some(plan) if plan.date == today => plan,
+        _ => bail!("Плана на сегодня нет: составьте его командой `you llm plan-day`"),
+    };
+
+    let client = settings::tracker_client()?;
+    let keys = plan
+        .issues
+        .iter()
+        .map(|planned| planned.key.to_string())
+        .collect();
+    let closed: HashSet<String> = summarize::fetch_keys(&client, keys)
+        .await?
+        .into_iter()
+        .filter(|issue| issue.resolved_at.is_some())
+        .map(|issue| issue.key)
+        .collect();
+    info!(
+        planned = plan.issues.len(),
+        closed = closed.len(),
+        "Статусы задач плана получены"
+    );
+
+    let issue = match file {
+        Some(_) => None,
+        None => issue.cloned().or(Config::load()?.today.issue),
+    };
+    match issue {
+        Some(issue) => sync_issue(&client, &issue, &plan, &closed).await,
+        None => {
+            let path = match file {
+                Some(file) => file.to_path_buf(),
+                None => default_file().context("Не удалось определить путь для today.md")?,
+            };
+            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            let (content, changes) = sync_file(&content, &plan, &closed);
+            write_file(&path, &content)?;
+            println!("{}: изменений {changes}", path.display());
+            Ok(())
+        }
+    }
+}
+
+/// Переносит план в чеклист задачи
+async fn sync_issue(
+    client: &TrackerClient,
+    issue: &IssueKey,
+    plan: &DayPlan,
+    closed: &HashSet<String>,
+) -> Result<()> {
+    let items = client.get_checklist(issue).await?;
+    let lines: Vec<ChecklistLine> = items
+        .iter()
+        .map(|item| ChecklistLine {
+            text: item.text.clone(),
+            checked: item.checked,
+        })
+        .collect();
+    let actions = sync_actions(plan, &lines, closed);
+    for action in &actions {
+        match action {
+            SyncAction::Add { text, checked } => {
+                client
+                    .add_checklist_item(issue, &ChecklistItemRequest::new(text, *checked))
+                    .await?;
+                println!("+ {text}");
+            }
+            SyncAction::Check { index } => {
+                let item = &items[*index];
+                client
+                    .update_checklist_item(issue, &item.id, &ChecklistItemRequest::checked(true))
+                    .await?;
+                println!("✓ {}", item.text);
+            }
+        }
+    }
+    println!("Чеклист {issue}: изменений {}", actions.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+
+    fn plan() -> DayPlan {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let text = "1. TREK-2 — сначала ревью\n2. TREK-1, затем релиз\n3. TREK-99 не из списка";
+        DayPlan::from_text(
+            date,
+            text,
+            &[issue("TREK-1").build(), issue("TREK-2").build()],
+        )
+    }
+
+    fn closed(keys: &[&str]) -> HashSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_text_keeps_plan_order_and_known_issues() {
+        let keys: Vec<String> = plan().issues.iter().map(|p| p.key.to_string()).collect();
+        assert_eq!(keys, ["TREK-2", "TREK-1"]);
+    }
+
+    #[test]
+    fn test_sync_actions_adds_missing_and_checks_closed() {
+        let lines = [
+            ChecklistLine {
+                text: "TREK-1: Задача TREK-1".to_string(),
+                checked: false,
+            },
+            ChecklistLine {
+                text: "Позвонить в банк".to_string(),
+                checked: false,
+            },
+        ];
+        let actions = sync_actions(&plan(), &lines, &closed(&["TREK-1"]));
+        assert_eq!(
+            actions,
+            [
+                SyncAction::Add {
+                    text: "TREK-2: Задача TREK-2".to_string(),
+                    checked: false
+                },
+                SyncAction::Check { index: 0 }
+            ]
+        );
+
+        // Повторная синхронизация ничего не меняет, а отметки руками не снимаются
+        let synced = [
+            ChecklistLine {
+                text: "TREK-1: Задача TREK-1".to_string(),
+                checked: true,
+            },
+            ChecklistLine {
+                text: "TREK-2: Задача TREK-2".to_string(),
+                checked: true,
+            },
+        ];
+        assert!(sync_actions(&plan(), &synced, &closed(&["TREK-1"])).is_empty());
+    }
+
+    #[test]
+    fn test_sync_file_starts_new_day_and_keeps_manual_lines() {
+        let (content, changes) = sync_file(
+            "# Сегодня, 15.10.2026\n\n- [ ] TREK-7: вчера\n",
+            &plan(),
+            &closed(&[]),
+        );
+        assert_eq!(changes, 2);
+        assert_eq!(
+            content,
+            "# Сегодня, 16.10.2026\n\n- [ ] TREK-2: Задача TREK-2\n- [ ] TREK-1: Задача TREK-1\n"
+        );
+
+        let edited = content.replace(
+            "- [ ] TREK-1: Задача TREK-1",
+            "- [ ] TREK-1: Задача TREK-1\n- [ ] Обед",
+        );
+        let (content, changes) = sync_file(&edited, &plan(), &closed(&["TREK-1"]));
+        assert_eq!(changes, 1);
+        assert!(content.ends_with("- [x] TREK-1: Задача TREK-1\n- [ ] Обед\n"));
+    }
+}
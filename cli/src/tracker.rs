@@ -1,21 +1,439 @@
 //! Команды для работы с трекером задач
 
-use anyhow::Result;
-use clap::Subcommand;
-use tracing::{info, instrument};
-use tracker_lib::task::format_issue_output;
-use tracker_lib::TrackerClient;
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Local, NaiveDate, Utc};
+use clap::{Subcommand, ValueEnum};
+use llm_lib::{LlmClient, LlmConfig};
+use serde::Deserialize;
+use tracing::{info, instrument, warn};
+use tracker_lib::comments::BulkComment;
+use tracker_lib::search::{QueryCheck, QueryError, SearchParams, SearchRequest};
+use tracker_lib::task::CreateIssueRequest;
+use tracker_lib::transitions::ExecuteTransitionRequest;
+use tracker_lib::{
+    ids::{IssueKey, QueueKey},
+    TrackerClient, TrackerError,
+};
+
+use crate::config::Config;
+use crate::email::{self, Email};
+use crate::feed;
+use crate::hooks;
+use crate::index::{self, IssueIndex};
+use crate::jira::{self, FieldMapping};
+use crate::language;
+use crate::llm::DEFAULT_MODEL;
+use crate::org;
+use crate::output::{self, HitOutput, IssueOutput, OutputFormat};
+use crate::people;
+use crate::progress;
+use crate::prs::{self, Forge};
+use crate::reminders::{self, Reminders};
+use crate::render::Renderer;
+use crate::settings;
+use crate::snooze::{self, Snoozes};
+use crate::stale;
+use crate::summarize;
+use crate::tags::{self, TagChange, TagOutcome};
+use crate::templates;
+use crate::timezone;
+use crate::titles;
+use crate::triage;
 
 /// Команды для работы с трекером задач
 #[derive(Subcommand)]
 pub enum TrackerCommands {
     /// Получить информацию о задаче
     Issue {
-        /// Идентификатор или ключ задачи (например, TREK-123)
+        /// Ключ задачи (например, TREK-123)
+        issue_key: IssueKey,
+
+        /// Шаблон вывода: full, short, имя своего шаблона или путь к файлу .tmpl
+        #[arg(short, long, default_value = templates::FULL)]
+        template: String,
+
+        /// Формат вывода; для json шаблон не используется (схема — `you schema tracker-issue`)
+        #[arg(short, long, value_enum, default_value_t)]
+        output: OutputFormat,
+    },
+    /// Создать задачу
+    Create {
+        /// Название задачи
+        summary: String,
+
+        /// Ключ очереди (например, TREK)
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// Описание задачи
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Ключ типа задачи (например, bug)
+        #[arg(long = "type")]
+        issue_type: Option<String>,
+
+        /// Ключ приоритета (например, critical)
+        #[arg(long)]
+        priority: Option<String>,
+
+        /// Логин исполнителя или часть его имени (ищется в справочнике пользователей)
+        #[arg(long)]
+        assignee: Option<String>,
+
+        /// Логин наблюдателя или часть имени; можно указать несколько раз
+        #[arg(long = "follower")]
+        followers: Vec<String>,
+
+        /// Тег задачи; можно указать несколько раз
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Перед созданием поискать похожие задачи в локальном индексе по эмбеддингам
+        #[arg(long)]
+        check_duplicates: bool,
+
+        /// Модель эмбеддингов для --check-duplicates
+        #[arg(long)]
+        embedding_model: Option<String>,
+
+        /// Создать задачу без вопроса, даже если найдены возможные дубли
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Не применять пресет очереди из конфигурации
+        #[arg(long)]
+        no_preset: bool,
+
+        /// Не предлагать варианты названия от LLM для длинного описания
+        #[arg(long)]
+        no_suggest_title: bool,
+    },
+    /// Создать задачу из письма: тема — название, текст — описание, вложения — в задачу
+    FromEmail {
+        /// Файл письма в формате .eml
+        #[arg(long)]
+        file: PathBuf,
+
+        /// Ключ очереди (например, TREK)
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// Ключ типа задачи (например, bug)
+        #[arg(long = "type")]
+        issue_type: Option<String>,
+
+        /// Тег задачи; можно указать несколько раз
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Предложить приоритет и исполнителя с помощью LLM
+        #[arg(long)]
+        llm: bool,
+
+        /// Модель LLM для --llm
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Создать задачу без подтверждения
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Не применять пресет очереди из конфигурации
+        #[arg(long)]
+        no_preset: bool,
+    },
+    /// Предложить классификацию неразобранных задач очереди с помощью LLM
+    Triage {
+        /// Ключ очереди (например, TREK)
+        queue: QueueKey,
+
+        /// Модель LLM
+        #[arg(short, long)]
+        model: Option<String>,
+    },
+    /// Найти зависшие задачи очереди, напомнить о них или вернуть их в бэклог
+    Stale {
+        /// Ключ очереди (например, TREK)
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// Сколько дней без работы считать зависанием
+        #[arg(short, long, default_value_t = 14)]
+        days: i64,
+
+        /// Активные статусы через запятую (по умолчанию inProgress, needInfo, inReview, testing)
+        #[arg(long, value_delimiter = ',')]
+        status: Vec<String>,
+
+        /// Сколько задач очереди проверять
+        #[arg(short, long, default_value_t = stale::DEFAULT_LIMIT)]
+        limit: usize,
+
+        /// Призвать исполнителя комментарием-напоминанием (текст пишет LLM)
+        #[arg(long)]
+        ping: bool,
+
+        /// Текст напоминания вместо написанного LLM
+        #[arg(long, requires = "ping")]
+        message: Option<String>,
+
+        /// Вернуть задачи в статус (ключ или название), например backlog
+        #[arg(long, value_name = "STATUS")]
+        move_to: Option<String>,
+
+        /// Модель LLM для напоминаний
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Выполнить действия без подтверждения
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Только показать задачи и действия
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Найти мои задачи в локальном индексе (перед поиском индекс синхронизируется)
+    Find {
+        /// Текст запроса (например, "that bug about socks proxy")
+        text: String,
+
+        /// Сколько задач показать
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+
+        /// Дополнительно ранжировать по близости эмбеддингов
+        #[arg(long)]
+        semantic: bool,
+
+        /// Модель эмбеддингов для --semantic
+        #[arg(long)]
+        embedding_model: Option<String>,
+
+        /// Искать без синхронизации с трекером
+        #[arg(long)]
+        no_sync: bool,
+
+        /// Формат вывода (схема json — `you schema tracker-find`)
+        #[arg(short, long, value_enum, default_value_t)]
+        output: OutputFormat,
+    },
+    /// Проверить запрос на языке запросов: ошибки с позициями и число совпадений
+    ExplainQuery {
+        /// Запрос (например, "Queue: TREK Resolved: week()")
+        query: String,
+    },
+    /// Синхронизировать локальный индекс моих задач
+    Sync {
+        /// Перечитать все задачи, а не только изменённые с прошлой синхронизации
+        #[arg(long)]
+        full: bool,
+    },
+    /// Связать открытые PR/MR с задачами: комментарий со ссылкой и перевод в ревью
+    SyncPrs {
+        /// Хостинг репозитория; по умолчанию определяется по адресу origin
+        #[arg(long, value_enum)]
+        forge: Option<Forge>,
+
+        /// Статус ревью (ключ или название)
+        #[arg(long, default_value = prs::DEFAULT_REVIEW_STATUS)]
+        status: String,
+
+        /// Сколько открытых PR просматривать
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+
+        /// Только показать, что будет сделано
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Добавить комментарии во много задач с паузами по лимитам API и повторами
+    CommentBulk {
+        /// CSV с колонками key и text: задача и текст комментария
+        #[arg(long, required_unless_present = "query", conflicts_with = "query")]
+        file: Option<PathBuf>,
+
+        /// Запрос на языке Трекера: один комментарий во все найденные задачи
+        #[arg(long, requires = "text")]
+        query: Option<String>,
+
+        /// Текст комментария для задач из --query
+        #[arg(long, requires = "query")]
+        text: Option<String>,
+
+        /// Только показать, какие комментарии будут добавлены
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Добавить и убрать теги во всех задачах запроса одним массовым изменением
+    Tags {
+        /// Запрос на языке Трекера (например, "Queue: TREK Tags: triage")
+        #[arg(long)]
+        query: String,
+
+        /// Добавляемый тег; можно указать несколько раз
+        #[arg(long = "add", required_unless_present = "remove")]
+        add: Vec<String>,
+
+        /// Удаляемый тег; можно указать несколько раз
+        #[arg(long = "remove")]
+        remove: Vec<String>,
+
+        /// Сколько задач запроса изменять
+        #[arg(short, long, default_value_t = tags::DEFAULT_LIMIT)]
+        limit: usize,
+
+        /// Изменить без подтверждения
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Только показать, как изменятся теги
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Найти пользователей организации по части логина или имени
+    Users {
+        /// Буквы логина или имени по порядку, например "ivpt" или "Иван П"
+        #[arg(default_value = "")]
+        query: String,
+
+        /// Сколько пользователей показать
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+
+        /// Обновить сохранённый справочник пользователей из Трекера
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Показать граф зависимостей задачи: блокеры, родительские задачи и связи
+    Graph {
+        /// Идентификатор или ключ корневой задачи
         issue_id: String,
+
+        /// Сколько шагов по связям делать от корневой задачи
+        #[arg(short, long, default_value_t = 2)]
+        depth: usize,
+
+        /// Формат вывода
+        #[arg(short, long, value_enum, default_value_t = GraphFormat::Tree)]
+        format: GraphFormat,
+    },
+    /// Импортировать задачи из экспорта Jira (JSON REST API или CSV)
+    Import {
+        /// Файл экспорта; `.csv` читается как CSV, остальное как JSON
+        #[arg(long)]
+        from: PathBuf,
+
+        /// Очередь, в которой создаются задачи
+        #[arg(short, long)]
+        queue: QueueKey,
+
+        /// YAML с соответствием типов, приоритетов и пользователей Jira
+        #[arg(long)]
+        mapping: Option<PathBuf>,
+
+        /// Только показать, какие задачи будут созданы
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Выгрузить задачи в JSON формата REST API Jira
+    Export {
+        /// Запрос на языке Трекера (например, "Queue: TREK")
+        #[arg(long)]
+        query: String,
+
+        /// Файл, в который записывается выгрузка
+        #[arg(long)]
+        to: PathBuf,
+
+        /// YAML с соответствием типов, приоритетов и пользователей Jira
+        #[arg(long)]
+        mapping: Option<PathBuf>,
+    },
+    /// Лента активности очереди: последние изменения недавно обновлённых задач
+    Feed {
+        /// Ключ очереди (например, TREK)
+        queue: QueueKey,
+
+        /// За какой период показать изменения: 30m, 2h, 3d или 1w
+        #[arg(long, value_parser = reminders::parse_delay, default_value = "1d")]
+        since: Duration,
+    },
+    /// Напомнить о задаче через заданное время (например, `--in 3d "ping QA"`)
+    Remind {
+        /// Ключ задачи (например, TREK-123)
+        issue_key: IssueKey,
+
+        /// Текст напоминания
+        text: String,
+
+        /// Через сколько напомнить: 30m, 2h, 3d или 1w
+        #[arg(long = "in", value_parser = reminders::parse_delay)]
+        delay: Duration,
+
+        /// Дополнительно записать событие в .ics файл для импорта в календарь
+        #[arg(long)]
+        ics: Option<PathBuf>,
+    },
+    /// Показать напоминания, отложить или выполнить напоминание
+    Reminders {
+        /// Показать и выполненные напоминания
+        #[arg(short, long)]
+        all: bool,
+
+        /// Отложить напоминание с этим номером
+        #[arg(long, value_name = "ID", conflicts_with = "done")]
+        snooze: Option<u64>,
+
+        /// На сколько отложить: 30m, 2h, 3d или 1w
+        #[arg(long = "for", value_parser = reminders::parse_delay, default_value = "1d")]
+        snooze_for: Duration,
+
+        /// Отметить напоминание с этим номером выполненным
+        #[arg(long, value_name = "ID")]
+        done: Option<u64>,
+    },
+    /// Скрыть задачу из списков до указанного дня (без ключа — показать отложенные)
+    Snooze {
+        /// Ключ задачи (например, TREK-5)
+        issue_key: Option<IssueKey>,
+
+        /// До какого дня скрыть: YYYY-MM-DD, tomorrow, monday…sunday, 3d или 1w
+        #[arg(long, value_parser = snooze::parse_until, requires = "issue_key")]
+        until: Option<NaiveDate>,
+
+        /// Вернуть задачу в списки сейчас
+        #[arg(long, conflicts_with = "until", requires = "issue_key")]
+        off: bool,
+    },
+    /// Прикрепить файлы к задаче; ход загрузки показывается полосой прогресса
+    Attach {
+        /// Ключ задачи (например, TREK-5)
+        issue_key: IssueKey,
+
+        /// Файлы для загрузки
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
     },
 }
 
+/// Формат вывода графа зависимостей
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Текстовое дерево с блокерами
+    Tree,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
 impl TrackerCommands {
     /// Выполняет команду трекера
     ///
@@ -24,7 +442,197 @@ impl TrackerCommands {
     /// Ok(()) при успешном выполнении команды
     pub async fn execute(&self) -> Result<()> {
         match self {
-            TrackerCommands::Issue { issue_id } => execute_issue(issue_id).await,
+            TrackerCommands::Issue {
+                issue_key,
+                template,
+                output,
+            } => execute_issue(issue_key, template, *output).await,
+            TrackerCommands::Create {
+                summary,
+                queue,
+                description,
+                issue_type,
+                priority,
+                assignee,
+                followers,
+                tags,
+                check_duplicates,
+                embedding_model,
+                yes,
+                no_preset,
+                no_suggest_title,
+            } => {
+                let client = settings::tracker_client()?;
+                // Один запрос к справочнику на исполнителя и наблюдателей
+                let queries: Vec<String> = assignee.iter().chain(followers).cloned().collect();
+                let mut logins = people::resolve_logins(&client, &queries).await?;
+                let followers = logins.split_off(usize::from(assignee.is_some()));
+                let mut request = CreateIssueRequest {
+                    description: description.clone(),
+                    issue_type: issue_type.clone(),
+                    priority: priority.clone(),
+                    assignee: logins.pop(),
+                    followers,
+                    tags: tags.clone(),
+                    ..CreateIssueRequest::new(queue.clone(), summary)
+                };
+                if !no_preset {
+                    if let Some(preset) = Config::load()?.presets.get(queue) {
+                        info!(queue = %queue, "Применён пресет очереди");
+                        preset.apply(&mut request);
+                    }
+                }
+                // Выбор названия нужен человеку у терминала, а не скрипту
+                if !yes && !no_suggest_title && io::stdin().is_terminal() {
+                    offer_titles(&mut request).await;
+                }
+                let embedding_model = check_duplicates.then(|| {
+                    embedding_model
+                        .as_deref()
+                        .unwrap_or(index::DEFAULT_EMBEDDING_MODEL)
+                });
+                execute_create(&request, embedding_model, *yes).await
+            }
+            TrackerCommands::FromEmail {
+                file,
+                queue,
+                issue_type,
+                tags,
+                llm,
+                model,
+                yes,
+                no_preset,
+            } => {
+                let model = llm.then(|| model.as_deref().unwrap_or(DEFAULT_MODEL));
+                let request = CreateIssueRequest {
+                    issue_type: issue_type.clone(),
+                    tags: tags.clone(),
+                    ..CreateIssueRequest::new(queue.clone(), "")
+                };
+                execute_from_email(file, request, model, *yes, *no_preset).await
+            }
+            TrackerCommands::Triage { queue, model } => {
+                execute_triage(queue, model.as_deref().unwrap_or(DEFAULT_MODEL)).await
+            }
+            TrackerCommands::Stale {
+                queue,
+                days,
+                status,
+                limit,
+                ping,
+                message,
+                move_to,
+                model,
+                yes,
+                dry_run,
+            } => {
+                let statuses = if status.is_empty() {
+                    stale::DEFAULT_ACTIVE_STATUSES
+                        .iter()
+                        .map(|status| status.to_string())
+                        .collect()
+                } else {
+                    status.clone()
+                };
+                let org = org::OrgDirectory::load();
+                org.check_statuses(status)?;
+                if let Some(move_to) = move_to {
+                    org.check_statuses(std::slice::from_ref(move_to))?;
+                }
+                let actions = StaleActions {
+                    ping: *ping,
+                    message: message.as_deref(),
+                    move_to: move_to.as_deref(),
+                    model: model.as_deref().unwrap_or(DEFAULT_MODEL),
+                    yes: *yes,
+                    dry_run: *dry_run,
+                };
+                execute_stale(queue, *days, &statuses, *limit, actions).await
+            }
+            TrackerCommands::Find {
+                text,
+                limit,
+                semantic,
+                embedding_model,
+                no_sync,
+                output,
+            } => {
+                let embedding_model = semantic.then(|| {
+                    embedding_model
+                        .as_deref()
+                        .unwrap_or(index::DEFAULT_EMBEDDING_MODEL)
+                });
+                execute_find(text, *limit, embedding_model, !no_sync, *output).await
+            }
+            TrackerCommands::Sync { full } => execute_sync(*full).await,
+            TrackerCommands::SyncPrs {
+                forge,
+                status,
+                limit,
+                dry_run,
+            } => execute_sync_prs(*forge, status, *limit, *dry_run).await,
+            TrackerCommands::CommentBulk {
+                file,
+                query,
+                text,
+                dry_run,
+            } => {
+                execute_comment_bulk(file.as_deref(), query.as_deref(), text.as_deref(), *dry_run)
+                    .await
+            }
+            TrackerCommands::Tags {
+                query,
+                add,
+                remove,
+                limit,
+                yes,
+                dry_run,
+            } => {
+                let change = TagChange {
+                    add: add.clone(),
+                    remove: remove.clone(),
+                };
+                execute_tags(query, &change, *limit, *yes, *dry_run).await
+            }
+            TrackerCommands::Users {
+                query,
+                limit,
+                refresh,
+            } => execute_users(query, *limit, *refresh).await,
+            TrackerCommands::Graph {
+                issue_id,
+                depth,
+                format,
+            } => execute_graph(issue_id, *depth, *format).await,
+            TrackerCommands::Import {
+                from,
+                queue,
+                mapping,
+                dry_run,
+            } => execute_import(from, queue, mapping.as_deref(), *dry_run).await,
+            TrackerCommands::Export { query, to, mapping } => {
+                execute_export(query, to, mapping.as_deref()).await
+            }
+            TrackerCommands::Feed { queue, since } => execute_feed(queue, *since).await,
+            TrackerCommands::Remind {
+                issue_key,
+                text,
+                delay,
+                ics,
+            } => execute_remind(issue_key, text, *delay, ics.as_deref()),
+            TrackerCommands::Reminders {
+                all,
+                snooze,
+                snooze_for,
+                done,
+            } => execute_reminders(*all, *snooze, *snooze_for, *done),
+            TrackerCommands::Snooze {
+                issue_key,
+                until,
+                off,
+            } => execute_snooze(issue_key.as_ref(), *until, *off),
+            TrackerCommands::Attach { issue_key, files } => execute_attach(issue_key, files).await,
+            TrackerCommands::ExplainQuery { query } => execute_explain_query(query).await,
         }
     }
 }
@@ -33,24 +641,31 @@ impl TrackerCommands {
 ///
 /// # Параметры
 ///
-/// * `issue_id` - Идентификатор или ключ задачи
+/// * `issue_key` - Ключ задачи
+/// * `template` - Имя шаблона вывода или путь к файлу шаблона
+/// * `output` - Текст по шаблону или JSON
 ///
 /// # Возвращает
 ///
 /// Ok(()) при успешном выполнении
-#[instrument(fields(issue_id = %issue_id))]
-async fn execute_issue(issue_id: &str) -> Result<()> {
-    info!("Выполнение команды issue для задачи: {}", issue_id);
+#[instrument(fields(issue_key = %issue_key))]
+async fn execute_issue(issue_key: &IssueKey, template: &str, output: OutputFormat) -> Result<()> {
+    info!("Выполнение команды issue для задачи: {}", issue_key);
+
+    // Шаблон проверяем до запроса, чтобы опечатка не стоила похода в сеть
+    let template = templates::load(template)?;
 
     // Создаём клиент из переменной окружения
-    let client = TrackerClient::from_env()?;
+    let client = settings::tracker_client()?;
 
     // Получаем информацию о задаче
-    let issue = client.get_issue(issue_id, None).await?;
+    let issue = client.get_issue(issue_key, None).await?;
 
     // Форматируем и выводим информацию
-    let output = format_issue_output(&issue);
-    println!("{}", output);
+    match output {
+        OutputFormat::Text => println!("{}", Renderer::stdout().issue(&template, &issue)),
+        OutputFormat::Json => output::print_json(&IssueOutput::from(&issue))?,
+    }
 
     let status = issue
         .status
@@ -66,3 +681,836 @@ async fn execute_issue(issue_id: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Для длинного описания предлагает выбрать название из вариантов LLM
+///
+/// Ошибка LLM не мешает создать задачу с введённым названием.
+async fn offer_titles(request: &mut CreateIssueRequest) {
+    let Some(description) = request
+        .description
+        .as_deref()
+        .filter(|d| titles::is_long(d))
+    else {
+        return;
+    };
+    let suggested = match LlmConfig::new(DEFAULT_MODEL).and_then(LlmClient::new) {
+        Ok(llm) => titles::suggest(&llm, &request.summary, description).await,
+        Err(err) => Err(err.into()),
+    };
+    let suggested = match suggested {
+        Ok(suggested) => suggested,
+        Err(err) => {
+            warn!(error = %format!("{err:#}"), "Варианты названия недоступны");
+            eprintln!("Варианты названия недоступны: {err:#}");
+            return;
+        }
+    };
+    let mut options = suggested.clone();
+    options.push(format!("Оставить: {}", request.summary));
+    if let Some(index) = people::choose("Описание длинное, LLM предлагает названия:", &options)
+    {
+        if let Some(title) = suggested.get(index) {
+            info!(title = %title, "Выбрано название от LLM");
+            request.summary = title.clone();
+        }
+    }
+}
+
+/// Создаёт задачу; с `embedding_model` сначала предупреждает о возможных дублях
+#[instrument(skip(request), fields(queue = %request.queue))]
+async fn execute_create(
+    request: &CreateIssueRequest,
+    embedding_model: Option<&str>,
+    yes: bool,
+) -> Result<()> {
+    let client = settings::tracker_client()?;
+
+    if let Some(model) = embedding_model {
+        let index = IssueIndex::open_default()?;
+        index::sync(&index, &client, false).await?;
+        let llm = LlmClient::new(LlmConfig::new(model)?)?;
+        let duplicates = index::find_duplicates(
+            &index,
+            &llm,
+            &request.summary,
+            request.description.as_deref(),
+        )
+        .await?;
+
+        if !duplicates.is_empty() {
+            println!("Похожие задачи, возможно дубли:");
+            for hit in &duplicates {
+                let status = hit.status.as_deref().unwrap_or("Неизвестен");
+                println!(
+                    "  {}  [{}]  {}  ({:.0}%)",
+                    hit.key,
+                    status,
+                    hit.summary,
+                    hit.score * 100.0
+                );
+            }
+            if !yes && !confirm("Всё равно создать задачу?") {
+                println!("Задача не создана");
+                return Ok(());
+            }
+        }
+    }
+
+    let issue = client.create_issue(request).await?;
+    println!("Создана задача {}: {}", issue.key, issue.summary);
+    info!(issue_key = %issue.key, "Задача создана");
+    export_created(&issue.key, &issue.summary);
+    Ok(())
+}
+
+/// Создаёт задачу из письма и загружает в неё вложения письма
+///
+/// Поля `request` из аргументов дополняются названием и описанием из письма, пресетом
+/// очереди и, если задана модель, приоритетом и исполнителем от LLM.
+#[instrument(skip(request))]
+async fn execute_from_email(
+    file: &Path,
+    mut request: CreateIssueRequest,
+    model: Option<&str>,
+    yes: bool,
+    no_preset: bool,
+) -> Result<()> {
+    let raw =
+        std::fs::read(file).with_context(|| format!("Не удалось прочитать {}", file.display()))?;
+    let email = Email::parse(&raw)?;
+    request.summary = email.summary();
+    request.description = Some(email.description());
+    request.unique = email.unique();
+    if !no_preset {
+        if let Some(preset) = Config::load()?.presets.get(&request.queue) {
+            info!(queue = %request.queue, "Применён пресет очереди");
+            preset.apply(&mut request);
+        }
+    }
+
+    let client = settings::tracker_client()?;
+    if let Some(model) = model {
+        let llm = LlmClient::new(LlmConfig::new(model)?)?;
+        let suggestion = email::suggest(&llm, &email).await?;
+        if let Some(priority) = suggestion.priority {
+            request.priority = Some(priority);
+        }
+        if let Some(assignee) = suggestion.assignee {
+            match people::resolve_logins(&client, std::slice::from_ref(&assignee)).await {
+                Ok(mut logins) => request.assignee = logins.pop(),
+                Err(err) => println!("Исполнитель «{assignee}» не подобран: {err:#}"),
+            }
+        }
+        if !suggestion.reason.is_empty() {
+            println!("LLM: {}", suggestion.reason);
+        }
+    }
+
+    println!("Очередь: {}", request.queue);
+    println!("Название: {}", request.summary);
+    if let Some(priority) = &request.priority {
+        println!("Приоритет: {priority}");
+    }
+    if let Some(assignee) = &request.assignee {
+        println!("Исполнитель: {assignee}");
+    }
+    for attachment in &email.attachments {
+        println!(
+            "Вложение: {} ({} байт)",
+            attachment.name,
+            attachment.data.len()
+        );
+    }
+    if !yes && !confirm("Создать задачу?") {
+        println!("Задача не создана");
+        return Ok(());
+    }
+
+    let issue = match client.create_issue(&request).await {
+        Err(TrackerError::ApiError { status, .. })
+            if status.as_u16() == 409 && request.unique.is_some() =>
+        {
+            bail!(
+                "Задача из этого письма уже создана (поле unique: {})",
+                request.unique.unwrap_or_default()
+            )
+        }
+        result => result?,
+    };
+    println!("Создана задача {}: {}", issue.key, issue.summary);
+    info!(issue_key = %issue.key, "Задача создана из письма");
+    export_created(&issue.key, &issue.summary);
+
+    let key: IssueKey = issue.key.parse()?;
+    let files = email
+        .attachments
+        .into_iter()
+        .map(|attachment| (attachment.name, attachment.data))
+        .collect();
+    upload_files(&client, &key, files).await
+}
+
+/// Проверяет запрос пробным поиском и показывает ошибки под местом в запросе
+#[instrument]
+async fn execute_explain_query(query: &str) -> Result<()> {
+    let client = settings::tracker_client()?;
+    match client.explain_query(query).await? {
+        QueryCheck::Valid { total, sample } => {
+            println!("Запрос корректен, задач найдётся: {total}");
+            if let Some(issue) = sample {
+                println!("Например: {} — {}", issue.key, issue.summary);
+            }
+            Ok(())
+        }
+        QueryCheck::Invalid(errors) => {
+            for error in &errors {
+                eprintln!("{}", describe_query_error(query, error));
+            }
+            bail!("Трекер отверг запрос: ошибок {}", errors.len());
+        }
+    }
+}
+
+/// Ошибка запроса; если известна позиция, под запросом ставится указатель
+fn describe_query_error(query: &str, error: &QueryError) -> String {
+    match error.position {
+        Some(position) if position <= query.chars().count() + 1 => format!(
+            "{}\n  {query}\n  {}^ позиция {position}",
+            error.message,
+            " ".repeat(position.saturating_sub(1))
+        ),
+        _ => error.message.clone(),
+    }
+}
+
+/// Передаёт созданную задачу в `post`-хуки команды
+pub fn export_created(key: &str, summary: &str) {
+    hooks::export("YOU_ISSUE_KEY", key);
+    hooks::export("YOU_ISSUE_SUMMARY", summary);
+}
+
+/// Прикрепляет файлы с диска к задаче
+#[instrument]
+async fn execute_attach(issue_key: &IssueKey, paths: &[PathBuf]) -> Result<()> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let name = path
+            .file_name()
+            .with_context(|| format!("{}: не файл", path.display()))?
+            .to_string_lossy()
+            .into_owned();
+        let data = std::fs::read(path)
+            .with_context(|| format!("Не удалось прочитать {}", path.display()))?;
+        files.push((name, data));
+    }
+    let client = settings::tracker_client()?;
+    upload_files(&client, issue_key, files).await
+}
+
+/// Загружает вложения по одному с полосой прогресса; ошибка одного файла не
+/// останавливает остальные
+async fn upload_files(
+    client: &TrackerClient,
+    issue_key: &IssueKey,
+    files: Vec<(String, Vec<u8>)>,
+) -> Result<()> {
+    let mut failed = 0;
+    for (name, data) in files {
+        let result = client
+            .upload_attachment_with_progress(issue_key, &name, data, progress::upload_bar(&name))
+            .await;
+        progress::finish_bar();
+        match result {
+            Ok(_) => println!("✓ {name}"),
+            Err(err) => {
+                failed += 1;
+                warn!(error = %err, name, "Не удалось загрузить вложение");
+                println!("✗ {name}: {err}");
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("Не загружено вложений: {failed}");
+    }
+    Ok(())
+}
+
+/// Спрашивает в терминале подтверждение; любой ответ, кроме «y»/«да», — отказ
+pub(crate) fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "да" | "д")
+}
+
+/// Показывает, как изменятся теги задач запроса, и после подтверждения применяет изменение
+///
+/// Команда завершается ошибкой, если хотя бы в одной задаче теги не изменились.
+#[instrument(skip(change))]
+async fn execute_tags(
+    query: &str,
+    change: &TagChange,
+    limit: usize,
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let issues = summarize::fetch_issues(&client, query, limit).await?;
+    let plans = tags::plan(&issues, change);
+    for plan in &plans {
+        println!("{plan}");
+    }
+    let changed: Vec<_> = plans.into_iter().filter(|plan| plan.changes()).collect();
+    println!(
+        "Найдено задач: {}, изменятся теги: {}",
+        issues.len(),
+        changed.len()
+    );
+    if changed.is_empty() || dry_run {
+        return Ok(());
+    }
+    if !yes && !confirm("Изменить теги?") {
+        println!("Теги не изменены");
+        return Ok(());
+    }
+
+    let outcomes = tags::apply(&client, &changed, change).await?;
+    let mut failed = 0;
+    for (plan, outcome) in &outcomes {
+        match outcome {
+            TagOutcome::Applied => println!("✓ {}", plan.key),
+            TagOutcome::NotApplied { actual } => {
+                failed += 1;
+                println!("✗ {}: теги сейчас {}", plan.key, actual.join(", "));
+            }
+            TagOutcome::Missing => {
+                failed += 1;
+                println!("✗ {}: задача не найдена", plan.key);
+            }
+        }
+    }
+    info!(changed = outcomes.len() - failed, failed, "Теги изменены");
+    if failed > 0 {
+        bail!("Теги не изменены в задачах: {failed}");
+    }
+    Ok(())
+}
+
+/// Выводит пользователей из справочника, подходящих под запрос
+#[instrument]
+async fn execute_users(query: &str, limit: usize, refresh: bool) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let directory = people::cached(&client, refresh).await?;
+
+    let found = directory.search(query);
+    if found.is_empty() {
+        println!("Пользователи по запросу «{query}» не найдены");
+        return Ok(());
+    }
+    for person in found.iter().take(limit) {
+        println!(
+            "{:<24} {}",
+            person.login,
+            person.display.as_deref().unwrap_or("")
+        );
+    }
+    if found.len() > limit {
+        println!("… и ещё {}", found.len() - limit);
+    }
+    Ok(())
+}
+
+/// Выводит предложенную классификацию для каждой неразобранной задачи очереди
+///
+/// Команда ничего не меняет в трекере; применить подсказки можно на экране Triage в TUI.
+#[instrument]
+async fn execute_triage(queue: &QueueKey, model: &str) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let llm = LlmClient::new(LlmConfig::new(model)?)?;
+
+    let issues = triage::fetch_untriaged(&client, queue).await?;
+    if issues.is_empty() {
+        println!("В очереди {queue} нет неразобранных задач");
+        return Ok(());
+    }
+
+    for issue in &issues {
+        println!("{} — {}", issue.key, issue.summary);
+        match triage::suggest(&llm, issue).await {
+            Ok(suggestion) => println!("{}\n", suggestion.describe()),
+            Err(err) => {
+                warn!(issue_key = %issue.key, error = %err, "Не удалось получить классификацию");
+                println!("Ошибка LLM: {err}\n");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Ищет задачи в локальном индексе и выводит их по убыванию релевантности
+#[instrument]
+async fn execute_find(
+    text: &str,
+    limit: usize,
+    embedding_model: Option<&str>,
+    sync: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let index = IssueIndex::open_default()?;
+    if sync {
+        let client = settings::tracker_client()?;
+        index::sync(&index, &client, false).await?;
+    }
+    let llm = embedding_model
+        .map(|model| LlmClient::new(LlmConfig::new(model)?))
+        .transpose()?;
+
+    let today = timezone::current().today();
+    let snoozes = Snoozes::load();
+    let fetch_limit = limit + snoozes.hidden_count(today);
+    let mut hits = index::find(&index, text, fetch_limit, llm.as_ref()).await?;
+    hits.retain(|hit| !snoozes.is_snoozed(&hit.key, today));
+    hits.truncate(limit);
+    if output == OutputFormat::Json {
+        let hits: Vec<HitOutput> = hits.iter().map(HitOutput::from).collect();
+        return output::print_json(&hits);
+    }
+    if hits.is_empty() {
+        println!("Ничего не найдено в {} задачах индекса", index.count()?);
+        return Ok(());
+    }
+    let renderer = Renderer::stdout();
+    for hit in hits {
+        let status = hit.status.as_deref().unwrap_or("Неизвестен");
+        println!(
+            "{}  [{}]  {}",
+            renderer.key(&hit.key),
+            renderer.status(status),
+            hit.summary
+        );
+    }
+    Ok(())
+}
+
+/// Синхронизирует локальный индекс с трекером
+#[instrument]
+async fn execute_sync(full: bool) -> Result<()> {
+    let index = IssueIndex::open_default()?;
+    let client = settings::tracker_client()?;
+    let synced = index::sync(&index, &client, full).await?;
+    println!(
+        "Обновлено задач: {synced}, всего в индексе: {}",
+        index.count()?
+    );
+    Ok(())
+}
+
+/// Связывает открытые PR текущего репозитория с задачами из их названий и коммитов
+#[instrument]
+async fn execute_sync_prs(
+    forge: Option<Forge>,
+    status: &str,
+    limit: usize,
+    dry_run: bool,
+) -> Result<()> {
+    let forge = match forge {
+        Some(forge) => forge,
+        None => Forge::detect().await?,
+    };
+    let client = settings::tracker_client()?;
+    let report = prs::sync(&client, forge, limit, status, dry_run).await?;
+    if report.is_empty() {
+        println!("В открытых PR не найдено ключей задач");
+    }
+    for line in report {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Строка CSV для `comment-bulk`
+#[derive(Deserialize)]
+struct CommentRow {
+    key: IssueKey,
+    text: String,
+}
+
+/// Добавляет комментарии из CSV или один текст во все найденные задачи
+///
+/// Ошибка одной задачи не останавливает остальные; команда завершается ошибкой, если
+/// хотя бы один комментарий не добавлен.
+#[instrument]
+async fn execute_comment_bulk(
+    file: Option<&Path>,
+    query: Option<&str>,
+    text: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    const PAGE_SIZE: u32 = 100;
+
+    let client = settings::tracker_client()?;
+    let items = match (file, query, text) {
+        (Some(file), _, _) => {
+            let mut reader = csv::Reader::from_path(file)
+                .with_context(|| format!("Не удалось открыть {}", file.display()))?;
+            let mut items = Vec::new();
+            for row in reader.deserialize::<CommentRow>() {
+                let row = row.with_context(|| format!("Неверная строка в {}", file.display()))?;
+                items.push(BulkComment::new(row.key, row.text));
+            }
+            items
+        }
+        (None, Some(query), Some(text)) => {
+            let request = SearchRequest {
+                query: Some(query.to_string()),
+                ..Default::default()
+            };
+            let mut items = Vec::new();
+            for page in 1.. {
+                let params = SearchParams {
+                    per_page: Some(PAGE_SIZE),
+                    page: Some(page),
+                    ..Default::default()
+                };
+                let batch = client.search_issues(&request, Some(params)).await?;
+                let last = batch.len() < PAGE_SIZE as usize;
+                for issue in &batch {
+                    items.push(BulkComment::new(issue.issue_key()?, text));
+                }
+                if last {
+                    break;
+                }
+            }
+            items
+        }
+        _ => bail!("Укажите --file или --query вместе с --text"),
+    };
+
+    if items.is_empty() {
+        println!("Нет задач для комментариев");
+        return Ok(());
+    }
+    if dry_run {
+        for item in &items {
+            println!("{}: {}", item.issue_key, item.request.text);
+        }
+        println!("Будет добавлено комментариев: {}", items.len());
+        return Ok(());
+    }
+
+    let report = client.add_comments_bulk(&items).await;
+    println!("{report}");
+    if !report.is_success() {
+        bail!("Не добавлено комментариев: {}", report.failed.len());
+    }
+    Ok(())
+}
+
+/// Действия `tracker stale` над найденными задачами
+#[derive(Debug)]
+struct StaleActions<'a> {
+    ping: bool,
+    message: Option<&'a str>,
+    move_to: Option<&'a str>,
+    model: &'a str,
+    yes: bool,
+    dry_run: bool,
+}
+
+/// Выводит зависшие задачи очереди и выполняет над ними выбранные действия
+///
+/// Напоминания отправляются через `add_comments_bulk` с призывом исполнителя.
+/// Команда завершается ошибкой, если хотя бы одно действие не удалось.
+#[instrument]
+async fn execute_stale(
+    queue: &QueueKey,
+    days: i64,
+    statuses: &[String],
+    limit: usize,
+    actions: StaleActions<'_>,
+) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let found = stale::find(&client, queue.as_ref(), statuses, days, limit).await?;
+    if found.is_empty() {
+        println!("В очереди {queue} нет задач без работы дольше {days} дн.");
+        return Ok(());
+    }
+    let now = Utc::now().fixed_offset();
+    for item in &found {
+        println!("{}", item.line(now));
+    }
+    if !actions.ping && actions.move_to.is_none() {
+        return Ok(());
+    }
+
+    let mut nudges = Vec::new();
+    if actions.ping {
+        let llm = match actions.message {
+            Some(_) => None,
+            None => Some(LlmClient::new(LlmConfig::new(actions.model)?)?),
+        };
+        let language = Config::load()?.language.enforce;
+        println!();
+        for item in &found {
+            let text = match &llm {
+                Some(llm) => {
+                    let comments = client.get_comments(&item.issue.issue_key()?).await?;
+                    let draft = stale::draft_nudge(llm, item, comments.last()).await?;
+                    language::enforce(llm, language, &draft).await?
+                }
+                None => actions.message.unwrap_or_default().to_string(),
+            };
+            println!("{}: {text}", item.issue.key);
+            let mut nudge = BulkComment::new(item.issue.issue_key()?, text);
+            nudge
+                .request
+                .summonees
+                .extend(item.summonee().map(str::to_string));
+            nudges.push(nudge);
+        }
+    }
+
+    if actions.dry_run {
+        println!("\nИзменения не внесены (--dry-run)");
+        return Ok(());
+    }
+    if !actions.yes && !confirm(&format!("\nВыполнить действия для {} задач?", found.len()))
+    {
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    if !nudges.is_empty() {
+        let report = client.add_comments_bulk(&nudges).await;
+        println!("{report}");
+        failed += report.failed.len();
+    }
+    if let Some(status) = actions.move_to {
+        let org = org::OrgDirectory::load();
+        let label = org.status_display(status).unwrap_or(status);
+        let mut moved = 0;
+        for item in &found {
+            let key = item.issue.issue_key()?;
+            let transitions = client.get_transitions(&key).await?;
+            let Some(transition) = prs::transition_to(&transitions, status) else {
+                println!("  {key}: нет перехода в {label}");
+                failed += 1;
+                continue;
+            };
+            match client
+                .execute_transition(
+                    &key,
+                    &transition.id,
+                    &ExecuteTransitionRequest::default(),
+                    item.issue.version,
+                )
+                .await
+            {
+                Ok(_) => moved += 1,
+                Err(err) => {
+                    warn!(issue_key = %key, error = %err, "Задача не переведена");
+                    println!("  {key}: {err}");
+                    failed += 1;
+                }
+            }
+        }
+        println!("Переведено в {label}: {moved} из {}", found.len());
+    }
+    if failed > 0 {
+        bail!("Не выполнено действий: {failed}");
+    }
+    Ok(())
+}
+
+/// Строит граф зависимостей задачи и выводит его в выбранном формате
+#[instrument]
+async fn execute_graph(issue_id: &str, depth: usize, format: GraphFormat) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let graph = client.build_dependency_graph(issue_id, depth).await?;
+    let output = match format {
+        GraphFormat::Tree => graph.render_tree(),
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+    };
+    println!("{output}");
+    Ok(())
+}
+
+/// Создаёт задачи из экспорта Jira; повторный запуск не создаёт дублей
+#[instrument]
+async fn execute_import(
+    from: &Path,
+    queue: &QueueKey,
+    mapping: Option<&Path>,
+    dry_run: bool,
+) -> Result<()> {
+    let mapping = FieldMapping::load(mapping)?;
+    let issues = jira::read_export(from)?;
+    if issues.is_empty() {
+        println!("В {} нет задач", from.display());
+        return Ok(());
+    }
+    let client = settings::tracker_client()?;
+    for line in jira::import(&client, &issues, queue, &mapping, dry_run).await? {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Выгружает найденные задачи в JSON формата Jira
+#[instrument]
+async fn execute_export(query: &str, to: &Path, mapping: Option<&Path>) -> Result<()> {
+    const PAGE_SIZE: u32 = 100;
+
+    let mapping = FieldMapping::load(mapping)?;
+    let client = settings::tracker_client()?;
+    let request = SearchRequest {
+        query: Some(query.to_string()),
+        ..Default::default()
+    };
+
+    let mut issues = Vec::new();
+    for page in 1.. {
+        let params = SearchParams {
+            per_page: Some(PAGE_SIZE),
+            page: Some(page),
+            ..Default::default()
+        };
+        let batch = client.search_issues(&request, Some(params)).await?;
+        let last = batch.len() < PAGE_SIZE as usize;
+        issues.extend(batch.iter().map(|issue| jira::to_jira(issue, &mapping)));
+        if last {
+            break;
+        }
+    }
+
+    let content = serde_json::to_string_pretty(&serde_json::json!({ "issues": issues }))?;
+    std::fs::write(to, content).with_context(|| format!("Не удалось записать {}", to.display()))?;
+    println!("Выгружено задач: {} в {}", issues.len(), to.display());
+    Ok(())
+}
+
+/// Выводит ленту активности очереди за период `since`
+#[instrument]
+async fn execute_feed(queue: &QueueKey, since: Duration) -> Result<()> {
+    let client = settings::tracker_client()?;
+    let since = Utc::now().fixed_offset() - since;
+    // Трекер фильтрует по дате, точное время отсекается при выводе
+    let activity = client
+        .get_queue_activity(queue, &since.format("%Y-%m-%d").to_string())
+        .await?;
+
+    let lines = feed::render(&activity, since);
+    if lines.is_empty() {
+        println!("В очереди {queue} нет изменений за этот период");
+    }
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Сохраняет напоминание о задаче и, если нужно, событие календаря
+#[instrument(skip(text))]
+fn execute_remind(
+    issue_key: &IssueKey,
+    text: &str,
+    delay: Duration,
+    ics: Option<&Path>,
+) -> Result<()> {
+    let mut reminders = Reminders::load();
+    let due = Utc::now().fixed_offset() + delay;
+    let reminder = reminders
+        .add(issue_key.clone(), text.to_string(), due)
+        .clone();
+    reminders.save()?;
+
+    if let Some(path) = ics {
+        let event = reminder
+            .calendar_event()
+            .context("Некорректный срок напоминания")?;
+        std::fs::write(path, calendar_lib::ics::to_ics(&[event]))
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        println!("Событие календаря записано в {}", path.display());
+    }
+    println!(
+        "Напоминание #{} о {issue_key}: {}",
+        reminder.id,
+        due.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+    Ok(())
+}
+
+/// Откладывает задачу до дня `until`, возвращает её с `off` или выводит отложенные задачи
+#[instrument]
+fn execute_snooze(issue_key: Option<&IssueKey>, until: Option<NaiveDate>, off: bool) -> Result<()> {
+    let mut snoozes = Snoozes::load();
+    let Some(issue_key) = issue_key else {
+        if snoozes.issues.is_empty() {
+            println!("Отложенных задач нет");
+        }
+        for (key, until) in &snoozes.issues {
+            println!("{key}  до {}", until.format("%d.%m.%Y"));
+        }
+        return Ok(());
+    };
+    if off {
+        if !snoozes.wake(issue_key) {
+            bail!("Задача {issue_key} не отложена");
+        }
+        snoozes.save()?;
+        println!("{issue_key} снова в списках");
+        return Ok(());
+    }
+    let until = until.context("Укажите --until или --off")?;
+    if until <= timezone::current().today() {
+        bail!("День {until} уже наступил");
+    }
+    snoozes.snooze(issue_key, until);
+    snoozes.save()?;
+    println!(
+        "{issue_key} скрыта до {}; в этот день придёт уведомление",
+        until.format("%d.%m.%Y")
+    );
+    Ok(())
+}
+
+/// Выводит напоминания; `snooze` переносит срок, `done` отмечает выполненным
+#[instrument]
+fn execute_reminders(
+    all: bool,
+    snooze: Option<u64>,
+    snooze_for: Duration,
+    done: Option<u64>,
+) -> Result<()> {
+    let mut reminders = Reminders::load();
+    if let Some(id) = snooze {
+        let reminder = reminders.snooze(id, Utc::now().fixed_offset() + snooze_for)?;
+        println!("Отложено: {}", reminder.line());
+        return reminders.save();
+    }
+    if let Some(id) = done {
+        let reminder = reminders.complete(id)?;
+        println!("Выполнено: {}", reminder.line());
+        return reminders.save();
+    }
+
+    let shown: Vec<_> = if all {
+        reminders.reminders.iter().collect()
+    } else {
+        reminders.active()
+    };
+    if shown.is_empty() {
+        println!("Напоминаний нет");
+    }
+    for reminder in shown {
+        println!("{}", reminder.line());
+    }
+    Ok(())
+}
@@ -0,0 +1,219 @@
+//! Разбор входящих задач с подсказками LLM
+//!
+//! Общая логика для команды `you tracker triage` и экрана разбора в TUI:
+//! поиск неразобранных задач, запрос классификации у LLM и применение результата.
+
+use anyhow::{Context, Result};
+use llm_lib::LlmClientTrait;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, info, instrument};
+use tracker_lib::{
+    ids::{IssueKey, QueueKey},
+    models::Issue,
+    search::{SearchParams, SearchRequest},
+    task::UpdateIssueRequest,
+    TrackerClient,
+};
+
+use crate::llm::extract_json_object;
+use crate::redact;
+
+/// Ключи типов задач, из которых выбирает LLM
+pub const ISSUE_TYPES: &[&str] = &["bug", "task", "improvement", "newFeature"];
+
+/// Ключи приоритетов от самого высокого к самому низкому
+pub const PRIORITIES: &[&str] = &["blocker", "critical", "normal", "minor", "trivial"];
+
+const MAX_UNTRIAGED: u32 = 50;
+
+const SYSTEM_PROMPT: &str = "Ты помогаешь разбирать входящие задачи в трекере. \
+Ответь только JSON объектом вида \
+{\"type\": \"...\", \"priority\": \"...\", \"tags\": [\"...\"], \"reason\": \"...\"} без пояснений.";
+
+/// Предложенная классификация задачи
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TriageSuggestion {
+    #[serde(rename = "type")]
+    pub issue_type: String,
+    pub priority: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl TriageSuggestion {
+    /// Переключает тип задачи на следующий из `ISSUE_TYPES`
+    pub fn cycle_type(&mut self) {
+        self.issue_type = next_value(ISSUE_TYPES, &self.issue_type);
+    }
+
+    /// Переключает приоритет на следующий из `PRIORITIES`
+    pub fn cycle_priority(&mut self) {
+        self.priority = next_value(PRIORITIES, &self.priority);
+    }
+
+    /// Тело PATCH запроса для применения классификации
+    pub fn update_body(&self) -> UpdateIssueRequest {
+        UpdateIssueRequest::new()
+            .set("type", json!({ "key": self.issue_type }))
+            .set("priority", json!({ "key": self.priority }))
+            .add("tags", self.tags.iter().map(String::as_str))
+    }
+
+    pub fn describe(&self) -> String {
+        let tags = if self.tags.is_empty() {
+            "—".to_string()
+        } else {
+            self.tags.join(", ")
+        };
+        format!(
+            "**Тип:** `{}`\n**Приоритет:** `{}`\n**Теги:** {tags}\n**Почему:** {}",
+            self.issue_type, self.priority, self.reason
+        )
+    }
+}
+
+fn next_value(values: &[&str], current: &str) -> String {
+    let index = values
+        .iter()
+        .position(|value| *value == current)
+        .map_or(0, |index| (index + 1) % values.len());
+    values[index].to_string()
+}
+
+/// Запрос неразобранных задач: без исполнителя и без резолюции
+pub fn untriaged_query(queue: &QueueKey) -> String {
+    format!("Queue: {queue} Assignee: empty() Resolution: empty() \"Sort by\": Created ASC")
+}
+
+#[instrument(skip(client))]
+pub async fn fetch_untriaged(client: &TrackerClient, queue: &QueueKey) -> Result<Vec<Issue>> {
+    let request = SearchRequest {
+        query: Some(untriaged_query(queue)),
+        ..Default::default()
+    };
+    let params = SearchParams {
+        per_page: Some(MAX_UNTRIAGED),
+        ..Default::default()
+    };
+    let issues = client.search_issues(&request, Some(params)).await?;
+    info!(count = issues.len(), "Найдены неразобранные задачи");
+    Ok(issues)
+}
+
+#[instrument(skip(llm, issue), fields(issue_key = %issue.key))]
+pub async fn suggest<T: LlmClientTrait>(llm: &T, issue: &Issue) -> Result<TriageSuggestion> {
+    let issue = redact::issue(issue);
+    let prompt = format!(
+        "Задача {}: {}\n\n{}\n\nДопустимые типы: {}\nДопустимые приоритеты: {}",
+        issue.key,
+        issue.summary,
+        issue.description.as_deref().unwrap_or("(без описания)"),
+        ISSUE_TYPES.join(", "),
+        PRIORITIES.join(", "),
+    );
+    let response = llm
+        .complete_with_system(SYSTEM_PROMPT.to_string(), prompt)
+        .await?;
+    debug!(response = %response, "Ответ LLM получен");
+    parse_suggestion(&response)
+}
+
+/// Извлекает JSON объект из ответа LLM, даже если он обёрнут в текст или блок кода
+pub fn parse_suggestion(response: &str) -> Result<TriageSuggestion> {
+    let mut suggestion: TriageSuggestion = serde_json::from_str(extract_json_object(response)?)
+        .context("Не удалось разобрать классификацию из ответа LLM")?;
+
+    if !ISSUE_TYPES.contains(&suggestion.issue_type.as_str()) {
+        suggestion.issue_type = "task".to_string();
+    }
+    if !PRIORITIES.contains(&suggestion.priority.as_str()) {
+        suggestion.priority = "normal".to_string();
+    }
+    Ok(suggestion)
+}
+
+#[instrument(skip(client, suggestion))]
+pub async fn apply(
+    client: &TrackerClient,
+    issue_key: &IssueKey,
+    suggestion: &TriageSuggestion,
+) -> Result<Issue> {
+    let issue = client
+        .update_issue(issue_key, &suggestion.update_body(), None)
+        .await?;
+    info!("Классификация применена");
+    Ok(issue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use llm_lib::MockLlmClientTrait;
+
+    fn issue() -> Issue {
+        testing::issue("TREK-1")
+            .summary("Падает экспорт")
+            .description("NPE при экспорте в CSV")
+            .build()
+    }
+
+    #[test]
+    fn test_parse_suggestion_from_code_block() {
+        let response = "```json\n{\"type\": \"bug\", \"priority\": \"critical\", \"tags\": [\"export\"], \"reason\": \"падение\"}\n```";
+        let suggestion = parse_suggestion(response).unwrap();
+        assert_eq!(suggestion.issue_type, "bug");
+        assert_eq!(suggestion.priority, "critical");
+        assert_eq!(suggestion.tags, vec!["export"]);
+    }
+
+    #[test]
+    fn test_parse_suggestion_falls_back_for_unknown_values() {
+        let suggestion = parse_suggestion("{\"type\": \"chore\", \"priority\": \"asap\"}").unwrap();
+        assert_eq!(suggestion.issue_type, "task");
+        assert_eq!(suggestion.priority, "normal");
+        assert!(parse_suggestion("не знаю").is_err());
+    }
+
+    #[test]
+    fn test_cycle_and_update_body() {
+        let mut suggestion =
+            parse_suggestion("{\"type\": \"newFeature\", \"priority\": \"trivial\"}").unwrap();
+        suggestion.cycle_type();
+        suggestion.cycle_priority();
+        assert_eq!(suggestion.issue_type, "bug");
+        assert_eq!(suggestion.priority, "blocker");
+        assert_eq!(
+            serde_json::to_value(suggestion.update_body()).unwrap(),
+            json!({
+                "type": { "key": "bug" },
+                "priority": { "key": "blocker" },
+                "tags": { "add": [] },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suggest_uses_llm_response() {
+        let mut mock_client = MockLlmClientTrait::new();
+        mock_client
+            .expect_complete_with_system()
+            .times(1)
+            .returning(|_, prompt| {
+                assert!(prompt.contains("TREK-1"));
+                Box::pin(async {
+                    Ok(
+                        "{\"type\": \"bug\", \"priority\": \"normal\", \"reason\": \"ошибка\"}"
+                            .to_string(),
+                    )
+                })
+            });
+
+        let suggestion = suggest(&mock_client, &issue()).await.unwrap();
+        assert_eq!(suggestion.issue_type, "bug");
+        assert_eq!(suggestion.reason, "ошибка");
+    }
+}
@@ -1,36 +1,147 @@
-use std::{io, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use llm_lib::events::LlmEvent;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Clear, Paragraph, Wrap},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
+use tracker_lib::events::{RateLimit, TrackerEvent};
+use tracker_lib::ids::IssueKey;
+
+use crate::{
+    drafts::Drafts, inbox::Inbox, llm::DEFAULT_MODEL, notify::Notifier, personas::Persona,
+    prompts::PromptPreset, queues::LiveRules, reminders, settings,
 };
 
 use super::{
-    menu::{Menu, MenuAction},
-    screens::{llm::LlmScreen, tracker::TrackerScreen, Screen, ScreenEvent, ScreenId},
+    bus::{AppEvent, EventBus},
+    chart,
+    dashboard::{Dashboard, DashboardAction},
+    focus::{FinishedFocus, FocusTimer},
+    layout::SplitLayout,
+    markdown,
+    output::OutputView,
+    screens::{
+        calendar::CalendarScreen, create::CreateScreen, inbox::InboxScreen, llm::LlmScreen,
+        sprint::SprintScreen, timesheet::TimesheetScreen, tracker::TrackerScreen,
+        triage::TriageScreen, ScreenEvent, ScreenId, ScreenRegistry,
+    },
+    session::{SavedScreen, SessionState},
+    setup::{Service, SetupAction, SetupScreen},
+    status_bar::StatusBar,
+    theme::Theme,
+    toast::{ToastLevel, Toasts},
+    transcript::SessionLog,
 };
 
+/// Сколько ближайших встреч показывать на главном экране
+const UPCOMING_MEETINGS: usize = 3;
+/// Период тактов: истечение уведомлений, фокус-таймер и перерисовка без ввода
+const TICK_INTERVAL: Duration = Duration::from_millis(250);
+/// Как часто проверять сроки напоминаний
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 enum ActiveView {
-    Menu,
+    Dashboard,
+    /// Экран настройки при ошибках инициализации клиентов
+    Setup,
     Screen(ScreenId),
 }
 
 pub struct App {
     active_view: ActiveView,
-    menu: Menu,
-    tracker: TrackerScreen,
-    llm: LlmScreen,
+    dashboard: Dashboard,
+    setup: SetupScreen,
+    screens: ScreenRegistry,
+    outputs: HashMap<ScreenId, OutputView>,
+    theme: Theme,
+    layout: SplitLayout,
+    refresh_interval: Option<Duration>,
+    last_refresh: Instant,
+    /// None до первой проверки напоминаний
+    last_reminder_check: Option<Instant>,
+    status_bar: StatusBar,
+    toasts: Toasts,
+    /// Уведомления на рабочем столе о напоминаниях в дополнение к всплывающим сообщениям
+    notifier: Notifier,
+    focus: Option<FocusTimer>,
+    focus_length: Duration,
+    /// Завершённый фокус, ожидающий подтверждения списания времени
+    focus_prompt: Option<FinishedFocus>,
+    /// Журнал команд и вывода экранов; None — журнал не пишется
+    session_log: Option<SessionLog>,
+    bus: EventBus,
+    tracker_sender: UnboundedSender<TrackerEvent>,
+    app_events: UnboundedReceiver<AppEvent>,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(
+        theme: Theme,
+        layout: SplitLayout,
+        refresh_interval: Option<Duration>,
+        focus_length: Duration,
+        notifier: Notifier,
+    ) -> Self {
+        let (tracker_sender, tracker_events) = mpsc::unbounded_channel();
+        let (llm_sender, llm_events) = mpsc::unbounded_channel();
+        let (bus, app_events) = EventBus::new();
+        bus.forward(tracker_events, AppEvent::Tracker);
+        bus.forward(llm_events, AppEvent::Llm);
+
+        // Порядок регистрации задаёт порядок меню на главном экране
+        let mut screens = ScreenRegistry::default();
+        screens.register(TrackerScreen::new(tracker_sender.clone(), bus.clone()));
+        screens.register(LlmScreen::new(llm_sender.clone()));
+        screens.register(CalendarScreen::new());
+        screens.register(TriageScreen::new(
+            tracker_sender.clone(),
+            llm_sender.clone(),
+            bus.clone(),
+        ));
+        screens.register(TimesheetScreen::new(tracker_sender.clone(), bus.clone()));
+        screens.register(SprintScreen::new(tracker_sender.clone(), bus.clone()));
+        screens.register(InboxScreen::new(tracker_sender.clone(), bus.clone()));
+        screens.register(CreateScreen::new(
+            tracker_sender.clone(),
+            llm_sender,
+            bus.clone(),
+        ));
+
         Self {
-            active_view: ActiveView::Menu,
-            menu: Menu::new(),
-            tracker: TrackerScreen::new(),
-            llm: LlmScreen::new(),
+            active_view: ActiveView::Dashboard,
+            dashboard: Dashboard::new(screens.metas(), tracker_sender.clone(), bus.clone()),
+            setup: SetupScreen::new(),
+            outputs: screens
+                .ids()
+                .into_iter()
+                .map(|id| (id, OutputView::default()))
+                .collect(),
+            screens,
+            theme,
+            layout,
+            refresh_interval,
+            last_refresh: Instant::now(),
+            last_reminder_check: None,
+            status_bar: StatusBar::new(DEFAULT_MODEL),
+            toasts: Toasts::default(),
+            notifier,
+            focus: None,
+            focus_length,
+            focus_prompt: None,
+            session_log: None,
+            bus,
+            tracker_sender,
+            app_events,
         }
     }
 
@@ -38,113 +149,681 @@ impl App {
         mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ) -> Result<()> {
+        self.dashboard.refresh_issues();
+
+        let return_to = match self.active_view {
+            ActiveView::Screen(screen_id) => Some(screen_id),
+            ActiveView::Dashboard | ActiveView::Setup => None,
+        };
+        if self.setup.check(&Service::ALL, return_to) {
+            self.active_view = ActiveView::Setup;
+        }
+
+        self.bus.spawn_terminal_input();
+        self.bus.spawn_ticks(TICK_INTERVAL);
+
         loop {
             terminal.draw(|f| self.draw(f))?;
 
-            if !event::poll(Duration::from_millis(100))? {
-                continue;
+            let Some(event) = self.app_events.recv().await else {
+                return Ok(());
+            };
+            if self.update(event).await? || self.process_pending().await? {
+                self.save_session();
+                return Ok(());
             }
+        }
+    }
 
-            let Event::Key(key) = event::read()? else {
+    /// Разбирает накопившиеся события шины без ожидания новых; true — пора завершаться
+    pub(super) async fn process_pending(&mut self) -> Result<bool> {
+        while let Ok(event) = self.app_events.try_recv() {
+            if self.update(event).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Обрабатывает одно событие шины; true — пора завершаться
+    pub(super) async fn update(&mut self, event: AppEvent) -> Result<bool> {
+        match event {
+            AppEvent::Key(key) => {
+                if key.kind != KeyEventKind::Press {
+                    return Ok(false);
+                }
+                if !self.captures_keys() && self.handle_global_key(key.code) {
+                    return Ok(true);
+                }
+                self.handle_key(key).await;
+            }
+            AppEvent::Tick => {
+                self.toasts.expire(Instant::now());
+                self.tick_focus();
+                self.auto_refresh();
+                self.check_reminders();
+            }
+            AppEvent::InputFailed(err) => return Err(err.into()),
+            AppEvent::Tracker(event) => self.apply_tracker_event(event),
+            AppEvent::Llm(event) => self.apply_llm_event(event),
+            AppEvent::Toast { level, text } => self.toasts.push(level, text),
+            AppEvent::IssueCountsLoaded(result) => self.dashboard.apply_issue_counts(result),
+            AppEvent::IssuesRefreshed(updates) => {
+                if let Some(tracker) = self.screens.find_mut::<TrackerScreen>() {
+                    tracker.apply_refresh(updates);
+                }
+            }
+            AppEvent::LlmContext { label, text } => {
+                if let Some(llm) = self.screens.find_mut::<LlmScreen>() {
+                    llm.attach_context(label, text);
+                    self.open_screen(ScreenId::Llm);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Передаёт экрану LLM библиотеку промптов с пресетами из конфигурации
+    pub fn set_prompts(&mut self, prompts: BTreeMap<String, PromptPreset>) {
+        if let Some(llm) = self.screens.find_mut::<LlmScreen>() {
+            llm.set_prompts(prompts);
+        }
+    }
+
+    /// Передаёт экрану LLM персоны с персонами из конфигурации
+    pub fn set_personas(&mut self, personas: BTreeMap<String, Persona>) {
+        if let Some(llm) = self.screens.find_mut::<LlmScreen>() {
+            llm.set_personas(personas);
+        }
+    }
+
+    /// Передаёт экрану Tracker глубину предпросмотра ссылок на задачи
+    pub fn set_link_depth(&mut self, depth: usize) {
+        if let Some(tracker) = self.screens.find_mut::<TrackerScreen>() {
+            tracker.set_link_depth(depth);
+        }
+    }
+
+    /// Передаёт экрану Tracker готовый клиент вместо клиента из окружения
+    #[cfg(test)]
+    pub fn set_tracker_client(&mut self, client: tracker_lib::TrackerClient) {
+        if let Some(tracker) = self.screens.find_mut::<TrackerScreen>() {
+            tracker.set_client(client);
+        }
+        self.setup.set_tracker_provided();
+    }
+
+    /// Включает автосохранение черновиков комментариев и запросов к LLM
+    pub fn set_drafts(&mut self, drafts: Drafts) {
+        if let Some(tracker) = self.screens.find_mut::<TrackerScreen>() {
+            tracker.set_drafts(drafts.clone());
+        }
+        if let Some(llm) = self.screens.find_mut::<LlmScreen>() {
+            llm.set_drafts(drafts);
+        }
+    }
+
+    /// Передаёт экрану входящих правила уведомлений по очередям
+    pub fn set_notify_rules(&mut self, rules: LiveRules) {
+        if let Some(inbox) = self.screens.find_mut::<InboxScreen>() {
+            inbox.set_notify_rules(rules);
+        }
+    }
+
+    /// Включает журнал сессии: команды и вывод всех экранов дописываются в файл
+    pub fn set_session_log(&mut self, log: SessionLog) {
+        self.session_log = Some(log);
+    }
+
+    /// Включает сохранение диалога экрана LLM вместе с ветками
+    pub fn set_chat_path(&mut self, path: PathBuf) {
+        if let Some(llm) = self.screens.find_mut::<LlmScreen>() {
+            llm.set_chat_path(path);
+        }
+    }
+
+    /// Восстанавливает экраны, прокрутку и поиск из прошлой сессии
+    pub fn restore_session(&mut self, mut state: SessionState) {
+        for id in self.screens.ids() {
+            let (Some(saved), Some(screen)) = (state.screens.remove(&id), self.screens.get_mut(id))
+            else {
                 continue;
             };
+            screen.restore(saved.snapshot);
+            self.outputs
+                .insert(id, OutputView::restored(saved.scroll_offset, saved.search));
+        }
+        if let Some(screen_id) = state.active_screen {
+            if self.screens.get(screen_id).is_some() {
+                self.active_view = ActiveView::Screen(screen_id);
+            }
+        }
+        if let Some(list_width) = state.list_width {
+            self.layout.set_list_percent(list_width);
+        }
+    }
 
-            if key.kind != KeyEventKind::Press {
-                continue;
+    fn save_session(&self) {
+        let active_screen = match self.active_view {
+            ActiveView::Dashboard | ActiveView::Setup => None,
+            ActiveView::Screen(screen_id) => Some(screen_id),
+        };
+        let screens = self
+            .screens
+            .ids()
+            .into_iter()
+            .filter_map(|id| Some((id, self.screens.get(id)?)))
+            .map(|(id, screen)| {
+                let view = &self.outputs[&id];
+                let saved = SavedScreen {
+                    snapshot: screen.snapshot(),
+                    scroll_offset: view.offset_from_bottom(),
+                    search: view.search().map(str::to_string),
+                };
+                (id, saved)
+            })
+            .collect();
+
+        let state = SessionState {
+            active_screen,
+            screens,
+            list_width: Some(self.layout.list_percent()),
+        };
+        if let Err(err) = state.save() {
+            warn!(error = %err, "Не удалось сохранить состояние TUI");
+        }
+    }
+
+    fn apply_tracker_event(&mut self, event: TrackerEvent) {
+        match &event {
+            TrackerEvent::RequestFinished {
+                path,
+                success: false,
+            } => self
+                .dashboard
+                .notify(ScreenId::Tracker, format!("Ошибка запроса Tracker: {path}")),
+            TrackerEvent::RateLimitUpdated(RateLimit {
+                retry_after_secs: Some(secs),
+                ..
+            }) => {
+                let message = format!("Лимит запросов Tracker исчерпан, повтор через {secs}с");
+                self.toasts.push(ToastLevel::Warning, message.clone());
+                self.dashboard.notify(ScreenId::Tracker, message);
             }
+            _ => {}
+        }
+        self.status_bar.apply_tracker_event(event);
+    }
 
-            if self.handle_global_key(key.code) {
-                return Ok(());
+    fn apply_llm_event(&mut self, event: LlmEvent) {
+        match &event {
+            LlmEvent::RequestFinished {
+                model,
+                success: false,
+                ..
+            } => self
+                .dashboard
+                .notify(ScreenId::Llm, format!("Ошибка запроса к {model}")),
+            LlmEvent::RateLimitUpdated(llm_lib::events::RateLimit {
+                retry_after_secs: Some(secs),
+                ..
+            }) => {
+                let message = format!("Лимит запросов LLM исчерпан, повтор через {secs}с");
+                self.toasts.push(ToastLevel::Warning, message.clone());
+                self.dashboard.notify(ScreenId::Llm, message);
+            }
+            LlmEvent::KeyRotated { from, to } => {
+                let message = format!("На ключе OpenRouter {from} кончились средства, дальше {to}");
+                self.toasts.push(ToastLevel::Warning, message.clone());
+                self.dashboard.notify(ScreenId::Llm, message);
+            }
+            _ => {}
+        }
+        self.status_bar.apply_llm_event(event);
+    }
+
+    /// Переносит наступившие напоминания во входящие и показывает их на главном экране
+    fn check_reminders(&mut self) {
+        if self
+            .last_reminder_check
+            .is_some_and(|last| last.elapsed() < REMINDER_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.last_reminder_check = Some(Instant::now());
+
+        let mut inbox = Inbox::load();
+        let due = match reminders::deliver_due(&mut inbox).and_then(|due| {
+            if !due.is_empty() {
+                inbox.save()?;
+            }
+            Ok(due)
+        }) {
+            Ok(due) => due,
+            Err(err) => {
+                warn!(error = %err, "Не удалось проверить напоминания");
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+        for notification in &due {
+            let message = format!(
+                "Напоминание {}: {}",
+                notification.issue_key, notification.text
+            );
+            self.toasts.push(ToastLevel::Info, message.clone());
+            self.dashboard.notify(ScreenId::Inbox, message);
+            self.notifier.inbox(notification);
+        }
+        if let Some(inbox_screen) = self.screens.find_mut::<InboxScreen>() {
+            inbox_screen.reload();
+        }
+    }
+
+    /// Периодически обновляет задачи, пока открыт экран Tracker
+    fn auto_refresh(&mut self) {
+        let Some(interval) = self.refresh_interval else {
+            return;
+        };
+        if !matches!(self.active_view, ActiveView::Screen(ScreenId::Tracker))
+            || self.last_refresh.elapsed() < interval
+        {
+            return;
+        }
+        self.last_refresh = Instant::now();
+        if let Some(tracker) = self.screens.find_mut::<TrackerScreen>() {
+            tracker.start_refresh();
+        }
+    }
+
+    /// Обновляет таймер в строке состояния и предлагает списать время по его окончании
+    fn tick_focus(&mut self) {
+        let now = Instant::now();
+        if self
+            .focus
+            .as_ref()
+            .is_some_and(|timer| timer.is_finished(now))
+        {
+            if let Some(timer) = self.focus.take() {
+                self.toasts.push(
+                    ToastLevel::Info,
+                    format!("Фокус на {} завершён", timer.issue_key()),
+                );
+                self.focus_prompt = timer.finish(now);
             }
+        }
+        self.status_bar
+            .set_focus(self.focus.as_ref().map(|timer| timer.describe(now)));
+    }
 
-            self.handle_key(key.code).await;
+    /// Повторный запуск по той же задаче останавливает таймер, по другой — переключает его
+    fn toggle_focus(&mut self, issue_key: String) {
+        let now = Instant::now();
+        if let Some(timer) = self.focus.take() {
+            let same_issue = timer.issue_key() == issue_key;
+            self.focus_prompt = timer.finish(now);
+            if same_issue {
+                return;
+            }
         }
+        self.toasts.push(
+            ToastLevel::Info,
+            format!(
+                "Фокус на {issue_key}: {} мин",
+                self.focus_length.as_secs() / 60
+            ),
+        );
+        self.focus = Some(FocusTimer::start(issue_key, self.focus_length));
+    }
+
+    fn handle_focus_prompt_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter | KeyCode::Char('y') => {
+                if let Some(finished) = self.focus_prompt.take() {
+                    self.log_focus(finished);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('n') => self.focus_prompt = None,
+            _ => {}
+        }
+    }
+
+    fn log_focus(&self, finished: FinishedFocus) {
+        let client = match settings::tracker_client() {
+            Ok(client) => client.with_event_sender(self.tracker_sender.clone()),
+            Err(err) => {
+                self.bus.toast(
+                    ToastLevel::Error,
+                    format!("Не удалось списать время: {err}"),
+                );
+                return;
+            }
+        };
+        let issue_key: IssueKey = match finished.issue_key.parse() {
+            Ok(issue_key) => issue_key,
+            Err(err) => {
+                self.bus.toast(
+                    ToastLevel::Error,
+                    format!("Не удалось списать время: {err}"),
+                );
+                return;
+            }
+        };
+        let bus = self.bus.clone();
+
+        tokio::spawn(async move {
+            let request = finished.worklog_request();
+            match client.add_worklog(&issue_key, &request).await {
+                Ok(_) => bus.toast(ToastLevel::Info, format!("Время списано в {issue_key}")),
+                Err(err) => bus.toast(
+                    ToastLevel::Error,
+                    format!("Не удалось списать время в {issue_key}: {err}"),
+                ),
+            }
+        });
     }
 
     fn handle_global_key(&mut self, code: KeyCode) -> bool {
         match code {
             KeyCode::Char('q') => true,
             KeyCode::Esc => {
-                self.active_view = ActiveView::Menu;
+                self.active_view = ActiveView::Dashboard;
                 false
             }
             _ => false,
         }
     }
 
-    async fn handle_key(&mut self, code: KeyCode) {
+    /// Открытое всплывающее окно или строка поиска перехватывают глобальные клавиши
+    fn captures_keys(&self) -> bool {
+        if self.focus_prompt.is_some() {
+            return true;
+        }
         match self.active_view {
-            ActiveView::Menu => match self.menu.handle_key(code) {
-                MenuAction::None => {}
-                MenuAction::Open(screen_id) => self.active_view = ActiveView::Screen(screen_id),
+            ActiveView::Dashboard => false,
+            ActiveView::Setup => self.setup.is_editing(),
+            ActiveView::Screen(screen_id) => {
+                self.screens
+                    .get(screen_id)
+                    .is_some_and(|screen| screen.popup().is_some())
+                    || self.outputs[&screen_id].is_searching()
+            }
+        }
+    }
+
+    fn handle_output_key(&mut self, screen_id: ScreenId, key: KeyEvent) -> bool {
+        let Some(screen) = self.screens.get(screen_id) else {
+            return false;
+        };
+        if screen.popup().is_some() {
+            return false;
+        }
+        let text = screen.output_text();
+        let typing = !screen.input_text().is_empty();
+
+        let Some(view) = self.outputs.get_mut(&screen_id) else {
+            return false;
+        };
+        if !view.is_searching() && !typing && key.code == KeyCode::Char('/') {
+            view.start_search();
+            return true;
+        }
+        view.handle_key(key, &text)
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) {
+        if self.focus_prompt.is_some() {
+            self.handle_focus_prompt_key(key.code);
+            return;
+        }
+        match self.active_view {
+            ActiveView::Dashboard => match self.dashboard.handle_key(key.code) {
+                DashboardAction::None => {}
+                DashboardAction::Open(screen_id) => {
+                    self.open_screen(screen_id);
+                }
+            },
+            ActiveView::Setup => match self.setup.handle_key(key) {
+                SetupAction::None => {}
+                SetupAction::Done(return_to) => {
+                    self.dashboard.refresh_issues();
+                    self.active_view = match return_to {
+                        Some(screen_id) => ActiveView::Screen(screen_id),
+                        None => ActiveView::Dashboard,
+                    };
+                }
             },
             ActiveView::Screen(screen_id) => {
-                let event = self.screen_mut(screen_id).handle_key(code);
-                if let ScreenEvent::Submit(input) = event {
-                    let command = self.screen(screen_id).command_preview(&input);
-                    self.screen_mut(screen_id).push_output(command);
-                    let response = self.screen_mut(screen_id).execute(input).await;
-                    self.screen_mut(screen_id).push_output(response);
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && matches!(key.code, KeyCode::Left | KeyCode::Right)
+                {
+                    self.layout.resize(key.code == KeyCode::Right);
+                    return;
+                }
+                if self.handle_output_key(screen_id, key) {
+                    return;
+                }
+                let Some(screen) = self.screens.get_mut(screen_id) else {
+                    return;
+                };
+                match screen.handle_key(key) {
+                    ScreenEvent::None => {}
+                    ScreenEvent::Submit(input) => self.submit(screen_id, input).await,
+                    ScreenEvent::Navigate(target, input) => {
+                        if self.open_screen(target) {
+                            self.submit(target, input).await;
+                        }
+                    }
+                    ScreenEvent::ToggleFocus(issue_key) => self.toggle_focus(issue_key),
                 }
             }
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    /// Открывает экран или экран настройки, если нужные клиенты не инициализируются
+    fn open_screen(&mut self, screen_id: ScreenId) -> bool {
+        let Some(screen) = self.screens.get(screen_id) else {
+            return false;
+        };
+        if self.setup.check(screen.meta().requires, Some(screen_id)) {
+            self.active_view = ActiveView::Setup;
+            return false;
+        }
+        self.active_view = ActiveView::Screen(screen_id);
+        true
+    }
+
+    async fn submit(&mut self, screen_id: ScreenId, input: String) {
+        let Some(screen) = self.screens.get_mut(screen_id) else {
+            return;
+        };
+        let command = screen.command_preview(&input);
+        self.dashboard.record_activity(screen_id, command.clone());
+        screen.push_output(command.clone());
+        let response = screen.execute(input).await;
+        if let Some(log) = &mut self.session_log {
+            let title = screen.meta().title;
+            log.write(title, &command);
+            log.write(title, &response);
+        }
+        screen.push_output(response);
+    }
+
+    pub(super) fn draw(&self, frame: &mut Frame) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(5),
                 Constraint::Length(3),
+                Constraint::Length(1),
             ])
             .split(frame.area());
 
-        let (title, output, input_title, input_text) = match self.active_view {
-            ActiveView::Menu => (
-                self.menu.title(),
-                self.menu.output_text(),
-                self.menu.input_title(),
+        let (title, input_title, input_text) = match self.active_view {
+            ActiveView::Dashboard => (
+                self.dashboard.title(),
+                self.dashboard.input_title(),
                 "".to_string(),
             ),
+            ActiveView::Setup => (
+                self.setup.title(),
+                self.setup.input_title().to_string(),
+                self.setup.input_text(),
+            ),
             ActiveView::Screen(screen_id) => {
-                let screen = self.screen(screen_id);
+                let Some(screen) = self.screens.get(screen_id) else {
+                    return;
+                };
                 (
-                    screen.title(),
-                    screen.output_text(),
-                    screen.input_title(),
+                    screen.meta().title,
+                    screen.input_title().to_string(),
                     screen.input_text().to_string(),
                 )
             }
         };
 
-        let header = Paragraph::new(format!("you tui | Режим: {title} | q: выход | Esc: меню"))
-            .block(Block::default().borders(Borders::ALL).title("Статус"));
-
-        let content = Paragraph::new(output)
-            .block(Block::default().borders(Borders::ALL).title("Вывод"))
-            .wrap(Wrap { trim: false });
+        let header = Paragraph::new(format!(
+            "you tui | Режим: {title} | q: выход | Esc: главная | PgUp/PgDn: прокрутка | /: поиск"
+        ))
+        .style(self.theme.text)
+        .block(self.theme.block("Статус"));
 
         let input = Paragraph::new(input_text)
-            .block(Block::default().borders(Borders::ALL).title(input_title))
+            .style(self.theme.text)
+            .block(self.theme.block(input_title))
             .wrap(Wrap { trim: true });
 
         frame.render_widget(header, chunks[0]);
-        frame.render_widget(content, chunks[1]);
+        match self.active_view {
+            ActiveView::Dashboard => self.dashboard.render(
+                frame,
+                chunks[1],
+                self.screens
+                    .find::<CalendarScreen>()
+                    .and_then(|calendar| calendar.upcoming_text(UPCOMING_MEETINGS)),
+                &self.theme,
+            ),
+            ActiveView::Setup => self.setup.render(frame, chunks[1], &self.theme),
+            ActiveView::Screen(screen_id) => {
+                let Some(screen) = self.screens.get(screen_id) else {
+                    return;
+                };
+                let detail_area = match (screen.list_text(), self.layout.split(chunks[1])) {
+                    (Some(list), Some((list_area, detail_area))) => {
+                        let list = Paragraph::new(selectable_lines(&list, &self.theme))
+                            .block(self.theme.block("Список | Ctrl+←/→: ширина"))
+                            .wrap(Wrap { trim: false });
+                        frame.render_widget(list, list_area);
+                        detail_area
+                    }
+                    _ => chunks[1],
+                };
+                let detail_area = match screen.chart() {
+                    Some(data) => {
+                        let parts = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([Constraint::Percentage(60), Constraint::Min(5)])
+                            .split(detail_area);
+                        chart::render(frame, parts[0], &data, &self.theme);
+                        parts[1]
+                    }
+                    None => detail_area,
+                };
+                match screen.columns() {
+                    Some(columns) => {
+                        let areas = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints(vec![
+                                Constraint::Ratio(1, columns.len() as u32);
+                                columns.len()
+                            ])
+                            .split(detail_area);
+                        for ((title, text), area) in columns.iter().zip(areas.iter()) {
+                            let column = Paragraph::new(markdown::render(text, &self.theme))
+                                .block(self.theme.block(title.as_str()))
+                                .wrap(Wrap { trim: false });
+                            frame.render_widget(column, *area);
+                        }
+                    }
+                    None => self.outputs[&screen_id].render(
+                        frame,
+                        detail_area,
+                        &screen.output_text(),
+                        "Вывод",
+                        &self.theme,
+                        screen.renders_markdown(),
+                    ),
+                }
+            }
+        }
         frame.render_widget(input, chunks[2]);
-    }
+        self.toasts.render(frame, chunks[1], &self.theme);
+        self.status_bar.render(frame, chunks[3], &self.theme);
 
-    fn screen(&self, id: ScreenId) -> &dyn Screen {
-        match id {
-            ScreenId::Tracker => &self.tracker,
-            ScreenId::Llm => &self.llm,
+        if let ActiveView::Screen(screen_id) = self.active_view {
+            if let Some(popup) = self
+                .screens
+                .get(screen_id)
+                .and_then(|screen| screen.popup())
+            {
+                let area = centered_rect(60, 50, frame.area());
+                let widget = Paragraph::new(selectable_lines(&popup.body, &self.theme))
+                    .block(self.theme.block(popup.title))
+                    .wrap(Wrap { trim: false });
+                frame.render_widget(Clear, area);
+                frame.render_widget(widget, area);
+            }
         }
-    }
 
-    fn screen_mut(&mut self, id: ScreenId) -> &mut dyn Screen {
-        match id {
-            ScreenId::Tracker => &mut self.tracker,
-            ScreenId::Llm => &mut self.llm,
+        if let Some(finished) = &self.focus_prompt {
+            let area = centered_rect(50, 30, frame.area());
+            let widget = Paragraph::new(finished.prompt())
+                .style(self.theme.text)
+                .block(self.theme.block("Фокус завершён"))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(Clear, area);
+            frame.render_widget(widget, area);
         }
     }
 }
+
+/// Строки, начинающиеся с "> ", выделяются как выбранные, а "+ " и "- " — как строки диффа
+fn selectable_lines<'a>(text: &'a str, theme: &Theme) -> Vec<Line<'a>> {
+    text.lines()
+        .map(|line| {
+            let style = if line.starts_with("> ") {
+                theme.selection
+            } else if line.starts_with("+ ") {
+                theme.diff_added
+            } else if line.starts_with("- ") {
+                theme.diff_removed
+            } else {
+                theme.text
+            };
+            Line::styled(line, style)
+        })
+        .collect()
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
@@ -0,0 +1,123 @@
+//! Общая шина событий TUI
+//!
+//! Всё, на что реагирует приложение, приходит сюда одним потоком: клавиши,
+//! такты таймера, события клиентов API и результаты фоновых задач. Цикл
+//! приложения по очереди передаёт их в `App::update` и перерисовывает экран,
+//! так что новым асинхронным функциям достаточно публиковать свои события.
+
+use std::{collections::BTreeMap, io, thread, time::Duration};
+
+use crossterm::event::{self, Event, KeyEvent};
+use llm_lib::events::LlmEvent;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracker_lib::{events::TrackerEvent, models::Issue};
+
+use super::toast::ToastLevel;
+
+/// Как долго поток ввода ждёт клавишу, прежде чем проверить, живо ли приложение
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+pub enum AppEvent {
+    /// Нажатие клавиши в терминале
+    Key(KeyEvent),
+    /// Такт таймера: истечение уведомлений, фокус-таймер, автообновление
+    Tick,
+    /// Чтение терминала завершилось ошибкой, дальше работать нельзя
+    InputFailed(io::Error),
+    /// Событие клиента Tracker: запрос, лимиты
+    Tracker(TrackerEvent),
+    /// Событие клиента LLM: запрос, лимиты, использование токенов
+    Llm(LlmEvent),
+    /// Короткое всплывающее сообщение
+    Toast { level: ToastLevel, text: String },
+    /// Счётчики моих задач по статусам для главного экрана
+    IssueCountsLoaded(Result<BTreeMap<String, usize>, String>),
+    /// Результат фонового обновления открытых задач; в списке только изменившиеся
+    IssuesRefreshed(Vec<RefreshedIssue>),
+    /// Контекст для чата с LLM: подпись для экрана и сам текст
+    LlmContext { label: String, text: String },
+}
+
+pub struct RefreshedIssue {
+    pub issue: Issue,
+    pub etag: Option<String>,
+    /// false для первого запроса без ETag: версию не с чем сравнить
+    pub changed: bool,
+}
+
+#[derive(Clone)]
+pub struct EventBus {
+    sender: UnboundedSender<AppEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> (Self, UnboundedReceiver<AppEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { sender }, receiver)
+    }
+
+    /// Публикует событие; закрытая шина означает, что приложение уже завершается
+    pub fn publish(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn toast(&self, level: ToastLevel, text: impl Into<String>) {
+        self.publish(AppEvent::Toast {
+            level,
+            text: text.into(),
+        });
+    }
+
+    /// Пересылает события канала клиента в шину, пока открыты обе стороны
+    pub fn forward<T: Send + 'static>(
+        &self,
+        mut receiver: UnboundedReceiver<T>,
+        wrap: fn(T) -> AppEvent,
+    ) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if sender.send(wrap(event)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Публикует такт с заданным периодом, пока приложение читает шину
+    pub fn spawn_ticks(&self, period: Duration) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if sender.send(AppEvent::Tick).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Читает клавиши терминала в отдельном потоке: чтение crossterm блокирующее
+    pub fn spawn_terminal_input(&self) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            while !sender.is_closed() {
+                let event = match event::poll(INPUT_POLL) {
+                    Ok(false) => continue,
+                    Ok(true) => match event::read() {
+                        Ok(Event::Key(key)) => AppEvent::Key(key),
+                        Ok(_) => continue,
+                        Err(err) => AppEvent::InputFailed(err),
+                    },
+                    Err(err) => AppEvent::InputFailed(err),
+                };
+                let failed = matches!(event, AppEvent::InputFailed(_));
+                if sender.send(event).is_err() || failed {
+                    break;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,69 @@
+//! Линейный график над выводом экрана
+
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType},
+};
+
+use super::theme::Theme;
+
+/// Данные графика, который экран показывает над панелью вывода
+pub struct ChartData {
+    pub title: String,
+    /// Количество делений по оси X
+    pub x_max: f64,
+    pub y_max: f64,
+    /// Подписи оси X слева направо
+    pub x_labels: Vec<String>,
+    pub series: Vec<ChartSeries>,
+}
+
+pub struct ChartSeries {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+    /// Опорная линия (например, идеальный темп) рисуется приглушённым цветом
+    pub reference: bool,
+}
+
+pub fn render(frame: &mut Frame, area: Rect, chart: &ChartData, theme: &Theme) {
+    let datasets = chart
+        .series
+        .iter()
+        .map(|series| {
+            let style = if series.reference {
+                theme.border
+            } else {
+                theme.title
+            };
+            Dataset::default()
+                .name(series.name.as_str())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(style)
+                .data(&series.points)
+        })
+        .collect();
+
+    let y_labels = vec![
+        "0".to_string(),
+        format!("{:.0}", chart.y_max / 2.0),
+        format!("{:.0}", chart.y_max),
+    ];
+
+    let widget = Chart::new(datasets)
+        .block(theme.block(chart.title.as_str()))
+        .x_axis(
+            Axis::default()
+                .style(theme.border)
+                .bounds([0.0, chart.x_max])
+                .labels(chart.x_labels.clone()),
+        )
+        .y_axis(
+            Axis::default()
+                .style(theme.border)
+                .bounds([0.0, chart.y_max])
+                .labels(y_labels),
+        );
+    frame.render_widget(widget, area);
+}
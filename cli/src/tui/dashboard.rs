@@ -0,0 +1,406 @@
+//! Главный экран: сводка по задачам, встречам, уведомлениям и недавним действиям
+
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{DateTime, Local};
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::{
+    events::TrackerEvent,
+    models::Issue,
+    search::{SearchParams, SearchRequest},
+    TrackerClient,
+};
+
+use crate::{settings, snooze, timezone};
+
+use super::{
+    bus::{AppEvent, EventBus},
+    screens::{ScreenId, ScreenMeta},
+    theme::Theme,
+    toast::ToastLevel,
+};
+
+/// Запрос открытых задач текущего пользователя
+const MY_ISSUES_QUERY: &str = "Assignee: me() Resolution: empty()";
+const MAX_FEED_ITEMS: usize = 20;
+const VISIBLE_FEED_ITEMS: usize = 5;
+
+pub enum DashboardAction {
+    None,
+    Open(ScreenId),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Panel {
+    Issues,
+    Meetings,
+    Notifications,
+    Activity,
+}
+
+impl Panel {
+    const ALL: [Panel; 4] = [
+        Panel::Issues,
+        Panel::Meetings,
+        Panel::Notifications,
+        Panel::Activity,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&panel| panel == self)
+            .unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+struct FeedItem {
+    source: ScreenId,
+    text: String,
+    at: DateTime<Local>,
+}
+
+enum IssueCounts {
+    Loading,
+    Loaded(BTreeMap<String, usize>),
+    Failed(String),
+}
+
+pub struct Dashboard {
+    selected: Panel,
+    issues: IssueCounts,
+    notifications: VecDeque<FeedItem>,
+    unread: usize,
+    activity: VecDeque<FeedItem>,
+    /// Экраны, доступные по клавишам, в порядке меню
+    menu: Vec<ScreenMeta>,
+    tracker_events: UnboundedSender<TrackerEvent>,
+    bus: EventBus,
+}
+
+impl Dashboard {
+    pub fn new(
+        menu: Vec<ScreenMeta>,
+        tracker_events: UnboundedSender<TrackerEvent>,
+        bus: EventBus,
+    ) -> Self {
+        Self {
+            selected: Panel::Issues,
+            issues: IssueCounts::Loaded(BTreeMap::new()),
+            notifications: VecDeque::new(),
+            unread: 0,
+            activity: VecDeque::new(),
+            menu,
+            tracker_events,
+            bus,
+        }
+    }
+
+    /// Запускает фоновую загрузку счётчиков задач, не блокируя интерфейс
+    pub fn refresh_issues(&mut self) {
+        let client = match settings::tracker_client() {
+            Ok(client) => client.with_event_sender(self.tracker_events.clone()),
+            Err(err) => {
+                self.issues = IssueCounts::Failed(err.to_string());
+                return;
+            }
+        };
+
+        let bus = self.bus.clone();
+        tokio::spawn(async move {
+            let result = fetch_issue_counts(&client)
+                .await
+                .map_err(|err| err.to_string());
+            if let Err(err) = &result {
+                bus.toast(
+                    ToastLevel::Error,
+                    format!("Не удалось загрузить задачи: {err}"),
+                );
+            }
+            bus.publish(AppEvent::IssueCountsLoaded(result));
+        });
+        self.issues = IssueCounts::Loading;
+    }
+
+    pub fn apply_issue_counts(&mut self, result: Result<BTreeMap<String, usize>, String>) {
+        self.issues = match result {
+            Ok(counts) => IssueCounts::Loaded(counts),
+            Err(err) => IssueCounts::Failed(err),
+        };
+    }
+
+    pub fn notify(&mut self, source: ScreenId, text: impl Into<String>) {
+        push_feed(&mut self.notifications, source, text.into());
+        self.unread += 1;
+    }
+
+    pub fn record_activity(&mut self, source: ScreenId, command: impl Into<String>) {
+        push_feed(&mut self.activity, source, command.into());
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> DashboardAction {
+        if let KeyCode::Char(key) = code {
+            if let Some(meta) = self.menu.iter().find(|meta| meta.shortcut == key) {
+                return DashboardAction::Open(meta.id);
+            }
+        }
+        match code {
+            KeyCode::Char('r') => {
+                self.refresh_issues();
+                DashboardAction::None
+            }
+            KeyCode::Tab | KeyCode::Right | KeyCode::Down => {
+                self.selected = self.selected.next();
+                DashboardAction::None
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Up => {
+                self.selected = self.selected.previous();
+                DashboardAction::None
+            }
+            KeyCode::Enter => self.open_selected(),
+            _ => DashboardAction::None,
+        }
+    }
+
+    fn open_selected(&mut self) -> DashboardAction {
+        let target = match self.selected {
+            Panel::Issues => Some(ScreenId::Tracker),
+            Panel::Meetings => Some(ScreenId::Calendar),
+            Panel::Notifications => {
+                self.unread = 0;
+                self.notifications.back().map(|item| item.source)
+            }
+            Panel::Activity => self.activity.back().map(|item| item.source),
+        };
+        target.map_or(DashboardAction::None, DashboardAction::Open)
+    }
+
+    pub fn title(&self) -> &'static str {
+        "Главная"
+    }
+
+    pub fn input_title(&self) -> String {
+        let menu: Vec<String> = self
+            .menu
+            .iter()
+            .map(|meta| format!("{}-{}", meta.shortcut, meta.label))
+            .collect();
+        format!(
+            "Главная: Tab/стрелки — блок, Enter — открыть, r — обновить, {}, q-Выход",
+            menu.join(", ")
+        )
+    }
+
+    /// Рисует сетку 2×2; `meetings` — ближайшие встречи из календаря
+    pub fn render(&self, frame: &mut Frame, area: Rect, meetings: Option<String>, theme: &Theme) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let cells: Vec<Rect> = rows
+            .iter()
+            .flat_map(|row| {
+                Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(*row)
+                    .to_vec()
+            })
+            .collect();
+
+        let meetings =
+            meetings.unwrap_or_else(|| "Календарь не настроен (CALENDAR_ICS)".to_string());
+        let notifications_title = if self.unread > 0 {
+            format!("Уведомления ({} новых)", self.unread)
+        } else {
+            "Уведомления".to_string()
+        };
+
+        let panels = [
+            (Panel::Issues, "Мои задачи".to_string(), self.issues_text()),
+            (Panel::Meetings, "Ближайшие встречи".to_string(), meetings),
+            (
+                Panel::Notifications,
+                notifications_title,
+                feed_text(&self.notifications, "Уведомлений нет"),
+            ),
+            (
+                Panel::Activity,
+                "Недавние действия".to_string(),
+                feed_text(&self.activity, "Пока ничего не сделано"),
+            ),
+        ];
+
+        for ((panel, title, text), cell) in panels.into_iter().zip(cells) {
+            let mut block = theme.block(title);
+            if panel == self.selected {
+                block = block.border_style(theme.selection);
+            }
+            let widget = Paragraph::new(text)
+                .style(theme.text)
+                .block(block)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(widget, cell);
+        }
+    }
+
+    fn issues_text(&self) -> String {
+        match &self.issues {
+            IssueCounts::Loading => "Загрузка…".to_string(),
+            IssueCounts::Failed(err) => format!("Ошибка Tracker: {err}"),
+            IssueCounts::Loaded(counts) if counts.is_empty() => {
+                "Открытых задач нет (r — обновить)".to_string()
+            }
+            IssueCounts::Loaded(counts) => {
+                let total: usize = counts.values().sum();
+                let mut lines: Vec<String> = counts
+                    .iter()
+                    .map(|(status, count)| format!("{status}: {count}"))
+                    .collect();
+                lines.push(format!("Всего: {total}"));
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+async fn fetch_issue_counts(
+    client: &TrackerClient,
+) -> tracker_lib::Result<BTreeMap<String, usize>> {
+    let request = SearchRequest {
+        query: Some(MY_ISSUES_QUERY.to_string()),
+        ..Default::default()
+    };
+    let params = SearchParams {
+        per_page: Some(100),
+        ..Default::default()
+    };
+    let mut issues = client.search_issues(&request, Some(params)).await?;
+    let visible = snooze::visible(timezone::current().today());
+    issues.retain(|issue| visible(&issue.key));
+    Ok(count_by_status(&issues))
+}
+
+fn count_by_status(issues: &[Issue]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for issue in issues {
+        let status = issue
+            .status
+            .as_ref()
+            .and_then(|status| status.display.clone())
+            .unwrap_or_else(|| "Без статуса".to_string());
+        *counts.entry(status).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn push_feed(feed: &mut VecDeque<FeedItem>, source: ScreenId, text: String) {
+    feed.push_back(FeedItem {
+        source,
+        text,
+        at: Local::now(),
+    });
+    if feed.len() > MAX_FEED_ITEMS {
+        feed.pop_front();
+    }
+}
+
+fn feed_text(feed: &VecDeque<FeedItem>, empty: &str) -> String {
+    if feed.is_empty() {
+        return empty.to_string();
+    }
+    feed.iter()
+        .rev()
+        .take(VISIBLE_FEED_ITEMS)
+        .map(|item| format!("{} {}", item.at.format("%H:%M"), item.text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn dashboard() -> Dashboard {
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (bus, _events) = EventBus::new();
+        let menu = vec![ScreenMeta {
+            id: ScreenId::Llm,
+            title: "LLM",
+            label: "LLM",
+            shortcut: '2',
+            requires: &[],
+        }];
+        Dashboard::new(menu, sender, bus)
+    }
+
+    #[test]
+    fn test_shortcuts_come_from_registered_screens() {
+        let mut dashboard = dashboard();
+        assert!(matches!(
+            dashboard.handle_key(KeyCode::Char('2')),
+            DashboardAction::Open(ScreenId::Llm)
+        ));
+        assert!(matches!(
+            dashboard.handle_key(KeyCode::Char('1')),
+            DashboardAction::None
+        ));
+        assert!(dashboard.input_title().contains("2-LLM, q-Выход"));
+    }
+
+    #[test]
+    fn test_enter_opens_screen_of_selected_panel() {
+        let mut dashboard = dashboard();
+        assert!(matches!(
+            dashboard.handle_key(KeyCode::Enter),
+            DashboardAction::Open(ScreenId::Tracker)
+        ));
+
+        dashboard.handle_key(KeyCode::Tab);
+        assert!(matches!(
+            dashboard.handle_key(KeyCode::Enter),
+            DashboardAction::Open(ScreenId::Calendar)
+        ));
+    }
+
+    #[test]
+    fn test_opening_notifications_marks_them_read() {
+        let mut dashboard = dashboard();
+        dashboard.notify(ScreenId::Llm, "Лимит исчерпан");
+        assert_eq!(dashboard.unread, 1);
+
+        dashboard.selected = Panel::Notifications;
+        assert!(matches!(
+            dashboard.handle_key(KeyCode::Enter),
+            DashboardAction::Open(ScreenId::Llm)
+        ));
+        assert_eq!(dashboard.unread, 0);
+    }
+
+    #[test]
+    fn test_feed_is_bounded() {
+        let mut dashboard = dashboard();
+        for index in 0..MAX_FEED_ITEMS + 5 {
+            dashboard.record_activity(ScreenId::Tracker, format!("> tracker issue Q-{index}"));
+        }
+        assert_eq!(dashboard.activity.len(), MAX_FEED_ITEMS);
+        let text = feed_text(&dashboard.activity, "");
+        let newest = text.lines().next().unwrap();
+        assert!(newest.ends_with(&format!("Q-{}", MAX_FEED_ITEMS + 4)));
+    }
+}
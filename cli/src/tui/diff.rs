@@ -0,0 +1,81 @@
+//! Предпросмотр изменений перед отправкой в Трекер
+//!
+//! Окно подтверждения показывает старые значения строками `- ` и новые
+//! строками `+ `; цвета строк задаются темой.
+
+/// Изменение одного поля; None — значения нет или оно неизвестно
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl FieldChange {
+    pub fn new(field: impl Into<String>, old: Option<String>, new: Option<String>) -> Self {
+        Self {
+            field: field.into(),
+            old,
+            new,
+        }
+    }
+}
+
+/// Подсказка по клавишам окна подтверждения
+pub const CONFIRM_HINT: &str = "Enter/y — применить, Esc/n — отмена";
+
+/// Тело окна подтверждения: неизменённые поля без маркера, изменения построчно
+pub fn render(changes: &[FieldChange]) -> String {
+    let mut lines = Vec::new();
+    for change in changes {
+        if change.old == change.new {
+            let value = change.old.as_deref().unwrap_or("—");
+            lines.push(format!("  {}: {value}", change.field));
+            continue;
+        }
+        for (marker, value) in [("-", &change.old), ("+", &change.new)] {
+            let Some(value) = value else {
+                continue;
+            };
+            let mut value_lines = value.lines();
+            let first = value_lines.next().unwrap_or_default();
+            lines.push(format!("{marker} {}: {first}", change.field));
+            lines.extend(value_lines.map(|line| format!("{marker}   {line}")));
+        }
+    }
+    lines.push(String::new());
+    lines.push(CONFIRM_HINT.to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_marks_changed_fields() {
+        let changes = [
+            FieldChange::new(
+                "Статус",
+                Some("Открыт".to_string()),
+                Some("В работе".to_string()),
+            ),
+            FieldChange::new(
+                "Очередь",
+                Some("TREK".to_string()),
+                Some("TREK".to_string()),
+            ),
+            FieldChange::new("Комментарий", None, Some("первая\nвторая".to_string())),
+        ];
+
+        let text = render(&changes);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "- Статус: Открыт");
+        assert_eq!(lines[1], "+ Статус: В работе");
+        assert_eq!(lines[2], "  Очередь: TREK");
+        assert_eq!(lines[3], "+ Комментарий: первая");
+        assert_eq!(lines[4], "+   вторая");
+        assert_eq!(lines.last(), Some(&CONFIRM_HINT));
+    }
+}
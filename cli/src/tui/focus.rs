@@ -0,0 +1,117 @@
+//! Фокус-таймер (помидор), привязанный к задаче
+//!
+//! По завершении или остановке таймер превращается в [`FinishedFocus`],
+//! который можно списать в задачу как запись учёта времени.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use tracker_lib::worklog::AddWorklogRequest;
+
+/// Записи короче минуты не предлагаются к списанию
+const MIN_LOGGED: Duration = Duration::from_secs(60);
+
+pub struct FocusTimer {
+    issue_key: String,
+    started: Instant,
+    started_at: DateTime<Local>,
+    length: Duration,
+}
+
+/// Завершённый отрезок работы, ожидающий подтверждения списания
+pub struct FinishedFocus {
+    pub issue_key: String,
+    started_at: DateTime<Local>,
+    pub elapsed: Duration,
+}
+
+impl FocusTimer {
+    pub fn start(issue_key: String, length: Duration) -> Self {
+        Self {
+            issue_key,
+            started: Instant::now(),
+            started_at: Local::now(),
+            length,
+        }
+    }
+
+    pub fn issue_key(&self) -> &str {
+        &self.issue_key
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.length
+            .saturating_sub(now.saturating_duration_since(self.started))
+    }
+
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.remaining(now).is_zero()
+    }
+
+    /// Текст для строки состояния: ключ задачи и оставшееся время
+    pub fn describe(&self, now: Instant) -> String {
+        let remaining = self.remaining(now).as_secs();
+        format!(
+            "🍅 {} {:02}:{:02}",
+            self.issue_key,
+            remaining / 60,
+            remaining % 60
+        )
+    }
+
+    /// Останавливает таймер; None, если списывать нечего
+    pub fn finish(self, now: Instant) -> Option<FinishedFocus> {
+        let elapsed = now.saturating_duration_since(self.started).min(self.length);
+        (elapsed >= MIN_LOGGED).then_some(FinishedFocus {
+            issue_key: self.issue_key,
+            started_at: self.started_at,
+            elapsed,
+        })
+    }
+}
+
+impl FinishedFocus {
+    pub fn worklog_request(&self) -> AddWorklogRequest {
+        AddWorklogRequest::new(
+            self.started_at
+                .format("%Y-%m-%dT%H:%M:%S%.3f%z")
+                .to_string(),
+            self.elapsed,
+        )
+        .with_comment("Фокус-таймер")
+    }
+
+    pub fn prompt(&self) -> String {
+        format!(
+            "Списать {} мин в {}?\n\ny/Enter — списать, n/Esc — не списывать",
+            (self.elapsed.as_secs() + 30) / 60,
+            self.issue_key
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_timer_counts_down_and_finishes() {
+        let timer = FocusTimer::start("TEST-1".to_string(), Duration::from_secs(25 * 60));
+        let now = timer.started + Duration::from_secs(60 + 30);
+
+        assert_eq!(timer.describe(now), "🍅 TEST-1 23:30");
+        assert!(!timer.is_finished(now));
+        assert!(timer.is_finished(timer.started + Duration::from_secs(25 * 60)));
+
+        let finished = timer.finish(now + Duration::from_secs(3600)).unwrap();
+        assert_eq!(finished.elapsed, Duration::from_secs(25 * 60));
+        assert_eq!(finished.worklog_request().duration, "PT25M");
+    }
+
+    #[test]
+    fn test_short_focus_is_not_logged() {
+        let timer = FocusTimer::start("TEST-1".to_string(), Duration::from_secs(25 * 60));
+        let now = timer.started + Duration::from_secs(10);
+        assert!(timer.finish(now).is_none());
+    }
+}
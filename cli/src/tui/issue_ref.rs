@@ -0,0 +1,183 @@
+//! Автодополнение ссылок на задачи в полях ввода
+//!
+//! `#` в комментарии или запросе к LLM открывает список задач: недавно открытых в TUI
+//! и недавно обновлённых из локального индекса. Символы после `#` фильтруют список,
+//! Enter заменяет `#` и фильтр ссылкой на задачу, Esc и пробел закрывают список и
+//! оставляют набранный текст как есть.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::debug;
+
+use super::picker::{Picker, PickerEvent, PickerItem};
+use crate::index::IssueIndex;
+
+/// Символ, который открывает список задач
+pub const TRIGGER: char = '#';
+
+/// Сколько недавно обновлённых задач брать из индекса
+const INDEX_LIMIT: usize = 50;
+
+/// Как вставлять ссылку на задачу
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefStyle {
+    /// Ключ задачи: Трекер сам показывает его ссылкой со статусом
+    Key,
+    /// Ключ и название в кавычках, чтобы LLM понимала, о какой задаче речь
+    WithSummary,
+}
+
+/// Недавние задачи TUI, дополненные задачами из индекса без повторов
+pub fn issue_items(recent: &[(String, String)]) -> Vec<PickerItem> {
+    let mut issues = recent.to_vec();
+    match IssueIndex::open_default().and_then(|index| index.recent(INDEX_LIMIT)) {
+        Ok(indexed) => issues.extend(indexed.into_iter().map(|issue| (issue.key, issue.summary))),
+        Err(err) => debug!(error = %format!("{err:#}"), "Индекс для автодополнения недоступен"),
+    }
+    let mut items: Vec<PickerItem> = Vec::new();
+    for (key, summary) in issues {
+        if items
+            .iter()
+            .all(|item| item.value.as_deref() != Some(key.as_str()))
+        {
+            items.push(PickerItem::new(
+                Some(key.clone()),
+                format!("{key}: {summary}"),
+            ));
+        }
+    }
+    items
+}
+
+/// Открытый список задач для поля ввода
+pub struct IssueCompletion {
+    /// Позиция `#` в тексте поля
+    start: usize,
+    items: Vec<PickerItem>,
+    picker: Picker,
+}
+
+impl IssueCompletion {
+    /// Открывает список; `#` уже добавлен в конец `text`
+    pub fn new(text: &str, items: Vec<PickerItem>) -> Self {
+        Self {
+            start: text.len().saturating_sub(TRIGGER.len_utf8()),
+            items,
+            picker: Picker::new(),
+        }
+    }
+
+    /// Обрабатывает клавишу в поле `text`; false — список закрыт
+    pub fn handle_key(&mut self, key: KeyEvent, text: &mut String, style: RefStyle) -> bool {
+        let matches = self.picker.matches(self.items.clone());
+        match key.code {
+            KeyCode::Esc => false,
+            KeyCode::Char(c) if c.is_whitespace() => {
+                text.push(c);
+                false
+            }
+            KeyCode::Backspace => {
+                text.pop();
+                self.picker.handle_key(key, &matches);
+                text.len() > self.start
+            }
+            KeyCode::Enter => {
+                if let PickerEvent::Picked(Some(item)) = self.picker.handle_key(key, &matches) {
+                    text.truncate(self.start);
+                    text.push_str(&reference(&item, style));
+                }
+                false
+            }
+            KeyCode::Char(c) => {
+                text.push(c);
+                self.picker.handle_key(key, &matches);
+                true
+            }
+            _ => {
+                self.picker.handle_key(key, &matches);
+                true
+            }
+        }
+    }
+
+    /// Строки списка с выделенной задачей
+    pub fn lines(&self) -> Vec<String> {
+        self.picker.lines(&self.picker.matches(self.items.clone()))
+    }
+}
+
+fn reference(item: &PickerItem, style: RefStyle) -> String {
+    let key = item.value.clone().unwrap_or_default();
+    match style {
+        RefStyle::Key => key,
+        RefStyle::WithSummary => {
+            let summary = item
+                .label
+                .strip_prefix(&format!("{key}: "))
+                .unwrap_or(&item.label);
+            format!("{key} «{summary}»")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn items() -> Vec<PickerItem> {
+        vec![
+            PickerItem::new(Some("TREK-1".to_string()), "TREK-1: Релиз"),
+            PickerItem::new(Some("OPS-7".to_string()), "OPS-7: Падает сборка"),
+        ]
+    }
+
+    fn type_keys(completion: &mut IssueCompletion, text: &mut String, keys: &[KeyCode]) -> bool {
+        keys.iter().all(|code| {
+            completion.handle_key(
+                KeyEvent::new(*code, KeyModifiers::NONE),
+                text,
+                RefStyle::WithSummary,
+            )
+        })
+    }
+
+    #[test]
+    fn test_enter_replaces_filter_with_reference() {
+        let mut text = "Это из-за #".to_string();
+        let mut completion = IssueCompletion::new(&text, items());
+        assert!(type_keys(
+            &mut completion,
+            &mut text,
+            &[KeyCode::Char('o'), KeyCode::Char('p')]
+        ));
+        assert_eq!(text, "Это из-за #op");
+        assert!(completion.lines().iter().any(|line| line.contains("OPS-7")));
+        assert!(!type_keys(&mut completion, &mut text, &[KeyCode::Enter]));
+        assert_eq!(text, "Это из-за OPS-7 «Падает сборка»");
+    }
+
+    #[test]
+    fn test_space_and_backspace_close_completion() {
+        let mut text = "#".to_string();
+        let mut completion = IssueCompletion::new(&text, items());
+        assert!(!type_keys(
+            &mut completion,
+            &mut text,
+            &[KeyCode::Char('1'), KeyCode::Char(' ')]
+        ));
+        assert_eq!(text, "#1 ");
+
+        let mut text = "шаг #".to_string();
+        let mut completion = IssueCompletion::new(&text, items());
+        assert!(!type_keys(
+            &mut completion,
+            &mut text,
+            &[KeyCode::Backspace]
+        ));
+        assert_eq!(text, "шаг ");
+
+        let item = &items()[0];
+        assert_eq!(reference(item, RefStyle::Key), "TREK-1");
+    }
+}
@@ -0,0 +1,100 @@
+//! Раскладка области вывода: одна панель или список слева и детали справа
+
+use ratatui::prelude::*;
+use serde::Deserialize;
+
+/// Минимальная ширина терминала, при которой включается раздельный режим
+const MIN_SPLIT_WIDTH: u16 = 100;
+const DEFAULT_LIST_PERCENT: u16 = 30;
+const MIN_LIST_PERCENT: u16 = 15;
+const MAX_LIST_PERCENT: u16 = 70;
+const RESIZE_STEP: u16 = 5;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    Single,
+    #[default]
+    Split,
+}
+
+pub struct SplitLayout {
+    mode: LayoutMode,
+    list_percent: u16,
+}
+
+impl SplitLayout {
+    pub fn new(mode: LayoutMode, list_percent: Option<u16>) -> Self {
+        let mut layout = Self {
+            mode,
+            list_percent: DEFAULT_LIST_PERCENT,
+        };
+        if let Some(percent) = list_percent {
+            layout.set_list_percent(percent);
+        }
+        layout
+    }
+
+    pub fn list_percent(&self) -> u16 {
+        self.list_percent
+    }
+
+    pub fn set_list_percent(&mut self, percent: u16) {
+        self.list_percent = percent.clamp(MIN_LIST_PERCENT, MAX_LIST_PERCENT);
+    }
+
+    /// Сдвигает границу панелей; `grow` расширяет список
+    pub fn resize(&mut self, grow: bool) {
+        let percent = if grow {
+            self.list_percent + RESIZE_STEP
+        } else {
+            self.list_percent.saturating_sub(RESIZE_STEP)
+        };
+        self.set_list_percent(percent);
+    }
+
+    /// Делит область на список и детали; None, если панель одна
+    pub fn split(&self, area: Rect) -> Option<(Rect, Rect)> {
+        if self.mode == LayoutMode::Single || area.width < MIN_SPLIT_WIDTH {
+            return None;
+        }
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(self.list_percent),
+                Constraint::Percentage(100 - self.list_percent),
+            ])
+            .split(area);
+        Some((columns[0], columns[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_is_clamped() {
+        let mut layout = SplitLayout::new(LayoutMode::Split, Some(MAX_LIST_PERCENT));
+        layout.resize(true);
+        assert_eq!(layout.list_percent(), MAX_LIST_PERCENT);
+
+        for _ in 0..20 {
+            layout.resize(false);
+        }
+        assert_eq!(layout.list_percent(), MIN_LIST_PERCENT);
+    }
+
+    #[test]
+    fn test_split_only_on_wide_terminals() {
+        let layout = SplitLayout::new(LayoutMode::Split, None);
+        assert!(layout.split(Rect::new(0, 0, 80, 20)).is_none());
+
+        let (list, detail) = layout.split(Rect::new(0, 0, 120, 20)).unwrap();
+        assert_eq!(list.width, 36);
+        assert_eq!(detail.width, 84);
+
+        let single = SplitLayout::new(LayoutMode::Single, None);
+        assert!(single.split(Rect::new(0, 0, 200, 20)).is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Предпросмотр задач, на которые ссылается открытая задача
+//!
+//! Ключи задач в описании и комментариях — отдельным словом или в конце ссылки на
+//! веб-интерфейс Трекера — раскрываются в карточке строками «ключ [статус] название».
+//! Сведения берутся из задач, уже открытых в TUI, затем из локального индекса и только
+//! потом из Трекера. Глубина `tui.link_preview_depth` задаёт, раскрывать ли ссылки
+//! в самих связанных задачах; 0 отключает предпросмотр.
+
+use std::collections::{HashMap, HashSet};
+
+use tracing::debug;
+use tracker_lib::{ids::IssueKey, models::Issue, TrackerClient};
+
+use crate::index::{IndexedIssue, IssueIndex};
+
+/// Глубина по умолчанию: только ссылки из самой задачи
+pub const DEFAULT_DEPTH: usize = 1;
+
+/// Больше уровней в карточке уже не читаются
+pub const MAX_DEPTH: usize = 3;
+
+/// Сколько задач раскрывать в одной карточке
+const MAX_LINKS: usize = 10;
+
+/// Сведения о задаче для строки предпросмотра
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    /// Описание, из которого берутся ссылки следующего уровня
+    pub description: Option<String>,
+}
+
+impl From<&Issue> for Preview {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            key: issue.key.clone(),
+            summary: issue.summary.clone(),
+            status: issue.status.as_ref().and_then(|s| s.display.clone()),
+            description: issue.description.clone(),
+        }
+    }
+}
+
+impl From<IndexedIssue> for Preview {
+    fn from(issue: IndexedIssue) -> Self {
+        Self {
+            key: issue.key,
+            summary: issue.summary,
+            status: issue.status,
+            description: issue.description,
+        }
+    }
+}
+
+/// Раскрытая ссылка и её уровень (1 — ссылка из самой задачи)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedIssue {
+    pub level: usize,
+    pub preview: Preview,
+}
+
+/// Ключи задач в тексте по порядку, без повторов
+///
+/// Ключ должен быть записан заглавными буквами, как его показывает Трекер, поэтому
+/// строчные `utf-8` и подобные слова ссылками не считаются.
+pub fn referenced_keys(text: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for word in text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-')) {
+        let is_key = word.contains('-')
+            && !word.chars().any(|c| c.is_ascii_lowercase())
+            && word.parse::<IssueKey>().is_ok();
+        if is_key && !keys.iter().any(|key| key == word) {
+            keys.push(word.to_string());
+        }
+    }
+    keys
+}
+
+/// Раскрывает ссылки из `texts` задачи `root` до глубины `depth`
+///
+/// Найденные задачи складываются в `cache`; задачи, которых нет в Трекере, пропускаются.
+pub async fn resolve(
+    client: &TrackerClient,
+    index: Option<&IssueIndex>,
+    cache: &mut HashMap<String, Preview>,
+    root: &str,
+    texts: &[&str],
+    depth: usize,
+) -> Vec<LinkedIssue> {
+    let mut seen = HashSet::from([root.to_string()]);
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    for text in texts.iter().rev() {
+        stack.extend(referenced_keys(text).into_iter().rev().map(|key| (1, key)));
+    }
+
+    let mut linked = Vec::new();
+    while let Some((level, key)) = stack.pop() {
+        if level > depth || linked.len() >= MAX_LINKS {
+            continue;
+        }
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        let Some(preview) = lookup(client, index, cache, &key).await else {
+            continue;
+        };
+        if level < depth {
+            let nested = referenced_keys(preview.description.as_deref().unwrap_or_default());
+            stack.extend(nested.into_iter().rev().map(|key| (level + 1, key)));
+        }
+        linked.push(LinkedIssue { level, preview });
+    }
+    linked
+}
+
+/// Сведения о задаче: из задач TUI, из индекса или из Трекера
+async fn lookup(
+    client: &TrackerClient,
+    index: Option<&IssueIndex>,
+    cache: &mut HashMap<String, Preview>,
+    key: &str,
+) -> Option<Preview> {
+    if let Some(preview) = cache.get(key) {
+        return Some(preview.clone());
+    }
+    let indexed = index.and_then(|index| index.get(key).ok().flatten());
+    let preview = match indexed {
+        Some(issue) => Preview::from(issue),
+        None => match client.get_issue(&key.parse().ok()?, None).await {
+            Ok(issue) => Preview::from(&issue),
+            Err(err) => {
+                debug!(key, error = %err, "Ссылка на задачу не раскрыта");
+                return None;
+            }
+        },
+    };
+    cache.insert(key.to_string(), preview.clone());
+    Some(preview)
+}
+
+/// Блок карточки со ссылками; None, если раскрывать нечего
+pub fn render(linked: &[LinkedIssue]) -> Option<String> {
+    if linked.is_empty() {
+        return None;
+    }
+    let mut output = String::from("🔗 Ссылки:\n");
+    for LinkedIssue { level, preview } in linked {
+        let indent = "   ".repeat(*level);
+        let branch = if *level > 1 { "└─ " } else { "" };
+        let status = preview.status.as_deref().unwrap_or("без статуса");
+        output.push_str(&format!(
+            "{indent}{branch}{} [{status}] {}\n",
+            preview.key, preview.summary
+        ));
+    }
+    output.push('\n');
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use tracker_lib::TrackerConfig;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+    use crate::testing::issue;
+
+    #[test]
+    fn test_referenced_keys_from_words_and_urls() {
+        let text = "Похоже на TREK-12 и https://tracker.yandex.ru/OPS-3, \
+см. также TREK-12, ветку trek-5-fix и кодировку utf-8";
+        assert_eq!(referenced_keys(text), ["TREK-12", "OPS-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uses_cache_index_then_tracker() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/DOCS-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "DOCS-1",
+                "summary": "Описать экспорт",
+                "status": {"key": "closed", "display": "Закрыт"}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = TrackerClient::new(
+            TrackerConfig::new("test-token")
+                .with_base_url(mock_server.uri())
+                .with_api_version("v3"),
+        )
+        .unwrap();
+
+        let index = IssueIndex::open_in_memory().unwrap();
+        index
+            .upsert(
+                &issue("OPS-3")
+                    .summary("Сборка")
+                    .description("Ждёт DOCS-1, а ещё OPS-9")
+                    .status("open", "Открыт")
+                    .build(),
+            )
+            .unwrap();
+        let mut cache = HashMap::from([(
+            "TREK-12".to_string(),
+            Preview::from(
+                &issue("TREK-12")
+                    .summary("Падает экспорт")
+                    .status("open", "Открыт")
+                    .build(),
+            ),
+        )]);
+
+        let linked = resolve(
+            &client,
+            Some(&index),
+            &mut cache,
+            "TREK-1",
+            &["Связано с TREK-12 и TREK-1", "Блокер: OPS-3"],
+            2,
+        )
+        .await;
+
+        assert_eq!(
+            render(&linked).unwrap(),
+            "🔗 Ссылки:\n   TREK-12 [Открыт] Падает экспорт\n   OPS-3 [Открыт] Сборка\n      \
+└─ DOCS-1 [Закрыт] Описать экспорт\n\n"
+        );
+        assert!(cache.contains_key("DOCS-1"));
+
+        // С глубиной 1 ссылки связанных задач не раскрываются
+        let linked = resolve(&client, Some(&index), &mut cache, "TREK-1", &["OPS-3"], 1).await;
+        assert_eq!(linked.len(), 1);
+        assert!(render(&[]).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+//! Упрощённый рендер Markdown для панели вывода
+//!
+//! Каждая исходная строка превращается ровно в одну строку на экране, чтобы
+//! прокрутка и поиск могли считать позиции по исходному тексту.
+
+use ratatui::prelude::*;
+
+use super::theme::Theme;
+
+/// Рендерит заголовки, списки, блоки кода, `**жирный**` и `` `код` ``
+pub fn render<'a>(text: &'a str, theme: &Theme) -> Vec<Line<'a>> {
+    let mut in_code = false;
+
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") {
+                in_code = !in_code;
+                let language = trimmed.trim_start_matches('`').trim();
+                return Line::styled(format!("─── {language}"), theme.code_comment);
+            }
+            if in_code {
+                return theme.highlight_code(line);
+            }
+            render_line(line, theme)
+        })
+        .collect()
+}
+
+fn render_line<'a>(line: &'a str, theme: &Theme) -> Line<'a> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    let heading_level = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+        let style = theme.title.add_modifier(Modifier::BOLD);
+        let spans = render_inline(trimmed[heading_level..].trim_start(), style, theme);
+        return Line::from(spans);
+    }
+
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        let mut spans = vec![Span::styled(format!("{indent}  • "), theme.text)];
+        spans.extend(render_inline(item, theme.text, theme));
+        return Line::from(spans);
+    }
+
+    let mut spans = vec![Span::styled(indent, theme.text)];
+    spans.extend(render_inline(trimmed, theme.text, theme));
+    Line::from(spans)
+}
+
+/// Разбирает `**жирный**` и `` `код` `` внутри строки
+fn render_inline<'a>(text: &'a str, base: Style, theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    let mut bold = false;
+
+    while !rest.is_empty() {
+        let next_marker = [rest.find("**"), rest.find('`')]
+            .into_iter()
+            .flatten()
+            .min();
+        let Some(position) = next_marker else {
+            spans.push(Span::styled(rest, current_style(base, bold)));
+            break;
+        };
+
+        if position > 0 {
+            spans.push(Span::styled(&rest[..position], current_style(base, bold)));
+        }
+        rest = &rest[position..];
+
+        if let Some(after) = rest.strip_prefix("**") {
+            bold = !bold;
+            rest = after;
+            continue;
+        }
+
+        let after_tick = &rest[1..];
+        match after_tick.find('`') {
+            Some(end) => {
+                spans.push(Span::styled(&after_tick[..end], theme.code));
+                rest = &after_tick[end + 1..];
+            }
+            None => {
+                spans.push(Span::styled(rest, current_style(base, bold)));
+                break;
+            }
+        }
+    }
+
+    spans
+}
+
+fn current_style(base: Style, bold: bool) -> Style {
+    if bold {
+        base.add_modifier(Modifier::BOLD)
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_keeps_line_count() {
+        let text = "# Title\n\n- item\n```rust\nlet x = 1;\n```\ntext";
+        let theme = Theme::dark();
+        assert_eq!(render(text, &theme).len(), text.lines().count());
+    }
+
+    #[test]
+    fn test_render_heading_and_list() {
+        let theme = Theme::dark();
+        let lines = render("## План\n- **важно** и `код`", &theme);
+
+        assert_eq!(plain(&lines[0]), "План");
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert_eq!(plain(&lines[1]), "  • важно и код");
+
+        let bold = lines[1]
+            .spans
+            .iter()
+            .find(|span| span.content == "важно")
+            .unwrap();
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+        let code = lines[1]
+            .spans
+            .iter()
+            .find(|span| span.content == "код")
+            .unwrap();
+        assert_eq!(code.style, theme.code);
+    }
+
+    #[test]
+    fn test_render_code_block_is_highlighted() {
+        let theme = Theme::dark();
+        let lines = render("```\nfn main() {}\n```", &theme);
+        assert_eq!(lines[1].spans[0].style, theme.code_keyword);
+    }
+}
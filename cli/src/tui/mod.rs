@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, time::Duration};
 
 use anyhow::Result;
 use crossterm::{
@@ -7,11 +7,65 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 
+use crate::{
+    config::Config,
+    queues::{LiveRules, NotifyRules},
+};
+
 mod app;
-mod menu;
+mod bus;
+mod chart;
+mod dashboard;
+mod diff;
+mod focus;
+mod issue_ref;
+pub mod layout;
+mod link_preview;
+mod markdown;
+mod output;
+mod picker;
 mod screens;
+mod session;
+mod setup;
+mod status_bar;
+mod system;
+#[cfg(test)]
+mod testing;
+pub mod theme;
+mod toast;
+mod transcript;
+
+/// Период фонового обновления задач, если он не задан в конфигурации
+const DEFAULT_REFRESH_SECS: u64 = 60;
+
+/// Длительность фокус-таймера, если она не задана в конфигурации
+const DEFAULT_FOCUS_MINUTES: u64 = 25;
 
 pub async fn run_tui() -> Result<()> {
+    let config = Config::load()?;
+    let theme = theme::Theme::from_config(&config.tui)?;
+    let layout = layout::SplitLayout::new(config.tui.layout, config.tui.list_width);
+    let refresh_interval = match config
+        .tui
+        .refresh_interval_secs
+        .unwrap_or(DEFAULT_REFRESH_SECS)
+    {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+    let focus_length =
+        Duration::from_secs(config.tui.focus_minutes.unwrap_or(DEFAULT_FOCUS_MINUTES) * 60);
+    if let Some(limit) = config.tui.scrollback {
+        screens::set_scrollback(limit);
+    }
+    // Журнал открывается до перехода в альтернативный экран, чтобы ошибка была видна
+    let session_log = config
+        .tui
+        .session_log
+        .as_deref()
+        .map(transcript::SessionLog::open)
+        .transpose()?;
+
     enable_raw_mode()?;
 
     let mut stdout = io::stdout();
@@ -20,7 +74,31 @@ pub async fn run_tui() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = app::App::new().run(&mut terminal).await;
+    let notifier = crate::notify::Notifier::new(&config.notify);
+    let mut app = app::App::new(theme, layout, refresh_interval, focus_length, notifier);
+    app.set_prompts(crate::prompts::library(&config.prompts));
+    app.set_personas(crate::personas::library(&config.personas));
+    app.set_link_depth(
+        config
+            .tui
+            .link_preview_depth
+            .unwrap_or(link_preview::DEFAULT_DEPTH),
+    );
+    app.restore_session(session::SessionState::load());
+    // Черновики восстанавливаются после сессии: они свежее её после падения
+    if let Ok(drafts) = crate::drafts::Drafts::open_default() {
+        app.set_drafts(drafts);
+    }
+    if let Some(dir) = crate::paths::state_dir() {
+        app.set_chat_path(dir.join("llm-chat.md"));
+    }
+    if let Some(log) = session_log {
+        app.set_session_log(log);
+    }
+    let notify_rules = LiveRules::new(NotifyRules::new(&config.queue));
+    notify_rules.watch();
+    app.set_notify_rules(notify_rules);
+    let result = app.run(&mut terminal).await;
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -0,0 +1,229 @@
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+};
+
+use super::{markdown, theme::Theme};
+
+/// Состояние прокрутки и поиска в панели вывода
+///
+/// Позиция хранится как отступ от конца, чтобы новые сообщения оставались видны,
+/// пока пользователь не пролистал историю вверх.
+#[derive(Default)]
+pub struct OutputView {
+    offset_from_bottom: usize,
+    search: Option<String>,
+    search_editing: bool,
+    last_total_rows: Cell<usize>,
+    last_visible_rows: Cell<usize>,
+    last_width: Cell<u16>,
+}
+
+impl OutputView {
+    /// Восстанавливает позицию прокрутки и подсветку поиска из сохранённой сессии
+    pub fn restored(offset_from_bottom: usize, search: Option<String>) -> Self {
+        Self {
+            offset_from_bottom,
+            search,
+            ..Default::default()
+        }
+    }
+
+    pub fn offset_from_bottom(&self) -> usize {
+        self.offset_from_bottom
+    }
+
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search_editing
+    }
+
+    pub fn start_search(&mut self) {
+        self.search = Some(String::new());
+        self.search_editing = true;
+    }
+
+    /// Обрабатывает клавиши прокрутки и поиска; возвращает true, если клавиша поглощена
+    pub fn handle_key(&mut self, key: KeyEvent, text: &str) -> bool {
+        if self.search_editing {
+            match key.code {
+                KeyCode::Esc => {
+                    self.search = None;
+                    self.search_editing = false;
+                }
+                KeyCode::Enter => self.search_editing = false,
+                KeyCode::Backspace => {
+                    if let Some(query) = self.search.as_mut() {
+                        query.pop();
+                    }
+                    self.jump_to_last_match(text);
+                }
+                KeyCode::Char(c) => {
+                    if let Some(query) = self.search.as_mut() {
+                        query.push(c);
+                    }
+                    self.jump_to_last_match(text);
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        let page = self.last_visible_rows.get().max(1);
+        let max_offset = self
+            .last_total_rows
+            .get()
+            .saturating_sub(self.last_visible_rows.get());
+
+        match key.code {
+            KeyCode::PageUp => {
+                self.offset_from_bottom = (self.offset_from_bottom + page).min(max_offset)
+            }
+            KeyCode::PageDown => {
+                self.offset_from_bottom = self.offset_from_bottom.saturating_sub(page)
+            }
+            KeyCode::Home => self.offset_from_bottom = max_offset,
+            KeyCode::End => {
+                self.offset_from_bottom = 0;
+                self.search = None;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn jump_to_last_match(&mut self, text: &str) {
+        let Some(query) = self.search.as_deref().filter(|query| !query.is_empty()) else {
+            return;
+        };
+        let width = self.last_width.get();
+        let mut row = 0;
+        let mut match_row = None;
+        for line in text.lines() {
+            if line.contains(query) {
+                match_row = Some(row);
+            }
+            row += wrapped_rows(line, width);
+        }
+
+        if let Some(match_row) = match_row {
+            let visible = self.last_visible_rows.get().max(1);
+            let bottom_row = (match_row + visible / 2 + 1).min(row);
+            self.offset_from_bottom = row.saturating_sub(bottom_row);
+        }
+    }
+
+    pub fn render(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        text: &str,
+        title: &str,
+        theme: &Theme,
+        markdown: bool,
+    ) {
+        let inner_width = area.width.saturating_sub(2);
+        let visible_rows = usize::from(area.height.saturating_sub(2));
+        let total_rows: usize = text
+            .lines()
+            .map(|line| wrapped_rows(line, inner_width))
+            .sum();
+
+        self.last_width.set(inner_width);
+        self.last_visible_rows.set(visible_rows);
+        self.last_total_rows.set(total_rows);
+
+        let max_offset = total_rows.saturating_sub(visible_rows);
+        let offset = self.offset_from_bottom.min(max_offset);
+        let top = max_offset - offset;
+
+        let title = match (&self.search, offset) {
+            (Some(query), _) => format!("{title} | поиск: {query}"),
+            (None, 0) => title.to_string(),
+            (None, _) => format!("{title} | ↑{offset} (End — к концу)"),
+        };
+
+        let lines = style_lines(text, theme, self.search.as_deref(), markdown);
+
+        let content = Paragraph::new(lines)
+            .block(theme.block(title))
+            .wrap(Wrap { trim: false })
+            .scroll((u16::try_from(top).unwrap_or(u16::MAX), 0));
+        frame.render_widget(content, area);
+
+        if max_offset > 0 {
+            let mut state = ScrollbarState::new(max_offset).position(top);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight).style(theme.border),
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut state,
+            );
+        }
+    }
+}
+
+fn wrapped_rows(line: &str, width: u16) -> usize {
+    let width = usize::from(width.max(1));
+    Line::from(line).width().div_ceil(width).max(1)
+}
+
+/// Раскрашивает вывод: Markdown, статусы задач и совпадения поиска
+fn style_lines<'a>(
+    text: &'a str,
+    theme: &Theme,
+    query: Option<&str>,
+    markdown: bool,
+) -> Vec<Line<'a>> {
+    let query = query.filter(|query| !query.is_empty());
+    let rendered = if markdown {
+        markdown::render(text, theme)
+    } else {
+        text.lines()
+            .map(|line| Line::styled(line, theme.text))
+            .collect()
+    };
+
+    text.lines()
+        .zip(rendered)
+        .map(|(line, rendered)| {
+            if let Some(query) = query.filter(|query| line.contains(query)) {
+                return highlight(line, query, theme.text, theme.search_match);
+            }
+            if let Some(status) = line.strip_prefix(STATUS_PREFIX) {
+                return Line::from(vec![
+                    Span::styled(STATUS_PREFIX, theme.text),
+                    Span::styled(status, theme.status_style(status)),
+                ]);
+            }
+            rendered
+        })
+        .collect()
+}
+
+/// Префикс строки статуса в выводе `format_issue_output`
+const STATUS_PREFIX: &str = "🔖 Статус: ";
+
+fn highlight<'a>(line: &'a str, query: &str, base: Style, match_style: Style) -> Line<'a> {
+    let mut spans = Vec::new();
+    let mut last_end = 0;
+    for (start, matched) in line.match_indices(query) {
+        if start > last_end {
+            spans.push(Span::styled(&line[last_end..start], base));
+        }
+        spans.push(Span::styled(matched, match_style));
+        last_end = start + matched.len();
+    }
+    if last_end < line.len() {
+        spans.push(Span::styled(&line[last_end..], base));
+    }
+    Line::from(spans)
+}
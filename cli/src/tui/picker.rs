@@ -0,0 +1,233 @@
+//! Список выбора с нечётким фильтром для всплывающих окон
+//!
+//! Экран сам собирает варианты, а `Picker` хранит введённый фильтр, выделенную строку
+//! и отмеченные варианты, если можно выбрать несколько.
+
+use std::collections::BTreeSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tracker_lib::ids::UserLogin;
+
+use crate::people::{self, Directory};
+
+/// Сколько вариантов видно в окне одновременно
+const VISIBLE_ITEMS: usize = 10;
+
+/// Вариант выбора: значение и подпись
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickerItem {
+    pub value: Option<String>,
+    pub label: String,
+}
+
+impl PickerItem {
+    pub fn new(value: Option<String>, label: impl Into<String>) -> Self {
+        Self {
+            value,
+            label: label.into(),
+        }
+    }
+}
+
+/// Что произошло после нажатия клавиши
+#[derive(Debug, PartialEq)]
+pub enum PickerEvent {
+    None,
+    /// Enter в списке с одним выбором; None — под фильтр ничего не подошло
+    Picked(Option<PickerItem>),
+    /// Enter в списке с несколькими выборами: значения отмеченных вариантов
+    Done(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Picker {
+    filter: String,
+    selected: usize,
+    multi: bool,
+    marked: BTreeSet<String>,
+}
+
+impl Picker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Список, в котором Tab отмечает варианты, а Enter завершает выбор; пробел остаётся
+    /// частью фильтра, чтобы искать по имени и фамилии
+    pub fn multi(marked: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            multi: true,
+            marked: marked.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    /// Варианты, подходящие под фильтр, от лучшего совпадения; без фильтра — все по порядку
+    pub fn matches(&self, items: Vec<PickerItem>) -> Vec<PickerItem> {
+        let filter = self.filter.trim();
+        if filter.is_empty() {
+            return items;
+        }
+        let mut scored: Vec<(i64, PickerItem)> = items
+            .into_iter()
+            .filter_map(|item| Some((people::fuzzy_score(filter, &item.label)?, item)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Обрабатывает клавишу; `items` — уже отфильтрованные варианты из `matches`
+    pub fn handle_key(&mut self, key: KeyEvent, items: &[PickerItem]) -> PickerEvent {
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < items.len() => self.selected += 1,
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+            KeyCode::Tab if self.multi => {
+                if let Some(value) = items.get(self.selected).and_then(|item| item.value.clone()) {
+                    if !self.marked.remove(&value) {
+                        self.marked.insert(value);
+                    }
+                }
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.selected = 0;
+            }
+            KeyCode::Enter if self.multi => {
+                return PickerEvent::Done(self.marked.iter().cloned().collect())
+            }
+            KeyCode::Enter => return PickerEvent::Picked(items.get(self.selected).cloned()),
+            _ => {}
+        }
+        PickerEvent::None
+    }
+
+    /// Строки окна: фильтр и видимая часть списка вокруг выделенного варианта
+    pub fn lines(&self, items: &[PickerItem]) -> Vec<String> {
+        let mut lines = vec![format!("Фильтр: {}", self.filter), String::new()];
+        if items.is_empty() {
+            lines.push("Ничего не найдено".to_string());
+            return lines;
+        }
+        let start = self.selected.saturating_sub(VISIBLE_ITEMS - 1);
+        for (index, item) in items.iter().enumerate().skip(start).take(VISIBLE_ITEMS) {
+            let cursor = if index == self.selected { ">" } else { " " };
+            let mark = match (&item.value, self.multi) {
+                (Some(value), true) if self.marked.contains(value) => "[x] ",
+                (_, true) => "[ ] ",
+                (_, false) => "",
+            };
+            lines.push(format!("{cursor} {mark}{}", item.label));
+        }
+        let hidden = items.len() - (start + VISIBLE_ITEMS).min(items.len());
+        if hidden > 0 {
+            lines.push(format!("  … ещё {hidden}"));
+        }
+        lines
+    }
+}
+
+/// Варианты пользователей из справочника и логин, введённый в фильтр, если его там нет
+pub fn user_items(directory: &Directory, filter: &str) -> Vec<PickerItem> {
+    let mut items: Vec<PickerItem> = directory
+        .people
+        .iter()
+        .map(|person| PickerItem::new(Some(person.login.clone()), person.label()))
+        .collect();
+    let typed = filter.trim();
+    if !typed.is_empty() && directory.get(typed).is_none() && typed.parse::<UserLogin>().is_ok() {
+        items.push(PickerItem::new(
+            Some(typed.to_string()),
+            format!("Логин {typed}"),
+        ));
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn items() -> Vec<PickerItem> {
+        [
+            "Анна Иванова (anna)",
+            "Иван Петров (ivan.petrov)",
+            "Сергей (sergey)",
+        ]
+        .iter()
+        .map(|label| {
+            let login = label.split('(').nth(1).unwrap().trim_end_matches(')');
+            PickerItem::new(Some(login.to_string()), *label)
+        })
+        .collect()
+    }
+
+    fn press(picker: &mut Picker, code: KeyCode) -> PickerEvent {
+        let items = picker.matches(items());
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &items)
+    }
+
+    #[test]
+    fn test_filter_ranks_fuzzy_matches() {
+        let mut picker = Picker::new();
+        for c in "ивп".chars() {
+            press(&mut picker, KeyCode::Char(c));
+        }
+        let labels: Vec<String> = picker
+            .matches(items())
+            .into_iter()
+            .map(|item| item.label)
+            .collect();
+        assert_eq!(labels, ["Иван Петров (ivan.petrov)"]);
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            PickerEvent::Picked(Some(items()[1].clone()))
+        );
+    }
+
+    #[test]
+    fn test_multi_picker_toggles_marks() {
+        let mut picker = Picker::multi(["sergey".to_string()]);
+        press(&mut picker, KeyCode::Tab);
+        press(&mut picker, KeyCode::Down);
+        press(&mut picker, KeyCode::Down);
+        press(&mut picker, KeyCode::Tab);
+
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            PickerEvent::Done(vec!["anna".to_string()])
+        );
+        assert!(picker.lines(&items())[2].starts_with("  [x] Анна"));
+
+        for c in "Иван П".chars() {
+            press(&mut picker, KeyCode::Char(c));
+        }
+        assert_eq!(picker.filter(), "Иван П");
+    }
+
+    #[test]
+    fn test_lines_scroll_to_selected() {
+        let many: Vec<PickerItem> = (0..15)
+            .map(|i| PickerItem::new(Some(i.to_string()), format!("user{i}")))
+            .collect();
+        let mut picker = Picker::new();
+        picker.select(12);
+
+        let lines = picker.lines(&many);
+        assert_eq!(lines[2], "  user3");
+        assert_eq!(lines[11], "> user12");
+        assert_eq!(lines[12], "  … ещё 2");
+    }
+}
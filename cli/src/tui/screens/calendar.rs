@@ -0,0 +1,196 @@
+use std::{future::Future, pin::Pin};
+
+use calendar_lib::{events_on, Event, Zone};
+use chrono::NaiveDateTime;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Screen, ScreenEvent, ScreenId, ScreenMeta};
+use crate::timezone;
+use crate::tui::system::open_url;
+
+pub struct CalendarScreen {
+    events: Vec<Event>,
+    selected: usize,
+    status: Option<String>,
+    /// Пояс, в котором показываются встречи и определяется «сегодня»
+    zone: Zone,
+}
+
+impl CalendarScreen {
+    pub fn new() -> Self {
+        let mut screen = Self {
+            events: Vec::new(),
+            selected: 0,
+            status: None,
+            zone: timezone::current(),
+        };
+        screen.reload();
+        screen
+    }
+
+    fn reload(&mut self) {
+        match calendar_lib::load_from_env_in(self.zone) {
+            Ok(events) => {
+                self.events = events_on(&events, self.zone.today())
+                    .into_iter()
+                    .cloned()
+                    .collect();
+                self.selected = self.selected.min(self.events.len().saturating_sub(1));
+                self.status = None;
+            }
+            Err(err) => {
+                self.events.clear();
+                self.status = Some(format!("Ошибка календаря: {err}"));
+            }
+        }
+    }
+
+    fn selected_event(&self) -> Option<&Event> {
+        self.events.get(self.selected)
+    }
+
+    /// Ближайшие незавершённые встречи для главного экрана; None, если календарь не настроен
+    pub fn upcoming_text(&self, limit: usize) -> Option<String> {
+        std::env::var_os("CALENDAR_ICS")?;
+        Some(render_upcoming(&self.events, limit, self.zone.now()))
+    }
+
+    fn open_meeting_link(&mut self) {
+        let Some(link) = self.selected_event().and_then(Event::meeting_link) else {
+            self.status = Some("У события нет ссылки на встречу".to_string());
+            return;
+        };
+        let link = link.to_string();
+        self.status = Some(match open_url(&link) {
+            Ok(_) => format!("Открываю {link}"),
+            Err(err) => format!("Не удалось открыть {link}: {err}"),
+        });
+    }
+}
+
+fn render_upcoming(events: &[Event], limit: usize, now: NaiveDateTime) -> String {
+    let upcoming: Vec<String> = events
+        .iter()
+        .filter(|event| event.end > now)
+        .take(limit)
+        .map(|event| {
+            let time = if event.all_day {
+                "весь день".to_string()
+            } else {
+                event.start.format("%H:%M").to_string()
+            };
+            let active = if event.is_active_at(now) { " ●" } else { "" };
+            format!("{time}  {}{active}", event.summary)
+        })
+        .collect();
+
+    if upcoming.is_empty() {
+        "Больше встреч сегодня нет".to_string()
+    } else {
+        upcoming.join("\n")
+    }
+}
+
+fn render_agenda(events: &[Event], selected: usize, now: NaiveDateTime) -> String {
+    if events.is_empty() {
+        return "На сегодня событий нет".to_string();
+    }
+
+    let now_line = format!("──── сейчас {} ────", now.format("%H:%M"));
+    let mut lines = Vec::new();
+    let mut now_shown = false;
+
+    for (index, event) in events.iter().enumerate() {
+        if !now_shown && event.start > now {
+            lines.push(now_line.clone());
+            now_shown = true;
+        }
+
+        let marker = if index == selected { ">" } else { " " };
+        let time = if event.all_day {
+            "весь день".to_string()
+        } else {
+            format!(
+                "{}–{}",
+                event.start.format("%H:%M"),
+                event.end.format("%H:%M")
+            )
+        };
+        let active = if event.is_active_at(now) { " ●" } else { "" };
+        lines.push(format!("{marker} {time}  {}{active}", event.summary));
+
+        let issues = event.issue_keys();
+        if !issues.is_empty() {
+            lines.push(format!("      задачи: {}", issues.join(", ")));
+        }
+    }
+
+    if !now_shown {
+        lines.push(now_line);
+    }
+
+    lines.join("\n")
+}
+
+impl Screen for CalendarScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Calendar,
+            title: "Calendar",
+            label: "Календарь",
+            shortcut: '3',
+            requires: &[],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        "Календарь: ↑/↓ выбор, i — задача, o — ссылка на встречу, r — обновить"
+    }
+
+    fn input_text(&self) -> &str {
+        ""
+    }
+
+    fn output_text(&self) -> String {
+        let agenda = render_agenda(&self.events, self.selected, self.zone.now());
+        match &self.status {
+            Some(status) => format!("{agenda}\n\n{status}"),
+            None => agenda,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.events.len() => self.selected += 1,
+            KeyCode::Char('r') => self.reload(),
+            KeyCode::Char('o') => self.open_meeting_link(),
+            KeyCode::Char('i') => {
+                let issue_key = self
+                    .selected_event()
+                    .and_then(|event| event.issue_keys().into_iter().next());
+                match issue_key {
+                    Some(issue_key) => return ScreenEvent::Navigate(ScreenId::Tracker, issue_key),
+                    None => self.status = Some("В событии нет ключей задач".to_string()),
+                }
+            }
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.status = Some(text);
+    }
+
+    fn command_preview(&self, input: &str) -> String {
+        format!("> calendar {input}")
+    }
+
+    fn execute<'a>(&'a mut self, _input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            self.reload();
+            "Календарь обновлён".to_string()
+        })
+    }
+}
@@ -0,0 +1,301 @@
+//! Лента комментариев задачи: переходы между комментариями, фильтр по автору и цитирование
+
+use std::collections::BTreeSet;
+
+use llm_lib::context::{self, ContextItem};
+use tracker_lib::models::{Comment, Issue};
+
+use crate::redact;
+
+/// Сколько комментариев показывать в списке вокруг выбранного
+const LIST_WINDOW: usize = 40;
+/// Бюджет контекста задачи для LLM в токенах
+const CONTEXT_TOKENS: usize = 4_000;
+
+/// Приоритеты частей контекста: заголовок и описание важнее комментариев
+const HEADING_PRIORITY: u32 = 1_000;
+const DESCRIPTION_PRIORITY: u32 = 900;
+
+pub struct CommentThread {
+    pub issue_key: String,
+    comments: Vec<Comment>,
+    /// Позиция среди комментариев, прошедших фильтр
+    selected: usize,
+    author: Option<String>,
+}
+
+impl CommentThread {
+    /// Открывает ленту на последнем комментарии
+    pub fn new(issue_key: String, comments: Vec<Comment>) -> Self {
+        let mut thread = Self {
+            issue_key,
+            comments,
+            selected: 0,
+            author: None,
+        };
+        thread.select_last();
+        thread
+    }
+
+    /// Заменяет комментарии после ответа, сохраняя фильтр
+    pub fn replace(&mut self, comments: Vec<Comment>) {
+        self.comments = comments;
+        self.select_last();
+    }
+
+    fn visible(&self) -> Vec<&Comment> {
+        self.comments
+            .iter()
+            .filter(|comment| {
+                self.author
+                    .as_deref()
+                    .is_none_or(|author| author_name(comment) == author)
+            })
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&Comment> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.visible().len().saturating_sub(1);
+    }
+
+    /// Переключает фильтр: все авторы, затем каждый автор по алфавиту
+    pub fn cycle_author(&mut self) {
+        let authors: BTreeSet<String> = self.comments.iter().map(author_name).collect();
+        self.author = match &self.author {
+            None => authors.into_iter().next(),
+            Some(current) => authors
+                .into_iter()
+                .skip_while(|author| author != current)
+                .nth(1),
+        };
+        self.select_last();
+    }
+
+    /// Текст ответа с цитатой выбранного комментария
+    pub fn quote(&self) -> String {
+        let Some(comment) = self.selected() else {
+            return String::new();
+        };
+        let mut reply = format!("> {} пишет:\n", author_name(comment));
+        for line in comment.text.lines() {
+            reply.push_str(&format!("> {line}\n"));
+        }
+        reply.push('\n');
+        reply
+    }
+
+    /// Список комментариев для левой панели; выбранный отмечен "> "
+    pub fn list_text(&self) -> String {
+        let visible = self.visible();
+        if visible.is_empty() {
+            return "Комментариев нет".to_string();
+        }
+        let start = self.selected.saturating_sub(LIST_WINDOW / 2);
+        visible
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(LIST_WINDOW)
+            .map(|(index, comment)| {
+                let marker = if index == self.selected { ">" } else { " " };
+                let first_line = comment.text.lines().next().unwrap_or_default();
+                format!("{marker} {}: {first_line}", author_name(comment))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Выбранный комментарий целиком
+    pub fn detail_text(&self) -> String {
+        let visible = self.visible();
+        let filter = self
+            .author
+            .as_deref()
+            .map(|author| format!(", автор: {author}"))
+            .unwrap_or_default();
+        let header = format!(
+            "# Комментарии {} ({}/{}{filter})",
+            self.issue_key,
+            (self.selected + 1).min(visible.len()),
+            visible.len()
+        );
+        match visible.get(self.selected) {
+            Some(comment) => format!(
+                "{header}\n\n**{}** {}\n\n{}",
+                author_name(comment),
+                comment.created_at.as_deref().unwrap_or_default(),
+                comment.text
+            ),
+            None => format!("{header}\n\nКомментариев нет"),
+        }
+    }
+}
+
+/// Контекст задачи для LLM: заголовок, описание и комментарии в пределах бюджета
+pub fn llm_context(issue: &Issue, comments: &[Comment]) -> String {
+    let issue = redact::issue(issue);
+    let comments: Vec<Comment> = comments
+        .iter()
+        .cloned()
+        .map(|mut comment| {
+            comment.text = redact::text(&issue.key, &comment.text);
+            comment
+        })
+        .collect();
+    issue_context(&issue, &comments, CONTEXT_TOKENS)
+}
+
+/// Если всё не помещается, из комментариев остаются более свежие
+fn issue_context(issue: &Issue, comments: &[Comment], budget_tokens: usize) -> String {
+    let status = issue
+        .status
+        .as_ref()
+        .and_then(|status| status.display.as_deref())
+        .map_or_else(
+            || "Статус не указан".to_string(),
+            |status| format!("Статус: {status}"),
+        );
+    let mut items =
+        vec![
+            ContextItem::new(format!("Задача {}: {}", issue.key, issue.summary), status)
+                .priority(HEADING_PRIORITY),
+        ];
+    if let Some(description) = issue
+        .description
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    {
+        items.push(ContextItem::new("Описание", description).priority(DESCRIPTION_PRIORITY));
+    }
+    items.extend(comments.iter().enumerate().map(|(index, comment)| {
+        let title = format!(
+            "{} ({})",
+            author_name(comment),
+            comment.created_at.as_deref().unwrap_or("дата неизвестна")
+        );
+        ContextItem::new(title, comment.text.clone()).priority(index as u32)
+    }));
+    context::pack(items, budget_tokens)
+}
+
+fn author_name(comment: &Comment) -> String {
+    comment
+        .created_by
+        .as_ref()
+        .and_then(|user| user.display.clone().or_else(|| user.login.clone()))
+        .unwrap_or_else(|| "неизвестный автор".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn comment(author: &str, text: &str) -> Comment {
+        serde_json::from_value(serde_json::json!({
+            "text": text,
+            "createdBy": {"display": author}
+        }))
+        .unwrap()
+    }
+
+    fn thread() -> CommentThread {
+        CommentThread::new(
+            "TREK-1".to_string(),
+            vec![
+                comment("Анна", "Первый"),
+                comment("Борис", "Второй\nс продолжением"),
+                comment("Анна", "Третий"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_thread_starts_at_last_comment_and_navigates() {
+        let mut thread = thread();
+        assert_eq!(thread.selected().unwrap().text, "Третий");
+
+        thread.select_previous();
+        thread.select_previous();
+        thread.select_previous();
+        assert_eq!(thread.selected().unwrap().text, "Первый");
+
+        thread.select_next();
+        assert!(thread
+            .list_text()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("> Борис"));
+    }
+
+    #[test]
+    fn test_author_filter_cycles_through_authors() {
+        let mut thread = thread();
+
+        thread.cycle_author();
+        assert_eq!(thread.list_text().lines().count(), 2);
+        assert!(thread.detail_text().contains("автор: Анна"));
+
+        thread.cycle_author();
+        assert_eq!(thread.selected().unwrap().text, "Второй\nс продолжением");
+
+        thread.cycle_author();
+        assert_eq!(thread.list_text().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_llm_context_keeps_recent_comments_within_budget() {
+        let issue = testing::issue("TREK-1")
+            .summary("Падает сборка")
+            .description("Логи во вложении")
+            .build();
+        let comments: Vec<Comment> = (1..=7)
+            .map(|index| {
+                let text = format!("Комментарий {index}: {}", "подробности ".repeat(15));
+                comment("Анна", text.trim_end())
+            })
+            .collect();
+
+        let context = issue_context(&issue, &comments, 200);
+
+        assert!(context.starts_with("## Задача TREK-1: Падает сборка\n\nСтатус не указан"));
+        assert!(context.contains("## Описание\n\nЛоги во вложении"));
+        assert!(!context.contains("Комментарий 4"));
+        assert!(context.contains("Комментарий 5"));
+        assert!(context.contains("Комментарий 7"));
+        assert!(llm_lib::context::estimate_tokens(&context) <= 200);
+
+        let full = llm_context(&issue, &comments);
+        assert!(full.contains("Комментарий 1") && full.ends_with("подробности"));
+    }
+
+    #[test]
+    fn test_quote_prefixes_every_line() {
+        let mut thread = thread();
+        thread.select_previous();
+
+        assert_eq!(
+            thread.quote(),
+            "> Борис пишет:\n> Второй\n> с продолжением\n\n"
+        );
+    }
+}
@@ -0,0 +1,884 @@
+use std::{future::Future, pin::Pin};
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use llm_lib::{events::LlmEvent, LlmClient, LlmConfig};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::{
+    events::TrackerEvent,
+    ids::{QueueKey, UserLogin},
+    models::QueueDetails,
+    task::CreateIssueRequest,
+    TrackerClient,
+};
+
+use super::{scrollback, Popup, Screen, ScreenEvent, ScreenId, ScreenMeta};
+use crate::config::Config;
+use crate::llm::DEFAULT_MODEL;
+use crate::org;
+use crate::people::{self, Directory};
+use crate::presets::QueuePreset;
+use crate::settings;
+use crate::titles;
+use crate::tui::{
+    bus::EventBus,
+    picker::{self, Picker, PickerEvent, PickerItem},
+    setup::Service,
+    toast::ToastLevel,
+};
+
+/// Экран создания задачи: мастер из шагов очередь → тип → название → описание →
+/// исполнитель → наблюдатели → предпросмотр
+///
+/// После длинного описания мастер показывает варианты названия от LLM.
+pub struct CreateScreen {
+    log: Vec<String>,
+    /// Очереди пользователя; загружаются при первом открытии мастера
+    queues: Vec<QueueDetails>,
+    /// Справочник пользователей для выбора исполнителя и наблюдателей
+    directory: Directory,
+    wizard: Option<Wizard>,
+    pending: Option<CreateAction>,
+    events: UnboundedSender<TrackerEvent>,
+    llm_events: UnboundedSender<LlmEvent>,
+    bus: EventBus,
+}
+
+enum CreateAction {
+    LoadQueues,
+    LoadQueue(QueueKey),
+    SuggestTitles {
+        summary: String,
+        description: String,
+    },
+    Create(Box<CreateIssueRequest>),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Step {
+    #[default]
+    Queue,
+    Type,
+    Summary,
+    Description,
+    /// Варианты названия от LLM для длинного описания
+    Title,
+    Assignee,
+    Followers,
+    Preview,
+}
+
+/// Очереди и пользователи, из которых выбирают на шагах мастера
+struct Sources<'a> {
+    queues: &'a [QueueDetails],
+    directory: &'a Directory,
+}
+
+/// Состояние мастера создания задачи
+#[derive(Debug, Default)]
+struct Wizard {
+    step: Step,
+    /// Фильтр и выделение на шагах выбора
+    picker: Picker,
+    queue: Option<QueueDetails>,
+    preset: QueuePreset,
+    issue_type: Option<PickerItem>,
+    summary: String,
+    description: String,
+    assignee: Option<PickerItem>,
+    followers: Vec<String>,
+    /// Варианты названия от LLM
+    titles: Vec<String>,
+    /// Описание, по которому получены варианты; при его правке варианты запрашиваются заново
+    titles_for: String,
+    /// Ошибка проверки поля на текущем шаге
+    error: Option<String>,
+}
+
+/// Что сделать после нажатия клавиши в мастере
+#[derive(Debug, PartialEq)]
+enum WizardEvent {
+    None,
+    Close,
+    LoadQueue(QueueKey),
+    SuggestTitles,
+    Create,
+}
+
+impl Wizard {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn go(&mut self, step: Step) {
+        self.step = step;
+        self.picker = match step {
+            Step::Followers => Picker::multi(self.followers.clone()),
+            _ => Picker::new(),
+        };
+        self.error = None;
+    }
+
+    /// Очередь выбрана: тип по умолчанию берётся из пресета, затем из настроек очереди
+    fn set_queue(&mut self, queue: QueueDetails, preset: QueuePreset) {
+        let preferred = preset
+            .issue_type
+            .clone()
+            .filter(|key| queue.allows_type(key))
+            .or_else(|| queue.default_type.as_ref().and_then(|t| t.key.clone()));
+        self.queue = Some(queue);
+        self.preset = preset;
+        self.go(Step::Type);
+        let preferred = self
+            .type_choices()
+            .iter()
+            .position(|choice| choice.value.is_some() && choice.value == preferred)
+            .unwrap_or(0);
+        self.picker.select(preferred);
+    }
+
+    fn type_choices(&self) -> Vec<PickerItem> {
+        let Some(queue) = &self.queue else {
+            return Vec::new();
+        };
+        let choices: Vec<PickerItem> = queue
+            .issue_types()
+            .filter_map(|issue_type| {
+                let key = issue_type.key.clone()?;
+                let label = match &issue_type.display {
+                    Some(display) => format!("{display} ({key})"),
+                    None => key.clone(),
+                };
+                Some(PickerItem::new(Some(key), label))
+            })
+            .collect();
+        if choices.is_empty() {
+            return vec![PickerItem::new(None, "Тип по умолчанию очереди")];
+        }
+        choices
+    }
+
+    /// Описание готово: для длинного описания сначала варианты названия от LLM
+    fn finish_description(&mut self) -> WizardEvent {
+        if !titles::is_long(&self.description) {
+            self.go(Step::Assignee);
+            return WizardEvent::None;
+        }
+        if self.titles.is_empty() || self.titles_for != self.description {
+            return WizardEvent::SuggestTitles;
+        }
+        self.go(Step::Title);
+        WizardEvent::None
+    }
+
+    /// Варианты от LLM получены: показать их выбор
+    fn set_titles(&mut self, description: String, titles: Vec<String>) {
+        self.titles = titles;
+        self.titles_for = description;
+        self.go(Step::Title);
+    }
+
+    /// Варианты LLM, затем введённое название
+    fn title_choices(&self) -> Vec<PickerItem> {
+        let mut choices: Vec<PickerItem> = self
+            .titles
+            .iter()
+            .map(|title| PickerItem::new(Some(title.clone()), title.clone()))
+            .collect();
+        choices.push(PickerItem::new(None, format!("Оставить: {}", self.summary)));
+        choices
+    }
+
+    /// Без исполнителя, владелец очереди первым, затем пользователи справочника
+    fn assignee_choices(&self, directory: &Directory) -> Vec<PickerItem> {
+        let mut choices = vec![PickerItem::new(None, "Без исполнителя")];
+        let lead = self
+            .queue
+            .as_ref()
+            .and_then(|queue| queue.lead.as_ref())
+            .and_then(|lead| lead.login.as_ref().map(|login| (login, &lead.display)));
+        if let Some((login, display)) = lead {
+            let label = match display {
+                Some(display) => format!("{display} ({login}), владелец очереди"),
+                None => format!("{login}, владелец очереди"),
+            };
+            choices.push(PickerItem::new(Some(login.clone()), label));
+        }
+        choices.extend(
+            picker::user_items(directory, self.picker.filter())
+                .into_iter()
+                .filter(|item| lead.is_none_or(|(login, _)| item.value.as_ref() != Some(login))),
+        );
+        choices
+    }
+
+    /// Варианты текущего шага выбора, отфильтрованные по введённому тексту
+    fn choices(&self, sources: &Sources) -> Vec<PickerItem> {
+        let choices = match self.step {
+            Step::Queue => sources
+                .queues
+                .iter()
+                .map(|queue| {
+                    let label = match &queue.name {
+                        Some(name) => format!("{} — {name}", queue.key),
+                        None => queue.key.clone(),
+                    };
+                    PickerItem::new(Some(queue.key.clone()), label)
+                })
+                .collect(),
+            Step::Type => self.type_choices(),
+            Step::Title => self.title_choices(),
+            Step::Assignee => self.assignee_choices(sources.directory),
+            Step::Followers => picker::user_items(sources.directory, self.picker.filter()),
+            Step::Summary | Step::Description | Step::Preview => return Vec::new(),
+        };
+        self.picker.matches(choices)
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, sources: &Sources) -> WizardEvent {
+        if key.code == KeyCode::Esc {
+            return self.back();
+        }
+        match self.step {
+            Step::Queue | Step::Type | Step::Title | Step::Assignee | Step::Followers => {
+                self.handle_picker_key(key, sources)
+            }
+            Step::Summary => {
+                match key.code {
+                    KeyCode::Enter => {
+                        let summary = self.summary.trim();
+                        if summary.is_empty() {
+                            self.error = Some("Название не может быть пустым".to_string());
+                        } else {
+                            self.summary = summary.to_string();
+                            self.go(Step::Description);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        self.summary.pop();
+                    }
+                    KeyCode::Char(c) => self.summary.push(c),
+                    _ => {}
+                }
+                WizardEvent::None
+            }
+            Step::Description => {
+                match key.code {
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return self.finish_description();
+                    }
+                    KeyCode::Tab => return self.finish_description(),
+                    KeyCode::Enter => self.description.push('\n'),
+                    KeyCode::Backspace => {
+                        self.description.pop();
+                    }
+                    KeyCode::Char(c) => self.description.push(c),
+                    _ => {}
+                }
+                WizardEvent::None
+            }
+            Step::Preview => match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => match self.request() {
+                    Ok(_) => WizardEvent::Create,
+                    Err(err) => {
+                        self.error = Some(err);
+                        WizardEvent::None
+                    }
+                },
+                _ => WizardEvent::None,
+            },
+        }
+    }
+
+    fn handle_picker_key(&mut self, key: KeyEvent, sources: &Sources) -> WizardEvent {
+        let choices = self.choices(sources);
+        match self.picker.handle_key(key, &choices) {
+            PickerEvent::None => WizardEvent::None,
+            PickerEvent::Picked(choice) => self.pick(choice),
+            PickerEvent::Done(followers) => {
+                self.followers = followers;
+                self.go(Step::Preview);
+                WizardEvent::None
+            }
+        }
+    }
+
+    fn pick(&mut self, choice: Option<PickerItem>) -> WizardEvent {
+        match self.step {
+            Step::Queue => {
+                // Очередь можно ввести ключом, даже если её нет в списке
+                let key = choice
+                    .and_then(|choice| choice.value)
+                    .unwrap_or_else(|| self.picker.filter().trim().to_uppercase());
+                match key.parse::<QueueKey>() {
+                    Ok(queue) => WizardEvent::LoadQueue(queue),
+                    Err(err) => {
+                        self.error = Some(err.to_string());
+                        WizardEvent::None
+                    }
+                }
+            }
+            Step::Type => {
+                let Some(choice) = choice else {
+                    self.error = Some("Выберите тип задачи".to_string());
+                    return WizardEvent::None;
+                };
+                self.issue_type = Some(choice);
+                self.go(Step::Summary);
+                WizardEvent::None
+            }
+            Step::Title => {
+                if let Some(title) = choice.and_then(|choice| choice.value) {
+                    self.summary = title;
+                }
+                self.go(Step::Assignee);
+                WizardEvent::None
+            }
+            Step::Assignee => {
+                let Some(choice) = choice else {
+                    self.error = Some("Введите логин или выберите «Без исполнителя»".to_string());
+                    return WizardEvent::None;
+                };
+                self.assignee = Some(choice);
+                self.go(Step::Followers);
+                WizardEvent::None
+            }
+            Step::Summary | Step::Description | Step::Followers | Step::Preview => {
+                WizardEvent::None
+            }
+        }
+    }
+
+    /// Вернуться на шаг назад; с первого шага — закрыть мастер
+    fn back(&mut self) -> WizardEvent {
+        let previous = match self.step {
+            Step::Queue => return WizardEvent::Close,
+            Step::Type => Step::Queue,
+            Step::Summary => Step::Type,
+            Step::Description => Step::Summary,
+            Step::Title | Step::Assignee => Step::Description,
+            Step::Followers => Step::Assignee,
+            Step::Preview => Step::Followers,
+        };
+        self.go(previous);
+        WizardEvent::None
+    }
+
+    /// Запрос на создание задачи с пресетом очереди; ошибка — описание неверного поля
+    fn request(&self) -> Result<CreateIssueRequest, String> {
+        let queue = self.queue.as_ref().ok_or("Очередь не выбрана")?;
+        let queue_key = queue
+            .key
+            .parse::<QueueKey>()
+            .map_err(|err| err.to_string())?;
+        let summary = self.summary.trim();
+        if summary.is_empty() {
+            return Err("Название не может быть пустым".to_string());
+        }
+        let mut request = CreateIssueRequest::new(queue_key, summary);
+        if let Some(key) = self.issue_type.as_ref().and_then(|t| t.value.clone()) {
+            if !queue.allows_type(&key) {
+                return Err(format!("Тип {key} недоступен в очереди {}", queue.key));
+            }
+            request.issue_type = Some(key);
+        }
+        let description = self.description.trim();
+        if !description.is_empty() {
+            request.description = Some(description.to_string());
+        }
+        if let Some(login) = self.assignee.as_ref().and_then(|a| a.value.as_deref()) {
+            request.assignee = Some(login.parse::<UserLogin>().map_err(|err| err.to_string())?);
+        }
+        for login in &self.followers {
+            request
+                .followers
+                .push(login.parse::<UserLogin>().map_err(|err| err.to_string())?);
+        }
+        self.preset.apply(&mut request);
+        Ok(request)
+    }
+
+    fn title(&self) -> String {
+        let (number, name, keys) = match self.step {
+            Step::Queue => (1, "очередь", "ввод — фильтр или ключ, ↑/↓, Enter — выбрать"),
+            Step::Type => (2, "тип", "ввод — фильтр, ↑/↓, Enter — выбрать"),
+            Step::Summary => (3, "название", "Enter — дальше"),
+            Step::Description => (4, "описание", "Enter — новая строка, Tab/Ctrl+S — дальше"),
+            Step::Title => (4, "название от LLM", "↑/↓, Enter — выбрать"),
+            Step::Assignee => (
+                5,
+                "исполнитель",
+                "ввод — имя или логин, ↑/↓, Enter — выбрать",
+            ),
+            Step::Followers => (
+                6,
+                "наблюдатели",
+                "ввод — имя, Tab — отметить, Enter — дальше",
+            ),
+            Step::Preview => (7, "предпросмотр", "Enter/y — создать"),
+        };
+        format!("Новая задача {number}/7: {name} | {keys} | Esc — назад")
+    }
+
+    fn body(&self, sources: &Sources) -> String {
+        let mut lines = Vec::new();
+        if let Some(error) = &self.error {
+            lines.push(format!("- {error}"));
+            lines.push(String::new());
+        }
+        match self.step {
+            Step::Queue | Step::Type | Step::Title | Step::Assignee | Step::Followers => {
+                lines.extend(self.picker.lines(&self.choices(sources)));
+            }
+            Step::Summary => lines.push(format!("Название: {}", self.summary)),
+            Step::Description => lines.push(self.description.clone()),
+            Step::Preview => lines.push(match self.request() {
+                Ok(request) => preview(&request),
+                Err(err) => format!("- {err}"),
+            }),
+        }
+        lines.join("\n")
+    }
+}
+
+/// Поля будущей задачи для предпросмотра
+fn preview(request: &CreateIssueRequest) -> String {
+    let mut lines = vec![
+        format!("Очередь: {}", request.queue),
+        format!(
+            "Тип: {}",
+            request.issue_type.as_deref().unwrap_or("по умолчанию")
+        ),
+        format!("Название: {}", request.summary),
+    ];
+    if let Some(priority) = &request.priority {
+        lines.push(format!("Приоритет: {priority}"));
+    }
+    lines.push(format!(
+        "Исполнитель: {}",
+        request
+            .assignee
+            .as_ref()
+            .map_or("не назначен".to_string(), |login| login
+                .to_string())
+    ));
+    if !request.components.is_empty() {
+        lines.push(format!("Компоненты: {}", request.components.join(", ")));
+    }
+    if !request.tags.is_empty() {
+        lines.push(format!("Теги: {}", request.tags.join(", ")));
+    }
+    if !request.followers.is_empty() {
+        let followers: Vec<String> = request.followers.iter().map(|f| f.to_string()).collect();
+        lines.push(format!("Наблюдатели: {}", followers.join(", ")));
+    }
+    if let Some(description) = &request.description {
+        lines.push(String::new());
+        lines.push(description.clone());
+    }
+    lines.join("\n")
+}
+
+impl CreateScreen {
+    pub fn new(
+        events: UnboundedSender<TrackerEvent>,
+        llm_events: UnboundedSender<LlmEvent>,
+        bus: EventBus,
+    ) -> Self {
+        Self {
+            log: vec!["Создание задачи: нажмите Enter, чтобы открыть мастер".to_string()],
+            queues: Vec::new(),
+            directory: Directory::default(),
+            wizard: None,
+            pending: None,
+            events,
+            llm_events,
+            bus,
+        }
+    }
+
+    fn tracker(&self) -> Result<TrackerClient> {
+        Ok(settings::tracker_client()?.with_event_sender(self.events.clone()))
+    }
+
+    fn llm(&self) -> Result<LlmClient> {
+        let config = LlmConfig::new(DEFAULT_MODEL)?;
+        Ok(LlmClient::new(config)?.with_event_sender(self.llm_events.clone()))
+    }
+
+    fn submit(&mut self, action: CreateAction) -> ScreenEvent {
+        let command = match &action {
+            CreateAction::LoadQueues => String::new(),
+            CreateAction::LoadQueue(queue) => queue.to_string(),
+            CreateAction::SuggestTitles { summary, .. } => summary.clone(),
+            CreateAction::Create(request) => request.queue.to_string(),
+        };
+        self.pending = Some(action);
+        ScreenEvent::Submit(command)
+    }
+
+    fn push_log(&mut self, text: String) {
+        self.log.push(text);
+        if self.log.len() > scrollback() {
+            let drain_count = self.log.len() - scrollback();
+            self.log.drain(0..drain_count);
+        }
+    }
+
+    async fn run(&mut self, action: CreateAction) -> Result<String> {
+        match action {
+            CreateAction::LoadQueues => {
+                let client = self.tracker()?;
+                self.queues = org::cached(&client, false).await?.queues;
+                // Без справочника исполнителя можно ввести логином
+                match people::cached(&client, false).await {
+                    Ok(directory) => self.directory = directory,
+                    Err(err) => tracing::warn!(error = %err, "Справочник пользователей недоступен"),
+                }
+                Ok(format!(
+                    "Доступно очередей: {}, пользователей: {}",
+                    self.queues.len(),
+                    self.directory.people.len()
+                ))
+            }
+            CreateAction::LoadQueue(queue) => {
+                let details = self.tracker()?.get_queue(&queue).await?;
+                let preset = Config::load()?
+                    .presets
+                    .get(&queue)
+                    .cloned()
+                    .unwrap_or_default();
+                let wizard = self.wizard.as_mut().context("Мастер создания закрыт")?;
+                wizard.set_queue(details, preset);
+                Ok(format!("Очередь {queue} выбрана"))
+            }
+            CreateAction::SuggestTitles {
+                summary,
+                description,
+            } => {
+                let suggested = match self.llm() {
+                    Ok(llm) => titles::suggest(&llm, &summary, &description).await,
+                    Err(err) => Err(err),
+                };
+                let wizard = self.wizard.as_mut().context("Мастер создания закрыт")?;
+                match suggested {
+                    Ok(suggested) => {
+                        let message = format!("Вариантов названия: {}", suggested.len());
+                        wizard.set_titles(description, suggested);
+                        Ok(message)
+                    }
+                    // Без вариантов мастер продолжает с введённым названием
+                    Err(err) => {
+                        let message = format!("Варианты названия недоступны: {err:#}");
+                        wizard.go(Step::Assignee);
+                        wizard.error = Some(message.clone());
+                        Ok(message)
+                    }
+                }
+            }
+            CreateAction::Create(request) => {
+                let issue = self.tracker()?.create_issue(&request).await?;
+                self.wizard = None;
+                let message = format!("Создана задача {}: {}", issue.key, issue.summary);
+                self.bus.toast(ToastLevel::Info, message.clone());
+                Ok(message)
+            }
+        }
+    }
+}
+
+impl Screen for CreateScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Create,
+            title: "Create",
+            label: "Новая задача",
+            shortcut: '8',
+            requires: &[Service::Tracker],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        "Создание задачи: Enter — новая задача"
+    }
+
+    fn input_text(&self) -> &str {
+        ""
+    }
+
+    fn output_text(&self) -> String {
+        self.log.join("\n\n")
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        let Some(wizard) = self.wizard.as_mut() else {
+            if key.code != KeyCode::Enter {
+                return ScreenEvent::None;
+            }
+            self.wizard = Some(Wizard::new());
+            if self.queues.is_empty() {
+                return self.submit(CreateAction::LoadQueues);
+            }
+            return ScreenEvent::None;
+        };
+        let sources = Sources {
+            queues: &self.queues,
+            directory: &self.directory,
+        };
+        match wizard.handle_key(key, &sources) {
+            WizardEvent::None => ScreenEvent::None,
+            WizardEvent::Close => {
+                self.wizard = None;
+                ScreenEvent::None
+            }
+            WizardEvent::LoadQueue(queue) => self.submit(CreateAction::LoadQueue(queue)),
+            WizardEvent::SuggestTitles => {
+                let action = CreateAction::SuggestTitles {
+                    summary: wizard.summary.clone(),
+                    description: wizard.description.clone(),
+                };
+                self.submit(action)
+            }
+            WizardEvent::Create => match wizard.request() {
+                Ok(request) => self.submit(CreateAction::Create(Box::new(request))),
+                Err(_) => ScreenEvent::None,
+            },
+        }
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.push_log(text);
+    }
+
+    fn popup(&self) -> Option<Popup> {
+        let wizard = self.wizard.as_ref()?;
+        Some(Popup {
+            title: wizard.title(),
+            body: wizard.body(&Sources {
+                queues: &self.queues,
+                directory: &self.directory,
+            }),
+        })
+    }
+
+    fn command_preview(&self, input: &str) -> String {
+        match &self.pending {
+            Some(CreateAction::LoadQueues) => "> tracker queues".to_string(),
+            Some(CreateAction::LoadQueue(_)) => format!("> tracker queue {input}"),
+            Some(CreateAction::SuggestTitles { .. }) => format!("> llm titles {input}"),
+            Some(CreateAction::Create(_)) | None => format!("> tracker create --queue {input}"),
+        }
+    }
+
+    fn execute<'a>(&'a mut self, _input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            let Some(action) = self.pending.take() else {
+                return "Откройте мастер клавишей Enter".to_string();
+            };
+            match self.run(action).await {
+                Ok(output) => output,
+                Err(err) => {
+                    let message = format!("Ошибка создания задачи: {err}");
+                    if let Some(wizard) = self.wizard.as_mut() {
+                        wizard.error = Some(err.to_string());
+                    }
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue() -> QueueDetails {
+        serde_json::from_value(serde_json::json!({
+            "key": "TREK",
+            "name": "Трекер",
+            "lead": {"login": "ann", "display": "Анна"},
+            "defaultType": {"key": "task"},
+            "issueTypesConfig": [
+                {"issueType": {"key": "task", "display": "Задача"}},
+                {"issueType": {"key": "bug", "display": "Ошибка"}}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn directory() -> Directory {
+        let users = serde_json::from_value(serde_json::json!([
+            {"login": "ann", "display": "Анна"},
+            {"login": "ivan.petrov", "display": "Иван Петров"},
+            {"login": "olga", "display": "Ольга Смирнова"}
+        ]))
+        .unwrap();
+        Directory::from_users(users, chrono::Utc::now())
+    }
+
+    fn press(wizard: &mut Wizard, code: KeyCode) -> WizardEvent {
+        let sources = Sources {
+            queues: &[queue()],
+            directory: &directory(),
+        };
+        wizard.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &sources)
+    }
+
+    fn type_text(wizard: &mut Wizard, text: &str) {
+        for c in text.chars() {
+            press(wizard, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_wizard_walks_steps_to_request() {
+        let mut wizard = Wizard::new();
+        type_text(&mut wizard, "трек");
+        assert_eq!(
+            press(&mut wizard, KeyCode::Enter),
+            WizardEvent::LoadQueue("TREK".parse().unwrap())
+        );
+
+        let preset = QueuePreset {
+            issue_type: Some("bug".to_string()),
+            tags: vec!["from-tui".to_string()],
+            ..QueuePreset::default()
+        };
+        wizard.set_queue(queue(), preset);
+        // Тип из пресета выбран заранее
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.step, Step::Summary);
+
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(
+            wizard.error.as_deref(),
+            Some("Название не может быть пустым")
+        );
+        type_text(&mut wizard, "Падает экспорт");
+        press(&mut wizard, KeyCode::Enter);
+        type_text(&mut wizard, "Шаги");
+        press(&mut wizard, KeyCode::Enter);
+        type_text(&mut wizard, "1. Экспорт");
+        press(&mut wizard, KeyCode::Tab);
+        assert_eq!(wizard.step, Step::Assignee);
+
+        press(&mut wizard, KeyCode::Down);
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.step, Step::Followers);
+
+        type_text(&mut wizard, "ивп");
+        press(&mut wizard, KeyCode::Tab);
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.step, Step::Preview);
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardEvent::Create);
+
+        let request = wizard.request().unwrap();
+        assert_eq!(request.issue_type.as_deref(), Some("bug"));
+        assert_eq!(request.summary, "Падает экспорт");
+        assert_eq!(request.description.as_deref(), Some("Шаги\n1. Экспорт"));
+        assert_eq!(
+            request.assignee.map(|login| login.to_string()).as_deref(),
+            Some("ann")
+        );
+        assert_eq!(request.tags, ["from-tui"]);
+        let followers: Vec<String> = request.followers.iter().map(|f| f.to_string()).collect();
+        assert_eq!(followers, ["ivan.petrov"]);
+    }
+
+    #[test]
+    fn test_assignee_choices_list_lead_once_then_directory() {
+        let mut wizard = Wizard::new();
+        wizard.set_queue(queue(), QueuePreset::default());
+        wizard.go(Step::Assignee);
+
+        let labels: Vec<String> = wizard
+            .assignee_choices(&directory())
+            .into_iter()
+            .map(|choice| choice.label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Без исполнителя",
+                "Анна (ann), владелец очереди",
+                "Иван Петров (ivan.petrov)",
+                "Ольга Смирнова (olga)"
+            ]
+        );
+
+        type_text(&mut wizard, "new.hire");
+        let choices = wizard.choices(&Sources {
+            queues: &[],
+            directory: &directory(),
+        });
+        assert_eq!(choices[0].label, "Логин new.hire");
+    }
+
+    #[test]
+    fn test_wizard_validates_type_against_queue() {
+        let mut wizard = Wizard::new();
+        // Пресет с типом, которого нет в очереди, не выбирается по умолчанию
+        let preset = QueuePreset {
+            issue_type: Some("epic".to_string()),
+            ..QueuePreset::default()
+        };
+        wizard.set_queue(queue(), preset);
+        assert_eq!(
+            wizard.picker.lines(&wizard.type_choices())[2],
+            "> Задача (task)"
+        );
+
+        wizard.issue_type = Some(PickerItem::new(Some("epic".to_string()), "Эпик"));
+        wizard.summary = "Релиз".to_string();
+        assert_eq!(
+            wizard.request().unwrap_err(),
+            "Тип epic недоступен в очереди TREK"
+        );
+    }
+
+    #[test]
+    fn test_long_description_offers_llm_titles() {
+        let mut wizard = Wizard::new();
+        wizard.set_queue(queue(), QueuePreset::default());
+        wizard.summary = "Ошибка".to_string();
+        wizard.go(Step::Description);
+        wizard.description = "Шаги воспроизведения. ".repeat(30);
+        assert_eq!(press(&mut wizard, KeyCode::Tab), WizardEvent::SuggestTitles);
+
+        let description = wizard.description.clone();
+        wizard.set_titles(
+            description,
+            vec!["Падает экспорт".to_string(), "Экспорт CSV".to_string()],
+        );
+        assert_eq!(wizard.step, Step::Title);
+        assert_eq!(
+            wizard.picker.lines(&wizard.title_choices())[4],
+            "  Оставить: Ошибка"
+        );
+        press(&mut wizard, KeyCode::Down);
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.summary, "Экспорт CSV");
+        assert_eq!(wizard.step, Step::Assignee);
+
+        // Для того же описания варианты не запрашиваются повторно
+        press(&mut wizard, KeyCode::Esc);
+        assert_eq!(press(&mut wizard, KeyCode::Tab), WizardEvent::None);
+        assert_eq!(wizard.step, Step::Title);
+        press(&mut wizard, KeyCode::Up);
+        press(&mut wizard, KeyCode::Down);
+        press(&mut wizard, KeyCode::Down);
+        press(&mut wizard, KeyCode::Enter);
+        assert_eq!(wizard.summary, "Экспорт CSV");
+    }
+
+    #[test]
+    fn test_wizard_escape_goes_back_then_closes() {
+        let mut wizard = Wizard::new();
+        wizard.set_queue(queue(), QueuePreset::default());
+        assert_eq!(press(&mut wizard, KeyCode::Esc), WizardEvent::None);
+        assert_eq!(wizard.step, Step::Queue);
+        assert_eq!(press(&mut wizard, KeyCode::Esc), WizardEvent::Close);
+    }
+}
@@ -0,0 +1,230 @@
+use std::{future::Future, pin::Pin};
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::events::TrackerEvent;
+
+use super::{Screen, ScreenEvent, ScreenId, ScreenMeta};
+use crate::{
+    inbox::{self, Inbox},
+    queues::LiveRules,
+    reminders::{self, Reminders},
+    settings,
+    tui::{bus::EventBus, setup::Service, toast::ToastLevel},
+};
+
+/// На сколько откладывается напоминание клавишей `s`
+const SNOOZE_HOURS: i64 = 24;
+
+/// Экран входящих: упоминания, назначения и смены статусов по моим задачам
+pub struct InboxScreen {
+    inbox: Inbox,
+    selected: usize,
+    status: Option<String>,
+    /// Правила уведомлений по очередям, которые следят за конфигурацией
+    rules: LiveRules,
+    tracker_events: UnboundedSender<TrackerEvent>,
+    bus: EventBus,
+}
+
+impl InboxScreen {
+    pub fn new(tracker_events: UnboundedSender<TrackerEvent>, bus: EventBus) -> Self {
+        Self {
+            inbox: Inbox::load(),
+            selected: 0,
+            status: None,
+            rules: LiveRules::default(),
+            tracker_events,
+            bus,
+        }
+    }
+
+    pub fn set_notify_rules(&mut self, rules: LiveRules) {
+        self.rules = rules;
+    }
+
+    /// Перечитывает входящие, например после доставки напоминаний
+    pub fn reload(&mut self) {
+        self.inbox = Inbox::load();
+        self.selected = self
+            .selected
+            .min(self.inbox.notifications.len().saturating_sub(1));
+    }
+
+    fn save(&mut self) {
+        if let Err(err) = self.inbox.save() {
+            self.status = Some(format!("Не удалось сохранить входящие: {err:#}"));
+        }
+    }
+
+    /// Открывает задачу выбранного уведомления и отмечает его прочитанным
+    fn open_selected(&mut self) -> ScreenEvent {
+        let Some(issue_key) = self
+            .inbox
+            .notifications
+            .get(self.selected)
+            .map(|n| n.issue_key.clone())
+        else {
+            return ScreenEvent::None;
+        };
+        self.inbox.mark_read(self.selected);
+        self.save();
+        ScreenEvent::Navigate(ScreenId::Tracker, issue_key)
+    }
+
+    /// Откладывает или выполняет напоминание выбранного уведомления
+    fn update_reminder(&mut self, snooze: bool) {
+        let Some(id) = self
+            .inbox
+            .notifications
+            .get(self.selected)
+            .and_then(reminders::reminder_id)
+        else {
+            self.status = Some("Выбранное уведомление — не напоминание".to_string());
+            return;
+        };
+        let mut reminders = Reminders::load();
+        let result = if snooze {
+            reminders
+                .snooze(
+                    id,
+                    Utc::now().fixed_offset() + Duration::hours(SNOOZE_HOURS),
+                )
+                .map(|r| format!("Отложено: {}", r.line()))
+        } else {
+            reminders
+                .complete(id)
+                .map(|r| format!("Выполнено: {}", r.line()))
+        };
+        self.status = Some(
+            match result.and_then(|message| reminders.save().map(|_| message)) {
+                Ok(message) => message,
+                Err(err) => format!("{err:#}"),
+            },
+        );
+        self.inbox.mark_read(self.selected);
+        self.save();
+    }
+
+    async fn refresh(&mut self) -> Result<String> {
+        let client = settings::tracker_client()?.with_event_sender(self.tracker_events.clone());
+        reminders::deliver_due(&mut self.inbox)?;
+        let added = inbox::poll(&client, &mut self.inbox, &self.rules.current()).await?;
+        self.inbox.save()?;
+        if added > 0 {
+            self.bus
+                .toast(ToastLevel::Info, format!("Новых уведомлений: {added}"));
+        }
+        Ok(format!(
+            "Входящие обновлены, непрочитанных: {}",
+            self.inbox.unread_count()
+        ))
+    }
+}
+
+impl Screen for InboxScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Inbox,
+            title: "Inbox",
+            label: "Входящие",
+            shortcut: '7',
+            requires: &[Service::Tracker],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        "Входящие: ↑/↓ выбор, Enter — открыть задачу, m — прочитано, a — прочитать все, \
+         s — отложить напоминание на день, d — выполнить, r — обновить"
+    }
+
+    fn input_text(&self) -> &str {
+        ""
+    }
+
+    fn output_text(&self) -> String {
+        let mut text = match self.inbox.notifications.get(self.selected) {
+            Some(n) => format!(
+                "{} {}\n\n{}\n\n{}{}",
+                n.issue_key,
+                n.issue_summary,
+                n.text,
+                n.at,
+                n.author
+                    .as_deref()
+                    .map(|author| format!(", {author}"))
+                    .unwrap_or_default()
+            ),
+            None => "Уведомлений нет. r — запросить Трекер".to_string(),
+        };
+        if let Some(status) = &self.status {
+            text.push_str(&format!("\n\n{status}"));
+        }
+        text
+    }
+
+    fn list_text(&self) -> Option<String> {
+        let header = format!("Непрочитанных: {}", self.inbox.unread_count());
+        let lines = self
+            .inbox
+            .notifications
+            .iter()
+            .enumerate()
+            .map(|(index, n)| {
+                let cursor = if index == self.selected { ">" } else { " " };
+                format!("{cursor}{}", n.line())
+            });
+        Some(
+            std::iter::once(header)
+                .chain(lines)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.inbox.notifications.len() => {
+                self.selected += 1
+            }
+            KeyCode::Enter => return self.open_selected(),
+            KeyCode::Char('m') => {
+                self.inbox.mark_read(self.selected);
+                self.save();
+            }
+            KeyCode::Char('a') => {
+                self.inbox.mark_all_read();
+                self.save();
+            }
+            KeyCode::Char('s') => self.update_reminder(true),
+            KeyCode::Char('d') => self.update_reminder(false),
+            KeyCode::Char('r') => return ScreenEvent::Submit(String::new()),
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.status = Some(text);
+    }
+
+    fn command_preview(&self, _input: &str) -> String {
+        "> inbox".to_string()
+    }
+
+    fn execute<'a>(&'a mut self, _input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            match self.refresh().await {
+                Ok(output) => output,
+                Err(err) => {
+                    let message = format!("Ошибка входящих: {err}");
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
+            }
+        })
+    }
+}
@@ -1,38 +1,227 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+};
 
-use crossterm::event::KeyCode;
-use llm_lib::{LlmClient, LlmClientTrait, LlmConfig};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use llm_lib::{events::LlmEvent, Branches, LlmClient, LlmConfig, Message, Role};
+use tokio::sync::mpsc::UnboundedSender;
 
-use super::{Screen, ScreenEvent};
+use crate::drafts::{self, Drafts};
+use crate::llm::{ChatCommand, CHAT_COMMANDS_HELP, DEFAULT_MODEL};
+use crate::personas::{self, Persona};
+use crate::prompts::{self, PromptPreset, RenderedPrompt};
+
+use super::{scrollback, Popup, Screen, ScreenEvent, ScreenId, ScreenMeta, ScreenSnapshot};
+use crate::tui::{
+    issue_ref::{self, IssueCompletion, RefStyle},
+    setup::Service,
+};
+
+/// Префикс записи с промптом пользователя в истории вывода
+const PROMPT_PREFIX: &str = "> llm ask ";
 
 pub struct LlmScreen {
     input: String,
     output: Vec<String>,
+    /// Прикреплённый контекст с другого экрана: подпись и текст для системного промпта
+    context: Option<(String, String)>,
+    /// Библиотека промптов и выбранный клавишей Tab пресет
+    prompts: BTreeMap<String, PromptPreset>,
+    preset: Option<String>,
+    /// Персоны и выбранная клавишей Ctrl+P персона
+    personas: BTreeMap<String, Persona>,
+    persona: Option<String>,
+    /// Черновик ввода на диске; None — черновики не сохраняются
+    drafts: Option<Drafts>,
+    /// Ветки диалога: запросы уходят вместе с историей текущей ветки
+    chat: Branches,
+    /// Файл стенограммы диалога; None — диалог не сохраняется
+    chat_path: Option<PathBuf>,
+    /// Ответы старой и новой ветки после `/fork` для сравнения бок о бок
+    comparison: Option<Vec<(String, String)>>,
+    /// Список задач после `#` в запросе
+    completion: Option<IssueCompletion>,
+    events: UnboundedSender<LlmEvent>,
 }
 
 impl LlmScreen {
-    pub fn new() -> Self {
+    pub fn new(events: UnboundedSender<LlmEvent>) -> Self {
         Self {
             input: String::new(),
             output: vec!["Режим LLM активирован".to_string()],
+            context: None,
+            prompts: prompts::library(&HashMap::new()),
+            preset: None,
+            personas: personas::library(&HashMap::new()),
+            persona: None,
+            drafts: None,
+            chat: Branches::default(),
+            chat_path: None,
+            comparison: None,
+            completion: None,
+            events,
+        }
+    }
+
+    /// Заменяет библиотеку промптов (встроенные пресеты и пресеты из конфигурации)
+    pub fn set_prompts(&mut self, prompts: BTreeMap<String, PromptPreset>) {
+        self.preset = self.preset.take().filter(|name| prompts.contains_key(name));
+        self.prompts = prompts;
+    }
+
+    /// Заменяет персоны (встроенные и из конфигурации)
+    pub fn set_personas(&mut self, personas: BTreeMap<String, Persona>) {
+        self.persona = self
+            .persona
+            .take()
+            .filter(|name| personas.contains_key(name));
+        self.personas = personas;
+    }
+
+    /// Включает автосохранение ввода и восстанавливает черновик
+    ///
+    /// Черновик пишется при каждом изменении ввода, поэтому он свежее ввода из
+    /// сохранённой сессии, которая после падения остаётся от прошлого выхода.
+    pub fn set_drafts(&mut self, drafts: Drafts) {
+        if let Some(draft) = drafts.load(drafts::LLM_CHAT) {
+            if draft != self.input {
+                self.input = draft;
+                self.push_output("📝 Восстановлен черновик запроса".to_string());
+            }
+        }
+        self.drafts = Some(drafts);
+    }
+
+    /// Включает сохранение диалога с ветками в `path` и восстанавливает его
+    pub fn set_chat_path(&mut self, path: PathBuf) {
+        match std::fs::read_to_string(&path) {
+            Ok(markdown) => {
+                self.chat = Branches::from_markdown(&markdown);
+                if self.chat.branches().len() > 1 {
+                    self.push_output(format!(
+                        "Восстановлен диалог: ветка {} из {}",
+                        self.chat.current_index() + 1,
+                        self.chat.branches().len()
+                    ));
+                }
+            }
+            Err(err) => tracing::debug!(error = %err, "Сохранённого диалога нет"),
+        }
+        self.chat_path = Some(path);
+    }
+
+    fn save_chat(&self) {
+        let Some(path) = &self.chat_path else {
+            return;
+        };
+        let result = match path.parent() {
+            Some(parent) => std::fs::create_dir_all(parent),
+            None => Ok(()),
         }
+        .and_then(|()| std::fs::write(path, self.chat.to_markdown()));
+        if let Err(err) = result {
+            tracing::warn!(error = %err, "Не удалось сохранить диалог");
+        }
+    }
+
+    /// Ответ на запрос `number` текущей ветки, если он уже получен
+    fn answer_to(&self, number: usize) -> Option<String> {
+        let messages = self.chat.current().messages();
+        let position = self.chat.current().prompt_position(number)?;
+        messages
+            .get(position + 1)
+            .filter(|message| message.role == Role::Assistant)
+            .map(|message| message.content.clone())
+    }
+
+    fn save_draft(&self) {
+        if let Some(drafts) = &self.drafts {
+            if let Err(err) = drafts.save(drafts::LLM_CHAT, &self.input) {
+                tracing::warn!(error = %err, "Не удалось сохранить черновик запроса");
+            }
+        }
+    }
+
+    /// Следующий пресет по кругу; после последнего — обычный запрос без пресета
+    fn cycle_preset(&mut self) {
+        self.preset = next_name(self.prompts.keys(), self.preset.as_ref());
+    }
+
+    /// Следующая персона по кругу; после последней — запрос без персоны
+    fn cycle_persona(&mut self) {
+        self.persona = next_name(self.personas.keys(), self.persona.as_ref());
+    }
+
+    /// Промпт и модель для запроса: через выбранный пресет или введённый текст как есть,
+    /// дополненные выбранной персоной
+    fn build_prompt(&self, input: &str) -> anyhow::Result<(RenderedPrompt, String)> {
+        let preset = self.preset.as_ref().and_then(|name| self.prompts.get(name));
+        self.render_prompt(preset, input)
+    }
+
+    fn render_prompt(
+        &self,
+        preset: Option<&PromptPreset>,
+        input: &str,
+    ) -> anyhow::Result<(RenderedPrompt, String)> {
+        let persona = self
+            .persona
+            .as_ref()
+            .and_then(|name| self.personas.get(name));
+        let mut rendered = match preset {
+            Some(preset) => preset.render(&prompts::vars_from_line(preset, input)?)?,
+            None => RenderedPrompt {
+                system: None,
+                prompt: input.to_string(),
+                temperature: None,
+            },
+        };
+        if let Some(persona) = persona {
+            persona.apply(&mut rendered);
+        }
+        let model = preset
+            .and_then(|preset| preset.model.clone())
+            .or_else(|| persona.and_then(|persona| persona.model.clone()))
+            .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        Ok((rendered, model))
+    }
+
+    /// Прикрепляет контекст ко всем следующим запросам до сброса через Ctrl+X
+    pub fn attach_context(&mut self, label: String, text: String) {
+        self.push_output(format!("📎 Контекст: {label}"));
+        self.context = Some((label, text));
     }
 
     fn limit_output(&mut self) {
-        if self.output.len() > 200 {
-            let drain_count = self.output.len().saturating_sub(200);
+        if self.output.len() > scrollback() {
+            let drain_count = self.output.len().saturating_sub(scrollback());
             self.output.drain(0..drain_count);
         }
     }
 }
 
 impl Screen for LlmScreen {
-    fn title(&self) -> &'static str {
-        "LLM"
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Llm,
+            title: "LLM",
+            label: "LLM",
+            shortcut: '2',
+            requires: &[Service::Llm],
+        }
     }
 
     fn input_title(&self) -> &'static str {
-        "LLM: введите промпт и нажмите Enter"
+        if self.preset.is_some() {
+            "LLM: текст для пресета + Enter, Tab сменить пресет"
+        } else if self.context.is_some() {
+            "LLM: промпт + Enter, Ctrl+X убрать контекст"
+        } else {
+            "LLM: промпт + Enter, /fork N — ветка с N-го запроса"
+        }
     }
 
     fn input_text(&self) -> &str {
@@ -43,14 +232,46 @@ impl Screen for LlmScreen {
         self.output.join("\n\n")
     }
 
-    fn handle_key(&mut self, key: KeyCode) -> ScreenEvent {
-        match key {
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        if let Some(completion) = self.completion.as_mut() {
+            if !completion.handle_key(key, &mut self.input, RefStyle::WithSummary) {
+                self.completion = None;
+            }
+            self.save_draft();
+            return ScreenEvent::None;
+        }
+        match key.code {
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some((label, _)) = self.context.take() {
+                    self.push_output(format!("Контекст убран: {label}"));
+                }
+                ScreenEvent::None
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cycle_persona();
+                ScreenEvent::None
+            }
+            KeyCode::Tab => {
+                self.cycle_preset();
+                ScreenEvent::None
+            }
             KeyCode::Backspace => {
                 self.input.pop();
+                self.save_draft();
+                ScreenEvent::None
+            }
+            KeyCode::Char(issue_ref::TRIGGER) => {
+                self.input.push(issue_ref::TRIGGER);
+                self.completion = Some(IssueCompletion::new(
+                    &self.input,
+                    issue_ref::issue_items(&[]),
+                ));
+                self.save_draft();
                 ScreenEvent::None
             }
             KeyCode::Char(c) => {
                 self.input.push(c);
+                self.save_draft();
                 ScreenEvent::None
             }
             KeyCode::Enter => {
@@ -59,6 +280,7 @@ impl Screen for LlmScreen {
                     return ScreenEvent::None;
                 }
                 self.input.clear();
+                self.save_draft();
                 ScreenEvent::Submit(input)
             }
             _ => ScreenEvent::None,
@@ -70,23 +292,327 @@ impl Screen for LlmScreen {
         self.limit_output();
     }
 
+    fn snapshot(&self) -> ScreenSnapshot {
+        ScreenSnapshot {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            selected_issue: None,
+        }
+    }
+
+    fn restore(&mut self, snapshot: ScreenSnapshot) {
+        self.input = snapshot.input;
+        if !snapshot.output.is_empty() {
+            self.output = snapshot.output;
+            self.limit_output();
+        }
+    }
+
+    fn renders_markdown(&self) -> bool {
+        true
+    }
+
+    fn list_text(&self) -> Option<String> {
+        let prompts: Vec<String> = self
+            .output
+            .iter()
+            .filter_map(|entry| entry.strip_prefix(PROMPT_PREFIX))
+            .enumerate()
+            .map(|(index, prompt)| format!("{}. {prompt}", index + 1))
+            .collect();
+        let list = if prompts.is_empty() {
+            "Запросов пока нет".to_string()
+        } else {
+            prompts.join("\n")
+        };
+        let list = match &self.context {
+            Some((label, _)) => format!("📎 {label}\n\n{list}"),
+            None => list,
+        };
+        let list = match self.chat.branches().len() {
+            1 => list,
+            count => format!(
+                "🌿 Ветка {} из {count}\n\n{list}",
+                self.chat.current_index() + 1
+            ),
+        };
+        let presets: Vec<String> = self
+            .prompts
+            .iter()
+            .map(|(name, preset)| {
+                let marker = if self.preset.as_ref() == Some(name) {
+                    "▶"
+                } else {
+                    " "
+                };
+                let variables = preset.variables().join(", ");
+                format!("{marker} {name} ({variables})")
+            })
+            .collect();
+        let personas: Vec<String> = self
+            .personas
+            .keys()
+            .map(|name| {
+                let marker = if self.persona.as_ref() == Some(name) {
+                    "▶"
+                } else {
+                    " "
+                };
+                format!("{marker} {name}")
+            })
+            .collect();
+        Some(format!(
+            "{list}\n\nПресеты (Tab):\n{}\n\nПерсоны (Ctrl+P):\n{}",
+            presets.join("\n"),
+            personas.join("\n")
+        ))
+    }
+
     fn command_preview(&self, input: &str) -> String {
-        format!("> llm ask {input}")
+        let persona = self
+            .persona
+            .as_ref()
+            .map(|persona| format!("@{persona} "))
+            .unwrap_or_default();
+        match &self.preset {
+            Some(preset) => format!("{PROMPT_PREFIX}{persona}[{preset}] {input}"),
+            None => format!("{PROMPT_PREFIX}{persona}{input}"),
+        }
+    }
+
+    fn popup(&self) -> Option<Popup> {
+        let completion = self.completion.as_ref()?;
+        Some(Popup {
+            title: "Задача: ↑/↓, Enter — вставить, Esc — закрыть".to_string(),
+            body: completion.lines().join("\n"),
+        })
+    }
+
+    fn columns(&self) -> Option<Vec<(String, String)>> {
+        self.comparison.clone()
     }
 
     fn execute<'a>(&'a mut self, input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
         Box::pin(async move {
-            match ask_llm(&input).await {
-                Ok(output) => output,
-                Err(err) => format!("Ошибка LLM: {err}"),
+            self.comparison = None;
+            let (prompt, forked, original) = match ChatCommand::parse(&input) {
+                None => (input, false, None),
+                Some(Err(err)) => return format!("{err}\n\n{CHAT_COMMANDS_HELP}"),
+                Some(Ok(command)) => {
+                    let original = match &command {
+                        ChatCommand::Fork { number, .. } => self
+                            .answer_to(*number)
+                            .map(|answer| (self.chat.current_index(), answer)),
+                        _ => None,
+                    };
+                    match command.apply(&mut self.chat) {
+                        Ok((message, Some(prompt))) => {
+                            self.push_output(message);
+                            (prompt, true, original)
+                        }
+                        Ok((message, None)) => {
+                            self.save_chat();
+                            return message;
+                        }
+                        Err(err) => return format!("Ошибка: {err}"),
+                    }
+                }
+            };
+            // Запрос новой ветки идёт без пресета: исходный запрос уже подставлен в шаблон
+            let rendered = if forked {
+                self.render_prompt(None, &prompt)
+            } else {
+                self.build_prompt(&prompt)
+            };
+            let context = self.context.as_ref().map(|(_, text)| text.clone());
+            let history = self.chat.current().messages().to_vec();
+            let result = match rendered {
+                Ok((prompt, model)) => {
+                    let text = prompt.prompt.clone();
+                    ask_llm(prompt, &model, context, &history, self.events.clone())
+                        .await
+                        .map(|answer| (text, answer))
+                }
+                Err(err) => Err(err),
+            };
+            let (text, answer) = match result {
+                Ok(result) => result,
+                Err(err) => return format!("Ошибка LLM: {err}"),
+            };
+            let branch = self.chat.current_mut();
+            branch.push(Message::user(text));
+            branch.push(Message::assistant(answer.clone()));
+            self.save_chat();
+            if let Some((parent, original)) = original {
+                self.comparison = Some(vec![
+                    (format!("Ветка {}", parent + 1), original),
+                    (
+                        format!("Ветка {}", self.chat.current_index() + 1),
+                        answer.clone(),
+                    ),
+                ]);
             }
+            answer
         })
     }
 }
 
-async fn ask_llm(prompt: &str) -> anyhow::Result<String> {
-    let config = LlmConfig::new("anthropic/claude-3.5-sonnet".to_string())?;
-    let client = LlmClient::new(config)?;
-    let response = client.complete(prompt.to_string()).await?;
-    Ok(response)
+/// Имя после `current` по кругу; после последнего — `None`
+fn next_name<'a>(
+    mut names: impl Iterator<Item = &'a String>,
+    current: Option<&String>,
+) -> Option<String> {
+    match current {
+        None => names.next().cloned(),
+        Some(current) => names.skip_while(|name| *name != current).nth(1).cloned(),
+    }
+}
+
+async fn ask_llm(
+    mut prompt: RenderedPrompt,
+    model: &str,
+    context: Option<String>,
+    history: &[Message],
+    events: UnboundedSender<LlmEvent>,
+) -> anyhow::Result<String> {
+    let config = LlmConfig::new(model)?;
+    let client = LlmClient::new(config)?.with_event_sender(events);
+    if let Some(context) = context {
+        let context = format!(
+            "Ниже контекст, который пользователь открыл в трекере. \
+             Отвечай на вопросы с опорой на него.\n\n{context}"
+        );
+        // Системный промпт пресета идёт первым, контекст — после него
+        prompt.system = Some(match prompt.system.take() {
+            Some(system) => format!("{system}\n\n{context}"),
+            None => context,
+        });
+    }
+    prompt.send_with_history(&client, history).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen() -> LlmScreen {
+        let (events, _) = tokio::sync::mpsc::unbounded_channel();
+        LlmScreen::new(events)
+    }
+
+    #[test]
+    fn test_tab_cycles_presets_and_back_to_plain_prompt() {
+        let mut screen = screen();
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            screen.handle_key(tab);
+            seen.push(screen.preset.clone());
+        }
+        assert_eq!(
+            seen,
+            [
+                Some("explain".to_string()),
+                Some("review".to_string()),
+                Some("translate-ru".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_prompt_with_preset() {
+        let mut screen = screen();
+        let (plain, model) = screen.build_prompt("привет").unwrap();
+        assert_eq!(plain.prompt, "привет");
+        assert!(plain.system.is_none());
+        assert_eq!(model, DEFAULT_MODEL);
+
+        screen.preset = Some("translate-ru".to_string());
+        let (rendered, _) = screen.build_prompt("Good morning").unwrap();
+        assert!(rendered.prompt.ends_with("Good morning"));
+        assert!(rendered.system.is_some());
+        assert!(screen
+            .command_preview("Good morning")
+            .contains("[translate-ru]"));
+
+        screen.set_prompts(BTreeMap::new());
+        assert!(screen.preset.is_none());
+    }
+
+    #[test]
+    fn test_ctrl_p_selects_persona_for_prompt() {
+        let mut screen = screen();
+        let mut personas = personas::library(&HashMap::new());
+        personas.insert(
+            "reviewer".to_string(),
+            Persona {
+                system: "Ты придирчивый ревьюер".to_string(),
+                model: Some("openai/gpt-4o".to_string()),
+                temperature: None,
+            },
+        );
+        screen.set_personas(personas);
+
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        screen.handle_key(ctrl_p);
+        assert_eq!(screen.persona.as_deref(), Some(personas::PROJECT_MANAGER));
+        let (prompt, model) = screen.build_prompt("что делать сегодня?").unwrap();
+        assert!(prompt.system.unwrap().contains("руководитель проектов"));
+        assert_eq!(prompt.temperature, Some(0.3));
+        assert_eq!(model, DEFAULT_MODEL);
+        assert!(screen.input.is_empty());
+
+        screen.handle_key(ctrl_p);
+        screen.preset = Some("translate-ru".to_string());
+        let (prompt, model) = screen.build_prompt("Good morning").unwrap();
+        assert!(prompt
+            .system
+            .unwrap()
+            .starts_with("Ты придирчивый ревьюер\n\n"));
+        assert_eq!(model, "openai/gpt-4o");
+        assert!(screen
+            .command_preview("Good morning")
+            .contains("@reviewer [translate-ru]"));
+
+        screen.handle_key(ctrl_p);
+        assert!(screen.persona.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_branch_commands_keep_chat_and_save_it() {
+        let dir = std::env::temp_dir().join(format!("you-llm-chat-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("llm-chat.md");
+        std::fs::write(
+            &path,
+            "# Conversation\n\n## User\n\nКак назвать модуль?\n\n## Assistant\n\nsync\n",
+        )
+        .unwrap();
+        let mut screen = screen();
+        screen.set_chat_path(path.clone());
+        assert_eq!(screen.answer_to(1).as_deref(), Some("sync"));
+        assert!(screen.answer_to(2).is_none());
+
+        let output = screen.execute("/fork 5".to_string()).await;
+        assert!(output.starts_with("Ошибка"));
+        assert_eq!(screen.chat.branches().len(), 1);
+
+        let output = screen.execute("/unknown".to_string()).await;
+        assert!(output.ends_with(CHAT_COMMANDS_HELP));
+
+        screen.chat.fork_at_prompt(1).unwrap();
+        let output = screen.execute("/branches".to_string()).await;
+        assert!(output.contains("* 2: от ветки 1 после сообщения 0"));
+        assert!(screen.list_text().unwrap().starts_with("🌿 Ветка 2 из 2"));
+
+        let output = screen.execute("/branch 1".to_string()).await;
+        assert!(output.ends_with("sync"));
+        assert!(screen.columns().is_none());
+        let saved = Branches::from_markdown(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(saved.branches().len(), 2);
+        assert_eq!(saved.branches()[1].parent, Some(0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,28 +1,225 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+use tracker_lib::models::Issue;
 
+use super::{chart::ChartData, setup::Service};
+
+pub mod calendar;
+mod comments;
+pub mod create;
+pub mod inbox;
 pub mod llm;
+pub mod sprint;
+pub mod timesheet;
 pub mod tracker;
+pub mod triage;
+
+/// Сколько записей вывода хранит каждый экран, если `tui.scrollback` не задан
+pub const DEFAULT_SCROLLBACK: usize = 1000;
+
+static SCROLLBACK: AtomicUsize = AtomicUsize::new(DEFAULT_SCROLLBACK);
+
+/// Сколько записей вывода хранит каждый экран
+pub fn scrollback() -> usize {
+    SCROLLBACK.load(Ordering::Relaxed)
+}
 
-#[derive(Clone, Copy)]
+/// Задаёт размер вывода экранов из конфигурации; хотя бы одна запись остаётся всегда
+pub fn set_scrollback(limit: usize) {
+    SCROLLBACK.store(limit.max(1), Ordering::Relaxed);
+}
+
+pub use tracker_lib::models::TRACKER_DATE_FORMAT;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ScreenId {
     Tracker,
     Llm,
+    Calendar,
+    Triage,
+    Timesheet,
+    Sprint,
+    Inbox,
+    Create,
+}
+
+/// Описание экрана для меню главного экрана и проверки настроек
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenMeta {
+    pub id: ScreenId,
+    /// Название режима в заголовке
+    pub title: &'static str,
+    /// Название в меню главного экрана
+    pub label: &'static str,
+    /// Клавиша перехода с главного экрана
+    pub shortcut: char,
+    /// Сервисы, без которых экран не может работать
+    pub requires: &'static [Service],
+}
+
+/// Зарегистрированные экраны в порядке меню главного экрана
+#[derive(Default)]
+pub struct ScreenRegistry {
+    screens: Vec<Box<dyn Screen>>,
+}
+
+impl ScreenRegistry {
+    pub fn register(&mut self, screen: impl Screen) {
+        self.screens.push(Box::new(screen));
+    }
+
+    pub fn get(&self, id: ScreenId) -> Option<&dyn Screen> {
+        self.screens
+            .iter()
+            .find(|screen| screen.meta().id == id)
+            .map(|screen| screen.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: ScreenId) -> Option<&mut dyn Screen> {
+        let screen = self
+            .screens
+            .iter_mut()
+            .find(|screen| screen.meta().id == id)?;
+        Some(screen.as_mut())
+    }
+
+    pub fn metas(&self) -> Vec<ScreenMeta> {
+        self.screens.iter().map(|screen| screen.meta()).collect()
+    }
+
+    pub fn ids(&self) -> Vec<ScreenId> {
+        self.screens.iter().map(|screen| screen.meta().id).collect()
+    }
+
+    /// Экран конкретного типа для действий, которых нет в общем трейте
+    pub fn find<T: Screen>(&self) -> Option<&T> {
+        self.screens
+            .iter()
+            .find_map(|screen| (screen.as_ref() as &dyn Any).downcast_ref::<T>())
+    }
+
+    pub fn find_mut<T: Screen>(&mut self) -> Option<&mut T> {
+        self.screens
+            .iter_mut()
+            .find_map(|screen| (screen.as_mut() as &mut dyn Any).downcast_mut::<T>())
+    }
 }
 
 pub enum ScreenEvent {
     None,
     Submit(String),
+    /// Перейти на другой экран и отправить туда ввод
+    Navigate(ScreenId, String),
+    /// Запустить или остановить фокус-таймер по задаче
+    ToggleFocus(String),
 }
 
-pub trait Screen {
-    fn title(&self) -> &'static str;
+/// Всплывающее окно поверх экрана; пока оно открыто, экран получает все клавиши
+pub struct Popup {
+    pub title: String,
+    pub body: String,
+}
+
+/// Сохраняемое между запусками состояние экрана
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenSnapshot {
+    pub input: String,
+    pub output: Vec<String>,
+    pub selected_issue: Option<Issue>,
+}
+
+pub trait Screen: Any {
+    fn meta(&self) -> ScreenMeta;
     fn input_title(&self) -> &'static str;
     fn input_text(&self) -> &str;
     fn output_text(&self) -> String;
-    fn handle_key(&mut self, key: KeyCode) -> ScreenEvent;
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent;
     fn push_output(&mut self, text: String);
     fn command_preview(&self, input: &str) -> String;
     fn execute<'a>(&'a mut self, input: String) -> Pin<Box<dyn Future<Output = String> + 'a>>;
+
+    fn popup(&self) -> Option<Popup> {
+        None
+    }
+
+    fn snapshot(&self) -> ScreenSnapshot {
+        ScreenSnapshot::default()
+    }
+
+    fn restore(&mut self, _snapshot: ScreenSnapshot) {}
+
+    /// Список для левой панели в раздельной раскладке; None — экран занимает всю область
+    fn list_text(&self) -> Option<String> {
+        None
+    }
+
+    /// Рендерить ли вывод экрана как Markdown
+    fn renders_markdown(&self) -> bool {
+        false
+    }
+
+    /// График над панелью вывода
+    fn chart(&self) -> Option<ChartData> {
+        None
+    }
+
+    /// Колонки с заголовками, которые показываются бок о бок вместо вывода
+    fn columns(&self) -> Option<Vec<(String, String)>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::{calendar::CalendarScreen, triage::TriageScreen, *};
+    use crate::tui::bus::EventBus;
+
+    fn registry() -> ScreenRegistry {
+        let (tracker_sender, _tracker_events) = mpsc::unbounded_channel();
+        let (llm_sender, _llm_events) = mpsc::unbounded_channel();
+        let (bus, _events) = EventBus::new();
+        let mut registry = ScreenRegistry::default();
+        registry.register(CalendarScreen::new());
+        registry.register(TriageScreen::new(tracker_sender, llm_sender, bus));
+        registry
+    }
+
+    #[test]
+    fn test_registry_looks_up_screens_by_id_and_type() {
+        let mut registry = registry();
+
+        assert_eq!(registry.ids(), vec![ScreenId::Calendar, ScreenId::Triage]);
+        assert_eq!(
+            registry.get(ScreenId::Triage).unwrap().meta().title,
+            "Triage"
+        );
+        assert!(registry.get(ScreenId::Tracker).is_none());
+        assert!(registry.find_mut::<CalendarScreen>().is_some());
+    }
+
+    #[test]
+    fn test_required_services() {
+        let registry = registry();
+
+        assert!(registry
+            .get(ScreenId::Calendar)
+            .unwrap()
+            .meta()
+            .requires
+            .is_empty());
+        assert_eq!(
+            registry.get(ScreenId::Triage).unwrap().meta().requires,
+            &[Service::Tracker, Service::Llm]
+        );
+    }
 }
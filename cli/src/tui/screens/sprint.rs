@@ -0,0 +1,460 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Days, Local, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::{mpsc::UnboundedSender, Semaphore};
+use tracker_lib::{
+    events::TrackerEvent,
+    models::{ChangelogEntry, Issue, SprintDetails},
+    TrackerClient,
+};
+
+use super::{Screen, ScreenEvent, ScreenId, ScreenMeta, TRACKER_DATE_FORMAT};
+use crate::settings;
+use crate::tui::{
+    bus::EventBus,
+    chart::{ChartData, ChartSeries},
+    setup::Service,
+    toast::ToastLevel,
+};
+
+/// Через сколько дней без изменений незакрытая задача считается застрявшей
+const STALE_DAYS: i64 = 3;
+
+/// Сколько историй задач загружать одновременно
+const CHANGELOG_CONCURRENCY: usize = 4;
+
+/// Статусы, означающие, что работа по задаче не начиналась
+const NOT_STARTED_STATUSES: &[&str] = &["open", "new", "backlog"];
+
+/// Задача спринта с историей резолюции
+struct SprintIssue {
+    key: String,
+    summary: String,
+    points: Option<f64>,
+    assignee: Option<String>,
+    status_key: Option<String>,
+    updated: Option<NaiveDate>,
+    /// Смены резолюции по дням: true — задача решена
+    resolution_changes: Vec<(NaiveDate, bool)>,
+}
+
+impl SprintIssue {
+    fn new(issue: Issue, changelog: &[ChangelogEntry]) -> Self {
+        let mut resolution_changes: Vec<(NaiveDate, bool)> = changelog
+            .iter()
+            .filter_map(|entry| {
+                let date = parse_date(entry.updated_at.as_deref()?)?;
+                let change = entry
+                    .fields
+                    .iter()
+                    .find(|change| change.field.id.as_deref() == Some("resolution"))?;
+                let resolved = change.to.as_ref().is_some_and(|value| !value.is_null());
+                Some((date, resolved))
+            })
+            .collect();
+        resolution_changes.sort_by_key(|(date, _)| *date);
+
+        Self {
+            points: issue.story_points,
+            assignee: issue.assignee.and_then(|user| user.display.or(user.login)),
+            status_key: issue.status.and_then(|status| status.key),
+            updated: issue.updated_at.as_deref().and_then(parse_date),
+            key: issue.key,
+            summary: issue.summary,
+            resolution_changes,
+        }
+    }
+
+    /// Решена ли задача на конец дня; задачи создаются без резолюции
+    fn resolved_on(&self, day: NaiveDate) -> bool {
+        self.resolution_changes
+            .iter()
+            .take_while(|(date, _)| *date <= day)
+            .last()
+            .is_some_and(|(_, resolved)| *resolved)
+    }
+
+    /// Причины, по которым незакрытая задача может не успеть в спринт
+    fn risks(&self, today: NaiveDate) -> Vec<String> {
+        let mut risks = Vec::new();
+        if self.assignee.is_none() {
+            risks.push("без исполнителя".to_string());
+        }
+        if self
+            .status_key
+            .as_deref()
+            .is_some_and(|status| NOT_STARTED_STATUSES.contains(&status))
+        {
+            risks.push("не начата".to_string());
+        }
+        if let Some(updated) = self.updated {
+            let idle = (today - updated).num_days();
+            if idle >= STALE_DAYS {
+                risks.push(format!("нет изменений {idle} дн."));
+            }
+        }
+        risks
+    }
+}
+
+/// Загруженный спринт: даты и задачи
+struct Burndown {
+    name: String,
+    start: NaiveDate,
+    end: NaiveDate,
+    issues: Vec<SprintIssue>,
+}
+
+impl Burndown {
+    fn days(&self) -> u64 {
+        (self.end - self.start).num_days().max(0) as u64
+    }
+
+    /// Объём задачи: story points или единица при подсчёте задач
+    fn weight(issue: &SprintIssue, points: bool) -> f64 {
+        if points {
+            issue.points.unwrap_or(0.0)
+        } else {
+            1.0
+        }
+    }
+
+    fn total(&self, points: bool) -> f64 {
+        self.issues
+            .iter()
+            .map(|issue| Self::weight(issue, points))
+            .sum()
+    }
+
+    fn remaining_on(&self, day: NaiveDate, points: bool) -> f64 {
+        self.issues
+            .iter()
+            .filter(|issue| !issue.resolved_on(day))
+            .map(|issue| Self::weight(issue, points))
+            .sum()
+    }
+
+    /// Остаток по дням от начала спринта до `today` включительно
+    fn actual(&self, today: NaiveDate, points: bool) -> Vec<(f64, f64)> {
+        let last = today.min(self.end);
+        (0..=self.days())
+            .map(|offset| self.start + Days::new(offset))
+            .take_while(|day| *day <= last)
+            .enumerate()
+            .map(|(index, day)| (index as f64, self.remaining_on(day, points)))
+            .collect()
+    }
+
+    fn chart(&self, today: NaiveDate, points: bool) -> ChartData {
+        let total = self.total(points);
+        let days = self.days() as f64;
+        let unit = if points { "sp" } else { "задачи" };
+        ChartData {
+            title: format!("Burndown {} ({unit}), p — переключить", self.name),
+            x_max: days.max(1.0),
+            y_max: total.max(1.0),
+            x_labels: vec![
+                self.start.format("%d.%m").to_string(),
+                self.end.format("%d.%m").to_string(),
+            ],
+            series: vec![
+                ChartSeries {
+                    name: "идеально".to_string(),
+                    points: vec![(0.0, total), (days, 0.0)],
+                    reference: true,
+                },
+                ChartSeries {
+                    name: "осталось".to_string(),
+                    points: self.actual(today, points),
+                    reference: false,
+                },
+            ],
+        }
+    }
+
+    fn summary(&self, today: NaiveDate) -> String {
+        let mut lines = vec![format!(
+            "# {} ({} — {})",
+            self.name,
+            self.start.format("%d.%m.%Y"),
+            self.end.format("%d.%m.%Y")
+        )];
+        lines.push(format!(
+            "Осталось задач: **{:.0}** из {:.0}, story points: **{}** из {}",
+            self.remaining_on(today, false),
+            self.total(false),
+            self.remaining_on(today, true),
+            self.total(true)
+        ));
+        lines.push(String::new());
+        lines.push("## В зоне риска".to_string());
+
+        let mut at_risk: Vec<(&SprintIssue, Vec<String>)> = self
+            .issues
+            .iter()
+            .filter(|issue| !issue.resolved_on(today))
+            .map(|issue| (issue, issue.risks(today)))
+            .filter(|(_, risks)| !risks.is_empty())
+            .collect();
+        at_risk
+            .sort_by(|(a, _), (b, _)| b.points.unwrap_or(0.0).total_cmp(&a.points.unwrap_or(0.0)));
+
+        if at_risk.is_empty() {
+            lines.push("Рисков не найдено".to_string());
+        }
+        for (issue, risks) in at_risk {
+            let points = issue
+                .points
+                .map(|points| format!(" ({points} sp)"))
+                .unwrap_or_default();
+            lines.push(format!(
+                "- `{}`{points} {} — {}",
+                issue.key,
+                issue.summary,
+                risks.join(", ")
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_str(value, TRACKER_DATE_FORMAT)
+        .ok()
+        .map(|date| date.with_timezone(&Local).date_naive())
+}
+
+fn parse_sprint_dates(sprint: &SprintDetails) -> Result<(NaiveDate, NaiveDate)> {
+    let parse = |value: Option<&str>| -> Result<NaiveDate> {
+        let value = value.context("У спринта не заданы даты")?;
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .with_context(|| format!("Некорректная дата спринта: {value}"))
+    };
+    Ok((
+        parse(sprint.start_date.as_deref())?,
+        parse(sprint.end_date.as_deref())?,
+    ))
+}
+
+/// Экран спринта: burndown по истории резолюций и задачи в зоне риска
+pub struct SprintScreen {
+    input: String,
+    burndown: Option<Burndown>,
+    /// Считать остаток в story points, а не в задачах
+    points: bool,
+    status: Option<String>,
+    events: UnboundedSender<TrackerEvent>,
+    bus: EventBus,
+}
+
+impl SprintScreen {
+    pub fn new(events: UnboundedSender<TrackerEvent>, bus: EventBus) -> Self {
+        Self {
+            input: String::new(),
+            burndown: None,
+            points: false,
+            status: Some("Введите идентификатор спринта и нажмите Enter".to_string()),
+            events,
+            bus,
+        }
+    }
+
+    fn client(&self) -> Result<TrackerClient> {
+        Ok(settings::tracker_client()?.with_event_sender(self.events.clone()))
+    }
+
+    async fn load(&mut self, input: &str) -> Result<String> {
+        let sprint_id: u64 = input
+            .trim()
+            .parse()
+            .with_context(|| format!("Некорректный идентификатор спринта: {input}"))?;
+        let client = self.client()?;
+
+        let sprint = client.get_sprint(sprint_id).await?;
+        let (start, end) = parse_sprint_dates(&sprint)?;
+        let issues = client.get_sprint_issues(sprint_id).await?;
+
+        let permits = Arc::new(Semaphore::new(CHANGELOG_CONCURRENCY));
+        let loads: Vec<_> = issues
+            .into_iter()
+            .map(|issue| {
+                let client = client.clone();
+                let permits = Arc::clone(&permits);
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await;
+                    let changelog = client
+                        .get_changelog(&issue.issue_key()?, Some("resolution"))
+                        .await?;
+                    anyhow::Ok(SprintIssue::new(issue, &changelog))
+                })
+            })
+            .collect();
+
+        let mut sprint_issues = Vec::with_capacity(loads.len());
+        for load in loads {
+            sprint_issues.push(load.await.context("Загрузка истории задачи прервана")??);
+        }
+
+        let count = sprint_issues.len();
+        self.burndown = Some(Burndown {
+            name: sprint.name.unwrap_or_else(|| sprint_id.to_string()),
+            start,
+            end,
+            issues: sprint_issues,
+        });
+        Ok(format!("Задач в спринте: {count}"))
+    }
+}
+
+impl Screen for SprintScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Sprint,
+            title: "Sprint",
+            label: "Спринт",
+            shortcut: '6',
+            requires: &[Service::Tracker],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        if self.burndown.is_some() {
+            "Спринт: идентификатор + Enter — другой спринт, p — задачи/story points"
+        } else {
+            "Спринт: введите идентификатор спринта и нажмите Enter"
+        }
+    }
+
+    fn input_text(&self) -> &str {
+        &self.input
+    }
+
+    fn output_text(&self) -> String {
+        let summary = self
+            .burndown
+            .as_ref()
+            .map(|burndown| burndown.summary(Local::now().date_naive()));
+        match (summary, &self.status) {
+            (Some(summary), Some(status)) => format!("{summary}\n\n{status}"),
+            (Some(summary), None) => summary,
+            (None, Some(status)) => status.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        match key.code {
+            KeyCode::Char('p') if self.input.is_empty() => self.points = !self.points,
+            KeyCode::Char(c) if c.is_ascii_digit() => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter if !self.input.is_empty() => {
+                return ScreenEvent::Submit(std::mem::take(&mut self.input));
+            }
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.status = Some(text);
+    }
+
+    fn renders_markdown(&self) -> bool {
+        true
+    }
+
+    fn chart(&self) -> Option<ChartData> {
+        let burndown = self.burndown.as_ref()?;
+        Some(burndown.chart(Local::now().date_naive(), self.points))
+    }
+
+    fn command_preview(&self, input: &str) -> String {
+        format!("> tracker sprint {input}")
+    }
+
+    fn execute<'a>(&'a mut self, input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            match self.load(&input).await {
+                Ok(output) => output,
+                Err(err) => {
+                    let message = format!("Ошибка спринта: {err}");
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn sprint_issue(key: &str, points: f64, changes: Vec<(NaiveDate, bool)>) -> SprintIssue {
+        SprintIssue {
+            key: key.to_string(),
+            summary: format!("Задача {key}"),
+            points: Some(points),
+            assignee: Some("user".to_string()),
+            status_key: Some("inProgress".to_string()),
+            updated: Some(date(20)),
+            resolution_changes: changes,
+        }
+    }
+
+    fn burndown() -> Burndown {
+        Burndown {
+            name: "Спринт".to_string(),
+            start: date(15),
+            end: date(19),
+            issues: vec![
+                sprint_issue("A-1", 3.0, vec![(date(16), true)]),
+                sprint_issue("A-2", 5.0, vec![(date(16), true), (date(17), false)]),
+                sprint_issue("A-3", 2.0, Vec::new()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_remaining_follows_resolution_history() {
+        let burndown = burndown();
+        let actual = burndown.actual(date(18), false);
+
+        assert_eq!(actual, vec![(0.0, 3.0), (1.0, 1.0), (2.0, 2.0), (3.0, 2.0)]);
+        assert_eq!(burndown.remaining_on(date(18), true), 7.0);
+    }
+
+    #[test]
+    fn test_chart_has_ideal_and_actual_lines() {
+        let chart = burndown().chart(date(30), true);
+
+        assert_eq!(chart.y_max, 10.0);
+        assert_eq!(chart.series[0].points, vec![(0.0, 10.0), (4.0, 0.0)]);
+        assert_eq!(chart.series[1].points.len(), 5);
+    }
+
+    #[test]
+    fn test_summary_lists_risky_issues_by_points() {
+        let mut burndown = burndown();
+        burndown.issues[1].assignee = None;
+        burndown.issues[2].status_key = Some("open".to_string());
+
+        let summary = burndown.summary(date(18));
+        let risky: Vec<&str> = summary
+            .lines()
+            .filter(|line| line.starts_with("- "))
+            .collect();
+
+        assert_eq!(risky.len(), 2);
+        assert!(risky[0].contains("A-2") && risky[0].contains("без исполнителя"));
+        assert!(risky[1].contains("A-3") && risky[1].contains("не начата"));
+    }
+}
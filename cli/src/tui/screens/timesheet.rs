@@ -0,0 +1,616 @@
+use std::{collections::BTreeSet, future::Future, pin::Pin, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::{
+    events::TrackerEvent,
+    ids::IssueKey,
+    models::Worklog,
+    worklog::{parse_duration, AddWorklogRequest, UpdateWorklogRequest},
+    TrackerClient,
+};
+
+use super::{Popup, Screen, ScreenEvent, ScreenId, ScreenMeta, TRACKER_DATE_FORMAT};
+use crate::settings;
+use crate::tui::{
+    bus::EventBus,
+    diff::{self, FieldChange},
+    setup::Service,
+    toast::ToastLevel,
+};
+
+const WEEKDAYS: [&str; 7] = ["Пн", "Вт", "Ср", "Чт", "Пт", "Сб", "Вс"];
+
+/// Час начала записей, созданных из табеля
+const DEFAULT_START_HOUR: u32 = 9;
+
+/// Запись учёта времени, разложенная по задаче и дню
+#[derive(Debug)]
+struct Entry {
+    id: u64,
+    issue_key: String,
+    date: NaiveDate,
+    duration: Duration,
+}
+
+impl Entry {
+    fn from_worklog(worklog: &Worklog) -> Option<Self> {
+        let start =
+            DateTime::parse_from_str(worklog.start.as_deref()?, TRACKER_DATE_FORMAT).ok()?;
+        Some(Self {
+            id: worklog.id?,
+            issue_key: worklog.issue.as_ref()?.key.clone()?,
+            date: start.with_timezone(&Local).date_naive(),
+            duration: parse_duration(worklog.duration.as_deref()?)?,
+        })
+    }
+}
+
+enum Editing {
+    /// Часы в выбранной ячейке
+    Hours(String),
+    /// Ключ задачи для новой строки
+    Issue(String),
+}
+
+enum TimesheetAction {
+    Load,
+    SetHours {
+        issue_key: String,
+        date: NaiveDate,
+        duration: Duration,
+    },
+}
+
+/// Табель: часы по задачам и дням недели с редактированием прямо в сетке
+pub struct TimesheetScreen {
+    week_start: NaiveDate,
+    entries: Vec<Entry>,
+    /// Задачи без записей на этой неделе, добавленные вручную
+    extra_rows: Vec<String>,
+    row: usize,
+    day: usize,
+    editing: Option<Editing>,
+    /// Изменение ячейки, ожидающее подтверждения
+    confirming: Option<TimesheetAction>,
+    login: Option<String>,
+    status: Option<String>,
+    pending: Option<TimesheetAction>,
+    events: UnboundedSender<TrackerEvent>,
+    bus: EventBus,
+}
+
+impl TimesheetScreen {
+    pub fn new(events: UnboundedSender<TrackerEvent>, bus: EventBus) -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            week_start: week_start(today),
+            entries: Vec::new(),
+            extra_rows: Vec::new(),
+            row: 0,
+            day: today.weekday().num_days_from_monday() as usize,
+            editing: None,
+            confirming: None,
+            login: None,
+            status: Some("r — загрузить неделю".to_string()),
+            pending: None,
+            events,
+            bus,
+        }
+    }
+
+    fn client(&self) -> Result<TrackerClient> {
+        Ok(settings::tracker_client()?.with_event_sender(self.events.clone()))
+    }
+
+    fn rows(&self) -> Vec<String> {
+        let keys: BTreeSet<&str> = self
+            .entries
+            .iter()
+            .map(|entry| entry.issue_key.as_str())
+            .chain(self.extra_rows.iter().map(String::as_str))
+            .collect();
+        keys.into_iter().map(str::to_string).collect()
+    }
+
+    fn selected_date(&self) -> NaiveDate {
+        self.week_start + Days::new(self.day as u64)
+    }
+
+    fn cell_duration(&self, issue_key: &str, date: NaiveDate) -> Duration {
+        self.entries
+            .iter()
+            .filter(|entry| entry.issue_key == issue_key && entry.date == date)
+            .map(|entry| entry.duration)
+            .sum()
+    }
+
+    fn submit(&mut self, action: TimesheetAction) -> ScreenEvent {
+        let command = match &action {
+            TimesheetAction::Load => self.week_start.to_string(),
+            TimesheetAction::SetHours {
+                issue_key,
+                date,
+                duration,
+            } => format!("{issue_key} {date} {}", format_hours(*duration)),
+        };
+        self.pending = Some(action);
+        ScreenEvent::Submit(command)
+    }
+
+    fn shift_week(&mut self, forward: bool) -> ScreenEvent {
+        self.week_start = if forward {
+            self.week_start + Days::new(7)
+        } else {
+            self.week_start - Days::new(7)
+        };
+        self.extra_rows.clear();
+        self.submit(TimesheetAction::Load)
+    }
+
+    fn handle_editing_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        let Some(editing) = self.editing.as_mut() else {
+            return ScreenEvent::None;
+        };
+        let buffer = match editing {
+            Editing::Hours(buffer) | Editing::Issue(buffer) => buffer,
+        };
+
+        match key.code {
+            KeyCode::Esc => self.editing = None,
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            KeyCode::Char(c) => buffer.push(c),
+            KeyCode::Enter => match self.editing.take() {
+                Some(Editing::Hours(input)) => {
+                    let Some(issue_key) = self.rows().get(self.row).cloned() else {
+                        return ScreenEvent::None;
+                    };
+                    let Some(duration) = parse_hours(&input) else {
+                        self.status = Some(format!("Некорректное время: {input}"));
+                        return ScreenEvent::None;
+                    };
+                    let date = self.selected_date();
+                    self.confirming = Some(TimesheetAction::SetHours {
+                        issue_key,
+                        date,
+                        duration,
+                    });
+                }
+                Some(Editing::Issue(input)) => {
+                    let issue_key = input.trim().to_uppercase();
+                    if !issue_key.is_empty() {
+                        self.extra_rows.push(issue_key.clone());
+                        self.row = self
+                            .rows()
+                            .iter()
+                            .position(|key| *key == issue_key)
+                            .unwrap_or(0);
+                    }
+                }
+                None => {}
+            },
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    async fn run(&mut self, action: TimesheetAction) -> Result<String> {
+        let client = self.client()?;
+        if let TimesheetAction::SetHours {
+            issue_key,
+            date,
+            duration,
+        } = action
+        {
+            self.set_hours(&client, &issue_key, date, duration).await?;
+            self.bus.toast(
+                ToastLevel::Info,
+                format!("{issue_key} {date}: {}", format_hours(duration)),
+            );
+        }
+        self.load(&client).await
+    }
+
+    /// Приводит сумму записей в ячейке к заданной: первая запись меняется, остальные удаляются
+    async fn set_hours(
+        &self,
+        client: &TrackerClient,
+        issue_key: &str,
+        date: NaiveDate,
+        duration: Duration,
+    ) -> Result<()> {
+        let mut cell = self
+            .entries
+            .iter()
+            .filter(|entry| entry.issue_key == issue_key && entry.date == date);
+        let issue_key: IssueKey = issue_key.parse()?;
+
+        match cell.next() {
+            Some(first) if !duration.is_zero() => {
+                client
+                    .update_worklog(
+                        &issue_key,
+                        first.id,
+                        &UpdateWorklogRequest::duration(duration),
+                    )
+                    .await?;
+            }
+            Some(first) => client.delete_worklog(&issue_key, first.id).await?,
+            None if duration.is_zero() => {}
+            None => {
+                let start = local_start(date, DEFAULT_START_HOUR)?;
+                let request =
+                    AddWorklogRequest::new(start.format(TRACKER_DATE_FORMAT).to_string(), duration);
+                client.add_worklog(&issue_key, &request).await?;
+            }
+        }
+        for entry in cell {
+            client.delete_worklog(&issue_key, entry.id).await?;
+        }
+        Ok(())
+    }
+
+    async fn load(&mut self, client: &TrackerClient) -> Result<String> {
+        let login = match &self.login {
+            Some(login) => login.clone(),
+            None => {
+                let login = client
+                    .get_myself()
+                    .await?
+                    .login
+                    .context("У текущего пользователя нет логина")?;
+                self.login = Some(login.clone());
+                login
+            }
+        };
+
+        let from = local_start(self.week_start, 0)?;
+        let week_end = self.week_start + Days::new(7);
+        let worklogs = client
+            .search_worklogs(
+                &login.parse()?,
+                &from.format(TRACKER_DATE_FORMAT).to_string(),
+                None,
+            )
+            .await?;
+
+        self.entries = worklogs
+            .iter()
+            .filter_map(Entry::from_worklog)
+            .filter(|entry| entry.date >= self.week_start && entry.date < week_end)
+            .collect();
+        self.row = self.row.min(self.rows().len().saturating_sub(1));
+
+        Ok(format!("Записей за неделю: {}", self.entries.len()))
+    }
+}
+
+/// Понедельник недели, в которую входит дата
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+fn local_start(date: NaiveDate, hour: u32) -> Result<DateTime<Local>> {
+    let naive = date.and_hms_opt(hour, 0, 0).context("Некорректное время")?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .context("Некорректное локальное время")
+}
+
+/// Разбирает часы в виде `1.5`, `1,5` или `1:30`
+fn parse_hours(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let minutes = match input.split_once(':') {
+        Some((hours, minutes)) => {
+            let minutes: u64 = minutes.parse().ok()?;
+            if minutes >= 60 {
+                return None;
+            }
+            hours.parse::<u64>().ok()? * 60 + minutes
+        }
+        None => {
+            let hours: f64 = input.replace(',', ".").parse().ok()?;
+            if !(0.0..=24.0).contains(&hours) {
+                return None;
+            }
+            (hours * 60.0).round() as u64
+        }
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// Часы в формате `ч:мм`; пустая ячейка — точка
+fn format_hours(duration: Duration) -> String {
+    let minutes = (duration.as_secs() + 30) / 60;
+    if minutes == 0 {
+        return "·".to_string();
+    }
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Рисует сетку «задача × день»; выбранная ячейка в квадратных скобках
+fn render_grid(
+    week_start: NaiveDate,
+    rows: &[String],
+    entries: &[Entry],
+    selected: (usize, usize),
+) -> String {
+    let key_width = rows.iter().map(String::len).max().unwrap_or(0).max(6);
+    let cell = |text: String, is_selected: bool| {
+        if is_selected {
+            format!("{:>7}", format!("[{text}]"))
+        } else {
+            format!("{text:>6} ")
+        }
+    };
+    let sum = |filter: &dyn Fn(&Entry) -> bool| -> Duration {
+        entries
+            .iter()
+            .filter(|entry| filter(entry))
+            .map(|entry| entry.duration)
+            .sum()
+    };
+    let dates: Vec<NaiveDate> = (0..7)
+        .map(|offset| week_start + Days::new(offset))
+        .collect();
+
+    let mut lines = vec![format!(
+        "Неделя {} — {}",
+        week_start.format("%d.%m.%Y"),
+        dates[6].format("%d.%m.%Y")
+    )];
+
+    let mut header = format!("  {:key_width$} ", "Задача");
+    for (name, date) in WEEKDAYS.iter().zip(&dates) {
+        header.push_str(&format!("{:>6} ", format!("{name} {}", date.day())));
+    }
+    header.push_str(" Итого");
+    lines.push(header);
+
+    if rows.is_empty() {
+        lines.push("  Записей нет (a — добавить задачу)".to_string());
+    }
+
+    for (row_index, issue_key) in rows.iter().enumerate() {
+        let marker = if row_index == selected.0 { ">" } else { " " };
+        let mut line = format!("{marker} {issue_key:key_width$} ");
+        for (day_index, date) in dates.iter().enumerate() {
+            let duration = sum(&|entry| entry.issue_key == *issue_key && entry.date == *date);
+            line.push_str(&cell(
+                format_hours(duration),
+                (row_index, day_index) == selected,
+            ));
+        }
+        let total = sum(&|entry| entry.issue_key == *issue_key);
+        line.push_str(&format!(" {:>5}", format_hours(total)));
+        lines.push(line);
+    }
+
+    let mut totals = format!("  {:key_width$} ", "Итого");
+    for date in &dates {
+        totals.push_str(&cell(
+            format_hours(sum(&|entry| entry.date == *date)),
+            false,
+        ));
+    }
+    totals.push_str(&format!(" {:>5}", format_hours(sum(&|_| true))));
+    lines.push(totals);
+
+    lines.join("\n")
+}
+
+impl Screen for TimesheetScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Timesheet,
+            title: "Timesheet",
+            label: "Табель",
+            shortcut: '5',
+            requires: &[Service::Tracker],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        "Табель: стрелки/hjkl ячейка, Enter часы, x очистить, a задача, [/] неделя, r обновить"
+    }
+
+    fn input_text(&self) -> &str {
+        ""
+    }
+
+    fn output_text(&self) -> String {
+        let grid = render_grid(
+            self.week_start,
+            &self.rows(),
+            &self.entries,
+            (self.row, self.day),
+        );
+        match &self.status {
+            Some(status) => format!("{grid}\n\n{status}"),
+            None => grid,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        if let Some(action) = self.confirming.take() {
+            return match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => self.submit(action),
+                _ => ScreenEvent::None,
+            };
+        }
+        if self.editing.is_some() {
+            return self.handle_editing_key(key);
+        }
+
+        let rows = self.rows();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.row + 1 < rows.len() => self.row += 1,
+            KeyCode::Left | KeyCode::Char('h') => self.day = self.day.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.day = (self.day + 1).min(6),
+            KeyCode::Char('[') => return self.shift_week(false),
+            KeyCode::Char(']') => return self.shift_week(true),
+            KeyCode::Char('r') => return self.submit(TimesheetAction::Load),
+            KeyCode::Char('a') => self.editing = Some(Editing::Issue(String::new())),
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some(issue_key) = rows.get(self.row) {
+                    let current = self.cell_duration(issue_key, self.selected_date());
+                    let value = if current.is_zero() {
+                        String::new()
+                    } else {
+                        format_hours(current)
+                    };
+                    self.editing = Some(Editing::Hours(value));
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(issue_key) = rows.get(self.row).cloned() {
+                    let date = self.selected_date();
+                    self.confirming = Some(TimesheetAction::SetHours {
+                        issue_key,
+                        date,
+                        duration: Duration::ZERO,
+                    });
+                }
+            }
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.status = Some(text);
+    }
+
+    fn popup(&self) -> Option<Popup> {
+        if let Some(TimesheetAction::SetHours {
+            issue_key,
+            date,
+            duration,
+        }) = &self.confirming
+        {
+            let hours = |duration: Duration| (!duration.is_zero()).then(|| format_hours(duration));
+            let change = FieldChange::new(
+                format!("{issue_key} {}", date.format("%d.%m")),
+                hours(self.cell_duration(issue_key, *date)),
+                hours(*duration),
+            );
+            return Some(Popup {
+                title: "Изменить табель?".to_string(),
+                body: diff::render(&[change]),
+            });
+        }
+
+        let popup = match self.editing.as_ref()? {
+            Editing::Hours(buffer) => Popup {
+                title: format!(
+                    "{} {}: часы (1.5 или 1:30, 0 — удалить), Enter — сохранить, Esc — отмена",
+                    self.rows().get(self.row).map_or("", String::as_str),
+                    self.selected_date().format("%d.%m")
+                ),
+                body: buffer.clone(),
+            },
+            Editing::Issue(buffer) => Popup {
+                title: "Ключ задачи, Enter — добавить, Esc — отмена".to_string(),
+                body: buffer.clone(),
+            },
+        };
+        Some(popup)
+    }
+
+    fn command_preview(&self, input: &str) -> String {
+        match &self.pending {
+            Some(TimesheetAction::SetHours { .. }) => format!("> tracker worklog set {input}"),
+            Some(TimesheetAction::Load) | None => format!("> tracker worklog week {input}"),
+        }
+    }
+
+    fn execute<'a>(&'a mut self, _input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            let action = self.pending.take().unwrap_or(TimesheetAction::Load);
+            match self.run(action).await {
+                Ok(output) => output,
+                Err(err) => {
+                    let message = format!("Ошибка табеля: {err}");
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u64, issue_key: &str, date: NaiveDate, minutes: u64) -> Entry {
+        Entry {
+            id,
+            issue_key: issue_key.to_string(),
+            date,
+            duration: Duration::from_secs(minutes * 60),
+        }
+    }
+
+    #[test]
+    fn test_parse_hours() {
+        assert_eq!(parse_hours("1.5"), Some(Duration::from_secs(90 * 60)));
+        assert_eq!(parse_hours("1,25"), Some(Duration::from_secs(75 * 60)));
+        assert_eq!(parse_hours("2:05"), Some(Duration::from_secs(125 * 60)));
+        assert_eq!(parse_hours("0"), Some(Duration::ZERO));
+        assert_eq!(parse_hours("1:75"), None);
+        assert_eq!(parse_hours("abc"), None);
+    }
+
+    #[test]
+    fn test_week_start_is_monday() {
+        let thursday = NaiveDate::from_ymd_opt(2024, 1, 18).unwrap();
+        assert_eq!(
+            week_start(thursday),
+            NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_entry_from_worklog() {
+        let worklog: Worklog = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "issue": {"key": "TREK-1"},
+            "start": "2024-01-15T12:00:00.000+0000",
+            "duration": "PT1H30M"
+        }))
+        .unwrap();
+
+        let entry = Entry::from_worklog(&worklog).unwrap();
+        assert_eq!(entry.issue_key, "TREK-1");
+        assert_eq!(entry.duration, Duration::from_secs(90 * 60));
+    }
+
+    #[test]
+    fn test_render_grid_sums_cells_and_marks_selection() {
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let tuesday = monday + Days::new(1);
+        let entries = vec![
+            entry(1, "TREK-1", monday, 60),
+            entry(2, "TREK-1", monday, 30),
+            entry(3, "TREK-2", tuesday, 120),
+        ];
+        let rows = vec!["TREK-1".to_string(), "TREK-2".to_string()];
+
+        let grid = render_grid(monday, &rows, &entries, (1, 1));
+        let lines: Vec<&str> = grid.lines().collect();
+
+        assert!(lines[0].contains("15.01.2024 — 21.01.2024"));
+        assert!(lines[2].starts_with("  TREK-1"));
+        assert!(lines[2].contains("1:30"));
+        assert!(lines[3].starts_with("> TREK-2"));
+        assert!(lines[3].contains("[2:00]"));
+        assert!(lines[4].trim_end().ends_with("3:30"));
+    }
+}
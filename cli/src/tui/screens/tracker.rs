@@ -1,39 +1,940 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+};
 
-use anyhow::Result;
-use crossterm::event::KeyCode;
-use tracker_lib::{task::format_issue_output, TrackerClient};
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::{
+    comments::AddCommentRequest,
+    events::TrackerEvent,
+    ids::IssueKey,
+    models::{Issue, Transition, User},
+    subtasks::SubtaskProgress,
+    task::format_issue_output,
+    transitions::ExecuteTransitionRequest,
+    Conditional, TrackerClient,
+};
 
-use super::{Screen, ScreenEvent};
+use super::{
+    comments::{llm_context, CommentThread},
+    scrollback, Popup, Screen, ScreenEvent, ScreenId, ScreenMeta, ScreenSnapshot,
+};
+use crate::config::Config;
+use crate::drafts::{self, Drafts};
+use crate::index::IssueIndex;
+use crate::search::{self, SavedSearch};
+use crate::settings;
+use crate::summarize;
+use crate::tui::{
+    bus::{AppEvent, EventBus, RefreshedIssue},
+    diff::{self, FieldChange},
+    issue_ref::{self, IssueCompletion, RefStyle},
+    link_preview::{self, Preview},
+    setup::Service,
+    system::{copy_to_clipboard, open_url},
+    toast::ToastLevel,
+};
+
+const MAX_RECENT_ISSUES: usize = 50;
+/// Сколько верхних задач списка обновляется в фоне
+const MAX_REFRESHED_ISSUES: usize = 20;
+const DEFAULT_WEB_URL: &str = "https://st.yandex-team.ru";
+/// Глубина графа зависимостей по F9
+const GRAPH_DEPTH: usize = 2;
 
 pub struct TrackerScreen {
     input: String,
     output: Vec<String>,
+    issue: Option<Issue>,
+    /// Недавно открытые задачи: ключ и название, новые сверху
+    recent: Vec<(String, String)>,
+    /// Фокус на списке задач: клавиши работают как быстрые действия
+    list_focused: bool,
+    list_selected: usize,
+    /// ETag последней известной версии задач для условных запросов
+    etags: HashMap<String, String>,
+    /// Задачи, изменившиеся при фоновом обновлении и ещё не открытые заново
+    changed: HashSet<String>,
+    refreshing: bool,
+    /// Открытое окно действия и ключ задачи, к которой оно относится
+    popup: Option<(String, ActionPopup)>,
+    /// Открытая лента комментариев текущей задачи
+    comments: Option<CommentThread>,
+    /// Список задач после `#` в комментарии
+    completion: Option<IssueCompletion>,
+    /// Черновики комментариев на диске; None — черновики не сохраняются
+    drafts: Option<Drafts>,
+    /// Сведения о задачах для предпросмотра ссылок
+    previews: HashMap<String, Preview>,
+    /// Глубина предпросмотра ссылок; 0 — не раскрывать
+    link_depth: usize,
+    pending: Option<IssueAction>,
+    /// Клиент, заданный явно; без него клиент собирается из окружения
+    client: Option<TrackerClient>,
+    events: UnboundedSender<TrackerEvent>,
+    bus: EventBus,
+}
+
+enum ActionPopup {
+    Transitions {
+        transitions: Vec<Transition>,
+        selected: usize,
+    },
+    /// Подтверждение изменения с предпросмотром старых и новых значений
+    Confirm {
+        action: IssueAction,
+        changes: Vec<FieldChange>,
+    },
+    Tag(String),
+    Comment(String),
+    /// Выбор сохранённого поиска из конфигурации
+    Searches {
+        searches: Vec<(String, SavedSearch)>,
+        selected: usize,
+    },
+}
+
+enum IssueAction {
+    LoadTransitions {
+        issue_key: String,
+    },
+    Transition {
+        issue_key: String,
+        transition_id: String,
+    },
+    AssignToMe {
+        issue_key: String,
+    },
+    AddTag {
+        issue_key: String,
+        tag: String,
+    },
+    Comment {
+        issue_key: String,
+        text: String,
+    },
+    LoadComments {
+        issue_key: String,
+    },
+    /// Передать задачу с последними комментариями в чат с LLM
+    AttachToLlm {
+        issue_key: String,
+    },
+    /// Показать дерево блокеров и связей задачи
+    LoadGraph {
+        issue_key: String,
+    },
+    RunSearch {
+        name: String,
+        query: String,
+    },
+}
+
+impl IssueAction {
+    fn command(&self) -> String {
+        match self {
+            IssueAction::LoadTransitions { issue_key } => format!("transitions {issue_key}"),
+            IssueAction::Transition {
+                issue_key,
+                transition_id,
+            } => format!("transition {issue_key} {transition_id}"),
+            IssueAction::AssignToMe { issue_key } => format!("assign {issue_key} me"),
+            IssueAction::AddTag { issue_key, tag } => format!("tag {issue_key} {tag}"),
+            IssueAction::Comment { issue_key, .. } => format!("comment {issue_key}"),
+            IssueAction::LoadComments { issue_key } => format!("comments {issue_key}"),
+            IssueAction::AttachToLlm { issue_key } => format!("llm context {issue_key}"),
+            IssueAction::LoadGraph { issue_key } => format!("graph {issue_key}"),
+            IssueAction::RunSearch { name, .. } => format!("search run {name}"),
+        }
+    }
 }
 
 impl TrackerScreen {
-    pub fn new() -> Self {
+    pub fn new(events: UnboundedSender<TrackerEvent>, bus: EventBus) -> Self {
         Self {
             input: String::new(),
             output: vec!["Режим Tracker активирован".to_string()],
+            issue: None,
+            recent: Vec::new(),
+            list_focused: false,
+            list_selected: 0,
+            etags: HashMap::new(),
+            changed: HashSet::new(),
+            refreshing: false,
+            popup: None,
+            comments: None,
+            completion: None,
+            drafts: None,
+            previews: HashMap::new(),
+            link_depth: link_preview::DEFAULT_DEPTH,
+            pending: None,
+            client: None,
+            events,
+            bus,
         }
     }
 
+    /// Глубина предпросмотра ссылок из конфигурации
+    pub fn set_link_depth(&mut self, depth: usize) {
+        self.link_depth = depth.min(link_preview::MAX_DEPTH);
+    }
+
+    /// Работать с Трекером через этот клиент, а не через клиент из окружения
+    #[cfg(test)]
+    pub fn set_client(&mut self, client: TrackerClient) {
+        self.client = Some(client);
+    }
+
+    fn client(&self) -> Result<TrackerClient> {
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => settings::tracker_client()?,
+        };
+        Ok(client.with_event_sender(self.events.clone()))
+    }
+
     fn limit_output(&mut self) {
-        if self.output.len() > 200 {
-            let drain_count = self.output.len().saturating_sub(200);
+        if self.output.len() > scrollback() {
+            let drain_count = self.output.len().saturating_sub(scrollback());
             self.output.drain(0..drain_count);
         }
     }
+
+    fn submit_action(&mut self, action: IssueAction) -> ScreenEvent {
+        let command = action.command();
+        self.pending = Some(action);
+        ScreenEvent::Submit(command)
+    }
+
+    fn handle_issue_action_key(&mut self, key: KeyEvent) -> Option<ScreenEvent> {
+        let issue_key = self.issue.as_ref()?.key.clone();
+        let event = match key.code {
+            KeyCode::F(2) => self.submit_action(IssueAction::LoadTransitions { issue_key }),
+            KeyCode::F(3) => self.confirm_assign(issue_key),
+            KeyCode::F(4) => self.open_popup(issue_key, ActionPopup::Tag(String::new())),
+            KeyCode::F(5) => self.open_comment(issue_key, String::new()),
+            KeyCode::F(6) => ScreenEvent::ToggleFocus(issue_key),
+            KeyCode::F(7) => self.submit_action(IssueAction::LoadComments { issue_key }),
+            KeyCode::F(8) => self.submit_action(IssueAction::AttachToLlm { issue_key }),
+            KeyCode::F(9) => self.submit_action(IssueAction::LoadGraph { issue_key }),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// Открывает выбор сохранённых поисков; поиски перечитываются из конфигурации
+    fn open_searches(&mut self) -> ScreenEvent {
+        let searches = match Config::load() {
+            Ok(config) => search::sorted(&config.searches),
+            Err(err) => {
+                self.bus.toast(
+                    ToastLevel::Error,
+                    format!("Не удалось загрузить поиски: {err}"),
+                );
+                return ScreenEvent::None;
+            }
+        };
+        if searches.is_empty() {
+            self.bus.toast(
+                ToastLevel::Info,
+                "Сохранённых поисков нет: добавьте поиск командой `you search save`",
+            );
+            return ScreenEvent::None;
+        }
+        self.open_popup(
+            String::new(),
+            ActionPopup::Searches {
+                searches,
+                selected: 0,
+            },
+        )
+    }
+
+    /// Включает автосохранение черновиков комментариев
+    pub fn set_drafts(&mut self, drafts: Drafts) {
+        self.drafts = Some(drafts);
+    }
+
+    /// Открывает редактор комментария; сохранённый черновик важнее начального текста
+    fn open_comment(&mut self, issue_key: String, text: String) -> ScreenEvent {
+        let draft = self
+            .drafts
+            .as_ref()
+            .and_then(|drafts| drafts.load(&drafts::comment(&issue_key)));
+        let text = match draft {
+            Some(draft) => {
+                self.bus.toast(
+                    ToastLevel::Info,
+                    format!("Восстановлен черновик комментария к {issue_key}"),
+                );
+                draft
+            }
+            None => text,
+        };
+        self.open_popup(issue_key, ActionPopup::Comment(text))
+    }
+
+    fn open_popup(&mut self, issue_key: String, popup: ActionPopup) -> ScreenEvent {
+        self.popup = Some((issue_key, popup));
+        ScreenEvent::None
+    }
+
+    /// Открытая задача, если это она; для задач из списка старые значения неизвестны
+    fn loaded_issue(&self, issue_key: &str) -> Option<&Issue> {
+        self.issue.as_ref().filter(|issue| issue.key == issue_key)
+    }
+
+    fn confirm(
+        &mut self,
+        issue_key: String,
+        action: IssueAction,
+        changes: Vec<FieldChange>,
+    ) -> ScreenEvent {
+        self.open_popup(issue_key, ActionPopup::Confirm { action, changes })
+    }
+
+    fn confirm_assign(&mut self, issue_key: String) -> ScreenEvent {
+        let old = self
+            .loaded_issue(&issue_key)
+            .map(|issue| user_name(issue.assignee.as_ref()));
+        let changes = vec![FieldChange::new("Исполнитель", old, Some("вы".to_string()))];
+        let action = IssueAction::AssignToMe {
+            issue_key: issue_key.clone(),
+        };
+        self.confirm(issue_key, action, changes)
+    }
+
+    /// Навигация по ленте комментариев и ответ с цитатой
+    fn handle_comments_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        let Some(thread) = self.comments.as_mut() else {
+            return ScreenEvent::None;
+        };
+        match key.code {
+            KeyCode::F(7) => self.comments = None,
+            KeyCode::Down | KeyCode::Char('j') => thread.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => thread.select_previous(),
+            KeyCode::Char('g') => thread.select_first(),
+            KeyCode::Char('G') => thread.select_last(),
+            KeyCode::Char('u') => thread.cycle_author(),
+            KeyCode::Char('r') => {
+                let (issue_key, quote) = (thread.issue_key.clone(), thread.quote());
+                return self.open_comment(issue_key, quote);
+            }
+            KeyCode::Char('c') => {
+                let issue_key = thread.issue_key.clone();
+                return self.open_comment(issue_key, String::new());
+            }
+            _ => {}
+        }
+        ScreenEvent::None
+    }
+
+    /// Быстрые действия в стиле vim над выбранной в списке задачей
+    fn handle_list_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        let Some(issue_key) = self
+            .recent
+            .get(self.list_selected)
+            .map(|(key, _)| key.clone())
+        else {
+            self.list_focused = false;
+            return ScreenEvent::None;
+        };
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list_selected = self.list_selected.saturating_sub(1);
+                ScreenEvent::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.list_selected + 1 < self.recent.len() {
+                    self.list_selected += 1;
+                }
+                ScreenEvent::None
+            }
+            KeyCode::Enter => ScreenEvent::Submit(issue_key),
+            KeyCode::Char('a') => self.confirm_assign(issue_key),
+            KeyCode::Char('c') => self.open_comment(issue_key, String::new()),
+            KeyCode::Char('s') => self.submit_action(IssueAction::LoadTransitions { issue_key }),
+            KeyCode::Char('f') => ScreenEvent::ToggleFocus(issue_key),
+            KeyCode::Char('y') => {
+                match copy_to_clipboard(&issue_key) {
+                    Ok(()) => self
+                        .bus
+                        .toast(ToastLevel::Info, format!("{issue_key} скопирован")),
+                    Err(err) => self.bus.toast(
+                        ToastLevel::Error,
+                        format!("Не удалось скопировать {issue_key}: {err}"),
+                    ),
+                }
+                ScreenEvent::None
+            }
+            KeyCode::Char('o') => {
+                let url = issue_url(&issue_key);
+                if let Err(err) = open_url(&url) {
+                    self.bus.toast(
+                        ToastLevel::Error,
+                        format!("Не удалось открыть {url}: {err}"),
+                    );
+                }
+                ScreenEvent::None
+            }
+            _ => ScreenEvent::None,
+        }
+    }
+
+    fn handle_popup_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        let Some((issue_key, popup)) = self.popup.as_mut() else {
+            return ScreenEvent::None;
+        };
+        let issue_key = issue_key.clone();
+
+        if let (Some(completion), ActionPopup::Comment(text)) =
+            (self.completion.as_mut(), &mut *popup)
+        {
+            if !completion.handle_key(key, text, RefStyle::Key) {
+                self.completion = None;
+            }
+            save_comment_draft(self.drafts.as_ref(), &issue_key, text);
+            return ScreenEvent::None;
+        }
+
+        if key.code == KeyCode::Esc {
+            self.popup = None;
+            return ScreenEvent::None;
+        }
+
+        match popup {
+            ActionPopup::Transitions {
+                transitions,
+                selected,
+            } => match key.code {
+                KeyCode::Up => {
+                    *selected = selected.saturating_sub(1);
+                    ScreenEvent::None
+                }
+                KeyCode::Down => {
+                    if *selected + 1 < transitions.len() {
+                        *selected += 1;
+                    }
+                    ScreenEvent::None
+                }
+                KeyCode::Enter => {
+                    let Some(transition) = transitions.get(*selected) else {
+                        return ScreenEvent::None;
+                    };
+                    let transition_id = transition.id.clone();
+                    let target = transition
+                        .to
+                        .as_ref()
+                        .and_then(|status| status.display.clone());
+                    let old = self
+                        .loaded_issue(&issue_key)
+                        .and_then(|issue| issue.status.as_ref())
+                        .and_then(|status| status.display.clone());
+                    let action = IssueAction::Transition {
+                        issue_key: issue_key.clone(),
+                        transition_id,
+                    };
+                    self.confirm(
+                        issue_key,
+                        action,
+                        vec![FieldChange::new("Статус", old, target)],
+                    )
+                }
+                _ => ScreenEvent::None,
+            },
+            ActionPopup::Confirm { .. } => match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => match self.popup.take() {
+                    Some((_, ActionPopup::Confirm { action, .. })) => self.submit_action(action),
+                    _ => ScreenEvent::None,
+                },
+                KeyCode::Char('n') => {
+                    self.popup = None;
+                    ScreenEvent::None
+                }
+                _ => ScreenEvent::None,
+            },
+            ActionPopup::Tag(tag) => match key.code {
+                KeyCode::Backspace => {
+                    tag.pop();
+                    ScreenEvent::None
+                }
+                KeyCode::Char(c) if c != ' ' => {
+                    tag.push(c);
+                    ScreenEvent::None
+                }
+                KeyCode::Enter if !tag.is_empty() => {
+                    let tag = std::mem::take(tag);
+                    let old = self
+                        .loaded_issue(&issue_key)
+                        .map(|issue| issue.tags.join(", "));
+                    let new = match &old {
+                        Some(old) if !old.is_empty() => format!("{old}, {tag}"),
+                        _ => tag.clone(),
+                    };
+                    let changes = vec![FieldChange::new("Теги", old, Some(new))];
+                    let action = IssueAction::AddTag {
+                        issue_key: issue_key.clone(),
+                        tag,
+                    };
+                    self.confirm(issue_key, action, changes)
+                }
+                _ => ScreenEvent::None,
+            },
+            ActionPopup::Searches { searches, selected } => match key.code {
+                KeyCode::Up => {
+                    *selected = selected.saturating_sub(1);
+                    ScreenEvent::None
+                }
+                KeyCode::Down => {
+                    if *selected + 1 < searches.len() {
+                        *selected += 1;
+                    }
+                    ScreenEvent::None
+                }
+                KeyCode::Enter => {
+                    let Some((name, search)) = searches.get(*selected) else {
+                        return ScreenEvent::None;
+                    };
+                    let action = IssueAction::RunSearch {
+                        name: name.clone(),
+                        query: search.query.clone(),
+                    };
+                    self.popup = None;
+                    self.submit_action(action)
+                }
+                _ => ScreenEvent::None,
+            },
+            ActionPopup::Comment(text) => match key.code {
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let text = text.trim().to_string();
+                    if text.is_empty() {
+                        return ScreenEvent::None;
+                    }
+                    let changes = vec![FieldChange::new("Комментарий", None, Some(text.clone()))];
+                    let action = IssueAction::Comment {
+                        issue_key: issue_key.clone(),
+                        text,
+                    };
+                    self.confirm(issue_key, action, changes)
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                    save_comment_draft(self.drafts.as_ref(), &issue_key, text);
+                    ScreenEvent::None
+                }
+                KeyCode::Enter => {
+                    text.push('\n');
+                    save_comment_draft(self.drafts.as_ref(), &issue_key, text);
+                    ScreenEvent::None
+                }
+                KeyCode::Char(issue_ref::TRIGGER) => {
+                    text.push(issue_ref::TRIGGER);
+                    self.completion = Some(IssueCompletion::new(
+                        text,
+                        issue_ref::issue_items(&self.recent),
+                    ));
+                    save_comment_draft(self.drafts.as_ref(), &issue_key, text);
+                    ScreenEvent::None
+                }
+                KeyCode::Char(c) => {
+                    text.push(c);
+                    save_comment_draft(self.drafts.as_ref(), &issue_key, text);
+                    ScreenEvent::None
+                }
+                _ => ScreenEvent::None,
+            },
+        }
+    }
+
+    async fn run_action(&mut self, action: IssueAction) -> Result<String> {
+        let client = self.client()?;
+        match action {
+            IssueAction::LoadTransitions { issue_key } => {
+                let transitions = client.get_transitions(&issue_key.parse()?).await?;
+                if transitions.is_empty() {
+                    return Ok(format!("Для задачи {issue_key} нет доступных переходов"));
+                }
+                let count = transitions.len();
+                self.popup = Some((
+                    issue_key,
+                    ActionPopup::Transitions {
+                        transitions,
+                        selected: 0,
+                    },
+                ));
+                Ok(format!("Доступно переходов: {count}"))
+            }
+            IssueAction::Transition {
+                issue_key,
+                transition_id,
+            } => {
+                client
+                    .execute_transition(
+                        &issue_key.parse()?,
+                        &transition_id,
+                        &ExecuteTransitionRequest::default(),
+                        None,
+                    )
+                    .await?;
+                self.bus
+                    .toast(ToastLevel::Info, format!("Статус {issue_key} изменён"));
+                let issue = client.get_issue(&issue_key.parse()?, None).await?;
+                Ok(self.show_issue(issue))
+            }
+            IssueAction::AssignToMe { issue_key } => {
+                let me = client.get_myself().await?;
+                let login = me
+                    .login
+                    .context("Трекер не вернул логин текущего пользователя")?;
+                let issue = client
+                    .assign_issue(&issue_key.parse()?, &login.parse()?)
+                    .await?;
+                self.bus
+                    .toast(ToastLevel::Info, format!("{issue_key} назначена на вас"));
+                Ok(self.show_issue(issue))
+            }
+            IssueAction::AddTag { issue_key, tag } => {
+                let issue = client
+                    .add_tags(&issue_key.parse()?, std::slice::from_ref(&tag))
+                    .await?;
+                self.bus.toast(
+                    ToastLevel::Info,
+                    format!("Тег {tag} добавлен к {issue_key}"),
+                );
+                Ok(self.show_issue(issue))
+            }
+            IssueAction::Comment { issue_key, text } => {
+                let key: IssueKey = issue_key.parse()?;
+                client
+                    .add_comment(&key, &AddCommentRequest::new(text))
+                    .await?;
+                // Комментарий уже отправлен, поэтому ошибка удаления черновика не важна
+                if let Some(drafts) = &self.drafts {
+                    if let Err(err) = drafts.remove(&drafts::comment(&issue_key)) {
+                        tracing::warn!(error = %err, "Не удалось удалить черновик комментария");
+                    }
+                }
+                let message = format!("Комментарий к {issue_key} добавлен");
+                self.bus.toast(ToastLevel::Info, message.clone());
+                if self
+                    .comments
+                    .as_ref()
+                    .is_some_and(|thread| thread.issue_key == issue_key)
+                {
+                    let comments = client.get_comments(&key).await?;
+                    if let Some(thread) = self.comments.as_mut() {
+                        thread.replace(comments);
+                    }
+                }
+                Ok(message)
+            }
+            IssueAction::LoadComments { issue_key } => {
+                let comments = client.get_comments(&issue_key.parse()?).await?;
+                let count = comments.len();
+                self.comments = Some(CommentThread::new(issue_key, comments));
+                Ok(format!("Комментариев: {count}"))
+            }
+            IssueAction::AttachToLlm { issue_key } => {
+                let key: IssueKey = issue_key.parse()?;
+                let issue = client.get_issue(&key, None).await?;
+                let comments = client.get_comments(&key).await?;
+                self.bus.publish(AppEvent::LlmContext {
+                    label: format!("{issue_key}: {}", issue.summary),
+                    text: llm_context(&issue, &comments),
+                });
+                Ok(format!("Задача {issue_key} передана в LLM"))
+            }
+            IssueAction::LoadGraph { issue_key } => {
+                let graph = client
+                    .build_dependency_graph(&issue_key, GRAPH_DEPTH)
+                    .await?;
+                Ok(graph.render_tree())
+            }
+            IssueAction::RunSearch { name, query } => {
+                let issues =
+                    summarize::fetch_issues(&client, &query, search::DEFAULT_LIMIT).await?;
+                if issues.is_empty() {
+                    return Ok(format!("По поиску {name} ничего не найдено"));
+                }
+                let rows: Vec<String> = issues.iter().map(search::issue_row).collect();
+                Ok(format!(
+                    "🔎 {name}: задач {}\n\n{}",
+                    issues.len(),
+                    rows.join("\n")
+                ))
+            }
+        }
+    }
+
+    async fn load_issue(&mut self, input: &str) -> Result<String> {
+        let issue_key: IssueKey = input.parse()?;
+        let client = self.client()?;
+        let issue = client.get_issue(&issue_key, None).await?;
+        let subtasks = client
+            .list_subtasks(&issue_key)
+            .await
+            .unwrap_or_else(|err| {
+                self.bus.toast(
+                    ToastLevel::Warning,
+                    format!("Не удалось получить подзадачи {issue_key}: {err}"),
+                );
+                Vec::new()
+            });
+        let tree = subtask_tree(&issue, &subtasks);
+        let links = self.link_previews(&client, &issue).await;
+        let mut output = self.show_issue(issue);
+        if let Some(tree) = tree {
+            output.push_str(&tree);
+        }
+        if let Some(links) = links {
+            output.push_str(&links);
+        }
+        Ok(output)
+    }
+
+    /// Блок со ссылками на задачи из описания и комментариев
+    async fn link_previews(&mut self, client: &TrackerClient, issue: &Issue) -> Option<String> {
+        if self.link_depth == 0 {
+            return None;
+        }
+        let Ok(issue_key) = issue.issue_key() else {
+            return None;
+        };
+        let comments = client.get_comments(&issue_key).await.unwrap_or_else(|err| {
+            tracing::debug!(error = %err, "Комментарии для предпросмотра ссылок недоступны");
+            Vec::new()
+        });
+        let mut texts: Vec<&str> = issue.description.as_deref().into_iter().collect();
+        texts.extend(comments.iter().map(|comment| comment.text.as_str()));
+        if texts
+            .iter()
+            .all(|text| link_preview::referenced_keys(text).is_empty())
+        {
+            return None;
+        }
+        let index = IssueIndex::open_default().ok();
+        let linked = link_preview::resolve(
+            client,
+            index.as_ref(),
+            &mut self.previews,
+            &issue.key,
+            &texts,
+            self.link_depth,
+        )
+        .await;
+        link_preview::render(&linked)
+    }
+
+    fn show_issue(&mut self, issue: Issue) -> String {
+        let output = format_issue_output(&issue);
+        // Свои изменения не должны подсвечиваться как чужие при следующем обновлении
+        self.etags.remove(&issue.key);
+        self.changed.remove(&issue.key);
+        if self
+            .comments
+            .as_ref()
+            .is_some_and(|thread| thread.issue_key != issue.key)
+        {
+            self.comments = None;
+        }
+        self.remember(&issue);
+        self.issue = Some(issue);
+        output
+    }
+
+    fn remember(&mut self, issue: &Issue) {
+        self.previews
+            .insert(issue.key.clone(), Preview::from(issue));
+        self.recent.retain(|(key, _)| key != &issue.key);
+        self.recent
+            .insert(0, (issue.key.clone(), issue.summary.clone()));
+        self.recent.truncate(MAX_RECENT_ISSUES);
+        self.list_selected = 0;
+    }
+
+    /// Запускает фоновое обновление верхних задач списка; результат придёт через шину
+    pub fn start_refresh(&mut self) {
+        if self.refreshing || self.recent.is_empty() {
+            return;
+        }
+        let Ok(client) = self.client() else {
+            return;
+        };
+        let targets: Vec<(IssueKey, Option<String>)> = self
+            .recent
+            .iter()
+            .take(MAX_REFRESHED_ISSUES)
+            .filter_map(|(key, _)| Some((key.parse().ok()?, self.etags.get(key).cloned())))
+            .collect();
+        let bus = self.bus.clone();
+        self.refreshing = true;
+
+        tokio::spawn(async move {
+            let mut refresh = RefreshResult::new(bus.clone());
+            for (issue_key, etag) in targets {
+                match client
+                    .get_issue_if_changed(&issue_key, etag.as_deref())
+                    .await
+                {
+                    Ok(Conditional::NotModified) => {}
+                    Ok(Conditional::Modified {
+                        value,
+                        etag: new_etag,
+                    }) => refresh.updates.push(RefreshedIssue {
+                        issue: value,
+                        etag: new_etag,
+                        changed: etag.is_some(),
+                    }),
+                    Err(err) => {
+                        bus.toast(
+                            ToastLevel::Warning,
+                            format!("Не удалось обновить {issue_key}: {err}"),
+                        );
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Применяет результат обновления и отмечает изменившиеся задачи
+    pub fn apply_refresh(&mut self, updates: Vec<RefreshedIssue>) {
+        self.refreshing = false;
+        for RefreshedIssue {
+            issue,
+            etag,
+            changed,
+        } in updates
+        {
+            if let Some(etag) = etag {
+                self.etags.insert(issue.key.clone(), etag);
+            }
+            if let Some(entry) = self.recent.iter_mut().find(|(key, _)| *key == issue.key) {
+                entry.1 = issue.summary.clone();
+            }
+            if !changed {
+                continue;
+            }
+
+            self.changed.insert(issue.key.clone());
+            let is_current = self
+                .issue
+                .as_ref()
+                .is_some_and(|current| current.key == issue.key);
+            if is_current {
+                self.push_output(format!(
+                    "🔄 {} изменилась\n\n{}",
+                    issue.key,
+                    format_issue_output(&issue)
+                ));
+                self.issue = Some(issue);
+            }
+        }
+    }
+}
+
+/// Сохраняет текст редактора комментария, чтобы он пережил падение или выход из TUI
+fn save_comment_draft(drafts: Option<&Drafts>, issue_key: &str, text: &str) {
+    let Some(drafts) = drafts else {
+        return;
+    };
+    if let Err(err) = drafts.save(&drafts::comment(issue_key), text) {
+        tracing::warn!(error = %err, issue_key, "Не удалось сохранить черновик комментария");
+    }
+}
+
+fn user_name(user: Option<&User>) -> String {
+    user.and_then(|user| user.display.clone().or_else(|| user.login.clone()))
+        .unwrap_or_else(|| "не назначен".to_string())
+}
+
+/// Дерево «родитель → задача → подзадачи» для карточки задачи
+///
+/// Возвращает `None`, если у задачи нет ни родителя, ни подзадач.
+fn subtask_tree(issue: &Issue, subtasks: &[Issue]) -> Option<String> {
+    if issue.parent.is_none() && subtasks.is_empty() {
+        return None;
+    }
+
+    let mut output = String::from("🌳 Дерево задачи:\n");
+    let mut indent = "   ";
+    if let Some(parent) = &issue.parent {
+        let key = parent
+            .key
+            .as_deref()
+            .or(parent.id.as_deref())
+            .unwrap_or("?");
+        let summary = parent.display.as_deref().unwrap_or("");
+        output.push_str(&format!("   {key} {summary}\n"));
+        output.push_str(&format!("   └─ {} {}", issue.key, issue.summary));
+        indent = "      ";
+    } else {
+        output.push_str(&format!("   {} {}", issue.key, issue.summary));
+    }
+    if !subtasks.is_empty() {
+        output.push_str(&format!(
+            " · подзадачи {}",
+            SubtaskProgress::from_issues(subtasks)
+        ));
+    }
+    output.push('\n');
+
+    for (index, subtask) in subtasks.iter().enumerate() {
+        let branch = if index + 1 == subtasks.len() {
+            "└─"
+        } else {
+            "├─"
+        };
+        let mark = if subtask.resolved_at.is_some() {
+            "✓"
+        } else {
+            "○"
+        };
+        let status = subtask
+            .status
+            .as_ref()
+            .and_then(|status| status.display.as_deref())
+            .unwrap_or("без статуса");
+        output.push_str(&format!(
+            "{indent}{branch} {mark} {} {} [{status}]\n",
+            subtask.key, subtask.summary
+        ));
+    }
+    output.push('\n');
+    Some(output)
+}
+
+/// Ссылка на задачу в веб-интерфейсе; адрес можно переопределить через `TRACKER_WEB_URL`
+fn issue_url(issue_key: &str) -> String {
+    let base = std::env::var("TRACKER_WEB_URL").unwrap_or_else(|_| DEFAULT_WEB_URL.to_string());
+    format!("{}/{issue_key}", base.trim_end_matches('/'))
 }
 
 impl Screen for TrackerScreen {
-    fn title(&self) -> &'static str {
-        "Tracker"
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Tracker,
+            title: "Tracker",
+            label: "Tracker",
+            shortcut: '1',
+            requires: &[Service::Tracker],
+        }
     }
 
     fn input_title(&self) -> &'static str {
-        "Tracker: введите ключ задачи и нажмите Enter"
+        if self.comments.is_some() {
+            "Комментарии: j/k выбор, g/G начало/конец, u автор, r ответить с цитатой, c новый, F7 закрыть"
+        } else if self.list_focused {
+            "Список: j/k выбор, Enter открыть, a на меня, c комментарий, s статус, y ключ, o браузер, f фокус, Tab ввод"
+        } else if self.issue.is_some() {
+            "Tracker: ключ задачи + Enter | F2 статус, F3 на меня, F4 тег, F5 комментарий, F6 фокус, F7 комментарии, F8 в LLM, F9 граф, F10 поиски"
+        } else {
+            "Tracker: введите ключ задачи и нажмите Enter | F10 сохранённые поиски"
+        }
     }
 
     fn input_text(&self) -> &str {
@@ -41,11 +942,39 @@ impl Screen for TrackerScreen {
     }
 
     fn output_text(&self) -> String {
-        self.output.join("\n\n")
+        match &self.comments {
+            Some(thread) => thread.detail_text(),
+            None => self.output.join("\n\n"),
+        }
     }
 
-    fn handle_key(&mut self, key: KeyCode) -> ScreenEvent {
-        match key {
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        if self.popup.is_some() {
+            return self.handle_popup_key(key);
+        }
+
+        if self.comments.is_some() {
+            return self.handle_comments_key(key);
+        }
+
+        if key.code == KeyCode::F(10) {
+            return self.open_searches();
+        }
+
+        if key.code == KeyCode::Tab && !self.recent.is_empty() {
+            self.list_focused = !self.list_focused;
+            return ScreenEvent::None;
+        }
+
+        if self.list_focused {
+            return self.handle_list_key(key);
+        }
+
+        if let Some(event) = self.handle_issue_action_key(key) {
+            return event;
+        }
+
+        match key.code {
             KeyCode::Backspace => {
                 self.input.pop();
                 ScreenEvent::None
@@ -71,22 +1000,213 @@ impl Screen for TrackerScreen {
         self.limit_output();
     }
 
+    fn snapshot(&self) -> ScreenSnapshot {
+        ScreenSnapshot {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            selected_issue: self.issue.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: ScreenSnapshot) {
+        self.input = snapshot.input;
+        if let Some(issue) = &snapshot.selected_issue {
+            self.remember(issue);
+        }
+        self.issue = snapshot.selected_issue;
+        if !snapshot.output.is_empty() {
+            self.output = snapshot.output;
+            self.limit_output();
+        }
+    }
+
+    fn list_text(&self) -> Option<String> {
+        if let Some(thread) = &self.comments {
+            return Some(thread.list_text());
+        }
+        if self.recent.is_empty() {
+            return Some("Открытых задач пока нет".to_string());
+        }
+        let current = self.issue.as_ref().map(|issue| issue.key.as_str());
+        let lines: Vec<String> = self
+            .recent
+            .iter()
+            .map(|(key, summary)| {
+                let marker = if Some(key.as_str()) == current {
+                    ">"
+                } else {
+                    " "
+                };
+                format!("{marker} {key}  {summary}")
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    fn renders_markdown(&self) -> bool {
+        true
+    }
+
     fn command_preview(&self, input: &str) -> String {
-        format!("> tracker issue {input}")
+        match &self.pending {
+            Some(action) => format!("> tracker {}", action.command()),
+            None => format!("> tracker issue {input}"),
+        }
     }
 
     fn execute<'a>(&'a mut self, input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
         Box::pin(async move {
-            match fetch_tracker_issue(&input).await {
+            let result = match self.pending.take() {
+                Some(action) => self.run_action(action).await,
+                None => self.load_issue(&input).await,
+            };
+            match result {
                 Ok(output) => output,
-                Err(err) => format!("Ошибка Tracker: {err}"),
+                Err(err) => {
+                    let message = format!("Ошибка Tracker: {err}");
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
             }
         })
     }
+
+    fn popup(&self) -> Option<Popup> {
+        let (issue_key, popup) = self.popup.as_ref()?;
+        let popup = match popup {
+            ActionPopup::Transitions {
+                transitions,
+                selected,
+            } => Popup {
+                title: format!("Смена статуса {issue_key}: ↑/↓, Enter — выполнить, Esc — отмена"),
+                body: transitions
+                    .iter()
+                    .enumerate()
+                    .map(|(index, transition)| {
+                        let marker = if index == *selected { ">" } else { " " };
+                        let name = transition.display.as_deref().unwrap_or(&transition.id);
+                        let target = transition
+                            .to
+                            .as_ref()
+                            .and_then(|status| status.display.as_deref())
+                            .unwrap_or("?");
+                        format!("{marker} {name} → {target}")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            },
+            ActionPopup::Confirm { action, changes } => Popup {
+                title: format!("Подтвердите: {}", action.command()),
+                body: diff::render(changes),
+            },
+            ActionPopup::Tag(tag) => Popup {
+                title: format!("Новый тег для {issue_key}: Enter — добавить, Esc — отмена"),
+                body: tag.clone(),
+            },
+            ActionPopup::Searches { searches, selected } => Popup {
+                title: "Сохранённые поиски: ↑/↓, Enter — выполнить, Esc — отмена".to_string(),
+                body: searches
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (name, search))| {
+                        let marker = if index == *selected { ">" } else { " " };
+                        format!("{marker} {name}  {}", search.query)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            },
+            ActionPopup::Comment(text) => match &self.completion {
+                Some(completion) => Popup {
+                    title: format!(
+                        "Комментарий к {issue_key}: ↑/↓, Enter — вставить задачу, Esc — закрыть список"
+                    ),
+                    body: format!("{text}\n\n{}", completion.lines().join("\n")),
+                },
+                None => Popup {
+                    title: format!(
+                        "Комментарий к {issue_key}: Enter — новая строка, # — задача, Ctrl+S — отправить, Esc — отмена"
+                    ),
+                    body: text.clone(),
+                },
+            },
+        };
+        Some(popup)
+    }
+}
+
+/// Результат фонового обновления, который публикуется при любом завершении задачи
+///
+/// Даже если задача паникует, экран получает `IssuesRefreshed` и снимает флаг
+/// `refreshing`, иначе автообновление больше не запустилось бы.
+struct RefreshResult {
+    bus: EventBus,
+    updates: Vec<RefreshedIssue>,
+}
+
+impl RefreshResult {
+    fn new(bus: EventBus) -> Self {
+        Self {
+            bus,
+            updates: Vec::new(),
+        }
+    }
 }
 
-async fn fetch_tracker_issue(issue_id: &str) -> Result<String> {
-    let client = TrackerClient::from_env()?;
-    let issue = client.get_issue(issue_id, None).await?;
-    Ok(format_issue_output(&issue))
+impl Drop for RefreshResult {
+    fn drop(&mut self) {
+        let updates = std::mem::take(&mut self.updates);
+        self.bus.publish(AppEvent::IssuesRefreshed(updates));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::issue;
+    use serde_json::json;
+
+    #[test]
+    fn test_subtask_tree_renders_parent_and_progress() {
+        let current = issue("TREK-2")
+            .summary("Переезд на новую схему")
+            .field("parent", json!({"key": "TREK-1", "display": "Хранилище"}))
+            .build();
+        let subtasks = [
+            issue("TREK-3")
+                .summary("Схема")
+                .status("closed", "Закрыт")
+                .field("resolvedAt", "2026-10-16T09:30:00.000+0000")
+                .build(),
+            issue("TREK-4").summary("Миграция").build(),
+        ];
+
+        assert_eq!(
+            subtask_tree(&current, &subtasks).unwrap(),
+            "🌳 Дерево задачи:\n   TREK-1 Хранилище\n   \
+             └─ TREK-2 Переезд на новую схему · подзадачи 1/2\n      \
+             ├─ ✓ TREK-3 Схема [Закрыт]\n      \
+             └─ ○ TREK-4 Миграция [без статуса]\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_result_is_published_after_panic() {
+        let (bus, mut events) = EventBus::new();
+        let task = tokio::spawn(async move {
+            let _refresh = RefreshResult::new(bus);
+            panic!("обновление упало");
+        });
+        assert!(task.await.is_err());
+
+        assert!(matches!(
+            events.recv().await,
+            Some(AppEvent::IssuesRefreshed(updates)) if updates.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_subtask_tree_skips_standalone_issue() {
+        let current = issue("TREK-5").summary("Отдельная задача").build();
+        assert!(subtask_tree(&current, &[]).is_none());
+    }
 }
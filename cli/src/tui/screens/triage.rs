@@ -0,0 +1,395 @@
+use std::{future::Future, pin::Pin};
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use llm_lib::{events::LlmEvent, LlmClient, LlmConfig};
+use tokio::sync::mpsc::UnboundedSender;
+use tracker_lib::{
+    events::TrackerEvent, ids::QueueKey, models::Issue, task::format_issue_output, TrackerClient,
+};
+
+use super::{scrollback, Popup, Screen, ScreenEvent, ScreenId, ScreenMeta};
+use crate::{
+    llm::DEFAULT_MODEL,
+    settings,
+    triage::{self, TriageSuggestion},
+    tui::{
+        bus::EventBus,
+        diff::{self, FieldChange},
+        setup::Service,
+        toast::ToastLevel,
+    },
+};
+
+/// Экран разбора входящих задач: задача, подсказка LLM и решение одной клавишей
+pub struct TriageScreen {
+    input: String,
+    log: Vec<String>,
+    issues: Vec<Issue>,
+    current: usize,
+    suggestion: Option<TriageSuggestion>,
+    /// Открыт предпросмотр изменений перед применением подсказки
+    confirming: bool,
+    pending: Option<TriageAction>,
+    tracker_events: UnboundedSender<TrackerEvent>,
+    llm_events: UnboundedSender<LlmEvent>,
+    bus: EventBus,
+}
+
+enum TriageAction {
+    Load { queue: QueueKey },
+    Suggest,
+    Apply,
+}
+
+impl TriageScreen {
+    pub fn new(
+        tracker_events: UnboundedSender<TrackerEvent>,
+        llm_events: UnboundedSender<LlmEvent>,
+        bus: EventBus,
+    ) -> Self {
+        Self {
+            input: String::new(),
+            log: vec!["Режим Triage: введите ключ очереди".to_string()],
+            issues: Vec::new(),
+            current: 0,
+            suggestion: None,
+            confirming: false,
+            pending: None,
+            tracker_events,
+            llm_events,
+            bus,
+        }
+    }
+
+    fn tracker(&self) -> Result<TrackerClient> {
+        Ok(settings::tracker_client()?.with_event_sender(self.tracker_events.clone()))
+    }
+
+    fn llm(&self) -> Result<LlmClient> {
+        let config = LlmConfig::new(DEFAULT_MODEL)?;
+        Ok(LlmClient::new(config)?.with_event_sender(self.llm_events.clone()))
+    }
+
+    fn current_issue(&self) -> Option<&Issue> {
+        self.issues.get(self.current)
+    }
+
+    fn submit(&mut self, action: TriageAction) -> ScreenEvent {
+        let command = match &action {
+            TriageAction::Load { queue } => queue.to_string(),
+            TriageAction::Suggest | TriageAction::Apply => self
+                .current_issue()
+                .map(|issue| issue.key.clone())
+                .unwrap_or_default(),
+        };
+        self.pending = Some(action);
+        ScreenEvent::Submit(command)
+    }
+
+    fn handle_decision_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        if self.confirming {
+            self.confirming = false;
+            return match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => self.submit(TriageAction::Apply),
+                _ => ScreenEvent::None,
+            };
+        }
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') if self.suggestion.is_some() => {
+                self.confirming = true;
+                ScreenEvent::None
+            }
+            KeyCode::Char('n') => {
+                self.advance();
+                if self.current_issue().is_some() {
+                    self.submit(TriageAction::Suggest)
+                } else {
+                    ScreenEvent::None
+                }
+            }
+            KeyCode::Char('r') => self.submit(TriageAction::Suggest),
+            KeyCode::Char('t') => {
+                if let Some(suggestion) = self.suggestion.as_mut() {
+                    suggestion.cycle_type();
+                }
+                ScreenEvent::None
+            }
+            KeyCode::Char('p') => {
+                if let Some(suggestion) = self.suggestion.as_mut() {
+                    suggestion.cycle_priority();
+                }
+                ScreenEvent::None
+            }
+            KeyCode::Char('g') => {
+                self.issues.clear();
+                self.suggestion = None;
+                ScreenEvent::None
+            }
+            _ => ScreenEvent::None,
+        }
+    }
+
+    fn advance(&mut self) {
+        self.suggestion = None;
+        self.current += 1;
+        if self.current >= self.issues.len() {
+            self.issues.clear();
+            self.current = 0;
+            self.push_log("Все задачи очереди разобраны. Введите следующую очередь".to_string());
+        }
+    }
+
+    fn push_log(&mut self, text: String) {
+        self.log.push(text);
+        if self.log.len() > scrollback() {
+            let drain_count = self.log.len() - scrollback();
+            self.log.drain(0..drain_count);
+        }
+    }
+
+    async fn run(&mut self, action: TriageAction) -> Result<String> {
+        match action {
+            TriageAction::Load { queue } => {
+                let issues = triage::fetch_untriaged(&self.tracker()?, &queue).await?;
+                if issues.is_empty() {
+                    return Ok(format!("В очереди {queue} нет неразобранных задач"));
+                }
+                let count = issues.len();
+                self.issues = issues;
+                self.current = 0;
+                self.suggest().await?;
+                Ok(format!("Неразобранных задач в {queue}: {count}"))
+            }
+            TriageAction::Suggest => {
+                self.suggest().await?;
+                Ok("Подсказка получена".to_string())
+            }
+            TriageAction::Apply => {
+                let issue_key = self
+                    .current_issue()
+                    .context("Нет задачи для разбора")?
+                    .issue_key()?;
+                let suggestion = self
+                    .suggestion
+                    .take()
+                    .context("Нет подсказки для применения")?;
+                triage::apply(&self.tracker()?, &issue_key, &suggestion).await?;
+                let message = format!(
+                    "{issue_key}: тип {}, приоритет {}",
+                    suggestion.issue_type, suggestion.priority
+                );
+                self.bus.toast(ToastLevel::Info, message.clone());
+
+                self.advance();
+                if self.current_issue().is_some() {
+                    self.suggest().await?;
+                }
+                Ok(message)
+            }
+        }
+    }
+
+    async fn suggest(&mut self) -> Result<()> {
+        let issue = self.current_issue().context("Нет задачи для разбора")?;
+        let suggestion = triage::suggest(&self.llm()?, issue).await?;
+        self.suggestion = Some(suggestion);
+        Ok(())
+    }
+}
+
+/// Изменения полей задачи, которые внесёт применение подсказки
+fn suggestion_changes(issue: &Issue, suggestion: &TriageSuggestion) -> Vec<FieldChange> {
+    let mut tags = issue.tags.clone();
+    for tag in &suggestion.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    let join = |tags: &[String]| (!tags.is_empty()).then(|| tags.join(", "));
+
+    vec![
+        FieldChange::new(
+            "Тип",
+            issue.issue_type.as_ref().and_then(|t| t.key.clone()),
+            Some(suggestion.issue_type.clone()),
+        ),
+        FieldChange::new(
+            "Приоритет",
+            issue.priority.as_ref().and_then(|p| p.key.clone()),
+            Some(suggestion.priority.clone()),
+        ),
+        FieldChange::new("Теги", join(&issue.tags), join(&tags)),
+    ]
+}
+
+impl Screen for TriageScreen {
+    fn meta(&self) -> ScreenMeta {
+        ScreenMeta {
+            id: ScreenId::Triage,
+            title: "Triage",
+            label: "Triage",
+            shortcut: '4',
+            requires: &[Service::Tracker, Service::Llm],
+        }
+    }
+
+    fn input_title(&self) -> &'static str {
+        if self.issues.is_empty() {
+            "Triage: введите ключ очереди и нажмите Enter"
+        } else {
+            "Triage: Enter/y принять, n пропустить, t тип, p приоритет, r заново, g другая очередь"
+        }
+    }
+
+    fn input_text(&self) -> &str {
+        &self.input
+    }
+
+    fn output_text(&self) -> String {
+        let Some(issue) = self.current_issue() else {
+            return self.log.join("\n\n");
+        };
+        let suggestion = self.suggestion.as_ref().map_or_else(
+            || "Подсказки нет (r — запросить)".to_string(),
+            |s| s.describe(),
+        );
+        format!(
+            "{}\n\n## Предложение LLM ({}/{})\n{suggestion}",
+            format_issue_output(issue),
+            self.current + 1,
+            self.issues.len()
+        )
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ScreenEvent {
+        if !self.issues.is_empty() {
+            return self.handle_decision_key(key);
+        }
+
+        match key.code {
+            KeyCode::Backspace => {
+                self.input.pop();
+                ScreenEvent::None
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                ScreenEvent::None
+            }
+            KeyCode::Enter => {
+                if self.input.trim().is_empty() {
+                    return ScreenEvent::None;
+                }
+                match self.input.parse::<QueueKey>() {
+                    Ok(queue) => {
+                        self.input.clear();
+                        self.submit(TriageAction::Load { queue })
+                    }
+                    Err(err) => {
+                        self.bus.toast(ToastLevel::Error, err.to_string());
+                        ScreenEvent::None
+                    }
+                }
+            }
+            _ => ScreenEvent::None,
+        }
+    }
+
+    fn push_output(&mut self, text: String) {
+        self.push_log(text);
+    }
+
+    fn list_text(&self) -> Option<String> {
+        if self.issues.is_empty() {
+            return None;
+        }
+        let lines: Vec<String> = self
+            .issues
+            .iter()
+            .enumerate()
+            .map(|(index, issue)| {
+                let marker = match index.cmp(&self.current) {
+                    std::cmp::Ordering::Less => "✓",
+                    std::cmp::Ordering::Equal => ">",
+                    std::cmp::Ordering::Greater => " ",
+                };
+                format!("{marker} {}  {}", issue.key, issue.summary)
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    fn renders_markdown(&self) -> bool {
+        true
+    }
+
+    fn popup(&self) -> Option<Popup> {
+        if !self.confirming {
+            return None;
+        }
+        let issue = self.current_issue()?;
+        let suggestion = self.suggestion.as_ref()?;
+        Some(Popup {
+            title: format!("Применить разбор {}?", issue.key),
+            body: diff::render(&suggestion_changes(issue, suggestion)),
+        })
+    }
+
+    fn command_preview(&self, input: &str) -> String {
+        match &self.pending {
+            Some(TriageAction::Load { .. }) => format!("> tracker triage {input}"),
+            Some(TriageAction::Suggest) => format!("> triage suggest {input}"),
+            Some(TriageAction::Apply) => format!("> triage apply {input}"),
+            None => format!("> triage {input}"),
+        }
+    }
+
+    fn execute<'a>(&'a mut self, input: String) -> Pin<Box<dyn Future<Output = String> + 'a>> {
+        Box::pin(async move {
+            let action = match self.pending.take() {
+                Some(action) => action,
+                None => match input.parse::<QueueKey>() {
+                    Ok(queue) => TriageAction::Load { queue },
+                    Err(err) => return format!("Ошибка Triage: {err}"),
+                },
+            };
+            match self.run(action).await {
+                Ok(output) => output,
+                Err(err) => {
+                    let message = format!("Ошибка Triage: {err}");
+                    self.bus.toast(ToastLevel::Error, message.clone());
+                    message
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_suggestion_changes_merge_tags() {
+        let issue = testing::issue("TREK-1")
+            .summary("Падает экспорт")
+            .priority("normal")
+            .tags(&["export"])
+            .build();
+        let suggestion = TriageSuggestion {
+            issue_type: "bug".to_string(),
+            priority: "critical".to_string(),
+            tags: vec!["export".to_string(), "crash".to_string()],
+            reason: String::new(),
+        };
+
+        let changes = suggestion_changes(&issue, &suggestion);
+
+        assert_eq!(changes[0].old, None);
+        assert_eq!(changes[0].new.as_deref(), Some("bug"));
+        assert_eq!(changes[1].old.as_deref(), Some("normal"));
+        assert_eq!(changes[2].new.as_deref(), Some("export, crash"));
+    }
+}
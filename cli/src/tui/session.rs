@@ -0,0 +1,151 @@
+//! Сохранение состояния TUI между запусками
+//!
+//! Состояние пишется в `$XDG_STATE_HOME/you/tui_state.json`
+//! (по умолчанию `~/.local/state/you/tui_state.json`). В нём черновики и вывод
+//! экранов, поэтому на Unix файл доступен только владельцу (0600).
+
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+
+use super::screens::{ScreenId, ScreenSnapshot};
+use crate::paths;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Экран, открытый при выходе; None — главный экран
+    pub active_screen: Option<ScreenId>,
+    pub screens: HashMap<ScreenId, SavedScreen>,
+    /// Ширина левой панели, выставленная через Ctrl+←/→
+    pub list_width: Option<u16>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedScreen {
+    pub snapshot: ScreenSnapshot,
+    pub scroll_offset: usize,
+    pub search: Option<String>,
+}
+
+impl SessionState {
+    /// Загружает сохранённое состояние; повреждённый или отсутствующий файл даёт пустое состояние
+    #[instrument]
+    pub fn load() -> Self {
+        let Some(path) = state_path() else {
+            return Self::default();
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "Состояние TUI не найдено");
+                return Self::default();
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(state) => {
+                info!(path = %path.display(), "Состояние TUI восстановлено");
+                state
+            }
+            Err(err) => {
+                warn!(path = %path.display(), error = %err, "Не удалось разобрать состояние TUI");
+                Self::default()
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub fn save(&self) -> Result<()> {
+        let path = state_path().context("Не удалось определить путь для состояния TUI")?;
+        self.save_to(&path)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        write_private(path, content.as_bytes())
+            .with_context(|| format!("Не удалось записать {}", path.display()))?;
+        info!(path = %path.display(), "Состояние TUI сохранено");
+        Ok(())
+    }
+}
+
+/// Перезаписывает файл с правами только для владельца
+///
+/// Права выставляются и у файла, созданного прежними версиями с правами по умолчанию.
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(content)
+}
+
+fn state_path() -> Option<PathBuf> {
+    Some(paths::state_dir()?.join("tui_state.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_state_roundtrip() {
+        let mut state = SessionState {
+            active_screen: Some(ScreenId::Llm),
+            ..Default::default()
+        };
+        state.screens.insert(
+            ScreenId::Llm,
+            SavedScreen {
+                snapshot: ScreenSnapshot {
+                    input: "черновик".to_string(),
+                    output: vec!["> llm ask привет".to_string(), "Привет!".to_string()],
+                    selected_issue: None,
+                },
+                scroll_offset: 3,
+                search: Some("привет".to_string()),
+            },
+        );
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: SessionState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.active_screen, Some(ScreenId::Llm));
+        let llm = &restored.screens[&ScreenId::Llm];
+        assert_eq!(llm.snapshot.output.len(), 2);
+        assert_eq!(llm.scroll_offset, 3);
+        assert_eq!(llm.search.as_deref(), Some("привет"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_state_file_is_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tui_state.json");
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        SessionState::default().save_to(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("screens"));
+    }
+}
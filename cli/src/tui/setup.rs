@@ -0,0 +1,265 @@
+//! Экран настройки: объясняет, чего не хватает клиентам, и сохраняет токены в keyring
+
+use core_lib::credentials;
+use crossterm::event::{KeyCode, KeyEvent};
+use llm_lib::{LlmClient, LlmConfig};
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+
+use super::{screens::ScreenId, theme::Theme};
+use crate::{
+    llm::DEFAULT_MODEL,
+    secrets::{self, Secret},
+    settings,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    Tracker,
+    Llm,
+}
+
+impl Service {
+    pub const ALL: [Service; 2] = [Service::Tracker, Service::Llm];
+
+    fn name(self) -> &'static str {
+        match self {
+            Service::Tracker => "Tracker",
+            Service::Llm => "LLM",
+        }
+    }
+
+    fn secret(self) -> Secret {
+        match self {
+            Service::Tracker => Secret::TrackerToken,
+            Service::Llm => Secret::OpenRouterToken,
+        }
+    }
+
+    /// Пробует создать клиент; возвращает описание ошибки инициализации
+    fn check(self) -> Option<String> {
+        let result = match self {
+            Service::Tracker => settings::tracker_client()
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Service::Llm => LlmConfig::new(DEFAULT_MODEL)
+                .and_then(LlmClient::new)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        };
+        result.err()
+    }
+}
+
+struct Problem {
+    service: Service,
+    message: String,
+    /// Токен, ввод которого устранит проблему; None — нужна ручная настройка окружения
+    missing_secret: Option<Secret>,
+}
+
+pub enum SetupAction {
+    None,
+    /// Всё настроено: вернуться на экран, с которого пришли
+    Done(Option<ScreenId>),
+}
+
+pub struct SetupScreen {
+    services: Vec<Service>,
+    problems: Vec<Problem>,
+    selected: usize,
+    editing: Option<String>,
+    status: Option<String>,
+    return_to: Option<ScreenId>,
+    /// Клиент Трекера передан приложению явно, окружение для него не проверяется
+    tracker_provided: bool,
+}
+
+impl SetupScreen {
+    pub fn new() -> Self {
+        Self {
+            services: Vec::new(),
+            problems: Vec::new(),
+            selected: 0,
+            editing: None,
+            status: None,
+            return_to: None,
+            tracker_provided: false,
+        }
+    }
+
+    /// Трекер настроен явно переданным клиентом
+    #[cfg(test)]
+    pub fn set_tracker_provided(&mut self) {
+        self.tracker_provided = true;
+    }
+
+    /// Проверяет сервисы; true, если что-то не настроено и нужен экран настройки
+    pub fn check(&mut self, services: &[Service], return_to: Option<ScreenId>) -> bool {
+        self.services = services.to_vec();
+        self.return_to = return_to;
+        self.recheck();
+        !self.problems.is_empty()
+    }
+
+    fn recheck(&mut self) {
+        self.problems = self
+            .services
+            .iter()
+            .filter(|&&service| !(service == Service::Tracker && self.tracker_provided))
+            .filter_map(|&service| {
+                let message = service.check()?;
+                let secret = service.secret();
+                let missing_secret = credentials::resolve(secret.env_var())
+                    .is_none()
+                    .then_some(secret);
+                Some(Problem {
+                    service,
+                    message,
+                    missing_secret,
+                })
+            })
+            .collect();
+        self.selected = self.selected.min(self.problems.len().saturating_sub(1));
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SetupAction {
+        if let Some(value) = self.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.editing = None,
+                KeyCode::Backspace => {
+                    value.pop();
+                }
+                KeyCode::Char(c) => value.push(c),
+                KeyCode::Enter => return self.save_token(),
+                _ => {}
+            }
+            return SetupAction::None;
+        }
+
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.problems.len() => self.selected += 1,
+            KeyCode::Enter => {
+                let fixable = self
+                    .problems
+                    .get(self.selected)
+                    .is_some_and(|problem| problem.missing_secret.is_some());
+                if fixable {
+                    self.editing = Some(String::new());
+                }
+            }
+            KeyCode::Char('r') => {
+                self.recheck();
+                if self.problems.is_empty() {
+                    return SetupAction::Done(self.return_to);
+                }
+            }
+            _ => {}
+        }
+        SetupAction::None
+    }
+
+    fn save_token(&mut self) -> SetupAction {
+        let value = self.editing.take().unwrap_or_default();
+        let value = value.trim();
+        let Some(secret) = self
+            .problems
+            .get(self.selected)
+            .and_then(|problem| problem.missing_secret)
+        else {
+            return SetupAction::None;
+        };
+        if value.is_empty() {
+            return SetupAction::None;
+        }
+
+        self.status = Some(match secrets::store(secret, value) {
+            Ok(()) => format!("{} сохранён", secret.env_var()),
+            Err(err) => format!("{err:#}"),
+        });
+        self.recheck();
+        if self.problems.is_empty() {
+            SetupAction::Done(self.return_to)
+        } else {
+            SetupAction::None
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        "Настройка"
+    }
+
+    pub fn input_title(&self) -> &'static str {
+        if self.is_editing() {
+            "Токен: Enter — сохранить в keyring, Esc — отмена"
+        } else {
+            "Настройка: ↑/↓ выбор, Enter — ввести токен, r — проверить снова, Esc — на главную"
+        }
+    }
+
+    /// Вводимый токен не показывается на экране
+    pub fn input_text(&self) -> String {
+        self.editing
+            .as_ref()
+            .map(|value| "•".repeat(value.chars().count()))
+            .unwrap_or_default()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let mut lines = vec![
+            Line::styled("Не удалось инициализировать клиенты:", theme.title),
+            Line::default(),
+        ];
+        for (index, problem) in self.problems.iter().enumerate() {
+            let style = if index == self.selected {
+                theme.selection
+            } else {
+                theme.text
+            };
+            lines.push(Line::styled(
+                format!("{}: {}", problem.service.name(), problem.message),
+                style,
+            ));
+            let hint = match problem.missing_secret {
+                Some(secret) => format!(
+                    "  Enter — ввести {} (будет сохранён в системном keyring)",
+                    secret.env_var()
+                ),
+                None => {
+                    "  Исправьте окружение (например, WORK_PROXY в формате host:port) и нажмите r"
+                        .to_string()
+                }
+            };
+            lines.push(Line::styled(hint, theme.code_comment));
+            lines.push(Line::default());
+        }
+        if let Some(status) = &self.status {
+            lines.push(Line::styled(status.clone(), theme.text));
+        }
+
+        let widget = Paragraph::new(lines)
+            .block(theme.block("Настройка"))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(widget, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_input_is_masked() {
+        let mut setup = SetupScreen::new();
+        setup.editing = Some("secret".to_string());
+        assert_eq!(setup.input_text(), "••••••");
+        assert!(setup.is_editing());
+    }
+}
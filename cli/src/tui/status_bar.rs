@@ -0,0 +1,180 @@
+use chrono::{DateTime, Local};
+use llm_lib::events::LlmEvent;
+use ratatui::{prelude::*, widgets::Paragraph};
+use tracker_lib::events::TrackerEvent;
+
+use super::theme::Theme;
+
+/// Нижняя строка состояния: организация, модель, активные запросы и лимиты API
+pub struct StatusBar {
+    org: Option<String>,
+    model: String,
+    pending: usize,
+    last_sync: Option<DateTime<Local>>,
+    tracker_budget: Option<Budget>,
+    llm_budget: Option<Budget>,
+    focus: Option<String>,
+    read_only: bool,
+    sandbox: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+struct Budget {
+    remaining: Option<u32>,
+    limit: Option<u32>,
+    retry_after_secs: Option<u64>,
+}
+
+impl Budget {
+    fn describe(&self) -> String {
+        match (self.retry_after_secs, self.remaining, self.limit) {
+            (Some(retry), _, _) => format!("пауза {retry}с"),
+            (None, Some(remaining), Some(limit)) => format!("{remaining}/{limit}"),
+            (None, Some(remaining), None) => remaining.to_string(),
+            (None, None, _) => "?".to_string(),
+        }
+    }
+}
+
+impl StatusBar {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            org: crate::settings::org_id(),
+            model: model.into(),
+            pending: 0,
+            last_sync: None,
+            tracker_budget: None,
+            llm_budget: None,
+            focus: None,
+            read_only: crate::settings::read_only(),
+            sandbox: crate::sandbox::active_queue().map(|queue| queue.to_string()),
+        }
+    }
+
+    pub fn apply_tracker_event(&mut self, event: TrackerEvent) {
+        match event {
+            TrackerEvent::RequestStarted { .. } => self.pending += 1,
+            TrackerEvent::RequestFinished { success, .. } => {
+                self.pending = self.pending.saturating_sub(1);
+                if success {
+                    self.last_sync = Some(Local::now());
+                }
+            }
+            TrackerEvent::RateLimitUpdated(rate_limit) => {
+                self.tracker_budget = Some(Budget {
+                    remaining: rate_limit.remaining,
+                    limit: rate_limit.limit,
+                    retry_after_secs: rate_limit.retry_after_secs,
+                })
+            }
+        }
+    }
+
+    pub fn apply_llm_event(&mut self, event: LlmEvent) {
+        match event {
+            LlmEvent::RequestStarted { .. } => self.pending += 1,
+            LlmEvent::RequestFinished { model, .. } => {
+                self.pending = self.pending.saturating_sub(1);
+                self.model = model;
+            }
+            LlmEvent::RateLimitUpdated(rate_limit) => {
+                self.llm_budget = Some(Budget {
+                    remaining: rate_limit.remaining,
+                    limit: rate_limit.limit,
+                    retry_after_secs: rate_limit.retry_after_secs,
+                })
+            }
+            // Остаток лимита относился к прежнему ключу
+            LlmEvent::KeyRotated { .. } => self.llm_budget = None,
+        }
+    }
+
+    /// Состояние фокус-таймера; None скрывает его из строки
+    pub fn set_focus(&mut self, focus: Option<String>) {
+        self.focus = focus;
+    }
+
+    pub fn text(&self) -> String {
+        let org = self.org.as_deref().unwrap_or("—");
+        let sync = self
+            .last_sync
+            .map(|time| time.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "—".to_string());
+        let budget = |budget: &Option<Budget>| {
+            budget
+                .as_ref()
+                .map(Budget::describe)
+                .unwrap_or_else(|| "—".to_string())
+        };
+
+        let focus = self
+            .focus
+            .as_ref()
+            .map(|focus| format!(" {focus} |"))
+            .unwrap_or_default();
+
+        let read_only = if self.read_only {
+            " | только чтение"
+        } else {
+            ""
+        };
+
+        // Песочница открывает строку, чтобы её нельзя было не заметить
+        let sandbox = self
+            .sandbox
+            .as_ref()
+            .map(|queue| format!(" ПЕСОЧНИЦА {queue} |"))
+            .unwrap_or_default();
+
+        format!(
+            "{sandbox}{focus} org: {org} | модель: {} | задачи: {} | синхр.: {sync} | лимит Tracker: {} | лимит LLM: {}{read_only}",
+            self.model,
+            self.pending,
+            budget(&self.tracker_budget),
+            budget(&self.llm_budget),
+        )
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Paragraph::new(self.text()).style(theme.selection), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracker_lib::events::RateLimit;
+
+    use super::*;
+
+    #[test]
+    fn test_status_bar_tracks_pending_and_budget() {
+        let mut status_bar = StatusBar::new("test-model");
+        status_bar.apply_tracker_event(TrackerEvent::RequestStarted {
+            path: "issues/TEST-1".to_string(),
+        });
+        assert!(status_bar.text().contains("задачи: 1"));
+
+        status_bar.apply_tracker_event(TrackerEvent::RateLimitUpdated(RateLimit {
+            limit: Some(100),
+            remaining: Some(42),
+            retry_after_secs: None,
+        }));
+        status_bar.apply_tracker_event(TrackerEvent::RequestFinished {
+            path: "issues/TEST-1".to_string(),
+            success: true,
+        });
+
+        let text = status_bar.text();
+        assert!(text.contains("задачи: 0"));
+        assert!(text.contains("лимит Tracker: 42/100"));
+        assert!(!text.contains("синхр.: —"));
+
+        status_bar.set_focus(Some("🍅 TEST-1 24:59".to_string()));
+        assert!(status_bar.text().starts_with(" 🍅 TEST-1 24:59 | org:"));
+
+        status_bar.sandbox = Some("SANDBOX".to_string());
+        assert!(status_bar
+            .text()
+            .starts_with(" ПЕСОЧНИЦА SANDBOX | 🍅 TEST-1 24:59 | org:"));
+    }
+}
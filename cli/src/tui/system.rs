@@ -0,0 +1,38 @@
+//! Взаимодействие с окружением: браузер и буфер обмена
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// Открывает ссылку в приложении по умолчанию
+pub fn open_url(url: &str) -> io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(url).spawn().map(|_| ())
+}
+
+/// Копирует текст в системный буфер обмена через pbcopy, wl-copy или xclip
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("pbcopy", &[])
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait().map(|_| ())
+}
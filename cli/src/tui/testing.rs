@@ -0,0 +1,214 @@
+//! Тестовый стенд TUI: приложение на TestBackend, сценарий клавиш и снимки экрана
+//!
+//! Снимки лежат в `src/tui/snapshots`; чтобы записать новые или перезаписать
+//! изменившиеся, запустите тесты с `UPDATE_SNAPSHOTS=1`. Клиента Трекера стенд
+//! получает явно ([`TuiHarness::with_tracker`]), а не из переменных окружения, поэтому
+//! тесты можно запускать параллельно.
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use tracker_lib::{TrackerClient, TrackerConfig};
+
+use crate::notify::{Notifier, NotifyConfig};
+
+use super::{
+    app::App,
+    bus::AppEvent,
+    layout::{LayoutMode, SplitLayout},
+    theme::Theme,
+    DEFAULT_FOCUS_MINUTES,
+};
+
+/// Сколько раз `wait_for` перерисовывает экран, прежде чем сдаться
+const WAIT_ATTEMPTS: usize = 100;
+const WAIT_STEP: Duration = Duration::from_millis(20);
+
+pub struct TuiHarness {
+    app: App,
+    terminal: Terminal<TestBackend>,
+}
+
+impl TuiHarness {
+    /// Приложение с темой и раскладкой по умолчанию, без фонового автообновления
+    pub fn new(width: u16, height: u16) -> Self {
+        let app = App::new(
+            Theme::default(),
+            SplitLayout::new(LayoutMode::default(), None),
+            None,
+            Duration::from_secs(DEFAULT_FOCUS_MINUTES * 60),
+            // Тесты не показывают уведомлений и не звенят
+            Notifier::new(&NotifyConfig {
+                desktop: false,
+                bell: false,
+                sinks: Vec::new(),
+            }),
+        );
+        let terminal = Terminal::new(TestBackend::new(width, height))
+            .expect("TestBackend не возвращает ошибок");
+        Self { app, terminal }
+    }
+
+    /// Экран Tracker обращается к Трекеру по адресу `base_url` (обычно wiremock)
+    pub fn with_tracker(mut self, base_url: &str) -> Self {
+        let config = TrackerConfig::new("test-oauth-token").with_base_url(base_url);
+        let client = TrackerClient::new(config).expect("клиент Трекера создаётся");
+        self.app.set_tracker_client(client);
+        self
+    }
+
+    pub async fn press(&mut self, code: KeyCode) {
+        self.press_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .await;
+    }
+
+    pub async fn press_key(&mut self, key: KeyEvent) {
+        let quit = self
+            .app
+            .update(AppEvent::Key(key))
+            .await
+            .expect("клавиши не завершаются ошибкой");
+        assert!(!quit, "приложение завершилось по клавише {key:?}");
+        self.settle().await;
+    }
+
+    /// Вводит текст посимвольно, как с клавиатуры
+    pub async fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c)).await;
+        }
+    }
+
+    /// Разбирает события фоновых задач, уже попавшие в шину
+    pub async fn settle(&mut self) {
+        self.app
+            .process_pending()
+            .await
+            .expect("события шины не завершаются ошибкой");
+    }
+
+    /// Текст экрана построчно, без хвостовых пробелов
+    pub fn render(&mut self) -> String {
+        self.terminal
+            .draw(|frame| self.app.draw(frame))
+            .expect("TestBackend не возвращает ошибок");
+        let buffer = self.terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(usize::from(buffer.area.width))
+            .map(|row| {
+                let line: String = row.iter().map(|cell| cell.symbol()).collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Ждёт, пока фоновые задачи не выведут `needle` на экран
+    pub async fn wait_for(&mut self, needle: &str) -> String {
+        for _ in 0..WAIT_ATTEMPTS {
+            self.settle().await;
+            let screen = self.render();
+            if screen.contains(needle) {
+                return screen;
+            }
+            tokio::time::sleep(WAIT_STEP).await;
+        }
+        panic!(
+            "на экране так и не появилось {needle:?}:\n{}",
+            self.render()
+        );
+    }
+}
+
+/// Сравнивает экран со снимком; строка состояния с временем синхронизации не учитывается
+///
+/// Снимок без файла — ошибка: новый снимок записывается только с `UPDATE_SNAPSHOTS=1`
+/// и коммитится вместе с тестом.
+pub fn assert_snapshot(name: &str, screen: &str) {
+    let screen = match screen.rsplit_once('\n') {
+        Some((body, _status_bar)) => body,
+        None => screen,
+    };
+    let path = snapshot_path(name);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().expect("у снимка есть каталог"))
+            .expect("каталог снимков создаётся");
+        fs::write(&path, format!("{screen}\n")).expect("снимок записывается");
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "нет снимка {}; UPDATE_SNAPSHOTS=1 запишет его. Экран:\n{screen}",
+            path.display()
+        )
+    });
+    assert_eq!(
+        screen,
+        expected.trim_end_matches('\n'),
+        "экран разошёлся со снимком {}; UPDATE_SNAPSHOTS=1 перезапишет его",
+        path.display()
+    );
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tui/snapshots")
+        .join(format!("{name}.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dashboard_lists_registered_screens() {
+        let mut tui = TuiHarness::new(100, 30);
+
+        let screen = tui.render();
+
+        assert!(screen.contains("Режим: Главная"));
+        assert!(screen.contains("1-Tracker, 2-LLM"));
+        assert_snapshot("dashboard", &screen);
+    }
+
+    #[tokio::test]
+    async fn test_tracker_loads_issue_and_escape_returns_home() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/issues/TREK-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "TREK-1",
+                "summary": "Падает сборка на CI",
+                "description": "Логи во вложении",
+                "status": {"key": "open", "display": "Открыт"}
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&mock_server)
+            .await;
+
+        let mut tui = TuiHarness::new(100, 30).with_tracker(&mock_server.uri());
+        tui.press(KeyCode::Char('1')).await;
+        tui.type_text("TREK-1").await;
+        tui.press(KeyCode::Enter).await;
+
+        let screen = tui.wait_for("Падает сборка на CI").await;
+        assert!(screen.contains("Режим: Tracker"));
+        assert_snapshot("tracker_issue", &screen);
+
+        tui.press(KeyCode::Esc).await;
+        assert!(tui.render().contains("Режим: Главная"));
+    }
+}
@@ -0,0 +1,283 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders},
+};
+use serde::Deserialize;
+
+use crate::config::TuiConfig;
+use crate::render::StatusKind;
+
+/// Цвета пользовательской темы; незаданные поля берутся из базовой темы
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Базовая тема: dark (по умолчанию) или light
+    pub base: Option<String>,
+    pub text: Option<String>,
+    pub border: Option<String>,
+    pub title: Option<String>,
+    pub selection: Option<String>,
+    pub search_match: Option<String>,
+    pub status_open: Option<String>,
+    pub status_in_progress: Option<String>,
+    pub status_done: Option<String>,
+    pub code: Option<String>,
+    pub code_keyword: Option<String>,
+    pub code_string: Option<String>,
+    pub code_comment: Option<String>,
+    pub diff_added: Option<String>,
+    pub diff_removed: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub text: Style,
+    pub border: Style,
+    pub title: Style,
+    pub selection: Style,
+    pub search_match: Style,
+    pub status_open: Style,
+    pub status_in_progress: Style,
+    pub status_done: Style,
+    pub code: Style,
+    pub code_keyword: Style,
+    pub code_string: Style,
+    pub code_comment: Style,
+    pub diff_added: Style,
+    pub diff_removed: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            text: Style::default().fg(Color::Gray),
+            border: Style::default().fg(Color::DarkGray),
+            title: Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+            selection: Style::default().bg(Color::DarkGray).fg(Color::White),
+            search_match: Style::default().bg(Color::Yellow).fg(Color::Black),
+            status_open: Style::default().fg(Color::LightBlue),
+            status_in_progress: Style::default().fg(Color::Yellow),
+            status_done: Style::default().fg(Color::Green),
+            code: Style::default().fg(Color::White).bg(Color::Rgb(40, 40, 40)),
+            code_keyword: Style::default().fg(Color::Magenta),
+            code_string: Style::default().fg(Color::Green),
+            code_comment: Style::default().fg(Color::DarkGray),
+            diff_added: Style::default().fg(Color::Green),
+            diff_removed: Style::default().fg(Color::Red),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            text: Style::default().fg(Color::Black),
+            border: Style::default().fg(Color::Gray),
+            title: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            selection: Style::default().bg(Color::LightBlue).fg(Color::Black),
+            search_match: Style::default().bg(Color::LightYellow).fg(Color::Black),
+            status_open: Style::default().fg(Color::Blue),
+            status_in_progress: Style::default().fg(Color::Rgb(180, 120, 0)),
+            status_done: Style::default().fg(Color::Rgb(0, 128, 0)),
+            code: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Rgb(235, 235, 235)),
+            code_keyword: Style::default().fg(Color::Rgb(160, 0, 160)),
+            code_string: Style::default().fg(Color::Rgb(0, 120, 0)),
+            code_comment: Style::default().fg(Color::Gray),
+            diff_added: Style::default().fg(Color::Rgb(0, 128, 0)),
+            diff_removed: Style::default().fg(Color::Rgb(180, 0, 0)),
+        }
+    }
+
+    /// Выбирает тему по конфигурации; неизвестное имя темы или цвет считаются ошибкой
+    pub fn from_config(config: &TuiConfig) -> Result<Self> {
+        let name = config.theme.as_deref().unwrap_or("dark");
+        match (name, config.themes.get(name)) {
+            (_, Some(custom)) => Self::from_theme_config(custom)
+                .with_context(|| format!("Некорректная тема '{name}'")),
+            ("dark", None) => Ok(Self::dark()),
+            ("light", None) => Ok(Self::light()),
+            (_, None) => bail!("Тема '{name}' не найдена в конфигурации"),
+        }
+    }
+
+    fn from_theme_config(config: &ThemeConfig) -> Result<Self> {
+        let mut theme = match config.base.as_deref() {
+            None | Some("dark") => Self::dark(),
+            Some("light") => Self::light(),
+            Some(other) => bail!("Неизвестная базовая тема '{other}'"),
+        };
+
+        override_fg(&mut theme.text, &config.text)?;
+        override_fg(&mut theme.border, &config.border)?;
+        override_fg(&mut theme.title, &config.title)?;
+        override_bg(&mut theme.selection, &config.selection)?;
+        override_bg(&mut theme.search_match, &config.search_match)?;
+        override_fg(&mut theme.status_open, &config.status_open)?;
+        override_fg(&mut theme.status_in_progress, &config.status_in_progress)?;
+        override_fg(&mut theme.status_done, &config.status_done)?;
+        override_fg(&mut theme.code, &config.code)?;
+        override_fg(&mut theme.code_keyword, &config.code_keyword)?;
+        override_fg(&mut theme.code_string, &config.code_string)?;
+        override_fg(&mut theme.code_comment, &config.code_comment)?;
+        override_fg(&mut theme.diff_added, &config.diff_added)?;
+        override_fg(&mut theme.diff_removed, &config.diff_removed)?;
+
+        Ok(theme)
+    }
+
+    pub fn block<'a>(&self, title: impl Into<Line<'a>>) -> Block<'a> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.border)
+            .title(title.into().style(self.title))
+    }
+
+    /// Цвет статуса задачи по его отображаемому названию
+    pub fn status_style(&self, status: &str) -> Style {
+        match StatusKind::of(status) {
+            StatusKind::Done => self.status_done,
+            StatusKind::InProgress => self.status_in_progress,
+            StatusKind::Open => self.status_open,
+            StatusKind::Other => self.text,
+        }
+    }
+
+    /// Подсвечивает строку кода: ключевые слова, строковые литералы и комментарии
+    pub fn highlight_code<'a>(&self, line: &'a str) -> Line<'a> {
+        let mut spans = Vec::new();
+        let mut rest = line;
+
+        while !rest.is_empty() {
+            if rest.starts_with("//") || rest.starts_with('#') {
+                spans.push(Span::styled(rest, self.code_comment));
+                break;
+            }
+
+            if let Some(after_quote) = rest.strip_prefix('"') {
+                let end = after_quote.find('"').map_or(rest.len(), |index| index + 2);
+                spans.push(Span::styled(&rest[..end], self.code_string));
+                rest = &rest[end..];
+                continue;
+            }
+
+            let word_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            if word_len > 0 {
+                let word = &rest[..word_len];
+                let style = if CODE_KEYWORDS.contains(&word) {
+                    self.code_keyword
+                } else {
+                    self.code
+                };
+                spans.push(Span::styled(word, style));
+                rest = &rest[word_len..];
+                continue;
+            }
+
+            let symbol_len = rest.chars().next().map_or(1, char::len_utf8);
+            spans.push(Span::styled(&rest[..symbol_len], self.code));
+            rest = &rest[symbol_len..];
+        }
+
+        Line::from(spans)
+    }
+}
+
+const CODE_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "class", "const", "continue", "def", "else", "enum", "false",
+    "fn", "for", "func", "function", "if", "impl", "import", "in", "let", "loop", "match", "mod",
+    "mut", "pub", "return", "self", "static", "struct", "trait", "true", "type", "use", "var",
+    "where", "while",
+];
+
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| anyhow::anyhow!("Некорректный цвет '{value}'"))
+}
+
+fn override_fg(style: &mut Style, value: &Option<String>) -> Result<()> {
+    if let Some(value) = value {
+        *style = style.fg(parse_color(value)?);
+    }
+    Ok(())
+}
+
+fn override_bg(style: &mut Style, value: &Option<String>) -> Result<()> {
+    if let Some(value) = value {
+        *style = style.bg(parse_color(value)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_custom_theme_overrides_base() {
+        let config = TuiConfig {
+            theme: Some("mine".to_string()),
+            themes: HashMap::from([(
+                "mine".to_string(),
+                ThemeConfig {
+                    base: Some("light".to_string()),
+                    status_done: Some("#00ff00".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.status_done.fg, Some(Color::Rgb(0, 255, 0)));
+        assert_eq!(theme.text.fg, Theme::light().text.fg);
+    }
+
+    #[test]
+    fn test_unknown_theme_is_error() {
+        let config = TuiConfig {
+            theme: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(Theme::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_status_style() {
+        let theme = Theme::dark();
+        assert_eq!(theme.status_style("В работе"), theme.status_in_progress);
+        assert_eq!(theme.status_style("Закрыт"), theme.status_done);
+        assert_eq!(theme.status_style("Открыт"), theme.status_open);
+    }
+
+    #[test]
+    fn test_highlight_code_spans() {
+        let theme = Theme::dark();
+        let line = theme.highlight_code("let x = \"hi\"; // note");
+        let keyword = &line.spans[0];
+        assert_eq!(keyword.content, "let");
+        assert_eq!(keyword.style, theme.code_keyword);
+        let string = line
+            .spans
+            .iter()
+            .find(|span| span.content == "\"hi\"")
+            .unwrap();
+        assert_eq!(string.style, theme.code_string);
+        assert_eq!(line.spans.last().unwrap().style, theme.code_comment);
+    }
+}
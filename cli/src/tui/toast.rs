@@ -0,0 +1,106 @@
+//! Всплывающие уведомления в правом верхнем углу
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Clear, Paragraph, Wrap},
+};
+
+use super::theme::Theme;
+
+const TOAST_TTL: Duration = Duration::from_secs(5);
+const MAX_TOASTS: usize = 3;
+const TOAST_WIDTH: u16 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+struct Toast {
+    level: ToastLevel,
+    text: String,
+    shown_at: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    items: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, text: String) {
+        self.items.push_back(Toast {
+            level,
+            text,
+            shown_at: Instant::now(),
+        });
+        if self.items.len() > MAX_TOASTS {
+            self.items.pop_front();
+        }
+    }
+
+    /// Убирает уведомления, показанные дольше `TOAST_TTL`
+    pub fn expire(&mut self, now: Instant) {
+        self.items
+            .retain(|toast| now.duration_since(toast.shown_at) < TOAST_TTL);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let width = TOAST_WIDTH.min(area.width);
+        let mut y = area.y;
+
+        for toast in &self.items {
+            let (title, style) = match toast.level {
+                ToastLevel::Info => ("Готово", theme.status_done),
+                ToastLevel::Warning => ("Внимание", theme.status_in_progress),
+                ToastLevel::Error => ("Ошибка", theme.search_match),
+            };
+            let text_width = usize::from(width.saturating_sub(2).max(1));
+            let rows = Line::from(toast.text.as_str())
+                .width()
+                .div_ceil(text_width)
+                .max(1);
+            let height = u16::try_from(rows).unwrap_or(u16::MAX).saturating_add(2);
+            if y + height > area.bottom() {
+                break;
+            }
+
+            let toast_area = Rect::new(area.right() - width, y, width, height);
+            let widget = Paragraph::new(toast.text.as_str())
+                .style(theme.text)
+                .block(theme.block(title).border_style(style))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(widget, toast_area);
+            y += height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_expire_and_are_bounded() {
+        let mut toasts = Toasts::default();
+        for index in 0..MAX_TOASTS + 2 {
+            toasts.push(ToastLevel::Info, format!("toast {index}"));
+        }
+        assert_eq!(toasts.items.len(), MAX_TOASTS);
+        assert_eq!(toasts.items[0].text, "toast 2");
+
+        toasts.expire(Instant::now());
+        assert_eq!(toasts.items.len(), MAX_TOASTS);
+
+        toasts.expire(Instant::now() + TOAST_TTL);
+        assert!(toasts.items.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+//! Журнал сессии TUI
+//!
+//! Если в `tui.session_log` указан файл, каждая команда экрана и её результат дописываются
+//! в него с отметкой времени. Вывод экрана в памяти ограничен `tui.scrollback`, а журнал —
+//! нет, поэтому вытесненные результаты можно найти и после выхода.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use tracing::{info, warn};
+
+/// Формат отметки времени записи
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub struct SessionLog {
+    path: PathBuf,
+    file: File,
+}
+
+impl SessionLog {
+    /// Открывает журнал на дозапись и отмечает начало сессии
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Не удалось создать каталог {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Не удалось открыть журнал сессии {}", path.display()))?;
+        let mut log = Self {
+            path: path.to_path_buf(),
+            file,
+        };
+        log.append(&format!(
+            "=== Сессия {} ===\n\n",
+            Local::now().format(TIMESTAMP_FORMAT)
+        ));
+        info!(path = %path.display(), "Журнал сессии TUI включён");
+        Ok(log)
+    }
+
+    /// Дописывает вывод экрана; ошибка записи только попадает в журнал приложения
+    pub fn write(&mut self, screen: &str, text: &str) {
+        let entry = format!(
+            "[{}] {screen}\n{}\n\n",
+            Local::now().format(TIMESTAMP_FORMAT),
+            text.trim_end()
+        );
+        self.append(&entry);
+    }
+
+    fn append(&mut self, text: &str) {
+        if let Err(err) = self.file.write_all(text.as_bytes()) {
+            warn!(path = %self.path.display(), error = %err, "Не удалось дописать журнал сессии");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_log_appends_entries() {
+        let dir = std::env::temp_dir().join(format!("you-session-log-{}", std::process::id()));
+        let path = dir.join("logs").join("session.log");
+
+        let mut log = SessionLog::open(&path).unwrap();
+        log.write("LLM", "> llm ask привет");
+        log.write("LLM", "Здравствуйте!\n");
+        drop(log);
+        SessionLog::open(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("=== Сессия ").count(), 2);
+        assert!(content.contains("] LLM\n> llm ask привет\n\n"));
+        assert!(content.contains("] LLM\nЗдравствуйте!\n\n=== Сессия "));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,380 @@
+//! Сквозные тесты CLI
+//!
+//! Запускают собранный бинарник `you` как отдельный процесс. Трекер и OpenRouter
+//! заменяются серверами wiremock, адреса которых передаются через `TRACKER_API_URL` и
+//! `OPEN_ROUTER_API_URL`; домашний каталог — временный, поэтому тесты не трогают
+//! конфигурацию, журналы и индекс пользователя и не требуют живых токенов.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use assert_cmd::assert::Assert;
+use assert_cmd::Command;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Переменные пользователя, которые меняют поведение команд
+const USER_ENV: &[&str] = &[
+    "TRACKER_ORG_ID",
+    "TRACKER_ORG_KIND",
+    "TRACKER_READ_ONLY",
+    "TRACKER_RECORD_DIR",
+    "TRACKER_REPLAY_DIR",
+    "CALENDAR_ICS",
+    "WORK_PROXY",
+    "RUST_LOG",
+    "CLICOLOR_FORCE",
+    "XDG_CONFIG_HOME",
+];
+
+static NEXT_HOME: AtomicUsize = AtomicUsize::new(0);
+
+/// Поддельные Трекер и OpenRouter и временный домашний каталог для запуска `you`
+struct Harness {
+    tracker: MockServer,
+    llm: MockServer,
+    home: PathBuf,
+}
+
+impl Harness {
+    async fn start() -> Self {
+        let home = std::env::temp_dir().join(format!(
+            "you-cli-tests-{}-{}",
+            std::process::id(),
+            NEXT_HOME.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&home).expect("Failed to create test home");
+
+        Self {
+            tracker: MockServer::start().await,
+            llm: MockServer::start().await,
+            home,
+        }
+    }
+
+    /// Запустить `you` с аргументами и дождаться завершения
+    ///
+    /// Процесс ждётся в отдельном потоке, чтобы серверы wiremock продолжали отвечать.
+    async fn run(&self, args: &[&str]) -> Assert {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let envs = [
+            ("TRACKER_TOKEN", "test-oauth-token".to_string()),
+            ("TRACKER_API_URL", self.tracker.uri()),
+            ("OPEN_ROUTER_TOKEN", "test-api-key".to_string()),
+            ("OPEN_ROUTER_API_URL", self.llm.uri()),
+            ("HOME", self.home.display().to_string()),
+            (
+                "XDG_STATE_HOME",
+                self.home.join("state").display().to_string(),
+            ),
+            (
+                "YOU_CONFIG",
+                self.home.join("config.yaml").display().to_string(),
+            ),
+            ("NO_COLOR", "1".to_string()),
+        ];
+
+        tokio::task::spawn_blocking(move || {
+            let mut command = Command::cargo_bin("cli").expect("CLI binary is not built");
+            for name in USER_ENV {
+                command.env_remove(name);
+            }
+            command.envs(envs).args(args).assert()
+        })
+        .await
+        .expect("CLI process panicked")
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.home);
+    }
+}
+
+fn stdout(assert: &Assert) -> String {
+    String::from_utf8_lossy(&assert.get_output().stdout).into_owned()
+}
+
+fn stderr(assert: &Assert) -> String {
+    String::from_utf8_lossy(&assert.get_output().stderr).into_owned()
+}
+
+async fn mount_issue(harness: &Harness) {
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1"))
+        .and(header("Authorization", "OAuth test-oauth-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Падает сборка на CI",
+            "description": "Логи во вложении",
+            "status": {"key": "open", "display": "Открыт"},
+            "queue": {"key": "TREK"},
+            "assignee": {"login": "ivanov"},
+            "tags": ["ci"]
+        })))
+        .mount(&harness.tracker)
+        .await;
+}
+
+#[tokio::test]
+async fn test_tracker_issue_prints_template() {
+    let harness = Harness::start().await;
+    mount_issue(&harness).await;
+
+    let assert = harness
+        .run(&["tracker", "issue", "TREK-1", "--template", "short"])
+        .await
+        .success();
+
+    assert_eq!(
+        stdout(&assert).trim(),
+        "TREK-1  [Открыт]  Падает сборка на CI"
+    );
+}
+
+#[tokio::test]
+async fn test_tracker_issue_prints_json() {
+    let harness = Harness::start().await;
+    mount_issue(&harness).await;
+
+    let assert = harness
+        .run(&["tracker", "issue", "TREK-1", "--output", "json"])
+        .await
+        .success();
+
+    let output: serde_json::Value = serde_json::from_str(&stdout(&assert)).unwrap();
+    assert_eq!(output["key"], "TREK-1");
+    assert_eq!(output["status"], "Открыт");
+    assert_eq!(output["assignee"], "ivanov");
+    assert_eq!(output["tags"], serde_json::json!(["ci"]));
+}
+
+#[tokio::test]
+async fn test_tracker_issue_not_found_fails() {
+    let harness = Harness::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-404"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "errorMessages": ["Задача не существует"]
+        })))
+        .mount(&harness.tracker)
+        .await;
+
+    let assert = harness
+        .run(&["tracker", "issue", "TREK-404"])
+        .await
+        .failure();
+
+    assert!(stdout(&assert).is_empty());
+    assert!(stderr(&assert).contains("Not Found"), "{}", stderr(&assert));
+}
+
+#[tokio::test]
+async fn test_tracker_create_posts_issue() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .and(body_partial_json(serde_json::json!({
+            "queue": "TREK",
+            "summary": "Обновить зависимости"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "key": "TREK-2",
+            "summary": "Обновить зависимости"
+        })))
+        .expect(1)
+        .mount(&harness.tracker)
+        .await;
+
+    let assert = harness
+        .run(&[
+            "tracker",
+            "create",
+            "Обновить зависимости",
+            "--queue",
+            "TREK",
+            "--no-preset",
+        ])
+        .await
+        .success();
+
+    assert_eq!(
+        stdout(&assert).trim(),
+        "Создана задача TREK-2: Обновить зависимости"
+    );
+}
+
+#[tokio::test]
+async fn test_read_only_create_fails_without_request() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&harness.tracker)
+        .await;
+
+    let assert = harness
+        .run(&[
+            "--read-only",
+            "tracker",
+            "create",
+            "Обновить зависимости",
+            "--queue",
+            "TREK",
+        ])
+        .await
+        .failure();
+
+    assert!(stderr(&assert).contains("Read-only"), "{}", stderr(&assert));
+}
+
+#[tokio::test]
+async fn test_broken_config_fails_before_request() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&harness.tracker)
+        .await;
+    // Опечатка в конфигурации не должна выключать read_only: true
+    std::fs::write(harness.home.join("config.yaml"), "read_only: [true\n")
+        .expect("Failed to write config");
+
+    let assert = harness
+        .run(&[
+            "tracker",
+            "create",
+            "Обновить зависимости",
+            "--queue",
+            "TREK",
+        ])
+        .await
+        .failure();
+
+    assert!(
+        stderr(&assert).contains("Некорректная конфигурация"),
+        "{}",
+        stderr(&assert)
+    );
+}
+
+#[tokio::test]
+async fn test_llm_ask_prints_answer() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "test-id",
+            "model": "test-model",
+            "created": 1234567890_u64,
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Rust — системный язык"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        })))
+        .expect(1)
+        .mount(&harness.llm)
+        .await;
+
+    let assert = harness
+        .run(&["llm", "ask", "Что такое Rust?", "--model", "test-model"])
+        .await
+        .success();
+
+    assert_eq!(stdout(&assert).trim(), "Rust — системный язык");
+}
+
+#[tokio::test]
+async fn test_schema_prints_json_schema() {
+    let harness = Harness::start().await;
+
+    let assert = harness.run(&["schema", "tracker-issue"]).await.success();
+
+    let schema: serde_json::Value = serde_json::from_str(&stdout(&assert)).unwrap();
+    assert_eq!(schema["title"], "you tracker issue");
+    assert_eq!(schema["type"], "object");
+}
+
+#[tokio::test]
+async fn test_unknown_command_exits_with_usage_error() {
+    let harness = Harness::start().await;
+
+    let assert = harness.run(&["tracker", "unknown"]).await.code(2);
+
+    assert!(stderr(&assert).contains("Usage"), "{}", stderr(&assert));
+}
+
+#[tokio::test]
+async fn test_comment_bulk_reports_failed_issues() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/comments"))
+        .and(body_partial_json(
+            serde_json::json!({"text": "Исправлено в 2.4"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 1,
+            "text": "Исправлено в 2.4"
+        })))
+        .expect(1)
+        .mount(&harness.tracker)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-404/comments"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&harness.tracker)
+        .await;
+    let file = harness.home.join("comments.csv");
+    std::fs::write(
+        &file,
+        "key,text\nTREK-1,Исправлено в 2.4\nTREK-404,Исправлено в 2.4\n",
+    )
+    .unwrap();
+
+    let assert = harness
+        .run(&["tracker", "comment-bulk", "--file", file.to_str().unwrap()])
+        .await
+        .failure();
+
+    let output = stdout(&assert);
+    assert!(
+        output.starts_with("Добавлено комментариев: 1 из 2"),
+        "{output}"
+    );
+    assert!(output.contains("TREK-404"), "{output}");
+}
+
+#[tokio::test]
+async fn test_explain_query_points_at_error() {
+    let harness = Harness::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "errors": {},
+            "errorMessages": ["Query syntax error at position 10: unknown field"],
+            "statusCode": 400
+        })))
+        .expect(1)
+        .mount(&harness.tracker)
+        .await;
+
+    let assert = harness
+        .run(&["tracker", "explain-query", "Queue: TR Resolvd: week()"])
+        .await
+        .failure();
+
+    let output = stderr(&assert);
+    assert!(
+        output.contains("  Queue: TR Resolvd: week()\n           ^ позиция 10"),
+        "{output}"
+    );
+    assert!(
+        output.contains("Трекер отверг запрос: ошибок 1"),
+        "{output}"
+    );
+}
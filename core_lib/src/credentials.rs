@@ -0,0 +1,56 @@
+//! Учётные данные: переменная окружения, а если её нет — системное хранилище ключей
+//!
+//! Значения хранятся в keyring под сервисом [`KEYRING_SERVICE`], а имя записи
+//! совпадает с именем переменной окружения (например, `TRACKER_TOKEN`).
+
+use keyring::Entry;
+use tracing::{debug, info, instrument, warn};
+
+use crate::{CoreError, Result};
+
+/// Имя сервиса, под которым значения сохраняются в keyring
+pub const KEYRING_SERVICE: &str = "you";
+
+/// Значение из окружения или keyring; недоступный keyring считается пустым
+#[instrument]
+pub fn resolve(name: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(name) {
+        return Some(value);
+    }
+    match from_keyring(name) {
+        Ok(value) => value,
+        Err(err) => {
+            warn!(name, error = %err, "Keyring недоступен");
+            None
+        }
+    }
+}
+
+/// То же, что [`resolve`], но отсутствие значения — ошибка конфигурации
+pub fn require(name: &str) -> Result<String> {
+    resolve(name).ok_or_else(|| {
+        CoreError::ConfigError(format!(
+            "{name} не задан: установите переменную окружения или сохраните значение в keyring"
+        ))
+    })
+}
+
+/// Значение из keyring; `None`, если записи нет
+pub fn from_keyring(name: &str) -> Result<Option<String>> {
+    match Entry::new(KEYRING_SERVICE, name).and_then(|entry| entry.get_password()) {
+        Ok(value) => {
+            debug!(name, "Значение загружено из keyring");
+            Ok(Some(value))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Сохраняет значение в keyring
+#[instrument(skip(value))]
+pub fn store(name: &str, value: &str) -> Result<()> {
+    Entry::new(KEYRING_SERVICE, name).and_then(|entry| entry.set_password(value))?;
+    info!(name, "Значение сохранено в keyring");
+    Ok(())
+}
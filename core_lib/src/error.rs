@@ -0,0 +1,11 @@
+/// Ошибки общей инфраструктуры клиентов
+#[derive(Debug, thiserror::Error)]
+pub enum CoreError {
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
+    #[error("Keyring error: {0}")]
+    Keyring(#[from] keyring::Error),
+}
+
+pub type Result<T> = std::result::Result<T, CoreError>;
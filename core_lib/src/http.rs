@@ -0,0 +1,22 @@
+//! Построение HTTP-клиентов с общими сетевыми настройками
+
+use reqwest::ClientBuilder;
+use tracing::debug;
+
+use crate::{proxy, Result};
+
+/// User-Agent по умолчанию: по нему API сервисов отличают трафик multitool
+pub const DEFAULT_USER_AGENT: &str = concat!("multitool/", env!("CARGO_PKG_VERSION"));
+
+/// Билдер HTTP-клиента с прокси из `WORK_PROXY` и User-Agent `multitool/<версия>`
+///
+/// Вызывающий может донастроить билдер (например, таймаут или свой User-Agent)
+/// перед `build()`.
+pub fn client_builder() -> Result<ClientBuilder> {
+    let mut builder = reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT);
+    if let Some(proxy) = proxy::from_env()? {
+        debug!("HTTP-клиент использует WORK_PROXY");
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder)
+}
@@ -0,0 +1,25 @@
+//! Общая инфраструктура клиентов: учётные данные, прокси и HTTP-клиент
+//!
+//! Библиотеки `tracker_lib`, `llm_lib` и `calendar_lib` получают токены и
+//! настройки через [`credentials::resolve`], а HTTP-клиенты строят через
+//! [`http::client_builder`], поэтому `WORK_PROXY` и keyring работают одинаково
+//! для всех сервисов.
+//!
+//! ## Пример
+//!
+//! ```no_run
+//! use core_lib::{credentials, http};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let token = credentials::require("TRACKER_TOKEN")?;
+//! let client = http::client_builder()?.build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod credentials;
+mod error;
+pub mod http;
+pub mod proxy;
+
+pub use error::{CoreError, Result};
@@ -0,0 +1,60 @@
+//! Прокси из переменной окружения `WORK_PROXY` (формат `host:port`, SOCKS5)
+
+use std::env::VarError;
+
+use crate::{CoreError, Result};
+
+/// Переменная окружения с адресом рабочего прокси
+pub const WORK_PROXY: &str = "WORK_PROXY";
+
+/// Прокси для всех запросов, если задан `WORK_PROXY`
+pub fn from_env() -> Result<Option<reqwest::Proxy>> {
+    let value = match std::env::var(WORK_PROXY) {
+        Ok(value) => value,
+        Err(VarError::NotPresent) => return Ok(None),
+        Err(VarError::NotUnicode(_)) => {
+            return Err(CoreError::ConfigError(
+                "Переменная окружения WORK_PROXY содержит не-UTF-8 значение".to_string(),
+            ));
+        }
+    };
+
+    let proxy_url = parse_work_proxy_url(&value)?;
+    let proxy = reqwest::Proxy::all(&proxy_url).map_err(|e| {
+        CoreError::ConfigError(format!(
+            "Некорректная конфигурация WORK_PROXY '{}': {}",
+            value, e
+        ))
+    })?;
+    Ok(Some(proxy))
+}
+
+/// Превращает `host:port` в URL SOCKS5-прокси
+pub fn parse_work_proxy_url(work_proxy_value: &str) -> Result<String> {
+    let trimmed_value = work_proxy_value.trim();
+    if trimmed_value.is_empty() {
+        return Err(CoreError::ConfigError(
+            "Переменная окружения WORK_PROXY установлена, но пуста. Ожидается формат host:port"
+                .to_string(),
+        ));
+    }
+
+    Ok(format!("socks5://{}", trimmed_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_work_proxy_url() {
+        assert_eq!(
+            parse_work_proxy_url(" proxy.local:1080 ").unwrap(),
+            "socks5://proxy.local:1080"
+        );
+        assert!(matches!(
+            parse_work_proxy_url("  "),
+            Err(CoreError::ConfigError(_))
+        ));
+    }
+}
@@ -1,10 +1,19 @@
 use crate::error::{LlmError, Result};
+use crate::events::{LlmEvent, RateLimit};
 use crate::models::{
-    ChatCompletionRequest, ChatCompletionResponse, CompletionOptions, ErrorResponse, Message,
+    ChatCompletionRequest, ChatCompletionResponse, CompletionOptions, EmbeddingRequest,
+    EmbeddingResponse, ErrorResponse, KeyInfo, KeyInfoResponse, Message, StreamChunk,
+    StreamedCompletion,
 };
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
 use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tracing::{debug, info, instrument, warn};
 
 #[cfg_attr(any(test, feature = "testing"), mockall::automock)]
@@ -23,9 +32,28 @@ pub trait LlmClientTrait {
         user_prompt: String,
     ) -> impl Future<Output = Result<String>> + Send;
 
+    /// Complete independent prompts sharing one system prompt; answers keep the prompt order
+    fn complete_batch(
+        &self,
+        system_prompt: String,
+        prompts: Vec<String>,
+    ) -> impl Future<Output = Result<Vec<String>>> + Send;
+
     fn model(&self) -> &str;
 }
 
+/// Environment variable overriding the OpenRouter API base URL
+pub const BASE_URL_VAR: &str = "OPEN_ROUTER_API_URL";
+
+/// Environment variable (or keyring entry) with extra OpenRouter keys separated by commas
+pub const FALLBACK_KEYS_VAR: &str = "OPEN_ROUTER_FALLBACK_TOKENS";
+
+/// How many batch prompts are sent to the API at the same time
+const BATCH_CONCURRENCY: usize = 4;
+
+/// Upper bound for a streamed completion; the client timeout is meant for whole responses
+const STREAM_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Debug, Clone)]
 pub struct LlmConfig {
     pub api_key: String,
@@ -34,130 +62,510 @@ pub struct LlmConfig {
     pub timeout_secs: u64,
     pub site_url: Option<String>,
     pub app_name: Option<String>,
+    /// User-Agent header, `multitool/<version>` by default
+    pub user_agent: String,
+    /// Extra keys tried in order once the current key runs out of credits
+    pub fallback_keys: Vec<String>,
 }
 
 impl LlmConfig {
     #[instrument(fields(model = %model.as_ref()))]
     pub fn new(model: impl Into<String> + AsRef<str>) -> Result<Self> {
-        let api_key = std::env::var("OPEN_ROUTER_TOKEN").map_err(|_| {
+        let api_key = core_lib::credentials::resolve("OPEN_ROUTER_TOKEN").ok_or_else(|| {
             LlmError::ConfigError("OPEN_ROUTER_TOKEN environment variable not set".to_string())
         })?;
 
-        debug!("Creating LlmConfig from environment variable");
+        debug!("Creating LlmConfig from environment variable or keyring");
+
+        // Lets tests and self-hosted gateways point the client at another server
+        let base_url = std::env::var(BASE_URL_VAR)
+            .unwrap_or_else(|_| "https://openrouter.ai/api/v1".to_string());
+
+        let fallback_keys: Vec<String> = core_lib::credentials::resolve(FALLBACK_KEYS_VAR)
+            .map(|keys| {
+                keys.split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !fallback_keys.is_empty() {
+            debug!(count = fallback_keys.len(), "Fallback API keys configured");
+        }
 
         Ok(Self {
             api_key,
-            base_url: "https://openrouter.ai/api/v1".to_string(),
+            base_url,
             model: model.into(),
             timeout_secs: 120,
             site_url: None,
             app_name: None,
+            user_agent: core_lib::http::DEFAULT_USER_AGENT.to_string(),
+            fallback_keys,
         })
     }
+
+    /// Keys to switch to, in order, when the current one runs out of credits
+    pub fn with_fallback_keys(mut self, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fallback_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Identify requests with a custom User-Agent in provider-side diagnostics
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+}
+
+/// Requests and tokens spent with one API key
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyUsage {
+    /// Last characters of the key, safe to show and log
+    pub label: String,
+    pub requests: u32,
+    pub total_tokens: u64,
+    /// The key ran out of credits and is no longer used
+    pub exhausted: bool,
+}
+
+/// API keys shared by client clones: the current one and usage per key
+#[derive(Debug)]
+struct KeyPool {
+    keys: Vec<String>,
+    current: AtomicUsize,
+    usage: Mutex<Vec<KeyUsage>>,
+}
+
+impl KeyPool {
+    fn new(config: &LlmConfig) -> Self {
+        let mut keys = vec![config.api_key.clone()];
+        for key in &config.fallback_keys {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+        let usage = keys
+            .iter()
+            .map(|key| KeyUsage {
+                label: key_label(key),
+                ..Default::default()
+            })
+            .collect();
+        Self {
+            keys,
+            current: AtomicUsize::new(0),
+            usage: Mutex::new(usage),
+        }
+    }
+
+    fn current(&self) -> (usize, &str) {
+        let index = self.current.load(Ordering::SeqCst);
+        (index, &self.keys[index])
+    }
+
+    fn usage(&self) -> std::sync::MutexGuard<'_, Vec<KeyUsage>> {
+        self.usage.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn record_request(&self, index: usize) {
+        self.usage()[index].requests += 1;
+    }
+
+    fn record_tokens(&self, index: usize, tokens: u32) {
+        self.usage()[index].total_tokens += u64::from(tokens);
+    }
+
+    /// Mark the key exhausted and switch to the next usable one; None when none is left
+    fn rotate(&self, index: usize) -> Option<usize> {
+        let mut usage = self.usage();
+        usage[index].exhausted = true;
+        let next = (index + 1..self.keys.len()).find(|&next| !usage[next].exhausted)?;
+        // Another request may have already switched further
+        let previous = self.current.fetch_max(next, Ordering::SeqCst);
+        Some(previous.max(next))
+    }
 }
 
+/// Last four characters of the key
+fn key_label(key: &str) -> String {
+    let tail: String = key
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    format!("…{tail}")
+}
+
+#[derive(Clone)]
 pub struct LlmClient {
     client: Client,
     config: LlmConfig,
+    keys: Arc<KeyPool>,
+    events: Option<UnboundedSender<LlmEvent>>,
 }
 
 impl LlmClient {
     #[instrument(skip(config), fields(model = %config.model))]
     pub fn new(config: LlmConfig) -> Result<Self> {
-        let client = Client::builder()
+        // Shared builder so WORK_PROXY applies to LLM requests as well
+        let client = core_lib::http::client_builder()?
             .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent.as_str())
             .build()
             .map_err(LlmError::RequestFailed)?;
 
         info!("Created LLM client for model: {}", config.model);
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            keys: Arc::new(KeyPool::new(&config)),
+            config,
+            events: None,
+        })
     }
-}
 
-impl LlmClientTrait for LlmClient {
-    #[instrument(skip(self, messages, options), fields(message_count = messages.len()))]
-    fn chat_completion(
+    /// Report request lifecycle and rate limit updates to the given channel
+    pub fn with_event_sender(mut self, sender: UnboundedSender<LlmEvent>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    fn emit(&self, event: LlmEvent) {
+        if let Some(sender) = &self.events {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Requests and tokens per API key, starting with the primary one
+    pub fn key_usage(&self) -> Vec<KeyUsage> {
+        self.keys.usage().clone()
+    }
+
+    async fn send_chat_completion(
         &self,
         messages: Vec<Message>,
         options: Option<CompletionOptions>,
-    ) -> impl Future<Output = Result<ChatCompletionResponse>> + Send {
-        async move {
-            if messages.is_empty() {
-                return Err(LlmError::InvalidRequest(
-                    "Messages cannot be empty".to_string(),
-                ));
+    ) -> Result<ChatCompletionResponse> {
+        if messages.is_empty() {
+            return Err(LlmError::InvalidRequest(
+                "Messages cannot be empty".to_string(),
+            ));
+        }
+
+        let request = ChatCompletionRequest {
+            model: self.config.model.clone(),
+            messages: messages.clone(),
+            options: options.unwrap_or_default(),
+            stream: false,
+        };
+
+        debug!(
+            "Sending chat completion request to {}",
+            self.config.base_url
+        );
+
+        let (key, response) = self
+            .send(|api_key| self.post("chat/completions", api_key, &request))
+            .await?;
+        let status = response.status();
+
+        debug!("Received response with status: {}", status);
+
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            self.emit(LlmEvent::RateLimitUpdated(rate_limit));
+        }
+
+        if status != StatusCode::OK {
+            return Err(Self::api_error(response).await);
+        }
+
+        let completion: ChatCompletionResponse = response.json().await?;
+        self.keys.record_tokens(key, completion.usage.total_tokens);
+        info!(
+            "Completion successful: {} tokens used",
+            completion.usage.total_tokens
+        );
+        Ok(completion)
+    }
+
+    /// Stream a chat completion, calling `on_delta` with every piece of the answer as it arrives
+    ///
+    /// The client timeout does not apply: a long generation may take much longer than a
+    /// regular request, so the stream is only limited to an hour.
+    #[instrument(skip_all, fields(message_count = messages.len()))]
+    pub async fn chat_completion_stream(
+        &self,
+        messages: Vec<Message>,
+        options: Option<CompletionOptions>,
+        mut on_delta: impl FnMut(&str),
+    ) -> Result<StreamedCompletion> {
+        if messages.is_empty() {
+            return Err(LlmError::InvalidRequest(
+                "Messages cannot be empty".to_string(),
+            ));
+        }
+
+        self.emit(LlmEvent::RequestStarted {
+            model: self.config.model.clone(),
+        });
+        let request = ChatCompletionRequest {
+            model: self.config.model.clone(),
+            messages,
+            options: options.unwrap_or_default(),
+            stream: true,
+        };
+        let result = async {
+            let (key, mut response) = self
+                .send(|api_key| {
+                    self.post("chat/completions", api_key, &request)
+                        .timeout(STREAM_TIMEOUT)
+                })
+                .await?;
+            if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                self.emit(LlmEvent::RateLimitUpdated(rate_limit));
+            }
+            if response.status() != StatusCode::OK {
+                return Err(Self::api_error(response).await);
             }
 
-            let request = ChatCompletionRequest {
-                model: self.config.model.clone(),
-                messages: messages.clone(),
-                options: options.unwrap_or_default(),
+            let mut completion = StreamedCompletion {
+                content: String::new(),
+                usage: None,
+                finish_reason: None,
             };
+            let mut buffer = Vec::new();
+            'stream: while let Some(bytes) = response.chunk().await? {
+                buffer.extend_from_slice(&bytes);
+                // Events may be split anywhere, even inside a UTF-8 character
+                while let Some(end) = buffer.iter().position(|byte| *byte == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=end).collect();
+                    let line = String::from_utf8_lossy(&line);
+                    let Some(data) = line.trim().strip_prefix("data:") else {
+                        // Blank separators and keep-alive comments
+                        continue;
+                    };
+                    let data = data.trim();
+                    if data == "[DONE]" {
+                        break 'stream;
+                    }
+                    apply_chunk(&mut completion, data, &mut on_delta)?;
+                }
+            }
 
-            debug!(
-                "Sending chat completion request to {}",
-                self.config.base_url
+            if let Some(usage) = &completion.usage {
+                self.keys.record_tokens(key, usage.total_tokens);
+            }
+            info!(
+                chars = completion.content.len(),
+                "Streamed completion finished"
             );
+            Ok(completion)
+        }
+        .await;
 
-            let url = format!("{}/chat/completions", self.config.base_url);
-            let mut request_builder = self
-                .client
-                .post(&url)
-                .header("Authorization", format!("Bearer {}", self.config.api_key))
-                .header("Content-Type", "application/json")
-                .json(&request);
+        self.emit(LlmEvent::RequestFinished {
+            model: self.config.model.clone(),
+            success: result.is_ok(),
+            total_tokens: result
+                .as_ref()
+                .ok()
+                .and_then(|completion| completion.usage.as_ref())
+                .map(|usage| usage.total_tokens),
+        });
+        result
+    }
 
-            if let Some(site_url) = &self.config.site_url {
-                request_builder = request_builder.header("HTTP-Referer", site_url);
-            }
-            if let Some(app_name) = &self.config.app_name {
-                request_builder = request_builder.header("X-Title", app_name);
+    /// Compute embedding vectors for the given texts with the configured model
+    #[instrument(skip(self, input), fields(count = input.len()))]
+    pub async fn embeddings(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = EmbeddingRequest {
+            model: self.config.model.clone(),
+            input,
+        };
+        let (_, response) = self
+            .send(|api_key| self.post("embeddings", api_key, &request))
+            .await?;
+        if response.status() != StatusCode::OK {
+            return Err(Self::api_error(response).await);
+        }
+
+        let mut embeddings: EmbeddingResponse = response.json().await?;
+        embeddings.data.sort_by_key(|item| item.index);
+        info!("Computed {} embeddings", embeddings.data.len());
+        Ok(embeddings
+            .data
+            .into_iter()
+            .map(|item| item.embedding)
+            .collect())
+    }
+
+    /// Check the API key: label, spent credits and limit
+    ///
+    /// Does not use the model, so it is a cheap way to validate the token and connectivity.
+    #[instrument(skip(self))]
+    pub async fn key_info(&self) -> Result<KeyInfo> {
+        let (_, api_key) = self.keys.current();
+        let response = self.request(Method::GET, "key", api_key).send().await?;
+        if response.status() != StatusCode::OK {
+            return Err(Self::api_error(response).await);
+        }
+
+        let key: KeyInfoResponse = response.json().await?;
+        debug!("API key is valid");
+        Ok(key.data)
+    }
+
+    /// Send a request with the current key; when it runs out of credits, retry with the next one
+    async fn send(&self, build: impl Fn(&str) -> RequestBuilder) -> Result<(usize, Response)> {
+        loop {
+            let (index, api_key) = self.keys.current();
+            self.keys.record_request(index);
+            let response = build(api_key).send().await?;
+            if response.status() != StatusCode::PAYMENT_REQUIRED {
+                return Ok((index, response));
             }
 
-            let response = request_builder.send().await?;
-            let status = response.status();
+            let error = Self::api_error(response).await;
+            let Some(next) = self.keys.rotate(index) else {
+                warn!("All API keys ran out of credits");
+                return Err(error);
+            };
+            let (from, to) = (key_label(api_key), key_label(&self.keys.keys[next]));
+            warn!(from = %from, to = %to, "API key ran out of credits, switching to the next one");
+            self.emit(LlmEvent::KeyRotated { from, to });
+        }
+    }
 
-            debug!("Received response with status: {}", status);
+    fn post(&self, path: &str, api_key: &str, body: &impl Serialize) -> RequestBuilder {
+        self.request(Method::POST, path, api_key)
+            .header("Content-Type", "application/json")
+            .json(body)
+    }
 
-            match status {
-                StatusCode::OK => {
-                    let completion: ChatCompletionResponse = response.json().await?;
-                    info!(
-                        "Completion successful: {} tokens used",
-                        completion.usage.total_tokens
-                    );
-                    Ok(completion)
-                }
-                StatusCode::UNAUTHORIZED => Err(LlmError::AuthError),
-                StatusCode::TOO_MANY_REQUESTS => {
-                    let retry_after = response
-                        .headers()
-                        .get("retry-after")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse().ok());
-                    warn!("Rate limit exceeded, retry after: {:?}", retry_after);
-                    Err(LlmError::RateLimitExceeded { retry_after })
-                }
-                _ => {
-                    let error_body = response.text().await?;
-                    warn!("API error response: {}", error_body);
-
-                    if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&error_body) {
-                        Err(LlmError::ApiError {
-                            status,
-                            message: error_response.error.message,
-                        })
-                    } else {
-                        Err(LlmError::ApiError {
-                            status,
-                            message: error_body,
-                        })
+    fn request(&self, method: Method, path: &str, api_key: &str) -> RequestBuilder {
+        let url = format!("{}/{path}", self.config.base_url);
+        let mut request_builder = self
+            .client
+            .request(method, &url)
+            .header("Authorization", format!("Bearer {api_key}"));
+
+        if let Some(site_url) = &self.config.site_url {
+            request_builder = request_builder.header("HTTP-Referer", site_url);
+        }
+        if let Some(app_name) = &self.config.app_name {
+            request_builder = request_builder.header("X-Title", app_name);
+        }
+        request_builder
+    }
+
+    async fn api_error(response: Response) -> LlmError {
+        let status = response.status();
+        match status {
+            StatusCode::UNAUTHORIZED => LlmError::AuthError,
+            StatusCode::PAYMENT_REQUIRED => {
+                let message = response.text().await.ok().and_then(|body| {
+                    serde_json::from_str::<ErrorResponse>(&body)
+                        .map(|error| error.error.message)
+                        .ok()
+                        .or(Some(body))
+                });
+                LlmError::InsufficientCredits(message.unwrap_or_default())
+            }
+            StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse().ok());
+                warn!("Rate limit exceeded, retry after: {:?}", retry_after);
+                LlmError::RateLimitExceeded { retry_after }
+            }
+            _ => {
+                let error_body = match response.text().await {
+                    Ok(body) => body,
+                    Err(err) => return LlmError::RequestFailed(err),
+                };
+                warn!("API error response: {}", error_body);
+
+                if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&error_body) {
+                    LlmError::ApiError {
+                        status,
+                        message: error_response.error.message,
+                    }
+                } else {
+                    LlmError::ApiError {
+                        status,
+                        message: error_body,
                     }
                 }
             }
         }
     }
+}
+
+/// Add one server-sent event to the streamed answer
+fn apply_chunk(
+    completion: &mut StreamedCompletion,
+    data: &str,
+    on_delta: &mut impl FnMut(&str),
+) -> Result<()> {
+    let chunk: StreamChunk = serde_json::from_str(data)?;
+    if let Some(error) = chunk.error {
+        return Err(LlmError::ApiError {
+            status: StatusCode::BAD_GATEWAY,
+            message: error.message,
+        });
+    }
+    if let Some(choice) = chunk.choices.into_iter().next() {
+        if let Some(content) = choice.delta.content.filter(|content| !content.is_empty()) {
+            on_delta(&content);
+            completion.content.push_str(&content);
+        }
+        if choice.finish_reason.is_some() {
+            completion.finish_reason = choice.finish_reason;
+        }
+    }
+    if chunk.usage.is_some() {
+        completion.usage = chunk.usage;
+    }
+    Ok(())
+}
+
+impl LlmClientTrait for LlmClient {
+    #[instrument(skip(self, messages, options), fields(message_count = messages.len()))]
+    fn chat_completion(
+        &self,
+        messages: Vec<Message>,
+        options: Option<CompletionOptions>,
+    ) -> impl Future<Output = Result<ChatCompletionResponse>> + Send {
+        async move {
+            self.emit(LlmEvent::RequestStarted {
+                model: self.config.model.clone(),
+            });
+
+            let result = self.send_chat_completion(messages, options).await;
+
+            self.emit(LlmEvent::RequestFinished {
+                model: self.config.model.clone(),
+                success: result.is_ok(),
+                total_tokens: result
+                    .as_ref()
+                    .ok()
+                    .map(|completion| completion.usage.total_tokens),
+            });
+
+            result
+        }
+    }
 
     #[instrument(skip(self, prompt))]
     fn complete(&self, prompt: String) -> impl Future<Output = Result<String>> + Send {
@@ -189,6 +597,38 @@ impl LlmClientTrait for LlmClient {
         }
     }
 
+    #[instrument(skip(self, system_prompt, prompts), fields(count = prompts.len()))]
+    fn complete_batch(
+        &self,
+        system_prompt: String,
+        prompts: Vec<String>,
+    ) -> impl Future<Output = Result<Vec<String>>> + Send {
+        async move {
+            let permits = Arc::new(Semaphore::new(BATCH_CONCURRENCY));
+            let mut tasks = JoinSet::new();
+            for (index, prompt) in prompts.into_iter().enumerate() {
+                let client = self.clone();
+                let system_prompt = system_prompt.clone();
+                let permits = Arc::clone(&permits);
+                tasks.spawn(async move {
+                    let _permit = permits.acquire_owned().await;
+                    let answer = client.complete_with_system(system_prompt, prompt).await;
+                    (index, answer)
+                });
+            }
+
+            let mut answers = vec![String::new(); tasks.len()];
+            while let Some(joined) = tasks.join_next().await {
+                let (index, answer) = joined.map_err(|err| {
+                    LlmError::InvalidRequest(format!("Batch request task failed: {err}"))
+                })?;
+                answers[index] = answer?;
+            }
+            info!("Completed batch of {} prompts", answers.len());
+            Ok(answers)
+        }
+    }
+
     fn model(&self) -> &str {
         &self.config.model
     }
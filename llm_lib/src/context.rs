@@ -0,0 +1,97 @@
+//! Packing heterogeneous context into a model's token budget
+//!
+//! Commands that ground a prompt in tracker data (issue text, comments, calendar
+//! entries, search results) describe each piece as a [`ContextItem`] and let [`pack`]
+//! decide what fits: items are taken by priority, the first one that does not fit is
+//! truncated, and the rest are dropped. The result keeps the original item order, so
+//! comments stay chronological no matter how they were prioritized.
+
+use tracing::debug;
+
+/// Rough estimate used for budgeting: this many characters make one token
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// A truncated item must keep at least this many tokens of body, otherwise it is dropped
+const MIN_TRUNCATED_TOKENS: usize = 32;
+
+/// Marker appended to a truncated body
+const TRUNCATED_MARKER: &str = "\n[…]";
+
+/// One piece of context with a title and a priority (higher is kept first)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextItem {
+    pub title: String,
+    pub body: String,
+    pub priority: u32,
+}
+
+impl ContextItem {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            priority: 0,
+        }
+    }
+
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    fn header(&self) -> String {
+        format!("## {}\n\n", self.title)
+    }
+}
+
+/// Estimated number of tokens in `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Format as many items as fit into `budget_tokens`
+///
+/// Items are considered from the highest priority down (ties keep their input order).
+/// An item that does not fit whole is truncated if a useful part of it fits; smaller
+/// lower-priority items may still fill the remaining space.
+pub fn pack(items: Vec<ContextItem>, budget_tokens: usize) -> String {
+    let mut by_priority: Vec<usize> = (0..items.len()).collect();
+    by_priority.sort_by_key(|&index| std::cmp::Reverse(items[index].priority));
+
+    let mut bodies: Vec<Option<String>> = vec![None; items.len()];
+    let mut remaining = budget_tokens;
+    for index in by_priority {
+        let item = &items[index];
+        // Blank line between sections
+        let overhead = estimate_tokens(&item.header()) + 1;
+        let needed = overhead + estimate_tokens(&item.body);
+        if needed <= remaining {
+            remaining -= needed;
+            bodies[index] = Some(item.body.clone());
+            continue;
+        }
+
+        let available = remaining
+            .saturating_sub(overhead)
+            .saturating_sub(estimate_tokens(TRUNCATED_MARKER));
+        if available >= MIN_TRUNCATED_TOKENS {
+            let kept: String = item
+                .body
+                .chars()
+                .take(available * CHARS_PER_TOKEN)
+                .collect();
+            let body = format!("{}{TRUNCATED_MARKER}", kept.trim_end());
+            remaining = remaining.saturating_sub(overhead + estimate_tokens(&body));
+            bodies[index] = Some(body);
+        } else {
+            debug!(title = %item.title, "Context item dropped: budget exhausted");
+        }
+    }
+
+    items
+        .iter()
+        .zip(bodies)
+        .filter_map(|(item, body)| Some(format!("{}{}", item.header(), body?.trim_end())))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -0,0 +1,300 @@
+//! Chat history that can be saved as a readable Markdown transcript and loaded back
+//!
+//! [`Branches`] adds forks: a chat can be continued from any earlier prompt in a new
+//! branch while the original branch stays intact. All branches share one transcript file.
+
+use crate::{LlmError, Message, Result, Role};
+
+/// Messages of one chat session in the order they were exchanged
+///
+/// `to_markdown` writes each message under a `## User` / `## Assistant` style header and
+/// keeps the content verbatim, so fenced code survives the round trip through
+/// `from_markdown`. Tool calls requested by the assistant are not part of the transcript.
+#[derive(Debug, Clone, Default)]
+pub struct Conversation {
+    messages: Vec<Message>,
+}
+
+const TITLE: &str = "# Chat transcript";
+
+/// Header of every branch after the first one
+const BRANCH_HEADER: &str = "# Branch ";
+
+fn header(role: &Role) -> &'static str {
+    match role {
+        Role::System => "## System",
+        Role::User => "## User",
+        Role::Assistant => "## Assistant",
+        Role::Tool => "## Tool",
+    }
+}
+
+fn parse_header(line: &str) -> Option<Role> {
+    match line.trim_end() {
+        "## System" => Some(Role::System),
+        "## User" => Some(Role::User),
+        "## Assistant" => Some(Role::Assistant),
+        "## Tool" => Some(Role::Tool),
+        _ => None,
+    }
+}
+
+/// Opening or closing line of a fenced code block
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+impl Conversation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a conversation with a system prompt
+    pub fn with_system(system_prompt: impl Into<String>) -> Self {
+        Self {
+            messages: vec![Message::system(system_prompt)],
+        }
+    }
+
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Index of the `number`-th user message, counting from 1
+    pub fn prompt_position(&self, number: usize) -> Option<usize> {
+        self.messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| message.role == Role::User)
+            .nth(number.checked_sub(1)?)
+            .map(|(index, _)| index)
+    }
+
+    /// Render the transcript: a title, then every message under its role header
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("{TITLE}\n");
+        self.write_messages(&mut markdown);
+        markdown
+    }
+
+    fn write_messages(&self, markdown: &mut String) {
+        for message in &self.messages {
+            markdown.push('\n');
+            markdown.push_str(header(&message.role));
+            markdown.push_str("\n\n");
+            markdown.push_str(message.content.trim_matches('\n'));
+            markdown.push('\n');
+        }
+    }
+
+    /// Parse a transcript written by `to_markdown`
+    ///
+    /// Text before the first role header is ignored, and headers inside fenced code
+    /// blocks are treated as content.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut messages = Vec::new();
+        let mut current: Option<(Role, Vec<&str>)> = None;
+        let mut in_fence = false;
+
+        for line in markdown.lines() {
+            if !in_fence {
+                if let Some(role) = parse_header(line) {
+                    messages.extend(current.take().map(finish));
+                    current = Some((role, Vec::new()));
+                    continue;
+                }
+            }
+            if is_fence(line) {
+                in_fence = !in_fence;
+            }
+            if let Some((_, lines)) = &mut current {
+                lines.push(line);
+            }
+        }
+        messages.extend(current.map(finish));
+
+        Self { messages }
+    }
+}
+
+fn finish((role, lines): (Role, Vec<&str>)) -> Message {
+    let content = lines.join("\n").trim_matches('\n').to_string();
+    match role {
+        Role::System => Message::system(content),
+        Role::User => Message::user(content),
+        Role::Assistant => Message::assistant(content),
+        Role::Tool => Message {
+            role: Role::Tool,
+            ..Message::user(content)
+        },
+    }
+}
+
+/// One line of a chat fork: where it came from and its whole history
+#[derive(Debug, Clone)]
+pub struct Branch {
+    /// Index of the branch this one was forked from
+    pub parent: Option<usize>,
+    /// How many messages were copied from the parent
+    pub forked_at: usize,
+    pub conversation: Conversation,
+}
+
+/// A chat with forks
+///
+/// Every branch keeps its full history, so any of them can be continued on its own.
+/// The transcript starts with the first branch in the [`Conversation`] format, and each
+/// next branch follows under a `# Branch N (from branch P, after message K)` header.
+/// A plain transcript without forks loads as a single branch.
+#[derive(Debug, Clone)]
+pub struct Branches {
+    branches: Vec<Branch>,
+    current: usize,
+}
+
+impl Default for Branches {
+    fn default() -> Self {
+        Self::new(Conversation::default())
+    }
+}
+
+impl Branches {
+    pub fn new(conversation: Conversation) -> Self {
+        Self {
+            branches: vec![Branch {
+                parent: None,
+                forked_at: 0,
+                conversation,
+            }],
+            current: 0,
+        }
+    }
+
+    pub fn branches(&self) -> &[Branch] {
+        &self.branches
+    }
+
+    /// Index of the branch new messages go to
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> &Conversation {
+        &self.branches[self.current].conversation
+    }
+
+    pub fn current_mut(&mut self) -> &mut Conversation {
+        &mut self.branches[self.current].conversation
+    }
+
+    /// Fork the current branch keeping its first `keep` messages and switch to the fork
+    pub fn fork(&mut self, keep: usize) -> Result<usize> {
+        let messages = self.current().messages();
+        if keep > messages.len() {
+            return Err(LlmError::InvalidRequest(format!(
+                "Branch has only {} messages",
+                messages.len()
+            )));
+        }
+        let conversation = Conversation {
+            messages: messages[..keep].to_vec(),
+        };
+        self.branches.push(Branch {
+            parent: Some(self.current),
+            forked_at: keep,
+            conversation,
+        });
+        self.current = self.branches.len() - 1;
+        Ok(self.current)
+    }
+
+    /// Fork the current branch right before its `number`-th prompt (counting from 1)
+    ///
+    /// Returns the original prompt text, so it can be sent again for another answer.
+    pub fn fork_at_prompt(&mut self, number: usize) -> Result<String> {
+        let position = self.current().prompt_position(number).ok_or_else(|| {
+            LlmError::InvalidRequest(format!("No prompt number {number} in the branch"))
+        })?;
+        let prompt = self.current().messages()[position].content.clone();
+        self.fork(position)?;
+        Ok(prompt)
+    }
+
+    pub fn switch(&mut self, index: usize) -> Result<()> {
+        if index >= self.branches.len() {
+            return Err(LlmError::InvalidRequest(format!("No branch {}", index + 1)));
+        }
+        self.current = index;
+        Ok(())
+    }
+
+    /// Render all branches into one transcript
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = self.branches[0].conversation.to_markdown();
+        for (index, branch) in self.branches.iter().enumerate().skip(1) {
+            markdown.push_str(&format!(
+                "\n{BRANCH_HEADER}{} (from branch {}, after message {})\n",
+                index + 1,
+                branch.parent.unwrap_or(0) + 1,
+                branch.forked_at
+            ));
+            branch.conversation.write_messages(&mut markdown);
+        }
+        markdown
+    }
+
+    /// Parse a transcript written by `to_markdown`; the last branch becomes current
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut chunks: Vec<(Option<&str>, Vec<&str>)> = vec![(None, Vec::new())];
+        let mut in_fence = false;
+        for line in markdown.lines() {
+            if !in_fence && line.starts_with(BRANCH_HEADER) {
+                chunks.push((Some(line), Vec::new()));
+                continue;
+            }
+            if is_fence(line) {
+                in_fence = !in_fence;
+            }
+            if let Some((_, lines)) = chunks.last_mut() {
+                lines.push(line);
+            }
+        }
+
+        let branches: Vec<Branch> = chunks
+            .into_iter()
+            .map(|(header, lines)| {
+                let (parent, forked_at) = header.map(parse_branch_header).unwrap_or((None, 0));
+                Branch {
+                    parent,
+                    forked_at,
+                    conversation: Conversation::from_markdown(&lines.join("\n")),
+                }
+            })
+            .collect();
+        Self {
+            current: branches.len() - 1,
+            branches,
+        }
+    }
+}
+
+/// Parent index and copied message count from `# Branch N (from branch P, after message K)`
+fn parse_branch_header(line: &str) -> (Option<usize>, usize) {
+    let numbers: Vec<usize> = line
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect();
+    match numbers[..] {
+        [_, parent, forked_at] => (parent.checked_sub(1), forked_at),
+        _ => (None, 0),
+    }
+}
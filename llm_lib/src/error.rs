@@ -15,6 +15,9 @@ pub enum LlmError {
     #[error("Authentication failed: missing or invalid API key")]
     AuthError,
 
+    #[error("Insufficient credits on every API key: {0}")]
+    InsufficientCredits(String),
+
     #[error("Rate limit exceeded. Retry after: {retry_after:?}")]
     RateLimitExceeded { retry_after: Option<u64> },
 
@@ -28,4 +31,10 @@ pub enum LlmError {
     InvalidRequest(String),
 }
 
+impl From<core_lib::CoreError> for LlmError {
+    fn from(err: core_lib::CoreError) -> Self {
+        LlmError::ConfigError(err.to_string())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, LlmError>;
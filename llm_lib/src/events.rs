@@ -0,0 +1,42 @@
+use reqwest::header::HeaderMap;
+
+/// Events emitted by the client while talking to the API
+#[derive(Debug, Clone, PartialEq)]
+pub enum LlmEvent {
+    RequestStarted {
+        model: String,
+    },
+    RequestFinished {
+        model: String,
+        success: bool,
+        total_tokens: Option<u32>,
+    },
+    RateLimitUpdated(RateLimit),
+    /// The key ran out of credits; requests continue with the next one (labels are key suffixes)
+    KeyRotated {
+        from: String,
+        to: String,
+    },
+}
+
+/// Rate limit budget reported by the API in response headers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimit {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    pub retry_after_secs: Option<u64>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+        let rate_limit = Self {
+            limit: header("x-ratelimit-limit").and_then(|v| v.parse().ok()),
+            remaining: header("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            retry_after_secs: header("retry-after").and_then(|v| v.parse().ok()),
+        };
+
+        (rate_limit != Self::default()).then_some(rate_limit)
+    }
+}
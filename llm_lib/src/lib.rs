@@ -6,6 +6,14 @@
 //!
 //! - Support for OpenRouter API
 //! - Chat completion with conversation history
+//! - Streamed completions delivered piece by piece as they are generated
+//! - Markdown transcripts of conversations that can be resumed later, with forks
+//! - Batch completion of independent prompts with bounded concurrency
+//! - Packing prioritized context into a token budget
+//! - Tool (function) calling
+//! - OpenRouter provider routing (order, fallbacks, quantization, data collection)
+//! - Text embeddings
+//! - Fallback API keys with automatic rotation when a key runs out of credits
 //! - Configurable models and parameters
 //! - Full tracing and observability support
 //! - Easy error handling with `anyhow`
@@ -67,12 +75,20 @@
 //! ```
 
 mod client;
+pub mod context;
+pub mod conversation;
 mod error;
+pub mod events;
 pub mod models;
 
-pub use client::{LlmClient, LlmClientTrait, LlmConfig};
+pub use client::{KeyUsage, LlmClient, LlmClientTrait, LlmConfig, BASE_URL_VAR, FALLBACK_KEYS_VAR};
+pub use conversation::{Branch, Branches, Conversation};
 pub use error::{LlmError, Result};
-pub use models::{ChatCompletionResponse, Choice, CompletionOptions, Message, Role, Usage};
+pub use models::{
+    ChatCompletionResponse, Choice, CompletionOptions, DataCollection, FileInput, FunctionCall,
+    FunctionDefinition, KeyInfo, Message, ProviderPreferences, Role, StreamedCompletion, Tool,
+    ToolCall, Usage,
+};
 
 #[cfg(any(test, feature = "testing"))]
 pub use client::MockLlmClientTrait;
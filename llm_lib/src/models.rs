@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -6,35 +7,300 @@ pub enum Role {
     System,
     User,
     Assistant,
+    /// Result of a tool call requested by the assistant
+    Tool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "WireMessage")]
 pub struct Message {
     pub role: Role,
+    /// Assistant messages that only request tool calls come with `null` content
+    #[serde(default, deserialize_with = "null_as_empty")]
     pub content: String,
+    /// Images and documents sent along with the text; needs a vision-capable model
+    #[serde(skip)]
+    pub files: Vec<FileInput>,
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
 }
 
 impl Message {
-    pub fn system(content: impl Into<String>) -> Self {
+    fn new(role: Role, content: impl Into<String>) -> Self {
         Self {
-            role: Role::System,
+            role,
             content: content.into(),
+            files: Vec::new(),
+            tool_calls: Vec::new(),
+            tool_call_id: None,
         }
     }
 
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(Role::System, content)
+    }
+
     pub fn user(content: impl Into<String>) -> Self {
+        Self::new(Role::User, content)
+    }
+
+    /// User message with attached images or PDFs, sent as multipart content
+    pub fn user_with_files(content: impl Into<String>, files: Vec<FileInput>) -> Self {
         Self {
-            role: Role::User,
-            content: content.into(),
+            files,
+            ..Self::new(Role::User, content)
         }
     }
 
     pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(Role::Assistant, content)
+    }
+
+    /// Answer to the tool call with the given id
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
         Self {
-            role: Role::Assistant,
-            content: content.into(),
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::new(Role::Tool, content)
+        }
+    }
+}
+
+/// File attached to a message, inlined into the request as a base64 data URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileInput {
+    pub filename: String,
+    /// MIME type, e.g. `image/png` or `application/pdf`
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl FileInput {
+    pub fn new(
+        filename: impl Into<String>,
+        mime_type: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            mime_type: mime_type.into(),
+            data: data.into(),
         }
     }
+
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, base64(&self.data))
+    }
+
+    /// Images go as `image_url` parts, anything else as a `file` part
+    fn part(&self) -> ContentPart {
+        if self.is_image() {
+            ContentPart::ImageUrl {
+                image_url: ImageUrl {
+                    url: self.data_url(),
+                },
+            }
+        } else {
+            ContentPart::File {
+                file: FileData {
+                    filename: self.filename.clone(),
+                    file_data: self.data_url(),
+                },
+            }
+        }
+    }
+}
+
+/// Message as sent to the API: plain text, or text and file parts when files are attached
+#[derive(Serialize)]
+struct WireMessage {
+    role: Role,
+    content: WireContent,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<ToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum WireContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+    File { file: FileData },
+}
+
+#[derive(Serialize)]
+struct ImageUrl {
+    url: String,
+}
+
+#[derive(Serialize)]
+struct FileData {
+    filename: String,
+    file_data: String,
+}
+
+impl From<Message> for WireMessage {
+    fn from(message: Message) -> Self {
+        let content = if message.files.is_empty() {
+            WireContent::Text(message.content)
+        } else {
+            let mut parts = vec![ContentPart::Text {
+                text: message.content,
+            }];
+            parts.extend(message.files.iter().map(FileInput::part));
+            WireContent::Parts(parts)
+        };
+        Self {
+            role: message.role,
+            content,
+            tool_calls: message.tool_calls,
+            tool_call_id: message.tool_call_id,
+        }
+    }
+}
+
+/// Standard base64 with padding, enough for data URLs without pulling in a crate
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((triple >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn null_as_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Function the model may call, described with a JSON Schema of its arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionDefinition,
+}
+
+impl Tool {
+    pub fn function(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: Value,
+    ) -> Self {
+        Self {
+            kind: "function".to_string(),
+            function: FunctionDefinition {
+                name: name.into(),
+                description: description.into(),
+                parameters,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub description: String,
+    pub parameters: Value,
+}
+
+/// Tool invocation requested by the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCall {
+    pub name: String,
+    /// Arguments as a JSON encoded string, exactly as the model produced them
+    pub arguments: String,
+}
+
+/// Whether upstream providers may store and train on request data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataCollection {
+    Allow,
+    Deny,
+}
+
+/// OpenRouter provider routing preferences, sent as the `provider` request field
+///
+/// Lets a request be pinned to specific upstream providers, e.g. ones that do not
+/// retain prompts, instead of whatever OpenRouter picks by price and uptime.
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+pub struct ProviderPreferences {
+    /// Provider slugs to try in this order (e.g. `anthropic`, `amazon-bedrock`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+
+    /// Whether other providers may serve the request when the listed ones fail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fallbacks: Option<bool>,
+
+    /// Accepted quantization levels (e.g. `fp8`, `bf16`); empty means any
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quantizations: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_collection: Option<DataCollection>,
+}
+
+impl ProviderPreferences {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn order(mut self, providers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.order = providers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn allow_fallbacks(mut self, allow: bool) -> Self {
+        self.allow_fallbacks = Some(allow);
+        self
+    }
+
+    pub fn quantizations(mut self, levels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.quantizations = levels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn data_collection(mut self, policy: DataCollection) -> Self {
+        self.data_collection = Some(policy);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -56,6 +322,12 @@ pub struct CompletionOptions {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderPreferences>,
 }
 
 impl CompletionOptions {
@@ -77,6 +349,18 @@ impl CompletionOptions {
         self.top_p = Some(top_p);
         self
     }
+
+    /// Offer tools the model may call instead of answering directly
+    pub fn tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Route the request according to OpenRouter provider preferences
+    pub fn provider(mut self, provider: ProviderPreferences) -> Self {
+        self.provider = Some(provider);
+        self
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -85,6 +369,9 @@ pub struct ChatCompletionRequest {
     pub messages: Vec<Message>,
     #[serde(flatten)]
     pub options: CompletionOptions,
+    /// Ask for server-sent events with answer deltas instead of one response
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,7 +381,7 @@ pub struct Choice {
     pub finish_reason: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
@@ -114,6 +401,80 @@ impl ChatCompletionResponse {
     pub fn content(&self) -> Option<&str> {
         self.choices.first().map(|c| c.message.content.as_str())
     }
+
+    /// Tool calls requested in the first choice
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        self.choices
+            .first()
+            .map(|c| c.message.tool_calls.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// One server-sent event of a streamed completion
+#[derive(Debug, Deserialize)]
+pub struct StreamChunk {
+    #[serde(default)]
+    pub choices: Vec<StreamChoice>,
+    /// Sent with the last chunk
+    pub usage: Option<Usage>,
+    /// The provider failed after the response has started
+    pub error: Option<ErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamChoice {
+    pub delta: Delta,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Delta {
+    pub content: Option<String>,
+}
+
+/// Answer assembled from a streamed completion
+#[derive(Debug, Clone)]
+pub struct StreamedCompletion {
+    pub content: String,
+    /// Token usage, if the API reported it at the end of the stream
+    pub usage: Option<Usage>,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub input: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingData {
+    pub index: usize,
+    pub embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingResponse {
+    pub data: Vec<EmbeddingData>,
+}
+
+/// API key details returned by OpenRouter's `GET /key`
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyInfo {
+    pub label: Option<String>,
+    /// Credits spent with this key
+    #[serde(default)]
+    pub usage: f64,
+    /// Credit limit, `None` for keys without one
+    pub limit: Option<f64>,
+    #[serde(default)]
+    pub is_free_tier: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeyInfoResponse {
+    pub data: KeyInfo,
 }
 
 #[derive(Debug, Deserialize)]
@@ -126,5 +487,6 @@ pub struct ErrorDetail {
     pub message: String,
     #[serde(rename = "type")]
     pub error_type: Option<String>,
-    pub code: Option<String>,
+    /// OpenRouter sends the HTTP status as a number, other gateways a string
+    pub code: Option<serde_json::Value>,
 }
@@ -1,5 +1,7 @@
-use llm_lib::{LlmClient, LlmClientTrait, LlmConfig, LlmError, Message, Role};
-use wiremock::matchers::{header, method, path};
+use llm_lib::{
+    Branches, Conversation, LlmClient, LlmClientTrait, LlmConfig, LlmError, Message, Role,
+};
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -28,6 +30,7 @@ async fn test_successful_chat_completion() {
     Mock::given(method("POST"))
         .and(path("/chat/completions"))
         .and(header("Authorization", "Bearer test-api-key"))
+        .and(header("User-Agent", "llm-lib-tests"))
         .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
         .mount(&mock_server)
         .await;
@@ -39,6 +42,8 @@ async fn test_successful_chat_completion() {
         timeout_secs: 30,
         site_url: None,
         app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
     };
 
     let client = LlmClient::new(config).expect("Failed to create client");
@@ -77,6 +82,8 @@ async fn test_authentication_error() {
         timeout_secs: 30,
         site_url: None,
         app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
     };
 
     let client = LlmClient::new(config).expect("Failed to create client");
@@ -108,6 +115,8 @@ async fn test_rate_limit_error() {
         timeout_secs: 30,
         site_url: None,
         app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
     };
 
     let client = LlmClient::new(config).expect("Failed to create client");
@@ -123,3 +132,659 @@ async fn test_rate_limit_error() {
         other => panic!("Expected RateLimitExceeded, got: {:?}", other),
     }
 }
+
+#[tokio::test]
+async fn test_key_info_validates_token() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/key"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {"label": "sk-or-v1-abc...xyz", "usage": 1.25, "limit": 10.0, "is_free_tier": false}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/key"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config.clone()).expect("Failed to create client");
+    let key = client.key_info().await.unwrap();
+    assert_eq!(key.label.as_deref(), Some("sk-or-v1-abc...xyz"));
+    assert_eq!(key.usage, 1.25);
+    assert_eq!(key.limit, Some(10.0));
+
+    let invalid = LlmClient::new(LlmConfig {
+        api_key: "invalid-key".to_string(),
+        ..config
+    })
+    .expect("Failed to create client");
+    assert!(matches!(invalid.key_info().await, Err(LlmError::AuthError)));
+}
+
+#[tokio::test]
+async fn test_events_emitted_for_completion() {
+    use llm_lib::events::{LlmEvent, RateLimit};
+
+    let mock_server = MockServer::start().await;
+
+    let response_body = serde_json::json!({
+        "id": "test-id",
+        "model": "test-model",
+        "created": 1234567890_u64,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "ok"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 1, "completion_tokens": 2, "total_tokens": 3}
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(response_body)
+                .insert_header("x-ratelimit-remaining", "19"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let client = LlmClient::new(config)
+        .expect("Failed to create client")
+        .with_event_sender(sender);
+
+    client.complete("Hello".to_string()).await.unwrap();
+
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        LlmEvent::RequestStarted {
+            model: "test-model".to_string()
+        }
+    );
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        LlmEvent::RateLimitUpdated(RateLimit {
+            limit: None,
+            remaining: Some(19),
+            retry_after_secs: None,
+        })
+    );
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        LlmEvent::RequestFinished {
+            model: "test-model".to_string(),
+            success: true,
+            total_tokens: Some(3),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_exhausted_key_rotates_to_fallback() {
+    use llm_lib::events::LlmEvent;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", "Bearer key-one"))
+        .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+            "error": {"code": 402, "message": "Insufficient credits"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("Authorization", "Bearer key-two"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "test-id",
+            "model": "test-model",
+            "created": 1234567890_u64,
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "ok"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 1, "completion_tokens": 2, "total_tokens": 3}
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "key-one".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    }
+    .with_fallback_keys(["key-two"]);
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let client = LlmClient::new(config)
+        .expect("Failed to create client")
+        .with_event_sender(sender);
+
+    // The second request goes straight to the fallback key
+    assert_eq!(client.complete("Hello".to_string()).await.unwrap(), "ok");
+    assert_eq!(client.complete("Again".to_string()).await.unwrap(), "ok");
+
+    let events: Vec<LlmEvent> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+    assert!(events.contains(&LlmEvent::KeyRotated {
+        from: "…-one".to_string(),
+        to: "…-two".to_string(),
+    }));
+
+    let usage = client.key_usage();
+    assert_eq!(usage.len(), 2);
+    assert!(usage[0].exhausted);
+    assert_eq!((usage[0].requests, usage[0].total_tokens), (1, 0));
+    assert!(!usage[1].exhausted);
+    assert_eq!((usage[1].requests, usage[1].total_tokens), (2, 6));
+}
+
+#[tokio::test]
+async fn test_insufficient_credits_without_fallback() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+            "error": {"code": 402, "message": "Insufficient credits"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "key-one".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config).expect("Failed to create client");
+    let result = client.complete("Hello".to_string()).await;
+    assert!(matches!(
+        result,
+        Err(LlmError::InsufficientCredits(message)) if message == "Insufficient credits"
+    ));
+}
+
+#[tokio::test]
+async fn test_tool_calls_round_trip() {
+    use llm_lib::{CompletionOptions, Tool};
+    use wiremock::matchers::body_partial_json;
+
+    let mock_server = MockServer::start().await;
+
+    let response_body = serde_json::json!({
+        "id": "test-id",
+        "model": "test-model",
+        "created": 1234567890_u64,
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call-1",
+                    "type": "function",
+                    "function": {"name": "get_issue", "arguments": "{\"key\":\"TREK-1\"}"}
+                }]
+            },
+            "finish_reason": "tool_calls"
+        }],
+        "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({
+            "tools": [{"type": "function", "function": {"name": "get_issue"}}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config).expect("Failed to create client");
+    let tool = Tool::function(
+        "get_issue",
+        "Fetch an issue by key",
+        serde_json::json!({"type": "object", "properties": {"key": {"type": "string"}}}),
+    );
+    let options = CompletionOptions::new().tools(vec![tool]);
+
+    let response = client
+        .chat_completion(vec![Message::user("Open TREK-1")], Some(options))
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.content(), Some(""));
+    let calls = response.tool_calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function.name, "get_issue");
+    assert_eq!(calls[0].function.arguments, "{\"key\":\"TREK-1\"}");
+
+    let answer = Message::tool(&calls[0].id, "{\"summary\":\"Bug\"}");
+    assert_eq!(answer.role, Role::Tool);
+    assert_eq!(answer.tool_call_id.as_deref(), Some("call-1"));
+}
+
+#[tokio::test]
+async fn test_embeddings_are_returned_in_input_order() {
+    use wiremock::matchers::body_partial_json;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .and(header("Authorization", "Bearer test-api-key"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "test-embedder",
+            "input": ["first", "second"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {"index": 1, "embedding": [0.0, 1.0]},
+                {"index": 0, "embedding": [1.0, 0.0]}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-embedder".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config).expect("Failed to create client");
+    let vectors = client
+        .embeddings(vec!["first".to_string(), "second".to_string()])
+        .await
+        .expect("Request failed");
+
+    assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+}
+
+#[tokio::test]
+async fn test_complete_batch_keeps_prompt_order() {
+    use wiremock::matchers::body_string_contains;
+
+    let mock_server = MockServer::start().await;
+
+    for prompt in ["first", "second", "third"] {
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_string_contains(format!("\"{prompt}\"")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": format!("id-{prompt}"),
+                "model": "test-model",
+                "created": 1234567890_u64,
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": format!("answer to {prompt}")},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config).expect("Failed to create client");
+    let answers = client
+        .complete_batch(
+            "Summarize".to_string(),
+            vec![
+                "first".to_string(),
+                "second".to_string(),
+                "third".to_string(),
+            ],
+        )
+        .await
+        .expect("Batch failed");
+
+    assert_eq!(
+        answers,
+        ["answer to first", "answer to second", "answer to third"]
+    );
+}
+
+#[test]
+fn test_conversation_markdown_round_trip() {
+    let mut conversation = Conversation::with_system("You are terse.");
+    conversation.push(Message::user("Show a heading in Markdown"));
+    conversation.push(Message::assistant(
+        "Like this:\n\n```markdown\n## User\n# Title\n```\n\nThat's it.",
+    ));
+
+    let markdown = conversation.to_markdown();
+    assert!(markdown.starts_with("# Chat transcript\n\n## System\n\nYou are terse.\n"));
+    assert!(markdown.contains("## Assistant\n\nLike this:\n\n```markdown\n## User\n"));
+
+    let restored = Conversation::from_markdown(&markdown);
+    let roles: Vec<Role> = restored.messages().iter().map(|m| m.role.clone()).collect();
+    assert_eq!(roles, [Role::System, Role::User, Role::Assistant]);
+    for (restored, original) in restored.messages().iter().zip(conversation.messages()) {
+        assert_eq!(restored.content, original.content);
+    }
+    assert!(Conversation::from_markdown("just notes").is_empty());
+}
+
+#[test]
+fn test_fork_keeps_original_branch() {
+    let mut conversation = Conversation::with_system("Be brief");
+    conversation.push(Message::user("What is Rust?"));
+    conversation.push(Message::assistant("A language"));
+    conversation.push(Message::user("And Go?"));
+    conversation.push(Message::assistant("Also a language"));
+    let mut branches = Branches::new(conversation);
+
+    let prompt = branches.fork_at_prompt(2).unwrap();
+    assert_eq!(prompt, "And Go?");
+    assert_eq!(branches.current_index(), 1);
+    assert_eq!(branches.current().messages().len(), 3);
+    branches.current_mut().push(Message::user("And Zig?"));
+
+    assert_eq!(branches.branches()[0].conversation.messages().len(), 5);
+    assert!(branches.fork_at_prompt(5).is_err());
+    assert!(branches.switch(2).is_err());
+}
+
+#[test]
+fn test_branches_markdown_round_trip() {
+    let mut conversation = Conversation::new();
+    conversation.push(Message::user("Question"));
+    conversation.push(Message::assistant("```\n# Branch 9 (not a header)\n```"));
+    let mut branches = Branches::new(conversation);
+    branches.fork(1).unwrap();
+    branches
+        .current_mut()
+        .push(Message::assistant("Another answer"));
+
+    let markdown = branches.to_markdown();
+    assert!(markdown.contains("\n# Branch 2 (from branch 1, after message 1)\n"));
+
+    let loaded = Branches::from_markdown(&markdown);
+    assert_eq!(loaded.branches().len(), 2);
+    assert_eq!(loaded.current_index(), 1);
+    assert_eq!(loaded.branches()[1].parent, Some(0));
+    assert_eq!(loaded.branches()[1].forked_at, 1);
+    assert_eq!(
+        loaded.branches()[0].conversation.messages()[1].content,
+        "```\n# Branch 9 (not a header)\n```"
+    );
+    assert_eq!(loaded.current().messages()[1].content, "Another answer");
+
+    let plain = Branches::from_markdown(&loaded.branches()[0].conversation.to_markdown());
+    assert_eq!(plain.branches().len(), 1);
+}
+
+#[tokio::test]
+async fn test_provider_preferences_are_sent() {
+    use llm_lib::{CompletionOptions, DataCollection, ProviderPreferences};
+    use wiremock::matchers::body_partial_json;
+
+    let mock_server = MockServer::start().await;
+
+    let response_body = serde_json::json!({
+        "id": "test-id",
+        "model": "test-model",
+        "created": 1234567890_u64,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "ok"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({
+            "provider": {
+                "order": ["anthropic", "amazon-bedrock"],
+                "allow_fallbacks": false,
+                "quantizations": ["fp8"],
+                "data_collection": "deny"
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+    let client = LlmClient::new(config).expect("Failed to create client");
+
+    let provider = ProviderPreferences::new()
+        .order(["anthropic", "amazon-bedrock"])
+        .allow_fallbacks(false)
+        .quantizations(["fp8"])
+        .data_collection(DataCollection::Deny);
+    let options = CompletionOptions::new().provider(provider);
+
+    let response = client
+        .chat_completion(vec![Message::user("Hi")], Some(options))
+        .await
+        .expect("Request failed");
+    assert_eq!(response.content(), Some("ok"));
+
+    // Without preferences the field is not sent at all
+    let body = serde_json::to_value(CompletionOptions::new()).unwrap();
+    assert!(body.get("provider").is_none());
+}
+
+#[test]
+fn test_context_pack_prioritizes_truncates_and_keeps_order() {
+    use llm_lib::context::{estimate_tokens, pack, ContextItem};
+
+    let items = vec![
+        ContextItem::new("Old comment", "old ".repeat(100)).priority(1),
+        ContextItem::new("Issue", "Build fails on CI").priority(10),
+        ContextItem::new("Long log", "line\n".repeat(200)).priority(5),
+        ContextItem::new("Meeting", "Standup at 10:00").priority(2),
+    ];
+
+    let packed = pack(items.clone(), 100);
+    assert!(estimate_tokens(&packed) <= 100);
+    assert!(packed.starts_with("## Issue\n\nBuild fails on CI\n\n## Long log\n\nline\n"));
+    assert!(packed.contains("[…]"));
+    assert!(!packed.contains("Old comment"));
+    assert!(!packed.contains("Meeting"));
+
+    let everything = pack(items, 10_000);
+    let titles: Vec<&str> = everything
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .collect();
+    assert_eq!(titles, ["Old comment", "Issue", "Long log", "Meeting"]);
+}
+
+#[tokio::test]
+async fn test_files_are_sent_as_content_parts() {
+    use llm_lib::FileInput;
+    use wiremock::matchers::body_partial_json;
+
+    let mock_server = MockServer::start().await;
+
+    let response_body = serde_json::json!({
+        "id": "test-id",
+        "model": "test-model",
+        "created": 1234567890_u64,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "A login form with an error"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({
+            "messages": [
+                {"role": "system", "content": "Describe attachments"},
+                {"role": "user", "content": [
+                    {"type": "text", "text": "What is on the screenshot?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw=="}},
+                    {"type": "file", "file": {
+                        "filename": "spec.pdf",
+                        "file_data": "data:application/pdf;base64,JVBERi0="
+                    }}
+                ]}
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+
+    let client = LlmClient::new(config).expect("Failed to create client");
+    let files = vec![
+        FileInput::new("screen.png", "image/png", vec![0x89, b'P', b'N', b'G']),
+        FileInput::new("spec.pdf", "application/pdf", b"%PDF-".to_vec()),
+    ];
+    let messages = vec![
+        Message::system("Describe attachments"),
+        Message::user_with_files("What is on the screenshot?", files),
+    ];
+
+    let response = client
+        .chat_completion(messages, None)
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.content(), Some("A login form with an error"));
+}
+
+#[tokio::test]
+async fn test_streamed_completion_collects_deltas() {
+    let mock_server = MockServer::start().await;
+
+    let body = [
+        ": OPENROUTER PROCESSING\n\n",
+        "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"При\"}}]}\n\n",
+        "data: {\"choices\":[{\"delta\":{\"content\":\"вет\"},\"finish_reason\":\"stop\"}]}\n\n",
+        "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":2,\"total_tokens\":5}}\n\n",
+        "data: [DONE]\n\n",
+    ]
+    .concat();
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({"stream": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = LlmConfig {
+        api_key: "test-api-key".to_string(),
+        base_url: mock_server.uri(),
+        model: "test-model".to_string(),
+        timeout_secs: 30,
+        site_url: None,
+        app_name: None,
+        user_agent: "llm-lib-tests".to_string(),
+        fallback_keys: Vec::new(),
+    };
+    let client = LlmClient::new(config).expect("Failed to create client");
+
+    let mut deltas = Vec::new();
+    let completion = client
+        .chat_completion_stream(vec![Message::user("Привет")], None, |delta| {
+            deltas.push(delta.to_string())
+        })
+        .await
+        .expect("Stream failed");
+
+    assert_eq!(deltas, ["При", "вет"]);
+    assert_eq!(completion.content, "Привет");
+    assert_eq!(completion.finish_reason.as_deref(), Some("stop"));
+    assert_eq!(completion.usage.map(|usage| usage.total_tokens), Some(5));
+    assert_eq!(client.key_usage()[0].total_tokens, 5);
+}
@@ -1,5 +1,7 @@
 use serde_json::json;
 use std::collections::HashMap;
+use tracker_lib::ids::IssueKey;
+use tracker_lib::task::UpdateIssueRequest;
 use tracker_lib::{Language, PaginationParams, TrackerClient, TrackerConfig};
 
 #[tokio::main]
@@ -51,26 +53,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Пример 5: Изменение задачи (PATCH)
     println!("\n=== Пример 5: Изменение задачи ===");
-    let update_body = json!({
-        "summary": "Updated Issue Title",
-        "description": "Updated description",
-        "type": {
-            "id": "1",
-            "key": "bug"
-        },
-        "priority": {
-            "id": "2",
-            "key": "minor"
-        }
-    });
-
-    match client.patch("issues/TEST-1", &update_body, None).await {
-        Ok((response, _)) => {
-            println!(
-                "Updated issue: {}",
-                serde_json::to_string_pretty(&response)?
-            );
-        }
+    let issue_key: IssueKey = "TEST-1".parse()?;
+    let update = UpdateIssueRequest::new()
+        .set("summary", "Updated Issue Title")
+        .set("description", "Updated description")
+        .set("type", json!({"key": "bug"}))
+        .set("priority", json!({"key": "minor"}));
+
+    match client.update_issue(&issue_key, &update, None).await {
+        Ok(issue) => println!("Updated issue: {} - {}", issue.key, issue.summary),
         Err(e) => eprintln!("Error: {}", e),
     }
 
@@ -124,78 +115,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Пример 8: Работа с массивами (добавление значений)
     println!("\n=== Пример 8: Добавление подписчиков ===");
-    let add_followers_body = json!({
-        "followers": {
-            "add": ["user1", "user2"]
-        }
-    });
+    let add_followers = UpdateIssueRequest::new().add("followers", ["user1", "user2"]);
 
-    match client
-        .patch("issues/TEST-1", &add_followers_body, None)
-        .await
-    {
-        Ok((response, _)) => {
-            println!(
-                "Updated followers: {}",
-                serde_json::to_string_pretty(&response)?
-            );
-        }
+    match client.update_issue(&issue_key, &add_followers, None).await {
+        Ok(issue) => println!("Updated followers: {:?}", issue.followers),
         Err(e) => eprintln!("Error: {}", e),
     }
 
     // Пример 9: Замена значений в массиве
     println!("\n=== Пример 9: Замена подписчиков ===");
-    let replace_followers_body = json!({
-        "followers": {
-            "replace": [
-                {"target": "user1", "replacement": "user3"},
-                {"target": "user2", "replacement": "user4"}
-            ]
-        }
-    });
+    let replace_followers =
+        UpdateIssueRequest::new().replace("followers", [("user1", "user3"), ("user2", "user4")]);
 
     match client
-        .patch("issues/TEST-1", &replace_followers_body, None)
+        .update_issue(&issue_key, &replace_followers, None)
         .await
     {
-        Ok((response, _)) => {
-            println!(
-                "Replaced followers: {}",
-                serde_json::to_string_pretty(&response)?
-            );
-        }
+        Ok(issue) => println!("Replaced followers: {:?}", issue.followers),
         Err(e) => eprintln!("Error: {}", e),
     }
 
     // Пример 10: Обнуление поля
     println!("\n=== Пример 10: Очистка поля ===");
-    let clear_field_body = json!({
-        "followers": null
-    });
+    let clear_followers = UpdateIssueRequest::new().clear("followers");
 
-    match client.patch("issues/TEST-1", &clear_field_body, None).await {
-        Ok((response, _)) => {
-            println!(
-                "Cleared field: {}",
-                serde_json::to_string_pretty(&response)?
-            );
-        }
+    match client
+        .update_issue(&issue_key, &clear_followers, None)
+        .await
+    {
+        Ok(issue) => println!("Cleared field: {:?}", issue.followers),
         Err(e) => eprintln!("Error: {}", e),
     }
 
     // Пример 11: Использование специальных символов в тексте
     println!("\n=== Пример 11: Текст с переносами строк и кавычками ===");
-    let description_body = json!({
-        "description": "Внесите исправления:\n1. Используйте значение \"1\" вместо значения \"2\"."
-    });
+    let description = UpdateIssueRequest::new().set(
+        "description",
+        "Внесите исправления:\n1. Используйте значение \"1\" вместо значения \"2\".",
+    );
 
-    match client.patch("issues/TEST-1", &description_body, None).await {
-        Ok((response, _)) => {
-            println!(
-                "Updated description: {}",
-                serde_json::to_string_pretty(&response)?
-            );
-        }
+    match client.update_issue(&issue_key, &description, None).await {
+        Ok(issue) => println!("Updated description: {:?}", issue.description),
         Err(e) => eprintln!("Error: {}", e),
     }
 
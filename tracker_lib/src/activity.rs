@@ -0,0 +1,95 @@
+//! Лента активности очереди
+//!
+//! Недавно обновлённые задачи очереди вместе с их последним изменением из истории:
+//! кто и что поменял. Задачи берутся поиском, история — отдельным запросом для
+//! каждой задачи, поэтому размер ленты ограничен [`ACTIVITY_LIMIT`].
+
+use crate::ids::QueueKey;
+use crate::models::{ChangelogEntry, Issue};
+use crate::search::{SearchParams, SearchRequest};
+use crate::{Result, TrackerClient};
+
+/// Сколько недавно обновлённых задач попадает в ленту
+pub const ACTIVITY_LIMIT: u32 = 50;
+
+/// Задача ленты и её последнее изменение
+#[derive(Debug, Clone)]
+pub struct QueueActivity {
+    pub issue: Issue,
+
+    /// Последняя запись истории; `None`, если история пуста
+    pub latest_change: Option<ChangelogEntry>,
+}
+
+impl QueueActivity {
+    /// Время активности: последнего изменения из истории, иначе обновления задачи
+    pub fn updated_at(&self) -> Option<&str> {
+        self.latest_change
+            .as_ref()
+            .and_then(|change| change.updated_at.as_deref())
+            .or(self.issue.updated_at.as_deref())
+    }
+}
+
+impl TrackerClient {
+    /// Получить ленту активности очереди
+    ///
+    /// # Параметры
+    ///
+    /// * `queue` - Ключ очереди
+    /// * `since` - Дата на языке запросов (например, `2026-10-15`): задачи, обновлённые
+    ///   начиная с неё
+    ///
+    /// Задачи возвращаются от недавно обновлённых к давним, не больше [`ACTIVITY_LIMIT`].
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for item in client.get_queue_activity(&"TREK".parse()?, "2026-10-15").await? {
+    ///     println!("{} {:?}", item.issue.key, item.updated_at());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(queue = %queue))]
+    pub async fn get_queue_activity(
+        &self,
+        queue: &QueueKey,
+        since: &str,
+    ) -> Result<Vec<QueueActivity>> {
+        tracing::debug!("Получение активности очереди с {}", since);
+
+        let request = SearchRequest {
+            query: Some(format!(
+                "Queue: {queue} Updated: >= \"{since}\" \"Sort by\": Updated DESC"
+            )),
+            ..Default::default()
+        };
+        let params = SearchParams {
+            per_page: Some(ACTIVITY_LIMIT),
+            ..Default::default()
+        };
+        let issues = self.search_issues(&request, Some(params)).await?;
+
+        let mut activity = Vec::with_capacity(issues.len());
+        for issue in issues {
+            // История приходит от старых записей к новым
+            let latest_change = self.get_changelog(&issue.issue_key()?, None).await?.pop();
+            activity.push(QueueActivity {
+                issue,
+                latest_change,
+            });
+        }
+
+        tracing::info!(
+            issues_count = activity.len(),
+            "Активность очереди получена успешно"
+        );
+
+        Ok(activity)
+    }
+}
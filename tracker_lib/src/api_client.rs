@@ -1,8 +1,15 @@
 use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::env::VarError;
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::events::{RateLimit, TrackerEvent};
+use crate::ids::{IssueKey, QueueKey};
+use crate::models::Issue;
+use crate::shared::{CachedResponse, SharedState, TokenRefresher};
+use crate::stream::ArraySplitter;
 
 /// Ошибки при работе с API Трекера
 #[derive(Debug, thiserror::Error)]
@@ -30,10 +37,59 @@ pub enum TrackerError {
 
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
+
+    #[error("Invalid identifier: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid sort order: {0}")]
+    InvalidOrder(String),
+
+    #[error("Invalid webhook payload: {0}")]
+    InvalidWebhook(String),
+
+    #[error("Unexpected API response: {0}")]
+    UnexpectedResponse(String),
+
+    #[error("Read-only mode: {method} {path} would modify data and was not sent")]
+    ReadOnly { method: Method, path: String },
+
+    #[error("Sandbox mode: {target} is outside sandbox queue {queue}, the change was not sent")]
+    OutsideSandbox { queue: QueueKey, target: String },
+
+    #[error("Version conflict (409): issue {} was modified concurrently, current version is {:?}", current.key, current.version)]
+    VersionConflict {
+        /// Актуальное состояние задачи на сервере
+        current: Box<Issue>,
+    },
+}
+
+impl From<core_lib::CoreError> for TrackerError {
+    fn from(err: core_lib::CoreError) -> Self {
+        TrackerError::ConfigError(err.to_string())
+    }
 }
 
 pub type Result<T> = std::result::Result<T, TrackerError>;
 
+/// Переменная окружения, включающая режим только для чтения (`1` или `true`)
+pub const READ_ONLY_VAR: &str = "TRACKER_READ_ONLY";
+
+/// Включён ли режим только для чтения переменной `TRACKER_READ_ONLY`
+pub fn read_only_from_env() -> bool {
+    std::env::var(READ_ONLY_VAR).is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes"))
+}
+
+/// Переменная окружения с очередью-песочницей: изменения задач вне неё отклоняются
+pub const SANDBOX_QUEUE_VAR: &str = "TRACKER_SANDBOX_QUEUE";
+
+/// Очередь-песочница из переменной `TRACKER_SANDBOX_QUEUE`
+pub fn sandbox_queue_from_env() -> Result<Option<QueueKey>> {
+    match std::env::var(SANDBOX_QUEUE_VAR) {
+        Ok(queue) if !queue.trim().is_empty() => queue.parse().map(Some),
+        _ => Ok(None),
+    }
+}
+
 /// Параметры постраничной навигации
 #[derive(Debug, Clone, Serialize)]
 pub struct PaginationParams {
@@ -65,6 +121,19 @@ pub struct PaginationMeta {
     pub total_count: Option<u32>,
 }
 
+/// Результат условного запроса с `If-None-Match`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conditional<T> {
+    /// Ресурс изменился (или ETag не передавался)
+    Modified {
+        value: T,
+        /// ETag новой версии ресурса для следующего запроса
+        etag: Option<String>,
+    },
+    /// Сервер ответил 304: ресурс не изменился
+    NotModified,
+}
+
 /// Язык локализации ответов API
 #[derive(Debug, Clone, Copy)]
 pub enum Language {
@@ -81,6 +150,69 @@ impl Language {
     }
 }
 
+/// Переменная окружения с идентификатором организации
+pub const ORG_ID_VAR: &str = "TRACKER_ORG_ID";
+
+/// Переменная окружения с типом организации (`360` или `cloud`)
+pub const ORG_KIND_VAR: &str = "TRACKER_ORG_KIND";
+
+/// Длина идентификатора ресурса Yandex Cloud
+const CLOUD_ID_LEN: usize = 20;
+
+/// Тип организации: от него зависит заголовок с её идентификатором
+///
+/// Организации Яндекс 360 передаются в `X-Org-ID`, организации Yandex Cloud — в
+/// `X-Cloud-Org-ID`. С неверным заголовком API отвечает 401, как на плохой токен.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrgKind {
+    /// Организация Яндекс 360 для бизнеса (числовой идентификатор)
+    Yandex360,
+    /// Организация Yandex Cloud (идентификатор вида `bpf3crucp1v2pgmu4vc2`)
+    Cloud,
+}
+
+impl OrgKind {
+    /// Тип организации по виду идентификатора
+    ///
+    /// Идентификаторы ресурсов Cloud — 20 строчных латинских букв и цифр, начиная с
+    /// буквы; всё остальное считается организацией 360.
+    pub fn detect(org_id: &str) -> Self {
+        let org_id = org_id.trim();
+        let is_cloud_id = org_id.len() == CLOUD_ID_LEN
+            && org_id.starts_with(|c: char| c.is_ascii_lowercase())
+            && org_id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+        if is_cloud_id {
+            OrgKind::Cloud
+        } else {
+            OrgKind::Yandex360
+        }
+    }
+
+    /// Заголовок, в котором передаётся идентификатор организации
+    pub fn header(&self) -> &'static str {
+        match self {
+            OrgKind::Yandex360 => "X-Org-ID",
+            OrgKind::Cloud => "X-Cloud-Org-ID",
+        }
+    }
+}
+
+impl std::str::FromStr for OrgKind {
+    type Err = TrackerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "360" | "yandex360" => Ok(OrgKind::Yandex360),
+            "cloud" => Ok(OrgKind::Cloud),
+            other => Err(TrackerError::ConfigError(format!(
+                "Неизвестный тип организации «{other}», ожидается 360 или cloud"
+            ))),
+        }
+    }
+}
+
 /// Конфигурация клиента API Трекера
 #[derive(Debug, Clone)]
 pub struct TrackerConfig {
@@ -96,8 +228,20 @@ pub struct TrackerConfig {
     /// Идентификатор организации (опционально)
     pub org_id: Option<String>,
 
+    /// Тип организации; если не задан, определяется по виду `org_id`
+    pub org_kind: Option<OrgKind>,
+
     /// Язык локализации (по умолчанию русский)
     pub language: Language,
+
+    /// Режим только для чтения: изменяющие запросы отклоняются без обращения к API
+    pub read_only: bool,
+
+    /// Заголовок User-Agent (по умолчанию `multitool/<версия>`)
+    pub user_agent: String,
+
+    /// Очередь-песочница: задачи других очередей не создаются и не изменяются
+    pub sandbox_queue: Option<QueueKey>,
 }
 
 impl TrackerConfig {
@@ -108,7 +252,11 @@ impl TrackerConfig {
             api_version: "v3".to_string(),
             oauth_token: oauth_token.into(),
             org_id: None,
+            org_kind: None,
             language: Language::Russian,
+            read_only: false,
+            user_agent: core_lib::http::DEFAULT_USER_AGENT.to_string(),
+            sandbox_queue: None,
         }
     }
 
@@ -118,6 +266,19 @@ impl TrackerConfig {
         self
     }
 
+    /// Явно задать тип организации вместо определения по идентификатору
+    pub fn with_org_kind(mut self, org_kind: OrgKind) -> Self {
+        self.org_kind = Some(org_kind);
+        self
+    }
+
+    /// Заголовок и значение идентификатора организации, если он указан
+    pub fn org_header(&self) -> Option<(&'static str, &str)> {
+        let org_id = self.org_id.as_deref()?;
+        let kind = self.org_kind.unwrap_or_else(|| OrgKind::detect(org_id));
+        Some((kind.header(), org_id))
+    }
+
     /// Установить язык локализации
     pub fn with_language(mut self, language: Language) -> Self {
         self.language = language;
@@ -135,56 +296,173 @@ impl TrackerConfig {
         self.api_version = api_version.into();
         self
     }
+
+    /// Включить или выключить режим только для чтения
+    ///
+    /// В этом режиме POST, PATCH и DELETE возвращают `TrackerError::ReadOnly`;
+    /// поиск (`_search`, `_count`) по-прежнему работает.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Ограничить изменения задач очередью-песочницей
+    ///
+    /// Создание задач, изменения задач по пути `issues/{KEY}/...` и массовые изменения
+    /// в других очередях возвращают `TrackerError::OutsideSandbox`. Запросы, которые
+    /// не относятся к задачам (фильтры, очереди), не ограничиваются.
+    pub fn with_sandbox_queue(mut self, queue: QueueKey) -> Self {
+        self.sandbox_queue = Some(queue);
+        self
+    }
+
+    /// Установить User-Agent, по которому запросы видны в диагностике API
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
 }
 
 /// Клиент для работы с API Яндекс.Трекера
+///
+/// Клоны клиента разделяют лимиты запросов, кэш ответов и текущий токен
+/// (см. [`TrackerClient::builder`]).
 #[derive(Debug, Clone)]
 pub struct TrackerClient {
     config: TrackerConfig,
     client: Client,
+    shared: Arc<SharedState>,
+    events: Option<UnboundedSender<TrackerEvent>>,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<crate::fixtures::FixtureMode>,
 }
 
-fn parse_work_proxy_url(work_proxy_value: &str) -> Result<String> {
-    let trimmed_value = work_proxy_value.trim();
-    if trimmed_value.is_empty() {
-        return Err(TrackerError::ConfigError(
-            "Переменная окружения WORK_PROXY установлена, но пуста. Ожидается формат host:port"
-                .to_string(),
-        ));
+/// Построитель клиента с явно подключёнными подсистемами
+///
+/// # Примеры
+///
+/// ```no_run
+/// # use tracker_lib::{TrackerClient, TrackerConfig};
+/// # fn refresh_iam_token() -> tracker_lib::Result<String> { Ok("t1.new".to_string()) }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let (events, _receiver) = tokio::sync::mpsc::unbounded_channel();
+/// let client = TrackerClient::builder(TrackerConfig::new("t1.iam-token"))
+///     .event_sender(events)
+///     .response_cache(true)
+///     .token_refresher(refresh_iam_token)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrackerClientBuilder {
+    config: TrackerConfig,
+    events: Option<UnboundedSender<TrackerEvent>>,
+    refresher: Option<Arc<dyn TokenRefresher>>,
+    cache: bool,
+    #[cfg(feature = "fixtures")]
+    fixtures: Option<crate::fixtures::FixtureMode>,
+}
+
+impl TrackerClientBuilder {
+    /// Отправлять события о запросах в канал (например, для строки состояния)
+    pub fn event_sender(mut self, sender: UnboundedSender<TrackerEvent>) -> Self {
+        self.events = Some(sender);
+        self
     }
 
-    Ok(format!("socks5://{}", trimmed_value))
+    /// Получать новый токен при ответе 401 и повторять запрос один раз
+    pub fn token_refresher(mut self, refresher: impl TokenRefresher + 'static) -> Self {
+        self.refresher = Some(Arc::new(refresher));
+        self
+    }
+
+    /// Кэшировать ответы GET по ETag: повторный запрос уходит с `If-None-Match`,
+    /// и при ответе 304 возвращается сохранённое тело
+    ///
+    /// Кэш хранит до 256 ответов и вытесняет давно не читанные, поэтому подходит
+    /// долгоживущим клиентам.
+    pub fn response_cache(mut self, enabled: bool) -> Self {
+        self.cache = enabled;
+        self
+    }
+
+    /// Поправить конфигурацию, например собранную из окружения
+    pub fn configure(mut self, configure: impl FnOnce(TrackerConfig) -> TrackerConfig) -> Self {
+        self.config = configure(self.config);
+        self
+    }
+
+    /// Записывать ответы в файлы или отвечать из записанных (см. модуль `fixtures`)
+    #[cfg(feature = "fixtures")]
+    pub fn fixtures(mut self, mode: crate::fixtures::FixtureMode) -> Self {
+        self.fixtures = Some(mode);
+        self
+    }
+
+    /// Создать клиент
+    ///
+    /// HTTP-клиент строится через `core_lib`, поэтому учитывает `WORK_PROXY`.
+    pub fn build(self) -> Result<TrackerClient> {
+        let client = core_lib::http::client_builder()?
+            .user_agent(self.config.user_agent.as_str())
+            .build()
+            .map_err(|e| TrackerError::ConfigError(e.to_string()))?;
+        let shared = SharedState::new(self.config.oauth_token.clone(), self.refresher, self.cache);
+
+        Ok(TrackerClient {
+            config: self.config,
+            client,
+            shared: Arc::new(shared),
+            events: self.events,
+            #[cfg(feature = "fixtures")]
+            fixtures: self.fixtures,
+        })
+    }
 }
 
 impl TrackerClient {
     /// Создать новый клиент с заданной конфигурацией
+    ///
+    /// HTTP-клиент строится через `core_lib`, поэтому учитывает `WORK_PROXY`.
     pub fn new(config: TrackerConfig) -> Result<Self> {
-        let mut client_builder = Client::builder();
-
-        match std::env::var("WORK_PROXY") {
-            Ok(work_proxy_value) => {
-                let proxy_url = parse_work_proxy_url(&work_proxy_value)?;
-                let proxy = reqwest::Proxy::all(&proxy_url).map_err(|e| {
-                    TrackerError::ConfigError(format!(
-                        "Некорректная конфигурация WORK_PROXY '{}': {}",
-                        work_proxy_value, e
-                    ))
-                })?;
-                client_builder = client_builder.proxy(proxy);
-            }
-            Err(VarError::NotPresent) => {}
-            Err(VarError::NotUnicode(_)) => {
-                return Err(TrackerError::ConfigError(
-                    "Переменная окружения WORK_PROXY содержит не-UTF-8 значение".to_string(),
-                ));
-            }
+        Self::builder(config).build()
+    }
+
+    /// Построитель клиента: события, кэш ответов и обновление токена
+    pub fn builder(config: TrackerConfig) -> TrackerClientBuilder {
+        TrackerClientBuilder {
+            config,
+            events: None,
+            refresher: None,
+            cache: false,
+            #[cfg(feature = "fixtures")]
+            fixtures: None,
         }
+    }
 
-        let client = client_builder
-            .build()
-            .map_err(|e| TrackerError::ConfigError(e.to_string()))?;
+    /// Последние лимиты запросов, о которых сообщил сервер (общие для всех клонов)
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.shared.rate_limit()
+    }
 
-        Ok(Self { config, client })
+    /// Отправлять события о запросах в канал (например, для строки состояния)
+    pub fn with_event_sender(mut self, sender: UnboundedSender<TrackerEvent>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    /// Записывать ответы в файлы или отвечать из записанных (см. модуль `fixtures`)
+    #[cfg(feature = "fixtures")]
+    pub fn with_fixtures(mut self, mode: crate::fixtures::FixtureMode) -> Self {
+        self.fixtures = Some(mode);
+        self
+    }
+
+    fn emit(&self, event: TrackerEvent) {
+        if let Some(sender) = &self.events {
+            // Получатель мог закрыться; клиент продолжает работать без наблюдателя
+            let _ = sender.send(event);
+        }
     }
 
     /// Создать клиент с минимальной конфигурацией (только OAuth токен)
@@ -194,6 +472,18 @@ impl TrackerClient {
 
     /// Создать клиент из переменной окружения TRACKER_TOKEN
     ///
+    /// Если переменная не задана, токен берётся из системного хранилища ключей.
+    ///
+    /// Адрес API можно переопределить переменной TRACKER_API_URL, например для
+    /// тестового стенда. TRACKER_READ_ONLY=1 включает режим только для чтения,
+    /// TRACKER_SANDBOX_QUEUE ограничивает изменения задач очередью-песочницей.
+    ///
+    /// Идентификатор организации берётся из TRACKER_ORG_ID; заголовок выбирается по
+    /// его виду или по TRACKER_ORG_KIND (`360` или `cloud`).
+    ///
+    /// С фичей `fixtures` учитываются TRACKER_RECORD_DIR и TRACKER_REPLAY_DIR;
+    /// при воспроизведении токен не нужен.
+    ///
     /// # Возвращает
     ///
     /// Result с клиентом или ошибкой, если переменная окружения не установлена
@@ -206,20 +496,199 @@ impl TrackerClient {
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// // Убедитесь, что переменная окружения TRACKER_TOKEN установлена
     /// let client = TrackerClient::from_env()?;
-    /// let issue = client.get_issue("TEST-1", None).await?;
+    /// let issue = client.get_issue(&"TEST-1".parse()?, None).await?;
     /// println!("Задача: {} - {}", issue.key, issue.summary);
     /// # Ok(())
     /// # }
     /// ```
     pub fn from_env() -> Result<Self> {
-        let token = std::env::var("TRACKER_TOKEN").map_err(|_| {
-            TrackerError::ConfigError(
-                "Переменная окружения TRACKER_TOKEN не установлена. \
+        Self::from_env_with(None, |config| config)
+    }
+
+    /// Создать клиент из окружения, как [`Self::from_env`], с поправками вызывающего
+    ///
+    /// `token` заменяет TRACKER_TOKEN, а `configure` меняет собранную из окружения
+    /// конфигурацию до создания клиента. Так приложение передаёт свои флаги (режим
+    /// только для чтения, песочницу) без изменения переменных окружения процесса.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env_with(None, |config| config.with_read_only(true))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env_with(
+        token: Option<String>,
+        configure: impl FnOnce(TrackerConfig) -> TrackerConfig,
+    ) -> Result<Self> {
+        Self::builder_from_env(token)?.configure(configure).build()
+    }
+
+    /// Построитель клиента из окружения, как [`Self::from_env`]
+    ///
+    /// `token` заменяет TRACKER_TOKEN. Через построитель к клиенту из окружения
+    /// подключаются, например, кэш ответов и наблюдатель событий.
+    pub fn builder_from_env(token: Option<String>) -> Result<TrackerClientBuilder> {
+        #[cfg(feature = "fixtures")]
+        if let Some(mode) = crate::fixtures::FixtureMode::from_env() {
+            let token = match mode {
+                crate::fixtures::FixtureMode::Replay(_) => Some("replay".to_string()),
+                crate::fixtures::FixtureMode::Record(_) => token
+                    .clone()
+                    .or_else(|| core_lib::credentials::resolve("TRACKER_TOKEN")),
+            };
+            if let Some(token) = token {
+                return Ok(Self::builder(Self::env_config(token)?).fixtures(mode));
+            }
+        }
+
+        let token = token
+            .or_else(|| core_lib::credentials::resolve("TRACKER_TOKEN"))
+            .ok_or_else(|| {
+                TrackerError::ConfigError(
+                    "Переменная окружения TRACKER_TOKEN не установлена. \
                  Установите её командой: export TRACKER_TOKEN=your-token"
-                    .to_string(),
-            )
-        })?;
-        Self::with_token(token)
+                        .to_string(),
+                )
+            })?;
+        Ok(Self::builder(Self::env_config(token)?))
+    }
+
+    fn env_config(token: String) -> Result<TrackerConfig> {
+        let mut config = TrackerConfig::new(token).with_read_only(read_only_from_env());
+        if let Some(queue) = sandbox_queue_from_env()? {
+            config = config.with_sandbox_queue(queue);
+        }
+        if let Ok(base_url) = std::env::var("TRACKER_API_URL") {
+            config = config.with_base_url(base_url);
+        }
+        if let Some(org_id) = std::env::var(ORG_ID_VAR)
+            .ok()
+            .filter(|id| !id.trim().is_empty())
+        {
+            config = config.with_org_id(org_id.trim());
+        }
+        if let Some(kind) = std::env::var(ORG_KIND_VAR)
+            .ok()
+            .filter(|kind| !kind.trim().is_empty())
+        {
+            config = config.with_org_kind(kind.parse()?);
+        }
+        Ok(config)
+    }
+
+    /// Отправить запрос с текущим токеном, соблюдая паузу лимита запросов
+    ///
+    /// При ответе 401 и заданном источнике токена токен обновляется, а запрос
+    /// повторяется один раз.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        self.shared.wait_for_rate_limit().await;
+
+        let token = self.shared.token();
+        let retry = if self.shared.can_refresh() {
+            request.try_clone()
+        } else {
+            None
+        };
+        let mut response = self.send_once(request, &token).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(retry) = retry {
+                if self.shared.refresh_token(&token).await? {
+                    response = self.send_once(retry, &self.shared.token()).await?;
+                }
+            }
+        }
+
+        self.shared.observe(
+            response.status(),
+            RateLimit::from_headers(response.headers()),
+        );
+        Ok(response)
+    }
+
+    /// Отправить запрос в сеть или, в режиме `fixtures`, через записанные ответы
+    async fn send_once(&self, request: RequestBuilder, token: &str) -> Result<Response> {
+        let request = request.header("Authorization", format!("OAuth {}", token));
+
+        #[cfg(feature = "fixtures")]
+        if let Some(mode) = &self.fixtures {
+            return crate::fixtures::send(mode, &self.client, request).await;
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Включён ли режим только для чтения
+    pub fn is_read_only(&self) -> bool {
+        self.config.read_only
+    }
+
+    /// Очередь-песочница, если изменения ограничены ею
+    pub fn sandbox_queue(&self) -> Option<&QueueKey> {
+        self.config.sandbox_queue.as_ref()
+    }
+
+    /// Отклонить изменяющий запрос в режиме только для чтения или вне песочницы
+    fn check_writable(&self, method: Method, resource_path: &str) -> Result<()> {
+        if is_read_request(&method, resource_path) {
+            return Ok(());
+        }
+        if self.config.read_only {
+            tracing::warn!(%method, resource_path, "Запрос отклонён: режим только для чтения");
+            return Err(TrackerError::ReadOnly {
+                method,
+                path: resource_path.to_string(),
+            });
+        }
+        let issue = resource_path
+            .trim_start_matches('/')
+            .strip_prefix("issues/")
+            .and_then(|rest| rest.split('/').next())
+            .filter(|segment| !segment.is_empty() && !segment.starts_with('_'));
+        match issue {
+            Some(issue) => self.check_sandbox_issues(&[issue]),
+            None => Ok(()),
+        }
+    }
+
+    /// Отклонить изменение задач вне очереди-песочницы
+    ///
+    /// Идентификатор, который не является ключом задачи, тоже отклоняется: по нему
+    /// не понять, из какой задача очереди.
+    pub(crate) fn check_sandbox_issues<S: AsRef<str>>(&self, issues: &[S]) -> Result<()> {
+        let Some(queue) = &self.config.sandbox_queue else {
+            return Ok(());
+        };
+        for issue in issues {
+            let issue = issue.as_ref();
+            let inside = issue
+                .parse::<IssueKey>()
+                .is_ok_and(|key| key.queue() == *queue);
+            if !inside {
+                tracing::warn!(issue, %queue, "Запрос отклонён: задача вне песочницы");
+                return Err(TrackerError::OutsideSandbox {
+                    queue: queue.clone(),
+                    target: issue.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Отклонить создание задачи в очереди, отличной от песочницы
+    pub(crate) fn check_sandbox_queue(&self, target: &QueueKey) -> Result<()> {
+        match &self.config.sandbox_queue {
+            Some(queue) if queue != target => {
+                tracing::warn!(%target, %queue, "Запрос отклонён: очередь вне песочницы");
+                Err(TrackerError::OutsideSandbox {
+                    queue: queue.clone(),
+                    target: target.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Построить полный URL для ресурса
@@ -233,17 +702,12 @@ impl TrackerClient {
 
     /// Подготовить HTTP запрос с необходимыми заголовками
     fn prepare_request(&self, method: Method, url: &str) -> RequestBuilder {
+        // Заголовок Authorization добавляется при отправке: токен может обновиться
         let mut builder = self.client.request(method, url);
 
-        // Добавляем заголовок Authorization
-        builder = builder.header(
-            "Authorization",
-            format!("OAuth {}", self.config.oauth_token),
-        );
-
-        // Добавляем идентификатор организации, если указан
-        if let Some(org_id) = &self.config.org_id {
-            builder = builder.header("X-Org-ID", org_id);
+        // Добавляем идентификатор организации в заголовок её типа, если указан
+        if let Some((header, org_id)) = self.config.org_header() {
+            builder = builder.header(header, org_id);
         }
 
         // Добавляем язык локализации
@@ -252,12 +716,8 @@ impl TrackerClient {
         builder
     }
 
-    /// Обработать ответ и извлечь метаданные пагинации
-    #[tracing::instrument(skip(self, response), fields(status = ?response.status()))]
-    async fn handle_response(&self, response: Response) -> Result<(Value, Option<PaginationMeta>)> {
-        let status = response.status();
-
-        // Извлекаем заголовки пагинации
+    /// Метаданные пагинации из заголовков ответа
+    fn pagination_meta(response: &Response) -> Option<PaginationMeta> {
         let total_pages = response
             .headers()
             .get("X-Total-Pages")
@@ -270,14 +730,19 @@ impl TrackerClient {
             .and_then(|v| v.to_str().ok())
             .and_then(|v| v.parse().ok());
 
-        let pagination_meta = if total_pages.is_some() || total_count.is_some() {
+        if total_pages.is_some() || total_count.is_some() {
             Some(PaginationMeta {
                 total_pages,
                 total_count,
             })
         } else {
             None
-        };
+        }
+    }
+
+    /// Превратить неуспешный ответ в ошибку с текстом от сервера
+    async fn check_status(response: Response) -> Result<Response> {
+        let status = response.status();
 
         if !status.is_success() {
             let error_text = response
@@ -317,12 +782,121 @@ impl TrackerClient {
             return Err(error);
         }
 
+        Ok(response)
+    }
+
+    /// Обработать ответ и извлечь метаданные пагинации
+    #[tracing::instrument(skip(self, response), fields(status = ?response.status()))]
+    async fn handle_response(&self, response: Response) -> Result<(Value, Option<PaginationMeta>)> {
+        let pagination_meta = Self::pagination_meta(&response);
+        let response = Self::check_status(response).await?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            tracing::debug!("Response received successfully (no content)");
+            return Ok((Value::Null, pagination_meta));
+        }
+
         let json_value = response.json::<Value>().await?;
         tracing::debug!("Response received successfully");
         Ok((json_value, pagination_meta))
     }
 
+    /// Прочитать ответ-массив по частям, передавая каждый элемент в `on_item`
+    #[tracing::instrument(skip(self, response, on_item), fields(status = ?response.status()))]
+    async fn handle_stream<T, F>(
+        &self,
+        response: Response,
+        on_item: &mut F,
+    ) -> Result<Option<PaginationMeta>>
+    where
+        T: DeserializeOwned,
+        F: FnMut(T) -> Result<()>,
+    {
+        let pagination_meta = Self::pagination_meta(&response);
+        let mut response = Self::check_status(response).await?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            tracing::debug!("Response received successfully (no content)");
+            return Ok(pagination_meta);
+        }
+
+        let mut splitter = ArraySplitter::new();
+        while let Some(chunk) = response.chunk().await? {
+            splitter.feed(&chunk, |item| on_item(serde_json::from_slice(item)?))?;
+        }
+        splitter.finish()?;
+        tracing::debug!(
+            items = splitter.count(),
+            "Массив в ответе разобран по частям"
+        );
+        Ok(pagination_meta)
+    }
+
+    /// Отправить запрос, уведомив наблюдателя о начале, завершении и лимитах
+    async fn execute(
+        &self,
+        resource_path: &str,
+        request: RequestBuilder,
+    ) -> Result<(Value, Option<PaginationMeta>)> {
+        self.emit(TrackerEvent::RequestStarted {
+            path: resource_path.to_string(),
+        });
+
+        let result = match self.send(request).await {
+            Ok(response) => {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    self.emit(TrackerEvent::RateLimitUpdated(rate_limit));
+                }
+                self.handle_response(response).await
+            }
+            Err(e) => Err(e),
+        };
+
+        self.emit(TrackerEvent::RequestFinished {
+            path: resource_path.to_string(),
+            success: result.is_ok(),
+        });
+
+        result
+    }
+
+    /// Отправить запрос и разобрать ответ-массив поэлементно (см. `handle_stream`)
+    async fn execute_each<T, F>(
+        &self,
+        resource_path: &str,
+        request: RequestBuilder,
+        mut on_item: F,
+    ) -> Result<Option<PaginationMeta>>
+    where
+        T: DeserializeOwned,
+        F: FnMut(T) -> Result<()>,
+    {
+        self.emit(TrackerEvent::RequestStarted {
+            path: resource_path.to_string(),
+        });
+
+        let result = match self.send(request).await {
+            Ok(response) => {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    self.emit(TrackerEvent::RateLimitUpdated(rate_limit));
+                }
+                self.handle_stream(response, &mut on_item).await
+            }
+            Err(e) => Err(e),
+        };
+
+        self.emit(TrackerEvent::RequestFinished {
+            path: resource_path.to_string(),
+            success: result.is_ok(),
+        });
+
+        result
+    }
+
     /// Выполнить GET запрос
+    ///
+    /// С кэшем ответов (`response_cache`) запрос уходит с ETag прошлого ответа, а
+    /// при ответе 304 возвращается сохранённое тело.
     pub async fn get(
         &self,
         resource_path: &str,
@@ -335,8 +909,194 @@ impl TrackerClient {
             request = request.query(params);
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        if self.shared.caches_responses() {
+            let key = cache_key(&url, query_params);
+            return self.execute_cached(resource_path, key, request).await;
+        }
+        self.execute(resource_path, request).await
+    }
+
+    /// Отправить GET запрос через кэш ответов по ETag
+    async fn execute_cached(
+        &self,
+        resource_path: &str,
+        key: String,
+        mut request: RequestBuilder,
+    ) -> Result<(Value, Option<PaginationMeta>)> {
+        let cached = self.shared.cached(&key);
+        if let Some(cached) = &cached {
+            request = request.header("If-None-Match", cached.etag.as_str());
+        }
+
+        self.emit(TrackerEvent::RequestStarted {
+            path: resource_path.to_string(),
+        });
+
+        let result = match self.send(request).await {
+            Ok(response) => {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    self.emit(TrackerEvent::RateLimitUpdated(rate_limit));
+                }
+                match cached {
+                    Some(cached) if response.status() == StatusCode::NOT_MODIFIED => {
+                        tracing::debug!("Ресурс не изменился (304), ответ взят из кэша");
+                        Ok((cached.value, cached.meta))
+                    }
+                    _ => {
+                        let etag = response
+                            .headers()
+                            .get("ETag")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        let result = self.handle_response(response).await;
+                        if let (Ok((value, meta)), Some(etag)) = (&result, etag) {
+                            self.shared.store(
+                                key,
+                                CachedResponse {
+                                    etag,
+                                    value: value.clone(),
+                                    meta: meta.clone(),
+                                },
+                            );
+                        }
+                        result
+                    }
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        self.emit(TrackerEvent::RequestFinished {
+            path: resource_path.to_string(),
+            success: result.is_ok(),
+        });
+
+        result
+    }
+
+    /// Выполнить GET запрос к ресурсу-массиву, разбирая элементы по мере чтения ответа
+    ///
+    /// Ответ не собирается в `Value` целиком: каждый элемент сразу десериализуется в
+    /// `T` и передаётся в `on_item`. Ошибка из `on_item` прерывает чтение.
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, models::Comment};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let mut count = 0;
+    /// client
+    ///     .get_each("issues/TREK-1/comments", None, |_: Comment| {
+    ///         count += 1;
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_each<T, F>(
+        &self,
+        resource_path: &str,
+        query_params: Option<&HashMap<String, String>>,
+        on_item: F,
+    ) -> Result<Option<PaginationMeta>>
+    where
+        T: DeserializeOwned,
+        F: FnMut(T) -> Result<()>,
+    {
+        let url = self.build_url(resource_path);
+        let mut request = self.prepare_request(Method::GET, &url);
+
+        if let Some(params) = query_params {
+            request = request.query(params);
+        }
+
+        self.execute_each(resource_path, request, on_item).await
+    }
+
+    /// Выполнить условный GET запрос: при совпадении ETag сервер не присылает тело
+    pub async fn get_if_none_match(
+        &self,
+        resource_path: &str,
+        etag: Option<&str>,
+        query_params: Option<&HashMap<String, String>>,
+    ) -> Result<Conditional<Value>> {
+        let url = self.build_url(resource_path);
+        let mut request = self.prepare_request(Method::GET, &url);
+
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(params) = query_params {
+            request = request.query(params);
+        }
+
+        self.emit(TrackerEvent::RequestStarted {
+            path: resource_path.to_string(),
+        });
+
+        let result = match self.send(request).await {
+            Ok(response) => {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    self.emit(TrackerEvent::RateLimitUpdated(rate_limit));
+                }
+                if response.status() == StatusCode::NOT_MODIFIED {
+                    tracing::debug!("Ресурс не изменился (304)");
+                    Ok(Conditional::NotModified)
+                } else {
+                    let etag = response
+                        .headers()
+                        .get("ETag")
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    self.handle_response(response)
+                        .await
+                        .map(|(value, _)| Conditional::Modified { value, etag })
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        self.emit(TrackerEvent::RequestFinished {
+            path: resource_path.to_string(),
+            success: result.is_ok(),
+        });
+
+        result
+    }
+
+    /// Выполнить GET запрос к файлу и вернуть тело ответа как есть
+    pub async fn get_bytes(&self, resource_path: &str) -> Result<Vec<u8>> {
+        let url = self.build_url(resource_path);
+        let request = self.prepare_request(Method::GET, &url);
+
+        self.emit(TrackerEvent::RequestStarted {
+            path: resource_path.to_string(),
+        });
+
+        let result = match self.send(request).await {
+            Ok(response) => {
+                if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                    self.emit(TrackerEvent::RateLimitUpdated(rate_limit));
+                }
+                match Self::check_status(response).await {
+                    Ok(response) => response
+                        .bytes()
+                        .await
+                        .map(|bytes| bytes.to_vec())
+                        .map_err(Into::into),
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        self.emit(TrackerEvent::RequestFinished {
+            path: resource_path.to_string(),
+            success: result.is_ok(),
+        });
+
+        result
     }
 
     /// Выполнить GET запрос с параметрами пагинации
@@ -362,8 +1122,7 @@ impl TrackerClient {
             request = request.query(params);
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.execute(resource_path, request).await
     }
 
     /// Выполнить POST запрос
@@ -373,6 +1132,7 @@ impl TrackerClient {
         body: &T,
         query_params: Option<&HashMap<String, String>>,
     ) -> Result<(Value, Option<PaginationMeta>)> {
+        self.check_writable(Method::POST, resource_path)?;
         let url = self.build_url(resource_path);
         let mut request = self.prepare_request(Method::POST, &url).json(body);
 
@@ -380,8 +1140,83 @@ impl TrackerClient {
             request = request.query(params);
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.execute(resource_path, request).await
+    }
+
+    /// Выполнить POST запрос с телом `multipart/form-data` (загрузка файлов)
+    pub async fn post_multipart(
+        &self,
+        resource_path: &str,
+        form: reqwest::multipart::Form,
+        query_params: Option<&HashMap<String, String>>,
+    ) -> Result<(Value, Option<PaginationMeta>)> {
+        self.check_writable(Method::POST, resource_path)?;
+        let url = self.build_url(resource_path);
+        let mut request = self.prepare_request(Method::POST, &url).multipart(form);
+
+        if let Some(params) = query_params {
+            request = request.query(params);
+        }
+
+        self.execute(resource_path, request).await
+    }
+
+    /// Выполнить POST запрос, ответ на который — массив (например, поиск)
+    ///
+    /// Элементы разбираются по мере чтения ответа, как в `get_each`.
+    pub async fn post_each<B, T, F>(
+        &self,
+        resource_path: &str,
+        body: &B,
+        query_params: Option<&HashMap<String, String>>,
+        on_item: F,
+    ) -> Result<Option<PaginationMeta>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+        F: FnMut(T) -> Result<()>,
+    {
+        self.check_writable(Method::POST, resource_path)?;
+        let url = self.build_url(resource_path);
+        let mut request = self.prepare_request(Method::POST, &url).json(body);
+
+        if let Some(params) = query_params {
+            request = request.query(params);
+        }
+
+        self.execute_each(resource_path, request, on_item).await
+    }
+
+    /// Параметры запроса с известной версией задачи для оптимистичной блокировки
+    pub(crate) fn version_query(version: Option<u32>) -> Option<HashMap<String, String>> {
+        version.map(|version| HashMap::from([("version".to_string(), version.to_string())]))
+    }
+
+    /// Превратить ответ 409 на запрос с версией в `VersionConflict` с актуальной задачей
+    pub(crate) async fn check_version_conflict<T>(
+        &self,
+        issue_id: &str,
+        version: Option<u32>,
+        result: Result<T>,
+    ) -> Result<T> {
+        match result {
+            Err(TrackerError::ApiError {
+                status: StatusCode::CONFLICT,
+                ..
+            }) if version.is_some() => {
+                tracing::warn!(
+                    issue_id,
+                    version = ?version,
+                    "Задача изменилась с момента чтения"
+                );
+                // Версию передают вместе с ключом из прочитанной задачи
+                let current = self.get_issue(&issue_id.parse()?, None).await?;
+                Err(TrackerError::VersionConflict {
+                    current: Box::new(current),
+                })
+            }
+            other => other,
+        }
     }
 
     /// Выполнить PATCH запрос
@@ -391,6 +1226,7 @@ impl TrackerClient {
         body: &T,
         query_params: Option<&HashMap<String, String>>,
     ) -> Result<(Value, Option<PaginationMeta>)> {
+        self.check_writable(Method::PATCH, resource_path)?;
         let url = self.build_url(resource_path);
         let mut request = self.prepare_request(Method::PATCH, &url).json(body);
 
@@ -398,8 +1234,7 @@ impl TrackerClient {
             request = request.query(params);
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.execute(resource_path, request).await
     }
 
     /// Выполнить DELETE запрос
@@ -408,6 +1243,7 @@ impl TrackerClient {
         resource_path: &str,
         query_params: Option<&HashMap<String, String>>,
     ) -> Result<(Value, Option<PaginationMeta>)> {
+        self.check_writable(Method::DELETE, resource_path)?;
         let url = self.build_url(resource_path);
         let mut request = self.prepare_request(Method::DELETE, &url);
 
@@ -415,9 +1251,34 @@ impl TrackerClient {
             request = request.query(params);
         }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        self.execute(resource_path, request).await
+    }
+}
+
+/// Ключ кэша: адрес и параметры запроса в порядке имён
+fn cache_key(url: &str, query_params: Option<&HashMap<String, String>>) -> String {
+    let mut params: Vec<(&String, &String)> = query_params
+        .map(|params| params.iter().collect())
+        .unwrap_or_default();
+    params.sort();
+    let query: Vec<String> = params
+        .into_iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    format!("{url}?{}", query.join("&"))
+}
+
+/// Запрос только читает данные: GET или POST поиска (`issues/_search`, `issues/_count`)
+fn is_read_request(method: &Method, resource_path: &str) -> bool {
+    if *method == Method::GET {
+        return true;
     }
+    let last_segment = resource_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    *method == Method::POST && matches!(last_segment, "_search" | "_count")
 }
 
 #[cfg(test)]
@@ -433,6 +1294,97 @@ mod tests {
         assert_eq!(config.oauth_token, "test-token");
         assert_eq!(config.org_id, Some("123".to_string()));
         assert_eq!(config.language.as_str(), "en");
+        assert!(config.user_agent.starts_with("multitool/"));
+        assert_eq!(
+            config.with_user_agent("release-bot/1.0").user_agent,
+            "release-bot/1.0"
+        );
+    }
+
+    #[test]
+    fn test_org_header_detects_kind() {
+        let config = TrackerConfig::new("test-token").with_org_id("123");
+        assert_eq!(config.org_header(), Some(("X-Org-ID", "123")));
+        assert_eq!(OrgKind::detect("test-org-123"), OrgKind::Yandex360);
+
+        let config = TrackerConfig::new("test-token").with_org_id("bpf3crucp1v2pgmu4vc2");
+        assert_eq!(
+            config.org_header(),
+            Some(("X-Cloud-Org-ID", "bpf3crucp1v2pgmu4vc2"))
+        );
+
+        let config = config.with_org_kind("360".parse().unwrap());
+        assert_eq!(
+            config.org_header(),
+            Some(("X-Org-ID", "bpf3crucp1v2pgmu4vc2"))
+        );
+        assert!(TrackerConfig::new("test-token").org_header().is_none());
+        assert!("cloudy".parse::<OrgKind>().is_err());
+    }
+
+    #[test]
+    fn test_read_only_allows_only_reading_requests() {
+        let client =
+            TrackerClient::new(TrackerConfig::new("test-token").with_read_only(true)).unwrap();
+
+        assert!(client
+            .check_writable(Method::POST, "issues/_search")
+            .is_ok());
+        assert!(client
+            .check_writable(Method::POST, "worklog/_search/")
+            .is_ok());
+        assert!(client.check_writable(Method::GET, "issues/TEST-1").is_ok());
+        assert!(matches!(
+            client.check_writable(Method::POST, "issues/"),
+            Err(TrackerError::ReadOnly { method, .. }) if method == Method::POST
+        ));
+        assert!(client
+            .check_writable(Method::PATCH, "issues/TEST-1")
+            .is_err());
+        assert!(client
+            .check_writable(Method::DELETE, "issues/TEST-1/worklog/1")
+            .is_err());
+
+        let writable = TrackerClient::with_token("test-token").unwrap();
+        assert!(!writable.is_read_only());
+        assert!(writable.check_writable(Method::POST, "issues/").is_ok());
+    }
+
+    #[test]
+    fn test_sandbox_rejects_issues_of_other_queues() {
+        let client = TrackerClient::new(
+            TrackerConfig::new("test-token").with_sandbox_queue("SANDBOX".parse().unwrap()),
+        )
+        .unwrap();
+        assert_eq!(client.sandbox_queue().unwrap().as_str(), "SANDBOX");
+
+        assert!(client
+            .check_writable(Method::PATCH, "issues/SANDBOX-3")
+            .is_ok());
+        assert!(client
+            .check_writable(Method::POST, "issues/SANDBOX-3/comments")
+            .is_ok());
+        assert!(client.check_writable(Method::POST, "issues/").is_ok());
+        assert!(client.check_writable(Method::POST, "filters").is_ok());
+        assert!(client.check_writable(Method::GET, "issues/PROD-1").is_ok());
+        assert!(matches!(
+            client.check_writable(Method::DELETE, "issues/PROD-1/worklog/2"),
+            Err(TrackerError::OutsideSandbox { target, .. }) if target == "PROD-1"
+        ));
+        // По внутреннему идентификатору очередь задачи не определить
+        assert!(client
+            .check_writable(Method::PATCH, "issues/507f1f77bcf86cd799439011")
+            .is_err());
+
+        assert!(client
+            .check_sandbox_queue(&"SANDBOX".parse().unwrap())
+            .is_ok());
+        assert!(client
+            .check_sandbox_queue(&"PROD".parse().unwrap())
+            .is_err());
+        assert!(client
+            .check_sandbox_issues(&["SANDBOX-1", "PROD-2"])
+            .is_err());
     }
 
     #[test]
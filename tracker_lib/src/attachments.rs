@@ -0,0 +1,318 @@
+//! Модуль для работы с вложениями задач
+//!
+//! Список файлов, прикреплённых к задаче, загрузка нового файла и скачивание файла
+//! по идентификатору.
+//!
+//! Файл уходит одним запросом; тело читается из потока, и каждые
+//! [`UPLOAD_PROGRESS_STEP`] байт вызывается обработчик прогресса. Загрузка повторяется
+//! до [`UPLOAD_RETRIES`] раз после ошибок, при которых вложение точно не создано: не
+//! удалось соединиться или Трекер ответил 429 или 503 (тогда повтор ждёт `Retry-After`).
+//! После 502, 504 и обрыва отправленного тела нельзя узнать, создал ли Трекер
+//! вложение, и повтор мог бы его продублировать.
+//!
+//! API Трекера принимает файл только целиком и не умеет продолжать загрузку с
+//! места обрыва, поэтому каждый повтор отправляет файл заново с нулевого смещения.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::comments::is_transient;
+use crate::ids::IssueKey;
+use crate::models::User;
+use crate::{Result, TrackerClient, TrackerError};
+
+/// Через сколько переданных байт сообщается прогресс загрузки
+pub const UPLOAD_PROGRESS_STEP: usize = 256 * 1024;
+
+/// Сколько раз повторять загрузку после временной ошибки
+pub const UPLOAD_RETRIES: u32 = 3;
+
+/// Пауза перед первым повтором; каждая следующая вдвое длиннее
+const UPLOAD_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Ход загрузки вложения
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Сколько байт передано в текущей попытке
+    pub sent: u64,
+
+    /// Размер файла в байтах
+    pub total: u64,
+
+    /// Номер попытки, начиная с 1
+    pub attempt: u32,
+}
+
+/// Файл, прикреплённый к задаче
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор вложения
+    pub id: String,
+
+    /// Имя файла
+    pub name: String,
+
+    /// Адрес для скачивания файла
+    pub content: Option<String>,
+
+    /// MIME-тип файла (например, `image/png`)
+    pub mimetype: Option<String>,
+
+    /// Размер файла в байтах
+    pub size: Option<u64>,
+
+    /// Дата и время загрузки
+    pub created_at: Option<String>,
+
+    /// Автор вложения
+    pub created_by: Option<User>,
+}
+
+impl TrackerClient {
+    /// Получить вложения задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for attachment in client.get_attachments(&"TREK-1".parse()?).await? {
+    ///     println!("{} {}", attachment.id, attachment.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_attachments(&self, issue_key: &IssueKey) -> Result<Vec<Attachment>> {
+        tracing::debug!("Получение вложений задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/attachments", issue_key);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+        let attachments: Vec<Attachment> = serde_json::from_value(json_value)?;
+
+        tracing::info!(count = attachments.len(), "Вложения получены успешно");
+
+        Ok(attachments)
+    }
+
+    /// Прикрепить файл к задаче
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `name` - Имя файла, под которым он появится в задаче
+    /// * `data` - Содержимое файла
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let data = std::fs::read("screen.png")?;
+    /// let attachment = client.upload_attachment(&"TREK-1".parse()?, "screen.png", data).await?;
+    /// println!("Загружено вложение {}", attachment.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, data), fields(issue_key = %issue_key, size = data.len()))]
+    pub async fn upload_attachment(
+        &self,
+        issue_key: &IssueKey,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<Attachment> {
+        self.upload_attachment_with_progress(issue_key, name, data, |_| {})
+            .await
+    }
+
+    /// Прикрепить файл к задаче, сообщая о ходе загрузки
+    ///
+    /// `on_progress` вызывается каждые [`UPLOAD_PROGRESS_STEP`] переданных байт. Если
+    /// соединение не установилось или Трекер ответил 429 или 503, файл отправляется
+    /// заново целиком (докачки API не поддерживает), а прогресс начинается с нуля со
+    /// следующим номером попытки; пауза учитывает `Retry-After`. Остальные ошибки не
+    /// повторяются: вложение могло уже появиться в задаче.
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `name` - Имя файла, под которым он появится в задаче
+    /// * `data` - Содержимое файла
+    /// * `on_progress` - Обработчик прогресса
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let data = std::fs::read("dump.tar.gz")?;
+    /// client
+    ///     .upload_attachment_with_progress(&"TREK-1".parse()?, "dump.tar.gz", data, |progress| {
+    ///         eprint!("\r{} / {} байт", progress.sent, progress.total);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(
+        skip(self, data, on_progress),
+        fields(issue_key = %issue_key, size = data.len())
+    )]
+    pub async fn upload_attachment_with_progress(
+        &self,
+        issue_key: &IssueKey,
+        name: &str,
+        data: Vec<u8>,
+        on_progress: impl Fn(UploadProgress) + Send + Sync + 'static,
+    ) -> Result<Attachment> {
+        tracing::debug!("Загрузка вложения: {}", name);
+
+        let resource_path = format!("issues/{}/attachments", issue_key);
+        let query_params = HashMap::from([("filename".to_string(), name.to_string())]);
+        let data: Arc<[u8]> = data.into();
+        let on_progress = Arc::new(on_progress);
+        let mut attempt = 1;
+        let json_value = loop {
+            let form = upload_form(name, Arc::clone(&data), attempt, Arc::clone(&on_progress));
+            match self
+                .post_multipart(&resource_path, form, Some(&query_params))
+                .await
+            {
+                Err(e) if attempt <= UPLOAD_RETRIES && is_transient(&e) => {
+                    let backoff = UPLOAD_RETRY_BACKOFF * 2u32.pow(attempt - 1);
+                    tracing::warn!(attempt, ?backoff, error = %e, "Повтор загрузки вложения");
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => break result?.0,
+            }
+        };
+        let attachment: Attachment = serde_json::from_value(json_value)?;
+
+        tracing::info!(attachment_id = %attachment.id, attempt, "Вложение загружено успешно");
+
+        Ok(attachment)
+    }
+
+    /// Скачать вложение задачи
+    ///
+    /// Возвращает описание вложения и содержимое файла.
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `attachment_id` - Идентификатор вложения
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let (attachment, data) = client.download_attachment(&"TREK-1".parse()?, "4159").await?;
+    /// std::fs::write(&attachment.name, data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn download_attachment(
+        &self,
+        issue_key: &IssueKey,
+        attachment_id: &str,
+    ) -> Result<(Attachment, Vec<u8>)> {
+        tracing::debug!("Скачивание вложения: {}", attachment_id);
+
+        let attachment = self
+            .get_attachments(issue_key)
+            .await?
+            .into_iter()
+            .find(|attachment| attachment.id == attachment_id)
+            .ok_or_else(|| TrackerError::NotFound {
+                resource: format!("вложение {attachment_id} в задаче {issue_key}"),
+            })?;
+
+        let resource_path = format!(
+            "issues/{}/attachments/{}/{}",
+            issue_key,
+            attachment.id,
+            encode_path_segment(&attachment.name)
+        );
+        let data = self.get_bytes(&resource_path).await?;
+
+        tracing::info!(size = data.len(), "Вложение скачано успешно");
+
+        Ok((attachment, data))
+    }
+}
+
+/// Форма с файлом, тело которой вызывает `on_progress` каждые [`UPLOAD_PROGRESS_STEP`] байт
+fn upload_form<F>(
+    name: &str,
+    data: Arc<[u8]>,
+    attempt: u32,
+    on_progress: Arc<F>,
+) -> reqwest::multipart::Form
+where
+    F: Fn(UploadProgress) + Send + Sync + 'static,
+{
+    let total = data.len();
+    let steps = (0..total).step_by(UPLOAD_PROGRESS_STEP).map(move |offset| {
+        let end = (offset + UPLOAD_PROGRESS_STEP).min(total);
+        on_progress(UploadProgress {
+            sent: end as u64,
+            total: total as u64,
+            attempt,
+        });
+        Ok::<_, std::io::Error>(data[offset..end].to_vec())
+    });
+    let body = reqwest::Body::wrap_stream(futures_util::stream::iter(steps));
+    let part = reqwest::multipart::Part::stream_with_length(body, total as u64)
+        .file_name(name.to_string());
+    reqwest::multipart::Form::new().part("file_data", part)
+}
+
+/// Имя файла для пути запроса: всё, кроме безопасных символов, кодируется как `%XX`
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("screen-1.png"), "screen-1.png");
+        assert_eq!(
+            encode_path_segment("отчёт #2.pdf"),
+            "%D0%BE%D1%82%D1%87%D1%91%D1%82%20%232.pdf"
+        );
+    }
+}
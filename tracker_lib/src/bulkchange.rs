@@ -0,0 +1,163 @@
+//! Модуль для массового изменения задач Яндекс.Трекера
+//!
+//! Трекер выполняет массовое изменение асинхронно: [`TrackerClient::bulk_update`]
+//! создаёт операцию и сразу возвращает её, а [`TrackerClient::wait_bulk_change`]
+//! опрашивает статус, пока операция не завершится.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::User;
+use crate::{Result, TrackerClient};
+
+/// Пауза между запросами статуса массового изменения
+pub const BULK_CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Тело запроса массового изменения задач
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkUpdateRequest {
+    /// Ключи изменяемых задач
+    pub issues: Vec<String>,
+
+    /// Новые значения полей в формате PATCH задачи, например
+    /// `{"tags": {"add": ["needs-review"]}}`
+    pub values: serde_json::Value,
+
+    /// Уведомлять ли пользователей об изменении
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify: Option<bool>,
+}
+
+impl BulkUpdateRequest {
+    /// Создать запрос изменения полей `values` во всех задачах `issues`
+    pub fn new(issues: Vec<String>, values: serde_json::Value) -> Self {
+        Self {
+            issues,
+            values,
+            notify: None,
+        }
+    }
+}
+
+/// Операция массового изменения и её ход
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkChange {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор операции
+    pub id: String,
+
+    /// Автор операции
+    #[serde(rename = "createdBy")]
+    pub created_by: Option<User>,
+
+    /// Статус: `CREATED`, `COMPLETE`, `FAILED` и промежуточные
+    pub status: Option<String>,
+
+    /// Описание статуса
+    #[serde(rename = "statusText")]
+    pub status_text: Option<String>,
+
+    /// Процент обработанных задач
+    #[serde(rename = "executionIssuePercent")]
+    pub execution_issue_percent: Option<u32>,
+
+    /// Количество задач в операции
+    #[serde(rename = "totalIssues")]
+    pub total_issues: Option<u32>,
+
+    /// Количество изменённых задач
+    #[serde(rename = "totalCompletedIssues")]
+    pub total_completed_issues: Option<u32>,
+}
+
+impl BulkChange {
+    /// Завершилась ли операция, успешно или нет
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_deref(), Some("COMPLETE" | "FAILED"))
+    }
+
+    /// Завершилась ли операция успешно
+    pub fn is_complete(&self) -> bool {
+        self.status.as_deref() == Some("COMPLETE")
+    }
+}
+
+impl TrackerClient {
+    /// Запустить массовое изменение задач
+    ///
+    /// # Параметры
+    ///
+    /// * `request` - Ключи задач и новые значения полей
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, bulkchange::BulkUpdateRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let request = BulkUpdateRequest::new(
+    ///     vec!["TREK-1".to_string(), "TREK-2".to_string()],
+    ///     serde_json::json!({ "tags": { "add": ["needs-review"] } }),
+    /// );
+    /// let change = client.bulk_update(&request).await?;
+    /// let change = client.wait_bulk_change(&change.id, std::time::Duration::from_secs(60)).await?;
+    /// println!("{:?}", change.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(count = request.issues.len()))]
+    pub async fn bulk_update(&self, request: &BulkUpdateRequest) -> Result<BulkChange> {
+        tracing::debug!("Запуск массового изменения задач");
+        self.check_sandbox_issues(&request.issues)?;
+
+        let (json_value, _) = self.post("bulkchange/_update", request, None).await?;
+        let change: BulkChange = serde_json::from_value(json_value)?;
+
+        tracing::info!(bulk_change_id = %change.id, "Массовое изменение запущено");
+
+        Ok(change)
+    }
+
+    /// Получить статус массового изменения
+    ///
+    /// # Параметры
+    ///
+    /// * `id` - Идентификатор операции из [`TrackerClient::bulk_update`]
+    #[tracing::instrument(skip(self))]
+    pub async fn get_bulk_change(&self, id: &str) -> Result<BulkChange> {
+        let resource_path = format!("bulkchange/{}", id);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+        let change: BulkChange = serde_json::from_value(json_value)?;
+
+        tracing::debug!(status = ?change.status, "Статус массового изменения получен");
+
+        Ok(change)
+    }
+
+    /// Дождаться завершения массового изменения
+    ///
+    /// Статус запрашивается раз в [`BULK_CHANGE_POLL_INTERVAL`]. Возвращает операцию
+    /// в конечном статусе (`COMPLETE` или `FAILED`), а если за `timeout` она не
+    /// завершилась — последний полученный статус: проверьте [`BulkChange::is_finished`].
+    #[tracing::instrument(skip(self))]
+    pub async fn wait_bulk_change(&self, id: &str, timeout: Duration) -> Result<BulkChange> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let change = self.get_bulk_change(id).await?;
+            if change.is_finished() {
+                tracing::info!(status = ?change.status, "Массовое изменение завершено");
+                return Ok(change);
+            }
+            if Instant::now() + BULK_CHANGE_POLL_INTERVAL > deadline {
+                tracing::warn!(status = ?change.status, "Массовое изменение не завершилось вовремя");
+                return Ok(change);
+            }
+            tokio::time::sleep(BULK_CHANGE_POLL_INTERVAL).await;
+        }
+    }
+}
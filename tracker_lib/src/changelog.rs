@@ -0,0 +1,61 @@
+//! Модуль для получения истории изменений задач в Яндекс.Трекере
+
+use std::collections::HashMap;
+
+use crate::ids::IssueKey;
+use crate::models::ChangelogEntry;
+use crate::{Result, TrackerClient};
+
+/// Максимальное количество записей истории в ответе
+const CHANGELOG_PER_PAGE: u32 = 100;
+
+impl TrackerClient {
+    /// Получить историю изменений задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `field` - Идентификатор поля, если нужны только его изменения (например, `resolution`)
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let changes = client.get_changelog(&"TREK-123".parse()?, Some("status")).await?;
+    /// println!("Смен статуса: {}", changes.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_changelog(
+        &self,
+        issue_key: &IssueKey,
+        field: Option<&str>,
+    ) -> Result<Vec<ChangelogEntry>> {
+        tracing::debug!("Получение истории изменений задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/changelog", issue_key);
+        let mut query_params = HashMap::new();
+        query_params.insert("perPage".to_string(), CHANGELOG_PER_PAGE.to_string());
+        if let Some(field) = field {
+            query_params.insert("field".to_string(), field.to_string());
+        }
+
+        let mut entries: Vec<ChangelogEntry> = Vec::new();
+        self.get_each(&resource_path, Some(&query_params), |entry| {
+            entries.push(entry);
+            Ok(())
+        })
+        .await?;
+
+        tracing::info!(
+            entries_count = entries.len(),
+            "История изменений получена успешно"
+        );
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,137 @@
+//! Модуль для работы с чеклистом задачи в Яндекс.Трекере
+//!
+//! Добавление и изменение пункта возвращают задачу целиком; клиент достаёт из неё
+//! обновлённый чеклист, чтобы не запрашивать его отдельно.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ids::IssueKey;
+use crate::models::User;
+use crate::{Result, TrackerClient};
+
+/// Пункт чеклиста задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Идентификатор пункта
+    pub id: String,
+
+    /// Текст пункта
+    pub text: String,
+
+    /// Отмечен ли пункт выполненным
+    #[serde(default)]
+    pub checked: bool,
+
+    /// Исполнитель пункта
+    pub assignee: Option<User>,
+
+    /// Тип пункта: `standard` или `criterion`
+    #[serde(rename = "checklistItemType")]
+    pub item_type: Option<String>,
+}
+
+/// Текст и отметка пункта для добавления или изменения
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChecklistItemRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+}
+
+impl ChecklistItemRequest {
+    /// Новый пункт с текстом и отметкой
+    pub fn new(text: impl Into<String>, checked: bool) -> Self {
+        Self {
+            text: Some(text.into()),
+            checked: Some(checked),
+        }
+    }
+
+    /// Изменение только отметки пункта
+    pub fn checked(checked: bool) -> Self {
+        Self {
+            text: None,
+            checked: Some(checked),
+        }
+    }
+}
+
+/// Чеклист из задачи, которую Трекер возвращает после изменения пункта
+fn items_from_issue(issue: serde_json::Value) -> Result<Vec<ChecklistItem>> {
+    match issue.get("checklistItems") {
+        Some(items) => Ok(serde_json::from_value(items.clone())?),
+        None => Ok(Vec::new()),
+    }
+}
+
+impl TrackerClient {
+    /// Получить чеклист задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for item in client.get_checklist(&"TREK-1".parse()?).await? {
+    ///     println!("[{}] {}", if item.checked { "x" } else { " " }, item.text);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_checklist(&self, issue_key: &IssueKey) -> Result<Vec<ChecklistItem>> {
+        let resource_path = format!("issues/{}/checklistItems", issue_key);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+        let items: Vec<ChecklistItem> = serde_json::from_value(json_value)?;
+
+        tracing::debug!(items_count = items.len(), "Чеклист задачи получен");
+
+        Ok(items)
+    }
+
+    /// Добавить пункт в чеклист задачи и вернуть обновлённый чеклист
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn add_checklist_item(
+        &self,
+        issue_key: &IssueKey,
+        request: &ChecklistItemRequest,
+    ) -> Result<Vec<ChecklistItem>> {
+        let resource_path = format!("issues/{}/checklistItems", issue_key);
+        let (json_value, _) = self.post(&resource_path, request, None).await?;
+
+        tracing::info!("Пункт чеклиста добавлен");
+
+        items_from_issue(json_value)
+    }
+
+    /// Изменить текст или отметку пункта чеклиста и вернуть обновлённый чеклист
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn update_checklist_item(
+        &self,
+        issue_key: &IssueKey,
+        item_id: &str,
+        request: &ChecklistItemRequest,
+    ) -> Result<Vec<ChecklistItem>> {
+        let resource_path = format!("issues/{}/checklistItems/{}", issue_key, item_id);
+        let (json_value, _) = self.patch(&resource_path, request, None).await?;
+
+        tracing::info!(checked = ?request.checked, "Пункт чеклиста изменён");
+
+        items_from_issue(json_value)
+    }
+
+    /// Удалить пункт чеклиста
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn delete_checklist_item(&self, issue_key: &IssueKey, item_id: &str) -> Result<()> {
+        let resource_path = format!("issues/{}/checklistItems/{}", issue_key, item_id);
+        self.delete(&resource_path, None).await?;
+
+        tracing::info!("Пункт чеклиста удалён");
+
+        Ok(())
+    }
+}
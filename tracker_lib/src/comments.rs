@@ -0,0 +1,304 @@
+//! Модуль для работы с комментариями задач в Яндекс.Трекере
+//!
+//! Содержит методы для получения и добавления комментариев, в том числе массового:
+//! [`TrackerClient::add_comments_bulk`] отправляет комментарии во много задач с паузами
+//! по лимитам API и повторами после временных ошибок.
+
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use crate::events::RateLimit;
+use crate::ids::IssueKey;
+use crate::models::Comment;
+use crate::{Result, TrackerClient, TrackerError};
+
+/// Пауза между комментариями массовой отправки
+pub const BULK_COMMENT_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Пауза, когда в окне лимита осталось меньше десятой части запросов
+const BULK_COMMENT_SLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Сколько раз повторяется комментарий после временной ошибки
+pub const BULK_COMMENT_RETRIES: u32 = 3;
+
+/// Первая пауза перед повтором; каждая следующая вдвое длиннее
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Тело запроса для добавления комментария
+#[derive(Debug, Clone, Serialize)]
+pub struct AddCommentRequest {
+    /// Текст комментария
+    pub text: String,
+
+    /// Логины пользователей, которых нужно призвать в комментарий
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub summonees: Vec<String>,
+}
+
+impl AddCommentRequest {
+    /// Создать запрос с текстом комментария
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            summonees: Vec::new(),
+        }
+    }
+}
+
+/// Комментарий для массовой отправки
+#[derive(Debug, Clone)]
+pub struct BulkComment {
+    /// Ключ задачи
+    pub issue_key: IssueKey,
+
+    /// Текст комментария и призываемые пользователи
+    pub request: AddCommentRequest,
+}
+
+impl BulkComment {
+    pub fn new(issue_key: IssueKey, text: impl Into<String>) -> Self {
+        Self {
+            issue_key,
+            request: AddCommentRequest::new(text),
+        }
+    }
+}
+
+/// Итог массовой отправки комментариев
+#[derive(Debug, Default)]
+pub struct BulkCommentReport {
+    /// Добавленные комментарии по задачам в порядке отправки
+    pub posted: Vec<(IssueKey, Comment)>,
+
+    /// Задачи, в которые комментарий добавить не удалось, и ошибки
+    pub failed: Vec<(IssueKey, TrackerError)>,
+}
+
+impl BulkCommentReport {
+    /// Все ли комментарии добавлены
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl fmt::Display for BulkCommentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.posted.len() + self.failed.len();
+        write!(
+            f,
+            "Добавлено комментариев: {} из {total}",
+            self.posted.len()
+        )?;
+        for (issue_key, error) in &self.failed {
+            write!(f, "\n  {issue_key}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TrackerClient {
+    /// Получить комментарии задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_comments(&self, issue_key: &IssueKey) -> Result<Vec<Comment>> {
+        tracing::debug!("Получение комментариев задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/comments", issue_key);
+        let mut comments: Vec<Comment> = Vec::new();
+        self.get_each(&resource_path, None, |comment| {
+            comments.push(comment);
+            Ok(())
+        })
+        .await?;
+
+        tracing::info!(
+            comments_count = comments.len(),
+            "Комментарии задачи получены успешно"
+        );
+
+        Ok(comments)
+    }
+
+    /// Добавить комментарий к задаче
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `request` - Текст комментария и призываемые пользователи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, comments::AddCommentRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let comment = client
+    ///     .add_comment(&"TREK-123".parse()?, &AddCommentRequest::new("Взял в работу"))
+    ///     .await?;
+    /// println!("Комментарий: {:?}", comment.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn add_comment(
+        &self,
+        issue_key: &IssueKey,
+        request: &AddCommentRequest,
+    ) -> Result<Comment> {
+        tracing::debug!("Добавление комментария к задаче: {}", issue_key);
+
+        let resource_path = format!("issues/{}/comments", issue_key);
+        let (json_value, _) = self.post(&resource_path, request, None).await?;
+
+        let comment: Comment = serde_json::from_value(json_value)?;
+
+        tracing::info!(comment_id = ?comment.id, "Комментарий добавлен успешно");
+
+        Ok(comment)
+    }
+
+    /// Добавить комментарии во много задач
+    ///
+    /// Комментарии отправляются по одному с паузой [`BULK_COMMENT_INTERVAL`], которая
+    /// растёт, когда лимит запросов почти исчерпан; после ответа 429 клиент сам ждёт
+    /// `Retry-After`. Комментарий повторяется до [`BULK_COMMENT_RETRIES`] раз только после
+    /// ошибок, при которых он точно не создан (429, 503, нет соединения), поэтому
+    /// дублей не бывает. Ответы 502 и 504 не повторяются: шлюз мог успеть передать
+    /// запрос Трекеру, и повтор создал бы второй комментарий. Ошибка одной задачи не
+    /// останавливает отправку.
+    ///
+    /// # Параметры
+    ///
+    /// * `items` - Задачи и тексты комментариев
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, comments::BulkComment};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let items = vec![
+    ///     BulkComment::new("TREK-1".parse()?, "Исправлено в 2.4"),
+    ///     BulkComment::new("TREK-2".parse()?, "Исправлено в 2.4"),
+    /// ];
+    /// let report = client.add_comments_bulk(&items).await;
+    /// println!("{report}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, items), fields(count = items.len()))]
+    pub async fn add_comments_bulk(&self, items: &[BulkComment]) -> BulkCommentReport {
+        tracing::debug!("Массовое добавление комментариев");
+
+        let mut report = BulkCommentReport::default();
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(bulk_pause(self.rate_limit().as_ref())).await;
+            }
+            match self.add_comment_with_retries(item).await {
+                Ok(comment) => report.posted.push((item.issue_key.clone(), comment)),
+                Err(e) => {
+                    tracing::warn!(issue_key = %item.issue_key, error = %e, "Комментарий не добавлен");
+                    report.failed.push((item.issue_key.clone(), e));
+                }
+            }
+        }
+
+        tracing::info!(
+            posted = report.posted.len(),
+            failed = report.failed.len(),
+            "Массовое добавление комментариев завершено"
+        );
+
+        report
+    }
+
+    async fn add_comment_with_retries(&self, item: &BulkComment) -> Result<Comment> {
+        let mut attempt = 0;
+        loop {
+            match self.add_comment(&item.issue_key, &item.request).await {
+                Err(e) if attempt < BULK_COMMENT_RETRIES && is_transient(&e) => {
+                    let backoff = RETRY_BACKOFF * 2u32.pow(attempt);
+                    attempt += 1;
+                    tracing::debug!(attempt, ?backoff, error = %e, "Повтор комментария");
+                    tokio::time::sleep(backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Пауза перед следующим комментарием по последним лимитам сервера
+fn bulk_pause(rate_limit: Option<&RateLimit>) -> Duration {
+    let nearly_exhausted = rate_limit
+        .and_then(|rate_limit| Some((rate_limit.remaining?, rate_limit.limit?)))
+        .is_some_and(|(remaining, limit)| remaining.saturating_mul(10) < limit);
+    if nearly_exhausted {
+        BULK_COMMENT_SLOW_INTERVAL
+    } else {
+        BULK_COMMENT_INTERVAL
+    }
+}
+
+/// Ошибка, после которой комментарий или вложение точно не создано и запрос можно
+/// отправить снова
+///
+/// 502 и 504 сюда не входят: шлюз отвечает ими и тогда, когда Трекер уже принял запрос.
+pub(crate) fn is_transient(error: &TrackerError) -> bool {
+    match error {
+        TrackerError::ApiError { status, .. } => matches!(
+            *status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ),
+        TrackerError::RequestFailed(e) => e.is_connect(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_comment_request_serialization() {
+        let request = AddCommentRequest::new("Первая строка\nВторая строка");
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["text"], "Первая строка\nВторая строка");
+        assert!(json.get("summonees").is_none());
+    }
+
+    #[test]
+    fn test_bulk_pause_slows_down_near_limit() {
+        let rate_limit = |remaining| RateLimit {
+            limit: Some(100),
+            remaining: Some(remaining),
+            retry_after_secs: None,
+        };
+        assert_eq!(bulk_pause(None), BULK_COMMENT_INTERVAL);
+        assert_eq!(bulk_pause(Some(&rate_limit(50))), BULK_COMMENT_INTERVAL);
+        assert_eq!(bulk_pause(Some(&rate_limit(5))), BULK_COMMENT_SLOW_INTERVAL);
+    }
+
+    #[test]
+    fn test_only_unapplied_errors_are_transient() {
+        let api_error = |status| TrackerError::ApiError {
+            status,
+            message: String::new(),
+        };
+        assert!(is_transient(&api_error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(is_transient(&api_error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!is_transient(&api_error(StatusCode::INTERNAL_SERVER_ERROR)));
+        assert!(!is_transient(&api_error(StatusCode::BAD_GATEWAY)));
+        assert!(!is_transient(&api_error(StatusCode::GATEWAY_TIMEOUT)));
+        assert!(!is_transient(&TrackerError::Forbidden));
+    }
+}
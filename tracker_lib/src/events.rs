@@ -0,0 +1,66 @@
+//! События клиента Трекера для внешних наблюдателей (например, строки состояния TUI)
+
+use reqwest::header::HeaderMap;
+
+/// Событие, отправляемое клиентом при выполнении запросов
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackerEvent {
+    /// Запрос отправлен
+    RequestStarted { path: String },
+
+    /// Запрос завершён (успешно или с ошибкой)
+    RequestFinished { path: String, success: bool },
+
+    /// Сервер сообщил текущие лимиты запросов
+    RateLimitUpdated(RateLimit),
+}
+
+/// Лимиты запросов из заголовков ответа
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimit {
+    /// Общее количество запросов в окне
+    pub limit: Option<u32>,
+
+    /// Оставшееся количество запросов в окне
+    pub remaining: Option<u32>,
+
+    /// Через сколько секунд можно повторить запрос
+    pub retry_after_secs: Option<u64>,
+}
+
+impl RateLimit {
+    /// Извлечь лимиты из заголовков; None, если сервер их не прислал
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+        let rate_limit = Self {
+            limit: header("X-RateLimit-Limit").and_then(|v| v.parse().ok()),
+            remaining: header("X-RateLimit-Remaining").and_then(|v| v.parse().ok()),
+            retry_after_secs: header("Retry-After").and_then(|v| v.parse().ok()),
+        };
+
+        (rate_limit != Self::default()).then_some(rate_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "42".parse().unwrap());
+
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(rate_limit.limit, Some(100));
+        assert_eq!(rate_limit.remaining, Some(42));
+        assert_eq!(rate_limit.retry_after_secs, None);
+    }
+
+    #[test]
+    fn test_rate_limit_absent() {
+        assert!(RateLimit::from_headers(&HeaderMap::new()).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! Модуль для работы с сохранёнными фильтрами Яндекс.Трекера
+//!
+//! Фильтр — именованный запрос на языке запросов, доступный в веб-интерфейсе.
+//! Найти задачи по фильтру можно через [`SearchRequest::filter_id`].
+//!
+//! [`SearchRequest::filter_id`]: crate::search::SearchRequest::filter_id
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, TrackerClient};
+
+/// Сохранённый фильтр
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFilter {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор фильтра
+    pub id: u64,
+
+    /// Название фильтра
+    pub name: Option<String>,
+
+    /// Запрос фильтра на языке запросов
+    pub query: Option<String>,
+}
+
+/// Тело запроса для создания и изменения фильтра
+#[derive(Debug, Clone, Serialize)]
+pub struct FilterRequest {
+    /// Название фильтра
+    pub name: String,
+
+    /// Запрос на языке запросов
+    pub query: String,
+}
+
+impl FilterRequest {
+    pub fn new(name: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            query: query.into(),
+        }
+    }
+}
+
+impl TrackerClient {
+    /// Создать сохранённый фильтр
+    ///
+    /// # Параметры
+    ///
+    /// * `request` - Название и запрос фильтра
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, filters::FilterRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let request = FilterRequest::new("Мои баги", "Queue: TREK Type: Bug Assignee: me()");
+    /// let filter = client.create_filter(&request).await?;
+    /// println!("Создан фильтр {}", filter.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(name = %request.name))]
+    pub async fn create_filter(&self, request: &FilterRequest) -> Result<SavedFilter> {
+        tracing::debug!("Создание фильтра: {}", request.query);
+
+        let (json_value, _) = self.post("filters/", request, None).await?;
+        let filter: SavedFilter = serde_json::from_value(json_value)?;
+
+        tracing::info!(filter_id = filter.id, "Фильтр создан успешно");
+
+        Ok(filter)
+    }
+
+    /// Изменить название и запрос сохранённого фильтра
+    ///
+    /// # Параметры
+    ///
+    /// * `filter_id` - Идентификатор фильтра
+    /// * `request` - Новые название и запрос
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, filters::FilterRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let request = FilterRequest::new("Мои баги", "Queue: TREK Type: Bug Resolution: empty()");
+    /// client.update_filter(42, &request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(name = %request.name))]
+    pub async fn update_filter(
+        &self,
+        filter_id: u64,
+        request: &FilterRequest,
+    ) -> Result<SavedFilter> {
+        tracing::debug!("Изменение фильтра: {}", filter_id);
+
+        let resource_path = format!("filters/{}", filter_id);
+        let (json_value, _) = self.patch(&resource_path, request, None).await?;
+        let filter: SavedFilter = serde_json::from_value(json_value)?;
+
+        tracing::info!(filter_id = filter.id, "Фильтр изменён успешно");
+
+        Ok(filter)
+    }
+}
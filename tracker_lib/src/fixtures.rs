@@ -0,0 +1,280 @@
+//! Запись и воспроизведение ответов API (фича `fixtures`)
+//!
+//! В режиме записи клиент ходит в настоящий Трекер и сохраняет каждый ответ
+//! в отдельный JSON файл каталога. В режиме воспроизведения сеть не используется:
+//! ответы читаются из тех же файлов, поэтому тестам и демонстрациям не нужен токен.
+//!
+//! Файл определяется методом, путём, параметрами и телом запроса; адрес API
+//! и заголовки запроса (включая `Authorization`) в файл не попадают.
+//!
+//! ```no_run
+//! # use tracker_lib::{fixtures::FixtureMode, TrackerClient};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Записать ответы живого Трекера
+//! let client = TrackerClient::from_env()?.with_fixtures(FixtureMode::Record("fixtures".into()));
+//! client.get_issue(&"TREK-1".parse()?, None).await?;
+//!
+//! // Позже — воспроизвести их без сети
+//! let client = TrackerClient::with_token("replay")?
+//!     .with_fixtures(FixtureMode::Replay("fixtures".into()));
+//! let issue = client.get_issue(&"TREK-1".parse()?, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use reqwest::header::{
+    HeaderName, CONTENT_ENCODING, CONTENT_LENGTH, SET_COOKIE, TRANSFER_ENCODING,
+};
+use reqwest::{Client, Request, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Result, TrackerError};
+
+/// Каталог для записи ответов (`you` и тесты читают его из окружения)
+pub const RECORD_DIR_VAR: &str = "TRACKER_RECORD_DIR";
+
+/// Каталог с записанными ответами для воспроизведения
+pub const REPLAY_DIR_VAR: &str = "TRACKER_REPLAY_DIR";
+
+/// Заголовки ответа, которые не сохраняются: тело в файле хранится уже разобранным
+const SKIPPED_HEADERS: [HeaderName; 4] = [
+    CONTENT_LENGTH,
+    CONTENT_ENCODING,
+    TRANSFER_ENCODING,
+    SET_COOKIE,
+];
+
+/// Режим работы с файлами ответов
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Выполнять запросы и сохранять ответы в каталог
+    Record(PathBuf),
+    /// Отвечать из каталога без обращения к сети
+    Replay(PathBuf),
+}
+
+impl FixtureMode {
+    /// Режим из `TRACKER_REPLAY_DIR` или `TRACKER_RECORD_DIR`; воспроизведение важнее
+    pub fn from_env() -> Option<Self> {
+        if let Ok(dir) = std::env::var(REPLAY_DIR_VAR) {
+            return Some(Self::Replay(dir.into()));
+        }
+        std::env::var(RECORD_DIR_VAR)
+            .ok()
+            .map(|dir| Self::Record(dir.into()))
+    }
+
+    fn dir(&self) -> &Path {
+        match self {
+            Self::Record(dir) | Self::Replay(dir) => dir,
+        }
+    }
+}
+
+/// Записанный запрос: по нему видно, к какому вызову относится файл
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// Записанный ответ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Тело ответа: JSON, строка для не-JSON ответов или null для пустого тела
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// Содержимое файла с ответом
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+impl RecordedRequest {
+    fn from_request(request: &Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| {
+                serde_json::from_slice(bytes)
+                    .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+            });
+        Self {
+            method: request.method().to_string(),
+            path: request.url().path().to_string(),
+            query: request.url().query().map(str::to_string),
+            body,
+        }
+    }
+
+    /// Имя файла: читаемая часть из метода и пути плюс хеш всего запроса
+    pub fn file_name(&self) -> String {
+        let readable: String = self
+            .path
+            .trim_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let body = self.body.as_ref().map(Value::to_string).unwrap_or_default();
+        let key = format!(
+            "{} {}?{}\n{body}",
+            self.method,
+            self.path,
+            self.query.as_deref().unwrap_or_default()
+        );
+        format!(
+            "{}_{readable}_{:016x}.json",
+            self.method.to_lowercase(),
+            fnv1a(key.as_bytes())
+        )
+    }
+}
+
+/// FNV-1a: имена файлов не должны зависеть от версии компилятора, как у `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Отправить запрос с учётом режима: записать ответ или взять его из файла
+pub(crate) async fn send(
+    mode: &FixtureMode,
+    client: &Client,
+    request: RequestBuilder,
+) -> Result<Response> {
+    let request = request.build()?;
+    let recorded = RecordedRequest::from_request(&request);
+    let file = mode.dir().join(recorded.file_name());
+
+    let response = match mode {
+        FixtureMode::Replay(_) => {
+            let content = std::fs::read_to_string(&file).map_err(|e| {
+                TrackerError::ConfigError(format!(
+                    "Нет записанного ответа для {} {} ({}): {e}",
+                    recorded.method,
+                    recorded.path,
+                    file.display()
+                ))
+            })?;
+            let fixture: Fixture = serde_json::from_str(&content)?;
+            tracing::debug!(file = %file.display(), "Ответ воспроизведён из файла");
+            fixture.response
+        }
+        FixtureMode::Record(dir) => {
+            let response = client.execute(request).await?;
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter(|(name, _)| !SKIPPED_HEADERS.contains(name))
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            let bytes = response.bytes().await?;
+            let body = if bytes.is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_slice(&bytes)
+                    .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+            };
+            let fixture = Fixture {
+                request: recorded,
+                response: RecordedResponse {
+                    status,
+                    headers,
+                    body,
+                },
+            };
+            let write = |content: String| {
+                std::fs::create_dir_all(dir)?;
+                std::fs::write(&file, content)
+            };
+            write(serde_json::to_string_pretty(&fixture)?).map_err(|e| {
+                TrackerError::ConfigError(format!(
+                    "Не удалось записать ответ в {}: {e}",
+                    file.display()
+                ))
+            })?;
+            tracing::debug!(file = %file.display(), "Ответ записан в файл");
+            fixture.response
+        }
+    };
+
+    to_response(response)
+}
+
+fn to_response(recorded: RecordedResponse) -> Result<Response> {
+    let body = match recorded.body {
+        Value::Null => Vec::new(),
+        Value::String(text) => text.into_bytes(),
+        value => serde_json::to_vec(&value)?,
+    };
+    let mut builder = http::Response::builder().status(recorded.status);
+    for (name, value) in &recorded.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .body(body)
+        .map_err(|e| TrackerError::ConfigError(format!("Некорректный записанный ответ: {e}")))?;
+    Ok(Response::from(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(body: Option<Value>) -> RecordedRequest {
+        RecordedRequest {
+            method: "POST".to_string(),
+            path: "/v3/issues/_search".to_string(),
+            query: Some("perPage=50".to_string()),
+            body,
+        }
+    }
+
+    #[test]
+    fn test_file_name_depends_on_whole_request() {
+        let first = request(Some(json!({"query": "Queue: TREK"}))).file_name();
+        let second = request(Some(json!({"query": "Queue: OPS"}))).file_name();
+
+        assert!(first.starts_with("post_v3_issues__search_"));
+        assert!(first.ends_with(".json"));
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            request(Some(json!({"query": "Queue: TREK"}))).file_name()
+        );
+    }
+
+    #[test]
+    fn test_to_response_restores_status_and_headers() {
+        let response = to_response(RecordedResponse {
+            status: 404,
+            headers: BTreeMap::from([("X-Total-Count".to_string(), "3".to_string())]),
+            body: Value::String("Not found".to_string()),
+        })
+        .unwrap();
+
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()["X-Total-Count"], "3");
+    }
+}
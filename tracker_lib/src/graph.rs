@@ -0,0 +1,483 @@
+//! Граф зависимостей задач
+//!
+//! Граф строится обходом в ширину от корневой задачи по связям и родительским
+//! задачам и выводится в DOT, Mermaid или как текстовое дерево блокеров.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use crate::models::{Issue, IssueLink};
+use crate::{Result, TrackerClient};
+
+/// Вид ребра графа
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// `from` блокирует `to`
+    Blocks,
+    /// `from` — родительская задача `to`
+    Parent,
+    /// Прочие связи с идентификатором типа (`relates`, `duplicates`, ...)
+    Related(String),
+}
+
+impl EdgeKind {
+    fn label(&self) -> &str {
+        match self {
+            EdgeKind::Blocks => "blocks",
+            EdgeKind::Parent => "parent of",
+            EdgeKind::Related(kind) => kind,
+        }
+    }
+}
+
+/// Ребро графа между двумя задачами
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// Задача в графе
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyNode {
+    pub key: String,
+    pub summary: Option<String>,
+    pub status: Option<String>,
+    /// Расстояние от корневой задачи
+    pub depth: usize,
+}
+
+/// Граф зависимостей вокруг корневой задачи
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    pub root: String,
+    pub nodes: BTreeMap<String, DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    pub fn new(root: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Добавляет задачу или уточняет её данные, сохраняя минимальную глубину
+    pub fn add_node(
+        &mut self,
+        key: &str,
+        summary: Option<String>,
+        status: Option<String>,
+        depth: usize,
+    ) {
+        let node = self
+            .nodes
+            .entry(key.to_string())
+            .or_insert_with(|| DependencyNode {
+                key: key.to_string(),
+                summary: None,
+                status: None,
+                depth,
+            });
+        node.depth = node.depth.min(depth);
+        if summary.is_some() {
+            node.summary = summary;
+        }
+        if status.is_some() {
+            node.status = status;
+        }
+    }
+
+    /// Добавляет ребро, если такого ещё нет
+    pub fn add_edge(&mut self, edge: DependencyEdge) {
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Задачи, которые блокируют `key`
+    pub fn blockers(&self, key: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::Blocks && edge.to == key)
+            .map(|edge| edge.from.as_str())
+            .collect()
+    }
+
+    /// Граф в формате Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![
+            "digraph dependencies {".to_string(),
+            "  rankdir=LR;".to_string(),
+        ];
+        for node in self.nodes.values() {
+            let mut label = node.key.clone();
+            if let Some(summary) = &node.summary {
+                label.push_str(&format!("\\n{}", summary.replace('"', "\\\"")));
+            }
+            if let Some(status) = &node.status {
+                label.push_str(&format!("\\n[{}]", status.replace('"', "\\\"")));
+            }
+            let style = if node.key == self.root {
+                ", style=bold"
+            } else {
+                ""
+            };
+            lines.push(format!("  \"{}\" [label=\"{label}\"{style}];", node.key));
+        }
+        for edge in &self.edges {
+            lines.push(format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                edge.from,
+                edge.to,
+                edge.kind.label()
+            ));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Граф в формате Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let id = |key: &str| key.replace('-', "_");
+        let mut lines = vec!["graph LR".to_string()];
+        for node in self.nodes.values() {
+            let mut label = node.key.clone();
+            if let Some(summary) = &node.summary {
+                label.push_str(&format!(": {summary}"));
+            }
+            if let Some(status) = &node.status {
+                label.push_str(&format!(" [{status}]"));
+            }
+            lines.push(format!(
+                "  {}[\"{}\"]",
+                id(&node.key),
+                label.replace('"', "#quot;")
+            ));
+        }
+        for edge in &self.edges {
+            lines.push(format!(
+                "  {} -->|{}| {}",
+                id(&edge.from),
+                edge.kind.label(),
+                id(&edge.to)
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Текстовое дерево от корневой задачи: сначала блокеры, затем остальные связи
+    pub fn render_tree(&self) -> String {
+        let mut lines = vec![self.node_line(&self.root)];
+        let mut visited = HashSet::from([self.root.as_str()]);
+        self.render_subtree(&self.root, None, "", &mut visited, &mut lines);
+        lines.join("\n")
+    }
+
+    fn render_subtree<'a>(
+        &'a self,
+        key: &str,
+        parent: Option<&str>,
+        prefix: &str,
+        visited: &mut HashSet<&'a str>,
+        lines: &mut Vec<String>,
+    ) {
+        // Связь с задачей, из которой пришли, уже показана строкой выше
+        let mut relations = self.relations(key);
+        relations.retain(|(_, other)| Some(*other) != parent);
+        for (index, (label, other)) in relations.iter().enumerate() {
+            let last = index + 1 == relations.len();
+            let branch = if last { "└─" } else { "├─" };
+            let first_visit = visited.insert(other);
+            let repeat = if first_visit { "" } else { " …" };
+            lines.push(format!(
+                "{prefix}{branch} {label} {}{repeat}",
+                self.node_line(other)
+            ));
+            if first_visit {
+                let child_prefix = format!("{prefix}{}", if last { "   " } else { "│  " });
+                self.render_subtree(other, Some(key), &child_prefix, visited, lines);
+            }
+        }
+    }
+
+    /// Связи задачи с подписью с её точки зрения, блокеры первыми
+    fn relations(&self, key: &str) -> Vec<(String, &str)> {
+        let mut relations: Vec<(u8, String, &str)> = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                let outgoing = edge.from == key;
+                if !outgoing && edge.to != key {
+                    return None;
+                }
+                let other = if outgoing { &edge.to } else { &edge.from };
+                let (order, label) = match (&edge.kind, outgoing) {
+                    (EdgeKind::Blocks, false) => (0, "⛔ ждёт".to_string()),
+                    (EdgeKind::Blocks, true) => (1, "→ блокирует".to_string()),
+                    (EdgeKind::Parent, false) => (2, "▲ родитель".to_string()),
+                    (EdgeKind::Parent, true) => (3, "▼ подзадача".to_string()),
+                    (EdgeKind::Related(kind), _) => (4, format!("~ {kind}")),
+                };
+                Some((order, label, other.as_str()))
+            })
+            .collect();
+        relations.sort_by_key(|(order, _, _)| *order);
+        relations
+            .into_iter()
+            .map(|(_, label, other)| (label, other))
+            .collect()
+    }
+
+    fn node_line(&self, key: &str) -> String {
+        let Some(node) = self.nodes.get(key) else {
+            return key.to_string();
+        };
+        let mut line = node.key.clone();
+        if let Some(summary) = &node.summary {
+            line.push_str(&format!(" {summary}"));
+        }
+        if let Some(status) = &node.status {
+            line.push_str(&format!(" [{status}]"));
+        }
+        line
+    }
+}
+
+/// Ребро для связи задачи `key`
+///
+/// Для `depends` исходящая связь означает, что задача зависит от связанной, то есть
+/// связанная её блокирует; для `subtask` исходящая связь указывает на подзадачу.
+/// Остальные связи неориентированные, поэтому концы упорядочиваются по ключу.
+pub fn link_edge(key: &str, link: &IssueLink) -> Option<DependencyEdge> {
+    let other = link.object.key.clone()?;
+    let key = key.to_string();
+    let type_id = link
+        .link_type
+        .as_ref()
+        .and_then(|link_type| link_type.id.as_deref())
+        .unwrap_or("relates");
+    let outward = link.direction.as_deref() != Some("inward");
+
+    let (from, to, kind) = match type_id {
+        "depends" if outward => (other, key, EdgeKind::Blocks),
+        "depends" => (key, other, EdgeKind::Blocks),
+        "subtask" if outward => (key, other, EdgeKind::Parent),
+        "subtask" => (other, key, EdgeKind::Parent),
+        _ => {
+            let (from, to) = if key <= other {
+                (key, other)
+            } else {
+                (other, key)
+            };
+            (from, to, EdgeKind::Related(type_id.to_string()))
+        }
+    };
+    Some(DependencyEdge { from, to, kind })
+}
+
+fn status_display(issue: &Issue) -> Option<String> {
+    issue
+        .status
+        .as_ref()
+        .and_then(|status| status.display.clone())
+}
+
+impl TrackerClient {
+    /// Построить граф зависимостей задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `root_key` - Ключ корневой задачи
+    /// * `depth` - Сколько шагов по связям и родительским задачам делать от корня
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let graph = client.build_dependency_graph("TREK-123", 2).await?;
+    /// println!("{}", graph.render_tree());
+    /// println!("Блокеры: {:?}", graph.blockers("TREK-123"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(root_key = %root_key))]
+    pub async fn build_dependency_graph(
+        &self,
+        root_key: &str,
+        depth: usize,
+    ) -> Result<DependencyGraph> {
+        tracing::debug!("Построение графа зависимостей");
+
+        let mut graph = DependencyGraph::new(root_key);
+        let mut queue = VecDeque::from([(root_key.to_string(), 0)]);
+        let mut visited = HashSet::new();
+
+        while let Some((key, level)) = queue.pop_front() {
+            if !visited.insert(key.clone()) {
+                continue;
+            }
+            let issue = self.get_issue(&key.parse()?, None).await?;
+            if level == 0 {
+                graph.root = issue.key.clone();
+            }
+            graph.add_node(
+                &issue.key,
+                Some(issue.summary.clone()),
+                status_display(&issue),
+                level,
+            );
+
+            let mut neighbours = Vec::new();
+            if let Some(parent_key) = issue.parent.as_ref().and_then(|p| p.key.clone()) {
+                let parent_summary = issue.parent.as_ref().and_then(|p| p.display.clone());
+                graph.add_node(&parent_key, parent_summary, None, level + 1);
+                graph.add_edge(DependencyEdge {
+                    from: parent_key.clone(),
+                    to: issue.key.clone(),
+                    kind: EdgeKind::Parent,
+                });
+                neighbours.push(parent_key);
+            }
+
+            for link in self.get_links(&issue.issue_key()?).await? {
+                let Some(edge) = link_edge(&issue.key, &link) else {
+                    continue;
+                };
+                let other = if edge.from == issue.key {
+                    edge.to.clone()
+                } else {
+                    edge.from.clone()
+                };
+                graph.add_node(
+                    &other,
+                    link.object.display.clone(),
+                    link.status.as_ref().and_then(|s| s.display.clone()),
+                    level + 1,
+                );
+                graph.add_edge(edge);
+                neighbours.push(other);
+            }
+
+            if level < depth {
+                queue.extend(
+                    neighbours
+                        .into_iter()
+                        .filter(|other| !visited.contains(other))
+                        .map(|other| (other, level + 1)),
+                );
+            }
+        }
+
+        tracing::info!(
+            nodes = graph.nodes.len(),
+            edges = graph.edges.len(),
+            "Граф зависимостей построен"
+        );
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(type_id: &str, direction: &str, key: &str) -> IssueLink {
+        serde_json::from_value(serde_json::json!({
+            "type": {"id": type_id},
+            "direction": direction,
+            "object": {"key": key}
+        }))
+        .unwrap()
+    }
+
+    fn sample_graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new("TREK-1");
+        graph.add_node(
+            "TREK-1",
+            Some("Релиз".to_string()),
+            Some("Открыт".to_string()),
+            0,
+        );
+        graph.add_node("TREK-2", Some("Миграция \"БД\"".to_string()), None, 1);
+        graph.add_node("TREK-3", None, None, 1);
+        graph.add_edge(DependencyEdge {
+            from: "TREK-2".to_string(),
+            to: "TREK-1".to_string(),
+            kind: EdgeKind::Blocks,
+        });
+        graph.add_edge(DependencyEdge {
+            from: "TREK-1".to_string(),
+            to: "TREK-3".to_string(),
+            kind: EdgeKind::Related("relates".to_string()),
+        });
+        graph
+    }
+
+    #[test]
+    fn test_link_edge_orients_dependencies() {
+        let blocker = link_edge("TREK-1", &link("depends", "outward", "TREK-2")).unwrap();
+        assert_eq!(
+            (blocker.from.as_str(), blocker.to.as_str()),
+            ("TREK-2", "TREK-1")
+        );
+        assert_eq!(blocker.kind, EdgeKind::Blocks);
+
+        let blocked = link_edge("TREK-1", &link("depends", "inward", "TREK-2")).unwrap();
+        assert_eq!(
+            (blocked.from.as_str(), blocked.to.as_str()),
+            ("TREK-1", "TREK-2")
+        );
+
+        // Неориентированная связь одинакова с обеих сторон
+        assert_eq!(
+            link_edge("TREK-2", &link("relates", "outward", "TREK-1")),
+            link_edge("TREK-1", &link("relates", "inward", "TREK-2"))
+        );
+    }
+
+    #[test]
+    fn test_add_node_keeps_minimal_depth_and_known_fields() {
+        let mut graph = DependencyGraph::new("TREK-1");
+        graph.add_node("TREK-2", Some("Из связи".to_string()), None, 1);
+        graph.add_node("TREK-2", None, Some("Закрыт".to_string()), 2);
+
+        let node = &graph.nodes["TREK-2"];
+        assert_eq!(node.depth, 1);
+        assert_eq!(node.summary.as_deref(), Some("Из связи"));
+        assert_eq!(node.status.as_deref(), Some("Закрыт"));
+    }
+
+    #[test]
+    fn test_render_tree_lists_blockers_first() {
+        let graph = sample_graph();
+
+        assert_eq!(graph.blockers("TREK-1"), ["TREK-2"]);
+        assert_eq!(
+            graph.render_tree(),
+            "TREK-1 Релиз [Открыт]\n├─ ⛔ ждёт TREK-2 Миграция \"БД\"\n└─ ~ relates TREK-3"
+        );
+    }
+
+    #[test]
+    fn test_dot_and_mermaid_output() {
+        let graph = sample_graph();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"TREK-1\" [label=\"TREK-1\\nРелиз\\n[Открыт]\", style=bold];"));
+        assert!(dot.contains("\"TREK-2\" [label=\"TREK-2\\nМиграция \\\"БД\\\"\"];"));
+        assert!(dot.contains("\"TREK-2\" -> \"TREK-1\" [label=\"blocks\"];"));
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph LR"));
+        assert!(mermaid.contains("TREK_2[\"TREK-2: Миграция #quot;БД#quot;\"]"));
+        assert!(mermaid.contains("TREK_2 -->|blocks| TREK_1"));
+        assert!(mermaid.contains("TREK_1 -->|relates| TREK_3"));
+    }
+}
@@ -0,0 +1,236 @@
+//! Типизированные идентификаторы Трекера
+//!
+//! Ключ задачи, ключ очереди и логин пользователя — разные строки, которые легко
+//! перепутать. Newtype-обёртки проверяют формат при разборе (`"TREK-123".parse::<IssueKey>()`,
+//! аргументы clap, десериализация конфигурации) и разыменовываются в `&str`, поэтому
+//! передаются в методы клиента, принимающие идентификатор строкой.
+
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::TrackerError;
+
+/// Ключ очереди, например `TREK`: латинская буква, затем буквы и цифры
+///
+/// При разборе ключ приводится к верхнему регистру.
+///
+/// ```
+/// # use tracker_lib::ids::QueueKey;
+/// let queue: QueueKey = "trek".parse().unwrap();
+/// assert_eq!(queue, "TREK");
+/// assert!("TREK-1".parse::<QueueKey>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QueueKey(String);
+
+/// Ключ задачи, например `TREK-123`: ключ очереди и номер через дефис
+///
+/// При разборе ключ приводится к верхнему регистру.
+///
+/// ```
+/// # use tracker_lib::ids::IssueKey;
+/// let key: IssueKey = "TREK-123".parse().unwrap();
+/// assert_eq!(key.queue(), "TREK");
+/// assert_eq!(key.number(), 123);
+/// assert!("507f1f77bcf86cd799439011".parse::<IssueKey>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IssueKey(String);
+
+/// Логин пользователя, например `ann` или `john.doe`
+///
+/// ```
+/// # use tracker_lib::ids::UserLogin;
+/// assert!("john.doe".parse::<UserLogin>().is_ok());
+/// assert!("John Doe".parse::<UserLogin>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UserLogin(String);
+
+pub(crate) fn is_queue_key(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_uppercase())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+pub(crate) fn is_issue_number(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_login(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@'))
+}
+
+impl FromStr for QueueKey {
+    type Err = TrackerError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let key = value.trim().to_ascii_uppercase();
+        if is_queue_key(&key) {
+            Ok(Self(key))
+        } else {
+            Err(TrackerError::InvalidId(format!(
+                "«{value}» не похоже на ключ очереди (например, TREK)"
+            )))
+        }
+    }
+}
+
+impl FromStr for IssueKey {
+    type Err = TrackerError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let key = value.trim().to_ascii_uppercase();
+        match key.split_once('-') {
+            Some((queue, number)) if is_queue_key(queue) && is_issue_number(number) => {
+                Ok(Self(key))
+            }
+            _ => Err(TrackerError::InvalidId(format!(
+                "«{value}» не похоже на ключ задачи (например, TREK-123)"
+            ))),
+        }
+    }
+}
+
+impl FromStr for UserLogin {
+    type Err = TrackerError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let login = value.trim();
+        if is_login(login) {
+            Ok(Self(login.to_string()))
+        } else {
+            Err(TrackerError::InvalidId(format!(
+                "«{value}» не похоже на логин пользователя"
+            )))
+        }
+    }
+}
+
+impl IssueKey {
+    /// Ключ очереди задачи
+    pub fn queue(&self) -> QueueKey {
+        QueueKey(self.queue_str().to_string())
+    }
+
+    /// Номер задачи в очереди
+    pub fn number(&self) -> u64 {
+        self.0
+            .split_once('-')
+            .and_then(|(_, number)| number.parse().ok())
+            .unwrap_or_default()
+    }
+
+    fn queue_str(&self) -> &str {
+        self.0
+            .split_once('-')
+            .map(|(queue, _)| queue)
+            .unwrap_or(&self.0)
+    }
+}
+
+macro_rules! string_id {
+    ($name:ident) => {
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = TrackerError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                value.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+string_id!(QueueKey);
+string_id!(IssueKey);
+string_id!(UserLogin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issue_key() {
+        let key: IssueKey = " trek2-45 ".parse().unwrap();
+        assert_eq!(key, "TREK2-45");
+        assert_eq!(key.queue(), "TREK2".parse::<QueueKey>().unwrap());
+        assert_eq!(key.number(), 45);
+
+        for invalid in ["TREK", "TREK-", "-1", "2024-05", "TREK-1a", "ann"] {
+            assert!(invalid.parse::<IssueKey>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_queue_and_login() {
+        assert_eq!("ops".parse::<QueueKey>().unwrap().to_string(), "OPS");
+        assert!("1OPS".parse::<QueueKey>().is_err());
+        assert!("".parse::<QueueKey>().is_err());
+
+        assert_eq!("ann".parse::<UserLogin>().unwrap(), "ann");
+        assert!("robot-ci_2@example.com".parse::<UserLogin>().is_ok());
+        assert!("".parse::<UserLogin>().is_err());
+        assert!("ann, bob".parse::<UserLogin>().is_err());
+    }
+
+    #[test]
+    fn test_serde_validates_and_keeps_plain_strings() {
+        let key: IssueKey = serde_json::from_str("\"TREK-1\"").unwrap();
+        assert_eq!(serde_json::to_string(&key).unwrap(), "\"TREK-1\"");
+        assert!(serde_json::from_str::<IssueKey>("\"{{issue.key}}\"").is_err());
+    }
+}
@@ -13,18 +13,46 @@
 //!     let client = TrackerClient::with_token("your-oauth-token")?;
 //!
 //!     // Получение информации о задаче
-//!     let issue = client.get_issue("TEST-1", None).await?;
+//!     let issue = client.get_issue(&"TEST-1".parse()?, None).await?;
 //!     println!("Задача: {} - {}", issue.key, issue.summary);
 //!
 //!     Ok(())
 //! }
 //! ```
 
+pub mod activity;
 mod api_client;
+pub mod attachments;
+pub mod bulkchange;
+pub mod changelog;
+pub mod checklists;
+pub mod comments;
+pub mod events;
+pub mod filters;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod graph;
+pub mod ids;
+pub mod links;
+pub mod metrics;
 pub mod models;
+pub mod operations;
+pub mod queues;
 pub mod search;
+mod shared;
+pub mod sprints;
+mod stream;
+pub mod subtasks;
 pub mod task;
+pub mod template;
+pub mod transitions;
+pub mod users;
+pub mod webhook;
+pub mod worklog;
 
 pub use api_client::{
-    Language, PaginationMeta, PaginationParams, Result, TrackerClient, TrackerConfig, TrackerError,
+    read_only_from_env, sandbox_queue_from_env, Conditional, Language, OrgKind, PaginationMeta,
+    PaginationParams, Result, TrackerClient, TrackerClientBuilder, TrackerConfig, TrackerError,
+    ORG_ID_VAR, ORG_KIND_VAR, READ_ONLY_VAR, SANDBOX_QUEUE_VAR,
 };
+pub use shared::TokenRefresher;
@@ -0,0 +1,91 @@
+//! Модуль для работы со связями задач в Яндекс.Трекере
+
+use serde::Serialize;
+
+use crate::ids::IssueKey;
+use crate::models::IssueLink;
+use crate::{Result, TrackerClient};
+
+/// Тело запроса для создания связи
+#[derive(Debug, Clone, Serialize)]
+struct CreateLinkRequest<'a> {
+    /// Тип связи со стороны текущей задачи, например `relates` или `depends on`
+    relationship: &'a str,
+    /// Ключ связываемой задачи
+    issue: &'a str,
+}
+
+impl TrackerClient {
+    /// Получить связи задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let links = client.get_links(&"TREK-123".parse()?).await?;
+    /// for link in links {
+    ///     println!("{:?} {:?}", link.direction, link.object.key);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_links(&self, issue_key: &IssueKey) -> Result<Vec<IssueLink>> {
+        tracing::debug!("Получение связей задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/links", issue_key);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+
+        let links: Vec<IssueLink> = serde_json::from_value(json_value)?;
+
+        tracing::info!(links_count = links.len(), "Связи задачи получены успешно");
+
+        Ok(links)
+    }
+
+    /// Связать задачу с другой задачей
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `relationship` - Тип связи со стороны задачи: `relates`, `depends on`,
+    ///   `is subtask for` и другие
+    /// * `target` - Ключ связываемой задачи
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn create_link(
+        &self,
+        issue_key: &IssueKey,
+        relationship: &str,
+        target: &IssueKey,
+    ) -> Result<IssueLink> {
+        let resource_path = format!("issues/{}/links", issue_key);
+        let request = CreateLinkRequest {
+            relationship,
+            issue: target.as_str(),
+        };
+        let (json_value, _) = self.post(&resource_path, &request, None).await?;
+        let link: IssueLink = serde_json::from_value(json_value)?;
+
+        tracing::info!(link_id = ?link.id, "Связь создана");
+
+        Ok(link)
+    }
+
+    /// Удалить связь задачи
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn delete_link(&self, issue_key: &IssueKey, link_id: u64) -> Result<()> {
+        let resource_path = format!("issues/{}/links/{}", issue_key, link_id);
+        self.delete(&resource_path, None).await?;
+
+        tracing::info!("Связь удалена");
+
+        Ok(())
+    }
+}
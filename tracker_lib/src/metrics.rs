@@ -0,0 +1,397 @@
+//! Метрики поставки: скорость команды по спринтам, время цикла и время выполнения
+//!
+//! Время выполнения (lead time) считается от создания задачи до резолюции, время
+//! цикла (cycle time) — от начала работы до резолюции. Начало работы берётся из
+//! истории изменений: первая смена статуса на любой, кроме «не начатых»
+//! ([`NOT_STARTED_STATUSES`]). Задача, статус которой не менялся, во время цикла не попадает.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde::Serialize;
+
+use crate::models::{ChangelogEntry, Issue, SprintDetails, TRACKER_DATE_FORMAT};
+use crate::{Result, TrackerClient};
+
+/// Статусы, означающие, что работа по задаче не начиналась
+pub const NOT_STARTED_STATUSES: &[&str] = &["open", "new", "backlog"];
+
+/// Статусы спринтов, по которым уже можно считать скорость
+const FINISHED_SPRINT_STATUSES: &[&str] = &["released", "archived"];
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, TRACKER_DATE_FORMAT).ok()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+fn days(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 86_400.0
+}
+
+/// Ключевые моменты жизни задачи
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueTimeline {
+    pub key: String,
+    pub story_points: Option<f64>,
+    pub created: Option<DateTime<FixedOffset>>,
+    /// Начало работы; None, если задача не выходила из «не начатых» статусов
+    pub started: Option<DateTime<FixedOffset>>,
+    pub resolved: Option<DateTime<FixedOffset>>,
+}
+
+impl IssueTimeline {
+    /// Собрать моменты задачи по её полям и истории изменений статуса
+    ///
+    /// ```
+    /// # use tracker_lib::metrics::IssueTimeline;
+    /// # use tracker_lib::models::{ChangelogEntry, Issue};
+    /// let issue: Issue = serde_json::from_value(serde_json::json!({
+    ///     "key": "TREK-1", "summary": "Релиз",
+    ///     "createdAt": "2026-10-01T09:00:00.000+0000",
+    ///     "resolvedAt": "2026-10-05T09:00:00.000+0000"
+    /// })).unwrap();
+    /// let changelog: Vec<ChangelogEntry> = serde_json::from_value(serde_json::json!([{
+    ///     "updatedAt": "2026-10-03T09:00:00.000+0000",
+    ///     "fields": [{"field": {"id": "status"}, "to": {"key": "inProgress"}}]
+    /// }])).unwrap();
+    /// let timeline = IssueTimeline::new(&issue, &changelog);
+    /// assert_eq!(timeline.cycle_time_days(), Some(2.0));
+    /// assert_eq!(timeline.lead_time_days(), Some(4.0));
+    /// ```
+    pub fn new(issue: &Issue, changelog: &[ChangelogEntry]) -> Self {
+        let started = changelog
+            .iter()
+            .filter_map(|entry| {
+                let at = parse_time(entry.updated_at.as_deref()?)?;
+                let change = entry
+                    .fields
+                    .iter()
+                    .find(|change| change.field.id.as_deref() == Some("status"))?;
+                let status = change.to.as_ref()?.get("key")?.as_str()?;
+                (!NOT_STARTED_STATUSES.contains(&status)).then_some(at)
+            })
+            .min();
+        Self {
+            key: issue.key.clone(),
+            story_points: issue.story_points,
+            created: issue.created_at.as_deref().and_then(parse_time),
+            started,
+            resolved: issue.resolved_at.as_deref().and_then(parse_time),
+        }
+    }
+
+    /// От создания до резолюции, в днях
+    pub fn lead_time_days(&self) -> Option<f64> {
+        Some(days(self.resolved? - self.created?))
+    }
+
+    /// От начала работы до резолюции, в днях
+    pub fn cycle_time_days(&self) -> Option<f64> {
+        Some(days(self.resolved? - self.started?))
+    }
+
+    /// Решена ли задача к концу дня `day` (по дате в поясе резолюции)
+    pub fn resolved_by(&self, day: NaiveDate) -> bool {
+        self.resolved
+            .is_some_and(|resolved| resolved.date_naive() <= day)
+    }
+}
+
+/// Перцентили длительностей в днях
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub count: usize,
+    pub p50: f64,
+    pub p85: f64,
+    pub p95: f64,
+}
+
+impl Percentiles {
+    /// Перцентили по методу ближайшего ранга; None для пустого списка
+    ///
+    /// ```
+    /// # use tracker_lib::metrics::Percentiles;
+    /// let stats = Percentiles::from_days((1..=20).map(f64::from).collect()).unwrap();
+    /// assert_eq!((stats.p50, stats.p85, stats.p95), (10.0, 17.0, 19.0));
+    /// ```
+    pub fn from_days(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        Some(Self {
+            count: values.len(),
+            p50: percentile(&values, 50.0),
+            p85: percentile(&values, 85.0),
+            p95: percentile(&values, 95.0),
+        })
+    }
+}
+
+/// Перцентиль `p` (от 0 до 100) отсортированного непустого списка
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Сколько задач спринта взято и сколько решено к его окончанию
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SprintVelocity {
+    pub sprint_id: Option<u64>,
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub committed: usize,
+    pub completed: usize,
+    pub committed_points: f64,
+    pub completed_points: f64,
+    /// Время цикла задач, решённых в спринте
+    pub cycle_time: Option<Percentiles>,
+    /// Время выполнения задач, решённых в спринте
+    pub lead_time: Option<Percentiles>,
+}
+
+impl SprintVelocity {
+    /// Скорость спринта по его задачам; None, если у спринта нет дат
+    pub fn new(sprint: &SprintDetails, issues: &[IssueTimeline]) -> Option<Self> {
+        let start = parse_date(sprint.start_date.as_deref()?)?;
+        let end = parse_date(sprint.end_date.as_deref()?)?;
+        let points = |issue: &&IssueTimeline| issue.story_points.unwrap_or_default();
+        let completed: Vec<&IssueTimeline> = issues
+            .iter()
+            .filter(|issue| issue.resolved_by(end))
+            .collect();
+        Some(Self {
+            sprint_id: sprint.id,
+            name: sprint
+                .name
+                .clone()
+                .or_else(|| sprint.id.map(|id| id.to_string()))
+                .unwrap_or_default(),
+            start,
+            end,
+            committed: issues.len(),
+            completed: completed.len(),
+            committed_points: issues.iter().map(|issue| points(&issue)).sum(),
+            completed_points: completed.iter().map(points).sum(),
+            cycle_time: Percentiles::from_days(
+                completed
+                    .iter()
+                    .filter_map(|issue| issue.cycle_time_days())
+                    .collect(),
+            ),
+            lead_time: Percentiles::from_days(
+                completed
+                    .iter()
+                    .filter_map(|issue| issue.lead_time_days())
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// Скорость по спринтам и распределение времени цикла и выполнения
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VelocityReport {
+    pub sprints: Vec<SprintVelocity>,
+    /// Среднее число решённых задач за спринт
+    pub average_completed: f64,
+    /// Среднее число решённых story points за спринт
+    pub average_points: f64,
+    pub cycle_time: Option<Percentiles>,
+    pub lead_time: Option<Percentiles>,
+}
+
+impl VelocityReport {
+    /// Отчёт по спринтам и их задачам; спринты без дат пропускаются
+    pub fn new(sprints: &[(SprintDetails, Vec<IssueTimeline>)]) -> Self {
+        let velocities: Vec<SprintVelocity> = sprints
+            .iter()
+            .filter_map(|(sprint, issues)| SprintVelocity::new(sprint, issues))
+            .collect();
+        let average = |value: fn(&SprintVelocity) -> f64| {
+            if velocities.is_empty() {
+                0.0
+            } else {
+                velocities.iter().map(value).sum::<f64>() / velocities.len() as f64
+            }
+        };
+        let average_completed = average(|sprint| sprint.completed as f64);
+        let average_points = average(|sprint| sprint.completed_points);
+
+        // Задача, переходившая из спринта в спринт, учитывается в длительностях один раз
+        let mut resolved: Vec<&IssueTimeline> = sprints
+            .iter()
+            .flat_map(|(_, issues)| issues)
+            .filter(|issue| issue.resolved.is_some())
+            .collect();
+        resolved.sort_by(|a, b| a.key.cmp(&b.key));
+        resolved.dedup_by(|a, b| a.key == b.key);
+
+        Self {
+            cycle_time: Percentiles::from_days(
+                resolved
+                    .iter()
+                    .filter_map(|issue| issue.cycle_time_days())
+                    .collect(),
+            ),
+            lead_time: Percentiles::from_days(
+                resolved
+                    .iter()
+                    .filter_map(|issue| issue.lead_time_days())
+                    .collect(),
+            ),
+            sprints: velocities,
+            average_completed,
+            average_points,
+        }
+    }
+}
+
+impl TrackerClient {
+    /// Собрать моменты задач по истории смен статуса
+    ///
+    /// История запрашивается по одной задаче, поэтому для больших выборок это долго.
+    #[tracing::instrument(skip(self, issues), fields(count = issues.len()))]
+    pub async fn issue_timelines(&self, issues: &[Issue]) -> Result<Vec<IssueTimeline>> {
+        let mut timelines = Vec::with_capacity(issues.len());
+        for issue in issues {
+            let changelog = self
+                .get_changelog(&issue.issue_key()?, Some("status"))
+                .await?;
+            timelines.push(IssueTimeline::new(issue, &changelog));
+        }
+        Ok(timelines)
+    }
+
+    /// Отчёт о скорости по последним `last` завершённым спринтам доски
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let report = client.velocity_report(12, 6).await?;
+    /// println!("В среднем решено задач за спринт: {:.1}", report.average_completed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn velocity_report(&self, board_id: u64, last: usize) -> Result<VelocityReport> {
+        let mut sprints: Vec<SprintDetails> = self
+            .get_board_sprints(board_id)
+            .await?
+            .into_iter()
+            .filter(|sprint| {
+                sprint
+                    .status
+                    .as_deref()
+                    .is_some_and(|status| FINISHED_SPRINT_STATUSES.contains(&status))
+            })
+            .collect();
+        sprints.sort_by(|a, b| a.start_date.cmp(&b.start_date));
+        let skip = sprints.len().saturating_sub(last);
+
+        let mut data = Vec::new();
+        for sprint in sprints.into_iter().skip(skip) {
+            let Some(sprint_id) = sprint.id else {
+                continue;
+            };
+            let issues = self.get_sprint_issues(sprint_id).await?;
+            let timelines = self.issue_timelines(&issues).await?;
+            data.push((sprint, timelines));
+        }
+
+        let report = VelocityReport::new(&data);
+        tracing::info!(
+            sprints = report.sprints.len(),
+            average_completed = report.average_completed,
+            "Отчёт о скорости собран"
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeline(key: &str, points: f64, created: &str, resolved: Option<&str>) -> IssueTimeline {
+        let at = |day: &str| parse_time(&format!("2026-10-{day}T12:00:00.000+0000"));
+        IssueTimeline {
+            key: key.to_string(),
+            story_points: Some(points),
+            created: at(created),
+            started: at(created).map(|created| created + Duration::days(1)),
+            resolved: resolved.and_then(at),
+        }
+    }
+
+    fn sprint(id: u64, start: &str, end: &str) -> SprintDetails {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": format!("Спринт {id}"),
+            "status": "released",
+            "startDate": start,
+            "endDate": end,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_started_ignores_not_started_statuses() {
+        let issue: Issue = serde_json::from_value(serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Задача",
+            "createdAt": "2026-10-01T09:00:00.000+0000",
+            "resolvedAt": "2026-10-02T09:00:00.000+0000"
+        }))
+        .unwrap();
+        let changelog: Vec<ChangelogEntry> = serde_json::from_value(serde_json::json!([
+            {"updatedAt": "2026-10-01T10:00:00.000+0000",
+             "fields": [{"field": {"id": "status"}, "to": {"key": "backlog"}}]},
+            {"updatedAt": "2026-10-02T09:00:00.000+0000",
+             "fields": [{"field": {"id": "status"}, "to": {"key": "closed"}}]}
+        ]))
+        .unwrap();
+
+        let timeline = IssueTimeline::new(&issue, &changelog);
+        // Закрыта прямо из бэклога: время цикла нулевое, время выполнения — сутки
+        assert_eq!(timeline.cycle_time_days(), Some(0.0));
+        assert_eq!(timeline.lead_time_days(), Some(1.0));
+        assert_eq!(IssueTimeline::new(&issue, &[]).cycle_time_days(), None);
+    }
+
+    #[test]
+    fn test_report_counts_sprints_and_dedups_carried_issues() {
+        let first = vec![
+            timeline("TREK-1", 3.0, "01", Some("05")),
+            timeline("TREK-2", 5.0, "01", None),
+        ];
+        let second = vec![
+            timeline("TREK-2", 5.0, "01", Some("12")),
+            timeline("TREK-3", 2.0, "08", Some("20")),
+        ];
+        let report = VelocityReport::new(&[
+            (sprint(1, "2026-10-01", "2026-10-07"), first),
+            (sprint(2, "2026-10-08", "2026-10-14"), second),
+        ]);
+
+        assert_eq!(report.sprints[0].completed, 1);
+        assert_eq!(report.sprints[0].committed_points, 8.0);
+        assert_eq!(report.sprints[0].completed_points, 3.0);
+        assert_eq!(report.sprints[0].lead_time.unwrap().p50, 4.0);
+        // TREK-3 решена после окончания второго спринта
+        assert_eq!(report.sprints[1].completed, 1);
+        assert_eq!(report.average_completed, 1.0);
+        assert_eq!(report.average_points, 4.0);
+
+        let lead_time = report.lead_time.unwrap();
+        assert_eq!(lead_time.count, 3);
+        assert_eq!((lead_time.p50, lead_time.p95), (11.0, 12.0));
+        assert_eq!(report.cycle_time.unwrap().p50, 10.0);
+    }
+}
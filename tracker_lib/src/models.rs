@@ -3,7 +3,14 @@
 //! Содержит структуры для представления задач, пользователей,
 //! статусов, приоритетов и других сущностей API.
 
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, TimeZone};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+
+use crate::attachments::Attachment;
+use crate::ids::IssueKey;
+
+/// Формат даты и времени в API Трекера (для `chrono`)
+pub const TRACKER_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
 
 /// Информация о пользователе
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +25,9 @@ pub struct User {
     /// Отображаемое имя пользователя
     pub display: Option<String>,
 
+    /// Логин пользователя
+    pub login: Option<String>,
+
     /// Уникальный идентификатор аккаунта пользователя в Яндекс.Паспорте
     #[serde(rename = "passportUid")]
     pub passport_uid: Option<u64>,
@@ -27,6 +37,28 @@ pub struct User {
     pub cloud_uid: Option<String>,
 }
 
+impl User {
+    /// Начинается ли с `prefix` логин или одно из слов имени; регистр не важен
+    pub fn matches_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return true;
+        }
+        let login_matches = self
+            .login
+            .as_ref()
+            .is_some_and(|login| login.to_lowercase().starts_with(&prefix));
+        login_matches
+            || self.display.as_ref().is_some_and(|display| {
+                let display = display.to_lowercase();
+                display.starts_with(&prefix)
+                    || display
+                        .split_whitespace()
+                        .any(|word| word.starts_with(&prefix))
+            })
+    }
+}
+
 /// Информация о статусе задачи
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Status {
@@ -242,6 +274,357 @@ pub struct Issue {
     /// Теги задачи
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Оценка в story points
+    #[serde(rename = "storyPoints")]
+    pub story_points: Option<f64>,
+
+    /// Дата и время резолюции задачи
+    #[serde(rename = "resolvedAt")]
+    pub resolved_at: Option<String>,
+
+    /// Оценка трудозатрат в формате ISO 8601 (например, `PT6H`)
+    pub estimation: Option<String>,
+
+    /// Дедлайн в формате YYYY-MM-DD
+    pub deadline: Option<String>,
+
+    /// Переходы, если запрошены через `expand=transitions`
+    #[serde(
+        default,
+        deserialize_with = "expanded",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transitions: Option<Vec<Transition>>,
+
+    /// Вложения, если запрошены через `expand=attachments`
+    #[serde(
+        default,
+        deserialize_with = "expanded",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub attachments: Option<Vec<Attachment>>,
+
+    /// Комментарии, если запрошены через `expand=comments`
+    #[serde(
+        default,
+        deserialize_with = "expanded",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub comments: Option<Vec<Comment>>,
+}
+
+impl Issue {
+    /// Ключ задачи как [`IssueKey`] для методов клиента
+    ///
+    /// Трекер всегда отдаёт ключ вида `TREK-123`, поэтому ошибка означает
+    /// неожиданный ответ сервера.
+    pub fn issue_key(&self) -> crate::Result<IssueKey> {
+        self.key.parse()
+    }
+
+    /// Дата дедлайна; None, если дедлайна нет или он в неожиданном формате
+    pub fn deadline_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.deadline.as_deref()?, "%Y-%m-%d").ok()
+    }
+
+    /// Конец дня дедлайна в часовом поясе `tz`
+    ///
+    /// В Трекере дедлайн — дата без времени, поэтому задача успевает к сроку, пока в
+    /// часовом поясе исполнителя не закончился этот день.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// # use tracker_lib::models::Issue;
+    /// let issue: Issue = serde_json::from_value(serde_json::json!({
+    ///     "key": "TREK-1",
+    ///     "summary": "Релиз",
+    ///     "deadline": "2026-10-20"
+    /// }))
+    /// .unwrap();
+    /// let deadline = issue.deadline_at(&chrono::Utc).unwrap();
+    /// assert_eq!(deadline.to_rfc3339(), "2026-10-20T23:59:59+00:00");
+    /// ```
+    pub fn deadline_at<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        let end_of_day = self.deadline_date()?.and_hms_opt(23, 59, 59)?;
+        tz.from_local_datetime(&end_of_day).earliest()
+    }
+}
+
+/// Разобрать раскрытые данные задачи
+///
+/// Если формат не совпал с ожидаемым, поле остаётся пустым, а задача разбирается:
+/// без раскрытых данных она полезнее, чем ошибка.
+fn expanded<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let Some(value) = Option::<serde_json::Value>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    match serde_json::from_value(value) {
+        Ok(items) => Ok(Some(items)),
+        Err(e) => {
+            tracing::warn!("Раскрытые данные задачи пропущены: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+/// Переход по жизненному циклу задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор перехода
+    pub id: String,
+
+    /// Отображаемое название перехода
+    pub display: Option<String>,
+
+    /// Статус, в который переводится задача
+    pub to: Option<Status>,
+}
+
+/// Комментарий к задаче
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор комментария
+    pub id: Option<u64>,
+
+    /// Текст комментария
+    #[serde(default)]
+    pub text: String,
+
+    /// Автор комментария
+    #[serde(rename = "createdBy")]
+    pub created_by: Option<User>,
+
+    /// Дата и время создания комментария
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+
+    /// Дата и время последнего изменения комментария
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+}
+
+/// Запись о затраченном времени
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Worklog {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор записи
+    pub id: Option<u64>,
+
+    /// Задача, к которой относится запись
+    pub issue: Option<ParentIssue>,
+
+    /// Комментарий к записи
+    pub comment: Option<String>,
+
+    /// Автор записи
+    #[serde(rename = "createdBy")]
+    pub created_by: Option<User>,
+
+    /// Дата и время начала работы
+    pub start: Option<String>,
+
+    /// Затраченное время в формате ISO 8601 (например, PT1H30M)
+    pub duration: Option<String>,
+
+    /// Дата и время создания записи
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+
+    /// Дата и время последнего изменения записи
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+}
+
+/// Спринт доски
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintDetails {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор спринта
+    pub id: Option<u64>,
+
+    /// Название спринта
+    pub name: Option<String>,
+
+    /// Статус спринта: draft, in_progress, released, archived
+    pub status: Option<String>,
+
+    /// Дата начала спринта в формате YYYY-MM-DD
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+
+    /// Дата окончания спринта в формате YYYY-MM-DD
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+}
+
+/// Очередь с настройками, нужными для создания задач
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueDetails {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Ключ очереди
+    pub key: String,
+
+    /// Название очереди
+    pub name: Option<String>,
+
+    /// Владелец очереди
+    pub lead: Option<User>,
+
+    /// Тип задачи по умолчанию
+    #[serde(rename = "defaultType")]
+    pub default_type: Option<IssueType>,
+
+    /// Приоритет по умолчанию
+    #[serde(rename = "defaultPriority")]
+    pub default_priority: Option<Priority>,
+
+    /// Типы задач очереди, если запрошены через `expand=issueTypesConfig`
+    #[serde(rename = "issueTypesConfig", default)]
+    pub issue_types_config: Vec<IssueTypeConfig>,
+}
+
+/// Настройка типа задачи в очереди
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueTypeConfig {
+    /// Тип задачи
+    #[serde(rename = "issueType")]
+    pub issue_type: IssueType,
+}
+
+impl QueueDetails {
+    /// Типы задач, доступные в очереди
+    pub fn issue_types(&self) -> impl Iterator<Item = &IssueType> {
+        self.issue_types_config
+            .iter()
+            .map(|config| &config.issue_type)
+    }
+
+    /// Можно ли создать в очереди задачу типа `key`
+    ///
+    /// Если типы очереди не запрошены, разрешён любой тип: проверку выполнит Трекер.
+    pub fn allows_type(&self, key: &str) -> bool {
+        self.issue_types_config.is_empty()
+            || self
+                .issue_types()
+                .any(|issue_type| issue_type.key.as_deref() == Some(key))
+    }
+}
+
+/// Ссылка на поле задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldRef {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор поля
+    pub id: Option<String>,
+
+    /// Отображаемое название поля
+    pub display: Option<String>,
+}
+
+/// Изменение одного поля в записи истории
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedField {
+    /// Изменённое поле
+    pub field: FieldRef,
+
+    /// Значение до изменения
+    pub from: Option<serde_json::Value>,
+
+    /// Значение после изменения
+    pub to: Option<serde_json::Value>,
+}
+
+/// Запись истории изменений задачи
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор записи
+    pub id: Option<String>,
+
+    /// Задача, к которой относится запись
+    pub issue: Option<ParentIssue>,
+
+    /// Дата и время изменения
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<String>,
+
+    /// Автор изменения
+    #[serde(rename = "updatedBy")]
+    pub updated_by: Option<User>,
+
+    /// Тип изменения, например IssueUpdated или IssueWorkflow
+    #[serde(rename = "type")]
+    pub change_type: Option<String>,
+
+    /// Изменённые поля
+    #[serde(default)]
+    pub fields: Vec<ChangedField>,
+}
+
+/// Тип связи между задачами
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkType {
+    /// Идентификатор типа связи (например, `depends`, `relates`, `subtask`)
+    pub id: Option<String>,
+
+    /// Название связи со стороны связанной задачи
+    pub inward: Option<String>,
+
+    /// Название связи со стороны текущей задачи
+    pub outward: Option<String>,
+}
+
+/// Связь задачи с другой задачей
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueLink {
+    /// Адрес ресурса API
+    #[serde(rename = "self")]
+    pub self_link: Option<String>,
+
+    /// Идентификатор связи
+    pub id: Option<u64>,
+
+    /// Тип связи
+    #[serde(rename = "type")]
+    pub link_type: Option<LinkType>,
+
+    /// Направление связи относительно текущей задачи: `inward` или `outward`
+    pub direction: Option<String>,
+
+    /// Связанная задача
+    pub object: ParentIssue,
+
+    /// Статус связанной задачи
+    pub status: Option<Status>,
 }
 
 /// Дополнительные поля для включения в ответ
@@ -293,6 +676,26 @@ mod tests {
         assert_eq!(issue.aliases.len(), 1);
     }
 
+    #[test]
+    fn test_deadline_in_timezone() {
+        let issue: Issue = serde_json::from_str(
+            r#"{"key": "TREK-1", "summary": "Релиз", "deadline": "2026-10-20"}"#,
+        )
+        .unwrap();
+        let novosibirsk = chrono::FixedOffset::east_opt(7 * 3600).unwrap();
+
+        let deadline = issue.deadline_at(&novosibirsk).unwrap();
+        assert_eq!(deadline.to_rfc3339(), "2026-10-20T23:59:59+07:00");
+        assert_eq!(
+            deadline.with_timezone(&chrono::Utc).to_rfc3339(),
+            "2026-10-20T16:59:59+00:00"
+        );
+
+        let no_deadline: Issue =
+            serde_json::from_str(r#"{"key": "TREK-2", "summary": "Без срока"}"#).unwrap();
+        assert!(no_deadline.deadline_at(&novosibirsk).is_none());
+    }
+
     #[test]
     fn test_issue_minimal_deserialization() {
         let json = r#"{
@@ -308,6 +711,43 @@ mod tests {
         assert!(issue.tags.is_empty());
     }
 
+    #[test]
+    fn test_transition_deserialization() {
+        let json = r#"{
+            "id": "close",
+            "display": "Закрыть",
+            "to": {"key": "closed", "display": "Закрыт"}
+        }"#;
+
+        let transition: Transition = serde_json::from_str(json).unwrap();
+        assert_eq!(transition.id, "close");
+        assert_eq!(transition.display.as_deref(), Some("Закрыть"));
+        assert_eq!(transition.to.and_then(|s| s.key).as_deref(), Some("closed"));
+    }
+
+    #[test]
+    fn test_issue_expanded_data() {
+        let issue: Issue = serde_json::from_value(serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Test task",
+            "transitions": [{"id": "close", "display": "Закрыть"}],
+            "attachments": [{"id": "4159", "name": "log.txt", "size": 12}],
+            "comments": {"unexpected": true}
+        }))
+        .unwrap();
+
+        assert_eq!(issue.transitions.unwrap()[0].id, "close");
+        assert_eq!(issue.attachments.unwrap()[0].name, "log.txt");
+        assert!(issue.comments.is_none());
+
+        let plain: Issue =
+            serde_json::from_value(serde_json::json!({"key": "TREK-2", "summary": "Plain"}))
+                .unwrap();
+        assert!(plain.transitions.is_none());
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("comments").is_none());
+    }
+
     #[test]
     fn test_expand_field_as_str() {
         assert_eq!(ExpandField::Transitions.as_str(), "transitions");
@@ -0,0 +1,546 @@
+//! Модуль для многошаговых операций над задачей с откатом
+//!
+//! [`OperationPlan`] описывает сценарий из нескольких вызовов API: создать задачу,
+//! указать родителя, связать с другой задачей, заполнить чеклист, назначить
+//! исполнителя. [`TrackerClient::run_operation`] выполняет шаги по порядку, а если
+//! шаг не удался, по возможности отменяет уже выполненные в обратном порядке:
+//! удаляет связи и пункты чеклиста, возвращает прежних родителя и исполнителя,
+//! закрывает созданную задачу (удалить задачу API не позволяет). Итог каждого шага
+//! собирается в [`OperationReport`].
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::checklists::ChecklistItemRequest;
+use crate::ids::{IssueKey, UserLogin};
+use crate::models::Issue;
+use crate::task::{CreateIssueRequest, UpdateIssueRequest};
+use crate::transitions::ExecuteTransitionRequest;
+use crate::{Result, TrackerClient, TrackerError};
+
+/// Статусы, в которые закрывается созданная задача при откате, в порядке предпочтения
+const CLOSED_STATUSES: &[&str] = &["cancelled", "closed", "resolved"];
+
+/// Комментарий к созданной задаче, которую закрыл откат
+const ROLLBACK_COMMENT: &str = "Задача закрыта: операция, создавшая её, не завершилась";
+
+/// Шаг операции над текущей задачей
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// Создать задачу; следующие шаги выполняются над ней
+    CreateIssue(Box<CreateIssueRequest>),
+    /// Сделать задачу подзадачей `parent`
+    SetParent(IssueKey),
+    /// Связать задачу с `target` связью `relationship` (например, `relates`)
+    Link {
+        relationship: String,
+        target: IssueKey,
+    },
+    /// Добавить пункты в чеклист
+    AddChecklist(Vec<String>),
+    /// Назначить исполнителя
+    Assign(UserLogin),
+}
+
+impl Step {
+    /// Описание шага для отчёта и прогресса
+    pub fn describe(&self) -> String {
+        match self {
+            Step::CreateIssue(request) => {
+                format!("Создать задачу в {}: {}", request.queue, request.summary)
+            }
+            Step::SetParent(parent) => format!("Указать родителя {parent}"),
+            Step::Link {
+                relationship,
+                target,
+            } => format!("Связать с {target} ({relationship})"),
+            Step::AddChecklist(items) => format!("Добавить в чеклист пунктов: {}", items.len()),
+            Step::Assign(login) => format!("Назначить на {login}"),
+        }
+    }
+}
+
+/// Сценарий операции: задача и шаги над ней
+#[derive(Debug, Clone)]
+pub struct OperationPlan {
+    issue: Option<IssueKey>,
+    steps: Vec<Step>,
+}
+
+impl OperationPlan {
+    /// Сценарий, который начинается с создания задачи
+    pub fn create(request: CreateIssueRequest) -> Self {
+        Self {
+            issue: None,
+            steps: vec![Step::CreateIssue(Box::new(request))],
+        }
+    }
+
+    /// Сценарий над существующей задачей
+    pub fn for_issue(issue_key: IssueKey) -> Self {
+        Self {
+            issue: Some(issue_key),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Добавить шаг в конец сценария
+    pub fn then(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Шаги сценария
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+}
+
+/// Итог шага
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum StepStatus {
+    /// Шаг выполнен и остался в силе
+    Done,
+    /// Шаг не удался
+    Failed { error: String },
+    /// Шаг не выполнялся: раньше случилась ошибка
+    Skipped,
+    /// Шаг выполнен и отменён при откате
+    RolledBack,
+    /// Шаг выполнен, но отменить его не удалось: нужна ручная правка
+    RollbackFailed { error: String },
+}
+
+/// Шаг и его итог
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepReport {
+    pub step: String,
+    #[serde(flatten)]
+    pub status: StepStatus,
+}
+
+/// Итог операции по шагам
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OperationReport {
+    /// Ключ задачи, над которой выполнялась операция
+    pub issue_key: Option<String>,
+    pub steps: Vec<StepReport>,
+}
+
+impl OperationReport {
+    /// Все ли шаги выполнены
+    pub fn is_success(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|report| report.status == StepStatus::Done)
+    }
+
+    /// Остались ли изменения, которые откат не отменил
+    pub fn needs_attention(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|report| matches!(report.status, StepStatus::RollbackFailed { .. }))
+    }
+}
+
+impl fmt::Display for OperationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.issue_key, self.is_success()) {
+            (Some(key), true) => write!(f, "Операция над {key} выполнена")?,
+            (Some(key), false) => write!(f, "Операция над {key} не выполнена")?,
+            (None, _) => write!(f, "Операция не выполнена")?,
+        }
+        for report in &self.steps {
+            let status = match &report.status {
+                StepStatus::Done => "выполнено".to_string(),
+                StepStatus::Failed { error } => format!("ошибка: {error}"),
+                StepStatus::Skipped => "пропущено".to_string(),
+                StepStatus::RolledBack => "отменено".to_string(),
+                StepStatus::RollbackFailed { error } => format!("не отменено: {error}"),
+            };
+            write!(f, "\n  {}: {status}", report.step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Этап, на котором находится шаг
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Run,
+    Rollback,
+}
+
+/// Ход операции для индикатора прогресса
+#[derive(Debug, Clone)]
+pub struct StepProgress {
+    /// Номер шага с нуля
+    pub index: usize,
+    pub total: usize,
+    pub step: String,
+    pub phase: Phase,
+}
+
+/// Как отменить выполненный шаг
+#[derive(Debug)]
+enum Undo {
+    CloseIssue(IssueKey),
+    RestoreParent {
+        issue_key: IssueKey,
+        previous: Option<String>,
+    },
+    DeleteLink {
+        issue_key: IssueKey,
+        link_id: Option<u64>,
+    },
+    DeleteChecklistItems {
+        issue_key: IssueKey,
+        item_ids: Vec<String>,
+    },
+    RestoreAssignee {
+        issue_key: IssueKey,
+        previous: Option<String>,
+    },
+}
+
+/// Шаг не удался; `undo` отменяет то, что он успел сделать
+struct StepError {
+    error: TrackerError,
+    undo: Option<Undo>,
+}
+
+impl From<TrackerError> for StepError {
+    fn from(error: TrackerError) -> Self {
+        Self { error, undo: None }
+    }
+}
+
+impl TrackerClient {
+    /// Выполнить сценарий, при ошибке отменив выполненные шаги
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, task::CreateIssueRequest};
+    /// # use tracker_lib::operations::{OperationPlan, Step};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let plan = OperationPlan::create(CreateIssueRequest::new("TREK".parse()?, "Релиз 2.4"))
+    ///     .then(Step::SetParent("TREK-1".parse()?))
+    ///     .then(Step::AddChecklist(vec!["Сборка".into(), "Заметки о выпуске".into()]))
+    ///     .then(Step::Assign("ann".parse()?));
+    /// let report = client.run_operation(&plan).await;
+    /// println!("{report}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_operation(&self, plan: &OperationPlan) -> OperationReport {
+        self.run_operation_with_progress(plan, |_| {}).await
+    }
+
+    /// Выполнить сценарий, сообщая в `on_progress` о начале каждого шага и отката
+    #[tracing::instrument(skip(self, plan, on_progress), fields(steps = plan.steps.len()))]
+    pub async fn run_operation_with_progress(
+        &self,
+        plan: &OperationPlan,
+        mut on_progress: impl FnMut(StepProgress),
+    ) -> OperationReport {
+        let total = plan.steps.len();
+        let mut report = OperationReport {
+            issue_key: plan.issue.as_ref().map(ToString::to_string),
+            steps: plan
+                .steps
+                .iter()
+                .map(|step| StepReport {
+                    step: step.describe(),
+                    status: StepStatus::Skipped,
+                })
+                .collect(),
+        };
+
+        let mut current = match &plan.issue {
+            Some(key) => match self.get_issue(key, None).await {
+                Ok(issue) => Some(issue),
+                Err(e) => {
+                    tracing::warn!(issue_key = %key, error = %e, "Задача для операции недоступна");
+                    if let Some(first) = report.steps.first_mut() {
+                        first.status = StepStatus::Failed {
+                            error: e.to_string(),
+                        };
+                    }
+                    return report;
+                }
+            },
+            None => None,
+        };
+
+        let mut undo: Vec<(usize, Undo)> = Vec::new();
+        for (index, step) in plan.steps.iter().enumerate() {
+            on_progress(StepProgress {
+                index,
+                total,
+                step: report.steps[index].step.clone(),
+                phase: Phase::Run,
+            });
+            match self.run_step(step, &mut current).await {
+                Ok(step_undo) => {
+                    report.steps[index].status = StepStatus::Done;
+                    undo.push((index, step_undo));
+                    report.issue_key = current.as_ref().map(|issue| issue.key.clone());
+                }
+                Err(StepError {
+                    error,
+                    undo: partial,
+                }) => {
+                    tracing::warn!(step = index, error = %error, "Шаг операции не выполнен");
+                    report.steps[index].status = StepStatus::Failed {
+                        error: error.to_string(),
+                    };
+                    if let Some(partial) = partial {
+                        undo.push((index, partial));
+                    }
+                    self.roll_back(undo, &mut report, &mut on_progress).await;
+                    return report;
+                }
+            }
+        }
+
+        tracing::info!(issue_key = ?report.issue_key, "Операция выполнена");
+        report
+    }
+
+    /// Выполнить шаг над текущей задачей и вернуть способ его отмены
+    async fn run_step(
+        &self,
+        step: &Step,
+        current: &mut Option<Issue>,
+    ) -> std::result::Result<Undo, StepError> {
+        if let Step::CreateIssue(request) = step {
+            let issue = self.create_issue(request).await?;
+            let undo = Undo::CloseIssue(issue.issue_key()?);
+            *current = Some(issue);
+            return Ok(undo);
+        }
+        let Some(issue) = current.as_ref() else {
+            return Err(TrackerError::ConfigError(
+                "Сценарий не начинается ни с задачи, ни с её создания".to_string(),
+            )
+            .into());
+        };
+        let issue_key = issue.issue_key()?;
+
+        match step {
+            Step::CreateIssue(_) => unreachable!("создание задачи обработано выше"),
+            Step::SetParent(parent) => {
+                let previous = issue.parent.as_ref().and_then(|p| p.key.clone());
+                let request = UpdateIssueRequest::new().set("parent", parent.as_str());
+                *current = Some(self.update_issue(&issue_key, &request, None).await?);
+                Ok(Undo::RestoreParent {
+                    issue_key,
+                    previous,
+                })
+            }
+            Step::Link {
+                relationship,
+                target,
+            } => {
+                let link = self.create_link(&issue_key, relationship, target).await?;
+                Ok(Undo::DeleteLink {
+                    issue_key,
+                    link_id: link.id,
+                })
+            }
+            Step::AddChecklist(items) => {
+                let mut known: HashSet<String> = self
+                    .get_checklist(&issue_key)
+                    .await?
+                    .into_iter()
+                    .map(|item| item.id)
+                    .collect();
+                let mut added = Vec::new();
+                for text in items {
+                    let request = ChecklistItemRequest::new(text.as_str(), false);
+                    match self.add_checklist_item(&issue_key, &request).await {
+                        Ok(checklist) => {
+                            for item in checklist {
+                                if known.insert(item.id.clone()) {
+                                    added.push(item.id);
+                                }
+                            }
+                        }
+                        Err(error) => {
+                            let undo = (!added.is_empty()).then(|| Undo::DeleteChecklistItems {
+                                issue_key: issue_key.clone(),
+                                item_ids: added,
+                            });
+                            return Err(StepError { error, undo });
+                        }
+                    }
+                }
+                Ok(Undo::DeleteChecklistItems {
+                    issue_key,
+                    item_ids: added,
+                })
+            }
+            Step::Assign(login) => {
+                let previous = issue.assignee.as_ref().and_then(|user| user.login.clone());
+                *current = Some(self.assign_issue(&issue_key, login).await?);
+                Ok(Undo::RestoreAssignee {
+                    issue_key,
+                    previous,
+                })
+            }
+        }
+    }
+
+    /// Отменить выполненные шаги в обратном порядке
+    async fn roll_back(
+        &self,
+        undo: Vec<(usize, Undo)>,
+        report: &mut OperationReport,
+        on_progress: &mut impl FnMut(StepProgress),
+    ) {
+        let total = report.steps.len();
+        for (index, action) in undo.into_iter().rev() {
+            on_progress(StepProgress {
+                index,
+                total,
+                step: report.steps[index].step.clone(),
+                phase: Phase::Rollback,
+            });
+            let result = self.undo(action).await;
+            let status = &mut report.steps[index].status;
+            match (result, &*status) {
+                (Ok(()), StepStatus::Done) => *status = StepStatus::RolledBack,
+                (Ok(()), _) => {}
+                (Err(e), StepStatus::Failed { error }) => {
+                    *status = StepStatus::RollbackFailed {
+                        error: format!("{error}; откат: {e}"),
+                    }
+                }
+                (Err(e), _) => {
+                    tracing::warn!(step = index, error = %e, "Шаг операции не отменён");
+                    *status = StepStatus::RollbackFailed {
+                        error: e.to_string(),
+                    }
+                }
+            }
+        }
+        tracing::info!(
+            needs_attention = report.needs_attention(),
+            "Откат операции завершён"
+        );
+    }
+
+    async fn undo(&self, action: Undo) -> Result<()> {
+        match action {
+            Undo::CloseIssue(issue_key) => self.close_created(&issue_key).await,
+            Undo::RestoreParent {
+                issue_key,
+                previous,
+            } => {
+                let request = match previous {
+                    Some(parent) => UpdateIssueRequest::new().set("parent", parent),
+                    None => UpdateIssueRequest::new().clear("parent"),
+                };
+                self.update_issue(&issue_key, &request, None).await?;
+                Ok(())
+            }
+            Undo::DeleteLink { issue_key, link_id } => {
+                let link_id = link_id.ok_or_else(|| {
+                    TrackerError::UnexpectedResponse(
+                        "Трекер не вернул идентификатор связи".to_string(),
+                    )
+                })?;
+                self.delete_link(&issue_key, link_id).await
+            }
+            Undo::DeleteChecklistItems {
+                issue_key,
+                item_ids,
+            } => {
+                for item_id in item_ids {
+                    self.delete_checklist_item(&issue_key, &item_id).await?;
+                }
+                Ok(())
+            }
+            Undo::RestoreAssignee {
+                issue_key,
+                previous,
+            } => {
+                let request = match previous {
+                    Some(login) => UpdateIssueRequest::new().set("assignee", login),
+                    None => UpdateIssueRequest::new().clear("assignee"),
+                };
+                self.update_issue(&issue_key, &request, None).await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Закрыть задачу, созданную операцией: переходом в отменённый или закрытый статус
+    async fn close_created(&self, issue_key: &IssueKey) -> Result<()> {
+        let transitions = self.get_transitions(issue_key).await?;
+        let transition = CLOSED_STATUSES
+            .iter()
+            .find_map(|status| {
+                transitions.iter().find(|transition| {
+                    transition
+                        .to
+                        .as_ref()
+                        .and_then(|to| to.key.as_deref())
+                        .is_some_and(|key| key == *status)
+                })
+            })
+            .ok_or_else(|| {
+                TrackerError::UnexpectedResponse(format!(
+                    "Нет перехода, чтобы закрыть созданную задачу {issue_key}"
+                ))
+            })?;
+        let request = ExecuteTransitionRequest {
+            comment: Some(ROLLBACK_COMMENT.to_string()),
+        };
+        self.execute_transition(issue_key, &transition.id, &request, None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_serialization_and_display() {
+        let report = OperationReport {
+            issue_key: Some("TREK-5".to_string()),
+            steps: vec![
+                StepReport {
+                    step: "Создать задачу в TREK: Релиз".to_string(),
+                    status: StepStatus::RolledBack,
+                },
+                StepReport {
+                    step: "Назначить на ann".to_string(),
+                    status: StepStatus::Failed {
+                        error: "нет прав".to_string(),
+                    },
+                },
+            ],
+        };
+
+        assert!(!report.is_success());
+        assert!(!report.needs_attention());
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["steps"][1],
+            serde_json::json!({"step": "Назначить на ann", "status": "failed", "error": "нет прав"})
+        );
+        assert_eq!(
+            report.to_string(),
+            "Операция над TREK-5 не выполнена\n  Создать задачу в TREK: Релиз: отменено\n  \
+Назначить на ann: ошибка: нет прав"
+        );
+    }
+}
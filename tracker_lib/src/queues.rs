@@ -0,0 +1,124 @@
+//! Модуль для получения очередей Яндекс.Трекера, их настроек и статусов организации
+
+use std::collections::HashMap;
+
+use crate::ids::QueueKey;
+use crate::models::{QueueDetails, Status};
+use crate::{Result, TrackerClient};
+
+/// Максимальное количество очередей на странице ответа
+const QUEUES_PER_PAGE: u32 = 100;
+
+impl TrackerClient {
+    /// Получить все очереди, доступные пользователю
+    ///
+    /// Очереди запрашиваются постранично, пока не будут получены все страницы.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for queue in client.get_queues().await? {
+    ///     println!("{}: {:?}", queue.key, queue.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn get_queues(&self) -> Result<Vec<QueueDetails>> {
+        tracing::debug!("Получение списка очередей");
+
+        let mut queues: Vec<QueueDetails> = Vec::new();
+        let mut page = 1;
+        loop {
+            let mut query_params = HashMap::new();
+            query_params.insert("perPage".to_string(), QUEUES_PER_PAGE.to_string());
+            query_params.insert("page".to_string(), page.to_string());
+
+            let pagination = self
+                .get_each("queues", Some(&query_params), |queue| {
+                    queues.push(queue);
+                    Ok(())
+                })
+                .await?;
+            let total_pages = pagination.and_then(|meta| meta.total_pages).unwrap_or(1);
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        tracing::info!(queues_count = queues.len(), "Очереди получены успешно");
+
+        Ok(queues)
+    }
+
+    /// Получить очередь вместе с доступными в ней типами задач
+    ///
+    /// # Параметры
+    ///
+    /// * `queue` - Ключ очереди
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let queue = client.get_queue(&"TREK".parse()?).await?;
+    /// println!("Можно создать баг: {}", queue.allows_type("bug"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(queue = %queue))]
+    pub async fn get_queue(&self, queue: &QueueKey) -> Result<QueueDetails> {
+        tracing::debug!("Получение настроек очереди: {}", queue);
+
+        let resource_path = format!("queues/{}", queue);
+        let mut query_params = HashMap::new();
+        query_params.insert("expand".to_string(), "issueTypesConfig".to_string());
+        let (json_value, _) = self.get(&resource_path, Some(&query_params)).await?;
+        let details: QueueDetails = serde_json::from_value(json_value)?;
+
+        tracing::info!(
+            issue_types_count = details.issue_types_config.len(),
+            "Настройки очереди получены успешно"
+        );
+
+        Ok(details)
+    }
+
+    /// Получить все статусы задач, настроенные в организации
+    ///
+    /// Список небольшой и отдаётся одной страницей; по нему можно показывать
+    /// названия статусов по их ключам и проверять ключи до запроса.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for status in client.get_statuses().await? {
+    ///     println!("{:?}: {:?}", status.key, status.display);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn get_statuses(&self) -> Result<Vec<Status>> {
+        tracing::debug!("Получение списка статусов");
+
+        let (json_value, _) = self.get("statuses", None).await?;
+        let statuses: Vec<Status> = serde_json::from_value(json_value)?;
+
+        tracing::info!(statuses_count = statuses.len(), "Статусы получены успешно");
+
+        Ok(statuses)
+    }
+}
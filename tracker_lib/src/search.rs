@@ -3,9 +3,170 @@
 //! Содержит структуры и методы для выполнения поисковых запросов
 //! с поддержкой различных режимов пагинации.
 
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::ids::QueueKey;
 use crate::models::{ExpandField, Issue};
-use crate::{Result, TrackerClient};
-use serde::Serialize;
+use crate::{Result, TrackerClient, TrackerError};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize, Serializer};
+use tokio::sync::Semaphore;
+
+/// Сколько очередей [`TrackerClient::search_across_queues`] ищет одновременно
+pub const QUEUE_SEARCH_CONCURRENCY: usize = 4;
+
+/// Размер страницы при постраничном поиске в одной очереди
+const QUEUE_SEARCH_PAGE_SIZE: usize = 50;
+
+/// Системные поля, по которым Трекер умеет сортировать результаты поиска
+///
+/// Кроме них допускаются локальные поля очереди вида `<id>--<имя>`.
+pub const SORTABLE_FIELDS: &[&str] = &[
+    "key",
+    "summary",
+    "type",
+    "priority",
+    "status",
+    "resolution",
+    "queue",
+    "assignee",
+    "createdBy",
+    "updatedBy",
+    "createdAt",
+    "updatedAt",
+    "resolvedAt",
+    "start",
+    "end",
+    "deadline",
+    "dueDate",
+    "storyPoints",
+    "originalEstimation",
+    "estimation",
+    "spent",
+    "votes",
+    "sprint",
+];
+
+/// Направление сортировки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// По возрастанию (`+`)
+    #[default]
+    Asc,
+    /// По убыванию (`-`)
+    Desc,
+}
+
+impl Direction {
+    fn sign(self) -> char {
+        match self {
+            Direction::Asc => '+',
+            Direction::Desc => '-',
+        }
+    }
+}
+
+/// Поле и направление сортировки результатов поиска
+///
+/// Разбирается из строки Трекера: `"+status"`, `"-createdAt"` или `"key"` (по возрастанию).
+/// Неизвестное поле отклоняется при разборе, а не сервером.
+///
+/// ```
+/// # use tracker_lib::search::{Direction, OrderBy};
+/// let order: OrderBy = "-createdAt".parse().unwrap();
+/// assert_eq!(order.field(), "createdAt");
+/// assert_eq!(order.direction(), Direction::Desc);
+/// assert_eq!(order.to_string(), "-createdAt");
+/// assert!("+createdat".parse::<OrderBy>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBy {
+    field: String,
+    direction: Direction,
+}
+
+impl OrderBy {
+    /// Сортировка по полю в заданном направлении
+    pub fn new(field: impl Into<String>, direction: Direction) -> Result<Self> {
+        let field = field.into();
+        if !is_sortable_field(&field) {
+            return Err(TrackerError::InvalidOrder(format!(
+                "по полю «{field}» нельзя сортировать; допустимы {} и локальные поля вида <id>--<имя>",
+                SORTABLE_FIELDS.join(", ")
+            )));
+        }
+        Ok(Self { field, direction })
+    }
+
+    /// Сортировка по возрастанию
+    pub fn asc(field: impl Into<String>) -> Result<Self> {
+        Self::new(field, Direction::Asc)
+    }
+
+    /// Сортировка по убыванию
+    pub fn desc(field: impl Into<String>) -> Result<Self> {
+        Self::new(field, Direction::Desc)
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+fn is_sortable_field(field: &str) -> bool {
+    if SORTABLE_FIELDS.contains(&field) {
+        return true;
+    }
+    // Локальное поле очереди: идентификатор и имя через двойной дефис
+    matches!(
+        field.split_once("--"),
+        Some((id, name)) if !id.is_empty()
+            && !name.is_empty()
+            && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    )
+}
+
+impl FromStr for OrderBy {
+    type Err = TrackerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        match value.strip_prefix('-') {
+            Some(field) => Self::desc(field),
+            None => Self::asc(value.strip_prefix('+').unwrap_or(value)),
+        }
+    }
+}
+
+impl fmt::Display for OrderBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.direction.sign(), self.field)
+    }
+}
+
+impl Serialize for OrderBy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Один ключ сортировки уходит строкой, как его описывает API, несколько — массивом строк
+fn serialize_order<S: Serializer>(
+    order: &[OrderBy],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match order {
+        [single] => single.serialize(serializer),
+        keys => keys.serialize(serializer),
+    }
+}
 
 /// Тело запроса для поиска задач
 #[derive(Debug, Clone, Default, Serialize)]
@@ -30,9 +191,152 @@ pub struct SearchRequest {
     #[serde(skip_serializing_if = "Option::is_none", rename = "filterId")]
     pub filter_id: Option<u64>,
 
-    /// Направление и поле сортировки (например, "+status" или "-createdAt")
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub order: Option<String>,
+    /// Ключи сортировки в порядке приоритета (например, `+status`, затем `-createdAt`)
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_order"
+    )]
+    pub order: Vec<OrderBy>,
+}
+
+impl SearchRequest {
+    /// Тот же запрос, ограниченный одной очередью
+    ///
+    /// Запрос на языке запросов дополняется условием `Queue:`, в фильтр добавляется
+    /// поле `queue`, а запрос без того и другого ищет по полю `queue`. Сортировка
+    /// `"Sort by"` остаётся в конце запроса, вне скобок с условиями.
+    ///
+    /// ```
+    /// # use tracker_lib::{ids::QueueKey, search::SearchRequest};
+    /// let request = SearchRequest {
+    ///     query: Some(r#"Assignee: me() "Sort by": Updated DESC"#.to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let queue: QueueKey = "TREK".parse().unwrap();
+    /// assert_eq!(
+    ///     request.for_queue(&queue).query.as_deref(),
+    ///     Some(r#"Queue: TREK AND (Assignee: me()) "Sort by": Updated DESC"#)
+    /// );
+    /// ```
+    pub fn for_queue(&self, queue: &QueueKey) -> SearchRequest {
+        let mut request = self.clone();
+        if let Some(query) = &self.query {
+            let (conditions, sort) = split_sort(query);
+            let scoped = if conditions.is_empty() {
+                format!("Queue: {queue}")
+            } else {
+                format!("Queue: {queue} AND ({conditions})")
+            };
+            request.query = Some(match sort {
+                Some(sort) => format!("{scoped} {sort}"),
+                None => scoped,
+            });
+        } else if let Some(serde_json::Value::Object(filter)) = &mut request.filter {
+            filter.insert("queue".to_string(), queue.to_string().into());
+        } else {
+            request.queue = Some(queue.to_string());
+        }
+        request
+    }
+}
+
+/// Условия запроса и директива сортировки `"Sort by"` (с кавычками или без), если она есть
+///
+/// Сортировка в языке запросов стоит после условий, поэтому всё от неё до конца
+/// запроса считается директивой.
+fn split_sort(query: &str) -> (&str, Option<&str>) {
+    // Строчные ASCII-буквы занимают столько же байт, поэтому позиции совпадают
+    let lowered = query.to_ascii_lowercase();
+    let Some(mut start) = lowered.find("sort by") else {
+        return (query.trim(), None);
+    };
+    if query[..start].ends_with('"') {
+        start -= 1;
+    }
+    (query[..start].trim(), Some(query[start..].trim()))
+}
+
+/// Результат поиска по нескольким очередям
+#[derive(Debug, Default)]
+pub struct QueuesSearch {
+    /// Найденные задачи без повторов: по очередям в порядке запроса
+    pub issues: Vec<Issue>,
+
+    /// Очереди, поиск по которым не удался, и ошибки
+    pub failures: Vec<(QueueKey, TrackerError)>,
+
+    /// Очереди, в которых нашлось не меньше `per_queue` задач: остальные не загружались
+    pub truncated: Vec<QueueKey>,
+}
+
+/// Ошибка в запросе на языке запросов, разобранная из ответа API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Текст ошибки от Трекера
+    pub message: String,
+
+    /// Позиция ошибки в запросе (с единицы, в символах), если Трекер её указал
+    pub position: Option<usize>,
+}
+
+/// Результат проверки запроса через [`TrackerClient::explain_query`]
+#[derive(Debug, Clone)]
+pub enum QueryCheck {
+    /// Запрос корректен
+    Valid {
+        /// Сколько задач ему соответствует
+        total: u32,
+        /// Первая найденная задача
+        sample: Option<Box<Issue>>,
+    },
+    /// Трекер отверг запрос
+    Invalid(Vec<QueryError>),
+}
+
+/// Тело ответа с ошибками API: общие сообщения и ошибки по полям
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ApiErrorBody {
+    error_messages: Vec<String>,
+    errors: std::collections::BTreeMap<String, String>,
+}
+
+/// Ключевые слова, после которых Трекер пишет позицию ошибки в запросе
+const POSITION_MARKERS: &[&str] = &["position", "позиция", "позиции", "column", "символ"];
+
+/// Разобрать ошибки запроса из тела ответа API
+///
+/// Тело, которое не похоже на JSON с ошибками, становится одной ошибкой целиком.
+pub(crate) fn parse_query_errors(body: &str) -> Vec<QueryError> {
+    let parsed: ApiErrorBody = serde_json::from_str(body).unwrap_or_default();
+    let mut messages = parsed.error_messages;
+    messages.extend(
+        parsed
+            .errors
+            .into_iter()
+            .map(|(field, message)| format!("{field}: {message}")),
+    );
+    if messages.is_empty() {
+        messages.push(body.trim().to_string());
+    }
+    messages
+        .into_iter()
+        .map(|message| QueryError {
+            position: error_position(&message),
+            message,
+        })
+        .collect()
+}
+
+/// Позиция ошибки из текста вида «… at position 12» или «позиция: 12»
+fn error_position(message: &str) -> Option<usize> {
+    let lower = message.to_lowercase();
+    POSITION_MARKERS.iter().find_map(|marker| {
+        let (_, rest) = lower.split_once(marker)?;
+        let rest = rest.trim_start_matches(|c: char| c == ':' || c == '=' || c.is_whitespace());
+        let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    })
 }
 
 /// Параметры запроса для поиска задач
@@ -74,7 +378,7 @@ impl TrackerClient {
     /// # Примеры
     ///
     /// ```no_run
-    /// # use tracker_lib::{TrackerClient, search::{SearchRequest, SearchParams}};
+    /// # use tracker_lib::{TrackerClient, search::{OrderBy, SearchRequest, SearchParams}};
     /// # use serde_json::json;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -86,7 +390,7 @@ impl TrackerClient {
     ///     "queue": "TREK",
     ///     "assignee": "empty()"
     /// }));
-    /// request.order = Some("+status".to_string());
+    /// request.order = vec![OrderBy::asc("status")?, OrderBy::desc("createdAt")?];
     ///
     /// let issues = client.search_issues(&request, None).await?;
     /// println!("Найдено задач: {}", issues.len());
@@ -152,14 +456,204 @@ impl TrackerClient {
             Some(&query_params)
         };
 
-        let (json_value, _) = self.post(resource_path, request, query).await?;
-
-        let issues: Vec<Issue> = serde_json::from_value(json_value)?;
+        // Выдача поиска бывает большой: задачи разбираются по мере чтения ответа
+        let mut issues: Vec<Issue> = Vec::new();
+        self.post_each(resource_path, request, query, |issue| {
+            issues.push(issue);
+            Ok(())
+        })
+        .await?;
 
         tracing::info!(issues_count = issues.len(), "Задачи найдены успешно");
 
         Ok(issues)
     }
+
+    /// Найти задачи в нескольких очередях параллельно
+    ///
+    /// Запрос выполняется отдельно для каждой очереди (см. [`SearchRequest::for_queue`]),
+    /// не больше [`QUEUE_SEARCH_CONCURRENCY`] одновременно. Из каждой очереди задачи
+    /// загружаются постранично, но не больше `per_queue`; очереди, где задач могло быть
+    /// больше, перечисляются в `truncated`. Задачи объединяются без повторов, а ошибка
+    /// одной очереди не мешает остальным и возвращается в `failures`.
+    ///
+    /// # Параметры
+    ///
+    /// * `queues` - Очереди для поиска
+    /// * `request` - Критерии поиска, общие для всех очередей
+    /// * `per_queue` - Сколько задач загружать из одной очереди не больше
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, ids::QueueKey, search::SearchRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let queues: Vec<QueueKey> = vec!["TREK".parse()?, "OPS".parse()?];
+    /// let request = SearchRequest {
+    ///     query: Some("Assignee: me() Resolution: empty()".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let found = client.search_across_queues(&queues, &request, 200).await;
+    /// println!("Найдено задач: {}", found.issues.len());
+    /// for (queue, error) in &found.failures {
+    ///     eprintln!("{queue}: {error}");
+    /// }
+    /// for queue in &found.truncated {
+    ///     eprintln!("{queue}: показаны первые 200 задач");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(queues = queues.len()))]
+    pub async fn search_across_queues(
+        &self,
+        queues: &[QueueKey],
+        request: &SearchRequest,
+        per_queue: usize,
+    ) -> QueuesSearch {
+        tracing::debug!("Поиск задач по нескольким очередям");
+
+        let permits = Arc::new(Semaphore::new(QUEUE_SEARCH_CONCURRENCY));
+        let searches: Vec<_> = queues
+            .iter()
+            .map(|queue| {
+                let client = self.clone();
+                let request = request.for_queue(queue);
+                let permits = Arc::clone(&permits);
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await;
+                    client.search_queue_pages(&request, per_queue).await
+                })
+            })
+            .collect();
+
+        let mut found = QueuesSearch::default();
+        let mut seen = HashSet::new();
+        for (queue, search) in queues.iter().zip(searches) {
+            let result = match search.await {
+                Ok(result) => result,
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            };
+            match result {
+                Ok((issues, truncated)) => {
+                    if truncated {
+                        found.truncated.push(queue.clone());
+                    }
+                    found.issues.extend(
+                        issues
+                            .into_iter()
+                            .filter(|issue| seen.insert(issue.key.clone())),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(queue = %queue, error = %e, "Поиск по очереди не удался");
+                    found.failures.push((queue.clone(), e));
+                }
+            }
+        }
+
+        tracing::info!(
+            issues_count = found.issues.len(),
+            failed_queues = found.failures.len(),
+            truncated_queues = found.truncated.len(),
+            "Поиск по очередям завершён"
+        );
+
+        found
+    }
+
+    /// Задачи одной очереди по страницам, не больше `limit`
+    ///
+    /// Второе значение — выдача упёрлась в `limit`, и в очереди могли остаться задачи.
+    async fn search_queue_pages(
+        &self,
+        request: &SearchRequest,
+        limit: usize,
+    ) -> Result<(Vec<Issue>, bool)> {
+        let per_page = limit.clamp(1, QUEUE_SEARCH_PAGE_SIZE);
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let params = SearchParams {
+                per_page: Some(per_page as u32),
+                page: Some(page),
+                ..Default::default()
+            };
+            let batch = self.search_issues(request, Some(params)).await?;
+            let last = batch.len() < per_page;
+            issues.extend(batch);
+            if issues.len() >= limit {
+                let more = !last || issues.len() > limit;
+                issues.truncate(limit);
+                return Ok((issues, more));
+            }
+            if last {
+                break;
+            }
+        }
+        Ok((issues, false))
+    }
+
+    /// Проверить запрос на языке запросов пробным поиском
+    ///
+    /// Запрашивается одна задача (`perPage=1`), а число совпадений берётся из
+    /// заголовка `X-Total-Count`. Если Трекер отвергает запрос (400 или 422), ошибки
+    /// возвращаются в [`QueryCheck::Invalid`] с позициями, когда они известны.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, search::QueryCheck};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// match client.explain_query("Queue: TREK Resolved: week()").await? {
+    ///     QueryCheck::Valid { total, .. } => println!("Найдётся задач: {total}"),
+    ///     QueryCheck::Invalid(errors) => {
+    ///         for error in errors {
+    ///             eprintln!("{:?}: {}", error.position, error.message);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn explain_query(&self, query: &str) -> Result<QueryCheck> {
+        let request = SearchRequest {
+            query: Some(query.to_string()),
+            ..Default::default()
+        };
+        let params = std::collections::HashMap::from([("perPage".to_string(), "1".to_string())]);
+
+        let (json_value, pagination) =
+            match self.post("issues/_search", &request, Some(&params)).await {
+                Ok(response) => response,
+                Err(TrackerError::ApiError { status, message })
+                    if matches!(
+                        status,
+                        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY
+                    ) =>
+                {
+                    tracing::info!(%status, "Трекер отверг запрос");
+                    return Ok(QueryCheck::Invalid(parse_query_errors(&message)));
+                }
+                Err(e) => return Err(e),
+            };
+
+        let issues: Vec<Issue> = serde_json::from_value(json_value)?;
+        let total = pagination
+            .and_then(|meta| meta.total_count)
+            .unwrap_or(issues.len() as u32);
+        tracing::info!(total, "Запрос проверен");
+
+        Ok(QueryCheck::Valid {
+            total,
+            sample: issues.into_iter().next().map(Box::new),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -173,17 +667,99 @@ mod tests {
                 "queue": "TREK",
                 "assignee": "empty()"
             })),
-            order: Some("+status".to_string()),
+            order: vec!["+status".parse().unwrap()],
             ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("\"filter\""));
         assert!(json.contains("\"queue\""));
-        assert!(json.contains("\"order\""));
+        assert!(json.contains("\"order\":\"+status\""));
         assert!(!json.contains("\"query\""));
     }
 
+    #[test]
+    fn test_order_by_parse() {
+        let order: OrderBy = "status".parse().unwrap();
+        assert_eq!(order, OrderBy::asc("status").unwrap());
+        assert_eq!(order.to_string(), "+status");
+        assert_eq!(
+            "-updatedAt".parse::<OrderBy>().unwrap().direction(),
+            Direction::Desc
+        );
+        assert!("6063181a59590573909db929--sla".parse::<OrderBy>().is_ok());
+
+        for invalid in ["", "+", "-", "+createdat", "-Status", "--sla", "id--"] {
+            assert!(
+                matches!(
+                    invalid.parse::<OrderBy>(),
+                    Err(TrackerError::InvalidOrder(_))
+                ),
+                "{invalid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_request_multiple_order_keys() {
+        let request = SearchRequest {
+            query: Some("Queue: TREK".to_string()),
+            order: vec![
+                OrderBy::asc("priority").unwrap(),
+                OrderBy::desc("createdAt").unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["order"],
+            serde_json::json!(["+priority", "-createdAt"])
+        );
+    }
+
+    #[test]
+    fn test_for_queue_scopes_filter_and_plain_requests() {
+        let queue: QueueKey = "OPS".parse().unwrap();
+
+        let filter = SearchRequest {
+            filter: Some(serde_json::json!({"assignee": "empty()"})),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.for_queue(&queue).filter,
+            Some(serde_json::json!({"assignee": "empty()", "queue": "OPS"}))
+        );
+
+        let plain = SearchRequest::default().for_queue(&queue);
+        assert_eq!(plain.queue.as_deref(), Some("OPS"));
+        assert!(plain.query.is_none());
+    }
+
+    #[test]
+    fn test_for_queue_keeps_sort_outside_conditions() {
+        let queue: QueueKey = "OPS".parse().unwrap();
+        let scoped = |query: &str| {
+            SearchRequest {
+                query: Some(query.to_string()),
+                ..Default::default()
+            }
+            .for_queue(&queue)
+            .query
+            .unwrap()
+        };
+
+        assert_eq!(
+            scoped("Assignee: me() Sort By: Created DESC"),
+            "Queue: OPS AND (Assignee: me()) Sort By: Created DESC"
+        );
+        assert_eq!(
+            scoped(r#""Sort by": Updated"#),
+            r#"Queue: OPS "Sort by": Updated"#
+        );
+        assert_eq!(scoped("Status: open"), "Queue: OPS AND (Status: open)");
+    }
+
     #[test]
     fn test_search_request_with_query() {
         let request = SearchRequest {
@@ -216,6 +792,32 @@ mod tests {
         assert_eq!(json, "{}");
     }
 
+    #[test]
+    fn test_parse_query_errors_with_positions() {
+        let body = r#"{"errors":{"Resolvd":"Поле не найдено"},"errorMessages":["Ошибка в запросе: неожиданный символ ')' at position 17"],"statusCode":400}"#;
+        assert_eq!(
+            parse_query_errors(body),
+            vec![
+                QueryError {
+                    message: "Ошибка в запросе: неожиданный символ ')' at position 17".to_string(),
+                    position: Some(17),
+                },
+                QueryError {
+                    message: "Resolvd: Поле не найдено".to_string(),
+                    position: None,
+                },
+            ]
+        );
+        assert_eq!(error_position("Ошибка разбора, позиция: 5"), Some(5));
+        assert_eq!(
+            parse_query_errors("Bad Request"),
+            vec![QueryError {
+                message: "Bad Request".to_string(),
+                position: None,
+            }]
+        );
+    }
+
     #[test]
     fn test_search_params_default() {
         let params = SearchParams::default();
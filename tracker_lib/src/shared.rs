@@ -0,0 +1,266 @@
+//! Состояние клиента, общее для всех его клонов
+//!
+//! `TrackerClient` свободно клонируется (например, в каждую задачу tokio), поэтому
+//! лимиты запросов, кэш ответов и текущий токен лежат за `Arc`: пауза после 429,
+//! сохранённый ETag или обновлённый токен сразу видны всем клонам.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::api_client::PaginationMeta;
+use crate::events::RateLimit;
+use crate::{Result, TrackerError};
+
+/// Пауза после исчерпания лимита, если сервер не прислал `Retry-After`
+const DEFAULT_PAUSE: Duration = Duration::from_secs(1);
+
+/// Сколько ответов хранит кэш; при переполнении вытесняется давно не читанный
+const RESPONSE_CACHE_CAPACITY: usize = 256;
+
+/// Источник нового токена, когда сервер отвечает 401
+///
+/// Например, IAM-токены Yandex Cloud живут 12 часов, и источник может получать
+/// новый командой `yc iam create-token`. Вызывается в отдельном потоке, поэтому
+/// может блокироваться.
+///
+/// ```
+/// # use tracker_lib::{TokenRefresher, TrackerClient, TrackerConfig, TrackerError};
+/// let refresher = || {
+///     std::env::var("TRACKER_TOKEN").map_err(|e| TrackerError::AuthError(e.to_string()))
+/// };
+/// let client = TrackerClient::builder(TrackerConfig::new("token"))
+///     .token_refresher(refresher)
+///     .build()?;
+/// # Ok::<(), TrackerError>(())
+/// ```
+pub trait TokenRefresher: Send + Sync {
+    fn refresh(&self) -> Result<String>;
+}
+
+impl<F> TokenRefresher for F
+where
+    F: Fn() -> Result<String> + Send + Sync,
+{
+    fn refresh(&self) -> Result<String> {
+        self()
+    }
+}
+
+/// Ответ из кэша: тело и метаданные, отданные сервером вместе с ETag
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    pub etag: String,
+    pub value: Value,
+    pub meta: Option<PaginationMeta>,
+}
+
+/// Кэш ответов с вытеснением давно не использованных записей
+///
+/// Долгоживущий клиент (TUI, `you api`) обходит много разных адресов, поэтому число
+/// записей ограничено. Устаревшими записи не становятся: каждая проверяется по ETag.
+struct ResponseCache {
+    capacity: usize,
+    entries: HashMap<String, (CachedResponse, u64)>,
+    /// Счётчик обращений: чем меньше отметка записи, тем дольше её не читали
+    clock: u64,
+}
+
+impl ResponseCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &str) -> Option<CachedResponse> {
+        let now = self.tick();
+        let (response, used) = self.entries.get_mut(key)?;
+        *used = now;
+        Some(response.clone())
+    }
+
+    fn insert(&mut self, key: String, response: CachedResponse) {
+        let now = self.tick();
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (response, now));
+    }
+}
+
+pub(crate) struct SharedState {
+    token: RwLock<String>,
+    refresher: Option<Arc<dyn TokenRefresher>>,
+    /// Обновление токена идёт одно на все клоны
+    refreshing: tokio::sync::Mutex<()>,
+    rate_limit: Mutex<Option<RateLimit>>,
+    paused_until: Mutex<Option<Instant>>,
+    /// None — кэш выключен
+    cache: Option<Mutex<ResponseCache>>,
+}
+
+impl fmt::Debug for SharedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedState")
+            .field("refresher", &self.refresher.is_some())
+            .field("rate_limit", &self.rate_limit())
+            .field("cache", &self.cache.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SharedState {
+    pub fn new(token: String, refresher: Option<Arc<dyn TokenRefresher>>, cache: bool) -> Self {
+        Self {
+            token: RwLock::new(token),
+            refresher,
+            refreshing: tokio::sync::Mutex::new(()),
+            rate_limit: Mutex::new(None),
+            paused_until: Mutex::new(None),
+            cache: cache.then(|| Mutex::new(ResponseCache::new(RESPONSE_CACHE_CAPACITY))),
+        }
+    }
+
+    pub fn token(&self) -> String {
+        self.token.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn can_refresh(&self) -> bool {
+        self.refresher.is_some()
+    }
+
+    /// Получить новый токен вместо отклонённого сервером
+    ///
+    /// Возвращает true, если токен сменился (этим вызовом или другим клоном, пока
+    /// ждали очереди), и false, если обновлять нечем.
+    pub async fn refresh_token(&self, rejected: &str) -> Result<bool> {
+        let Some(refresher) = self.refresher.clone() else {
+            return Ok(false);
+        };
+        let _guard = self.refreshing.lock().await;
+        if self.token() != rejected {
+            return Ok(true);
+        }
+
+        let token = tokio::task::spawn_blocking(move || refresher.refresh())
+            .await
+            .map_err(|e| TrackerError::AuthError(e.to_string()))??;
+        *self.token.write().unwrap_or_else(|e| e.into_inner()) = token;
+        tracing::info!("Токен обновлён после ответа 401");
+        Ok(true)
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Запомнить лимиты из ответа; после 429, 503 с `Retry-After` или исчерпания
+    /// лимита запросы ждут
+    pub fn observe(&self, status: StatusCode, rate_limit: Option<RateLimit>) {
+        let retry_after = rate_limit.as_ref().and_then(|r| r.retry_after_secs);
+        let exhausted = status == StatusCode::TOO_MANY_REQUESTS
+            || status == StatusCode::SERVICE_UNAVAILABLE && retry_after.is_some()
+            || rate_limit.as_ref().and_then(|r| r.remaining) == Some(0);
+        if exhausted {
+            let pause = rate_limit
+                .as_ref()
+                .and_then(|r| r.retry_after_secs)
+                .map_or(DEFAULT_PAUSE, Duration::from_secs);
+            tracing::warn!(
+                ?pause,
+                "Лимит запросов исчерпан, следующие запросы подождут"
+            );
+            *self.paused_until.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(Instant::now() + pause);
+        }
+        if let Some(rate_limit) = rate_limit {
+            *self.rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = Some(rate_limit);
+        }
+    }
+
+    /// Дождаться конца паузы после исчерпания лимита
+    pub async fn wait_for_rate_limit(&self) {
+        let until = *self.paused_until.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(wait) = until.and_then(|until| until.checked_duration_since(Instant::now()))
+        else {
+            return;
+        };
+        tracing::debug!(?wait, "Ожидание окончания паузы лимита запросов");
+        tokio::time::sleep(wait).await;
+    }
+
+    pub fn caches_responses(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    pub fn cached(&self, key: &str) -> Option<CachedResponse> {
+        let cache = self.cache.as_ref()?;
+        cache.lock().unwrap_or_else(|e| e.into_inner()).get(key)
+    }
+
+    pub fn store(&self, key: String, response: CachedResponse) {
+        if let Some(cache) = &self.cache {
+            cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, response);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(etag: &str) -> CachedResponse {
+        CachedResponse {
+            etag: etag.to_string(),
+            value: Value::Null,
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn test_response_cache_evicts_least_recently_used() {
+        let mut cache = ResponseCache::new(2);
+        cache.insert("a".to_string(), response("1"));
+        cache.insert("b".to_string(), response("2"));
+        // Чтение освежает запись, поэтому вытесняется «b»
+        assert!(cache.get("a").is_some());
+        cache.insert("c".to_string(), response("3"));
+
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a").unwrap().etag, "1");
+        assert_eq!(cache.get("c").unwrap().etag, "3");
+
+        // Обновление существующей записи ничего не вытесняет
+        cache.insert("c".to_string(), response("4"));
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.get("c").unwrap().etag, "4");
+    }
+}
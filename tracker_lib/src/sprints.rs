@@ -0,0 +1,85 @@
+//! Модуль для работы со спринтами досок Яндекс.Трекера
+
+use serde_json::json;
+
+use crate::models::{Issue, SprintDetails};
+use crate::search::{SearchParams, SearchRequest};
+use crate::{Result, TrackerClient};
+
+/// Максимальное количество задач спринта в одном запросе
+const SPRINT_ISSUES_PER_PAGE: u32 = 500;
+
+impl TrackerClient {
+    /// Получить параметры спринта
+    ///
+    /// # Параметры
+    ///
+    /// * `sprint_id` - Идентификатор спринта
+    #[tracing::instrument(skip(self))]
+    pub async fn get_sprint(&self, sprint_id: u64) -> Result<SprintDetails> {
+        tracing::debug!("Получение спринта: {}", sprint_id);
+
+        let resource_path = format!("sprints/{}", sprint_id);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+        let sprint: SprintDetails = serde_json::from_value(json_value)?;
+
+        tracing::info!(name = ?sprint.name, "Спринт получен успешно");
+
+        Ok(sprint)
+    }
+
+    /// Получить спринты доски
+    ///
+    /// # Параметры
+    ///
+    /// * `board_id` - Идентификатор доски
+    #[tracing::instrument(skip(self))]
+    pub async fn get_board_sprints(&self, board_id: u64) -> Result<Vec<SprintDetails>> {
+        tracing::debug!("Получение спринтов доски: {}", board_id);
+
+        let resource_path = format!("boards/{}/sprints", board_id);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+        let sprints: Vec<SprintDetails> = serde_json::from_value(json_value)?;
+
+        tracing::info!(
+            sprints_count = sprints.len(),
+            "Спринты доски получены успешно"
+        );
+
+        Ok(sprints)
+    }
+
+    /// Получить задачи спринта
+    ///
+    /// # Параметры
+    ///
+    /// * `sprint_id` - Идентификатор спринта
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let issues = client.get_sprint_issues(42).await?;
+    /// println!("Задач в спринте: {}", issues.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn get_sprint_issues(&self, sprint_id: u64) -> Result<Vec<Issue>> {
+        tracing::debug!("Получение задач спринта: {}", sprint_id);
+
+        let request = SearchRequest {
+            filter: Some(json!({ "sprint": sprint_id.to_string() })),
+            ..Default::default()
+        };
+        let params = SearchParams {
+            per_page: Some(SPRINT_ISSUES_PER_PAGE),
+            ..Default::default()
+        };
+
+        self.search_issues(&request, Some(params)).await
+    }
+}
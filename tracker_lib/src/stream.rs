@@ -0,0 +1,202 @@
+//! Потоковый разбор JSON-массивов из ответа API
+//!
+//! Поиск, история изменений и комментарии возвращают массивы на тысячи объектов.
+//! Вместо того чтобы собирать весь ответ в `serde_json::Value`, тело читается
+//! частями, а [`ArraySplitter`] выделяет из них элементы верхнего уровня. Каждый
+//! элемент сразу десериализуется в свой тип, поэтому в памяти одновременно
+//! находятся только текущая часть тела и текущий элемент.
+
+use serde::de::Error as _;
+
+use crate::{Result, TrackerError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Ещё не встретилась открывающая скобка массива
+    Start,
+    /// Внутри массива
+    Items,
+    /// Массив закрыт; дальше допустимы только пробельные символы
+    Done,
+}
+
+/// Разбивает поток байтов JSON-массива на элементы верхнего уровня
+///
+/// Границы частей могут приходиться на любое место, в том числе внутрь строки или
+/// многобайтового символа: разделители JSON — ASCII, поэтому разбор идёт по байтам.
+#[derive(Debug)]
+pub(crate) struct ArraySplitter {
+    state: State,
+    /// Глубина вложенности внутри текущего элемента
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Была запятая, после которой ещё не начался элемент
+    expect_item: bool,
+    item: Vec<u8>,
+    count: usize,
+}
+
+impl ArraySplitter {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: State::Start,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            expect_item: false,
+            item: Vec::new(),
+            count: 0,
+        }
+    }
+
+    /// Количество уже выделенных элементов
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Разобрать очередную часть тела, передавая каждый готовый элемент в `on_item`
+    pub(crate) fn feed(
+        &mut self,
+        chunk: &[u8],
+        mut on_item: impl FnMut(&[u8]) -> Result<()>,
+    ) -> Result<()> {
+        for &byte in chunk {
+            match self.state {
+                State::Start => match byte {
+                    b'[' => self.state = State::Items,
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => return Err(invalid("ожидался JSON-массив")),
+                },
+                State::Done => {
+                    if !byte.is_ascii_whitespace() {
+                        return Err(invalid("лишние данные после JSON-массива"));
+                    }
+                }
+                State::Items => self.push(byte, &mut on_item)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Проверить, что массив закрыт
+    pub(crate) fn finish(&self) -> Result<()> {
+        match self.state {
+            State::Done => Ok(()),
+            _ => Err(invalid("JSON-массив оборвался")),
+        }
+    }
+
+    fn push(&mut self, byte: u8, on_item: &mut impl FnMut(&[u8]) -> Result<()>) -> Result<()> {
+        if self.in_string {
+            self.item.push(byte);
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+            }
+            return Ok(());
+        }
+
+        match byte {
+            b',' | b']' if self.depth == 0 => {
+                if self.item.is_empty() {
+                    // Пустой элемент допустим только в пустом массиве `[]`
+                    if byte == b',' || self.expect_item {
+                        return Err(invalid("пустой элемент JSON-массива"));
+                    }
+                } else {
+                    on_item(&self.item)?;
+                    self.item.clear();
+                    self.count += 1;
+                }
+                self.expect_item = byte == b',';
+                if byte == b']' {
+                    self.state = State::Done;
+                }
+            }
+            _ if byte.is_ascii_whitespace() && self.depth == 0 => {}
+            _ => {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth = self
+                            .depth
+                            .checked_sub(1)
+                            .ok_or_else(|| invalid("непарная закрывающая скобка"))?;
+                    }
+                    _ => {}
+                }
+                self.item.push(byte);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: &str) -> TrackerError {
+    TrackerError::JsonParseFailed(serde_json::Error::custom(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(chunks: &[&[u8]]) -> Result<Vec<String>> {
+        let mut splitter = ArraySplitter::new();
+        let mut items = Vec::new();
+        for chunk in chunks {
+            splitter.feed(chunk, |item| {
+                items.push(String::from_utf8(item.to_vec()).unwrap());
+                Ok(())
+            })?;
+        }
+        splitter.finish()?;
+        Ok(items)
+    }
+
+    fn split_body(body: &str) -> Result<Vec<String>> {
+        split(&[body.as_bytes()])
+    }
+
+    #[test]
+    fn test_split_across_chunk_boundaries() {
+        let body =
+            r#" [ {"key": "TREK-1", "summary": "a, [b] \"c\" {d}"}, {"tags": ["x", "y"]}, 7 ] "#;
+        let expected = [
+            r#"{"key": "TREK-1", "summary": "a, [b] \"c\" {d}"}"#,
+            r#"{"tags": ["x", "y"]}"#,
+            "7",
+        ];
+
+        assert_eq!(split_body(body).unwrap(), expected);
+        // Части по одному байту: граница проходит внутри строк и escape-последовательностей
+        let bytes: Vec<&[u8]> = body.as_bytes().chunks(1).collect();
+        assert_eq!(split(&bytes).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_split_multibyte_text() {
+        let body = r#"[{"summary": "Падает экспорт"}]"#.as_bytes();
+        let (head, tail) = body.split_at(21);
+
+        assert_eq!(
+            split(&[head, tail]).unwrap(),
+            [r#"{"summary": "Падает экспорт"}"#]
+        );
+    }
+
+    #[test]
+    fn test_split_rejects_malformed_arrays() {
+        assert!(split_body("[]").unwrap().is_empty());
+        assert!(split_body("{\"key\": 1}").is_err());
+        assert!(split_body("[1, 2").is_err());
+        assert!(split_body("[1,, 2]").is_err());
+        assert!(split_body("[1, 2,]").is_err());
+        assert!(split_body("[1] 2").is_err());
+        assert!(split_body("[1}]").is_err());
+    }
+}
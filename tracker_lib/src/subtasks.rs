@@ -0,0 +1,172 @@
+//! Подзадачи
+//!
+//! Создание подзадачи под родительской задачей, список подзадач и прогресс их
+//! выполнения. Подзадача считается выполненной, если у неё есть резолюция.
+
+use std::fmt;
+
+use crate::ids::IssueKey;
+use crate::models::Issue;
+use crate::search::{OrderBy, SearchParams, SearchRequest};
+use crate::task::CreateIssueRequest;
+use crate::{Result, TrackerClient};
+
+/// Размер страницы при получении подзадач
+const SUBTASKS_PAGE_SIZE: u32 = 100;
+
+/// Прогресс подзадач: сколько выполнено из общего числа
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubtaskProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl SubtaskProgress {
+    /// Посчитать прогресс по списку подзадач
+    ///
+    /// ```
+    /// # use tracker_lib::subtasks::SubtaskProgress;
+    /// # use tracker_lib::models::Issue;
+    /// let subtasks: Vec<Issue> = serde_json::from_value(serde_json::json!([
+    ///     {"key": "TREK-2", "summary": "Схема", "resolvedAt": "2026-10-16T09:30:00.000+0000"},
+    ///     {"key": "TREK-3", "summary": "Миграция"}
+    /// ])).unwrap();
+    /// assert_eq!(SubtaskProgress::from_issues(&subtasks).to_string(), "1/2");
+    /// ```
+    pub fn from_issues(subtasks: &[Issue]) -> Self {
+        Self {
+            done: subtasks
+                .iter()
+                .filter(|issue| issue.resolved_at.is_some())
+                .count(),
+            total: subtasks.len(),
+        }
+    }
+
+    /// Все подзадачи выполнены; задача без подзадач не считается завершённой
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.done == self.total
+    }
+}
+
+impl fmt::Display for SubtaskProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.done, self.total)
+    }
+}
+
+impl TrackerClient {
+    /// Создать подзадачу
+    ///
+    /// # Параметры
+    ///
+    /// * `parent_key` - Ключ родительской задачи
+    /// * `request` - Очередь, название и необязательные поля подзадачи; поле `parent`
+    ///   заменяется на `parent_key`
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, task::CreateIssueRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let request = CreateIssueRequest::new("TREK".parse()?, "Написать миграцию");
+    /// let subtask = client.create_subtask(&"TREK-1".parse()?, &request).await?;
+    /// println!("Создана подзадача {}", subtask.key);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(parent_key = %parent_key))]
+    pub async fn create_subtask(
+        &self,
+        parent_key: &IssueKey,
+        request: &CreateIssueRequest,
+    ) -> Result<Issue> {
+        tracing::debug!("Создание подзадачи: {}", request.summary);
+
+        let request = CreateIssueRequest {
+            parent: Some(parent_key.clone()),
+            ..request.clone()
+        };
+        self.create_issue(&request).await
+    }
+
+    /// Получить подзадачи задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `parent_key` - Ключ родительской задачи
+    ///
+    /// Подзадачи возвращаются в порядке создания, все страницы выдачи.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for subtask in client.list_subtasks(&"TREK-1".parse()?).await? {
+    ///     println!("{} {}", subtask.key, subtask.summary);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(parent_key = %parent_key))]
+    pub async fn list_subtasks(&self, parent_key: &IssueKey) -> Result<Vec<Issue>> {
+        tracing::debug!("Получение подзадач: {}", parent_key);
+
+        let request = SearchRequest {
+            filter: Some(serde_json::json!({ "parent": parent_key })),
+            order: vec![OrderBy::asc("createdAt")?],
+            ..Default::default()
+        };
+
+        let mut subtasks = Vec::new();
+        for page in 1.. {
+            let params = SearchParams {
+                per_page: Some(SUBTASKS_PAGE_SIZE),
+                page: Some(page),
+                ..Default::default()
+            };
+            let batch = self.search_issues(&request, Some(params)).await?;
+            let last = batch.len() < SUBTASKS_PAGE_SIZE as usize;
+            subtasks.extend(batch);
+            if last {
+                break;
+            }
+        }
+
+        tracing::info!(
+            subtasks_count = subtasks.len(),
+            "Подзадачи получены успешно"
+        );
+
+        Ok(subtasks)
+    }
+
+    /// Получить прогресс подзадач: сколько выполнено из общего числа
+    ///
+    /// # Параметры
+    ///
+    /// * `parent_key` - Ключ родительской задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let progress = client.subtask_progress(&"TREK-1".parse()?).await?;
+    /// println!("Выполнено {progress}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(parent_key = %parent_key))]
+    pub async fn subtask_progress(&self, parent_key: &IssueKey) -> Result<SubtaskProgress> {
+        let subtasks = self.list_subtasks(parent_key).await?;
+        Ok(SubtaskProgress::from_issues(&subtasks))
+    }
+}
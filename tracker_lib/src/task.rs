@@ -1,14 +1,210 @@
 //! Модуль для работы с задачами в Яндекс.Трекере
 //!
-//! Содержит методы для получения информации о конкретных задачах.
+//! Содержит методы для получения, создания и изменения задач.
 
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::ids::{is_issue_number, is_queue_key, IssueKey, QueueKey, UserLogin};
 use crate::models::{ExpandField, Issue};
-use crate::{Result, TrackerClient};
+use crate::template::{self, IssueTemplate};
+use crate::{Conditional, Result, TrackerClient};
+
+/// Тело запроса для создания задачи
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateIssueRequest {
+    /// Ключ очереди
+    pub queue: QueueKey,
+
+    /// Название задачи
+    pub summary: String,
+
+    /// Описание задачи
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Ключ типа задачи (например, bug)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<String>,
+
+    /// Ключ приоритета (например, normal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Логин исполнителя
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<UserLogin>,
+
+    /// Теги задачи
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Компоненты задачи (названия или идентификаторы)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<String>,
+
+    /// Логины наблюдателей задачи
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub followers: Vec<UserLogin>,
+
+    /// Ключ родительской задачи, если новая задача — подзадача
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<IssueKey>,
+
+    /// Срок выполнения задачи
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<NaiveDate>,
+
+    /// Уникальный идентификатор запроса: повторное создание задачи с тем же
+    /// значением отклоняется Трекером с кодом 409
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<String>,
+}
+
+impl CreateIssueRequest {
+    /// Создать запрос с очередью и названием задачи
+    pub fn new(queue: QueueKey, summary: impl Into<String>) -> Self {
+        Self {
+            queue,
+            summary: summary.into(),
+            description: None,
+            issue_type: None,
+            priority: None,
+            assignee: None,
+            tags: Vec::new(),
+            components: Vec::new(),
+            followers: Vec::new(),
+            parent: None,
+            deadline: None,
+            unique: None,
+        }
+    }
+}
+
+/// Тело запроса изменения задачи в семантике PATCH Трекера
+///
+/// Поле можно задать, обнулить или изменить как массив: добавить, удалить или
+/// заменить отдельные значения (наблюдатели, теги, спринты). Поля, не упомянутые
+/// в запросе, Трекер не трогает.
+///
+/// # Примеры
+///
+/// ```
+/// # use tracker_lib::task::UpdateIssueRequest;
+/// let request = UpdateIssueRequest::new()
+///     .set("summary", "Новое название")
+///     .clear("assignee")
+///     .add("tags", ["backend"])
+///     .remove("tags", ["triage"])
+///     .replace("followers", [("ann", "bob")]);
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap(),
+///     serde_json::json!({
+///         "summary": "Новое название",
+///         "assignee": null,
+///         "tags": {"add": ["backend"], "remove": ["triage"]},
+///         "followers": {"replace": [{"target": "ann", "replacement": "bob"}]}
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct UpdateIssueRequest {
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UpdateIssueRequest {
+    /// Пустой запрос
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Задать значение поля целиком
+    pub fn set(mut self, field: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.fields.insert(field.to_string(), value.into());
+        self
+    }
+
+    /// Обнулить поле
+    pub fn clear(self, field: &str) -> Self {
+        self.set(field, serde_json::Value::Null)
+    }
+
+    /// Добавить значения в поле-массив, сохранив существующие
+    pub fn add<V: Into<serde_json::Value>>(
+        self,
+        field: &str,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        self.array_operation(field, "add", values)
+    }
+
+    /// Удалить значения из поля-массива
+    pub fn remove<V: Into<serde_json::Value>>(
+        self,
+        field: &str,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        self.array_operation(field, "remove", values)
+    }
+
+    /// Заменить отдельные значения поля-массива: пары «было — стало»
+    pub fn replace<V: Into<serde_json::Value>>(
+        self,
+        field: &str,
+        pairs: impl IntoIterator<Item = (V, V)>,
+    ) -> Self {
+        let pairs = pairs
+            .into_iter()
+            .map(|(target, replacement)| {
+                serde_json::json!({ "target": target.into(), "replacement": replacement.into() })
+            })
+            .collect();
+        self.array_operation(field, "replace", pairs)
+    }
+
+    /// Есть ли в запросе хоть одно изменение
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Добавляет операцию над массивом к уже заданным операциям этого поля
+    ///
+    /// Значение, заданное через [`Self::set`] или [`Self::clear`], заменяется.
+    fn array_operation(
+        mut self,
+        field: &str,
+        operation: &str,
+        values: Vec<serde_json::Value>,
+    ) -> Self {
+        let entry = self
+            .fields
+            .entry(field.to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if !entry.is_object() {
+            *entry = serde_json::Value::Object(Default::default());
+        }
+        if let serde_json::Value::Object(operations) = entry {
+            let existing = operations
+                .entry(operation)
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            if let serde_json::Value::Array(existing) = existing {
+                existing.extend(values);
+            }
+        }
+        self
+    }
+}
 
 /// Форматирует полный вывод информации о задаче (чистая функция)
 ///
+/// Вывод строится по встроенному шаблону [`template::FULL`]; другие шаблоны
+/// применяются через [`IssueTemplate`].
+///
 /// # Параметры
 ///
 /// * `issue` - Задача из Трекера
@@ -25,44 +221,46 @@ use crate::{Result, TrackerClient};
 /// # #[tokio::main]
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = TrackerClient::from_env()?;
-/// let issue = client.get_issue("TREK-123", None).await?;
+/// let issue = client.get_issue(&"TREK-123".parse()?, None).await?;
 /// let output = format_issue_output(&issue);
 /// println!("{}", output);
 /// # Ok(())
 /// # }
 /// ```
 pub fn format_issue_output(issue: &Issue) -> String {
-    let key = &issue.key;
-    let title = &issue.summary;
-    let status = issue
-        .status
-        .as_ref()
-        .and_then(|s| s.display.as_ref())
-        .map(|s| s.as_str())
-        .unwrap_or("Неизвестен");
-    let description = issue.description.as_deref().unwrap_or("Нет описания");
-    let link = format!("https://st.yandex-team.ru/{}", key);
-
-    let mut output = String::new();
-    output.push('\n');
-    output.push_str(&format!("📋 Задача: {}\n", key));
-    output.push('\n');
-    output.push_str("📌 Заголовок:\n");
-    output.push_str(&format!("   {}\n", title));
-    output.push('\n');
-    output.push_str(&format!("🔖 Статус: {}\n", status));
-    output.push('\n');
-    output.push_str("📝 Описание:\n");
-    // Разбиваем описание на строки для лучшей читаемости
-    for line in description.lines() {
-        output.push_str(&format!("   {}\n", line));
-    }
-    output.push('\n');
-    output.push_str("🔗 Ссылка:\n");
-    output.push_str(&format!("   {}\n", link));
-    output.push('\n');
-
-    output
+    IssueTemplate::new(template::FULL).render(issue)
+}
+
+/// Найти ключи задач (например, `TREK-123`) в произвольном тексте
+///
+/// Ключи возвращаются в порядке первого появления, без повторов.
+///
+/// # Примеры
+///
+/// ```
+/// # use tracker_lib::task::find_issue_keys;
+/// assert_eq!(find_issue_keys("TREK-1: fix (see TREK-2, TREK-1)"), ["TREK-1", "TREK-2"]);
+/// ```
+pub fn find_issue_keys(text: &str) -> Vec<IssueKey> {
+    let mut keys: Vec<IssueKey> = Vec::new();
+    let words = text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'));
+    for word in words {
+        // Ключ может быть частью имени ветки: TREK-3-socks-proxy
+        let parts: Vec<&str> = word.split('-').collect();
+        for pair in parts.windows(2) {
+            let (queue, number) = (pair[0], pair[1]);
+            // Ключ в тексте должен быть написан заглавными, иначе слишком много ложных совпадений
+            if !is_queue_key(queue) || !is_issue_number(number) {
+                continue;
+            }
+            if let Ok(key) = format!("{queue}-{number}").parse::<IssueKey>() {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+    keys
 }
 
 /// Параметры запроса для получения задачи
@@ -77,7 +275,7 @@ impl TrackerClient {
     ///
     /// # Параметры
     ///
-    /// * `issue_id` - Идентификатор или ключ задачи
+    /// * `issue_key` - Ключ задачи
     /// * `params` - Дополнительные параметры запроса (опционально)
     ///
     /// # Примеры
@@ -87,16 +285,20 @@ impl TrackerClient {
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = TrackerClient::with_token("your-oauth-token")?;
-    /// let issue = client.get_issue("TREK-123", None).await?;
+    /// let issue = client.get_issue(&"TREK-123".parse()?, None).await?;
     /// println!("Задача: {} - {}", issue.key, issue.summary);
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self), fields(issue_id = %issue_id))]
-    pub async fn get_issue(&self, issue_id: &str, params: Option<GetIssueParams>) -> Result<Issue> {
-        tracing::debug!("Получение задачи: {}", issue_id);
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_issue(
+        &self,
+        issue_key: &IssueKey,
+        params: Option<GetIssueParams>,
+    ) -> Result<Issue> {
+        tracing::debug!("Получение задачи: {}", issue_key);
 
-        let resource_path = format!("issues/{}", issue_id);
+        let resource_path = format!("issues/{}", issue_key);
 
         let expand = params
             .unwrap_or_default()
@@ -125,6 +327,149 @@ impl TrackerClient {
 
         Ok(issue)
     }
+
+    /// Получить задачу, только если она изменилась с момента получения `etag`
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `etag` - ETag из предыдущего ответа; None — запросить задачу безусловно
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{Conditional, TrackerClient};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// if let Conditional::Modified { value, etag } = client.get_issue_if_changed(&"TREK-123".parse()?, None).await? {
+    ///     println!("Задача: {} (ETag {:?})", value.summary, etag);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_issue_if_changed(
+        &self,
+        issue_key: &IssueKey,
+        etag: Option<&str>,
+    ) -> Result<Conditional<Issue>> {
+        tracing::debug!("Условное получение задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}", issue_key);
+        let fetched = match self.get_if_none_match(&resource_path, etag, None).await? {
+            Conditional::NotModified => Conditional::NotModified,
+            Conditional::Modified { value, etag } => Conditional::Modified {
+                value: serde_json::from_value(value)?,
+                etag,
+            },
+        };
+
+        Ok(fetched)
+    }
+
+    /// Создать задачу
+    ///
+    /// # Параметры
+    ///
+    /// * `request` - Очередь, название и необязательные поля новой задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, task::CreateIssueRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let mut request = CreateIssueRequest::new("TREK".parse()?, "Падает экспорт в CSV");
+    /// request.issue_type = Some("bug".to_string());
+    /// let issue = client.create_issue(&request).await?;
+    /// println!("Создана задача {}", issue.key);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(queue = %request.queue))]
+    pub async fn create_issue(&self, request: &CreateIssueRequest) -> Result<Issue> {
+        tracing::debug!("Создание задачи в очереди: {}", request.queue);
+        self.check_sandbox_queue(&request.queue)?;
+
+        let (json_value, _) = self.post("issues/", request, None).await?;
+
+        let issue: Issue = serde_json::from_value(json_value)?;
+
+        tracing::info!(issue_key = %issue.key, "Задача создана успешно");
+
+        Ok(issue)
+    }
+
+    /// Изменить поля задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `request` - Изменяемые поля
+    /// * `version` - Известная версия задачи (опционально). Если задача успела
+    ///   измениться, возвращается `TrackerError::VersionConflict` с её текущим состоянием
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::{TrackerClient, task::UpdateIssueRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let request = UpdateIssueRequest::new()
+    ///     .set("summary", "Новое название")
+    ///     .add("followers", ["ann"]);
+    /// let issue = client.update_issue(&"TREK-123".parse()?, &request, None).await?;
+    /// println!("Задача: {} - {}", issue.key, issue.summary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn update_issue(
+        &self,
+        issue_key: &IssueKey,
+        request: &UpdateIssueRequest,
+        version: Option<u32>,
+    ) -> Result<Issue> {
+        tracing::debug!("Изменение задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}", issue_key);
+        let query = Self::version_query(version);
+        let result = self.patch(&resource_path, request, query.as_ref()).await;
+        let (json_value, _) = self
+            .check_version_conflict(issue_key.as_str(), version, result)
+            .await?;
+
+        let issue: Issue = serde_json::from_value(json_value)?;
+
+        tracing::info!(issue_key = %issue.key, "Задача изменена успешно");
+
+        Ok(issue)
+    }
+
+    /// Назначить исполнителя задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `login` - Логин нового исполнителя
+    pub async fn assign_issue(&self, issue_key: &IssueKey, login: &UserLogin) -> Result<Issue> {
+        let request = UpdateIssueRequest::new().set("assignee", login.as_str());
+        self.update_issue(issue_key, &request, None).await
+    }
+
+    /// Добавить теги к задаче, сохранив существующие
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `tags` - Добавляемые теги
+    pub async fn add_tags(&self, issue_key: &IssueKey, tags: &[String]) -> Result<Issue> {
+        let request = UpdateIssueRequest::new().add("tags", tags.iter().map(String::as_str));
+        self.update_issue(issue_key, &request, None).await
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +505,13 @@ mod tests {
             previous_status: None,
             favorite: false,
             tags: vec![],
+            story_points: None,
+            resolved_at: None,
+            estimation: None,
+            deadline: None,
+            transitions: None,
+            attachments: None,
+            comments: None,
         }
     }
 
@@ -200,4 +552,13 @@ mod tests {
         assert!(output.contains("   Third line"));
         assert!(output.contains("   https://st.yandex-team.ru/TREK-9844"));
     }
+
+    #[test]
+    fn test_find_issue_keys() {
+        assert_eq!(
+            find_issue_keys("[TREK-12] Fix proxy; refs TREK-12, OPS2-7 and feature/TREK-3-socks"),
+            ["TREK-12", "OPS2-7", "TREK-3"]
+        );
+        assert!(find_issue_keys("trek-1 TREK- -1 TREK-1a 2024-05").is_empty());
+    }
 }
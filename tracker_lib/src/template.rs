@@ -0,0 +1,239 @@
+//! Шаблоны вывода задачи
+//!
+//! Шаблон — текст с переменными в фигурных скобках: `{key}`, `{status}` и другие из
+//! [`VARIABLES`]. Скобки с другим содержимым выводятся как есть. Если перед переменной
+//! в строке только пробелы, многострочное значение выводится с тем же отступом.
+
+use crate::models::{Issue, User};
+
+/// Адрес задачи в веб-интерфейсе Трекера
+const WEB_URL: &str = "https://st.yandex-team.ru";
+
+/// Переменные, доступные в шаблоне
+pub const VARIABLES: &[&str] = &[
+    "key",
+    "summary",
+    "status",
+    "description",
+    "link",
+    "type",
+    "priority",
+    "queue",
+    "assignee",
+    "author",
+    "created",
+    "updated",
+    "tags",
+];
+
+/// Полный вывод задачи: ключ, заголовок, статус, описание и ссылка
+pub const FULL: &str = "
+📋 Задача: {key}
+
+📌 Заголовок:
+   {summary}
+
+🔖 Статус: {status}
+
+📝 Описание:
+   {description}
+
+🔗 Ссылка:
+   {link}
+
+";
+
+/// Одна строка: ключ, статус и заголовок
+pub const SHORT: &str = "{key}  [{status}]  {summary}";
+
+/// Шаблон вывода задачи
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueTemplate {
+    source: String,
+}
+
+impl IssueTemplate {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// Встроенный шаблон по имени: `full` или `short`
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(Self::new(FULL)),
+            "short" => Some(Self::new(SHORT)),
+            _ => None,
+        }
+    }
+
+    /// Переменные шаблона, которых нет в [`VARIABLES`]
+    ///
+    /// ```
+    /// # use tracker_lib::template::IssueTemplate;
+    /// let template = IssueTemplate::new("{key} {asignee} {\"json\": 1}");
+    /// assert_eq!(template.unknown_variables(), ["asignee"]);
+    /// ```
+    pub fn unknown_variables(&self) -> Vec<&str> {
+        let mut unknown: Vec<&str> = Vec::new();
+        for part in self.source.split('{').skip(1) {
+            let Some((name, _)) = part.split_once('}') else {
+                continue;
+            };
+            if is_variable_name(name) && !VARIABLES.contains(&name) && !unknown.contains(&name) {
+                unknown.push(name);
+            }
+        }
+        unknown
+    }
+
+    /// Вывести задачу по шаблону
+    ///
+    /// ```
+    /// # use tracker_lib::{models::Issue, template::IssueTemplate};
+    /// let issue: Issue = serde_json::from_value(serde_json::json!({
+    ///     "key": "TREK-1",
+    ///     "summary": "Починить экспорт",
+    ///     "status": {"display": "Открыт"}
+    /// })).unwrap();
+    /// let output = IssueTemplate::builtin("short").unwrap().render(&issue);
+    /// assert_eq!(output, "TREK-1  [Открыт]  Починить экспорт");
+    /// ```
+    pub fn render(&self, issue: &Issue) -> String {
+        self.render_with(issue, |_, value| value)
+    }
+
+    /// Вывести задачу по шаблону, пропуская значения через `style`
+    ///
+    /// `style` получает имя переменной и её значение и возвращает текст для вывода,
+    /// например, с цветом или ссылкой. Отступ многострочных значений добавляется после.
+    pub fn render_with<F>(&self, issue: &Issue, style: F) -> String
+    where
+        F: Fn(&str, String) -> String,
+    {
+        let mut result = String::with_capacity(self.source.len());
+        let mut rest = self.source.as_str();
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after
+                .split_once('}')
+                .and_then(|(name, tail)| Some((name, variable(issue, name)?, tail)));
+            match value {
+                Some((name, value, tail)) => {
+                    let value = value.trim_end_matches('\n').to_string();
+                    push_indented(&mut result, &style(name, value));
+                    rest = tail;
+                }
+                None => {
+                    result.push('{');
+                    rest = after;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Адрес задачи в веб-интерфейсе
+pub fn issue_url(key: &str) -> String {
+    format!("{WEB_URL}/{key}")
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Значение переменной шаблона; None — такой переменной нет
+fn variable(issue: &Issue, name: &str) -> Option<String> {
+    let value = match name {
+        "key" => issue.key.clone(),
+        "summary" => issue.summary.clone(),
+        "status" => display(issue.status.as_ref().and_then(|s| s.display.as_deref()))
+            .unwrap_or("Неизвестен")
+            .to_string(),
+        "description" => display(issue.description.as_deref())
+            .unwrap_or("Нет описания")
+            .to_string(),
+        "link" => issue_url(&issue.key),
+        "type" => or_dash(issue.issue_type.as_ref().and_then(|t| t.display.as_deref())),
+        "priority" => or_dash(issue.priority.as_ref().and_then(|p| p.display.as_deref())),
+        "queue" => or_dash(issue.queue.as_ref().and_then(|q| q.key.as_deref())),
+        "assignee" => user(issue.assignee.as_ref()).unwrap_or_else(|| "Не назначен".to_string()),
+        "author" => user(issue.created_by.as_ref()).unwrap_or_else(|| "—".to_string()),
+        "created" => or_dash(issue.created_at.as_deref()),
+        "updated" => or_dash(issue.updated_at.as_deref()),
+        "tags" => or_dash(Some(issue.tags.join(", ")).as_deref()),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn display(value: Option<&str>) -> Option<&str> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn or_dash(value: Option<&str>) -> String {
+    display(value).unwrap_or("—").to_string()
+}
+
+fn user(user: Option<&User>) -> Option<String> {
+    let user = user?;
+    user.display.clone().or_else(|| user.login.clone())
+}
+
+/// Дописать значение; строки многострочного значения получают отступ текущей строки
+fn push_indented(result: &mut String, value: &str) {
+    let line_start = result.rfind('\n').map_or(0, |i| i + 1);
+    let indent = result[line_start..].to_string();
+    if indent.trim().is_empty() {
+        result.push_str(&value.replace('\n', &format!("\n{indent}")));
+    } else {
+        result.push_str(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue() -> Issue {
+        serde_json::from_value(serde_json::json!({
+            "key": "TREK-7",
+            "summary": "Экспорт в CSV",
+            "description": "Первая строка\n\nТретья строка\n",
+            "assignee": {"login": "ivanov"},
+            "tags": ["export", "csv"]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_indents_multiline_values() {
+        let template = IssueTemplate::new("{key}: {assignee} [{tags}] {type}\n   {description}\n");
+        assert_eq!(
+            template.render(&issue()),
+            "TREK-7: ivanov [export, csv] —\n   Первая строка\n   \n   Третья строка\n"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_braces() {
+        let template = IssueTemplate::new("{\"key\": \"{key}\"} {missing}");
+        assert_eq!(template.render(&issue()), "{\"key\": \"TREK-7\"} {missing}");
+        assert_eq!(template.unknown_variables(), ["missing"]);
+        assert!(IssueTemplate::new(FULL).unknown_variables().is_empty());
+    }
+
+    #[test]
+    fn test_render_with_styles_values_before_indent() {
+        let template = IssueTemplate::new("[{key}]\n  {description}");
+        let output = template.render_with(&issue(), |name, value| match name {
+            "key" => value.to_lowercase(),
+            _ => format!("<{value}>"),
+        });
+        assert_eq!(output, "[trek-7]\n  <Первая строка\n  \n  Третья строка>");
+    }
+}
@@ -0,0 +1,120 @@
+//! Модуль для работы с жизненным циклом задач в Яндекс.Трекере
+//!
+//! Содержит методы для получения доступных переходов и смены статуса задачи.
+
+use serde::Serialize;
+
+use crate::ids::IssueKey;
+use crate::models::Transition;
+use crate::{Result, TrackerClient};
+
+/// Тело запроса для выполнения перехода
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecuteTransitionRequest {
+    /// Комментарий, добавляемый к задаче при переходе
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl TrackerClient {
+    /// Получить список доступных переходов для задачи
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let transitions = client.get_transitions(&"TREK-123".parse()?).await?;
+    /// for transition in transitions {
+    ///     println!("{}", transition.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_transitions(&self, issue_key: &IssueKey) -> Result<Vec<Transition>> {
+        tracing::debug!("Получение переходов задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/transitions", issue_key);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+
+        let transitions: Vec<Transition> = serde_json::from_value(json_value)?;
+
+        tracing::info!(
+            transitions_count = transitions.len(),
+            "Переходы задачи получены успешно"
+        );
+
+        Ok(transitions)
+    }
+
+    /// Выполнить переход задачи в другой статус
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `transition_id` - Идентификатор перехода
+    /// * `request` - Дополнительные параметры перехода
+    /// * `version` - Известная версия задачи (опционально), см. [`TrackerClient::update_issue`]
+    ///
+    /// # Возвращает
+    ///
+    /// Список переходов, доступных после смены статуса
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key, transition_id = %transition_id))]
+    pub async fn execute_transition(
+        &self,
+        issue_key: &IssueKey,
+        transition_id: &str,
+        request: &ExecuteTransitionRequest,
+        version: Option<u32>,
+    ) -> Result<Vec<Transition>> {
+        tracing::debug!(
+            "Выполнение перехода {} для задачи {}",
+            transition_id,
+            issue_key
+        );
+
+        let resource_path = format!(
+            "issues/{}/transitions/{}/_execute",
+            issue_key, transition_id
+        );
+        let query = Self::version_query(version);
+        let result = self.post(&resource_path, request, query.as_ref()).await;
+        let (json_value, _) = self
+            .check_version_conflict(issue_key.as_str(), version, result)
+            .await?;
+
+        let transitions: Vec<Transition> = serde_json::from_value(json_value)?;
+
+        tracing::info!("Переход задачи выполнен успешно");
+
+        Ok(transitions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execute_transition_request_empty_serialization() {
+        let request = ExecuteTransitionRequest::default();
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, "{}");
+    }
+
+    #[test]
+    fn test_execute_transition_request_with_comment() {
+        let request = ExecuteTransitionRequest {
+            comment: Some("Готово".to_string()),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(json.contains("\"comment\":\"Готово\""));
+    }
+}
@@ -0,0 +1,123 @@
+//! Модуль для работы с пользователями Яндекс.Трекера
+
+use std::collections::HashMap;
+
+use crate::models::User;
+use crate::{Result, TrackerClient};
+
+/// Максимальное количество пользователей на странице ответа
+const USERS_PER_PAGE: u32 = 100;
+
+impl TrackerClient {
+    /// Получить информацию о текущем пользователе (владельце токена)
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// let me = client.get_myself().await?;
+    /// println!("Логин: {:?}", me.login);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn get_myself(&self) -> Result<User> {
+        tracing::debug!("Получение информации о текущем пользователе");
+
+        let (json_value, _) = self.get("myself", None).await?;
+        let user: User = serde_json::from_value(json_value)?;
+
+        tracing::info!(login = ?user.login, "Текущий пользователь получен успешно");
+
+        Ok(user)
+    }
+
+    /// Получить всех пользователей организации
+    ///
+    /// Пользователи запрашиваются постранично, пока не будут получены все страницы.
+    /// С включённым кэшем ответов повторный запрос неизменившегося списка не
+    /// передаёт его заново.
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for user in client.get_users().await? {
+    ///     println!("{:?}: {:?}", user.login, user.display);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn get_users(&self) -> Result<Vec<User>> {
+        tracing::debug!("Получение списка пользователей организации");
+
+        let mut users: Vec<User> = Vec::new();
+        let mut page = 1;
+        loop {
+            let mut query_params = HashMap::new();
+            query_params.insert("perPage".to_string(), USERS_PER_PAGE.to_string());
+            query_params.insert("page".to_string(), page.to_string());
+
+            let pagination = self
+                .get_each("users", Some(&query_params), |user| {
+                    users.push(user);
+                    Ok(())
+                })
+                .await?;
+            let total_pages = pagination.and_then(|meta| meta.total_pages).unwrap_or(1);
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        tracing::info!(users_count = users.len(), "Пользователи получены успешно");
+
+        Ok(users)
+    }
+
+    /// Найти пользователей, у которых логин или слово имени начинается с `prefix`
+    ///
+    /// API Трекера не ищет пользователей по части имени, поэтому список организации
+    /// загружается целиком и фильтруется на клиенте. Пустой префикс возвращает всех.
+    /// Результат отсортирован по логину.
+    ///
+    /// # Параметры
+    ///
+    /// * `prefix` - Начало логина или имени, регистр не важен
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use tracker_lib::TrackerClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::from_env()?;
+    /// for user in client.search_users("ива").await? {
+    ///     println!("{:?}", user.display);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub async fn search_users(&self, prefix: &str) -> Result<Vec<User>> {
+        let mut users: Vec<User> = self
+            .get_users()
+            .await?
+            .into_iter()
+            .filter(|user| user.matches_prefix(prefix))
+            .collect();
+        users.sort_by(|a, b| a.login.cmp(&b.login));
+
+        tracing::debug!(found = users.len(), "Поиск пользователей завершён");
+
+        Ok(users)
+    }
+}
@@ -0,0 +1,167 @@
+//! Модуль для приёма вебхуков Яндекс.Трекера
+//!
+//! Трекер отправляет HTTP-запросы из триггеров с телом, заданным в настройках
+//! триггера. Модуль описывает ожидаемый формат тела и проверку общего секрета.
+//! Пример тела для действия «HTTP-запрос» триггера:
+//!
+//! ```json
+//! {
+//!   "event": "issueCreated",
+//!   "issue": {"key": "{{issue.key}}", "summary": "{{issue.summary}}", "queue": "{{issue.queue.key}}"},
+//!   "author": "{{currentUser.login}}"
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{IssueKey, QueueKey};
+use crate::{Result, TrackerError};
+
+/// Заголовок, в котором триггер передаёт общий секрет
+pub const SECRET_HEADER: &str = "X-Tracker-Webhook-Secret";
+
+/// Событие, по которому сработал триггер
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    IssueCreated,
+    IssueUpdated,
+    StatusChanged,
+    CommentAdded,
+    /// Изменился исполнитель задачи
+    IssueAssigned,
+    /// Истёк таймер SLA
+    SlaBreached,
+    /// Любое другое значение `event`
+    #[serde(other)]
+    Other,
+}
+
+/// Задача из тела вебхука
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookIssue {
+    /// Ключ задачи
+    pub key: IssueKey,
+
+    /// Название задачи
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// Ключ очереди
+    #[serde(default)]
+    pub queue: Option<QueueKey>,
+
+    /// Статус задачи
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Тело вебхука
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Событие триггера
+    pub event: WebhookEvent,
+
+    /// Задача, к которой относится событие
+    pub issue: WebhookIssue,
+
+    /// Логин пользователя, выполнившего действие
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// Текст комментария для `commentAdded`
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl WebhookPayload {
+    /// Разобрать и проверить тело вебхука
+    ///
+    /// Кроме формата проверяется, что ключ задачи похож на ключ: незаполненный
+    /// шаблон триггера вроде `{{issue.key}}` отклоняется.
+    ///
+    /// # Примеры
+    ///
+    /// ```
+    /// # use tracker_lib::webhook::{WebhookEvent, WebhookPayload};
+    /// let body = br#"{"event": "statusChanged", "issue": {"key": "TREK-1"}}"#;
+    /// let payload = WebhookPayload::parse(body).unwrap();
+    /// assert_eq!(payload.event, WebhookEvent::StatusChanged);
+    /// assert_eq!(payload.queue(), "TREK");
+    /// ```
+    pub fn parse(body: &[u8]) -> Result<Self> {
+        serde_json::from_slice(body).map_err(|e| TrackerError::InvalidWebhook(e.to_string()))
+    }
+
+    /// Очередь задачи: из тела или из ключа задачи
+    pub fn queue(&self) -> QueueKey {
+        self.issue
+            .queue
+            .clone()
+            .unwrap_or_else(|| self.issue.key.queue())
+    }
+}
+
+/// Сравнить секрет из запроса с ожидаемым за время, не зависящее от совпадающего префикса
+pub fn verify_secret(expected: &str, provided: Option<&str>) -> bool {
+    let Some(provided) = provided else {
+        return false;
+    };
+    let (expected, provided) = (expected.as_bytes(), provided.as_bytes());
+    expected.len() == provided.len()
+        && expected
+            .iter()
+            .zip(provided)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_payload() {
+        let body = r#"{
+            "event": "commentAdded",
+            "issue": {"key": "TREK-12", "summary": "Экспорт", "queue": "TREK"},
+            "author": "ann",
+            "comment": "Посмотрите"
+        }"#;
+
+        let payload = WebhookPayload::parse(body.as_bytes()).unwrap();
+        assert_eq!(payload.event, WebhookEvent::CommentAdded);
+        assert_eq!(payload.issue.summary.as_deref(), Some("Экспорт"));
+        assert_eq!(payload.comment.as_deref(), Some("Посмотрите"));
+        assert_eq!(payload.queue(), "TREK");
+
+        let without_queue = br#"{"event": "issueCreated", "issue": {"key": "ops-3"}}"#;
+        let payload = WebhookPayload::parse(without_queue).unwrap();
+        assert_eq!(payload.issue.key, "OPS-3");
+        assert_eq!(payload.queue(), "OPS");
+    }
+
+    #[test]
+    fn test_parse_rejects_unfilled_template_and_keeps_unknown_events() {
+        let unfilled = br#"{"event": "issueCreated", "issue": {"key": "{{issue.key}}"}}"#;
+        assert!(matches!(
+            WebhookPayload::parse(unfilled),
+            Err(TrackerError::InvalidWebhook(_))
+        ));
+
+        let unknown = br#"{"event": "issueMoved", "issue": {"key": "TREK-1"}}"#;
+        assert_eq!(
+            WebhookPayload::parse(unknown).unwrap().event,
+            WebhookEvent::Other
+        );
+        assert!(WebhookPayload::parse(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_verify_secret() {
+        assert!(verify_secret("s3cret", Some("s3cret")));
+        assert!(!verify_secret("s3cret", Some("s3creT")));
+        assert!(!verify_secret("s3cret", Some("s3cret!")));
+        assert!(!verify_secret("s3cret", None));
+    }
+}
@@ -0,0 +1,329 @@
+//! Модуль для работы с учётом времени в Яндекс.Трекере
+//!
+//! Содержит методы для поиска, добавления, изменения и удаления записей
+//! о затраченном времени.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::ids::{IssueKey, UserLogin};
+use crate::models::Worklog;
+use crate::{Result, TrackerClient};
+
+/// Тело запроса для добавления записи о затраченном времени
+#[derive(Debug, Clone, Serialize)]
+pub struct AddWorklogRequest {
+    /// Дата и время начала работы в формате `YYYY-MM-DDThh:mm:ss.sss±hhmm`
+    pub start: String,
+
+    /// Затраченное время в формате ISO 8601
+    pub duration: String,
+
+    /// Комментарий к записи
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl AddWorklogRequest {
+    /// Создать запрос; длительность округляется до минут
+    pub fn new(start: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            start: start.into(),
+            duration: format_duration(duration),
+            comment: None,
+        }
+    }
+
+    /// Добавить комментарий к записи
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+/// Тело запроса для изменения записи о затраченном времени
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateWorklogRequest {
+    /// Новое затраченное время в формате ISO 8601
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+
+    /// Новый комментарий к записи
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl UpdateWorklogRequest {
+    /// Изменить только длительность записи
+    pub fn duration(duration: Duration) -> Self {
+        Self {
+            duration: Some(format_duration(duration)),
+            comment: None,
+        }
+    }
+}
+
+/// Диапазон дат для поиска записей
+#[derive(Debug, Clone, Serialize)]
+struct DateRange<'a> {
+    from: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<&'a str>,
+}
+
+/// Тело запроса поиска записей о затраченном времени
+#[derive(Debug, Clone, Serialize)]
+struct WorklogSearchRequest<'a> {
+    #[serde(rename = "createdBy")]
+    created_by: &'a str,
+    #[serde(rename = "createdAt")]
+    created_at: DateRange<'a>,
+}
+
+/// Сколько записей запрашивать за страницу поиска
+const WORKLOGS_PER_PAGE: u32 = 100;
+
+/// Часов в рабочем дне Трекера, используется для длительностей вида `P1D`
+const HOURS_PER_DAY: u64 = 8;
+
+/// Рабочих дней в неделе Трекера, используется для длительностей вида `P1W`
+const DAYS_PER_WEEK: u64 = 5;
+
+/// Разбирает длительность ISO 8601 из ответа Трекера (например, `P1DT2H30M`)
+///
+/// Дни и недели считаются рабочими, как в интерфейсе Трекера: день — 8 часов,
+/// неделя — 5 дней. Возвращает None для некорректной строки.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let rest = value.strip_prefix('P')?;
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in rest.chars() {
+        match c {
+            'T' if number.is_empty() => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            unit => {
+                let amount: f64 = number.parse().ok()?;
+                number.clear();
+                let unit_seconds = match (in_time, unit) {
+                    (false, 'W') => DAYS_PER_WEEK * HOURS_PER_DAY * 3600,
+                    (false, 'D') => HOURS_PER_DAY * 3600,
+                    (true, 'H') => 3600,
+                    (true, 'M') => 60,
+                    (true, 'S') => 1,
+                    _ => return None,
+                };
+                seconds += amount * unit_seconds as f64;
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Форматирует длительность в ISO 8601 с точностью до минут (например, `PT1H5M`)
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = (duration.as_secs() + 30) / 60;
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    match (hours, minutes) {
+        (0, minutes) => format!("PT{}M", minutes),
+        (hours, 0) => format!("PT{}H", hours),
+        (hours, minutes) => format!("PT{}H{}M", hours, minutes),
+    }
+}
+
+impl TrackerClient {
+    /// Получить записи о затраченном времени по задаче
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn get_worklogs(&self, issue_key: &IssueKey) -> Result<Vec<Worklog>> {
+        tracing::debug!("Получение учёта времени задачи: {}", issue_key);
+
+        let resource_path = format!("issues/{}/worklog", issue_key);
+        let (json_value, _) = self.get(&resource_path, None).await?;
+
+        let worklogs: Vec<Worklog> = serde_json::from_value(json_value)?;
+
+        tracing::info!(
+            worklogs_count = worklogs.len(),
+            "Учёт времени задачи получен успешно"
+        );
+
+        Ok(worklogs)
+    }
+
+    /// Добавить запись о затраченном времени
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `request` - Начало, длительность и комментарий
+    ///
+    /// # Примеры
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use tracker_lib::{TrackerClient, worklog::AddWorklogRequest};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = TrackerClient::with_token("your-oauth-token")?;
+    /// let request = AddWorklogRequest::new("2024-01-15T10:00:00.000+0300", Duration::from_secs(1500));
+    /// let worklog = client.add_worklog(&"TREK-123".parse()?, &request).await?;
+    /// println!("Запись: {:?}", worklog.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn add_worklog(
+        &self,
+        issue_key: &IssueKey,
+        request: &AddWorklogRequest,
+    ) -> Result<Worklog> {
+        tracing::debug!("Добавление учёта времени к задаче: {}", issue_key);
+
+        let resource_path = format!("issues/{}/worklog", issue_key);
+        let (json_value, _) = self.post(&resource_path, request, None).await?;
+
+        let worklog: Worklog = serde_json::from_value(json_value)?;
+
+        tracing::info!(worklog_id = ?worklog.id, "Учёт времени добавлен успешно");
+
+        Ok(worklog)
+    }
+
+    /// Изменить запись о затраченном времени
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `worklog_id` - Идентификатор записи
+    /// * `request` - Новые длительность и комментарий
+    #[tracing::instrument(skip(self, request), fields(issue_key = %issue_key))]
+    pub async fn update_worklog(
+        &self,
+        issue_key: &IssueKey,
+        worklog_id: u64,
+        request: &UpdateWorklogRequest,
+    ) -> Result<Worklog> {
+        tracing::debug!(
+            "Изменение учёта времени {} задачи {}",
+            worklog_id,
+            issue_key
+        );
+
+        let resource_path = format!("issues/{}/worklog/{}", issue_key, worklog_id);
+        let (json_value, _) = self.patch(&resource_path, request, None).await?;
+
+        let worklog: Worklog = serde_json::from_value(json_value)?;
+
+        tracing::info!("Учёт времени изменён успешно");
+
+        Ok(worklog)
+    }
+
+    /// Удалить запись о затраченном времени
+    ///
+    /// # Параметры
+    ///
+    /// * `issue_key` - Ключ задачи
+    /// * `worklog_id` - Идентификатор записи
+    #[tracing::instrument(skip(self), fields(issue_key = %issue_key))]
+    pub async fn delete_worklog(&self, issue_key: &IssueKey, worklog_id: u64) -> Result<()> {
+        tracing::debug!("Удаление учёта времени {} задачи {}", worklog_id, issue_key);
+
+        let resource_path = format!("issues/{}/worklog/{}", issue_key, worklog_id);
+        self.delete(&resource_path, None).await?;
+
+        tracing::info!("Учёт времени удалён успешно");
+
+        Ok(())
+    }
+
+    /// Найти записи о затраченном времени пользователя
+    ///
+    /// Проходит все страницы выдачи по заголовку `X-Total-Pages`.
+    ///
+    /// # Параметры
+    ///
+    /// * `login` - Логин автора записей
+    /// * `from` - Начало периода создания записей
+    /// * `to` - Конец периода; None — по текущий момент
+    #[tracing::instrument(skip(self))]
+    pub async fn search_worklogs(
+        &self,
+        login: &UserLogin,
+        from: &str,
+        to: Option<&str>,
+    ) -> Result<Vec<Worklog>> {
+        tracing::debug!("Поиск учёта времени пользователя: {}", login);
+
+        let request = WorklogSearchRequest {
+            created_by: login.as_str(),
+            created_at: DateRange { from, to },
+        };
+
+        let mut worklogs: Vec<Worklog> = Vec::new();
+        let mut page = 1;
+        loop {
+            let mut query_params = HashMap::new();
+            query_params.insert("perPage".to_string(), WORKLOGS_PER_PAGE.to_string());
+            query_params.insert("page".to_string(), page.to_string());
+
+            let (json_value, pagination) = self
+                .post("worklog/_search", &request, Some(&query_params))
+                .await?;
+            let page_worklogs: Vec<Worklog> = serde_json::from_value(json_value)?;
+            worklogs.extend(page_worklogs);
+
+            let total_pages = pagination.and_then(|meta| meta.total_pages).unwrap_or(1);
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        tracing::info!(
+            worklogs_count = worklogs.len(),
+            "Учёт времени найден успешно"
+        );
+
+        Ok(worklogs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(25 * 60)), "PT25M");
+        assert_eq!(format_duration(Duration::from_secs(2 * 3600)), "PT2H");
+        assert_eq!(
+            format_duration(Duration::from_secs(3600 + 5 * 60 + 40)),
+            "PT1H6M"
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT25M"), Some(Duration::from_secs(25 * 60)));
+        assert_eq!(
+            parse_duration("P1DT1H30M"),
+            Some(Duration::from_secs(9 * 3600 + 30 * 60))
+        );
+        assert_eq!(parse_duration("PT0.5H"), Some(Duration::from_secs(1800)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::from_secs(40 * 3600)));
+        assert_eq!(parse_duration("1H"), None);
+        assert_eq!(parse_duration("PT5"), None);
+    }
+}
@@ -0,0 +1,91 @@
+//! Интеграционные тесты для модуля activity
+//!
+//! Тестируют сборку ленты активности очереди из поиска и истории изменений
+
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_queue_activity_takes_latest_change() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(query_param("perPage", "50"))
+        .and(body_json(serde_json::json!({
+            "query": "Queue: TREK Updated: >= \"2026-10-15\" \"Sort by\": Updated DESC"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "key": "TREK-2",
+                "summary": "Падает экспорт",
+                "updatedAt": "2026-10-16T09:30:00.000+0000"
+            },
+            {
+                "key": "TREK-1",
+                "summary": "Новая задача",
+                "updatedAt": "2026-10-15T18:00:00.000+0000"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-2/changelog"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "c1",
+                "updatedAt": "2026-10-14T10:00:00.000+0000",
+                "type": "IssueCreated"
+            },
+            {
+                "id": "c2",
+                "updatedAt": "2026-10-16T09:30:00.000+0000",
+                "updatedBy": {"id": "42", "display": "Иван Петров"},
+                "type": "IssueWorkflow",
+                "fields": [{
+                    "field": {"id": "status", "display": "Статус"},
+                    "from": {"key": "open", "display": "Открыт"},
+                    "to": {"key": "inProgress", "display": "В работе"}
+                }]
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/changelog"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let activity = client
+        .get_queue_activity(&"TREK".parse().unwrap(), "2026-10-15")
+        .await
+        .unwrap();
+
+    assert_eq!(activity.len(), 2);
+    let latest = activity[0].latest_change.as_ref().unwrap();
+    assert_eq!(latest.id.as_deref(), Some("c2"));
+    assert_eq!(latest.fields[0].field.id.as_deref(), Some("status"));
+    assert_eq!(
+        activity[0].updated_at(),
+        Some("2026-10-16T09:30:00.000+0000")
+    );
+
+    // Без истории временем активности служит обновление задачи
+    assert!(activity[1].latest_change.is_none());
+    assert_eq!(
+        activity[1].updated_at(),
+        Some("2026-10-15T18:00:00.000+0000")
+    );
+}
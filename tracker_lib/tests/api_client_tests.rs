@@ -220,6 +220,42 @@ async fn test_org_id_header_is_set() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_cloud_org_id_header_is_set() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/org"))
+        .and(header("X-Cloud-Org-ID", "bpf3crucp1v2pgmu4vc2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client_with_org(&mock_server, "bpf3crucp1v2pgmu4vc2").await;
+    let result = client.get("test/org", None).await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_user_agent_header_is_set() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/myself"))
+        .and(header("User-Agent", "release-bot/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_user_agent("release-bot/1.0");
+    let client = TrackerClient::new(config).unwrap();
+
+    assert!(client.get("myself", None).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_language_header_is_set() {
     let mock_server = MockServer::start().await;
@@ -389,3 +425,256 @@ async fn test_pagination_with_partial_headers() {
     assert_eq!(meta.total_count, Some(100));
     assert_eq!(meta.total_pages, None);
 }
+
+#[tokio::test]
+async fn test_events_emitted_for_request() {
+    use tracker_lib::events::{RateLimit, TrackerEvent};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/events"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({}))
+                .insert_header("X-RateLimit-Limit", "100")
+                .insert_header("X-RateLimit-Remaining", "99"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let client = create_test_client(&mock_server)
+        .await
+        .with_event_sender(sender);
+    client.get("test/events", None).await.unwrap();
+
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        TrackerEvent::RequestStarted {
+            path: "test/events".to_string()
+        }
+    );
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        TrackerEvent::RateLimitUpdated(RateLimit {
+            limit: Some(100),
+            remaining: Some(99),
+            retry_after_secs: None,
+        })
+    );
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        TrackerEvent::RequestFinished {
+            path: "test/events".to_string(),
+            success: true
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_read_only_rejects_writes_without_sending() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_read_only(true);
+    let client = TrackerClient::new(config).unwrap();
+
+    let found = client
+        .post(
+            "issues/_search",
+            &serde_json::json!({"query": "Queue: TREK"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(found.0, serde_json::json!([]));
+
+    let err = client
+        .patch("issues/TREK-1", &serde_json::json!({"summary": "x"}), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TrackerError::ReadOnly { ref path, .. } if path == "issues/TREK-1"));
+    assert!(err.to_string().contains("PATCH issues/TREK-1"));
+}
+
+#[tokio::test]
+async fn test_get_each_streams_array_items() {
+    let mock_server = MockServer::start().await;
+
+    let items: Vec<serde_json::Value> = (1..=2000)
+        .map(|i| serde_json::json!({"id": i, "text": format!("Комментарий, [{i}] \"{{}}\"")}))
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/comments"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(&items)
+                .insert_header("X-Total-Count", "2000"),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-2/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[{\"id\": 1}, {\"id\""))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+
+    let mut ids = Vec::new();
+    let meta = client
+        .get_each("issues/TREK-1/comments", None, |item: serde_json::Value| {
+            ids.push(item["id"].as_u64().unwrap());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(ids, (1..=2000).collect::<Vec<u64>>());
+    assert_eq!(meta.unwrap().total_count, Some(2000));
+
+    // Оборванный ответ — ошибка, даже если часть элементов уже разобрана
+    let mut seen = 0;
+    let err = client
+        .get_each("issues/TREK-2/comments", None, |_: serde_json::Value| {
+            seen += 1;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TrackerError::JsonParseFailed(_)));
+    assert_eq!(seen, 1);
+}
+
+#[tokio::test]
+async fn test_token_refreshed_after_unauthorized_for_all_clones() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/auth"))
+        .and(header("Authorization", "OAuth test-oauth-token"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/test/auth"))
+        .and(header("Authorization", "OAuth fresh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+    let client = TrackerClient::builder(config)
+        .token_refresher(|| Ok::<_, TrackerError>("fresh-token".to_string()))
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    let (result, _) = client.get("test/auth", None).await.unwrap();
+    assert_eq!(result["ok"], true);
+    clone.get("test/auth", None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unauthorized_without_refresher_is_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/auth"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let result = client.get("test/auth", None).await;
+    assert!(matches!(result, Err(TrackerError::Unauthorized)));
+}
+
+#[tokio::test]
+async fn test_response_cache_shared_between_clones() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/cached"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/test/cached"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"name": "cached"}))
+                .insert_header("ETag", "\"v1\"")
+                .insert_header("X-Total-Count", "1"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+    let client = TrackerClient::builder(config)
+        .response_cache(true)
+        .build()
+        .unwrap();
+
+    let (first, _) = client.get("test/cached", None).await.unwrap();
+    let (second, meta) = client.clone().get("test/cached", None).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(second["name"], "cached");
+    assert_eq!(meta.unwrap().total_count, Some(1));
+}
+
+#[tokio::test]
+async fn test_rate_limit_pause_shared_between_clones() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/test/limited"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "1")
+                .insert_header("X-RateLimit-Remaining", "0"),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/test/limited"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    assert!(client.get("test/limited", None).await.is_err());
+
+    let clone = client.clone();
+    assert_eq!(clone.rate_limit().unwrap().retry_after_secs, Some(1));
+
+    let started = std::time::Instant::now();
+    clone.get("test/limited", None).await.unwrap();
+    assert!(started.elapsed() >= std::time::Duration::from_millis(900));
+}
@@ -0,0 +1,236 @@
+//! Интеграционные тесты для модуля attachments
+//!
+//! Тестируют получение списка вложений, загрузку с прогрессом и повторами после
+//! временных ошибок и скачивание файла
+
+use std::sync::{Arc, Mutex};
+
+use tracker_lib::attachments::{UploadProgress, UPLOAD_PROGRESS_STEP};
+use tracker_lib::{TrackerClient, TrackerConfig, TrackerError};
+use wiremock::matchers::{body_string_contains, header_regex, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+async fn mount_attachments(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "self": "https://api.tracker.yandex.net/v3/issues/TREK-1/attachments/4159",
+                "id": "4159",
+                "name": "скриншот ошибки.png",
+                "content": "https://api.tracker.yandex.net/v3/issues/TREK-1/attachments/4159/скриншот ошибки.png",
+                "mimetype": "image/png",
+                "size": 4,
+                "createdAt": "2026-10-16T09:30:00.000+0000",
+                "createdBy": {"id": "1134669289", "display": "Иван Иванов"}
+            }
+        ])))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_attachments() {
+    let mock_server = MockServer::start().await;
+    mount_attachments(&mock_server).await;
+
+    let client = create_test_client(&mock_server).await;
+    let attachments = client
+        .get_attachments(&"TREK-1".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].id, "4159");
+    assert_eq!(attachments[0].mimetype.as_deref(), Some("image/png"));
+    assert_eq!(attachments[0].size, Some(4));
+}
+
+#[tokio::test]
+async fn test_download_attachment() {
+    let mock_server = MockServer::start().await;
+    mount_attachments(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/v3/issues/TREK-1/attachments/4159/%D1%81%D0%BA%D1%80%D0%B8%D0%BD%D1%88%D0%BE%D1%82%20%D0%BE%D1%88%D0%B8%D0%B1%D0%BA%D0%B8.png",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "image/png")
+                .set_body_bytes(vec![0x89, b'P', b'N', b'G']),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let (attachment, data) = client
+        .download_attachment(&"TREK-1".parse().unwrap(), "4159")
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.name, "скриншот ошибки.png");
+    assert_eq!(data, vec![0x89, b'P', b'N', b'G']);
+}
+
+#[tokio::test]
+async fn test_download_missing_attachment() {
+    let mock_server = MockServer::start().await;
+    mount_attachments(&mock_server).await;
+
+    let client = create_test_client(&mock_server).await;
+    let err = client
+        .download_attachment(&"TREK-1".parse().unwrap(), "1")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TrackerError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn test_upload_attachment_sends_multipart_file() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .and(query_param("filename", "log.txt"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=",
+        ))
+        .and(body_string_contains(
+            "name=\"file_data\"; filename=\"log.txt\"",
+        ))
+        .and(body_string_contains("panic at line 42"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "4160",
+            "name": "log.txt",
+            "mimetype": "text/plain",
+            "size": 17
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let attachment = client
+        .upload_attachment(
+            &"TREK-1".parse().unwrap(),
+            "log.txt",
+            b"panic at line 42\n".to_vec(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.id, "4160");
+    assert_eq!(attachment.size, Some(17));
+}
+
+#[tokio::test]
+async fn test_upload_attachment_reports_progress() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "4161",
+            "name": "dump.bin"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let data = vec![7u8; UPLOAD_PROGRESS_STEP * 2 + 10];
+    let total = data.len() as u64;
+    let reports: Arc<Mutex<Vec<UploadProgress>>> = Arc::default();
+    let sink = Arc::clone(&reports);
+    let attachment = client
+        .upload_attachment_with_progress(&"TREK-1".parse().unwrap(), "dump.bin", data, move |p| {
+            sink.lock().unwrap().push(p)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.id, "4161");
+    let reports = reports.lock().unwrap();
+    let sent: Vec<u64> = reports.iter().map(|p| p.sent).collect();
+    assert_eq!(
+        sent,
+        [
+            UPLOAD_PROGRESS_STEP as u64,
+            UPLOAD_PROGRESS_STEP as u64 * 2,
+            total
+        ]
+    );
+    assert!(reports.iter().all(|p| p.total == total && p.attempt == 1));
+}
+
+#[tokio::test]
+async fn test_upload_attachment_retries_after_retry_after() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "4162",
+            "name": "dump.bin"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let attempts: Arc<Mutex<Vec<u32>>> = Arc::default();
+    let sink = Arc::clone(&attempts);
+    let started = std::time::Instant::now();
+    let attachment = client
+        .upload_attachment_with_progress(
+            &"TREK-1".parse().unwrap(),
+            "dump.bin",
+            vec![7u8; 10],
+            move |p| sink.lock().unwrap().push(p.attempt),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(attachment.id, "4162");
+    assert_eq!(*attempts.lock().unwrap(), [1, 2]);
+    assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_upload_attachment_is_not_retried_after_gateway_timeout() {
+    let mock_server = MockServer::start().await;
+
+    // Шлюз мог передать файл Трекеру: повтор создал бы дубль
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/attachments"))
+        .respond_with(ResponseTemplate::new(504))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let result = client
+        .upload_attachment(&"TREK-1".parse().unwrap(), "dump.bin", vec![7u8; 10])
+        .await;
+
+    assert!(matches!(result, Err(TrackerError::ApiError { .. })));
+}
@@ -0,0 +1,75 @@
+//! Интеграционные тесты для модуля bulkchange
+//!
+//! Тестируют запуск массового изменения и ожидание его завершения
+
+use std::time::Duration;
+
+use tracker_lib::{bulkchange::BulkUpdateRequest, TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_bulk_update_sends_issues_and_values() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/bulkchange/_update"))
+        .and(body_json(serde_json::json!({
+            "issues": ["TREK-1", "TREK-2"],
+            "values": {"tags": {"add": ["needs-review"], "remove": ["triage"]}}
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "593cd211ef7e8a3c1d2b1e33",
+            "status": "CREATED",
+            "totalIssues": 2
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = BulkUpdateRequest::new(
+        vec!["TREK-1".to_string(), "TREK-2".to_string()],
+        serde_json::json!({"tags": {"add": ["needs-review"], "remove": ["triage"]}}),
+    );
+    let change = client.bulk_update(&request).await.unwrap();
+
+    assert_eq!(change.id, "593cd211ef7e8a3c1d2b1e33");
+    assert_eq!(change.total_issues, Some(2));
+    assert!(!change.is_finished());
+}
+
+#[tokio::test]
+async fn test_wait_bulk_change_returns_finished_status() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/bulkchange/593cd211ef7e8a3c1d2b1e33"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "593cd211ef7e8a3c1d2b1e33",
+            "status": "COMPLETE",
+            "executionIssuePercent": 100,
+            "totalIssues": 2,
+            "totalCompletedIssues": 2
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let change = client
+        .wait_bulk_change("593cd211ef7e8a3c1d2b1e33", Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert!(change.is_complete());
+    assert_eq!(change.total_completed_issues, Some(2));
+}
@@ -0,0 +1,87 @@
+//! Интеграционные тесты для модуля checklists
+//!
+//! Тестируют получение чеклиста и разбор задачи, которую Трекер возвращает после изменения пункта
+
+use tracker_lib::{checklists::ChecklistItemRequest, TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_checklist() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/checklistItems"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "5fde5f0a1aee261d", "text": "TREK-2: Ревью", "checked": true},
+            {"id": "5fde5f0a1aee261e", "text": "Позвонить в банк", "checklistItemType": "standard"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let items = client
+        .get_checklist(&"TREK-1".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert!(items[0].checked);
+    assert!(!items[1].checked);
+    assert_eq!(items[1].item_type.as_deref(), Some("standard"));
+}
+
+#[tokio::test]
+async fn test_add_and_check_item_return_updated_checklist() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/checklistItems"))
+        .and(body_json(
+            serde_json::json!({"text": "TREK-3: Релиз", "checked": false}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Сегодня",
+            "checklistItems": [{"id": "1", "text": "TREK-3: Релиз", "checked": false}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-1/checklistItems/1"))
+        .and(body_json(serde_json::json!({"checked": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Сегодня",
+            "checklistItems": [{"id": "1", "text": "TREK-3: Релиз", "checked": true}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let issue = "TREK-1".parse().unwrap();
+
+    let added = client
+        .add_checklist_item(&issue, &ChecklistItemRequest::new("TREK-3: Релиз", false))
+        .await
+        .unwrap();
+    assert_eq!(added[0].id, "1");
+
+    let checked = client
+        .update_checklist_item(&issue, "1", &ChecklistItemRequest::checked(true))
+        .await
+        .unwrap();
+    assert!(checked[0].checked);
+}
@@ -0,0 +1,140 @@
+//! Интеграционные тесты для модуля comments
+//!
+//! Тестируют получение и добавление комментариев к задаче, в том числе массовое
+
+use tracker_lib::comments::{AddCommentRequest, BulkComment};
+use tracker_lib::ids::IssueKey;
+use tracker_lib::{TrackerClient, TrackerConfig, TrackerError};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+/// Ключ задачи из строки теста
+fn key(value: &str) -> IssueKey {
+    value.parse().unwrap()
+}
+
+#[tokio::test]
+async fn test_get_comments_success() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": 1,
+                "text": "Первый комментарий",
+                "createdBy": {"id": "user1", "display": "User 1", "login": "user1"},
+                "createdAt": "2024-01-15T10:00:00.000+0000"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let comments = client.get_comments(&key("TREK-1")).await.unwrap();
+
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].text, "Первый комментарий");
+    assert_eq!(
+        comments[0]
+            .created_by
+            .as_ref()
+            .and_then(|u| u.login.as_deref()),
+        Some("user1")
+    );
+}
+
+#[tokio::test]
+async fn test_add_comment_success() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/comments"))
+        .and(body_json(serde_json::json!({"text": "Взял в работу"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 42,
+            "text": "Взял в работу"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let comment = client
+        .add_comment(&key("TREK-1"), &AddCommentRequest::new("Взял в работу"))
+        .await
+        .unwrap();
+
+    assert_eq!(comment.id, Some(42));
+    assert_eq!(comment.text, "Взял в работу");
+}
+
+#[tokio::test]
+async fn test_add_comments_bulk_retries_and_reports_failures() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 1,
+            "text": "Исправлено в 2.4"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-2/comments"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-2/comments"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 2,
+            "text": "Исправлено в 2.4"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-404/comments"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    // Шлюз мог передать комментарий Трекеру: 504 не повторяется
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-504/comments"))
+        .respond_with(ResponseTemplate::new(504))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let items: Vec<BulkComment> = ["TREK-1", "TREK-2", "TREK-404", "TREK-504"]
+        .into_iter()
+        .map(|value| BulkComment::new(key(value), "Исправлено в 2.4"))
+        .collect();
+    let report = client.add_comments_bulk(&items).await;
+
+    assert!(!report.is_success());
+    let posted: Vec<&str> = report.posted.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(posted, ["TREK-1", "TREK-2"]);
+    assert_eq!(report.failed.len(), 2);
+    assert_eq!(report.failed[0].0, "TREK-404");
+    assert!(matches!(report.failed[0].1, TrackerError::NotFound { .. }));
+    assert_eq!(report.failed[1].0, "TREK-504");
+    assert!(report
+        .to_string()
+        .starts_with("Добавлено комментариев: 2 из 4"));
+}
@@ -0,0 +1,69 @@
+//! Интеграционные тесты для модуля filters
+//!
+//! Тестируют создание и изменение сохранённых фильтров
+
+use tracker_lib::filters::FilterRequest;
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_create_filter() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/filters/"))
+        .and(body_json(serde_json::json!({
+            "name": "mybugs",
+            "query": "Queue: TREK Type: Bug Assignee: me()"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "self": "https://api.tracker.yandex.net/v3/filters/42",
+            "id": 42,
+            "name": "mybugs",
+            "query": "Queue: TREK Type: Bug Assignee: me()"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = FilterRequest::new("mybugs", "Queue: TREK Type: Bug Assignee: me()");
+    let filter = client.create_filter(&request).await.unwrap();
+
+    assert_eq!(filter.id, 42);
+    assert_eq!(filter.name.as_deref(), Some("mybugs"));
+}
+
+#[tokio::test]
+async fn test_update_filter() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/filters/42"))
+        .and(body_json(serde_json::json!({
+            "name": "mybugs",
+            "query": "Queue: TREK Type: Bug"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 42,
+            "name": "mybugs",
+            "query": "Queue: TREK Type: Bug"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = FilterRequest::new("mybugs", "Queue: TREK Type: Bug");
+    let filter = client.update_filter(42, &request).await.unwrap();
+
+    assert_eq!(filter.query.as_deref(), Some("Queue: TREK Type: Bug"));
+}
@@ -0,0 +1,87 @@
+//! Интеграционные тесты записи и воспроизведения ответов
+//!
+//! Запускаются с фичей: `cargo test -p tracker_lib --features fixtures`
+
+#![cfg(feature = "fixtures")]
+
+use std::path::PathBuf;
+
+use tracker_lib::fixtures::FixtureMode;
+use tracker_lib::ids::IssueKey;
+use tracker_lib::{TrackerClient, TrackerConfig, TrackerError};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Ключ задачи из строки теста
+fn key(value: &str) -> IssueKey {
+    value.parse().unwrap()
+}
+
+fn fixtures_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tracker-fixtures-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn test_record_then_replay_without_network() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"key": "TREK-1", "summary": "Записанная задача"}),
+            ),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-404"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Issue not found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let dir = fixtures_dir("roundtrip");
+    let recorder =
+        TrackerClient::new(TrackerConfig::new("secret-token").with_base_url(mock_server.uri()))
+            .unwrap()
+            .with_fixtures(FixtureMode::Record(dir.clone()));
+    recorder.get_issue(&key("TREK-1"), None).await.unwrap();
+    assert!(recorder.get_issue(&key("TREK-404"), None).await.is_err());
+
+    let recorded: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(recorded.len(), 2);
+    for entry in recorded {
+        let content = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+        assert!(!content.contains("secret-token"));
+    }
+
+    // Адрес, на котором никто не слушает: ответы должны прийти из файлов
+    let player =
+        TrackerClient::new(TrackerConfig::new("replay").with_base_url("http://127.0.0.1:9"))
+            .unwrap()
+            .with_fixtures(FixtureMode::Replay(dir.clone()));
+    let issue = player.get_issue(&key("TREK-1"), None).await.unwrap();
+    assert_eq!(issue.summary, "Записанная задача");
+    assert!(matches!(
+        player.get_issue(&key("TREK-404"), None).await,
+        Err(TrackerError::NotFound { resource }) if resource == "Issue not found"
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_replay_reports_missing_fixture() {
+    let dir = fixtures_dir("missing");
+    let player = TrackerClient::new(TrackerConfig::new("replay"))
+        .unwrap()
+        .with_fixtures(FixtureMode::Replay(dir));
+
+    let err = player.get_issue(&key("TREK-2"), None).await.unwrap_err();
+    assert!(
+        matches!(err, TrackerError::ConfigError(message) if message.contains("/v3/issues/TREK-2"))
+    );
+}
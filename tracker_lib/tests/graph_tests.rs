@@ -0,0 +1,112 @@
+//! Интеграционные тесты для модулей links и graph
+//!
+//! Тестируют получение связей и построение графа зависимостей
+
+use tracker_lib::graph::EdgeKind;
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+async fn mount_issue(mock_server: &MockServer, issue: serde_json::Value, links: serde_json::Value) {
+    let key = issue["key"].as_str().unwrap().to_string();
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/issues/{key}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&issue))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v3/issues/{key}/links")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&links))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_links_success() {
+    let mock_server = MockServer::start().await;
+
+    mount_issue(
+        &mock_server,
+        serde_json::json!({"key": "TREK-1", "summary": "Релиз"}),
+        serde_json::json!([{
+            "id": 11,
+            "type": {"id": "depends", "inward": "Блокирует", "outward": "Зависит от"},
+            "direction": "outward",
+            "object": {"key": "TREK-2", "display": "Миграция"},
+            "status": {"key": "open", "display": "Открыт"}
+        }]),
+    )
+    .await;
+
+    let client = create_test_client(&mock_server).await;
+    let links = client.get_links(&"TREK-1".parse().unwrap()).await.unwrap();
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].object.key.as_deref(), Some("TREK-2"));
+    assert_eq!(
+        links[0].link_type.as_ref().and_then(|t| t.id.as_deref()),
+        Some("depends")
+    );
+}
+
+#[tokio::test]
+async fn test_build_dependency_graph_respects_depth() {
+    let mock_server = MockServer::start().await;
+
+    mount_issue(
+        &mock_server,
+        serde_json::json!({
+            "key": "TREK-1",
+            "summary": "Релиз",
+            "status": {"key": "open", "display": "Открыт"},
+            "parent": {"key": "TREK-10", "display": "Эпик"}
+        }),
+        serde_json::json!([{
+            "type": {"id": "depends"},
+            "direction": "outward",
+            "object": {"key": "TREK-2", "display": "Миграция"}
+        }]),
+    )
+    .await;
+    mount_issue(
+        &mock_server,
+        serde_json::json!({"key": "TREK-2", "summary": "Миграция"}),
+        serde_json::json!([
+            {"type": {"id": "depends"}, "direction": "inward", "object": {"key": "TREK-1"}},
+            {"type": {"id": "depends"}, "direction": "outward", "object": {"key": "TREK-3", "display": "Схема"}}
+        ]),
+    )
+    .await;
+    mount_issue(
+        &mock_server,
+        serde_json::json!({"key": "TREK-10", "summary": "Эпик"}),
+        serde_json::json!([]),
+    )
+    .await;
+
+    let client = create_test_client(&mock_server).await;
+    let graph = client.build_dependency_graph("TREK-1", 1).await.unwrap();
+
+    // TREK-3 виден по связи TREK-2, но его связи уже не запрашиваются
+    assert_eq!(
+        graph.nodes.keys().collect::<Vec<_>>(),
+        ["TREK-1", "TREK-10", "TREK-2", "TREK-3"]
+    );
+    assert_eq!(graph.nodes["TREK-3"].depth, 2);
+    assert_eq!(graph.edges.len(), 3);
+    assert_eq!(graph.blockers("TREK-1"), ["TREK-2"]);
+    assert_eq!(graph.blockers("TREK-2"), ["TREK-3"]);
+    assert!(graph
+        .edges
+        .iter()
+        .any(|edge| edge.kind == EdgeKind::Parent && edge.from == "TREK-10"));
+}
@@ -0,0 +1,243 @@
+//! Интеграционные тесты для модуля operations
+//!
+//! Проверяют выполнение сценария по шагам и откат выполненных шагов после ошибки
+
+use tracker_lib::operations::{OperationPlan, Phase, Step, StepStatus};
+use tracker_lib::task::CreateIssueRequest;
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+fn issue(key: &str) -> serde_json::Value {
+    serde_json::json!({"key": key, "summary": "Релиз 2.4"})
+}
+
+#[tokio::test]
+async fn test_run_operation_success() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(issue("TREK-5")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-5"))
+        .and(body_json(serde_json::json!({"parent": "TREK-1"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("TREK-5")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-5/checklistItems"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-5/checklistItems"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-5",
+            "checklistItems": [{"id": "a", "text": "Сборка", "checked": false}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-5"))
+        .and(body_json(serde_json::json!({"assignee": "ann"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("TREK-5")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let plan = OperationPlan::create(CreateIssueRequest::new(
+        "TREK".parse().unwrap(),
+        "Релиз 2.4",
+    ))
+    .then(Step::SetParent("TREK-1".parse().unwrap()))
+    .then(Step::AddChecklist(vec!["Сборка".to_string()]))
+    .then(Step::Assign("ann".parse().unwrap()));
+
+    let mut progress = Vec::new();
+    let report = client
+        .run_operation_with_progress(&plan, |p| progress.push((p.index, p.phase)))
+        .await;
+
+    assert!(report.is_success(), "{report}");
+    assert_eq!(report.issue_key.as_deref(), Some("TREK-5"));
+    assert_eq!(
+        progress,
+        [
+            (0, Phase::Run),
+            (1, Phase::Run),
+            (2, Phase::Run),
+            (3, Phase::Run)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_run_operation_closes_created_issue_on_failure() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(issue("TREK-5")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-5"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-5/transitions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "start", "to": {"key": "inProgress"}},
+            {"id": "cancel", "to": {"key": "cancelled"}}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-5/transitions/cancel/_execute"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let plan = OperationPlan::create(CreateIssueRequest::new(
+        "TREK".parse().unwrap(),
+        "Релиз 2.4",
+    ))
+    .then(Step::Assign("ann".parse().unwrap()))
+    .then(Step::Link {
+        relationship: "relates".to_string(),
+        target: "TREK-9".parse().unwrap(),
+    });
+
+    let report = client.run_operation(&plan).await;
+
+    assert!(!report.is_success());
+    assert!(!report.needs_attention());
+    assert_eq!(report.steps[0].status, StepStatus::RolledBack);
+    assert!(matches!(report.steps[1].status, StepStatus::Failed { .. }));
+    assert_eq!(report.steps[2].status, StepStatus::Skipped);
+}
+
+#[tokio::test]
+async fn test_run_operation_rolls_back_existing_issue() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-7",
+            "summary": "Миграция",
+            "parent": {"key": "TREK-1"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-7"))
+        .and(body_json(serde_json::json!({"parent": "TREK-2"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("TREK-7")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-7"))
+        .and(body_json(serde_json::json!({"parent": "TREK-1"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("TREK-7")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-7/links"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 42,
+            "object": {"key": "TREK-9"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v3/issues/TREK-7/links/42"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-7/checklistItems"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "old", "text": "Бэкап"}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-7/checklistItems"))
+        .and(body_json(
+            serde_json::json!({"text": "Схема", "checked": false}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-7",
+            "checklistItems": [{"id": "old", "text": "Бэкап"}, {"id": "new", "text": "Схема"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-7/checklistItems"))
+        .and(body_json(
+            serde_json::json!({"text": "Данные", "checked": false}),
+        ))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v3/issues/TREK-7/checklistItems/new"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let plan = OperationPlan::for_issue("TREK-7".parse().unwrap())
+        .then(Step::SetParent("TREK-2".parse().unwrap()))
+        .then(Step::Link {
+            relationship: "relates".to_string(),
+            target: "TREK-9".parse().unwrap(),
+        })
+        .then(Step::AddChecklist(vec![
+            "Схема".to_string(),
+            "Данные".to_string(),
+        ]))
+        .then(Step::Assign("ann".parse().unwrap()));
+
+    let mut rolled_back = Vec::new();
+    let report = client
+        .run_operation_with_progress(&plan, |p| {
+            if p.phase == Phase::Rollback {
+                rolled_back.push(p.index);
+            }
+        })
+        .await;
+
+    assert_eq!(rolled_back, [2, 1, 0]);
+    assert_eq!(report.steps[0].status, StepStatus::RolledBack);
+    assert_eq!(report.steps[1].status, StepStatus::RolledBack);
+    assert!(matches!(report.steps[2].status, StepStatus::Failed { .. }));
+    assert_eq!(report.steps[3].status, StepStatus::Skipped);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["issue_key"], "TREK-7");
+    assert_eq!(json["steps"][0]["status"], "rolled_back");
+}
@@ -0,0 +1,105 @@
+//! Интеграционные тесты для модуля queues
+//!
+//! Тестируют постраничное получение очередей, типов задач очереди и статусов
+
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_queues_reads_all_pages() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/queues"))
+        .and(query_param("page", "1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([{"key": "TREK", "name": "Трекер"}])),
+        )
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/queues"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([{"key": "OPS", "name": "Эксплуатация"}])),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let queues = client.get_queues().await.unwrap();
+
+    let keys: Vec<&str> = queues.iter().map(|queue| queue.key.as_str()).collect();
+    assert_eq!(keys, ["TREK", "OPS"]);
+}
+
+#[tokio::test]
+async fn test_get_queue_with_issue_types() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/queues/TREK"))
+        .and(query_param("expand", "issueTypesConfig"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK",
+            "name": "Трекер",
+            "defaultType": {"key": "task", "display": "Задача"},
+            "issueTypesConfig": [
+                {"issueType": {"key": "task", "display": "Задача"}},
+                {"issueType": {"key": "bug", "display": "Ошибка"}}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let queue = client.get_queue(&"TREK".parse().unwrap()).await.unwrap();
+
+    assert_eq!(queue.issue_types().count(), 2);
+    assert!(queue.allows_type("bug"));
+    assert!(!queue.allows_type("epic"));
+    assert_eq!(
+        queue.default_type.and_then(|issue_type| issue_type.key),
+        Some("task".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_get_statuses() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/statuses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "1", "key": "open", "display": "Открыт"},
+            {"id": "2", "key": "inReview", "display": "Ревью"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let statuses = client.get_statuses().await.unwrap();
+
+    let keys: Vec<Option<&str>> = statuses
+        .iter()
+        .map(|status| status.key.as_deref())
+        .collect();
+    assert_eq!(keys, [Some("open"), Some("inReview")]);
+    assert_eq!(statuses[1].display.as_deref(), Some("Ревью"));
+}
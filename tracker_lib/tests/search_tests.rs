@@ -2,9 +2,10 @@
 //!
 //! Тестируют функциональность поиска задач с различными параметрами
 
+use tracker_lib::ids::QueueKey;
 use tracker_lib::models::ExpandField;
-use tracker_lib::search::{SearchParams, SearchRequest};
-use tracker_lib::{TrackerClient, TrackerConfig};
+use tracker_lib::search::{QueryCheck, SearchParams, SearchRequest};
+use tracker_lib::{TrackerClient, TrackerConfig, TrackerError};
 use wiremock::matchers::{body_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -102,7 +103,7 @@ async fn test_search_issues_with_filter() {
         "queue": "TREK",
         "assignee": "empty()"
     }));
-    request.order = Some("+status".to_string());
+    request.order = vec!["+status".parse().unwrap()];
 
     let result = client.search_issues(&request, None).await;
 
@@ -627,3 +628,176 @@ async fn test_search_issues_with_filter_id() {
     assert_eq!(issues[0].key, "TREK-500");
     assert_eq!(issues[0].summary, "Задача из сохраненного фильтра");
 }
+
+#[tokio::test]
+async fn test_search_across_queues_merges_and_reports_failures() {
+    let mock_server = MockServer::start().await;
+
+    let queue_search = |queue: &str, issues: serde_json::Value, status: u16| {
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/_search"))
+            .and(body_json(serde_json::json!({
+                "query": format!("Queue: {queue} AND (Assignee: me())")
+            })))
+            .respond_with(ResponseTemplate::new(status).set_body_json(issues))
+    };
+    queue_search(
+        "TREK",
+        serde_json::json!([
+            {"key": "TREK-1", "summary": "Первая"},
+            {"key": "OPS-7", "summary": "Перенесённая"}
+        ]),
+        200,
+    )
+    .expect(1)
+    .mount(&mock_server)
+    .await;
+    queue_search(
+        "OPS",
+        serde_json::json!([
+            {"key": "OPS-7", "summary": "Перенесённая"},
+            {"key": "OPS-8", "summary": "Вторая"}
+        ]),
+        200,
+    )
+    .expect(1)
+    .mount(&mock_server)
+    .await;
+    queue_search(
+        "DOCS",
+        serde_json::json!({"errorMessages": ["Очередь недоступна"]}),
+        403,
+    )
+    .expect(1)
+    .mount(&mock_server)
+    .await;
+
+    let client = create_test_client(&mock_server).await;
+    let queues: Vec<QueueKey> = ["TREK", "DOCS", "OPS"]
+        .iter()
+        .map(|queue| queue.parse().unwrap())
+        .collect();
+    let request = SearchRequest {
+        query: Some("Assignee: me()".to_string()),
+        ..Default::default()
+    };
+
+    let found = client.search_across_queues(&queues, &request, 50).await;
+
+    let keys: Vec<&str> = found
+        .issues
+        .iter()
+        .map(|issue| issue.key.as_str())
+        .collect();
+    assert_eq!(keys, ["TREK-1", "OPS-7", "OPS-8"]);
+    assert_eq!(found.failures.len(), 1);
+    assert_eq!(found.failures[0].0, "DOCS");
+    assert!(matches!(found.failures[0].1, TrackerError::Forbidden));
+    assert!(found.truncated.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_across_queues_pages_up_to_per_queue_limit() {
+    let mock_server = MockServer::start().await;
+
+    let page = |queue: &str, page: u32, count: usize| {
+        let issues: Vec<serde_json::Value> = (0..count)
+            .map(|index| {
+                serde_json::json!({
+                    "key": format!("{queue}-{}", (page as usize - 1) * 50 + index + 1),
+                    "summary": "Задача"
+                })
+            })
+            .collect();
+        Mock::given(method("POST"))
+            .and(path("/v3/issues/_search"))
+            .and(query_param("perPage", "50"))
+            .and(query_param("page", page.to_string()))
+            .and(body_json(serde_json::json!({
+                "query": format!("Queue: {queue} AND (Assignee: me())")
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issues))
+            .expect(1)
+    };
+    page("TREK", 1, 50).mount(&mock_server).await;
+    page("TREK", 2, 5).mount(&mock_server).await;
+    page("OPS", 1, 50).mount(&mock_server).await;
+    page("OPS", 2, 50).mount(&mock_server).await;
+
+    let client = create_test_client(&mock_server).await;
+    let queues: Vec<QueueKey> = vec!["TREK".parse().unwrap(), "OPS".parse().unwrap()];
+    let request = SearchRequest {
+        query: Some("Assignee: me()".to_string()),
+        ..Default::default()
+    };
+
+    let found = client.search_across_queues(&queues, &request, 60).await;
+
+    assert_eq!(found.issues.len(), 55 + 60);
+    assert_eq!(found.issues[54].key, "TREK-55");
+    assert_eq!(found.issues.last().unwrap().key, "OPS-60");
+    assert!(found.failures.is_empty());
+    assert_eq!(found.truncated, ["OPS"]);
+}
+
+#[tokio::test]
+async fn test_explain_query_reports_total_and_errors() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(query_param("perPage", "1"))
+        .and(body_json(
+            serde_json::json!({"query": "Queue: TREK Resolved: week()"}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Count", "37")
+                .set_body_json(serde_json::json!([
+                    {"key": "TREK-9", "summary": "Закрыта на этой неделе"}
+                ])),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(body_json(
+            serde_json::json!({"query": "Queue: TREK Resolved: week("}),
+        ))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "errors": {},
+            "errorMessages": ["Query syntax error at position 26: expected ')'"],
+            "statusCode": 400
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+
+    match client
+        .explain_query("Queue: TREK Resolved: week()")
+        .await
+        .unwrap()
+    {
+        QueryCheck::Valid { total, sample } => {
+            assert_eq!(total, 37);
+            assert_eq!(sample.unwrap().key, "TREK-9");
+        }
+        QueryCheck::Invalid(errors) => panic!("Запрос должен быть корректным: {errors:?}"),
+    }
+
+    match client
+        .explain_query("Queue: TREK Resolved: week(")
+        .await
+        .unwrap()
+    {
+        QueryCheck::Invalid(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].position, Some(26));
+        }
+        QueryCheck::Valid { .. } => panic!("Запрос должен быть отвергнут"),
+    }
+}
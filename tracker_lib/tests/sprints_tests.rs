@@ -0,0 +1,88 @@
+//! Интеграционные тесты для модулей sprints и changelog
+//!
+//! Тестируют получение спринта, его задач и истории изменений задачи
+
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_sprint_and_issues() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/sprints/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 42,
+            "name": "Спринт 42",
+            "status": "in_progress",
+            "startDate": "2024-01-15",
+            "endDate": "2024-01-28"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(body_json(serde_json::json!({"filter": {"sprint": "42"}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"key": "TREK-1", "summary": "Первая", "storyPoints": 3.0},
+            {"key": "TREK-2", "summary": "Вторая"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let sprint = client.get_sprint(42).await.unwrap();
+    assert_eq!(sprint.start_date.as_deref(), Some("2024-01-15"));
+
+    let issues = client.get_sprint_issues(42).await.unwrap();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].story_points, Some(3.0));
+    assert_eq!(issues[1].story_points, None);
+}
+
+#[tokio::test]
+async fn test_get_changelog_filtered_by_field() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/changelog"))
+        .and(query_param("field", "resolution"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "abc",
+                "updatedAt": "2024-01-17T10:00:00.000+0000",
+                "type": "IssueWorkflow",
+                "fields": [{
+                    "field": {"id": "resolution", "display": "Резолюция"},
+                    "from": null,
+                    "to": {"key": "fixed", "display": "Решён"}
+                }]
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let changelog = client
+        .get_changelog(&"TREK-1".parse().unwrap(), Some("resolution"))
+        .await
+        .unwrap();
+
+    assert_eq!(changelog.len(), 1);
+    assert_eq!(
+        changelog[0].fields[0].field.id.as_deref(),
+        Some("resolution")
+    );
+    assert!(changelog[0].fields[0].from.is_none());
+}
@@ -0,0 +1,104 @@
+//! Интеграционные тесты для модуля subtasks
+//!
+//! Тестируют создание подзадач, получение списка по страницам и подсчёт прогресса
+
+use tracker_lib::subtasks::SubtaskProgress;
+use tracker_lib::task::CreateIssueRequest;
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_create_subtask_sets_parent() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .and(body_json(serde_json::json!({
+            "queue": "TREK",
+            "summary": "Написать миграцию",
+            "parent": "TREK-1"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "key": "TREK-2",
+            "summary": "Написать миграцию",
+            "parent": {"key": "TREK-1", "display": "Переезд на новую схему"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = CreateIssueRequest::new("TREK".parse().unwrap(), "Написать миграцию");
+    let subtask = client
+        .create_subtask(&"TREK-1".parse().unwrap(), &request)
+        .await
+        .unwrap();
+
+    assert_eq!(subtask.key, "TREK-2");
+    assert_eq!(
+        subtask.parent.and_then(|parent| parent.key).as_deref(),
+        Some("TREK-1")
+    );
+}
+
+#[tokio::test]
+async fn test_list_subtasks_reads_all_pages_and_counts_progress() {
+    let mock_server = MockServer::start().await;
+    let search_body = serde_json::json!({
+        "filter": {"parent": "TREK-1"},
+        "order": "+createdAt"
+    });
+
+    let first_page: Vec<serde_json::Value> = (2..102)
+        .map(|number| {
+            serde_json::json!({
+                "key": format!("TREK-{number}"),
+                "summary": "Подзадача",
+                "resolvedAt": "2026-10-16T09:30:00.000+0000"
+            })
+        })
+        .collect();
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(query_param("page", "1"))
+        .and(query_param("perPage", "100"))
+        .and(body_json(&search_body))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/_search"))
+        .and(query_param("page", "2"))
+        .and(body_json(&search_body))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"key": "TREK-102", "summary": "Последняя подзадача"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let parent = "TREK-1".parse().unwrap();
+
+    let subtasks = client.list_subtasks(&parent).await.unwrap();
+    assert_eq!(subtasks.len(), 101);
+    assert_eq!(subtasks[100].key, "TREK-102");
+
+    let progress = client.subtask_progress(&parent).await.unwrap();
+    assert_eq!(
+        progress,
+        SubtaskProgress {
+            done: 100,
+            total: 101
+        }
+    );
+    assert!(!progress.is_complete());
+}
@@ -2,11 +2,17 @@
 //!
 //! Используют wiremock для мокирования HTTP запросов к API Яндекс.Трекера
 
-use tracker_lib::task::GetIssueParams;
-use tracker_lib::{TrackerClient, TrackerConfig};
-use wiremock::matchers::{method, path};
+use tracker_lib::ids::IssueKey;
+use tracker_lib::task::{CreateIssueRequest, GetIssueParams, UpdateIssueRequest};
+use tracker_lib::{Conditional, TrackerClient, TrackerConfig, TrackerError};
+use wiremock::matchers::{body_json, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+/// Ключ задачи из строки теста
+fn key(value: &str) -> IssueKey {
+    value.parse().unwrap()
+}
+
 #[tokio::test]
 async fn test_get_issue_success() {
     // Создаем mock HTTP сервер
@@ -61,7 +67,7 @@ async fn test_get_issue_success() {
     let client = TrackerClient::new(config).expect("Failed to create client");
 
     // Выполняем тестируемый метод
-    let result = client.get_issue("TREK-123", None).await;
+    let result = client.get_issue(&key("TREK-123"), None).await;
 
     // Проверяем результат
     assert!(result.is_ok(), "Expected Ok, got Err: {:?}", result.err());
@@ -92,7 +98,7 @@ async fn test_get_issue_not_found() {
     let client = TrackerClient::new(config).expect("Failed to create client");
 
     // Выполняем запрос
-    let result = client.get_issue("NONEXISTENT-999", None).await;
+    let result = client.get_issue(&key("NONEXISTENT-999"), None).await;
 
     // Проверяем, что получили ошибку NotFound
     assert!(result.is_err(), "Expected Err, got Ok");
@@ -121,7 +127,7 @@ async fn test_get_issue_unauthorized() {
 
     let client = TrackerClient::new(config).expect("Failed to create client");
 
-    let result = client.get_issue("TREK-123", None).await;
+    let result = client.get_issue(&key("TREK-123"), None).await;
 
     assert!(result.is_err(), "Expected Err, got Ok");
     let error = result.unwrap_err();
@@ -180,6 +186,7 @@ async fn test_get_issue_with_expand_params() {
     // Проверяем, что expand параметры передаются в query string
     Mock::given(method("GET"))
         .and(path("/v3/issues/TREK-456"))
+        .and(query_param("expand", "attachments,comments"))
         .respond_with(ResponseTemplate::new(200).set_body_json(&response_json))
         .mount(&mock_server)
         .await;
@@ -198,10 +205,274 @@ async fn test_get_issue_with_expand_params() {
         ],
     };
 
-    let result = client.get_issue("TREK-456", Some(params)).await;
+    let result = client.get_issue(&key("TREK-456"), Some(params)).await;
 
     assert!(result.is_ok(), "Expected Ok, got Err: {:?}", result.err());
     let issue = result.unwrap();
     assert_eq!(issue.key, "TREK-456");
     assert_eq!(issue.summary, "Задача с expand параметрами");
+
+    let attachments = issue.attachments.expect("attachments were expanded");
+    assert_eq!(attachments[0].name, "test.txt");
+    // Комментарии запрошены, но сервер их не вернул
+    assert!(issue.comments.is_none());
+    assert!(issue.transitions.is_none());
+}
+
+#[tokio::test]
+async fn test_add_tags_sends_add_operation() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-123"))
+        .and(body_json(serde_json::json!({"tags": {"add": ["backend"]}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-123",
+            "summary": "Тестовая задача",
+            "tags": ["existing", "backend"]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let issue = client
+        .add_tags(&key("TREK-123"), &["backend".to_string()])
+        .await
+        .unwrap();
+
+    assert_eq!(issue.tags, vec!["existing", "backend"]);
+}
+
+#[tokio::test]
+async fn test_create_issue_sends_only_set_fields() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .and(body_json(serde_json::json!({
+            "queue": "TREK",
+            "summary": "Падает экспорт",
+            "type": "bug"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "key": "TREK-124",
+            "summary": "Падает экспорт"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let mut request = CreateIssueRequest::new("TREK".parse().unwrap(), "Падает экспорт");
+    request.issue_type = Some("bug".to_string());
+    let issue = client.create_issue(&request).await.unwrap();
+
+    assert_eq!(issue.key, "TREK-124");
+}
+
+#[tokio::test]
+async fn test_create_issue_outside_sandbox_is_not_sent() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "key": "SANDBOX-1",
+            "summary": "Проверка автоматизации"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3")
+        .with_sandbox_queue("SANDBOX".parse().unwrap());
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let request = CreateIssueRequest::new("TREK".parse().unwrap(), "Проверка автоматизации");
+    let err = client.create_issue(&request).await.unwrap_err();
+    assert!(matches!(err, TrackerError::OutsideSandbox { ref target, .. } if target == "TREK"));
+
+    let request = CreateIssueRequest::new("SANDBOX".parse().unwrap(), "Проверка автоматизации");
+    let issue = client.create_issue(&request).await.unwrap();
+    assert_eq!(issue.key, "SANDBOX-1");
+}
+
+#[tokio::test]
+async fn test_get_issue_if_changed_uses_etag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-123"))
+        .and(header("If-None-Match", "\"v2\""))
+        .respond_with(ResponseTemplate::new(304))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-123"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v2\"")
+                .set_body_json(serde_json::json!({
+                    "key": "TREK-123",
+                    "summary": "Тестовая задача"
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let first = client
+        .get_issue_if_changed(&key("TREK-123"), None)
+        .await
+        .unwrap();
+    let Conditional::Modified { value, etag } = first else {
+        panic!("Ожидалась изменённая задача");
+    };
+    assert_eq!(value.key, "TREK-123");
+    assert_eq!(etag.as_deref(), Some("\"v2\""));
+
+    let second = client
+        .get_issue_if_changed(&key("TREK-123"), etag.as_deref())
+        .await
+        .unwrap();
+    assert!(matches!(second, Conditional::NotModified));
+}
+
+#[tokio::test]
+async fn test_update_issue_with_version_reports_conflict() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-123"))
+        .and(query_param("version", "3"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+            "errorMessages": ["Version conflict"]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-123",
+            "version": 4,
+            "summary": "Название от коллеги"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let result = client
+        .update_issue(
+            &key("TREK-123"),
+            &UpdateIssueRequest::new().set("summary", "Моё название"),
+            Some(3),
+        )
+        .await;
+
+    let Err(TrackerError::VersionConflict { current }) = result else {
+        panic!("Ожидался конфликт версий, получено {result:?}");
+    };
+    assert_eq!(current.version, Some(4));
+    assert_eq!(current.summary, "Название от коллеги");
+}
+
+#[tokio::test]
+async fn test_update_issue_without_version_keeps_api_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-123"))
+        .respond_with(ResponseTemplate::new(409))
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let result = client
+        .update_issue(
+            &key("TREK-123"),
+            &UpdateIssueRequest::new().set("summary", "x"),
+            None,
+        )
+        .await;
+
+    assert!(matches!(result, Err(TrackerError::ApiError { .. })));
+}
+
+#[tokio::test]
+async fn test_update_issue_with_field_operations() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-123"))
+        .and(query_param("version", "7"))
+        .and(body_json(serde_json::json!({
+            "summary": "Экспорт CSV",
+            "deadline": null,
+            "followers": {
+                "add": ["ann", "bob"],
+                "replace": [{"target": "carl", "replacement": "dora"}]
+            },
+            "tags": {"remove": ["triage"]},
+            "sprint": {"add": [{"id": "42"}]}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TREK-123",
+            "version": 8,
+            "summary": "Экспорт CSV"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = TrackerConfig::new("test-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    let client = TrackerClient::new(config).expect("Failed to create client");
+
+    let request = UpdateIssueRequest::new()
+        .set("summary", "Экспорт CSV")
+        .set("deadline", "2026-10-20")
+        .clear("deadline")
+        .add("followers", ["ann"])
+        .replace("followers", [("carl", "dora")])
+        .add("followers", ["bob"])
+        .remove("tags", ["triage"])
+        .add("sprint", [serde_json::json!({"id": "42"})]);
+    assert!(!request.is_empty());
+
+    let issue = client
+        .update_issue(&key("TREK-123"), &request, Some(7))
+        .await
+        .unwrap();
+    assert_eq!(issue.version, Some(8));
 }
@@ -0,0 +1,77 @@
+//! Интеграционные тесты для модуля transitions
+//!
+//! Тестируют получение и выполнение переходов по жизненному циклу задачи
+
+use tracker_lib::transitions::ExecuteTransitionRequest;
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_transitions_success() {
+    let mock_server = MockServer::start().await;
+
+    let response_json = serde_json::json!([
+        {
+            "id": "start_progress",
+            "display": "В работу",
+            "to": {"id": "3", "key": "inProgress", "display": "В работе"}
+        },
+        {
+            "id": "close",
+            "display": "Закрыть",
+            "to": {"id": "4", "key": "closed", "display": "Закрыт"}
+        }
+    ]);
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/transitions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&response_json))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let transitions = client
+        .get_transitions(&"TREK-1".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].id, "start_progress");
+    assert_eq!(transitions[1].display.as_deref(), Some("Закрыть"));
+}
+
+#[tokio::test]
+async fn test_execute_transition_with_comment() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/transitions/close/_execute"))
+        .and(body_json(serde_json::json!({"comment": "Готово"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "reopen", "display": "Переоткрыть"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = ExecuteTransitionRequest {
+        comment: Some("Готово".to_string()),
+    };
+    let transitions = client
+        .execute_transition(&"TREK-1".parse().unwrap(), "close", &request, None)
+        .await
+        .unwrap();
+
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].id, "reopen");
+}
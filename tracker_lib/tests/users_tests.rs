@@ -0,0 +1,78 @@
+//! Интеграционные тесты для модуля users
+//!
+//! Тестируют постраничное получение пользователей и поиск по префиксу
+
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+/// Две страницы пользователей организации
+async fn mount_users(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v3/users"))
+        .and(query_param("page", "1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([
+                    {"login": "petrov", "display": "Пётр Петров"},
+                    {"login": "ivanova", "display": "Анна Иванова"}
+                ])),
+        )
+        .mount(mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/users"))
+        .and(query_param("page", "2"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([
+                    {"login": "ivanov", "display": "Иван Сидоров"}
+                ])),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_users_reads_all_pages() {
+    let mock_server = MockServer::start().await;
+    mount_users(&mock_server).await;
+
+    let client = create_test_client(&mock_server).await;
+    let users = client.get_users().await.unwrap();
+
+    let logins: Vec<&str> = users.iter().filter_map(|u| u.login.as_deref()).collect();
+    assert_eq!(logins, ["petrov", "ivanova", "ivanov"]);
+}
+
+#[tokio::test]
+async fn test_search_users_matches_login_and_name_words() {
+    let mock_server = MockServer::start().await;
+    mount_users(&mock_server).await;
+
+    let client = create_test_client(&mock_server).await;
+
+    let by_login = client.search_users("IVAN").await.unwrap();
+    let logins: Vec<&str> = by_login.iter().filter_map(|u| u.login.as_deref()).collect();
+    assert_eq!(logins, ["ivanov", "ivanova"]);
+
+    let by_name = client.search_users("Анна").await.unwrap();
+    assert_eq!(by_name[0].login.as_deref(), Some("ivanova"));
+
+    let by_last_name = client.search_users("сидор").await.unwrap();
+    assert_eq!(by_last_name[0].login.as_deref(), Some("ivanov"));
+
+    assert_eq!(client.search_users("").await.unwrap().len(), 3);
+}
@@ -0,0 +1,169 @@
+//! Интеграционные тесты для модуля worklog
+//!
+//! Тестируют получение и добавление записей о затраченном времени
+
+use std::time::Duration;
+
+use tracker_lib::worklog::{AddWorklogRequest, UpdateWorklogRequest};
+use tracker_lib::{TrackerClient, TrackerConfig};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Создать тестовый клиент с mock сервером
+async fn create_test_client(mock_server: &MockServer) -> TrackerClient {
+    let config = TrackerConfig::new("test-oauth-token")
+        .with_base_url(mock_server.uri())
+        .with_api_version("v3");
+
+    TrackerClient::new(config).expect("Failed to create test client")
+}
+
+#[tokio::test]
+async fn test_get_worklogs_success() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v3/issues/TREK-1/worklog"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": 7,
+                "issue": {"key": "TREK-1", "display": "Задача"},
+                "start": "2024-01-15T10:00:00.000+0000",
+                "duration": "PT1H"
+            }
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let worklogs = client
+        .get_worklogs(&"TREK-1".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(worklogs.len(), 1);
+    assert_eq!(worklogs[0].id, Some(7));
+    assert_eq!(worklogs[0].duration.as_deref(), Some("PT1H"));
+}
+
+#[tokio::test]
+async fn test_add_worklog_sends_iso_duration() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/issues/TREK-1/worklog"))
+        .and(body_json(serde_json::json!({
+            "start": "2024-01-15T10:00:00.000+0300",
+            "duration": "PT25M",
+            "comment": "Фокус"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 8,
+            "start": "2024-01-15T10:00:00.000+0300",
+            "duration": "PT25M",
+            "comment": "Фокус"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request =
+        AddWorklogRequest::new("2024-01-15T10:00:00.000+0300", Duration::from_secs(25 * 60))
+            .with_comment("Фокус");
+    let worklog = client
+        .add_worklog(&"TREK-1".parse().unwrap(), &request)
+        .await
+        .unwrap();
+
+    assert_eq!(worklog.id, Some(8));
+}
+
+#[tokio::test]
+async fn test_search_worklogs_by_author_reads_all_pages() {
+    let mock_server = MockServer::start().await;
+    let body = serde_json::json!({
+        "createdBy": "user1",
+        "createdAt": {"from": "2024-01-15T00:00:00.000+0300"}
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v3/worklog/_search"))
+        .and(query_param("page", "1"))
+        .and(body_json(&body))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([
+                    {"id": 1, "issue": {"key": "TREK-1"}, "duration": "PT2H"}
+                ])),
+        )
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/v3/worklog/_search"))
+        .and(query_param("page", "2"))
+        .and(body_json(&body))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Total-Pages", "2")
+                .set_body_json(serde_json::json!([
+                    {"id": 2, "issue": {"key": "TREK-2"}, "duration": "PT30M"}
+                ])),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let worklogs = client
+        .search_worklogs(
+            &"user1".parse().unwrap(),
+            "2024-01-15T00:00:00.000+0300",
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(worklogs.len(), 2);
+    assert_eq!(
+        worklogs[1].issue.as_ref().and_then(|i| i.key.as_deref()),
+        Some("TREK-2")
+    );
+}
+
+#[tokio::test]
+async fn test_update_and_delete_worklog() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/v3/issues/TREK-1/worklog/7"))
+        .and(body_json(serde_json::json!({"duration": "PT1H30M"})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": 7, "duration": "PT1H30M"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/v3/issues/TREK-1/worklog/7"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = create_test_client(&mock_server).await;
+    let request = UpdateWorklogRequest::duration(Duration::from_secs(90 * 60));
+    let worklog = client
+        .update_worklog(&"TREK-1".parse().unwrap(), 7, &request)
+        .await
+        .unwrap();
+    assert_eq!(worklog.duration.as_deref(), Some("PT1H30M"));
+
+    client
+        .delete_worklog(&"TREK-1".parse().unwrap(), 7)
+        .await
+        .unwrap();
+}